@@ -0,0 +1,581 @@
+-- Baseline schema: every table and index the bot used before versioned
+-- migrations, including the columns that older databases gained through
+-- ad-hoc ALTERs. Databases created before schema_version existed are
+-- upgraded column-by-column first and then recorded at this version.
+
+CREATE TABLE IF NOT EXISTS wallets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    address TEXT NOT NULL UNIQUE,
+    encrypted_private_key BLOB,
+    salt BLOB,
+    nonce BLOB,
+    created_at TEXT NOT NULL,
+    last_active TEXT,
+    password_changed_at TEXT,
+    withdraw_daily_limit TEXT
+);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id TEXT PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    rate_limit_per_minute INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT,
+    last_used_at TEXT,
+    revoked_at TEXT,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS notification_channels (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    name TEXT NOT NULL,
+    target TEXT NOT NULL,
+    events TEXT NOT NULL,
+    template TEXT,
+    rate_limit_per_minute INTEGER NOT NULL DEFAULT 10,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    last_sent_at TEXT,
+    last_error TEXT,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    generated_at TEXT NOT NULL,
+    realized_pnl TEXT NOT NULL,
+    report_json TEXT NOT NULL,
+    UNIQUE(wallet_address, period, period_start),
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS ledger_transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    strategy TEXT,
+    reference TEXT,
+    txn_key TEXT UNIQUE,
+    memo TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ledger_postings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    txn_id INTEGER NOT NULL,
+    account TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    quantity TEXT,
+    FOREIGN KEY (txn_id) REFERENCES ledger_transactions(id)
+);
+
+CREATE TABLE IF NOT EXISTS positions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    side TEXT NOT NULL,
+    entry_price TEXT NOT NULL,
+    size TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    opened_at TEXT NOT NULL,
+    closed_at TEXT,
+    exit_price TEXT,
+    pnl TEXT,
+    status TEXT NOT NULL DEFAULT 'Open',
+    wallet_address TEXT,
+    is_paper INTEGER NOT NULL DEFAULT 1,
+    end_date TEXT,
+    token_id TEXT,
+    remaining_size TEXT,
+    realized_pnl TEXT DEFAULT '0',
+    total_sold_size TEXT DEFAULT '0',
+    avg_exit_price TEXT,
+    neg_risk INTEGER DEFAULT 0,
+    fee_paid TEXT DEFAULT '0',
+    redeemed_at TEXT,
+    redeem_tx_id TEXT,
+    redeem_proceeds TEXT,
+    order_id TEXT,
+    slug TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_positions_wallet ON positions(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_positions_wallet_status ON positions(wallet_address, status);
+
+CREATE INDEX IF NOT EXISTS idx_positions_market ON positions(market_id);
+
+CREATE TABLE IF NOT EXISTS opportunities (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    side TEXT NOT NULL,
+    entry_price TEXT NOT NULL,
+    expected_return REAL NOT NULL,
+    edge REAL NOT NULL,
+    time_to_close_hours REAL,
+    liquidity TEXT NOT NULL,
+    found_at TEXT NOT NULL,
+    acted_on INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS scan_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    scanned_at TEXT NOT NULL,
+    markets_found INTEGER NOT NULL,
+    sniper_opportunities INTEGER NOT NULL,
+    no_bias_opportunities INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_index (
+    id TEXT PRIMARY KEY,
+    condition_id TEXT NOT NULL,
+    updated_at TEXT,
+    market_json TEXT NOT NULL,
+    indexed_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_scan_costs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    mode TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    requests INTEGER NOT NULL,
+    markets_fetched INTEGER NOT NULL,
+    added INTEGER NOT NULL,
+    updated INTEGER NOT NULL,
+    removed INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_scan_costs_mode ON market_scan_costs(mode, started_at);
+
+CREATE INDEX IF NOT EXISTS idx_wallets_address ON wallets(address);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_wallet ON sessions(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_expires ON sessions(expires_at);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_wallet ON api_tokens(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_notification_channels_wallet ON notification_channels(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_reports_period ON reports(period, period_start);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_transactions_wallet ON ledger_transactions(wallet_address, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_transactions_reference ON ledger_transactions(reference);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_postings_txn ON ledger_postings(txn_id);
+
+CREATE INDEX IF NOT EXISTS idx_positions_status ON positions(status);
+
+CREATE TABLE IF NOT EXISTS api_credentials (
+    wallet_address TEXT PRIMARY KEY,
+    api_key TEXT NOT NULL,
+    api_secret TEXT NOT NULL,
+    api_passphrase TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS auto_trading_settings (
+    wallet_address TEXT PRIMARY KEY,
+    enabled INTEGER DEFAULT 0,
+    auto_buy_enabled INTEGER DEFAULT 0,
+    max_position_size TEXT DEFAULT '50',
+    max_total_exposure TEXT DEFAULT '500',
+    min_edge REAL DEFAULT 0.05,
+    strategies TEXT DEFAULT '["sniper"]',
+    take_profit_enabled INTEGER DEFAULT 1,
+    take_profit_percent REAL DEFAULT 0.20,
+    stop_loss_enabled INTEGER DEFAULT 1,
+    stop_loss_percent REAL DEFAULT 0.10,
+    trailing_stop_enabled INTEGER DEFAULT 0,
+    trailing_stop_percent REAL DEFAULT 0.10,
+    time_exit_enabled INTEGER DEFAULT 0,
+    time_exit_hours REAL DEFAULT 24.0,
+    max_positions INTEGER DEFAULT 10,
+    cooldown_minutes INTEGER DEFAULT 5,
+    max_daily_loss TEXT DEFAULT '100',
+    dispute_sniper_enabled INTEGER DEFAULT 0,
+    min_dispute_edge REAL DEFAULT 0.10,
+    max_dispute_position_size TEXT DEFAULT '25',
+    dispute_exit_on_escalation INTEGER DEFAULT 1,
+    dispute_dvm_enabled INTEGER DEFAULT 0,
+    dispute_dvm_min_vote_share REAL DEFAULT 0.65,
+    dispute_take_profit_enabled INTEGER DEFAULT 0,
+    dispute_take_profit_price REAL DEFAULT 0.97,
+    dispute_liveness_exit_enabled INTEGER DEFAULT 0,
+    dispute_liveness_exit_minutes INTEGER DEFAULT 15,
+    dispute_hold_to_settlement INTEGER DEFAULT 0,
+    market_filter TEXT DEFAULT '{}',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS auto_trade_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    position_id INTEGER,
+    action TEXT NOT NULL,
+    market_question TEXT,
+    side TEXT,
+    entry_price TEXT,
+    exit_price TEXT,
+    size TEXT,
+    pnl TEXT,
+    trigger_reason TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS position_peaks (
+    position_id INTEGER PRIMARY KEY,
+    peak_price TEXT NOT NULL,
+    peak_at TEXT NOT NULL,
+    FOREIGN KEY (position_id) REFERENCES positions(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_auto_settings_enabled ON auto_trading_settings(enabled);
+
+CREATE INDEX IF NOT EXISTS idx_auto_log_wallet ON auto_trade_log(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_auto_log_created ON auto_trade_log(created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_positions_token_id ON positions(token_id);
+
+CREATE TABLE IF NOT EXISTS orders (
+    id TEXT PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    market_id TEXT,
+    side TEXT NOT NULL,
+    order_type TEXT NOT NULL,
+    price TEXT NOT NULL,
+    original_size TEXT NOT NULL,
+    filled_size TEXT DEFAULT '0',
+    avg_fill_price TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    position_id INTEGER,
+    neg_risk INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_orders_wallet ON orders(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_orders_status ON orders(status);
+
+CREATE TABLE IF NOT EXISTS onchain_jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Queued',
+    description TEXT NOT NULL,
+    reference TEXT,
+    relay_tx_id TEXT,
+    tx_hash TEXT,
+    payload TEXT,
+    replaces_job_id INTEGER,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_onchain_jobs_wallet ON onchain_jobs(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_onchain_jobs_status ON onchain_jobs(status);
+
+CREATE TABLE IF NOT EXISTS withdrawal_allowlist (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    address TEXT NOT NULL,
+    label TEXT,
+    created_at TEXT NOT NULL,
+    active_at TEXT NOT NULL,
+    UNIQUE(wallet_address, address)
+);
+
+CREATE TABLE IF NOT EXISTS withdrawals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    transaction_id TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawals_wallet ON withdrawals(wallet_address, created_at);
+
+CREATE TABLE IF NOT EXISTS wrapped_keys (
+    wallet_address TEXT PRIMARY KEY,
+    ciphertext TEXT NOT NULL,
+    nonce TEXT NOT NULL,
+    master_key_id TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS key_audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT,
+    action TEXT NOT NULL,
+    detail TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_key_audit_wallet ON key_audit_log(wallet_address);
+
+CREATE TABLE IF NOT EXISTS description_hashes (
+    market_id TEXT PRIMARY KEY,
+    description_hash TEXT NOT NULL,
+    last_updated INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS chain_cursors (
+    name TEXT PRIMARY KEY,
+    last_block INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS uma_chain_assertions (
+    assertion_id TEXT PRIMARY KEY,
+    domain_id TEXT NOT NULL,
+    claim TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    callback_recipient TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    bond TEXT NOT NULL,
+    assertion_timestamp INTEGER NOT NULL,
+    expiration_time INTEGER NOT NULL,
+    disputer TEXT,
+    dispute_timestamp INTEGER,
+    settlement_resolution INTEGER,
+    settlement_timestamp INTEGER,
+    block_number INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_uma_chain_settled ON uma_chain_assertions(settlement_timestamp);
+
+CREATE TABLE IF NOT EXISTS mc_config (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    bankroll TEXT NOT NULL DEFAULT '40',
+    tier INTEGER NOT NULL DEFAULT 1,
+    mode TEXT NOT NULL DEFAULT 'observation',
+    peak_bankroll TEXT NOT NULL DEFAULT '40',
+    pause_state TEXT NOT NULL DEFAULT 'active',
+    pause_until TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mc_scout_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL,
+    condition_id TEXT,
+    question TEXT NOT NULL,
+    slug TEXT,
+    side TEXT NOT NULL,
+    price TEXT NOT NULL,
+    volume TEXT,
+    category TEXT,
+    end_date TEXT,
+    passed INTEGER NOT NULL DEFAULT 0,
+    certainty_score INTEGER NOT NULL DEFAULT 0,
+    reasons TEXT NOT NULL DEFAULT '[]',
+    slippage_pct REAL,
+    would_trade INTEGER NOT NULL DEFAULT 0,
+    token_id TEXT,
+    scanned_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mc_scout_scanned ON mc_scout_log(scanned_at);
+
+CREATE INDEX IF NOT EXISTS idx_mc_scout_passed ON mc_scout_log(passed);
+
+CREATE TABLE IF NOT EXISTS mc_trades (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL,
+    condition_id TEXT,
+    question TEXT NOT NULL,
+    slug TEXT,
+    side TEXT NOT NULL,
+    entry_price TEXT NOT NULL,
+    exit_price TEXT,
+    size TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    pnl TEXT,
+    certainty_score INTEGER NOT NULL DEFAULT 0,
+    category TEXT,
+    status TEXT NOT NULL DEFAULT 'open',
+    tier_at_entry INTEGER NOT NULL DEFAULT 1,
+    token_id TEXT,
+    end_date TEXT,
+    opened_at TEXT NOT NULL,
+    closed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_mc_trades_status ON mc_trades(status);
+
+CREATE INDEX IF NOT EXISTS idx_mc_trades_category ON mc_trades(category);
+
+CREATE INDEX IF NOT EXISTS idx_mc_trades_opened ON mc_trades(opened_at);
+
+CREATE TABLE IF NOT EXISTS mc_tier_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    from_tier INTEGER NOT NULL,
+    to_tier INTEGER NOT NULL,
+    bankroll TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mc_drawdown_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_type TEXT NOT NULL,
+    peak_bankroll TEXT NOT NULL,
+    current_bankroll TEXT NOT NULL,
+    drawdown_pct REAL NOT NULL,
+    action_taken TEXT NOT NULL,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mint_maker_settings (
+    wallet_address TEXT PRIMARY KEY,
+    enabled INTEGER DEFAULT 0,
+    preset TEXT DEFAULT 'balanced',
+    bid_offset_cents INTEGER DEFAULT 2,
+    max_pair_cost REAL DEFAULT 0.98,
+    min_spread_profit REAL DEFAULT 0.01,
+    max_pairs_per_market INTEGER DEFAULT 5,
+    max_total_pairs INTEGER DEFAULT 20,
+    stale_order_seconds INTEGER DEFAULT 120,
+    assets TEXT DEFAULT '["BTC","ETH","SOL"]',
+    min_minutes_to_close REAL DEFAULT 2.0,
+    max_minutes_to_close REAL DEFAULT 14.0,
+    balance_reserve REAL DEFAULT 0.0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    auto_place INTEGER DEFAULT 0,
+    auto_place_size TEXT DEFAULT '2',
+    auto_max_markets INTEGER DEFAULT 1,
+    auto_redeem INTEGER DEFAULT 0,
+    stop_loss_pct INTEGER DEFAULT 25,
+    stop_loss_delay_secs INTEGER DEFAULT 30,
+    auto_place_delay_mins INTEGER DEFAULT 0,
+    auto_size_pct INTEGER DEFAULT 0,
+    auto_max_attempts INTEGER DEFAULT 1,
+    smart_mode INTEGER DEFAULT 0,
+    pre_place INTEGER DEFAULT 0,
+    stop_after_profit INTEGER DEFAULT 0,
+    relay_backoff_until TEXT,
+    momentum_threshold REAL DEFAULT 0.10,
+    depth_check INTEGER DEFAULT 0,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE TABLE IF NOT EXISTS mint_maker_pairs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    condition_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    asset TEXT NOT NULL,
+    yes_order_id TEXT NOT NULL,
+    no_order_id TEXT NOT NULL,
+    yes_bid_price TEXT NOT NULL,
+    no_bid_price TEXT NOT NULL,
+    yes_fill_price TEXT,
+    no_fill_price TEXT,
+    pair_cost TEXT,
+    profit TEXT,
+    size TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    merge_tx_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    yes_size TEXT,
+    no_size TEXT,
+    slug TEXT,
+    yes_token_id TEXT,
+    no_token_id TEXT,
+    neg_risk INTEGER DEFAULT 0,
+    stop_loss_order_id TEXT,
+    redeem_tx_id TEXT,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mm_pairs_wallet ON mint_maker_pairs(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_mm_pairs_status ON mint_maker_pairs(status);
+
+CREATE INDEX IF NOT EXISTS idx_mm_pairs_market ON mint_maker_pairs(market_id);
+
+CREATE TABLE IF NOT EXISTS mint_maker_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    action TEXT NOT NULL,
+    market_id TEXT,
+    question TEXT,
+    asset TEXT,
+    yes_price TEXT,
+    no_price TEXT,
+    pair_cost TEXT,
+    profit TEXT,
+    size TEXT,
+    details TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mm_log_wallet ON mint_maker_log(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_mm_log_created ON mint_maker_log(created_at DESC);
+
+CREATE TABLE IF NOT EXISTS mint_maker_analytics (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    wallet_address TEXT NOT NULL,
+    pair_id INTEGER,
+    market_id TEXT NOT NULL,
+    asset TEXT,
+    slug TEXT,
+    expensive_side TEXT NOT NULL,
+    expensive_price REAL NOT NULL,
+    cheap_price REAL NOT NULL,
+    size REAL NOT NULL,
+    created_at TEXT NOT NULL,
+    expensive_filled_at TEXT,
+    cheap_placed_at TEXT,
+    cheap_filled_at TEXT,
+    outcome TEXT DEFAULT 'pending',
+    orphan_side TEXT,
+    market_winner TEXT,
+    pnl REAL,
+    resolved_at TEXT,
+    FOREIGN KEY (wallet_address) REFERENCES wallets(address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mm_analytics_wallet ON mint_maker_analytics(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_mm_analytics_outcome ON mint_maker_analytics(outcome);
+
+CREATE INDEX IF NOT EXISTS idx_mm_analytics_asset ON mint_maker_analytics(asset);
@@ -0,0 +1,9 @@
+-- Leader-election leases: one row per lease, held by the server instance that
+-- renewed it last until expires_at.
+
+CREATE TABLE IF NOT EXISTS leader_leases (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    acquired_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
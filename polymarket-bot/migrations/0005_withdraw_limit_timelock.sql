@@ -0,0 +1,5 @@
+-- Raising a wallet's daily withdrawal limit waits out the same time-lock as a
+-- new allowlist address: the requested limit is kept here until `withdraw_limit_pending_at`.
+
+ALTER TABLE wallets ADD COLUMN withdraw_limit_pending TEXT;
+ALTER TABLE wallets ADD COLUMN withdraw_limit_pending_at TEXT;
@@ -0,0 +1,341 @@
+-- Shared PostgreSQL schema: the tables behind the repository traits
+-- (wallets and sessions, positions, orders, Mint Maker, MC and the ledger).
+-- Column types follow what the Rust code binds: timestamps and decimals stay
+-- TEXT as in SQLite, flags bound as i32 stay INTEGER, is_paper is a BOOLEAN.
+
+CREATE TABLE IF NOT EXISTS wallets (
+    id BIGSERIAL PRIMARY KEY,
+    address TEXT NOT NULL UNIQUE,
+    encrypted_private_key BYTEA,
+    salt BYTEA,
+    nonce BYTEA,
+    created_at TEXT NOT NULL,
+    last_active TEXT,
+    password_changed_at TEXT,
+    withdraw_daily_limit TEXT
+);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_wallet ON sessions(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_expires ON sessions(expires_at);
+
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id TEXT PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    rate_limit_per_minute BIGINT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT,
+    last_used_at TEXT,
+    revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_wallet ON api_tokens(wallet_address);
+
+CREATE TABLE IF NOT EXISTS api_credentials (
+    wallet_address TEXT PRIMARY KEY REFERENCES wallets(address),
+    api_key TEXT NOT NULL,
+    api_secret TEXT NOT NULL,
+    api_passphrase TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ledger_transactions (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    strategy TEXT,
+    reference TEXT,
+    txn_key TEXT UNIQUE,
+    memo TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_transactions_wallet ON ledger_transactions(wallet_address, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_transactions_reference ON ledger_transactions(reference);
+
+CREATE TABLE IF NOT EXISTS ledger_postings (
+    id BIGSERIAL PRIMARY KEY,
+    txn_id BIGINT NOT NULL REFERENCES ledger_transactions(id),
+    account TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    quantity TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_postings_txn ON ledger_postings(txn_id);
+
+CREATE TABLE IF NOT EXISTS positions (
+    id BIGSERIAL PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    side TEXT NOT NULL,
+    entry_price TEXT NOT NULL,
+    size TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    opened_at TEXT NOT NULL,
+    closed_at TEXT,
+    exit_price TEXT,
+    pnl TEXT,
+    status TEXT NOT NULL DEFAULT 'Open',
+    wallet_address TEXT,
+    is_paper BOOLEAN NOT NULL DEFAULT TRUE,
+    end_date TEXT,
+    token_id TEXT,
+    remaining_size TEXT,
+    realized_pnl TEXT DEFAULT '0',
+    total_sold_size TEXT DEFAULT '0',
+    avg_exit_price TEXT,
+    neg_risk INTEGER DEFAULT 0,
+    fee_paid TEXT DEFAULT '0',
+    redeemed_at TEXT,
+    redeem_tx_id TEXT,
+    redeem_proceeds TEXT,
+    order_id TEXT,
+    slug TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_positions_wallet_status ON positions(wallet_address, status);
+
+CREATE INDEX IF NOT EXISTS idx_positions_market ON positions(market_id);
+
+CREATE INDEX IF NOT EXISTS idx_positions_status ON positions(status);
+
+CREATE INDEX IF NOT EXISTS idx_positions_token_id ON positions(token_id);
+
+CREATE TABLE IF NOT EXISTS position_peaks (
+    position_id BIGINT PRIMARY KEY REFERENCES positions(id),
+    peak_price TEXT NOT NULL,
+    peak_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS orders (
+    id TEXT PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    market_id TEXT,
+    side TEXT NOT NULL,
+    order_type TEXT NOT NULL,
+    price TEXT NOT NULL,
+    original_size TEXT NOT NULL,
+    filled_size TEXT DEFAULT '0',
+    avg_fill_price TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    position_id BIGINT,
+    neg_risk INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_orders_wallet ON orders(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_orders_status ON orders(status);
+
+CREATE TABLE IF NOT EXISTS mc_config (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    bankroll TEXT NOT NULL DEFAULT '40',
+    tier INTEGER NOT NULL DEFAULT 1,
+    mode TEXT NOT NULL DEFAULT 'observation',
+    peak_bankroll TEXT NOT NULL DEFAULT '40',
+    pause_state TEXT NOT NULL DEFAULT 'active',
+    pause_until TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mc_scout_log (
+    id BIGSERIAL PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    condition_id TEXT,
+    question TEXT NOT NULL,
+    slug TEXT,
+    side TEXT NOT NULL,
+    price TEXT NOT NULL,
+    volume TEXT,
+    category TEXT,
+    end_date TEXT,
+    passed INTEGER NOT NULL DEFAULT 0,
+    certainty_score INTEGER NOT NULL DEFAULT 0,
+    reasons TEXT NOT NULL DEFAULT '[]',
+    slippage_pct DOUBLE PRECISION,
+    would_trade INTEGER NOT NULL DEFAULT 0,
+    token_id TEXT,
+    scanned_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mc_scout_scanned ON mc_scout_log(scanned_at);
+
+CREATE INDEX IF NOT EXISTS idx_mc_scout_passed ON mc_scout_log(passed);
+
+CREATE TABLE IF NOT EXISTS mc_trades (
+    id BIGSERIAL PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    condition_id TEXT,
+    question TEXT NOT NULL,
+    slug TEXT,
+    side TEXT NOT NULL,
+    entry_price TEXT NOT NULL,
+    exit_price TEXT,
+    size TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    pnl TEXT,
+    certainty_score INTEGER NOT NULL DEFAULT 0,
+    category TEXT,
+    status TEXT NOT NULL DEFAULT 'open',
+    tier_at_entry INTEGER NOT NULL DEFAULT 1,
+    token_id TEXT,
+    end_date TEXT,
+    opened_at TEXT NOT NULL,
+    closed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_mc_trades_status ON mc_trades(status);
+
+CREATE INDEX IF NOT EXISTS idx_mc_trades_category ON mc_trades(category);
+
+CREATE INDEX IF NOT EXISTS idx_mc_trades_opened ON mc_trades(opened_at);
+
+CREATE TABLE IF NOT EXISTS mc_tier_history (
+    id BIGSERIAL PRIMARY KEY,
+    from_tier INTEGER NOT NULL,
+    to_tier INTEGER NOT NULL,
+    bankroll TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mc_drawdown_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    peak_bankroll TEXT NOT NULL,
+    current_bankroll TEXT NOT NULL,
+    drawdown_pct DOUBLE PRECISION NOT NULL,
+    action_taken TEXT NOT NULL,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mint_maker_settings (
+    wallet_address TEXT PRIMARY KEY REFERENCES wallets(address),
+    enabled INTEGER DEFAULT 0,
+    preset TEXT DEFAULT 'balanced',
+    bid_offset_cents INTEGER DEFAULT 2,
+    max_pair_cost DOUBLE PRECISION DEFAULT 0.98,
+    min_spread_profit DOUBLE PRECISION DEFAULT 0.01,
+    max_pairs_per_market INTEGER DEFAULT 5,
+    max_total_pairs INTEGER DEFAULT 20,
+    stale_order_seconds BIGINT DEFAULT 120,
+    assets TEXT DEFAULT '["BTC","ETH","SOL"]',
+    min_minutes_to_close DOUBLE PRECISION DEFAULT 2.0,
+    max_minutes_to_close DOUBLE PRECISION DEFAULT 14.0,
+    balance_reserve DOUBLE PRECISION DEFAULT 0.0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    auto_place INTEGER DEFAULT 0,
+    auto_place_size TEXT DEFAULT '2',
+    auto_max_markets INTEGER DEFAULT 1,
+    auto_redeem INTEGER DEFAULT 0,
+    stop_loss_pct INTEGER DEFAULT 25,
+    stop_loss_delay_secs INTEGER DEFAULT 30,
+    auto_place_delay_mins INTEGER DEFAULT 0,
+    auto_size_pct INTEGER DEFAULT 0,
+    auto_max_attempts INTEGER DEFAULT 1,
+    smart_mode INTEGER DEFAULT 0,
+    pre_place INTEGER DEFAULT 0,
+    stop_after_profit INTEGER DEFAULT 0,
+    relay_backoff_until TEXT,
+    momentum_threshold DOUBLE PRECISION DEFAULT 0.10,
+    depth_check INTEGER DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS mint_maker_pairs (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    market_id TEXT NOT NULL,
+    condition_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    asset TEXT NOT NULL,
+    yes_order_id TEXT NOT NULL,
+    no_order_id TEXT NOT NULL,
+    yes_bid_price TEXT NOT NULL,
+    no_bid_price TEXT NOT NULL,
+    yes_fill_price TEXT,
+    no_fill_price TEXT,
+    pair_cost TEXT,
+    profit TEXT,
+    size TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    merge_tx_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    yes_size TEXT,
+    no_size TEXT,
+    slug TEXT,
+    yes_token_id TEXT,
+    no_token_id TEXT,
+    neg_risk INTEGER DEFAULT 0,
+    stop_loss_order_id TEXT,
+    redeem_tx_id TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_mm_pairs_wallet ON mint_maker_pairs(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_mm_pairs_status ON mint_maker_pairs(status);
+
+CREATE INDEX IF NOT EXISTS idx_mm_pairs_market ON mint_maker_pairs(market_id);
+
+CREATE TABLE IF NOT EXISTS mint_maker_log (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    action TEXT NOT NULL,
+    market_id TEXT,
+    question TEXT,
+    asset TEXT,
+    yes_price TEXT,
+    no_price TEXT,
+    pair_cost TEXT,
+    profit TEXT,
+    size TEXT,
+    details TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mm_log_wallet ON mint_maker_log(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_mm_log_created ON mint_maker_log(created_at DESC);
+
+CREATE TABLE IF NOT EXISTS mint_maker_analytics (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    pair_id BIGINT,
+    market_id TEXT NOT NULL,
+    asset TEXT,
+    slug TEXT,
+    expensive_side TEXT NOT NULL,
+    expensive_price DOUBLE PRECISION NOT NULL,
+    cheap_price DOUBLE PRECISION NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    created_at TEXT NOT NULL,
+    expensive_filled_at TEXT,
+    cheap_placed_at TEXT,
+    cheap_filled_at TEXT,
+    outcome TEXT DEFAULT 'pending',
+    orphan_side TEXT,
+    market_winner TEXT,
+    pnl DOUBLE PRECISION,
+    resolved_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_mm_analytics_wallet ON mint_maker_analytics(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_mm_analytics_outcome ON mint_maker_analytics(outcome);
+
+CREATE INDEX IF NOT EXISTS idx_mm_analytics_asset ON mint_maker_analytics(asset);
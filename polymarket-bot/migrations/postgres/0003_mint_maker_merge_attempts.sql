@@ -0,0 +1,4 @@
+-- Mint Maker merge attempts per pair, saved on shutdown so the retry limit
+-- survives a restart.
+
+ALTER TABLE mint_maker_pairs ADD COLUMN IF NOT EXISTS merge_attempts BIGINT NOT NULL DEFAULT 0;
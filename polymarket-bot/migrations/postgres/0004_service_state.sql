@@ -0,0 +1,198 @@
+-- Service state that every instance must see, not just the one that wrote it:
+-- auto-trading settings and log, the on-chain job queue, withdrawals and their
+-- allowlist, wrapped keys and the key audit log, notification channels,
+-- reports, the scanner market index and the on-chain log cursors.
+
+CREATE TABLE IF NOT EXISTS auto_trading_settings (
+    wallet_address TEXT PRIMARY KEY REFERENCES wallets(address),
+    enabled INTEGER DEFAULT 0,
+    auto_buy_enabled INTEGER DEFAULT 0,
+    max_position_size TEXT DEFAULT '50',
+    max_total_exposure TEXT DEFAULT '500',
+    min_edge DOUBLE PRECISION DEFAULT 0.05,
+    strategies TEXT DEFAULT '["sniper"]',
+    take_profit_enabled INTEGER DEFAULT 1,
+    take_profit_percent DOUBLE PRECISION DEFAULT 0.20,
+    stop_loss_enabled INTEGER DEFAULT 1,
+    stop_loss_percent DOUBLE PRECISION DEFAULT 0.10,
+    trailing_stop_enabled INTEGER DEFAULT 0,
+    trailing_stop_percent DOUBLE PRECISION DEFAULT 0.10,
+    time_exit_enabled INTEGER DEFAULT 0,
+    time_exit_hours DOUBLE PRECISION DEFAULT 24.0,
+    max_positions INTEGER DEFAULT 10,
+    cooldown_minutes INTEGER DEFAULT 5,
+    max_daily_loss TEXT DEFAULT '100',
+    dispute_sniper_enabled INTEGER DEFAULT 0,
+    min_dispute_edge DOUBLE PRECISION DEFAULT 0.10,
+    max_dispute_position_size TEXT DEFAULT '25',
+    dispute_exit_on_escalation INTEGER DEFAULT 1,
+    dispute_dvm_enabled INTEGER DEFAULT 0,
+    dispute_dvm_min_vote_share DOUBLE PRECISION DEFAULT 0.65,
+    dispute_take_profit_enabled INTEGER DEFAULT 0,
+    dispute_take_profit_price DOUBLE PRECISION DEFAULT 0.97,
+    dispute_liveness_exit_enabled INTEGER DEFAULT 0,
+    dispute_liveness_exit_minutes INTEGER DEFAULT 15,
+    dispute_hold_to_settlement INTEGER DEFAULT 0,
+    market_filter TEXT DEFAULT '{}',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_auto_settings_enabled ON auto_trading_settings(enabled);
+
+CREATE TABLE IF NOT EXISTS auto_trade_log (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    position_id BIGINT,
+    action TEXT NOT NULL,
+    market_question TEXT,
+    side TEXT,
+    entry_price TEXT,
+    exit_price TEXT,
+    size TEXT,
+    pnl TEXT,
+    trigger_reason TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_auto_log_wallet ON auto_trade_log(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_auto_log_created ON auto_trade_log(created_at DESC);
+
+CREATE TABLE IF NOT EXISTS onchain_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Queued',
+    description TEXT NOT NULL,
+    reference TEXT,
+    relay_tx_id TEXT,
+    tx_hash TEXT,
+    payload TEXT,
+    replaces_job_id BIGINT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_onchain_jobs_wallet ON onchain_jobs(wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_onchain_jobs_status ON onchain_jobs(status);
+
+CREATE TABLE IF NOT EXISTS withdrawal_allowlist (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    address TEXT NOT NULL,
+    label TEXT,
+    created_at TEXT NOT NULL,
+    active_at TEXT NOT NULL,
+    UNIQUE (wallet_address, address)
+);
+
+CREATE TABLE IF NOT EXISTS withdrawals (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    transaction_id TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawals_wallet ON withdrawals(wallet_address, created_at);
+
+CREATE TABLE IF NOT EXISTS wrapped_keys (
+    wallet_address TEXT PRIMARY KEY,
+    ciphertext TEXT NOT NULL,
+    nonce TEXT NOT NULL,
+    master_key_id TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS key_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT,
+    action TEXT NOT NULL,
+    detail TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_key_audit_wallet ON key_audit_log(wallet_address);
+
+CREATE TABLE IF NOT EXISTS notification_channels (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    name TEXT NOT NULL,
+    target TEXT NOT NULL,
+    events TEXT NOT NULL,
+    template TEXT,
+    rate_limit_per_minute BIGINT NOT NULL DEFAULT 10,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    last_sent_at TEXT,
+    last_error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_channels_wallet ON notification_channels(wallet_address);
+
+CREATE TABLE IF NOT EXISTS reports (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address TEXT NOT NULL REFERENCES wallets(address),
+    period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    generated_at TEXT NOT NULL,
+    realized_pnl TEXT NOT NULL,
+    report_json TEXT NOT NULL,
+    UNIQUE (wallet_address, period, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_reports_period ON reports(period, period_start);
+
+CREATE TABLE IF NOT EXISTS market_index (
+    id TEXT PRIMARY KEY,
+    condition_id TEXT NOT NULL,
+    updated_at TEXT,
+    market_json TEXT NOT NULL,
+    indexed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_index_condition ON market_index(condition_id);
+
+CREATE TABLE IF NOT EXISTS market_scan_costs (
+    id BIGSERIAL PRIMARY KEY,
+    mode TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    requests BIGINT NOT NULL,
+    markets_fetched BIGINT NOT NULL,
+    added BIGINT NOT NULL,
+    updated BIGINT NOT NULL,
+    removed BIGINT NOT NULL,
+    duration_ms BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_scan_costs_mode ON market_scan_costs(mode, started_at);
+
+CREATE TABLE IF NOT EXISTS chain_cursors (
+    name TEXT PRIMARY KEY,
+    last_block BIGINT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS uma_chain_assertions (
+    assertion_id TEXT PRIMARY KEY,
+    domain_id TEXT NOT NULL,
+    claim TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    callback_recipient TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    bond TEXT NOT NULL,
+    assertion_timestamp BIGINT NOT NULL,
+    expiration_time BIGINT NOT NULL,
+    disputer TEXT,
+    dispute_timestamp BIGINT,
+    settlement_resolution INTEGER,
+    settlement_timestamp BIGINT,
+    block_number BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_uma_chain_settled ON uma_chain_assertions(settlement_timestamp);
@@ -0,0 +1,5 @@
+-- Withdrawals are reserved as `pending` before the relay submission, so the
+-- daily limit check and the reservation happen in one transaction, and are
+-- marked `submitted` or `failed` once the relay answers.
+
+ALTER TABLE withdrawals ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'submitted';
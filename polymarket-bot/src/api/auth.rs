@@ -0,0 +1,348 @@
+//! Request authentication
+//!
+//! Every authenticated route takes an `Auth<S>` extractor instead of validating
+//! the bearer token itself. The bearer is either:
+//! - a wallet session ID (from unlock/connect), which carries every scope, or
+//! - a long-lived API token (`pmb_...`) limited to its scopes and a per-token
+//!   request rate, for automation that shouldn't hold a full wallet session
+//!
+//! The type parameter names the scope a route needs: `Auth` (read),
+//! `Auth<scope::Trade>`, `Auth<scope::Withdraw>`, or `Auth<scope::Session>` for
+//! routes that only a wallet session may call (key export, token management).
+//! Trade and withdraw routes answer 503 on instances that aren't the leader.
+
+use crate::api::server::AppState;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization},
+    TypedHeader,
+};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Prefix that distinguishes API tokens from session IDs
+pub const API_TOKEN_PREFIX: &str = "pmb_";
+
+/// Rate limit window for API tokens
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Permission granted to an API token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// View positions, orders, settings and history
+    Read,
+    /// Place/cancel orders, close positions, change auto-trading settings
+    Trade,
+    /// Move funds out of the wallet
+    Withdraw,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Trade => "trade",
+            Scope::Withdraw => "withdraw",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "read" => Some(Scope::Read),
+            "trade" => Some(Scope::Trade),
+            "withdraw" => Some(Scope::Withdraw),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a stored comma-separated scope list, ignoring unknown entries
+pub fn parse_scopes(s: &str) -> Vec<Scope> {
+    s.split(',').filter_map(Scope::parse).collect()
+}
+
+/// Serialize scopes for storage (deduplicated, read always included)
+pub fn format_scopes(scopes: &[Scope]) -> String {
+    let mut out = vec![Scope::Read];
+    for scope in scopes {
+        if !out.contains(scope) {
+            out.push(*scope);
+        }
+    }
+    out.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(",")
+}
+
+/// What the bearer token turned out to be
+#[derive(Debug, Clone)]
+pub enum Credential {
+    Session { id: String },
+    ApiToken { id: String, scopes: Vec<Scope> },
+}
+
+impl Credential {
+    /// Whether this credential grants a scope (sessions grant all of them)
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        match self {
+            Credential::Session { .. } => true,
+            Credential::ApiToken { scopes, .. } => scope == Scope::Read || scopes.contains(&scope),
+        }
+    }
+}
+
+/// Scope markers for `Auth<S>`
+pub mod scope {
+    /// Any valid credential (every token can read its own wallet)
+    #[derive(Debug, Clone, Copy)]
+    pub struct Read;
+    /// Credential with the `trade` scope
+    #[derive(Debug, Clone, Copy)]
+    pub struct Trade;
+    /// Credential with the `withdraw` scope
+    #[derive(Debug, Clone, Copy)]
+    pub struct Withdraw;
+    /// A wallet session; API tokens are rejected
+    #[derive(Debug, Clone, Copy)]
+    pub struct Session;
+}
+
+/// Requirement a credential must meet for a route
+pub trait ScopeRequirement {
+    /// Scope required from API tokens, `None` when only sessions are accepted
+    const SCOPE: Option<Scope>;
+}
+
+impl ScopeRequirement for scope::Read {
+    const SCOPE: Option<Scope> = Some(Scope::Read);
+}
+
+impl ScopeRequirement for scope::Trade {
+    const SCOPE: Option<Scope> = Some(Scope::Trade);
+}
+
+impl ScopeRequirement for scope::Withdraw {
+    const SCOPE: Option<Scope> = Some(Scope::Withdraw);
+}
+
+impl ScopeRequirement for scope::Session {
+    const SCOPE: Option<Scope> = None;
+}
+
+/// Authenticated caller of a route requiring scope `S`
+#[derive(Debug, Clone)]
+pub struct Auth<S = scope::Read> {
+    /// Wallet the credential belongs to (lowercase)
+    pub wallet_address: String,
+    pub credential: Credential,
+    _scope: PhantomData<fn() -> S>,
+}
+
+/// Authentication failure, rendered as `{"error": ...}` like route errors
+#[derive(Debug)]
+pub struct AuthRejection {
+    status: StatusCode,
+    message: String,
+    retry_after: Option<Duration>,
+}
+
+impl AuthRejection {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            retry_after: None,
+        }
+    }
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        let mut response = (
+            self.status,
+            Json(serde_json::json!({ "error": self.message })),
+        )
+            .into_response();
+        if let Some(retry_after) = self.retry_after {
+            if let Ok(value) = HeaderValue::from_str(&retry_after.as_secs().max(1).to_string()) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl<S: ScopeRequirement> FromRequestParts<AppState> for Auth<S> {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let TypedHeader(Authorization(bearer)) =
+            TypedHeader::<Authorization<Bearer>>::from_request_parts(parts, state)
+                .await
+                .map_err(|_| AuthRejection::new(StatusCode::UNAUTHORIZED, "Missing bearer token"))?;
+        let token = bearer.token();
+
+        let (wallet_address, credential) = if token.starts_with(API_TOKEN_PREFIX) {
+            let api_token = state
+                .db
+                .auth().get_active_api_token(&hash_token(token))
+                .await
+                .map_err(|e| AuthRejection::new(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?
+                .ok_or_else(|| AuthRejection::new(StatusCode::UNAUTHORIZED, "Invalid, expired or revoked API token"))?;
+
+            let limit = u32::try_from(api_token.rate_limit_per_minute).unwrap_or(u32::MAX);
+            if let Err(retry_after) = state.token_limiter.check(&api_token.id, limit) {
+                return Err(AuthRejection {
+                    retry_after: Some(retry_after),
+                    ..AuthRejection::new(StatusCode::TOO_MANY_REQUESTS, "API token rate limit exceeded")
+                });
+            }
+
+            if let Err(e) = state.db.auth().touch_api_token(&api_token.id).await {
+                warn!("Failed to record API token use for {}: {}", api_token.id, e);
+            }
+
+            let scopes = parse_scopes(&api_token.scopes);
+            (api_token.wallet_address, Credential::ApiToken { id: api_token.id, scopes })
+        } else {
+            let session = state
+                .db
+                .auth().get_session(token)
+                .await
+                .map_err(|e| AuthRejection::new(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?
+                .ok_or_else(|| AuthRejection::new(StatusCode::UNAUTHORIZED, "Invalid or expired session"))?;
+            (session.wallet_address, Credential::Session { id: session.id })
+        };
+
+        match S::SCOPE {
+            None if !matches!(credential, Credential::Session { .. }) => {
+                return Err(AuthRejection::new(
+                    StatusCode::FORBIDDEN,
+                    "This endpoint requires a wallet session, not an API token",
+                ));
+            }
+            Some(scope) if !credential.has_scope(scope) => {
+                return Err(AuthRejection::new(
+                    StatusCode::FORBIDDEN,
+                    format!("API token lacks the '{}' scope", scope.as_str()),
+                ));
+            }
+            _ => {}
+        }
+
+        // Only the leader trades, so two instances never act on the same wallet
+        if matches!(S::SCOPE, Some(Scope::Trade | Scope::Withdraw)) && !state.leader.is_leader() {
+            return Err(AuthRejection {
+                retry_after: Some(Duration::from_secs(state.config.leader_lease_seconds)),
+                ..AuthRejection::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "This instance is a follower; send trading requests to the leader",
+                )
+            });
+        }
+
+        Ok(Self {
+            wallet_address: wallet_address.to_lowercase(),
+            credential,
+            _scope: PhantomData,
+        })
+    }
+}
+
+/// Generate a new API token secret
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{}{}", API_TOKEN_PREFIX, hex::encode(bytes))
+}
+
+/// SHA-256 hex of a token secret (what gets stored)
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Fixed-window request counter per API token
+#[derive(Default)]
+pub struct ApiTokenLimiter {
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl ApiTokenLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request for a token. Returns the time until the window resets
+    /// when the token is over its per-minute limit.
+    pub fn check(&self, token_id: &str, limit_per_minute: u32) -> Result<(), Duration> {
+        self.check_at(token_id, limit_per_minute, Instant::now())
+    }
+
+    fn check_at(&self, token_id: &str, limit_per_minute: u32, now: Instant) -> Result<(), Duration> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let (started, count) = windows.entry(token_id.to_string()).or_insert((now, 0));
+
+        if now.duration_since(*started) >= RATE_WINDOW {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= limit_per_minute {
+            return Err(RATE_WINDOW.saturating_sub(now.duration_since(*started)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_round_trip() {
+        let stored = format_scopes(&[Scope::Trade, Scope::Trade, Scope::Withdraw]);
+        assert_eq!(stored, "read,trade,withdraw");
+        assert_eq!(parse_scopes(&stored), vec![Scope::Read, Scope::Trade, Scope::Withdraw]);
+        assert_eq!(parse_scopes("read, bogus"), vec![Scope::Read]);
+    }
+
+    #[test]
+    fn test_credential_scopes() {
+        let session = Credential::Session { id: "s".to_string() };
+        assert!(session.has_scope(Scope::Withdraw));
+
+        let token = Credential::ApiToken { id: "t".to_string(), scopes: vec![Scope::Trade] };
+        assert!(token.has_scope(Scope::Read));
+        assert!(token.has_scope(Scope::Trade));
+        assert!(!token.has_scope(Scope::Withdraw));
+    }
+
+    #[test]
+    fn test_limiter_window() {
+        let limiter = ApiTokenLimiter::new();
+        let start = Instant::now();
+        assert!(limiter.check_at("t", 2, start).is_ok());
+        assert!(limiter.check_at("t", 2, start).is_ok());
+        assert!(limiter.check_at("t", 2, start + Duration::from_secs(10)).is_err());
+        assert!(limiter.check_at("other", 2, start).is_ok());
+        assert!(limiter.check_at("t", 2, start + RATE_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_token_format() {
+        let token = generate_token();
+        assert!(token.starts_with(API_TOKEN_PREFIX));
+        assert_eq!(token.len(), API_TOKEN_PREFIX.len() + 64);
+        assert_ne!(hash_token(&token), token);
+    }
+}
@@ -2,9 +2,12 @@
 //!
 //! Provides REST endpoints and WebSocket support for multi-user trading.
 
+pub mod auth;
 pub mod routes;
 pub mod server;
+pub mod shutdown;
 pub mod ws;
 
+pub use auth::{scope, Auth};
 pub use server::{create_app, AppState, ScanStatus};
 pub use ws::WalletBalanceUpdate;
@@ -1,16 +1,15 @@
 //! Auto-trading API endpoints
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
+use crate::db::KeyAuditEntry;
 use crate::services::auto_trader::{AutoTradeLog, AutoTradingSettings, AutoTradingStats, UpdateSettingsRequest};
+use crate::taxonomy::TaxonomyFilter;
 use axum::{
     extract::{Query, State},
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -54,10 +53,18 @@ pub struct AutoTradingSettingsDto {
     pub max_positions: i32,
     pub cooldown_minutes: i32,
     pub max_daily_loss: String,
+    pub market_filter: TaxonomyFilter,
     pub dispute_sniper_enabled: bool,
     pub min_dispute_edge: f64,
     pub dispute_position_size: String,
     pub dispute_exit_on_escalation: bool,
+    pub dispute_dvm_enabled: bool,
+    pub dispute_dvm_min_vote_share: f64,
+    pub dispute_take_profit_enabled: bool,
+    pub dispute_take_profit_price: f64,
+    pub dispute_liveness_exit_enabled: bool,
+    pub dispute_liveness_exit_minutes: i32,
+    pub dispute_hold_to_settlement: bool,
 }
 
 impl From<AutoTradingSettings> for AutoTradingSettingsDto {
@@ -80,10 +87,18 @@ impl From<AutoTradingSettings> for AutoTradingSettingsDto {
             max_positions: s.max_positions,
             cooldown_minutes: s.cooldown_minutes,
             max_daily_loss: s.max_daily_loss.to_string(),
+            market_filter: s.market_filter,
             dispute_sniper_enabled: s.dispute_sniper_enabled,
             min_dispute_edge: s.min_dispute_edge,
             dispute_position_size: s.dispute_position_size.to_string(),
             dispute_exit_on_escalation: s.dispute_exit_on_escalation,
+            dispute_dvm_enabled: s.dispute_dvm_enabled,
+            dispute_dvm_min_vote_share: s.dispute_dvm_min_vote_share,
+            dispute_take_profit_enabled: s.dispute_take_profit_enabled,
+            dispute_take_profit_price: s.dispute_take_profit_price,
+            dispute_liveness_exit_enabled: s.dispute_liveness_exit_enabled,
+            dispute_liveness_exit_minutes: s.dispute_liveness_exit_minutes,
+            dispute_hold_to_settlement: s.dispute_hold_to_settlement,
         }
     }
 }
@@ -191,32 +206,11 @@ pub struct HistoryQuery {
 /// Get auto-trading settings for authenticated wallet
 pub async fn get_settings(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<SettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let settings = state
-        .db
-        .get_auto_trading_settings(&session.wallet_address)
+        .db.auto_trading()
+        .get_auto_trading_settings(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -235,34 +229,13 @@ pub async fn get_settings(
 /// Update auto-trading settings
 pub async fn update_settings(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<UpdateSettingsRequest>,
 ) -> Result<Json<SettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get current settings
     let mut settings = state
-        .db
-        .get_auto_trading_settings(&session.wallet_address)
+        .db.auto_trading()
+        .get_auto_trading_settings(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -325,6 +298,9 @@ pub async fn update_settings(
     if let Some(max_daily_loss) = req.max_daily_loss {
         settings.max_daily_loss = Decimal::from_str(&max_daily_loss).unwrap_or(settings.max_daily_loss);
     }
+    if let Some(market_filter) = req.market_filter {
+        settings.market_filter = market_filter;
+    }
     if let Some(dispute_sniper_enabled) = req.dispute_sniper_enabled {
         settings.dispute_sniper_enabled = dispute_sniper_enabled;
     }
@@ -337,10 +313,31 @@ pub async fn update_settings(
     if let Some(dispute_exit_on_escalation) = req.dispute_exit_on_escalation {
         settings.dispute_exit_on_escalation = dispute_exit_on_escalation;
     }
+    if let Some(dispute_dvm_enabled) = req.dispute_dvm_enabled {
+        settings.dispute_dvm_enabled = dispute_dvm_enabled;
+    }
+    if let Some(dispute_dvm_min_vote_share) = req.dispute_dvm_min_vote_share {
+        settings.dispute_dvm_min_vote_share = dispute_dvm_min_vote_share;
+    }
+    if let Some(dispute_take_profit_enabled) = req.dispute_take_profit_enabled {
+        settings.dispute_take_profit_enabled = dispute_take_profit_enabled;
+    }
+    if let Some(dispute_take_profit_price) = req.dispute_take_profit_price {
+        settings.dispute_take_profit_price = dispute_take_profit_price;
+    }
+    if let Some(dispute_liveness_exit_enabled) = req.dispute_liveness_exit_enabled {
+        settings.dispute_liveness_exit_enabled = dispute_liveness_exit_enabled;
+    }
+    if let Some(dispute_liveness_exit_minutes) = req.dispute_liveness_exit_minutes {
+        settings.dispute_liveness_exit_minutes = dispute_liveness_exit_minutes;
+    }
+    if let Some(dispute_hold_to_settlement) = req.dispute_hold_to_settlement {
+        settings.dispute_hold_to_settlement = dispute_hold_to_settlement;
+    }
 
     // Save updated settings
     state
-        .db
+        .db.auto_trading()
         .update_auto_trading_settings(&settings)
         .await
         .map_err(|e| {
@@ -366,34 +363,13 @@ pub struct EnableAutoTradingRequest {
 /// Enable auto-trading with password (stores decrypted key in memory for auto-signing)
 pub async fn enable(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<EnableAutoTradingRequest>,
 ) -> Result<Json<SuccessResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get encrypted key for this wallet
     let encrypted_key = state
         .db
-        .get_encrypted_key(&session.wallet_address)
+        .auth().get_encrypted_key(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -423,13 +399,24 @@ pub async fn enable(
             )
         })?;
 
-    // Store the decrypted key in memory for auto-trading
-    state.key_store.store_key(&session.wallet_address, private_key).await;
+    // Unlock the decrypted key in the signer for auto-trading
+    state
+        .key_store
+        .store_key(&auth.wallet_address, private_key)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to unlock key: {}", e),
+                }),
+            )
+        })?;
 
     // Enable auto-trading in database
     state
-        .db
-        .enable_auto_trading(&session.wallet_address)
+        .db.auto_trading()
+        .enable_auto_trading(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -449,36 +436,12 @@ pub async fn enable(
 /// Disable auto-trading
 pub async fn disable(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
 ) -> Result<Json<SuccessResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
-    // Remove the decrypted key from memory
-    state.key_store.remove_key(&session.wallet_address).await;
-
     // Disable auto-trading in database
     state
-        .db
-        .disable_auto_trading(&session.wallet_address)
+        .db.auto_trading()
+        .disable_auto_trading(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -489,45 +452,39 @@ pub async fn disable(
             )
         })?;
 
+    // Remove the decrypted key from memory unless Mint Maker still signs with it
+    let released = state.release_key(&auth.wallet_address).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Database error: {}", e),
+            }),
+        )
+    })?;
+
+    let message = if released {
+        "Auto-trading disabled. Your wallet key has been removed from memory."
+    } else {
+        "Auto-trading disabled. Your wallet key stays in memory while Mint Maker is enabled."
+    };
     Ok(Json(SuccessResponse {
         success: true,
-        message: Some("Auto-trading disabled. Your wallet key has been removed from memory.".to_string()),
+        message: Some(message.to_string()),
     }))
 }
 
 /// Get auto-trading history
 pub async fn get_history(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
     Query(query): Query<HistoryQuery>,
 ) -> Result<Json<HistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let limit = query.limit.unwrap_or(50);
     let offset = query.offset.unwrap_or(0);
 
     let history = state
-        .db
-        .get_auto_trade_history(&session.wallet_address, limit, offset)
+        .db.auto_trading()
+        .get_auto_trade_history(&auth.wallet_address, limit, offset)
         .await
         .map_err(|e| {
             (
@@ -550,11 +507,11 @@ pub async fn get_history(
 /// Get auto-trading stats
 pub async fn get_stats(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<StatsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
+    let stats = state
+        .db.auto_trading()
+        .get_auto_trading_stats(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -563,19 +520,30 @@ pub async fn get_stats(
                     error: format!("Database error: {}", e),
                 }),
             )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
         })?;
 
-    let stats = state
-        .db
-        .get_auto_trading_stats(&session.wallet_address)
+    Ok(Json(StatsResponse {
+        stats: stats.into(),
+    }))
+}
+
+/// Key vault audit response
+#[derive(Debug, Serialize)]
+pub struct KeyAuditResponse {
+    pub entries: Vec<KeyAuditEntry>,
+}
+
+/// Get the key vault audit log (wraps, unwraps, purges) for the authenticated wallet
+pub async fn get_key_audit(
+    State(state): State<AppState>,
+    auth: Auth,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<KeyAuditResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = query.limit.unwrap_or(50);
+
+    let entries = state
+        .db.key_vault()
+        .get_key_audit_log(&auth.wallet_address, limit)
         .await
         .map_err(|e| {
             (
@@ -586,9 +554,7 @@ pub async fn get_stats(
             )
         })?;
 
-    Ok(Json(StatsResponse {
-        stats: stats.into(),
-    }))
+    Ok(Json(KeyAuditResponse { entries }))
 }
 
 /// Status response with current state
@@ -605,32 +571,11 @@ pub struct StatusResponse {
 /// Get current auto-trading status
 pub async fn get_status(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<StatusResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let settings = state
-        .db
-        .get_auto_trading_settings(&session.wallet_address)
+        .db.auto_trading()
+        .get_auto_trading_settings(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -643,26 +588,26 @@ pub async fn get_status(
 
     let open_positions = state
         .db
-        .count_open_positions(&session.wallet_address)
+        .positions().count_open_positions(&auth.wallet_address)
         .await
         .unwrap_or(0);
 
     let total_exposure = state
         .db
-        .get_total_exposure(&session.wallet_address)
+        .positions().get_total_exposure(&auth.wallet_address)
         .await
         .unwrap_or_default();
 
     let daily_pnl = state
-        .db
-        .get_daily_auto_pnl(&session.wallet_address)
+        .db.auto_trading()
+        .get_daily_auto_pnl(&auth.wallet_address)
         .await
         .unwrap_or_default();
 
     // Fetch on-chain USDC balance
     let wallet_balance = fetch_usdc_balance_rpc(
         &state.config.polygon_rpc_url,
-        &session.wallet_address,
+        &auth.wallet_address,
     )
     .await
     .unwrap_or_else(|_| "0.00".to_string());
@@ -5,13 +5,10 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
 
 /// Polymarket CLOB API endpoint
@@ -103,33 +100,11 @@ pub async fn get_server_time() -> Result<Json<serde_json::Value>, (StatusCode, J
 /// The frontend signs an EIP-712 message, we forward it to Polymarket
 pub async fn derive_api_key(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<DeriveApiKeyRequest>,
 ) -> Result<Json<ApiCredentialsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Verify the address matches the session wallet
-    if req.address.to_lowercase() != session.wallet_address.to_lowercase() {
+    if req.address.to_lowercase() != auth.wallet_address.to_lowercase() {
         return Err((
             StatusCode::FORBIDDEN,
             Json(ErrorResponse {
@@ -220,8 +195,8 @@ pub async fn derive_api_key(
     // Store credentials in database for this wallet
     state
         .db
-        .store_api_credentials(
-            &session.wallet_address,
+        .auth().store_api_credentials(
+            &auth.wallet_address,
             &credentials.api_key,
             &credentials.secret,
             &credentials.passphrase,
@@ -236,11 +211,11 @@ pub async fn derive_api_key(
             )
         })?;
 
-    info!("API credentials stored for wallet {}", session.wallet_address);
+    info!("API credentials stored for wallet {}", auth.wallet_address);
 
     // Spawn a User WebSocket for this wallet so they get real-time order events
     state.spawn_user_ws(
-        session.wallet_address.clone(),
+        auth.wallet_address.clone(),
         credentials.api_key.clone(),
         credentials.secret.clone(),
         credentials.passphrase.clone(),
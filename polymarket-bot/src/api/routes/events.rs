@@ -0,0 +1,51 @@
+//! Multi-outcome (neg-risk) event API endpoints
+
+use crate::api::server::AppState;
+use crate::types::NegRiskEvent;
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Query parameters for listing neg-risk events
+#[derive(Debug, Deserialize)]
+pub struct ListEventsQuery {
+    /// Only return events whose YES price sum is away from 1.00
+    pub mispriced: Option<bool>,
+    /// Override the configured mispricing tolerance (e.g. 0.03)
+    pub tolerance: Option<Decimal>,
+    /// Maximum number to return
+    pub limit: Option<usize>,
+}
+
+/// Neg-risk events response
+#[derive(Debug, Serialize)]
+pub struct EventsResponse {
+    pub events: Vec<NegRiskEvent>,
+    pub total: usize,
+    pub tolerance: Decimal,
+}
+
+/// List neg-risk events from the last scan, most mispriced first
+pub async fn list_neg_risk_events(
+    State(state): State<AppState>,
+    Query(query): Query<ListEventsQuery>,
+) -> Json<EventsResponse> {
+    let tolerance = query.tolerance.unwrap_or(state.config.neg_risk_sum_tolerance);
+    let events = state.neg_risk_events.read().await;
+
+    let filtered: Vec<NegRiskEvent> = events
+        .iter()
+        .filter(|e| !query.mispriced.unwrap_or(false) || e.is_mispriced(tolerance))
+        .take(query.limit.unwrap_or(50))
+        .cloned()
+        .collect();
+
+    Json(EventsResponse {
+        total: filtered.len(),
+        events: filtered,
+        tolerance,
+    })
+}
@@ -0,0 +1,100 @@
+//! Trade export endpoints
+//!
+//! Fills, tax lots and realized gains for bookkeeping, as JSON (everything) or
+//! CSV (one section per download).
+
+use crate::api::auth::Auth;
+use crate::api::server::AppState;
+use crate::export::{ExportSection, LotMethod, TradeExport};
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest range one export may cover
+const MAX_RANGE_DAYS: i64 = 3 * 366;
+
+/// Error response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Export query params
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// First day (defaults to January 1 of `to`'s year)
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive (defaults to today)
+    pub to: Option<NaiveDate>,
+    /// "json" (default) or "csv"
+    pub format: Option<String>,
+    /// "fifo" (default) or "specific"
+    pub method: Option<String>,
+    /// CSV table: "fills" (default), "lots", "gains" or "daily"
+    pub section: Option<String>,
+}
+
+fn db_error(e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+        }),
+    )
+}
+
+fn bad_request(error: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: error.into() }))
+}
+
+/// GET /api/export/trades?from=2026-01-01&to=2026-12-31&format=csv&method=fifo&section=gains
+pub async fn export_trades(
+    State(state): State<AppState>,
+    auth: Auth,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(to.year(), 1, 1).unwrap_or(to));
+    if from > to {
+        return Err(bad_request("from must not be after to"));
+    }
+    if (to - from).num_days() > MAX_RANGE_DAYS {
+        return Err(bad_request(format!("Export at most {} days at a time", MAX_RANGE_DAYS)));
+    }
+    let method = match query.method.as_deref() {
+        Some(m) => LotMethod::parse(m).ok_or_else(|| bad_request("method must be 'fifo' or 'specific'"))?,
+        None => LotMethod::default(),
+    };
+    let section = match query.section.as_deref() {
+        Some(s) => ExportSection::parse(s)
+            .ok_or_else(|| bad_request("section must be 'fills', 'lots', 'gains' or 'daily'"))?,
+        None => ExportSection::default(),
+    };
+
+    let export = TradeExport::generate(&state.db, &auth.wallet_address, from, to, method)
+        .await
+        .map_err(db_error)?;
+
+    match query.format.as_deref().unwrap_or("json") {
+        "json" => Ok(Json(export).into_response()),
+        "csv" => {
+            let filename = format!("trades-{:?}-{}-{}.csv", section, from, to).to_lowercase();
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+                ],
+                export.to_csv(section),
+            )
+                .into_response())
+        }
+        _ => Err(bad_request("format must be 'json' or 'csv'")),
+    }
+}
@@ -0,0 +1,80 @@
+//! Ledger endpoints
+//!
+//! Balances, realized results and open holdings marked to the latest price,
+//! plus the raw transaction journal they are derived from.
+
+use crate::api::auth::Auth;
+use crate::api::server::AppState;
+use crate::ledger::{LedgerSummary, LedgerTxn};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Default number of transactions listed
+const DEFAULT_LIMIT: i64 = 50;
+/// Max number of transactions listed
+const MAX_LIMIT: i64 = 500;
+
+/// Error response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Transaction list query params
+#[derive(Debug, Deserialize)]
+pub struct TransactionsQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Transaction list response
+#[derive(Debug, Serialize)]
+pub struct TransactionsResponse {
+    pub transactions: Vec<LedgerTxn>,
+}
+
+fn db_error(e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+        }),
+    )
+}
+
+/// GET /api/ledger
+pub async fn get_ledger(
+    State(state): State<AppState>,
+    auth: Auth,
+) -> Result<Json<LedgerSummary>, (StatusCode, Json<ErrorResponse>)> {
+    let entries = state
+        .db
+        .ledger().get_ledger_entries(&auth.wallet_address, None)
+        .await
+        .map_err(db_error)?;
+    let marks = state.price_cache.snapshot().await;
+
+    Ok(Json(LedgerSummary::from_entries(&entries, &marks)))
+}
+
+/// GET /api/ledger/transactions?limit=50&offset=0
+pub async fn list_transactions(
+    State(state): State<AppState>,
+    auth: Auth,
+    Query(query): Query<TransactionsQuery>,
+) -> Result<Json<TransactionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let transactions = state
+        .db
+        .ledger().get_ledger_transactions(&auth.wallet_address, limit, offset)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(TransactionsResponse { transactions }))
+}
@@ -142,3 +142,22 @@ pub async fn get_metrics(
     state.metrics.set_rate_limiter_util(general, post, delete).await;
     Json(state.metrics.snapshot().await)
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ScanCostsQuery {
+    pub limit: Option<i64>,
+}
+
+/// Get the cost of recent market scan cycles, newest first
+pub async fn get_scan_costs(
+    State(state): State<AppState>,
+    Query(params): Query<ScanCostsQuery>,
+) -> Result<Json<Vec<crate::db::ScanCostRow>>, (StatusCode, Json<serde_json::Value>)> {
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+    state.db.market_index().get_scan_costs(limit).await.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
+        )
+    })
+}
@@ -40,7 +40,7 @@ pub async fn get_scout_log(
     let limit = params.limit.unwrap_or(50);
     let offset = params.offset.unwrap_or(0);
 
-    match state.db.mc_get_scout_log(limit, offset).await {
+    match state.db.mc().mc_get_scout_log(limit, offset).await {
         Ok((logs, total)) => Json(McScoutLogResponse { logs, total }),
         Err(e) => {
             tracing::warn!("Failed to get MC scout log: {}", e);
@@ -86,7 +86,7 @@ pub async fn get_trades(
     let limit = params.limit.unwrap_or(50);
     let offset = params.offset.unwrap_or(0);
 
-    match state.db.mc_get_trades(limit, offset).await {
+    match state.db.mc().mc_get_trades(limit, offset).await {
         Ok((trades, total)) => {
             let rows: Vec<McTradeRow> = trades.into_iter().map(|t| McTradeRow {
                 id: t.id,
@@ -137,7 +137,7 @@ pub struct McTierHistoryResponse {
 pub async fn get_tier_history(
     State(state): State<AppState>,
 ) -> Json<McTierHistoryResponse> {
-    match state.db.mc_get_tier_history().await {
+    match state.db.mc().mc_get_tier_history().await {
         Ok(history) => {
             let entries: Vec<McTierHistoryEntry> = history.into_iter().map(|h| McTierHistoryEntry {
                 id: h.id,
@@ -181,7 +181,7 @@ pub async fn update_config(
                 message: "Invalid bankroll value".to_string(),
             });
         }
-        if let Err(e) = state.db.mc_update_bankroll(bankroll, bankroll).await {
+        if let Err(e) = state.db.mc().mc_update_bankroll(bankroll, bankroll).await {
             return Json(McConfigResponse {
                 success: false,
                 message: format!("Failed to update bankroll: {}", e),
@@ -196,7 +196,7 @@ pub async fn update_config(
                 message: "Mode must be 'observation' or 'live'".to_string(),
             });
         }
-        if let Err(e) = state.db.mc_update_mode(mode).await {
+        if let Err(e) = state.db.mc().mc_update_mode(mode).await {
             return Json(McConfigResponse {
                 success: false,
                 message: format!("Failed to update mode: {}", e),
@@ -1,20 +1,17 @@
 //! Mint Maker API route handlers
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
 use crate::db::MintMakerSettingsRow;
 use crate::services::mint_maker::order_manager;
 use crate::services::safe_activation::{self, BuilderCredentials};
 use crate::wallet::decrypt_private_key;
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use crate::services::WalletSigner;
 use axum::{
     extract::State,
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use polymarket_client_sdk::auth::ExposeSecret;
 use polymarket_client_sdk::clob::{Client as ClobClient, Config as ClobConfig};
 use polymarket_client_sdk::clob::types::{
@@ -26,7 +23,6 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use tracing::{info, warn};
 
-const POLYGON_CHAIN_ID: u64 = 137;
 const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
 
 #[derive(Debug, Serialize)]
@@ -34,17 +30,6 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
-/// Helper to validate session and return wallet address
-async fn validate_session(
-    state: &AppState,
-    token: &str,
-) -> Result<String, (StatusCode, Json<ErrorResponse>)> {
-    let session = state.db.get_session(token).await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?
-        .ok_or_else(|| (StatusCode::UNAUTHORIZED, Json(ErrorResponse { error: "Invalid or expired session".to_string() })))?;
-    Ok(session.wallet_address)
-}
-
 // ==================== GET /api/mint-maker/settings ====================
 
 #[derive(Debug, Serialize)]
@@ -54,10 +39,10 @@ pub struct SettingsResponse {
 
 pub async fn get_settings(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<SettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
-    let settings = state.db.get_mint_maker_settings(&wallet).await
+    let wallet = auth.wallet_address;
+    let settings = state.db.mint_maker().get_mint_maker_settings(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
     Ok(Json(SettingsResponse { settings }))
 }
@@ -93,11 +78,11 @@ pub struct UpdateSettingsRequest {
 
 pub async fn update_settings(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<UpdateSettingsRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
-    let mut settings = state.db.get_mint_maker_settings(&wallet).await
+    let wallet = auth.wallet_address;
+    let mut settings = state.db.mint_maker().get_mint_maker_settings(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
     // Apply preset if specified
@@ -130,7 +115,7 @@ pub async fn update_settings(
     if let Some(v) = req.stop_after_profit { settings.stop_after_profit = v; }
     if let Some(p) = &req.preset { settings.preset = p.clone(); }
 
-    state.db.upsert_mint_maker_settings(&settings).await
+    state.db.mint_maker().upsert_mint_maker_settings(&settings).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
     Ok(Json(serde_json::json!({ "success": true })))
@@ -182,25 +167,24 @@ pub struct EnableRequest {
 
 pub async fn enable(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<EnableRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
+    let wallet = auth.wallet_address;
 
     // Verify password by attempting to decrypt key
-    let encrypted_key = state.db.get_encrypted_key(&wallet).await
+    let encrypted_key = state.db.auth().get_encrypted_key(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "No stored key".to_string() })))?;
 
     let private_key = decrypt_private_key(&encrypted_key, &req.password)
         .map_err(|_| (StatusCode::UNAUTHORIZED, Json(ErrorResponse { error: "Invalid password".to_string() })))?;
 
-    // Store decrypted key in key store for auto operations
-    state.key_store.store_key(&wallet, private_key.clone()).await;
-
-    let signer: PrivateKeySigner = private_key.parse()
+    // Unlock the key in the key store for auto operations; from here on only signatures are requested
+    state.key_store.store_key(&wallet, private_key).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Invalid key: {}", e) })))?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
+    let signer = state.key_store.signer(&wallet).await
+        .ok_or_else(|| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Key store did not unlock the key".to_string() })))?;
 
     // Step 1: Ensure Safe proxy is deployed and has on-chain approvals
     // Generated wallets use Gnosis Safe proxies — the Safe must be deployed
@@ -219,7 +203,7 @@ pub async fn enable(
     };
 
     if let Some(bcreds) = &builder_creds {
-        match safe_activation::ensure_safe_activated(&signer, bcreds).await {
+        match safe_activation::ensure_safe_activated(&signer, bcreds, &state.onchain_jobs).await {
             Ok(safe_addr) => info!("MintMaker: Safe activated at {}", safe_addr),
             Err(e) => {
                 warn!("MintMaker: Safe activation error: {}", e);
@@ -249,7 +233,7 @@ pub async fn enable(
     info!("MintMaker: Derived API credentials for {}", wallet);
 
     // Store credentials in DB for future use (cancel orders, check fills, etc.)
-    if let Err(e) = state.db.store_api_credentials(&wallet, &api_key, &api_secret, &api_passphrase).await {
+    if let Err(e) = state.db.auth().store_api_credentials(&wallet, &api_key, &api_secret, &api_passphrase).await {
         warn!("MintMaker: Failed to store API credentials: {}", e);
     }
 
@@ -290,10 +274,10 @@ pub async fn enable(
     }
 
     // Enable in settings
-    let mut settings = state.db.get_mint_maker_settings(&wallet).await
+    let mut settings = state.db.mint_maker().get_mint_maker_settings(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
     settings.enabled = true;
-    state.db.upsert_mint_maker_settings(&settings).await
+    state.db.mint_maker().upsert_mint_maker_settings(&settings).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
     info!("MintMaker enabled for wallet {}", wallet);
@@ -304,14 +288,18 @@ pub async fn enable(
 
 pub async fn disable(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
+    let wallet = auth.wallet_address;
 
-    let mut settings = state.db.get_mint_maker_settings(&wallet).await
+    let mut settings = state.db.mint_maker().get_mint_maker_settings(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
     settings.enabled = false;
-    state.db.upsert_mint_maker_settings(&settings).await
+    state.db.mint_maker().upsert_mint_maker_settings(&settings).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
+
+    // Drop the key unless auto-trading still signs with it
+    state.release_key(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
     info!("MintMaker disabled for wallet {}", wallet);
@@ -322,10 +310,10 @@ pub async fn disable(
 
 pub async fn get_pairs(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
-    let pairs = state.db.get_mint_maker_recent_pairs(&wallet, 50).await
+    let wallet = auth.wallet_address;
+    let pairs = state.db.mint_maker().get_mint_maker_recent_pairs(&wallet, 50).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
     Ok(Json(serde_json::json!({ "pairs": pairs })))
 }
@@ -334,10 +322,10 @@ pub async fn get_pairs(
 
 pub async fn get_stats(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
-    let (total, merged, cancelled, profit, cost, avg_spread) = state.db.get_mint_maker_stats(&wallet).await
+    let wallet = auth.wallet_address;
+    let (total, merged, cancelled, profit, cost, avg_spread) = state.db.mint_maker().get_mint_maker_stats(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
     let fill_rate = if total > 0 { merged as f64 / total as f64 * 100.0 } else { 0.0 };
 
@@ -358,17 +346,17 @@ pub async fn get_stats(
 
 pub async fn get_analytics(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
+    let wallet = auth.wallet_address;
 
-    let summary = state.db.get_mint_maker_analytics_summary(&wallet).await
+    let summary = state.db.mint_maker().get_mint_maker_analytics_summary(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
-    let by_asset = state.db.get_mint_maker_analytics_by_asset(&wallet).await
+    let by_asset = state.db.mint_maker().get_mint_maker_analytics_by_asset(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
-    let (avg_exp_fill_secs, avg_cheap_fill_secs) = state.db.get_mint_maker_fill_times(&wallet).await
+    let (avg_exp_fill_secs, avg_cheap_fill_secs) = state.db.mint_maker().get_mint_maker_fill_times(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
     // Calculate rates
@@ -423,10 +411,10 @@ pub async fn get_analytics(
 
 pub async fn get_log(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
-    let log = state.db.get_mint_maker_log(&wallet, 50).await
+    let wallet = auth.wallet_address;
+    let log = state.db.mint_maker().get_mint_maker_log(&wallet, 50).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
     Ok(Json(serde_json::json!({ "log": log })))
 }
@@ -452,23 +440,23 @@ pub struct PlacePairRequest {
 
 pub async fn place_pair(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<PlacePairRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
+    let wallet = auth.wallet_address;
 
     // Check capacity
-    let settings = state.db.get_mint_maker_settings(&wallet).await
+    let settings = state.db.mint_maker().get_mint_maker_settings(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
-    let market_pairs = state.db.count_mint_maker_open_pairs_for_market(&wallet, &req.market_id).await.unwrap_or(0);
+    let market_pairs = state.db.mint_maker().count_mint_maker_open_pairs_for_market(&wallet, &req.market_id).await.unwrap_or(0);
     if market_pairs >= settings.max_pairs_per_market as i64 {
         return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
             error: format!("Max pairs per market reached ({}/{})", market_pairs, settings.max_pairs_per_market),
         })));
     }
 
-    let total_pairs = state.db.count_mint_maker_total_open_pairs(&wallet).await.unwrap_or(0);
+    let total_pairs = state.db.mint_maker().count_mint_maker_total_open_pairs(&wallet).await.unwrap_or(0);
     if total_pairs >= settings.max_total_pairs as i64 {
         return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
             error: format!("Max total pairs reached ({}/{})", total_pairs, settings.max_total_pairs),
@@ -476,12 +464,14 @@ pub async fn place_pair(
     }
 
     // Decrypt private key
-    let encrypted_key = state.db.get_encrypted_key(&wallet).await
+    let encrypted_key = state.db.auth().get_encrypted_key(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "No stored key".to_string() })))?;
 
     let private_key = decrypt_private_key(&encrypted_key, &req.password)
         .map_err(|_| (StatusCode::UNAUTHORIZED, Json(ErrorResponse { error: "Invalid password".to_string() })))?;
+    let signer = WalletSigner::from_private_key(&wallet, zeroize::Zeroizing::new(private_key)).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Invalid key: {}", e) })))?;
 
     // Parse prices and USD amount per side
     let yes_price = Decimal::from_str(&req.yes_price)
@@ -507,10 +497,6 @@ pub async fn place_pair(
 
     // Ensure Safe has CLOB approval and cache is refreshed before placing orders
     {
-        let signer: PrivateKeySigner = private_key.parse()
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Invalid key: {}", e) })))?;
-        let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
         if let (Some(key), Some(secret), Some(pass)) = (
             state.config.builder_api_key.as_ref(),
             state.config.builder_secret.as_ref(),
@@ -521,7 +507,7 @@ pub async fn place_pair(
                 secret: secret.clone(),
                 passphrase: pass.clone(),
             };
-            match safe_activation::ensure_safe_activated(&signer, &bcreds).await {
+            match safe_activation::ensure_safe_activated(&signer, &bcreds, &state.onchain_jobs).await {
                 Ok(safe_addr) => {
                     info!("MintMaker place_pair: Safe ready at {}", safe_addr);
 
@@ -543,12 +529,12 @@ pub async fn place_pair(
         }
 
         // Refresh CLOB's cached view of on-chain balance & allowances.
-        if let Err(e) = order_manager::refresh_clob_allowance_cache(&private_key).await {
+        if let Err(e) = order_manager::refresh_clob_allowance_cache(&state.clob_pool, &signer).await {
             warn!("MintMaker place_pair: CLOB cache refresh failed: {}", e);
         }
 
         // Ensure API credentials exist (needed for cancel operations)
-        if let Err(e) = order_manager::ensure_clob_api_credentials(&private_key, &state.db, &wallet).await {
+        if let Err(e) = order_manager::ensure_clob_api_credentials(&signer, &state.db, &wallet).await {
             warn!("MintMaker place_pair: Failed to ensure API credentials: {}", e);
         }
     }
@@ -573,7 +559,7 @@ pub async fn place_pair(
         req.market_id, yes_price, yes_shares, no_price, no_shares, usd_per_side);
 
     // Place YES as GTC at scanner price (aggressive limit for fast fill + 0% maker fee)
-    let yes_order_id = order_manager::place_gtc_bid(&private_key, &req.yes_token_id, yes_price, yes_shares).await
+    let yes_order_id = order_manager::place_gtc_bid(&state.clob_pool, &signer, &req.yes_token_id, yes_price, yes_shares).await
         .map_err(|e| {
             warn!("MintMaker: YES GTC failed for {}: {:?}", req.market_id, e);
             (StatusCode::BAD_GATEWAY, Json(ErrorResponse { error: format!("Failed to place YES bid: {}", e) }))
@@ -581,18 +567,18 @@ pub async fn place_pair(
 
     // Place NO as GTC at scanner price.
     // If this fails, try to cancel YES; if cancel fails, record orphan.
-    let no_order_id = match order_manager::place_gtc_bid(&private_key, &req.no_token_id, no_price, no_shares).await {
+    let no_order_id = match order_manager::place_gtc_bid(&state.clob_pool, &signer, &req.no_token_id, no_price, no_shares).await {
         Ok(id) => id,
         Err(e) => {
             warn!("MintMaker: NO GTC failed, cancelling YES order {}: {}", yes_order_id, e);
-            let cancel_ok = if let Ok(Some((ak, as_, ap))) = state.db.get_api_credentials(&wallet).await {
+            let cancel_ok = if let Ok(Some((ak, as_, ap))) = state.db.auth().get_api_credentials(&wallet).await {
                 order_manager::cancel_order(&wallet, &yes_order_id, &ak, &as_, &ap).await.is_ok()
             } else { false };
 
             if !cancel_ok {
                 warn!("MintMaker: YES cancel failed — recording orphaned order {}", yes_order_id);
                 let shares_str = yes_shares.to_string();
-                if let Ok(_pair_id) = state.db.create_mint_maker_pair(
+                if let Ok(_pair_id) = state.db.mint_maker().create_mint_maker_pair(
                     &wallet, &req.market_id, &req.condition_id, &req.question, &req.asset,
                     &yes_order_id, "",
                     &yes_price.to_string(), "0",
@@ -605,7 +591,7 @@ pub async fn place_pair(
                     "Orphaned",
                 ).await {
                 }
-                let _ = state.db.log_mint_maker_action(
+                let _ = state.db.mint_maker().log_mint_maker_action(
                     &wallet, "orphaned", Some(&req.market_id), Some(&req.question), Some(&req.asset),
                     Some(&yes_price.to_string()), None,
                     None, None,
@@ -621,7 +607,7 @@ pub async fn place_pair(
     // should fill quickly on fresh markets, and qualify as maker (0% fee) if they rest.
 
     // Record pair in DB
-    let pair_id = state.db.create_mint_maker_pair(
+    let pair_id = state.db.mint_maker().create_mint_maker_pair(
         &wallet, &req.market_id, &req.condition_id, &req.question, &req.asset,
         &yes_order_id, &no_order_id, &yes_price.to_string(), &no_price.to_string(),
         &merge_size.to_string(),
@@ -637,7 +623,7 @@ pub async fn place_pair(
     // Log action
     let per_pair_profit = Decimal::ONE - pair_cost_per_share;
     let total_profit = per_pair_profit * merge_size;
-    let _ = state.db.log_mint_maker_action(
+    let _ = state.db.mint_maker().log_mint_maker_action(
         &wallet, "place_pair", Some(&req.market_id), Some(&req.question), Some(&req.asset),
         Some(&yes_price.to_string()), Some(&no_price.to_string()),
         Some(&pair_cost_per_share.to_string()), Some(&per_pair_profit.to_string()),
@@ -669,20 +655,20 @@ pub struct CancelPairRequest {
 
 pub async fn cancel_pair(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<CancelPairRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let wallet = validate_session(&state, auth.token()).await?;
+    let wallet = auth.wallet_address;
 
     // Get the pair
-    let pairs = state.db.get_mint_maker_open_pairs(&wallet).await
+    let pairs = state.db.mint_maker().get_mint_maker_open_pairs(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
     let pair = pairs.iter().find(|p| p.id == req.pair_id)
         .ok_or_else(|| (StatusCode::NOT_FOUND, Json(ErrorResponse { error: "Pair not found".to_string() })))?;
 
     // Get API credentials for cancellation
-    let creds = state.db.get_api_credentials(&wallet).await
+    let creds = state.db.auth().get_api_credentials(&wallet).await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "No API credentials".to_string() })))?;
 
@@ -693,10 +679,10 @@ pub async fn cancel_pair(
     let _ = order_manager::cancel_order(&wallet, &pair.no_order_id, &api_key, &api_secret, &api_passphrase).await;
 
     // Update status
-    state.db.update_mint_maker_pair_status(req.pair_id, "Cancelled").await
+    state.db.mint_maker().update_mint_maker_pair_status(req.pair_id, "Cancelled").await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("DB error: {}", e) })))?;
 
-    let _ = state.db.log_mint_maker_action(
+    let _ = state.db.mint_maker().log_mint_maker_action(
         &wallet, "cancel_pair", Some(&pair.market_id), Some(&pair.question), Some(&pair.asset),
         None, None, None, None, Some(&pair.size), Some("Manual cancel"),
     ).await;
@@ -4,11 +4,18 @@ pub mod auto_trading;
 pub mod builder;
 pub mod clob_auth;
 pub mod discord;
+pub mod events;
+pub mod export;
+pub mod ledger;
 pub mod market_data;
 pub mod mc;
 pub mod mint_maker;
+pub mod notifications;
+pub mod onchain;
 pub mod opportunities;
 pub mod orders;
 pub mod positions;
+pub mod reports;
+pub mod tokens;
 pub mod trades;
 pub mod wallet;
@@ -0,0 +1,325 @@
+//! Notification channel endpoints
+//!
+//! Channels send wallet activity to third-party endpoints, so only a wallet
+//! session may add or change them; API tokens can list and test them.
+
+use crate::api::auth::{scope, Auth};
+use crate::api::server::AppState;
+use crate::db::NotificationChannel;
+use crate::services::notifications::{
+    format_kinds, parse_kinds, template, ChannelTarget, Message, Notification, NotificationKind, Sinks,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Default per-channel message limit
+const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 10;
+/// Highest per-channel message limit a user can set
+const MAX_RATE_LIMIT_PER_MINUTE: u32 = 60;
+/// Max channels per wallet
+const MAX_CHANNELS: usize = 10;
+
+/// Error response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Channel as returned by the API (secrets in the target are masked)
+#[derive(Debug, Serialize)]
+pub struct ChannelDto {
+    pub id: i64,
+    pub name: String,
+    pub target: ChannelTarget,
+    pub events: Vec<NotificationKind>,
+    pub template: Option<String>,
+    pub rate_limit_per_minute: i64,
+    pub enabled: bool,
+    pub created_at: String,
+    pub last_sent_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+impl ChannelDto {
+    fn from_channel(channel: NotificationChannel) -> Option<Self> {
+        let target: ChannelTarget = serde_json::from_str(&channel.target).ok()?;
+        Some(Self {
+            id: channel.id,
+            name: channel.name,
+            target: target.redacted(),
+            events: parse_kinds(&channel.events),
+            template: channel.template,
+            rate_limit_per_minute: channel.rate_limit_per_minute,
+            enabled: channel.enabled,
+            created_at: channel.created_at,
+            last_sent_at: channel.last_sent_at,
+            last_error: channel.last_error,
+        })
+    }
+}
+
+/// Channel list response
+#[derive(Debug, Serialize)]
+pub struct ChannelsResponse {
+    pub channels: Vec<ChannelDto>,
+    /// Every kind a channel can subscribe to
+    pub kinds: Vec<NotificationKind>,
+}
+
+/// Create channel request
+#[derive(Debug, Deserialize)]
+pub struct CreateChannelRequest {
+    pub name: String,
+    pub target: ChannelTarget,
+    pub events: Vec<NotificationKind>,
+    pub template: Option<String>,
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+/// Update channel request (omitted fields are unchanged; empty template resets to the default layout)
+#[derive(Debug, Deserialize)]
+pub struct UpdateChannelRequest {
+    pub name: Option<String>,
+    pub target: Option<ChannelTarget>,
+    pub events: Option<Vec<NotificationKind>>,
+    pub template: Option<String>,
+    pub rate_limit_per_minute: Option<u32>,
+    pub enabled: Option<bool>,
+}
+
+fn db_error(e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+        }),
+    )
+}
+
+fn bad_request(error: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: error.into() }))
+}
+
+fn not_found() -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: "Notification channel not found".to_string(),
+        }),
+    )
+}
+
+fn validate_name(name: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if name.is_empty() || name.len() > 64 {
+        return Err(bad_request("Channel name must be 1-64 characters"));
+    }
+    Ok(())
+}
+
+fn validate_rate_limit(rate_limit: u32) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if rate_limit == 0 || rate_limit > MAX_RATE_LIMIT_PER_MINUTE {
+        return Err(bad_request(format!(
+            "rate_limit_per_minute must be between 1 and {}",
+            MAX_RATE_LIMIT_PER_MINUTE
+        )));
+    }
+    Ok(())
+}
+
+fn validate_template(template: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    template::validate(template).map_err(bad_request)
+}
+
+/// GET /api/notifications/channels
+pub async fn list_channels(
+    State(state): State<AppState>,
+    auth: Auth,
+) -> Result<Json<ChannelsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let channels = state
+        .db.notifications()
+        .get_notification_channels(&auth.wallet_address)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(ChannelsResponse {
+        channels: channels.into_iter().filter_map(ChannelDto::from_channel).collect(),
+        kinds: NotificationKind::ALL.to_vec(),
+    }))
+}
+
+/// POST /api/notifications/channels
+pub async fn create_channel(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Json(req): Json<CreateChannelRequest>,
+) -> Result<Json<ChannelDto>, (StatusCode, Json<ErrorResponse>)> {
+    let name = req.name.trim();
+    validate_name(name)?;
+    req.target
+        .validate(state.config.smtp.is_some())
+        .map_err(bad_request)?;
+    if req.events.is_empty() {
+        return Err(bad_request("Subscribe to at least one event kind"));
+    }
+    let template = req.template.as_deref().map(str::trim).filter(|t| !t.is_empty());
+    if let Some(template) = template {
+        validate_template(template)?;
+    }
+    let rate_limit = req.rate_limit_per_minute.unwrap_or(DEFAULT_RATE_LIMIT_PER_MINUTE);
+    validate_rate_limit(rate_limit)?;
+
+    let existing = state
+        .db.notifications()
+        .get_notification_channels(&auth.wallet_address)
+        .await
+        .map_err(db_error)?;
+    if existing.len() >= MAX_CHANNELS {
+        return Err(bad_request(format!(
+            "A wallet can have at most {} notification channels",
+            MAX_CHANNELS
+        )));
+    }
+
+    let target_json = serde_json::to_string(&req.target).map_err(|e| db_error(e.into()))?;
+    let channel = state
+        .db.notifications()
+        .create_notification_channel(
+            &auth.wallet_address,
+            name,
+            &target_json,
+            &format_kinds(&req.events),
+            template,
+            i64::from(rate_limit),
+        )
+        .await
+        .map_err(db_error)?;
+
+    info!(
+        "Created {} notification channel {} for {} ({})",
+        req.target.type_name(), channel.id, auth.wallet_address, channel.events
+    );
+
+    ChannelDto::from_channel(channel)
+        .map(Json)
+        .ok_or_else(|| db_error(anyhow::anyhow!("Stored channel target is invalid")))
+}
+
+/// PUT /api/notifications/channels/:id
+pub async fn update_channel(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Path(id): Path<i64>,
+    Json(req): Json<UpdateChannelRequest>,
+) -> Result<Json<ChannelDto>, (StatusCode, Json<ErrorResponse>)> {
+    let mut channel = state
+        .db.notifications()
+        .get_notification_channel(&auth.wallet_address, id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+
+    if let Some(name) = req.name {
+        let name = name.trim();
+        validate_name(name)?;
+        channel.name = name.to_string();
+    }
+    if let Some(target) = req.target {
+        target.validate(state.config.smtp.is_some()).map_err(bad_request)?;
+        channel.target = serde_json::to_string(&target).map_err(|e| db_error(e.into()))?;
+    }
+    if let Some(events) = req.events {
+        if events.is_empty() {
+            return Err(bad_request("Subscribe to at least one event kind"));
+        }
+        channel.events = format_kinds(&events);
+    }
+    if let Some(template) = req.template {
+        let template = template.trim();
+        if template.is_empty() {
+            channel.template = None;
+        } else {
+            validate_template(template)?;
+            channel.template = Some(template.to_string());
+        }
+    }
+    if let Some(rate_limit) = req.rate_limit_per_minute {
+        validate_rate_limit(rate_limit)?;
+        channel.rate_limit_per_minute = i64::from(rate_limit);
+    }
+    if let Some(enabled) = req.enabled {
+        channel.enabled = enabled;
+    }
+
+    state
+        .db.notifications()
+        .update_notification_channel(&channel)
+        .await
+        .map_err(db_error)?;
+
+    ChannelDto::from_channel(channel)
+        .map(Json)
+        .ok_or_else(|| db_error(anyhow::anyhow!("Stored channel target is invalid")))
+}
+
+/// DELETE /api/notifications/channels/:id
+pub async fn delete_channel(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let deleted = state
+        .db.notifications()
+        .delete_notification_channel(&auth.wallet_address, id)
+        .await
+        .map_err(db_error)?;
+
+    if !deleted {
+        return Err(not_found());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/notifications/channels/:id/test — send a sample message right away (no rate limit)
+pub async fn test_channel(
+    State(state): State<AppState>,
+    auth: Auth,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let channel = state
+        .db.notifications()
+        .get_notification_channel(&auth.wallet_address, id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+    let target: ChannelTarget = serde_json::from_str(&channel.target)
+        .map_err(|e| db_error(e.into()))?;
+
+    let notification = Notification::new(NotificationKind::Fill, Some(&auth.wallet_address), "Test notification")
+        .field("channel", &channel.name)
+        .field("market", "Example market")
+        .field("pnl", "$0.00");
+    let message = Message::new(&notification, channel.template.as_deref(), 0);
+
+    let sinks = Sinks::new(state.config.smtp.as_ref()).map_err(db_error)?;
+    let result = sinks.deliver(&target, &message).await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+    let _ = state
+        .db.notifications()
+        .record_notification_delivery(channel.id, error.as_deref())
+        .await;
+
+    match error {
+        None => Ok(StatusCode::NO_CONTENT),
+        Some(error) => Err((
+            StatusCode::BAD_GATEWAY,
+            Json(ErrorResponse {
+                error: format!("Delivery failed: {}", error),
+            }),
+        )),
+    }
+}
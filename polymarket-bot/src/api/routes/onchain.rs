@@ -0,0 +1,91 @@
+//! On-chain job API endpoints
+//! Lets the UI follow relay and direct transactions without polling the relay itself
+
+use crate::api::auth::Auth;
+use crate::api::server::AppState;
+use crate::types::OnchainJob;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Error response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Query parameters for listing jobs
+#[derive(Debug, Deserialize)]
+pub struct ListJobsQuery {
+    /// Maximum number to return (default 50, max 200)
+    pub limit: Option<i64>,
+}
+
+/// On-chain jobs response
+#[derive(Debug, Serialize)]
+pub struct JobsResponse {
+    pub jobs: Vec<OnchainJob>,
+    pub total: usize,
+}
+
+/// GET /api/onchain/jobs — recent on-chain jobs for the authenticated wallet
+pub async fn list_jobs(
+    State(state): State<AppState>,
+    auth: Auth,
+    Query(query): Query<ListJobsQuery>,
+) -> Result<Json<JobsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let wallet_address = auth.wallet_address;
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+
+    let jobs = state
+        .db.onchain_jobs()
+        .get_onchain_jobs_for_wallet(&wallet_address, limit)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Database error: {}", e),
+                }),
+            )
+        })?;
+
+    let total = jobs.len();
+    Ok(Json(JobsResponse { jobs, total }))
+}
+
+/// GET /api/onchain/jobs/:id — a single job owned by the authenticated wallet
+pub async fn get_job(
+    State(state): State<AppState>,
+    auth: Auth,
+    Path(id): Path<i64>,
+) -> Result<Json<OnchainJob>, (StatusCode, Json<ErrorResponse>)> {
+    let wallet_address = auth.wallet_address;
+
+    let job = state
+        .db.onchain_jobs()
+        .get_onchain_job(id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Database error: {}", e),
+                }),
+            )
+        })?
+        .filter(|job| job.wallet_address == wallet_address)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Job not found".to_string(),
+                }),
+            )
+        })?;
+
+    Ok(Json(job))
+}
@@ -1,6 +1,7 @@
 //! Open Orders API endpoints
 //! Fetches and manages orders from Polymarket CLOB
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
 use crate::services::{EndpointClass, derive_safe_wallet};
 use axum::{
@@ -8,10 +9,6 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -57,34 +54,12 @@ pub struct CancelOrderResponse {
 /// Get open orders for the authenticated wallet
 pub async fn get_open_orders(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<OpenOrdersResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get API credentials for this wallet
     let credentials = state
         .db
-        .get_api_credentials(&session.wallet_address)
+        .auth().get_api_credentials(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -107,10 +82,10 @@ pub async fn get_open_orders(
     };
 
     // Derive proxy wallet address for external wallets
-    let proxy_address = derive_safe_wallet(&session.wallet_address)
-        .unwrap_or_else(|_| session.wallet_address.clone());
+    let proxy_address = derive_safe_wallet(&auth.wallet_address)
+        .unwrap_or_else(|_| auth.wallet_address.clone());
 
-    info!("Fetching orders for EOA: {} -> Proxy: {}", session.wallet_address, proxy_address);
+    info!("Fetching orders for EOA: {} -> Proxy: {}", auth.wallet_address, proxy_address);
 
     // Rate limit general API requests
     if state.rate_limiter.acquire(EndpointClass::General).await {
@@ -120,7 +95,7 @@ pub async fn get_open_orders(
 
     // Fetch open orders from CLOB using proxy address
     let orders = match fetch_open_orders_from_clob(
-        &session.wallet_address, // EOA for auth headers
+        &auth.wallet_address, // EOA for auth headers
         &proxy_address,          // Proxy for maker filter
         &api_key,
         &api_secret,
@@ -162,35 +137,13 @@ pub async fn get_open_orders(
 /// Cancel an order by ID
 pub async fn cancel_order(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Path(order_id): Path<String>,
 ) -> Result<Json<CancelOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get API credentials
     let credentials = state
         .db
-        .get_api_credentials(&session.wallet_address)
+        .auth().get_api_credentials(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -211,7 +164,7 @@ pub async fn cancel_order(
 
     let (api_key, api_secret, api_passphrase) = credentials;
 
-    info!("Cancelling order {} for wallet {}", order_id, session.wallet_address);
+    info!("Cancelling order {} for wallet {}", order_id, auth.wallet_address);
 
     // Rate limit cancel requests
     if state.rate_limiter.acquire(EndpointClass::DeleteOrder).await {
@@ -222,7 +175,7 @@ pub async fn cancel_order(
     // Cancel the order via CLOB API (use EOA for auth)
     cancel_order_on_clob(
         &order_id,
-        &session.wallet_address, // EOA for auth
+        &auth.wallet_address, // EOA for auth
         &api_key,
         &api_secret,
         &api_passphrase,
@@ -441,32 +394,11 @@ async fn cancel_order_on_clob(
 /// Cancel all open orders for the authenticated wallet
 pub async fn cancel_all_orders(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
 ) -> Result<Json<CancelOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let credentials = state
         .db
-        .get_api_credentials(&session.wallet_address)
+        .auth().get_api_credentials(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -487,7 +419,7 @@ pub async fn cancel_all_orders(
 
     let (api_key, api_secret, api_passphrase) = credentials;
 
-    info!("Cancelling ALL orders for wallet {}", session.wallet_address);
+    info!("Cancelling ALL orders for wallet {}", auth.wallet_address);
 
     if state.rate_limiter.acquire(EndpointClass::DeleteOrder).await {
         state.metrics.inc_api_rate_limited();
@@ -495,7 +427,7 @@ pub async fn cancel_all_orders(
     state.metrics.inc_api_calls();
 
     cancel_all_on_clob(
-        &session.wallet_address,
+        &auth.wallet_address,
         &api_key,
         &api_secret,
         &api_passphrase,
@@ -520,33 +452,12 @@ pub async fn cancel_all_orders(
 /// Cancel all orders for a specific market
 pub async fn cancel_market_orders(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Path(market_id): Path<String>,
 ) -> Result<Json<CancelOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let credentials = state
         .db
-        .get_api_credentials(&session.wallet_address)
+        .auth().get_api_credentials(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -567,7 +478,7 @@ pub async fn cancel_market_orders(
 
     let (api_key, api_secret, api_passphrase) = credentials;
 
-    info!("Cancelling orders for market {} wallet {}", market_id, session.wallet_address);
+    info!("Cancelling orders for market {} wallet {}", market_id, auth.wallet_address);
 
     if state.rate_limiter.acquire(EndpointClass::DeleteOrder).await {
         state.metrics.inc_api_rate_limited();
@@ -576,7 +487,7 @@ pub async fn cancel_market_orders(
 
     cancel_market_on_clob(
         &market_id,
-        &session.wallet_address,
+        &auth.wallet_address,
         &api_key,
         &api_secret,
         &api_passphrase,
@@ -598,8 +509,8 @@ pub async fn cancel_market_orders(
     }))
 }
 
-/// Cancel all orders on Polymarket CLOB
-async fn cancel_all_on_clob(
+/// Cancel all orders on Polymarket CLOB (also used by the shutdown sequence)
+pub(crate) async fn cancel_all_on_clob(
     wallet_address: &str,
     api_key: &str,
     api_secret: &str,
@@ -721,32 +632,11 @@ async fn cancel_market_on_clob(
 /// Get order lifecycle history for a wallet
 pub async fn get_order_lifecycle(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<Vec<crate::types::Order>>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let orders = state
         .db
-        .get_orders_for_wallet(&session.wallet_address, None)
+        .orders().get_orders_for_wallet(&auth.wallet_address, None)
         .await
         .map_err(|e| {
             (
@@ -1,5 +1,6 @@
 //! Position API endpoints
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
 use crate::types::{BotStats, Position};
 use axum::{
@@ -7,10 +8,6 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use base64::Engine;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -45,40 +42,18 @@ pub struct ErrorResponse {
 /// List positions for authenticated wallet
 pub async fn list_positions(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
     Query(query): Query<ListPositionsQuery>,
 ) -> Result<Json<PositionsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get positions based on filter
     let positions = match query.status.as_deref() {
         Some("open") => state
             .db
-            .get_open_positions_for_wallet(&session.wallet_address)
+            .positions().get_open_positions_for_wallet(&auth.wallet_address)
             .await,
         _ => state
             .db
-            .get_positions_for_wallet(&session.wallet_address)
+            .positions().get_positions_for_wallet(&auth.wallet_address)
             .await,
     }
     .map_err(|e| {
@@ -98,33 +73,11 @@ pub async fn list_positions(
 /// Get stats for authenticated wallet
 pub async fn get_stats(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth,
 ) -> Result<Json<StatsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     let stats = state
         .db
-        .get_stats_for_wallet(&session.wallet_address)
+        .positions().get_stats_for_wallet(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -153,32 +106,10 @@ pub struct UpdateEntryPriceRequest {
 /// Update entry_price for a position (fix incorrect entry prices)
 pub async fn update_entry_price(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Path(position_id): Path<i64>,
     Json(req): Json<UpdateEntryPriceRequest>,
 ) -> Result<Json<UpdateTokenIdResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Validate entry_price format
     let _price = Decimal::from_str(&req.entry_price).map_err(|_| {
         (
@@ -192,7 +123,7 @@ pub async fn update_entry_price(
     // Update the entry_price
     state
         .db
-        .update_position_entry_price(&session.wallet_address, position_id, &req.entry_price)
+        .positions().update_position_entry_price(&auth.wallet_address, position_id, &req.entry_price)
         .await
         .map_err(|e| {
             (
@@ -215,36 +146,14 @@ pub struct UpdateTokenIdResponse {
 /// Update token_id for a position (backfill for existing positions)
 pub async fn update_token_id(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Path(position_id): Path<i64>,
     Json(req): Json<UpdateTokenIdRequest>,
 ) -> Result<Json<UpdateTokenIdResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Update the token_id
     state
         .db
-        .update_position_token_id(&session.wallet_address, position_id, &req.token_id)
+        .positions().update_position_token_id(&auth.wallet_address, position_id, &req.token_id)
         .await
         .map_err(|e| {
             (
@@ -292,35 +201,13 @@ pub struct RedeemPositionResponse {
 /// Redeem a resolved winning position (claim USDC)
 pub async fn redeem_position(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Path(position_id): Path<i64>,
 ) -> Result<Json<RedeemPositionResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get the position
     let position = state
         .db
-        .get_position_by_id(&session.wallet_address, position_id)
+        .positions().get_position_by_id(&auth.wallet_address, position_id)
         .await
         .map_err(|e| {
             (
@@ -549,32 +436,10 @@ pub async fn redeem_position(
 /// Close a position (mark as sold) - supports full or partial sells
 pub async fn close_position(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Path(position_id): Path<i64>,
     Json(req): Json<ClosePositionRequest>,
 ) -> Result<Json<ClosePositionResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Parse exit price
     let exit_price = Decimal::from_str(&req.exit_price).map_err(|_| {
         (
@@ -599,7 +464,7 @@ pub async fn close_position(
 
         let result = state
             .db
-            .partial_close_position_for_wallet(&session.wallet_address, position_id, sell_shares, exit_price)
+            .positions().partial_close_position_for_wallet(&auth.wallet_address, position_id, sell_shares, exit_price)
             .await
             .map_err(|e| {
                 (
@@ -621,7 +486,7 @@ pub async fn close_position(
         // Full sell (legacy behavior) - close all remaining shares
         let pnl = state
             .db
-            .close_position_for_wallet(&session.wallet_address, position_id, exit_price, None)
+            .positions().close_position_for_wallet(&auth.wallet_address, position_id, exit_price, None)
             .await
             .map_err(|e| {
                 (
@@ -0,0 +1,140 @@
+//! Performance report endpoints
+//!
+//! Daily and weekly reports are generated automatically once a period ends;
+//! `POST /reports/generate` builds one on demand (including the current,
+//! still-open period) and replaces any stored report for the same period.
+
+use crate::api::auth::Auth;
+use crate::api::server::AppState;
+use crate::services::{Report, ReportPeriod};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default number of reports listed
+const DEFAULT_LIMIT: i64 = 30;
+/// Max number of reports listed
+const MAX_LIMIT: i64 = 365;
+
+/// Error response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Stored report
+#[derive(Debug, Serialize)]
+pub struct ReportDto {
+    pub id: i64,
+    #[serde(flatten)]
+    pub report: Report,
+}
+
+/// Report list response
+#[derive(Debug, Serialize)]
+pub struct ReportsResponse {
+    pub reports: Vec<ReportDto>,
+}
+
+/// Report list query params
+#[derive(Debug, Deserialize)]
+pub struct ReportsQuery {
+    /// "daily" or "weekly" (both when omitted)
+    pub period: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Generate report request
+#[derive(Debug, Deserialize)]
+pub struct GenerateReportRequest {
+    pub period: ReportPeriod,
+    /// Any day inside the period (defaults to today)
+    pub date: Option<NaiveDate>,
+}
+
+fn db_error(e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+        }),
+    )
+}
+
+fn bad_request(error: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: error.into() }))
+}
+
+/// GET /api/reports?period=daily&limit=30
+pub async fn list_reports(
+    State(state): State<AppState>,
+    auth: Auth,
+    Query(query): Query<ReportsQuery>,
+) -> Result<Json<ReportsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let period = match query.period.as_deref() {
+        Some(p) => Some(ReportPeriod::parse(p).ok_or_else(|| bad_request("period must be 'daily' or 'weekly'"))?),
+        None => None,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let reports = state
+        .db.reports()
+        .get_reports(&auth.wallet_address, period.map(|p| p.as_str()), limit)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(ReportsResponse {
+        reports: reports
+            .into_iter()
+            .map(|(id, report)| ReportDto { id, report })
+            .collect(),
+    }))
+}
+
+/// GET /api/reports/:id
+pub async fn get_report(
+    State(state): State<AppState>,
+    auth: Auth,
+    Path(id): Path<i64>,
+) -> Result<Json<ReportDto>, (StatusCode, Json<ErrorResponse>)> {
+    let (id, report) = state
+        .db.reports()
+        .get_report(&auth.wallet_address, id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Report not found".to_string(),
+                }),
+            )
+        })?;
+
+    Ok(Json(ReportDto { id, report }))
+}
+
+/// POST /api/reports/generate
+pub async fn generate_report(
+    State(state): State<AppState>,
+    auth: Auth,
+    Json(req): Json<GenerateReportRequest>,
+) -> Result<Json<ReportDto>, (StatusCode, Json<ErrorResponse>)> {
+    let today = Utc::now().date_naive();
+    let date = req.date.unwrap_or(today);
+    if date > today {
+        return Err(bad_request("date cannot be in the future"));
+    }
+
+    let (id, report) = state
+        .reporter
+        .generate(&auth.wallet_address, req.period, date)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(ReportDto { id, report }))
+}
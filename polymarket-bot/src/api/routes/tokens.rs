@@ -0,0 +1,173 @@
+//! API token management endpoints
+//!
+//! Long-lived scoped tokens for automation scripts. Only a wallet session can
+//! create, list or revoke tokens; the secret is returned once at creation.
+
+use crate::api::auth::{format_scopes, generate_token, hash_token, scope, Auth, Scope};
+use crate::api::server::AppState;
+use crate::db::ApiToken;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Default per-token request limit
+const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 60;
+/// Highest per-token request limit a user can set
+const MAX_RATE_LIMIT_PER_MINUTE: u32 = 1_200;
+/// Max unrevoked tokens per wallet
+const MAX_ACTIVE_TOKENS: usize = 20;
+
+/// Error response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Create token request
+#[derive(Debug, Deserialize)]
+pub struct CreateTokenRequest {
+    pub name: String,
+    /// Any of "read", "trade", "withdraw" (read is always granted)
+    pub scopes: Vec<String>,
+    pub rate_limit_per_minute: Option<u32>,
+    /// Days until the token expires (never when omitted)
+    pub expires_in_days: Option<i64>,
+}
+
+/// Create token response; `token` is only ever shown here
+#[derive(Debug, Serialize)]
+pub struct CreateTokenResponse {
+    pub token: String,
+    pub api_token: ApiToken,
+}
+
+/// Token list response
+#[derive(Debug, Serialize)]
+pub struct TokensResponse {
+    pub tokens: Vec<ApiToken>,
+}
+
+fn db_error(e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+        }),
+    )
+}
+
+fn bad_request(error: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: error.into() }))
+}
+
+/// GET /api/auth/tokens — tokens of the session wallet (secrets are never returned)
+pub async fn list_tokens(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+) -> Result<Json<TokensResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let tokens = state
+        .db
+        .auth().list_api_tokens(&auth.wallet_address)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(TokensResponse { tokens }))
+}
+
+/// POST /api/auth/tokens — create a scoped API token
+pub async fn create_token(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Json(req): Json<CreateTokenRequest>,
+) -> Result<Json<CreateTokenResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let name = req.name.trim();
+    if name.is_empty() || name.len() > 64 {
+        return Err(bad_request("Token name must be 1-64 characters"));
+    }
+
+    let mut scopes = Vec::new();
+    for s in &req.scopes {
+        let scope = Scope::parse(s).ok_or_else(|| bad_request(format!("Unknown scope '{}'", s)))?;
+        scopes.push(scope);
+    }
+    if scopes.is_empty() {
+        return Err(bad_request("At least one scope is required"));
+    }
+
+    let rate_limit = req.rate_limit_per_minute.unwrap_or(DEFAULT_RATE_LIMIT_PER_MINUTE);
+    if rate_limit == 0 || rate_limit > MAX_RATE_LIMIT_PER_MINUTE {
+        return Err(bad_request(format!(
+            "rate_limit_per_minute must be between 1 and {}",
+            MAX_RATE_LIMIT_PER_MINUTE
+        )));
+    }
+
+    let expires_at = match req.expires_in_days {
+        Some(days) if days <= 0 => return Err(bad_request("expires_in_days must be positive")),
+        Some(days) => Some(Utc::now() + Duration::days(days)),
+        None => None,
+    };
+
+    let existing = state
+        .db
+        .auth().list_api_tokens(&auth.wallet_address)
+        .await
+        .map_err(db_error)?;
+    if existing.iter().filter(|t| t.revoked_at.is_none()).count() >= MAX_ACTIVE_TOKENS {
+        return Err(bad_request(format!(
+            "A wallet can hold at most {} active tokens; revoke one first",
+            MAX_ACTIVE_TOKENS
+        )));
+    }
+
+    let token = generate_token();
+    let api_token = state
+        .db
+        .auth().create_api_token(
+            &auth.wallet_address,
+            name,
+            &hash_token(&token),
+            &format_scopes(&scopes),
+            i64::from(rate_limit),
+            expires_at,
+        )
+        .await
+        .map_err(db_error)?;
+
+    info!(
+        "Created API token {} ({}) for {} with scopes {}",
+        api_token.id, api_token.name, auth.wallet_address, api_token.scopes
+    );
+
+    Ok(Json(CreateTokenResponse { token, api_token }))
+}
+
+/// DELETE /api/auth/tokens/:id — revoke a token immediately
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Path(token_id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let revoked = state
+        .db
+        .auth().revoke_api_token(&auth.wallet_address, &token_id)
+        .await
+        .map_err(db_error)?;
+
+    if !revoked {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Token not found or already revoked".to_string(),
+            }),
+        ));
+    }
+
+    info!("Revoked API token {} for {}", token_id, auth.wallet_address);
+    Ok(Json(serde_json::json!({ "revoked": token_id })))
+}
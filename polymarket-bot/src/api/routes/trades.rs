@@ -1,7 +1,9 @@
 //! Trade API endpoints
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
-use crate::services::{EndpointClass, derive_safe_wallet};
+use crate::services::signer::WalletSigner;
+use crate::services::{ClobAccount, EndpointClass, derive_safe_wallet};
 use crate::types::{Side, StrategyType};
 use crate::wallet::decrypt_private_key;
 use axum::{
@@ -9,10 +11,6 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -20,10 +18,6 @@ use std::str::FromStr;
 use base64::Engine;
 use tracing::{info, warn, debug};
 use alloy::primitives::U256;
-use alloy::signers::{local::PrivateKeySigner, Signer};
-use polymarket_client_sdk::clob::{
-    Client as ClobClient, Config as ClobConfig,
-};
 use polymarket_client_sdk::clob::types::{
     Amount, OrderType as ClobOrderType, Side as ClobSide,
 };
@@ -57,11 +51,6 @@ async fn check_order_scoring(token_id: &str, side: &str, price: &str, size: &str
     }
 }
 
-/// Polygon chain ID
-const POLYGON_CHAIN_ID: u64 = 137;
-/// CLOB API endpoint
-const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
-
 /// Execute trade request
 #[derive(Debug, Deserialize)]
 pub struct ExecuteTradeRequest {
@@ -108,35 +97,13 @@ pub struct ErrorResponse {
 /// Execute a live trade
 pub async fn execute_trade(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<ExecuteTradeRequest>,
 ) -> Result<Json<TradeResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Get encrypted key
     let encrypted_key = state
         .db
-        .get_encrypted_key(&session.wallet_address)
+        .auth().get_encrypted_key(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -155,7 +122,7 @@ pub async fn execute_trade(
             )
         })?;
 
-    // Decrypt private key
+    // Decrypt private key into a one-off signer
     let private_key = decrypt_private_key(&encrypted_key, &req.password).map_err(|_| {
         (
             StatusCode::UNAUTHORIZED,
@@ -164,6 +131,16 @@ pub async fn execute_trade(
             }),
         )
     })?;
+    let wallet_signer = WalletSigner::from_private_key(&auth.wallet_address, zeroize::Zeroizing::new(private_key))
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Invalid private key: {}", e),
+                }),
+            )
+        })?;
 
     // Parse side
     let side = match req.side.to_lowercase().as_str() {
@@ -241,52 +218,23 @@ pub async fn execute_trade(
 
     info!(
         "Executing LIVE trade for wallet {} - market: {}, side: {:?}, size: ${}, order_type: {}, price: {:?}, post_only: {}",
-        session.wallet_address, req.market_id, side, size,
+        auth.wallet_address, req.market_id, side, size,
         order_type_str,
         limit_price_decimal.unwrap_or(entry_price),
         post_only
     );
 
-    // Create signer from private key
-    let signer: PrivateKeySigner = private_key.parse().map_err(|e| {
+    // Authenticated client from the shared pool (credentials cached per wallet)
+    let pooled = state.clob_pool.client(&wallet_signer, ClobAccount::Eoa).await.map_err(|e| {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_GATEWAY,
             Json(ErrorResponse {
-                error: format!("Failed to parse private key: {}", e),
+                error: format!("Failed to authenticate with CLOB: {:#}", e),
             }),
         )
     })?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-    // Create CLOB config
-    let clob_config = ClobConfig::builder()
-        .use_server_time(true)
-        .build();
-
-    // Create and authenticate client
-    debug!("Authenticating with CLOB API...");
-    let client = ClobClient::new(CLOB_ENDPOINT, clob_config)
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Failed to create CLOB client: {}", e),
-                }),
-            )
-        })?
-        .authentication_builder(&signer)
-        .authenticate()
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::BAD_GATEWAY,
-                Json(ErrorResponse {
-                    error: format!("Failed to authenticate with CLOB: {}", e),
-                }),
-            )
-        })?;
-
-    info!("CLOB client authenticated successfully");
+    let client = &pooled.client;
+    let signer = &pooled.signer;
 
     // Convert token_id to U256
     let token_id_u256 = U256::from_str_radix(&token_id, 10).map_err(|e| {
@@ -358,7 +306,7 @@ pub async fn execute_trade(
                 }))
             })?;
 
-        let signed_order = client.sign(&signer, order).await.map_err(|e| {
+        let signed_order = client.sign(signer, order).await.map_err(|e| {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
                 error: format!("Failed to sign order: {}", e),
             }))
@@ -368,13 +316,18 @@ pub async fn execute_trade(
             state.metrics.inc_api_rate_limited();
         }
         state.metrics.inc_api_calls();
-        let response = client.post_order(signed_order).await.map_err(|e| {
-            state.metrics.inc_orders_failed();
-            state.metrics.inc_api_errors();
-            (StatusCode::BAD_GATEWAY, Json(ErrorResponse {
-                error: format!("Failed to submit order: {}", e),
-            }))
-        })?;
+        let response = match client.post_order(signed_order).await {
+            Ok(response) => response,
+            Err(e) => {
+                state.metrics.inc_orders_failed();
+                state.metrics.inc_api_errors();
+                let error = e.to_string();
+                state.clob_pool.invalidate_on_auth_error(&wallet_signer, &error).await;
+                return Err((StatusCode::BAD_GATEWAY, Json(ErrorResponse {
+                    error: format!("Failed to submit order: {}", error),
+                })));
+            }
+        };
 
         state.metrics.inc_orders_submitted();
         state.metrics.inc_order_type(order_type_str);
@@ -410,7 +363,7 @@ pub async fn execute_trade(
                 }))
             })?;
 
-        let signed_order = client.sign(&signer, order).await.map_err(|e| {
+        let signed_order = client.sign(signer, order).await.map_err(|e| {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
                 error: format!("Failed to sign order: {}", e),
             }))
@@ -420,13 +373,18 @@ pub async fn execute_trade(
             state.metrics.inc_api_rate_limited();
         }
         state.metrics.inc_api_calls();
-        let response = client.post_order(signed_order).await.map_err(|e| {
-            state.metrics.inc_orders_failed();
-            state.metrics.inc_api_errors();
-            (StatusCode::BAD_GATEWAY, Json(ErrorResponse {
-                error: format!("Failed to submit order: {}", e),
-            }))
-        })?;
+        let response = match client.post_order(signed_order).await {
+            Ok(response) => response,
+            Err(e) => {
+                state.metrics.inc_orders_failed();
+                state.metrics.inc_api_errors();
+                let error = e.to_string();
+                state.clob_pool.invalidate_on_auth_error(&wallet_signer, &error).await;
+                return Err((StatusCode::BAD_GATEWAY, Json(ErrorResponse {
+                    error: format!("Failed to submit order: {}", error),
+                })));
+            }
+        };
 
         state.metrics.inc_orders_submitted();
         state.metrics.inc_order_type("FOK");
@@ -464,7 +422,7 @@ pub async fn execute_trade(
 
             match tp_order {
                 Ok(order) => {
-                    match client.sign(&signer, order).await {
+                    match client.sign(signer, order).await {
                         Ok(signed) => {
                             if state.rate_limiter.acquire(EndpointClass::PostOrder).await {
                                 state.metrics.inc_api_rate_limited();
@@ -496,8 +454,8 @@ pub async fn execute_trade(
     let actual_entry_price = limit_price_decimal.unwrap_or(entry_price);
     let position_id = state
         .db
-        .create_position_for_wallet(
-            &session.wallet_address,
+        .positions().create_position_for_wallet(
+            &auth.wallet_address,
             &req.market_id,
             &question,
             Some(&slug),
@@ -545,31 +503,9 @@ pub async fn execute_trade(
 /// Execute a paper trade (no real money)
 pub async fn paper_trade(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<PaperTradeRequest>,
 ) -> Result<Json<TradeResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Parse side
     let side = match req.side.to_lowercase().as_str() {
         "yes" => Side::Yes,
@@ -619,8 +555,8 @@ pub async fn paper_trade(
     // Record the paper trade
     let position_id = state
         .db
-        .create_position_for_wallet(
-            &session.wallet_address,
+        .positions().create_position_for_wallet(
+            &auth.wallet_address,
             &req.market_id,
             &question,
             Some(&slug),
@@ -701,33 +637,11 @@ pub struct SignedTradeResponse {
 /// Execute a pre-signed order from an external wallet
 pub async fn execute_signed_trade(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<SignedTradeRequest>,
 ) -> Result<Json<SignedTradeResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Verify the signer matches the session wallet
-    if req.signed_order.signer.to_lowercase() != session.wallet_address.to_lowercase() {
+    if req.signed_order.signer.to_lowercase() != auth.wallet_address.to_lowercase() {
         return Err((
             StatusCode::FORBIDDEN,
             Json(ErrorResponse {
@@ -771,13 +685,13 @@ pub async fn execute_signed_trade(
 
     info!(
         "NEWCODE Submitting signed order for wallet {} - market: {}, side: {:?}, size: {}",
-        session.wallet_address, req.market_id, side, size
+        auth.wallet_address, req.market_id, side, size
     );
 
     // Get API credentials for this wallet
     let credentials = state
         .db
-        .get_api_credentials(&session.wallet_address)
+        .auth().get_api_credentials(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -789,24 +703,24 @@ pub async fn execute_signed_trade(
         })?;
 
     // Derive the proxy wallet address from EOA (Polymarket uses Safe proxies for browser wallets)
-    let proxy_address = match derive_safe_wallet(&session.wallet_address) {
+    let proxy_address = match derive_safe_wallet(&auth.wallet_address) {
         Ok(addr) => addr,
         Err(e) => {
             warn!("Failed to derive proxy wallet: {}", e);
-            session.wallet_address.clone() // Fallback to EOA
+            auth.wallet_address.clone() // Fallback to EOA
         }
     };
-    println!(">>> EOA: {} -> Proxy: {}", session.wallet_address, proxy_address);
-    warn!("EOA: {} -> Proxy: {}", session.wallet_address, proxy_address);
+    println!(">>> EOA: {} -> Proxy: {}", auth.wallet_address, proxy_address);
+    warn!("EOA: {} -> Proxy: {}", auth.wallet_address, proxy_address);
 
     let order_result = match credentials {
         Some((api_key, api_secret, api_passphrase)) => {
             info!("Using API credentials: key={}..., secret_len={}, pass_len={}",
                   &api_key[..8.min(api_key.len())], api_secret.len(), api_passphrase.len());
-            submit_signed_order_to_clob(&req.signed_order, &session.wallet_address, &proxy_address, &api_key, &api_secret, &api_passphrase).await
+            submit_signed_order_to_clob(&req.signed_order, &auth.wallet_address, &proxy_address, &api_key, &api_secret, &api_passphrase).await
         }
         None => {
-            warn!("No API credentials found for wallet {}", session.wallet_address);
+            warn!("No API credentials found for wallet {}", auth.wallet_address);
             Err("No API credentials found. Please authenticate with Polymarket first.".to_string())
         }
     };
@@ -831,8 +745,8 @@ pub async fn execute_signed_trade(
     // Record the position regardless of CLOB result
     let position_id = state
         .db
-        .create_position_for_wallet(
-            &session.wallet_address,
+        .positions().create_position_for_wallet(
+            &auth.wallet_address,
             &req.market_id,
             &req.question,
             req.slug.as_deref(),
@@ -882,31 +796,9 @@ pub struct RecordPositionRequest {
 /// Record a position after browser submitted to CLOB
 pub async fn record_position(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<RecordPositionRequest>,
 ) -> Result<Json<TradeResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
     // Parse side
     let side = match req.side.to_lowercase().as_str() {
         "yes" => Side::Yes,
@@ -942,7 +834,7 @@ pub async fn record_position(
 
     info!(
         "Recording position for wallet {} - market: {}, side: {:?}, size: {}, order_id: {:?}",
-        session.wallet_address, req.market_id, side, size, req.order_id
+        auth.wallet_address, req.market_id, side, size, req.order_id
     );
 
     // Parse end_date from request
@@ -954,8 +846,8 @@ pub async fn record_position(
     // Record the position
     let position_id = state
         .db
-        .create_position_for_wallet(
-            &session.wallet_address,
+        .positions().create_position_for_wallet(
+            &auth.wallet_address,
             &req.market_id,
             &req.question,
             req.slug.as_deref(),
@@ -1127,36 +1019,14 @@ pub struct SubmitOrderResponse {
 /// This endpoint handles L2 authentication since crypto.subtle isn't available over HTTP
 pub async fn submit_sdk_order(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<SubmitSdkOrderRequest>,
 ) -> Result<Json<SubmitOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
     use hmac::{Hmac, Mac};
     use sha2::Sha256;
     type HmacSha256 = Hmac<Sha256>;
 
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
-    info!("Submitting SDK order for wallet {}", session.wallet_address);
+    info!("Submitting SDK order for wallet {}", auth.wallet_address);
     info!("Order: {:?}", req.signed_order);
 
     // Convert SDK order format to API format
@@ -1257,7 +1127,7 @@ pub async fn submit_sdk_order(
     let response = client
         .post("https://clob.polymarket.com/order")
         .header("Content-Type", "application/json")
-        .header("POLY_ADDRESS", &session.wallet_address)
+        .header("POLY_ADDRESS", &auth.wallet_address)
         .header("POLY_SIGNATURE", &signature)
         .header("POLY_TIMESTAMP", &timestamp)
         .header("POLY_API_KEY", &req.api_key)
@@ -1332,32 +1202,10 @@ pub struct EnableTradingResponse {
 /// Enable trading by setting USDC allowances via backend (handles L2 auth)
 pub async fn enable_trading(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<EnableTradingRequest>,
 ) -> Result<Json<EnableTradingResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
-    info!("Enabling trading for wallet {}", session.wallet_address);
+    info!("Enabling trading for wallet {}", auth.wallet_address);
 
     // Decode the base64url-encoded secret
     let secret_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
@@ -1388,7 +1236,7 @@ pub async fn enable_trading(
     // Enable COLLATERAL (USDC) allowance
     let collateral_result = set_allowance(
         &client,
-        &session.wallet_address,
+        &auth.wallet_address,
         &req.api_key,
         &req.api_passphrase,
         &secret_bytes,
@@ -1402,7 +1250,7 @@ pub async fn enable_trading(
     // Enable CONDITIONAL token allowance
     let conditional_result = set_allowance(
         &client,
-        &session.wallet_address,
+        &auth.wallet_address,
         &req.api_key,
         &req.api_passphrase,
         &secret_bytes,
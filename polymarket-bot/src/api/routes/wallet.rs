@@ -1,25 +1,28 @@
 //! Wallet API endpoints
 
+use crate::api::auth::{scope, Auth};
 use crate::api::server::AppState;
+use crate::db::{WithdrawalAddressRow, WithdrawalReservation};
+use crate::types::OnchainJobKind;
 use crate::services::safe_activation::{self, BuilderCredentials};
+use crate::services::{Notification, NotificationKind, WalletSigner};
+use crate::wallet::safeguards::{self, AllowedDestination, SafeguardViolation};
 use crate::wallet::{
     decrypt_private_key, encrypt_private_key,
     generate_wallet as create_wallet_keypair,
     wallet_from_private_key,
 };
-use alloy::primitives::keccak256;
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use alloy::primitives::{keccak256, Address, U256};
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use std::str::FromStr;
+use tracing::{error, info, warn};
 
 // USDC.e (bridged) contract address on Polygon - used by Polymarket
 const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
@@ -112,7 +115,7 @@ pub async fn generate_wallet(
     // Store wallet in database
     state
         .db
-        .create_wallet(&wallet.address, encrypted_key.as_ref())
+        .auth().create_wallet(&wallet.address, encrypted_key.as_ref())
         .await
         .map_err(|e| {
             (
@@ -124,7 +127,7 @@ pub async fn generate_wallet(
         })?;
 
     // Create session
-    let session = state.db.create_session(&wallet.address).await.map_err(|e| {
+    let session = state.db.auth().create_session(&wallet.address).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -156,7 +159,7 @@ pub async fn import_wallet(
     })?;
 
     // Check if wallet already exists
-    let existing = state.db.get_wallet(&address).await.map_err(|e| {
+    let existing = state.db.auth().get_wallet(&address).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -184,7 +187,7 @@ pub async fn import_wallet(
         // Store wallet
         state
             .db
-            .create_wallet(&address, encrypted_key.as_ref())
+            .auth().create_wallet(&address, encrypted_key.as_ref())
             .await
             .map_err(|e| {
                 (
@@ -197,7 +200,7 @@ pub async fn import_wallet(
     }
 
     // Create session
-    let session = state.db.create_session(&address).await.map_err(|e| {
+    let session = state.db.auth().create_session(&address).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -220,7 +223,7 @@ pub async fn unlock_wallet(
     // Get encrypted key
     let encrypted_key = state
         .db
-        .get_encrypted_key(&req.address)
+        .auth().get_encrypted_key(&req.address)
         .await
         .map_err(|e| {
             (
@@ -250,7 +253,7 @@ pub async fn unlock_wallet(
     })?;
 
     // Create session
-    let session = state.db.create_session(&req.address).await.map_err(|e| {
+    let session = state.db.auth().create_session(&req.address).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -260,7 +263,7 @@ pub async fn unlock_wallet(
     })?;
 
     // Update activity
-    let _ = state.db.update_wallet_activity(&req.address).await;
+    let _ = state.db.auth().update_wallet_activity(&req.address).await;
 
     Ok(Json(UnlockWalletResponse {
         session_token: session.id,
@@ -293,33 +296,13 @@ struct JsonRpcResponse {
 pub async fn get_balance(
     State(state): State<AppState>,
     Query(query): Query<BalanceQuery>,
-    auth: Option<TypedHeader<Authorization<Bearer>>>,
+    auth: Option<Auth>,
 ) -> Result<Json<WalletBalanceResponse>, (StatusCode, Json<ErrorResponse>)> {
     // Get address from query param or session
     let address = if let Some(addr) = query.address {
         addr.to_lowercase()
-    } else if let Some(TypedHeader(auth_header)) = auth {
-        let session = state
-            .db
-            .get_session(auth_header.token())
-            .await
-            .map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse {
-                        error: format!("Database error: {}", e),
-                    }),
-                )
-            })?
-            .ok_or_else(|| {
-                (
-                    StatusCode::UNAUTHORIZED,
-                    Json(ErrorResponse {
-                        error: "Invalid or expired session".to_string(),
-                    }),
-                )
-            })?;
-        session.wallet_address
+    } else if let Some(auth) = auth {
+        auth.wallet_address
     } else {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -353,7 +336,7 @@ pub async fn get_balance(
         .unwrap_or_else(|_| "0.00".to_string());
 
     // For generated wallets (those with an encrypted key), also return Safe balance
-    let (safe_address, safe_usdc_balance) = match state.db.get_encrypted_key(&address).await {
+    let (safe_address, safe_usdc_balance) = match state.db.auth().get_encrypted_key(&address).await {
         Ok(Some(_)) => {
             // Generated wallet — derive Safe address and fetch its balance
             match crate::services::safe_proxy::derive_safe_wallet(&address) {
@@ -488,7 +471,7 @@ pub async fn connect_external_wallet(
     }
 
     // Check if wallet exists, create if not
-    let existing = state.db.get_wallet(&address).await.map_err(|e| {
+    let existing = state.db.auth().get_wallet(&address).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -500,7 +483,7 @@ pub async fn connect_external_wallet(
     if existing.is_none() {
         state
             .db
-            .create_wallet(&address, None) // No encrypted key for external wallets
+            .auth().create_wallet(&address, None) // No encrypted key for external wallets
             .await
             .map_err(|e| {
                 (
@@ -513,7 +496,7 @@ pub async fn connect_external_wallet(
     }
 
     // Create session
-    let session = state.db.create_session(&address).await.map_err(|e| {
+    let session = state.db.auth().create_session(&address).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -543,35 +526,15 @@ pub struct ExportPrivateKeyResponse {
 /// Export private key (requires password verification)
 pub async fn export_private_key(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Session>,
     Json(req): Json<ExportPrivateKeyRequest>,
 ) -> Result<Json<ExportPrivateKeyResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Get session
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
+    check_password_cooldown(&state, &auth.wallet_address).await?;
 
     // Get encrypted key
     let encrypted_key = state
         .db
-        .get_encrypted_key(&session.wallet_address)
+        .auth().get_encrypted_key(&auth.wallet_address)
         .await
         .map_err(|e| {
             (
@@ -600,51 +563,32 @@ pub async fn export_private_key(
         )
     })?;
 
+    notify_security_event(&state, &auth.wallet_address, "Private key exported", Vec::new());
+
     Ok(Json(ExportPrivateKeyResponse { private_key }))
 }
 
 /// Disconnect wallet - stops the User WebSocket and clears session resources
 pub async fn disconnect_wallet(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Session>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(auth.token())
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Database error: {}", e),
-                }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid or expired session".to_string(),
-                }),
-            )
-        })?;
-
-    let wallet_address = &session.wallet_address;
+    let wallet_address = &auth.wallet_address;
     tracing::info!("Disconnecting wallet {}", wallet_address);
 
     // Stop the User WebSocket for this wallet (frees resources)
     state.stop_user_ws(wallet_address).await;
 
-    // Clear the key from in-memory store
-    state.key_store.remove_key(wallet_address).await;
+    // Clear the key from in-memory store, unless auto-trading or Mint Maker still signs with it
+    if let Err(e) = state.release_key(wallet_address).await {
+        warn!("Failed to release key for {}: {}", wallet_address, e);
+    }
 
     Ok(Json(serde_json::json!({ "status": "disconnected", "wallet": wallet_address })))
 }
 
 // ── Deposit / Withdraw endpoints ────────────────────────────────────────
 
-const POLYGON_CHAIN_ID: u64 = 137;
-
 /// Deposit request (EOA → Safe)
 #[derive(Debug, Deserialize)]
 pub struct DepositRequest {
@@ -656,51 +600,41 @@ pub struct DepositRequest {
 #[derive(Debug, Serialize)]
 pub struct DepositResponse {
     pub tx_hash: String,
+    /// On-chain job tracking the deposit's confirmation
+    pub job_id: Option<i64>,
     pub safe_address: String,
     pub amount: String,
 }
 
-/// Withdraw request (Safe → EOA)
+/// Withdraw request (Safe → own EOA, or an allowlisted address)
 #[derive(Debug, Deserialize)]
 pub struct WithdrawRequest {
     pub password: String,
     pub amount: String,
+    /// Destination; defaults to the wallet's own EOA
+    pub to_address: Option<String>,
 }
 
 /// Withdraw response
 #[derive(Debug, Serialize)]
 pub struct WithdrawResponse {
     pub transaction_id: String,
+    /// On-chain job tracking the relay transaction
+    pub job_id: Option<i64>,
     pub safe_address: String,
+    pub to_address: String,
     pub amount: String,
 }
 
-/// Helper: validate session + decrypt private key → (wallet_address, PrivateKeySigner)
+/// Helper: decrypt the wallet's private key → (wallet_address, one-off WalletSigner)
 async fn decrypt_signer(
     state: &AppState,
-    token: &str,
+    wallet_address: &str,
     password: &str,
-) -> Result<(String, PrivateKeySigner), (StatusCode, Json<ErrorResponse>)> {
-    let session = state
-        .db
-        .get_session(token)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse { error: format!("Database error: {}", e) }),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse { error: "Invalid or expired session".to_string() }),
-            )
-        })?;
-
+) -> Result<(String, WalletSigner), (StatusCode, Json<ErrorResponse>)> {
     let encrypted_key = state
         .db
-        .get_encrypted_key(&session.wallet_address)
+        .auth().get_encrypted_key(wallet_address)
         .await
         .map_err(|e| {
             (
@@ -724,8 +658,8 @@ async fn decrypt_signer(
         )
     })?;
 
-    let signer: PrivateKeySigner = private_key
-        .parse()
+    let signer = WalletSigner::from_private_key(wallet_address, zeroize::Zeroizing::new(private_key))
+        .await
         .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -733,7 +667,7 @@ async fn decrypt_signer(
             )
         })?;
 
-    Ok((session.wallet_address, signer))
+    Ok((wallet_address.to_string(), signer))
 }
 
 /// Helper: build BuilderCredentials from config
@@ -766,74 +700,13 @@ fn build_transfer_data(to: &[u8; 20], amount_raw: u128) -> Vec<u8> {
     data
 }
 
-// ── RLP encoding helpers (legacy EIP-155 transactions) ─────────────────
-
-fn rlp_encode_uint(val: u64) -> Vec<u8> {
-    if val == 0 {
-        return vec![0x80]; // empty string
-    }
-    let bytes = val.to_be_bytes();
-    let start = bytes.iter().position(|&b| b != 0).unwrap_or(8);
-    let trimmed = &bytes[start..];
-    if trimmed.len() == 1 && trimmed[0] < 0x80 {
-        trimmed.to_vec()
-    } else {
-        let mut out = vec![0x80 + trimmed.len() as u8];
-        out.extend_from_slice(trimmed);
-        out
-    }
-}
-
-fn rlp_encode_bytes(data: &[u8]) -> Vec<u8> {
-    if data.is_empty() {
-        return vec![0x80];
-    }
-    if data.len() == 1 && data[0] < 0x80 {
-        return data.to_vec();
-    }
-    if data.len() < 56 {
-        let mut out = vec![0x80 + data.len() as u8];
-        out.extend_from_slice(data);
-        out
-    } else {
-        let len_bytes = {
-            let b = data.len().to_be_bytes();
-            let start = b.iter().position(|&x| x != 0).unwrap_or(b.len());
-            b[start..].to_vec()
-        };
-        let mut out = vec![0xb7 + len_bytes.len() as u8];
-        out.extend_from_slice(&len_bytes);
-        out.extend_from_slice(data);
-        out
-    }
-}
-
-fn rlp_encode_list(items: &[Vec<u8>]) -> Vec<u8> {
-    let payload: Vec<u8> = items.iter().flat_map(|i| i.iter().copied()).collect();
-    if payload.len() < 56 {
-        let mut out = vec![0xc0 + payload.len() as u8];
-        out.extend_from_slice(&payload);
-        out
-    } else {
-        let len_bytes = {
-            let b = payload.len().to_be_bytes();
-            let start = b.iter().position(|&x| x != 0).unwrap_or(b.len());
-            b[start..].to_vec()
-        };
-        let mut out = vec![0xf7 + len_bytes.len() as u8];
-        out.extend_from_slice(&len_bytes);
-        out.extend_from_slice(&payload);
-        out
-    }
-}
-
 /// POST /api/wallet/deposit — Transfer USDC from EOA to Safe
 pub async fn deposit_to_safe(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Trade>,
     Json(req): Json<DepositRequest>,
 ) -> Result<Json<DepositResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let (wallet_address, signer) = decrypt_signer(&state, auth.token(), &req.password).await?;
+    let (wallet_address, signer) = decrypt_signer(&state, &auth.wallet_address, &req.password).await?;
 
     // Parse amount (USDC has 6 decimals)
     let amount_f: f64 = req.amount.parse().map_err(|_| {
@@ -852,9 +725,9 @@ pub async fn deposit_to_safe(
 
     // Auto-activate Safe if builder creds available
     if let Some(bcreds) = get_builder_creds(&state) {
-        match safe_activation::ensure_safe_activated(&signer, &bcreds).await {
+        match safe_activation::ensure_safe_activated(&signer, &bcreds, &state.onchain_jobs).await {
             Ok(addr) => info!("Deposit: Safe activated at {}", addr),
-            Err(e) => tracing::warn!("Deposit: Safe activation warning: {}", e),
+            Err(e) => warn!("Deposit: Safe activation warning: {}", e),
         }
     }
 
@@ -865,135 +738,40 @@ pub async fn deposit_to_safe(
         .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Address length error".to_string() })))?;
     let calldata = build_transfer_data(&safe_addr_bytes, amount_raw);
 
-    // Fetch nonce and gas price via RPC
-    let client = reqwest::Client::new();
-    let rpc_url = &state.config.polygon_rpc_url;
-
-    let nonce_resp: JsonRpcResponse = client
-        .post(rpc_url)
-        .json(&JsonRpcRequest {
-            jsonrpc: "2.0",
-            method: "eth_getTransactionCount",
-            params: serde_json::json!([&wallet_address, "latest"]),
-            id: 1,
-        })
-        .send()
+    // Estimate, sign and send as an EIP-1559 transaction from the EOA
+    let usdc_contract = Address::from_str(USDC_ADDRESS).expect("valid USDC address");
+    let pending = state
+        .polygon_tx
+        .send(&signer, usdc_contract, U256::ZERO, calldata)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("RPC error: {}", e) })))?
-        .json()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: format!("Transaction failed: {:#}", e) })))?;
+    let tx_hash = pending.hash.clone();
+
+    // Confirmation (and speed-up if it gets stuck) is driven by the on-chain job worker
+    let description = format!("Deposit {} USDC to Safe", req.amount);
+    let job_id = match state
+        .onchain_jobs
+        .track_tx(&wallet_address, OnchainJobKind::Deposit, &description, Some(&safe_address), &pending)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("RPC parse error: {}", e) })))?;
-    let nonce = u64::from_str_radix(
-        nonce_resp.result.as_deref().unwrap_or("0x0").trim_start_matches("0x"),
-        16,
-    )
-    .unwrap_or(0);
-
-    let gas_resp: JsonRpcResponse = client
-        .post(rpc_url)
-        .json(&JsonRpcRequest {
-            jsonrpc: "2.0",
-            method: "eth_gasPrice",
-            params: serde_json::json!([]),
-            id: 2,
-        })
-        .send()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("RPC error: {}", e) })))?
-        .json()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("RPC parse error: {}", e) })))?;
-    let gas_price = u64::from_str_radix(
-        gas_resp.result.as_deref().unwrap_or("0x0").trim_start_matches("0x"),
-        16,
-    )
-    .unwrap_or(30_000_000_000); // 30 gwei fallback
-
-    let gas_limit: u64 = 80_000; // ERC-20 transfer
-    let usdc_contract: [u8; 20] = hex::decode(USDC_ADDRESS.trim_start_matches("0x"))
-        .unwrap()
-        .try_into()
-        .unwrap();
-
-    // RLP-encode unsigned legacy tx for EIP-155 signing:
-    // [nonce, gasPrice, gasLimit, to, value=0, data, chainId, 0, 0]
-    let unsigned_items = vec![
-        rlp_encode_uint(nonce),
-        rlp_encode_uint(gas_price),
-        rlp_encode_uint(gas_limit),
-        rlp_encode_bytes(&usdc_contract),
-        rlp_encode_uint(0), // value = 0 (we're calling transfer, not sending MATIC)
-        rlp_encode_bytes(&calldata),
-        rlp_encode_uint(POLYGON_CHAIN_ID),
-        rlp_encode_uint(0),
-        rlp_encode_uint(0),
-    ];
-    let unsigned_rlp = rlp_encode_list(&unsigned_items);
-    let tx_hash_unsigned = keccak256(&unsigned_rlp);
-
-    // Sign the hash
-    let sig = signer
-        .sign_hash(&tx_hash_unsigned)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Signing error: {}", e) })))?;
-    let sig_bytes = sig.as_bytes(); // 65 bytes: r(32) + s(32) + v(1)
-
-    // Recover v for EIP-155: v = recovery_id + chainId*2 + 35
-    // sig.as_bytes() returns v as 27/28 (legacy) or 0/1 (recovery id)
-    let recovery_id = match sig_bytes[64] {
-        0 | 1 => sig_bytes[64] as u64,
-        27 => 0u64,
-        28 => 1u64,
-        other => (other - 27) as u64,
+    {
+        Ok(id) => Some(id),
+        Err(e) => {
+            warn!("Deposit {} sent but job tracking failed: {}", tx_hash, e);
+            None
+        }
     };
-    let v_eip155 = recovery_id + POLYGON_CHAIN_ID * 2 + 35;
-
-    // RLP-encode signed tx: [nonce, gasPrice, gasLimit, to, value, data, v, r, s]
-    let signed_items = vec![
-        rlp_encode_uint(nonce),
-        rlp_encode_uint(gas_price),
-        rlp_encode_uint(gas_limit),
-        rlp_encode_bytes(&usdc_contract),
-        rlp_encode_uint(0),
-        rlp_encode_bytes(&calldata),
-        rlp_encode_uint(v_eip155),
-        rlp_encode_bytes(&sig_bytes[..32]),  // r
-        rlp_encode_bytes(&sig_bytes[32..64]), // s
-    ];
-    let signed_rlp = rlp_encode_list(&signed_items);
-    let raw_tx_hex = format!("0x{}", hex::encode(&signed_rlp));
-
-    // Send via eth_sendRawTransaction
-    let send_resp: serde_json::Value = client
-        .post(rpc_url)
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "eth_sendRawTransaction",
-            "params": [raw_tx_hex],
-            "id": 3
-        }))
-        .send()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("RPC error: {}", e) })))?
-        .json()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("RPC parse error: {}", e) })))?;
-
-    if let Some(err) = send_resp.get("error") {
-        let msg = err.get("message").and_then(|v| v.as_str()).unwrap_or("Unknown RPC error");
-        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: format!("Transaction failed: {}", msg) })));
-    }
-
-    let tx_hash = send_resp
-        .get("result")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown")
-        .to_string();
 
     info!("Deposit tx sent: {} ({} USDC -> Safe {})", tx_hash, req.amount, safe_address);
 
+    if let Ok(amount) = Decimal::from_str(&req.amount) {
+        if let Err(e) = state.db.record_deposit(&wallet_address, amount, &tx_hash).await {
+            warn!("Deposit {} sent but ledger entry failed: {}", tx_hash, e);
+        }
+    }
+
     Ok(Json(DepositResponse {
         tx_hash,
+        job_id,
         safe_address,
         amount: req.amount,
     }))
@@ -1002,10 +780,10 @@ pub async fn deposit_to_safe(
 /// POST /api/wallet/withdraw — Transfer USDC from Safe to EOA via relay
 pub async fn withdraw_from_safe(
     State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    auth: Auth<scope::Withdraw>,
     Json(req): Json<WithdrawRequest>,
 ) -> Result<Json<WithdrawResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let (wallet_address, signer) = decrypt_signer(&state, auth.token(), &req.password).await?;
+    let (wallet_address, signer) = decrypt_signer(&state, &auth.wallet_address, &req.password).await?;
 
     // Parse amount
     let amount_f: f64 = req.amount.parse().map_err(|_| {
@@ -1015,6 +793,25 @@ pub async fn withdraw_from_safe(
         return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Amount must be positive".to_string() })));
     }
     let amount_raw = (amount_f * 1_000_000.0) as u128;
+    let amount = Decimal::from_str(&req.amount).map_err(|_| {
+        (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Invalid amount".to_string() }))
+    })?;
+
+    let to_address = req
+        .to_address
+        .as_deref()
+        .map(|a| a.trim().to_lowercase())
+        .unwrap_or_else(|| wallet_address.to_lowercase());
+    let to: Address = to_address.parse().map_err(|_| {
+        (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Invalid destination address".to_string() }))
+    })?;
+
+    // Safeguards: password cool-down, allowlisted + unlocked destination (the rolling daily
+    // limit is checked when the withdrawal is reserved, just before submission)
+    check_password_cooldown(&state, &wallet_address).await?;
+    let allowlist = allowed_destinations(&state, &wallet_address).await?;
+    safeguards::check_destination(&to_address, &wallet_address, &allowlist, Utc::now())
+        .map_err(safeguard_error)?;
 
     // Derive Safe address
     let safe_address = crate::services::safe_proxy::derive_safe_wallet(&wallet_address)
@@ -1022,12 +819,8 @@ pub async fn withdraw_from_safe(
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Failed to derive Safe: {}", e) }))
         })?;
 
-    // Build USDC transfer(eoa, amount) calldata
-    let eoa_bytes: [u8; 20] = hex::decode(wallet_address.trim_start_matches("0x"))
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Hex error: {}", e) })))?
-        .try_into()
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Address length error".to_string() })))?;
-    let calldata = build_transfer_data(&eoa_bytes, amount_raw);
+    // Build USDC transfer(to, amount) calldata
+    let calldata = build_transfer_data(&to.0 .0, amount_raw);
 
     // Builder credentials required for relay
     let builder_creds = get_builder_creds(&state).ok_or_else(|| {
@@ -1045,7 +838,24 @@ pub async fn withdraw_from_safe(
         (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Address parse error: {}", e) }))
     })?;
 
-    let tx_id = safe_activation::execute_safe_transaction(
+    // Reserve the amount against the daily limit in one transaction, so concurrent
+    // requests can't both pass it
+    let limit = withdraw_limit(&state, &wallet_address).await?;
+    let since = Utc::now() - Duration::hours(safeguards::LIMIT_WINDOW_HOURS);
+    let withdrawal_id = match state
+        .db
+        .withdrawals()
+        .reserve_withdrawal(&wallet_address, &to_address, amount, limit, since)
+        .await
+        .map_err(db_error)?
+    {
+        WithdrawalReservation::Reserved { id } => id,
+        WithdrawalReservation::OverLimit { withdrawn } => {
+            return Err(safeguard_error(safeguards::daily_limit_exceeded(withdrawn, limit)));
+        }
+    };
+
+    let tx_id = match safe_activation::execute_safe_transaction(
         &signer,
         &client,
         &builder_creds,
@@ -1054,15 +864,373 @@ pub async fn withdraw_from_safe(
         &calldata,
     )
     .await
-    .map_err(|e| {
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Relay error: {}", e) }))
-    })?;
+    {
+        Ok(tx_id) => tx_id,
+        Err(e) => {
+            if let Err(fail_err) = state.db.withdrawals().fail_withdrawal(withdrawal_id).await {
+                warn!("Failed to release withdrawal {} after relay error: {}", withdrawal_id, fail_err);
+            }
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Relay error: {}", e) })));
+        }
+    };
 
-    info!("Withdraw tx submitted: {} ({} USDC from Safe {} -> EOA {})", tx_id, req.amount, safe_address, wallet_address);
+    info!("Withdraw tx submitted: {} ({} USDC from Safe {} -> {})", tx_id, req.amount, safe_address, to_address);
+
+    notify_security_event(
+        &state,
+        &wallet_address,
+        "Withdrawal submitted",
+        vec![
+            ("Amount", format!("{} USDC", req.amount)),
+            ("To", to_address.clone()),
+            ("Transaction", tx_id.clone()),
+        ],
+    );
+
+    // The reservation stays pending (and keeps counting against the limit) if this fails
+    if let Err(e) = state.db.withdrawals().record_withdrawal(withdrawal_id, &tx_id).await {
+        error!("Withdraw {} submitted but recording it failed: {}", tx_id, e);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Withdrawal {} was submitted but recording it failed: {}", tx_id, e),
+            }),
+        ));
+    }
+
+    let description = format!("Withdraw {} USDC from Safe", req.amount);
+    let job_id = match state
+        .onchain_jobs
+        .track_relay(&wallet_address, OnchainJobKind::Withdraw, &description, Some(&safe_address), &tx_id)
+        .await
+    {
+        Ok(id) => Some(id),
+        Err(e) => {
+            warn!("Withdraw {} submitted but job tracking failed: {}", tx_id, e);
+            None
+        }
+    };
 
     Ok(Json(WithdrawResponse {
         transaction_id: tx_id,
+        job_id,
         safe_address,
+        to_address,
         amount: req.amount,
     }))
 }
+
+// ── Withdrawal safeguards ───────────────────────────────────────────────
+
+/// Map a safeguard violation to a 403 response
+fn safeguard_error(violation: SafeguardViolation) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::FORBIDDEN, Json(ErrorResponse { error: violation.to_string() }))
+}
+
+fn db_error(e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Database error: {}", e) }))
+}
+
+/// Refuse while the wallet is in its post-password-change cool-down
+async fn check_password_cooldown(
+    state: &AppState,
+    wallet_address: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let changed_at = state.db.auth().get_password_changed_at(wallet_address).await.map_err(db_error)?;
+    safeguards::check_password_cooldown(
+        changed_at,
+        Duration::hours(state.config.password_change_cooldown_hours),
+        Utc::now(),
+    )
+    .map_err(safeguard_error)
+}
+
+/// Effective daily limit: the wallet's own (once any raise unlocked), capped at the server-wide one
+async fn withdraw_limit(state: &AppState, wallet_address: &str) -> Result<Decimal, (StatusCode, Json<ErrorResponse>)> {
+    Ok(state
+        .db
+        .auth().get_withdraw_limit(wallet_address)
+        .await
+        .map_err(db_error)?
+        .effective(state.config.withdraw_daily_limit, Utc::now()))
+}
+
+/// (effective daily limit, USDC withdrawn in the last 24h)
+async fn withdraw_usage(
+    state: &AppState,
+    wallet_address: &str,
+) -> Result<(Decimal, Decimal), (StatusCode, Json<ErrorResponse>)> {
+    let limit = withdraw_limit(state, wallet_address).await?;
+    let since = Utc::now() - Duration::hours(safeguards::LIMIT_WINDOW_HOURS);
+    let withdrawn = state.db.withdrawals().get_withdrawn_since(wallet_address, since).await.map_err(db_error)?;
+    Ok((limit, withdrawn))
+}
+
+async fn allowed_destinations(
+    state: &AppState,
+    wallet_address: &str,
+) -> Result<Vec<AllowedDestination>, (StatusCode, Json<ErrorResponse>)> {
+    let rows = state.db.withdrawals().get_withdrawal_addresses(wallet_address).await.map_err(db_error)?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let active_at = DateTime::parse_from_rfc3339(&row.active_at).ok()?.with_timezone(&Utc);
+            Some(AllowedDestination { address: row.address, active_at })
+        })
+        .collect())
+}
+
+/// Verify the wallet password without keeping the decrypted key around
+async fn verify_password(
+    state: &AppState,
+    wallet_address: &str,
+    password: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    decrypt_signer(state, wallet_address, password).await.map(|_| ())
+}
+
+/// Log a security-relevant wallet event and notify the operator webhook and the wallet's channels
+fn notify_security_event(state: &AppState, wallet_address: &str, title: &str, details: Vec<(&'static str, String)>) {
+    warn!("[Security] {} for wallet {} {:?}", title, wallet_address, details);
+
+    let notification = details.into_iter().fold(
+        Notification::new(NotificationKind::Security, Some(wallet_address), title),
+        |n, (name, value)| n.field(&name.to_lowercase().replace(' ', "_"), value),
+    );
+    state.notifier.notify(notification);
+}
+
+/// Withdrawal safeguard settings response
+#[derive(Debug, Serialize)]
+pub struct WithdrawSettingsResponse {
+    /// Effective daily limit (USDC, rolling 24h)
+    pub daily_limit: String,
+    /// Server-wide maximum daily limit
+    pub max_daily_limit: String,
+    pub withdrawn_last_24h: String,
+    pub remaining_today: String,
+    /// Raised daily limit waiting out the time-lock
+    pub pending_daily_limit: Option<String>,
+    pub pending_limit_active_at: Option<String>,
+    /// Withdrawals and key export are locked until this time after a password change
+    pub locked_until: Option<String>,
+    /// Hours before a newly added address or a raised limit takes effect
+    pub address_delay_hours: i64,
+    pub allowlist: Vec<WithdrawalAddressRow>,
+}
+
+/// GET /api/wallet/withdraw/settings — limits, usage, cool-down and allowlist
+pub async fn get_withdraw_settings(
+    State(state): State<AppState>,
+    auth: Auth,
+) -> Result<Json<WithdrawSettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (limit, withdrawn) = withdraw_usage(&state, &auth.wallet_address).await?;
+    let changed_at = state.db.auth().get_password_changed_at(&auth.wallet_address).await.map_err(db_error)?;
+    let locked_until = changed_at
+        .map(|t| t + Duration::hours(state.config.password_change_cooldown_hours))
+        .filter(|until| *until > Utc::now())
+        .map(|until| until.to_rfc3339());
+    let allowlist = state.db.withdrawals().get_withdrawal_addresses(&auth.wallet_address).await.map_err(db_error)?;
+    let setting = state.db.auth().get_withdraw_limit(&auth.wallet_address).await.map_err(db_error)?;
+    let pending = setting.waiting(Utc::now());
+
+    Ok(Json(WithdrawSettingsResponse {
+        daily_limit: limit.to_string(),
+        max_daily_limit: state.config.withdraw_daily_limit.to_string(),
+        withdrawn_last_24h: withdrawn.to_string(),
+        remaining_today: (limit - withdrawn).max(Decimal::ZERO).to_string(),
+        pending_daily_limit: pending
+            .map(|p| p.limit.unwrap_or(state.config.withdraw_daily_limit).to_string()),
+        pending_limit_active_at: pending.map(|p| p.active_at.to_rfc3339()),
+        locked_until,
+        address_delay_hours: state.config.withdraw_address_delay_hours,
+        allowlist,
+    }))
+}
+
+/// Add allowlist address request
+#[derive(Debug, Deserialize)]
+pub struct AddWithdrawAddressRequest {
+    pub password: String,
+    pub address: String,
+    pub label: Option<String>,
+}
+
+/// POST /api/wallet/withdraw/allowlist — allowlist a destination (usable after the time-lock)
+pub async fn add_withdraw_address(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Json(req): Json<AddWithdrawAddressRequest>,
+) -> Result<Json<WithdrawalAddressRow>, (StatusCode, Json<ErrorResponse>)> {
+    let address: Address = req.address.trim().parse().map_err(|_| {
+        (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Invalid address".to_string() }))
+    })?;
+    verify_password(&state, &auth.wallet_address, &req.password).await?;
+
+    let address = format!("{:?}", address);
+    let active_at = Utc::now() + Duration::hours(state.config.withdraw_address_delay_hours);
+    let label = req.label.as_deref().map(str::trim).filter(|l| !l.is_empty());
+    let row = state
+        .db.withdrawals()
+        .add_withdrawal_address(&auth.wallet_address, &address, label, active_at)
+        .await
+        .map_err(db_error)?;
+
+    notify_security_event(
+        &state,
+        &auth.wallet_address,
+        "Withdrawal address added",
+        vec![("Address", row.address.clone()), ("Usable from", row.active_at.clone())],
+    );
+
+    Ok(Json(row))
+}
+
+/// DELETE /api/wallet/withdraw/allowlist/:id — remove a destination immediately
+pub async fn remove_withdraw_address(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let removed = state
+        .db.withdrawals()
+        .remove_withdrawal_address(&auth.wallet_address, id)
+        .await
+        .map_err(db_error)?;
+    if !removed {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse { error: "Address not found".to_string() })));
+    }
+
+    notify_security_event(&state, &auth.wallet_address, "Withdrawal address removed", vec![("Id", id.to_string())]);
+
+    Ok(Json(serde_json::json!({ "removed": id })))
+}
+
+/// Set daily limit request (omit `daily_limit` to use the server maximum)
+#[derive(Debug, Deserialize)]
+pub struct SetWithdrawLimitRequest {
+    pub password: String,
+    pub daily_limit: Option<String>,
+}
+
+/// PUT /api/wallet/withdraw/limit — set the wallet's own daily limit (at most the server maximum).
+/// Lowering it applies at once; raising it waits out the allowlist time-lock.
+pub async fn set_withdraw_limit(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Json(req): Json<SetWithdrawLimitRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = match req.daily_limit.as_deref() {
+        Some(l) => {
+            let limit = Decimal::from_str(l.trim()).map_err(|_| {
+                (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Invalid limit".to_string() }))
+            })?;
+            if limit < Decimal::ZERO || limit > state.config.withdraw_daily_limit {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        error: format!("Limit must be between 0 and {}", state.config.withdraw_daily_limit),
+                    }),
+                ));
+            }
+            Some(limit)
+        }
+        None => None,
+    };
+    verify_password(&state, &auth.wallet_address, &req.password).await?;
+
+    let now = Utc::now();
+    let server_limit = state.config.withdraw_daily_limit;
+    let setting = state
+        .db
+        .auth().get_withdraw_limit(&auth.wallet_address)
+        .await
+        .map_err(db_error)?
+        .request(limit, server_limit, Duration::hours(state.config.withdraw_address_delay_hours), now);
+    state
+        .db
+        .auth().set_withdraw_limit(&auth.wallet_address, &setting)
+        .await
+        .map_err(db_error)?;
+
+    let effective = setting.effective(server_limit, now);
+    let requested = limit.unwrap_or(server_limit);
+    match setting.waiting(now) {
+        Some(pending) => notify_security_event(
+            &state,
+            &auth.wallet_address,
+            "Daily withdrawal limit raise requested",
+            vec![
+                ("Limit", format!("{} USDC", requested)),
+                ("Effective from", pending.active_at.to_rfc3339()),
+            ],
+        ),
+        None => notify_security_event(
+            &state,
+            &auth.wallet_address,
+            "Daily withdrawal limit changed",
+            vec![("Limit", format!("{} USDC", effective))],
+        ),
+    }
+
+    Ok(Json(serde_json::json!({
+        "daily_limit": effective.to_string(),
+        "pending_daily_limit": setting.waiting(now).map(|_| requested.to_string()),
+        "pending_limit_active_at": setting.waiting(now).map(|p| p.active_at.to_rfc3339()),
+    })))
+}
+
+/// Change password request
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// POST /api/wallet/change-password — re-encrypt the stored key; starts the withdrawal cool-down
+pub async fn change_password(
+    State(state): State<AppState>,
+    auth: Auth<scope::Session>,
+    Json(req): Json<ChangePasswordRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    if req.new_password.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "New password is required".to_string() })));
+    }
+
+    let encrypted_key = state
+        .db
+        .auth().get_encrypted_key(&auth.wallet_address)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse { error: "No private key stored — this may be an external wallet".to_string() }),
+            )
+        })?;
+
+    let private_key = zeroize::Zeroizing::new(
+        decrypt_private_key(&encrypted_key, &req.current_password).map_err(|_| {
+            (StatusCode::UNAUTHORIZED, Json(ErrorResponse { error: "Invalid password".to_string() }))
+        })?,
+    );
+    let reencrypted = encrypt_private_key(&private_key, &req.new_password).map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: format!("Encryption failed: {}", e) }))
+    })?;
+
+    state
+        .db
+        .auth().update_encrypted_key(&auth.wallet_address, &reencrypted)
+        .await
+        .map_err(db_error)?;
+
+    let locked_until = Utc::now() + Duration::hours(state.config.password_change_cooldown_hours);
+    notify_security_event(
+        &state,
+        &auth.wallet_address,
+        "Password changed",
+        vec![("Withdrawals locked until", locked_until.to_rfc3339())],
+    );
+
+    Ok(Json(serde_json::json!({ "locked_until": locked_until.to_rfc3339() })))
+}
@@ -1,13 +1,18 @@
 //! Axum server setup and configuration
 
+use crate::api::auth::ApiTokenLimiter;
 use crate::api::routes;
 use crate::api::ws::{ws_handler, WalletBalanceUpdate};
-use crate::services::{KeyStore, McStatusUpdate, MintMakerStatusUpdate, Metrics, OrderEvent, PriceUpdate, PriceUpdateTx, RateLimiter, TickSizeCache, UserWebSocket};
-use crate::types::{DisputeAlert, Opportunity, TrackedMarket};
+use crate::services::safe_activation::BuilderCredentials;
+use crate::services::{ClobClientPool, KeyStore, KeyVault, LeaderElection, McStatusUpdate, MintMakerStatusUpdate, Metrics, Notifier, OnchainJobTracker, OrderEvent, PolygonTxService, PriceCache, PriceUpdate, PriceUpdateTx, RateLimiter, Reporter, TickSizeCache, UserWebSocket};
+use crate::market_index::MarketsUpdate;
+use crate::types::{DisputeAlert, NegRiskEvent, OnchainJob, Opportunity, ResolutionOutcome};
 use crate::{Config, Database, Scanner, StrategyRunner};
 use anyhow::Result;
 use axum::{
-    http::{header, Method},
+    extract::State,
+    http::{header, Method, StatusCode},
+    Json,
     routing::{get, post},
     Router,
 };
@@ -17,7 +22,7 @@ use tokio::sync::{broadcast, Mutex, RwLock, watch};
 use tokio::task::JoinHandle;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Scan status info for frontend progress bar
 #[derive(Debug, Clone)]
@@ -39,6 +44,8 @@ pub struct AppState {
     pub opportunity_tx: broadcast::Sender<Vec<Opportunity>>,
     /// Broadcast channel for real-time price updates
     pub price_tx: PriceUpdateTx,
+    /// Latest price per token (fed from price_tx), used to mark holdings to market
+    pub price_cache: Arc<PriceCache>,
     /// Broadcast channel for scan status updates
     pub scan_status_tx: broadcast::Sender<ScanStatus>,
     /// Timestamp of last completed scan (ms)
@@ -55,6 +62,14 @@ pub struct AppState {
     pub tick_size_cache: Arc<TickSizeCache>,
     /// Rate limiter for CLOB API calls
     pub rate_limiter: Arc<RateLimiter>,
+    /// Per-wallet authenticated CLOB clients (shared by trade routes and auto-trading services)
+    pub clob_pool: Arc<ClobClientPool>,
+    /// EIP-1559 transaction sender for direct EOA transactions (deposits)
+    pub polygon_tx: Arc<PolygonTxService>,
+    /// Persisted queue of on-chain jobs (relay + direct txs), driven by a single worker
+    pub onchain_jobs: Arc<OnchainJobTracker>,
+    /// Broadcast channel for position resolutions (feeds the auto redeemer)
+    pub resolution_tx: broadcast::Sender<ResolutionOutcome>,
     /// Broadcast channel for order events from User Channel WebSocket
     pub order_event_tx: broadcast::Sender<OrderEvent>,
     /// Metrics collector
@@ -63,8 +78,8 @@ pub struct AppState {
     pub mc_tx: broadcast::Sender<McStatusUpdate>,
     /// Cached MC status for new WS connections
     pub mc_status: Arc<RwLock<Option<McStatusUpdate>>>,
-    /// Broadcast channel for feeding raw markets to MC scanner
-    pub mc_markets_tx: broadcast::Sender<Vec<TrackedMarket>>,
+    /// Broadcast channel for feeding scanned markets (full set plus deltas) to MC scanner
+    pub mc_markets_tx: broadcast::Sender<MarketsUpdate>,
     /// Active User WebSocket connections: wallet_address -> (shutdown_sender, join_handle)
     /// Used to dynamically spawn/stop per-wallet WebSocket connections
     pub user_ws_handles: Arc<Mutex<HashMap<String, (watch::Sender<bool>, JoinHandle<()>)>>>,
@@ -72,15 +87,25 @@ pub struct AppState {
     pub mint_maker_tx: broadcast::Sender<MintMakerStatusUpdate>,
     /// Cached Mint Maker status for new WS connections
     pub mint_maker_status: Arc<RwLock<Option<MintMakerStatusUpdate>>>,
-    /// Broadcast channel for feeding raw markets to Mint Maker runner
-    pub mint_maker_markets_tx: broadcast::Sender<Vec<TrackedMarket>>,
+    /// Broadcast channel for feeding scanned markets (full set plus deltas) to Mint Maker runner
+    pub mint_maker_markets_tx: broadcast::Sender<MarketsUpdate>,
     /// Token IDs from live mint maker markets (shared with main scanner for price WS subscription)
     pub mm_live_tokens: Arc<RwLock<HashSet<String>>>,
+    /// Cached neg-risk multi-outcome events from last scan
+    pub neg_risk_events: Arc<RwLock<Vec<NegRiskEvent>>>,
+    /// Per-token request counters for API token rate limits
+    pub token_limiter: Arc<ApiTokenLimiter>,
+    /// Raises notifications for the notification router
+    pub notifier: Notifier,
+    /// Generates daily/weekly performance reports
+    pub reporter: Arc<Reporter>,
+    /// Trading lease between instances; followers serve the API read-only
+    pub leader: LeaderElection,
 }
 
 impl AppState {
     pub async fn new(config: Config) -> Result<Self> {
-        let db = Database::new(&config.database_path).await?;
+        let db = Database::connect(&config).await?;
         let scanner = Scanner::new(config.clone());
         let runner = StrategyRunner::new(&config);
 
@@ -94,23 +119,54 @@ impl AppState {
         let (mc_markets_tx, _) = broadcast::channel(16);
         let (mint_maker_tx, _) = broadcast::channel(32);
         let (mint_maker_markets_tx, _) = broadcast::channel(16);
+        let (resolution_tx, _) = broadcast::channel(64);
+
+        let db = Arc::new(db);
+        let rate_limiter = Arc::new(RateLimiter::new());
+        let clob_pool = Arc::new(ClobClientPool::new(db.clone(), rate_limiter.clone()));
+        let polygon_tx = Arc::new(PolygonTxService::new(config.polygon_rpc_url.clone()));
+        let mut key_store = KeyStore::from_config(&config);
+        if config.signer_socket.is_some() {
+            // The external signer holds keys across server restarts; the master key
+            // and the wrapped keys never enter this process
+            if config.key_vault_master_key.is_some() || config.key_vault_master_key_file.is_some() {
+                warn!("Key vault master key ignored: keys are held by the external signer");
+            }
+        } else if let Some(vault) = KeyVault::from_config(&config, db.clone())? {
+            key_store = key_store.with_vault(Arc::new(vault));
+        }
+        let onchain_jobs = Arc::new(OnchainJobTracker::new(
+            db.clone(),
+            polygon_tx.clone(),
+            key_store.clone(),
+            BuilderCredentials::from_config(&config),
+        ));
+        let notifier = Notifier::new();
+        let price_cache = Arc::new(PriceCache::new());
+        let reporter = Arc::new(Reporter::new(db.clone(), notifier.clone(), price_cache.clone()));
+        let leader = LeaderElection::new(db.clone(), config.instance_id.clone(), config.leader_lease_seconds);
 
         Ok(Self {
-            db: Arc::new(db),
+            db,
             config: Arc::new(config),
             scanner: Arc::new(scanner),
             runner: Arc::new(runner),
             opportunities: Arc::new(RwLock::new(Vec::new())),
             opportunity_tx,
             price_tx,
+            price_cache,
             scan_status_tx,
             last_scan_at: Arc::new(RwLock::new(0)),
-            key_store: KeyStore::new(),
+            key_store,
             dispute_tx,
             disputes: Arc::new(RwLock::new(Vec::new())),
             balance_tx,
             tick_size_cache: Arc::new(TickSizeCache::new()),
-            rate_limiter: Arc::new(RateLimiter::new()),
+            rate_limiter,
+            clob_pool,
+            polygon_tx,
+            onchain_jobs,
+            resolution_tx,
             order_event_tx,
             metrics: Metrics::new(),
             mc_tx,
@@ -121,6 +177,11 @@ impl AppState {
             mint_maker_status: Arc::new(RwLock::new(None)),
             mint_maker_markets_tx,
             mm_live_tokens: Arc::new(RwLock::new(HashSet::new())),
+            neg_risk_events: Arc::new(RwLock::new(Vec::new())),
+            token_limiter: Arc::new(ApiTokenLimiter::new()),
+            notifier,
+            reporter,
+            leader,
         })
     }
 
@@ -164,6 +225,16 @@ impl AppState {
         self.mint_maker_tx.subscribe()
     }
 
+    /// Subscribe to on-chain job updates
+    pub fn subscribe_onchain_jobs(&self) -> broadcast::Receiver<OnchainJob> {
+        self.onchain_jobs.subscribe()
+    }
+
+    /// Subscribe to position resolution outcomes
+    pub fn subscribe_resolutions(&self) -> broadcast::Receiver<ResolutionOutcome> {
+        self.resolution_tx.subscribe()
+    }
+
     /// Spawn a User WebSocket connection for a wallet.
     /// If one already exists for this wallet, it's stopped first.
     pub async fn spawn_user_ws(
@@ -219,6 +290,36 @@ impl AppState {
     pub async fn has_user_ws(&self, wallet_address: &str) -> bool {
         self.user_ws_handles.lock().await.contains_key(wallet_address)
     }
+
+    /// Lock a wallet's key and delete its wrapped copy, unless auto-trading or
+    /// Mint Maker is still enabled for it. Returns whether the key was released.
+    pub async fn release_key(&self, wallet_address: &str) -> Result<bool> {
+        let auto_trading = self.db.auto_trading().get_auto_trading_settings(wallet_address).await?;
+        let mint_maker = self.db.mint_maker().get_mint_maker_settings(wallet_address).await?;
+        if auto_trading.enabled || mint_maker.enabled {
+            return Ok(false);
+        }
+
+        self.key_store.remove_key(wallet_address).await;
+        Ok(true)
+    }
+
+    /// Wallets with auto-trading or Mint Maker enabled whose key isn't unlocked on
+    /// this instance, so the trading services can't sign for them here
+    pub async fn locked_wallets(&self) -> Result<Vec<String>> {
+        let mut enabled: HashSet<String> = HashSet::new();
+        enabled.extend(self.db.auto_trading().get_auto_trading_enabled_wallets().await?);
+        enabled.extend(self.db.mint_maker().get_mint_maker_enabled_wallets().await?);
+
+        let mut locked = Vec::new();
+        for wallet_address in enabled.into_iter().map(|w| w.to_lowercase()).collect::<HashSet<_>>() {
+            if !self.key_store.has_key(&wallet_address).await {
+                locked.push(wallet_address);
+            }
+        }
+        locked.sort();
+        Ok(locked)
+    }
 }
 
 /// Create the Axum application with all routes
@@ -241,8 +342,17 @@ pub fn create_app(state: AppState) -> Router {
         .route("/wallet/disconnect", post(routes::wallet::disconnect_wallet))
         .route("/wallet/deposit", post(routes::wallet::deposit_to_safe))
         .route("/wallet/withdraw", post(routes::wallet::withdraw_from_safe))
+        .route("/wallet/withdraw/settings", get(routes::wallet::get_withdraw_settings))
+        .route("/wallet/withdraw/allowlist", post(routes::wallet::add_withdraw_address))
+        .route("/wallet/withdraw/allowlist/:id", axum::routing::delete(routes::wallet::remove_withdraw_address))
+        .route("/wallet/withdraw/limit", axum::routing::put(routes::wallet::set_withdraw_limit))
+        .route("/wallet/change-password", post(routes::wallet::change_password))
+        // On-chain job routes
+        .route("/onchain/jobs", get(routes::onchain::list_jobs))
+        .route("/onchain/jobs/:id", get(routes::onchain::get_job))
         // Opportunity routes
         .route("/opportunities", get(routes::opportunities::list_opportunities))
+        .route("/events/neg-risk", get(routes::events::list_neg_risk_events))
         // Position routes
         .route("/positions", get(routes::positions::list_positions))
         .route("/positions/stats", get(routes::positions::get_stats))
@@ -268,8 +378,27 @@ pub fn create_app(state: AppState) -> Router {
         // CLOB authentication routes
         .route("/auth/time", get(routes::clob_auth::get_server_time))
         .route("/auth/derive-api-key", post(routes::clob_auth::derive_api_key))
+        // API token routes (scoped tokens for automation)
+        .route("/auth/tokens", get(routes::tokens::list_tokens))
+        .route("/auth/tokens", post(routes::tokens::create_token))
+        .route("/auth/tokens/:id", axum::routing::delete(routes::tokens::revoke_token))
         // Discord webhook routes
         .route("/discord/alerts", post(routes::discord::send_alerts))
+        // Notification channel routes
+        .route("/notifications/channels", get(routes::notifications::list_channels))
+        .route("/notifications/channels", post(routes::notifications::create_channel))
+        .route("/notifications/channels/:id", axum::routing::put(routes::notifications::update_channel))
+        .route("/notifications/channels/:id", axum::routing::delete(routes::notifications::delete_channel))
+        .route("/notifications/channels/:id/test", post(routes::notifications::test_channel))
+        // Report routes
+        .route("/reports", get(routes::reports::list_reports))
+        .route("/reports/generate", post(routes::reports::generate_report))
+        .route("/reports/:id", get(routes::reports::get_report))
+        // Ledger routes
+        .route("/ledger", get(routes::ledger::get_ledger))
+        .route("/ledger/transactions", get(routes::ledger::list_transactions))
+        // Trade export routes
+        .route("/export/trades", get(routes::export::export_trades))
         // Auto-trading routes
         .route("/auto-trading/settings", get(routes::auto_trading::get_settings))
         .route("/auto-trading/settings", axum::routing::put(routes::auto_trading::update_settings))
@@ -277,11 +406,13 @@ pub fn create_app(state: AppState) -> Router {
         .route("/auto-trading/disable", post(routes::auto_trading::disable))
         .route("/auto-trading/history", get(routes::auto_trading::get_history))
         .route("/auto-trading/stats", get(routes::auto_trading::get_stats))
+        .route("/auto-trading/key-audit", get(routes::auto_trading::get_key_audit))
         .route("/auto-trading/status", get(routes::auto_trading::get_status))
         // Market data routes
         .route("/market/prices", get(routes::market_data::get_price_history))
         .route("/market/tick-size", get(routes::market_data::get_tick_size))
         .route("/metrics", get(routes::market_data::get_metrics))
+        .route("/market/scan-costs", get(routes::market_data::get_scan_costs))
         // Millionaires Club routes
         .route("/mc/status", get(routes::mc::get_status))
         .route("/mc/scout-log", get(routes::mc::get_scout_log))
@@ -310,6 +441,7 @@ pub fn create_app(state: AppState) -> Router {
         .nest("/api", api_routes)
         .route("/ws", get(ws_handler))
         .route("/health", get(health_check))
+        .route("/health/leader", get(leader_check))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
@@ -319,3 +451,23 @@ pub fn create_app(state: AppState) -> Router {
 async fn health_check() -> &'static str {
     "OK"
 }
+
+/// Leader check for load balancers: 200 on the instance running the trading
+/// services, 503 on followers. `locked_wallets` lists enabled wallets this
+/// instance holds no key for (it couldn't trade for them as leader).
+async fn leader_check(State(state): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
+    let is_leader = state.leader.is_leader();
+    let leader = state.leader.current_leader().await.ok().flatten();
+    let locked_wallets = state.locked_wallets().await.unwrap_or_default();
+    let status = if is_leader { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+
+    (
+        status,
+        Json(serde_json::json!({
+            "instance_id": state.leader.instance_id(),
+            "is_leader": is_leader,
+            "leader": leader.map(|l| l.holder),
+            "locked_wallets": locked_wallets,
+        })),
+    )
+}
@@ -0,0 +1,330 @@
+//! Resting orders at shutdown
+//!
+//! Once the trading services are stopped, on shutdown or after losing the
+//! leader lease, the server either cancels every open CLOB order of the wallets
+//! the bot trades for or leaves them resting for the next leader, and logs what
+//! each wallet still had open either way.
+
+use crate::api::routes::orders::cancel_all_on_clob;
+use crate::api::server::AppState;
+use crate::services::EndpointClass;
+use crate::types::OrderLifecycleStatus;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Start the leader-only services whenever this instance holds the lease and wind
+/// them down (cancelling resting orders when `cancel_orders` is set) as soon as it
+/// loses it, giving the wind-down at most `timeout`. Returns the services still
+/// running when `shutdown` fires, for the shutdown sequence to wind down.
+pub async fn supervise<S, Start, StartFut, WindDown, WindDownFut>(
+    mut leader: watch::Receiver<bool>,
+    mut shutdown: watch::Receiver<bool>,
+    timeout: Duration,
+    cancel_orders: bool,
+    mut start: Start,
+    mut wind_down: WindDown,
+) -> Option<S>
+where
+    Start: FnMut() -> StartFut,
+    StartFut: Future<Output = S>,
+    WindDown: FnMut(S, bool) -> WindDownFut,
+    WindDownFut: Future<Output = ()>,
+{
+    let mut services = None;
+
+    loop {
+        let leading = *leader.borrow_and_update();
+        if leading && services.is_none() {
+            info!("Leader lease acquired, starting trading services...");
+            services = Some(start().await);
+        } else if !leading {
+            if let Some(running) = services.take() {
+                warn!("Leader lease lost, stopping trading services");
+                if tokio::time::timeout(timeout, wind_down(running, cancel_orders)).await.is_err() {
+                    warn!("Wind-down after losing the lease timed out after {:?}", timeout);
+                }
+            }
+        }
+
+        tokio::select! {
+            changed = leader.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+            }
+            _ = shutdown.changed() => break,
+        }
+    }
+
+    services
+}
+
+/// Run the shutdown wind-down for at most `timeout`, then release the lease even
+/// if it timed out, so a follower doesn't wait for the lease to expire. Returns
+/// whether the wind-down finished in time.
+pub async fn wind_down_then_step_down<W, S>(timeout: Duration, wind_down: W, step_down: S) -> bool
+where
+    W: Future<Output = ()>,
+    S: Future<Output = ()>,
+{
+    let finished = tokio::time::timeout(timeout, wind_down).await.is_ok();
+    step_down.await;
+    finished
+}
+
+/// What a wallet had resting when the server stopped
+#[derive(Debug, Clone, Default)]
+pub struct OpenOrders {
+    pub wallet_address: String,
+    /// Mint Maker pairs with a bid still waiting for a fill
+    pub mint_maker_bids: usize,
+    /// Mint Maker stop-loss sells on the book
+    pub stop_losses: usize,
+    /// Tracked orders still pending or live
+    pub live_orders: usize,
+    /// Whether cancel-all went through for the wallet (the counts above are the
+    /// locally tracked orders; cancel-all also covers ones placed elsewhere)
+    pub cancelled: bool,
+}
+
+impl OpenOrders {
+    pub fn is_empty(&self) -> bool {
+        self.mint_maker_bids == 0 && self.stop_losses == 0 && self.live_orders == 0
+    }
+}
+
+/// Count the resting orders of every wallet with auto-trading or Mint Maker
+/// enabled or open Mint Maker pairs. When `cancel` is set, cancel-all runs on
+/// the CLOB for each of them whatever the local counts say, since the local
+/// records can miss orders.
+pub async fn wind_down_orders(state: &AppState, cancel: bool) -> Vec<OpenOrders> {
+    let mut wallets = BTreeSet::new();
+    match state.db.mint_maker().get_mint_maker_wallets_with_open_pairs().await {
+        Ok(found) => wallets.extend(found),
+        Err(e) => warn!("Shutdown: failed to load wallets with open Mint Maker pairs: {}", e),
+    }
+    match state.db.mint_maker().get_mint_maker_enabled_wallets().await {
+        Ok(found) => wallets.extend(found),
+        Err(e) => warn!("Shutdown: failed to load Mint Maker wallets: {}", e),
+    }
+    match state.db.auto_trading().get_auto_trading_enabled_wallets().await {
+        Ok(found) => wallets.extend(found),
+        Err(e) => warn!("Shutdown: failed to load auto-trading wallets: {}", e),
+    }
+
+    let mut report = Vec::new();
+    for wallet_address in wallets {
+        let mut open = count_open_orders(state, &wallet_address).await;
+        if cancel {
+            open.cancelled = cancel_wallet_orders(state, &wallet_address).await;
+        }
+        report.push(open);
+    }
+
+    report
+}
+
+async fn count_open_orders(state: &AppState, wallet_address: &str) -> OpenOrders {
+    let pairs = state
+        .db
+        .mint_maker()
+        .get_mint_maker_open_pairs(wallet_address)
+        .await
+        .unwrap_or_default();
+    let orders = state
+        .db
+        .orders()
+        .get_orders_for_wallet(wallet_address, None)
+        .await
+        .unwrap_or_default();
+
+    OpenOrders {
+        wallet_address: wallet_address.to_string(),
+        mint_maker_bids: pairs
+            .iter()
+            .filter(|p| p.status == "Pending" || p.status == "HalfFilled")
+            .count(),
+        stop_losses: pairs
+            .iter()
+            .filter(|p| p.status == "StopLoss" && p.stop_loss_order_id.is_some())
+            .count(),
+        live_orders: orders
+            .iter()
+            .filter(|o| matches!(o.status, OrderLifecycleStatus::Pending | OrderLifecycleStatus::Live))
+            .count(),
+        cancelled: false,
+    }
+}
+
+async fn cancel_wallet_orders(state: &AppState, wallet_address: &str) -> bool {
+    let (api_key, api_secret, api_passphrase) = match state.db.auth().get_api_credentials(wallet_address).await {
+        Ok(Some(credentials)) => credentials,
+        Ok(None) => {
+            warn!("Shutdown: no API credentials for {}, orders left open", wallet_address);
+            return false;
+        }
+        Err(e) => {
+            warn!("Shutdown: failed to load API credentials for {}: {}", wallet_address, e);
+            return false;
+        }
+    };
+
+    if state.rate_limiter.acquire(EndpointClass::DeleteOrder).await {
+        state.metrics.inc_api_rate_limited();
+    }
+    state.metrics.inc_api_calls();
+
+    match cancel_all_on_clob(wallet_address, &api_key, &api_secret, &api_passphrase).await {
+        Ok(()) => true,
+        Err(e) => {
+            state.metrics.inc_api_errors();
+            warn!("Shutdown: failed to cancel orders for {}: {}", wallet_address, e);
+            false
+        }
+    }
+}
+
+/// Log what was cancelled and what was left resting
+pub fn log_report(report: &[OpenOrders]) {
+    let mut left_open = 0;
+    for open in report {
+        if open.cancelled {
+            info!(
+                "Shutdown: cancelled all orders of {} (tracked: {} Mint Maker bids, {} stop losses, {} live orders)",
+                open.wallet_address, open.mint_maker_bids, open.stop_losses, open.live_orders
+            );
+        } else if !open.is_empty() {
+            left_open += 1;
+            warn!(
+                "Shutdown: left open for {}: {} Mint Maker bids, {} stop losses, {} live orders",
+                open.wallet_address, open.mint_maker_bids, open.stop_losses, open.live_orders
+            );
+        }
+    }
+
+    info!(
+        "Shutdown: {} wallets checked, {} with orders left resting",
+        report.len(),
+        left_open
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Started(u32),
+        WoundDown { services: u32, cancel_orders: bool },
+    }
+
+    /// Run `supervise` with services numbered by start order, reporting each start and
+    /// wind-down; `hang` makes every wind-down run forever
+    fn spawn_supervisor(
+        leader: watch::Receiver<bool>,
+        shutdown: watch::Receiver<bool>,
+        cancel_orders: bool,
+        hang: bool,
+    ) -> (tokio::task::JoinHandle<Option<u32>>, mpsc::UnboundedReceiver<Event>) {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(async move {
+            let mut next = 0;
+            let start_events = events_tx.clone();
+            supervise(
+                leader,
+                shutdown,
+                Duration::from_millis(50),
+                cancel_orders,
+                move || {
+                    next += 1;
+                    let services = next;
+                    let events = start_events.clone();
+                    async move {
+                        events.send(Event::Started(services)).unwrap();
+                        services
+                    }
+                },
+                move |services, cancel_orders| {
+                    let events = events_tx.clone();
+                    async move {
+                        events.send(Event::WoundDown { services, cancel_orders }).unwrap();
+                        if hang {
+                            std::future::pending::<()>().await;
+                        }
+                    }
+                },
+            )
+            .await
+        });
+        (handle, events_rx)
+    }
+
+    #[tokio::test]
+    async fn test_lease_loss_cancels_orders() {
+        let (leader_tx, leader_rx) = watch::channel(false);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (supervisor, mut events) = spawn_supervisor(leader_rx, shutdown_rx, true, false);
+
+        leader_tx.send(true).unwrap();
+        assert_eq!(events.recv().await, Some(Event::Started(1)));
+
+        leader_tx.send(false).unwrap();
+        assert_eq!(
+            events.recv().await,
+            Some(Event::WoundDown { services: 1, cancel_orders: true })
+        );
+
+        // Nothing left running for the shutdown sequence
+        shutdown_tx.send(true).unwrap();
+        assert_eq!(supervisor.await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_lease_loss_wind_down_is_bounded() {
+        let (leader_tx, leader_rx) = watch::channel(true);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (supervisor, mut events) = spawn_supervisor(leader_rx, shutdown_rx, false, true);
+        assert_eq!(events.recv().await, Some(Event::Started(1)));
+
+        leader_tx.send(false).unwrap();
+        assert_eq!(
+            events.recv().await,
+            Some(Event::WoundDown { services: 1, cancel_orders: false })
+        );
+
+        // The stuck wind-down is given up on, so the lease can be taken back
+        leader_tx.send(true).unwrap();
+        assert_eq!(events.recv().await, Some(Event::Started(2)));
+
+        // Services still running at shutdown are handed back
+        shutdown_tx.send(true).unwrap();
+        assert_eq!(supervisor.await.unwrap(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_steps_down_after_timeout() {
+        let stepped_down = Arc::new(AtomicBool::new(false));
+        let step_down = {
+            let stepped_down = stepped_down.clone();
+            async move { stepped_down.store(true, Ordering::SeqCst) }
+        };
+
+        let finished = wind_down_then_step_down(
+            Duration::from_millis(50),
+            std::future::pending::<()>(),
+            step_down,
+        )
+        .await;
+        assert!(!finished);
+        assert!(stepped_down.load(Ordering::SeqCst));
+
+        assert!(wind_down_then_step_down(Duration::from_millis(50), async {}, async {}).await);
+    }
+}
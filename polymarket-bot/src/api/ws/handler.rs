@@ -2,7 +2,7 @@
 
 use crate::api::server::AppState;
 use crate::services::{McStatusUpdate, MintMakerStatusUpdate, OrderEvent, PriceUpdate};
-use crate::types::{DisputeAlert, Opportunity};
+use crate::types::{DisputeAlert, OnchainJob, Opportunity};
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
@@ -57,6 +57,9 @@ pub enum WsServerMessage {
     /// Mint Maker status update
     #[serde(rename = "mint_maker_status")]
     MintMakerStatus(MintMakerStatusUpdate),
+    /// On-chain job state change (queued, submitted, mined, failed, replaced)
+    #[serde(rename = "onchain_job")]
+    OnchainJob(OnchainJob),
 }
 
 /// WebSocket message from client to server
@@ -164,6 +167,8 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     let mut mc_rx = state.subscribe_mc();
     // Subscribe to Mint Maker status updates
     let mut mint_maker_rx = state.subscribe_mint_maker();
+    // Subscribe to on-chain job updates
+    let mut onchain_job_rx = state.subscribe_onchain_jobs();
 
     // Spawn task to forward opportunity, price, and scan status updates to this client
     let send_task = tokio::spawn(async move {
@@ -349,6 +354,28 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                         }
                     }
                 }
+
+                // Handle on-chain job updates
+                result = onchain_job_rx.recv() => {
+                    match result {
+                        Ok(job) => {
+                            let msg = WsServerMessage::OnchainJob(job);
+                            if let Ok(json) = serde_json::to_string(&msg) {
+                                if sender.send(Message::Text(json)).await.is_err() {
+                                    debug!("WebSocket send failed, client disconnected");
+                                    break;
+                                }
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            debug!("On-chain job updates lagged by {} messages", n);
+                        }
+                        Err(e) => {
+                            error!("On-chain job broadcast receive error: {}", e);
+                            break;
+                        }
+                    }
+                }
             }
         }
     });
@@ -4,15 +4,20 @@
 
 use anyhow::Result;
 use chrono::Utc;
-use polymarket_bot::api::{create_app, AppState, ScanStatus, WalletBalanceUpdate};
-use polymarket_bot::services::{AutoBuyer, AutoSeller, DisputeSniper, DisputeTracker, McScanner, MintMakerRunner, PositionMonitor, PriceWebSocket};
-use polymarket_bot::{Config, ResolutionTracker};
+use polymarket_bot::api::{create_app, shutdown, AppState, ScanStatus, WalletBalanceUpdate};
+use polymarket_bot::services::safe_activation::BuilderCredentials;
+use polymarket_bot::services::notifications::Sinks;
+use polymarket_bot::services::{AutoBuyer, AutoRedeemer, AutoSeller, DisputeSniper, DisputeTracker, McScanner, MintMakerRunner, NegRiskArbExecutor, NotificationRouter, PositionMonitor, PriceWebSocket};
+use polymarket_bot::strategies::NegRiskArbStrategy;
+use polymarket_bot::{Config, MarketIndex, MarketsUpdate, ResolutionTracker};
 use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::net::TcpListener;
-use tokio::sync::mpsc;
-use tracing::{debug, info};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
@@ -39,6 +44,7 @@ async fn main() -> Result<()> {
     println!("╠══════════════════════════════════════════════════════════════╣");
     println!("║  Paper Trading: {:<44} ║", if config.paper_trading { "YES (safe mode)" } else { "NO - LIVE MODE" });
     println!("║  Discord Webhook: {:<42} ║", if config.discord_webhook_url.is_some() { "ENABLED" } else { "DISABLED" });
+    println!("║  Email (SMTP): {:<45} ║", if config.smtp.is_some() { "ENABLED" } else { "DISABLED" });
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
 
@@ -60,57 +66,39 @@ async fn main() -> Result<()> {
         PriceWebSocket::run(token_rx, ws_opportunities, ws_opportunity_tx, ws_price_tx, ws_tick_cache, ws_metrics).await;
     });
 
-    // ==================== AUTO-TRADING SERVICES ====================
+    // ==================== KEY VAULT ====================
 
-    // Channel for sell signals from position monitor to auto-seller
-    let (sell_tx, sell_rx) = mpsc::channel(64);
+    // Resume wrapped keys of enabled wallets (no-op unless a vault master key is configured)
+    match state.key_store.resume("resume after restart").await {
+        Ok(count) if count > 0 => info!("Resumed auto-trading keys for {} wallets", count),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to resume wrapped keys: {}", e),
+    }
+    state.key_store.spawn_kill_switch_watch();
 
-    // Clone sell_tx for dispute sniper before it's moved into PositionMonitor
-    let sniper_sell_tx = sell_tx.clone();
+    // ==================== NOTIFICATIONS ====================
 
-    // Spawn Position Monitor (monitors prices and generates sell signals)
-    let monitor_db = state.db.clone();
-    let monitor_price_rx = state.price_tx.subscribe();
+    // Routes wallet events (fills, exits, disputes, orphans, redeems, errors, reports,
+    // security) to each wallet's channels, and security/errors to the operator webhook
+    let notify_router = NotificationRouter::new(
+        state.db.clone(),
+        Sinks::new(config.smtp.as_ref())?,
+        config.discord_webhook_url.clone(),
+    );
+    let notify_notifier = state.notifier.clone();
+    let notify_dispute_rx = state.dispute_tx.subscribe();
     tokio::spawn(async move {
-        info!("Starting position monitor for auto-sell triggers...");
-        let monitor = PositionMonitor::new(monitor_db);
-        if let Err(e) = monitor.load_peaks().await {
-            tracing::warn!("Failed to load position peaks: {}", e);
-        }
-        monitor.run(monitor_price_rx, sell_tx).await;
+        info!("Starting notification router...");
+        notify_router.run(notify_notifier, notify_dispute_rx).await;
     });
 
-    // Spawn Auto-Seller (executes sell orders from position monitor)
-    let seller_db = state.db.clone();
-    let seller_key_store = state.key_store.clone();
-    tokio::spawn(async move {
-        info!("Starting auto-seller service...");
-        let seller = AutoSeller::new(seller_db, seller_key_store);
-        seller.run(sell_rx).await;
-    });
+    // ==================== PRICE CACHE ====================
 
-    // Spawn Auto-Buyer (automatically buys matching opportunities)
-    let buyer_db = state.db.clone();
-    let buyer_key_store = state.key_store.clone();
-    let buyer_opportunities = state.opportunities.clone();
-    let buyer_opp_rx = state.opportunity_tx.subscribe();
-    let buyer_rpc_url = config.polygon_rpc_url.clone();
-    let buyer_slippage = config.slippage_tolerance;
-    tokio::spawn(async move {
-        info!("Starting auto-buyer service...");
-        let buyer = AutoBuyer::new(buyer_db, buyer_key_store, buyer_opportunities, buyer_rpc_url, buyer_slippage);
-        buyer.run(buyer_opp_rx).await;
-    });
-
-    // Spawn Dispute Sniper (auto-buys proposed dispute outcomes with edge)
-    let sniper_db = state.db.clone();
-    let sniper_key_store = state.key_store.clone();
-    let sniper_dispute_rx = state.dispute_tx.subscribe();
-    let sniper_rpc_url = config.polygon_rpc_url.clone();
+    // Latest token prices, used to mark ledger holdings and report exposure to market
+    let price_cache = state.price_cache.clone();
+    let price_cache_rx = state.price_tx.subscribe();
     tokio::spawn(async move {
-        info!("Starting dispute auto-sniper...");
-        let sniper = DisputeSniper::new(sniper_db, sniper_key_store, sniper_rpc_url);
-        sniper.run(sniper_dispute_rx, sniper_sell_tx).await;
+        price_cache.run(price_cache_rx).await;
     });
 
     // Clone state for background scanner
@@ -129,10 +117,12 @@ async fn main() -> Result<()> {
     let dispute_db = state.db.clone();
     let dispute_tx = state.dispute_tx.clone();
     let dispute_cache = state.disputes.clone();
+    let dispute_rpc_url = config.polygon_rpc_url.clone();
+    let dispute_source = config.dispute_source;
     tokio::spawn(async move {
         info!("Starting UMA dispute tracker...");
         let mut rx = dispute_tx.subscribe();
-        let tracker = DisputeTracker::new(dispute_db);
+        let tracker = DisputeTracker::new(dispute_db, dispute_rpc_url, dispute_source);
 
         // Spawn cache updater
         let cache = dispute_cache.clone();
@@ -145,51 +135,64 @@ async fn main() -> Result<()> {
         tracker.run(Duration::from_secs(60), dispute_tx).await;
     });
 
-    // ==================== MILLIONAIRES CLUB SCANNER ====================
+    // ==================== STATUS CACHES ====================
 
-    let mc_db = state.db.clone();
-    let mc_tx = state.mc_tx.clone();
+    // Keep the latest MC and Mint Maker status for new WS connections
     let mc_status_cache = state.mc_status.clone();
-    let mc_disputes = state.disputes.clone();
-    let mc_markets_rx = state.mc_markets_tx.subscribe();
+    let mut mc_status_rx = state.mc_tx.subscribe();
     tokio::spawn(async move {
-        // Spawn cache updater
-        let cache = mc_status_cache.clone();
-        let mut cache_rx = mc_tx.subscribe();
-        tokio::spawn(async move {
-            while let Ok(status) = cache_rx.recv().await {
-                *cache.write().await = Some(status);
-            }
-        });
-
-        let mut scanner = McScanner::new(mc_db).await;
-        scanner.run(mc_markets_rx, mc_disputes, mc_tx).await;
+        while let Ok(status) = mc_status_rx.recv().await {
+            *mc_status_cache.write().await = Some(status);
+        }
     });
 
-    // ==================== MINT MAKER SERVICE ====================
-
-    let mm_db = state.db.clone();
-    let mm_key_store = state.key_store.clone();
-    let mm_config = config.mint_maker.clone();
-    let mm_tx = state.mint_maker_tx.clone();
     let mm_status_cache = state.mint_maker_status.clone();
-    let mm_client = reqwest::Client::new();
-    let mm_tick_size_cache = state.tick_size_cache.clone();
-    let mm_price_tx = state.price_tx.clone();
-    let mm_live_tokens = state.mm_live_tokens.clone();
+    let mut mm_status_rx = state.mint_maker_tx.subscribe();
     tokio::spawn(async move {
-        // Spawn cache updater
-        let cache = mm_status_cache.clone();
-        let mut cache_rx = mm_tx.subscribe();
-        tokio::spawn(async move {
-            while let Ok(status) = cache_rx.recv().await {
-                *cache.write().await = Some(status);
-            }
-        });
+        while let Ok(status) = mm_status_rx.recv().await {
+            *mm_status_cache.write().await = Some(status);
+        }
+    });
 
-        info!("Starting Mint Maker runner (dedicated scanner)...");
-        let runner = MintMakerRunner::new(mm_db, mm_key_store, mm_config, mm_client, mm_tick_size_cache, mm_price_tx, mm_live_tokens);
-        runner.run(mm_tx).await;
+    // ==================== LEADER ELECTION ====================
+
+    // Only the instance holding the trading lease runs the trading services;
+    // the others serve the API read-only and take over when the lease lapses
+    let election = state.leader.clone();
+    let election_handle = tokio::spawn(async move {
+        election.run().await;
+    });
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let supervisor_state = state.clone();
+    let supervisor_config = config.clone();
+    let supervisor = tokio::spawn(async move {
+        let state = &supervisor_state;
+        let config = &supervisor_config;
+        shutdown::supervise(
+            state.leader.subscribe(),
+            shutdown_rx,
+            Duration::from_secs(config.shutdown_timeout_seconds),
+            config.shutdown_cancel_orders,
+            move || async move {
+                // Keys other instances wrapped since this one started
+                if let Err(e) = state.key_store.resume("resume on leader takeover").await {
+                    warn!("Failed to resume wrapped keys: {}", e);
+                }
+                match state.locked_wallets().await {
+                    Ok(locked) if !locked.is_empty() => warn!(
+                        "No unlocked key on this instance for {} enabled wallets, they won't trade until unlocked: {}",
+                        locked.len(),
+                        locked.join(", ")
+                    ),
+                    Ok(_) => {}
+                    Err(e) => warn!("Failed to check for locked wallets: {}", e),
+                }
+                spawn_trading_services(state, config)
+            },
+            move |running: TradingServices, cancel_orders| running.wind_down(state, cancel_orders),
+        )
+        .await
     });
 
     // ==================== USER CHANNEL WEBSOCKET ====================
@@ -199,7 +202,7 @@ async fn main() -> Result<()> {
     {
         let startup_state = state.clone();
         tokio::spawn(async move {
-            match startup_state.db.get_wallets_with_api_credentials().await {
+            match startup_state.db.auth().get_wallets_with_api_credentials().await {
                 Ok(wallets) => {
                     for (address, api_key, api_secret, api_passphrase) in wallets {
                         info!("Starting User WebSocket for wallet {}", address);
@@ -214,6 +217,7 @@ async fn main() -> Result<()> {
     }
 
     // Create the Axum app
+    let shutdown_state = state.clone();
     let app = create_app(state);
 
     // Bind to address
@@ -225,32 +229,118 @@ async fn main() -> Result<()> {
     println!("  API:       http://localhost:3000/api");
     println!("  WebSocket: ws://localhost:3000/ws");
     println!("  Health:    http://localhost:3000/health");
+    println!("  Instance:  {} (leader: /health/leader)", config.instance_id);
     println!();
 
-    // Run the server
-    axum::serve(listener, app).await?;
+    // Run the server until SIGTERM / Ctrl+C; in-flight requests finish first
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    // ==================== SHUTDOWN ====================
+
+    // Stop renewing the lease and stop new placements, save runner state,
+    // cancel or hand over resting orders, then flush the database
+    info!("Shutting down...");
+    election_handle.abort();
+    let _ = shutdown_tx.send(true);
+
+    let wind_down = async {
+        if let Ok(Some(services)) = supervisor.await {
+            services.wind_down(&shutdown_state, config.shutdown_cancel_orders).await;
+        }
+    };
+    let timeout = Duration::from_secs(config.shutdown_timeout_seconds);
+    if !shutdown::wind_down_then_step_down(timeout, wind_down, shutdown_state.leader.step_down()).await {
+        warn!("Shutdown sequence timed out after {:?}", timeout);
+    }
+
+    shutdown_state.db.close().await;
+    info!("Shutdown complete");
 
     Ok(())
 }
 
+/// Resolves on Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    info!("Shutdown signal received");
+}
+
 /// Background scanner that periodically fetches opportunities and checks resolutions
 async fn run_scanner(state: AppState, token_tx: mpsc::Sender<Vec<String>>) {
     let scan_interval = state.config.scan_interval_seconds;
-    let resolution_tracker = ResolutionTracker::new(state.db.clone());
+    let resolution_tracker = ResolutionTracker::new(state.db.clone(), state.resolution_tx.clone());
+
+    // Market index survives restarts so the first cycle can stay incremental
+    let mut market_index = match MarketIndex::load(&state.db).await {
+        Ok(index) => {
+            info!("Loaded {} markets from the market index", index.len());
+            index
+        }
+        Err(e) => {
+            tracing::warn!("Failed to load market index, starting with a full scan: {}", e);
+            MarketIndex::new()
+        }
+    };
 
     loop {
         let scan_start = Instant::now();
 
-        // Check for resolved positions (real-time with each scan)
-        if let Err(e) = resolution_tracker.check_resolutions().await {
-            tracing::warn!("Resolution check failed: {}", e);
+        // Check for resolved positions (real-time with each scan); the leader
+        // alone, since resolutions feed the auto redeemer
+        if state.leader.is_leader() {
+            if let Err(e) = resolution_tracker.check_resolutions().await {
+                tracing::warn!("Resolution check failed: {}", e);
+            }
         }
 
         // Scan for new opportunities
-        match state.scanner.fetch_markets().await {
-            Ok(markets) => {
-                // Feed filtered markets to MC scanner (it can use sniper-filtered set)
-                let _ = state.mc_markets_tx.send(markets.clone());
+        match state.scanner.scan(&mut market_index).await {
+            Ok((deltas, cost)) => {
+                if let Err(e) = market_index.persist(&state.db, &deltas).await {
+                    tracing::warn!("Failed to persist market index: {}", e);
+                }
+                if let Err(e) = state.db.market_index().record_scan_cost(&cost).await {
+                    tracing::warn!("Failed to record scan cost: {}", e);
+                }
+                state.metrics.record_scan(&cost).await;
+
+                let markets = market_index.markets();
+
+                // Feed the full set plus this cycle's deltas to the MC scanner and Mint Maker
+                let update = MarketsUpdate {
+                    markets: Arc::new(markets.clone()),
+                    deltas: Arc::new(deltas),
+                    cost,
+                };
+                let _ = state.mc_markets_tx.send(update.clone());
+                let _ = state.mint_maker_markets_tx.send(update);
 
                 let all_opps = state.runner.find_all_opportunities(&markets);
 
@@ -314,7 +404,7 @@ async fn run_scanner(state: AppState, token_tx: mpsc::Sender<Vec<String>>) {
                 }
 
                 // Open position tokens (user's active holdings)
-                if let Ok(position_tokens) = state.db.get_all_open_position_token_ids().await {
+                if let Ok(position_tokens) = state.db.positions().get_all_open_position_token_ids().await {
                     all_tokens.extend(position_tokens);
                 }
 
@@ -351,6 +441,26 @@ async fn run_scanner(state: AppState, token_tx: mpsc::Sender<Vec<String>>) {
             }
         }
 
+        // Group neg-risk multi-outcome events and flag price sums away from 1.00
+        match state.scanner.fetch_neg_risk_events().await {
+            Ok(events) => {
+                let tolerance = state.config.neg_risk_sum_tolerance;
+                for event in events.iter().filter(|e| e.is_mispriced(tolerance)) {
+                    debug!(
+                        "Neg-risk event '{}' YES sum {} ({} outcomes, deviation {})",
+                        event.title,
+                        event.yes_price_sum,
+                        event.outcomes.len(),
+                        event.price_deviation()
+                    );
+                }
+                *state.neg_risk_events.write().await = events;
+            }
+            Err(e) => {
+                tracing::warn!("Neg-risk event scan failed: {}", e);
+            }
+        }
+
         // Broadcast wallet balances for all active wallets
         broadcast_wallet_balances(&state).await;
 
@@ -363,13 +473,220 @@ async fn run_scanner(state: AppState, token_tx: mpsc::Sender<Vec<String>>) {
     }
 }
 
+/// How long stopping services get to finish the step in progress before they're aborted
+const SERVICE_STOP_GRACE: Duration = Duration::from_secs(30);
+
+/// Leader-only services, running
+struct TradingServices {
+    /// Set to stop every service at its next loop boundary
+    stop_tx: watch::Sender<bool>,
+    handles: Vec<JoinHandle<()>>,
+    mint_maker: Arc<MintMakerRunner>,
+}
+
+impl TradingServices {
+    /// Stop every service once the step in progress (an order post, a relay
+    /// submit) finishes and wait until they're gone, so nothing places another
+    /// order. A service still running after `SERVICE_STOP_GRACE` is aborted.
+    /// Returns the Mint Maker runner for its state.
+    async fn stop(self) -> Arc<MintMakerRunner> {
+        let _ = self.stop_tx.send(true);
+        let deadline = tokio::time::Instant::now() + SERVICE_STOP_GRACE;
+        for mut handle in self.handles {
+            if tokio::time::timeout_at(deadline, &mut handle).await.is_err() {
+                warn!("Trading service still running after {:?}, aborting it", SERVICE_STOP_GRACE);
+                handle.abort();
+                let _ = handle.await;
+            }
+        }
+        self.mint_maker
+    }
+
+    /// Stop the services, hand the Mint Maker state over to whoever leads next,
+    /// then cancel or report the orders left resting
+    async fn wind_down(self, state: &AppState, cancel_orders: bool) {
+        self.stop().await.persist_state().await;
+
+        let report = shutdown::wind_down_orders(state, cancel_orders).await;
+        shutdown::log_report(&report);
+    }
+}
+
+/// Spawn the services that trade, sign or write shared state. Only the leader
+/// runs them; they're stopped when it loses the lease or shuts down.
+fn spawn_trading_services(state: &AppState, config: &Config) -> TradingServices {
+    let (stop_tx, stop_rx) = watch::channel(false);
+    let mut handles = Vec::new();
+
+    // ==================== ON-CHAIN JOB WORKER ====================
+
+    // Single worker that drives every queued/submitted relay and direct transaction
+    let jobs_worker = state.onchain_jobs.clone();
+    let jobs_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting on-chain job worker...");
+        jobs_worker.run(jobs_stop).await;
+    }));
+
+    // ==================== REPORTS ====================
+
+    // Daily and weekly per-wallet performance reports (summaries go out through the notifier)
+    let reporter = state.reporter.clone();
+    let reporter_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting report generator...");
+        reporter.run(reporter_stop).await;
+    }));
+
+    // ==================== KEY EXPIRY WATCH ====================
+
+    // Bots whose key locked on its own are disabled and their owners notified
+    let expiry_key_store = state.key_store.clone();
+    let expiry_db = state.db.clone();
+    let expiry_notifier = state.notifier.clone();
+    let expiry_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        expiry_key_store.run_expiry_watch(expiry_db, expiry_notifier, expiry_stop).await;
+    }));
+
+    // ==================== AUTO-TRADING SERVICES ====================
+
+    // Channel for sell signals from position monitor to auto-seller
+    let (sell_tx, sell_rx) = mpsc::channel(64);
+
+    // Clone sell_tx for dispute sniper before it's moved into PositionMonitor
+    let sniper_sell_tx = sell_tx.clone();
+
+    // Spawn Position Monitor (monitors prices and generates sell signals)
+    let monitor_db = state.db.clone();
+    let monitor_price_rx = state.price_tx.subscribe();
+    let monitor_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting position monitor for auto-sell triggers...");
+        let monitor = PositionMonitor::new(monitor_db);
+        if let Err(e) = monitor.load_peaks().await {
+            tracing::warn!("Failed to load position peaks: {}", e);
+        }
+        monitor.run(monitor_price_rx, sell_tx, monitor_stop).await;
+    }));
+
+    // Spawn Auto-Seller (executes sell orders from position monitor)
+    let seller_db = state.db.clone();
+    let seller_key_store = state.key_store.clone();
+    let seller_clob_pool = state.clob_pool.clone();
+    let seller_order_events = state.order_event_tx.clone();
+    let seller_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting auto-seller service...");
+        let seller = AutoSeller::new(seller_db, seller_key_store, seller_clob_pool, seller_order_events);
+        seller.run(sell_rx, seller_stop).await;
+    }));
+
+    // Spawn Auto-Buyer (automatically buys matching opportunities)
+    let buyer_db = state.db.clone();
+    let buyer_key_store = state.key_store.clone();
+    let buyer_clob_pool = state.clob_pool.clone();
+    let buyer_opportunities = state.opportunities.clone();
+    let buyer_opp_rx = state.opportunity_tx.subscribe();
+    let buyer_rpc_url = config.polygon_rpc_url.clone();
+    let buyer_slippage = config.slippage_tolerance;
+    let buyer_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting auto-buyer service...");
+        let buyer = AutoBuyer::new(buyer_db, buyer_key_store, buyer_clob_pool, buyer_opportunities, buyer_rpc_url, buyer_slippage);
+        buyer.run(buyer_opp_rx, buyer_stop).await;
+    }));
+
+    // Spawn Dispute Sniper (auto-buys proposed dispute outcomes with edge)
+    let sniper_db = state.db.clone();
+    let sniper_key_store = state.key_store.clone();
+    let sniper_clob_pool = state.clob_pool.clone();
+    let sniper_dispute_rx = state.dispute_tx.subscribe();
+    let sniper_rpc_url = config.polygon_rpc_url.clone();
+    let sniper_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting dispute auto-sniper...");
+        let sniper = DisputeSniper::new(sniper_db, sniper_key_store, sniper_clob_pool, sniper_rpc_url);
+        sniper.run(sniper_dispute_rx, sniper_sell_tx, sniper_stop).await;
+    }));
+
+    // Spawn Auto Redeemer (redeems resolved winning positions for every strategy)
+    let redeemer_db = state.db.clone();
+    let redeemer_key_store = state.key_store.clone();
+    let redeemer_onchain_jobs = state.onchain_jobs.clone();
+    let redeemer_creds = BuilderCredentials::from_config(config);
+    let redeemer_outcome_rx = state.subscribe_resolutions();
+    let redeemer_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting auto redeemer...");
+        let redeemer = AutoRedeemer::new(redeemer_db, redeemer_key_store, redeemer_onchain_jobs, redeemer_creds);
+        redeemer.run(redeemer_outcome_rx, redeemer_stop).await;
+    }));
+
+    // Spawn Neg-Risk Arb Executor (buys mispriced neg-risk baskets from the scanner's event cache)
+    let arb_db = state.db.clone();
+    let arb_key_store = state.key_store.clone();
+    let arb_clob_pool = state.clob_pool.clone();
+    let arb_events = state.neg_risk_events.clone();
+    let arb_strategy = NegRiskArbStrategy::new(config.neg_risk_arb.clone(), config.taker_fee_bps);
+    let arb_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        info!("Starting neg-risk basket arb executor...");
+        let executor = NegRiskArbExecutor::new(arb_db, arb_key_store, arb_clob_pool, arb_strategy, arb_events);
+        executor.run(arb_stop).await;
+    }));
+
+    // ==================== MILLIONAIRES CLUB SCANNER ====================
+
+    let mc_db = state.db.clone();
+    let mc_tx = state.mc_tx.clone();
+    let mc_disputes = state.disputes.clone();
+    let mc_markets_rx = state.mc_markets_tx.subscribe();
+    let mc_taxonomy = config.mc_taxonomy.clone();
+    let mc_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        let mut scanner = McScanner::new(mc_db, mc_taxonomy).await;
+        scanner.run(mc_markets_rx, mc_disputes, mc_tx, mc_stop).await;
+    }));
+
+    // ==================== MINT MAKER SERVICE ====================
+
+    let mm_db = state.db.clone();
+    let mm_key_store = state.key_store.clone();
+    let mm_config = config.mint_maker.clone();
+    let mm_tx = state.mint_maker_tx.clone();
+    let mm_client = reqwest::Client::new();
+    let mm_tick_size_cache = state.tick_size_cache.clone();
+    let mm_live_tokens = state.mm_live_tokens.clone();
+    let mm_clob_pool = state.clob_pool.clone();
+    let mm_onchain_jobs = state.onchain_jobs.clone();
+    let mint_maker = Arc::new(MintMakerRunner::new(mm_db, mm_key_store, mm_clob_pool, mm_onchain_jobs, mm_config, mm_client, mm_tick_size_cache, mm_live_tokens));
+
+    // Live prices for the runner's caches, stopped with the runner
+    let listener = mint_maker.clone();
+    let listener_price_rx = state.price_tx.subscribe();
+    let listener_stop = stop_rx.clone();
+    handles.push(tokio::spawn(async move {
+        listener.listen_prices(listener_price_rx, listener_stop).await;
+    }));
+
+    let runner = mint_maker.clone();
+    let runner_stop = stop_rx;
+    handles.push(tokio::spawn(async move {
+        info!("Starting Mint Maker runner (dedicated scanner)...");
+        runner.run(mm_tx, runner_stop).await;
+    }));
+
+    TradingServices { stop_tx, handles, mint_maker }
+}
+
 /// USDC.e (bridged) contract address on Polygon
 const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
 
 /// Fetch and broadcast USDC balances for all wallets with active sessions
 async fn broadcast_wallet_balances(state: &AppState) {
     // Get all wallets that have active sessions
-    let wallets = match state.db.get_active_wallet_addresses().await {
+    let wallets = match state.db.auth().get_active_wallet_addresses().await {
         Ok(w) => w,
         Err(e) => {
             tracing::debug!("Failed to get active wallets for balance update: {}", e);
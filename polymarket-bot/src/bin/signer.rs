@@ -0,0 +1,57 @@
+//! Polymarket Signer
+//!
+//! Holds unlocked auto-trading keys outside the web server process and signs
+//! hashes on request over a Unix socket. Point the server at it with
+//! `SIGNER_SOCKET=<path>`.
+
+use anyhow::Result;
+use polymarket_bot::services::signer::{self, LocalSignerBackend, SignerBackend};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .compact()
+        .init();
+
+    dotenvy::dotenv().ok();
+
+    let socket_path = std::env::var("SIGNER_SOCKET")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("polymarket-signer.sock"));
+    let ttl = std::env::var("KEY_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|secs: &u64| *secs > 0)
+        .map(Duration::from_secs);
+    let idle_lock = std::env::var("KEY_IDLE_LOCK_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|secs: &u64| *secs > 0)
+        .map(Duration::from_secs);
+
+    info!("Key TTL: {:?}, idle lock: {:?}", ttl, idle_lock);
+    let backend = Arc::new(LocalSignerBackend::new(ttl, idle_lock));
+    backend.spawn_auto_lock();
+    let backend: Arc<dyn SignerBackend> = backend;
+
+    tokio::select! {
+        result = signer::serve(&socket_path, backend.clone()) => result?,
+        _ = tokio::signal::ctrl_c() => {
+            info!("Shutting down, locking all keys");
+            backend.lock_all().await?;
+            let _ = std::fs::remove_file(&socket_path);
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 //! Configuration management for the Polymarket bot
 
+use crate::taxonomy::{parse_categories, TaxonomyFilter};
 use anyhow::Result;
 use rust_decimal::Decimal;
 use std::env;
@@ -14,6 +15,22 @@ pub struct Config {
     /// Path to SQLite database
     pub database_path: String,
 
+    /// PostgreSQL URL for state shared between server instances (requires the `postgres` feature)
+    pub database_url: Option<String>,
+
+    /// Name this server instance holds the leader lease under (default: `<hostname>-<pid>`)
+    pub instance_id: String,
+
+    /// Seconds a leader lease lasts without renewal before a follower may take over (default: 15)
+    pub leader_lease_seconds: u64,
+
+    /// Cancel every open CLOB order of the bot's wallets on shutdown instead of
+    /// leaving them for the next leader (default: false)
+    pub shutdown_cancel_orders: bool,
+
+    /// Seconds the shutdown sequence may take before the server exits anyway (default: 30)
+    pub shutdown_timeout_seconds: u64,
+
     /// Polygon RPC URL for balance queries
     pub polygon_rpc_url: String,
 
@@ -29,6 +46,9 @@ pub struct Config {
     /// Scan interval in seconds
     pub scan_interval_seconds: u64,
 
+    /// Minutes between full market re-fetches; cycles in between only fetch changed markets (default: 30)
+    pub scan_full_reconcile_minutes: u64,
+
     /// Minimum liquidity for markets to consider
     pub min_liquidity: Decimal,
 
@@ -38,9 +58,18 @@ pub struct Config {
     /// Mint Maker strategy settings
     pub mint_maker: MintMakerConfig,
 
-    /// Discord webhook URL for sniper alerts (optional)
+    /// Neg-risk basket arbitrage settings
+    pub neg_risk_arb: NegRiskArbConfig,
+
+    /// Market categories/tags the Millionaires Club scanner evaluates (MC_INCLUDE_CATEGORIES etc.)
+    pub mc_taxonomy: TaxonomyFilter,
+
+    /// Discord webhook URL for sniper alerts (optional); also receives security and error notifications
     pub discord_webhook_url: Option<String>,
 
+    /// SMTP relay for email notification channels (optional)
+    pub smtp: Option<SmtpConfig>,
+
     /// Polymarket Builder credentials (for relay service)
     pub builder_api_key: Option<String>,
     pub builder_secret: Option<String>,
@@ -51,6 +80,77 @@ pub struct Config {
 
     /// Slippage tolerance for market orders (default: 0.005 = 0.5%)
     pub slippage_tolerance: f64,
+
+    /// Where the dispute tracker reads UMA assertion events from (default: auto)
+    pub dispute_source: DisputeSource,
+
+    /// How far a neg-risk event's YES price sum may drift from 1.00 before it is flagged (default: 0.02)
+    pub neg_risk_sum_tolerance: Decimal,
+
+    /// Unix socket of an external signer process (keys stay in this process when unset)
+    pub signer_socket: Option<String>,
+
+    /// Seconds an unlocked auto-trading key stays usable before it is locked, which also
+    /// stops the wallet's bots (default: 0 = keys stay unlocked while a bot is enabled)
+    pub key_ttl_seconds: u64,
+
+    /// Seconds without a signature before an unlocked key auto-locks (default: 0 = never)
+    pub key_idle_lock_seconds: u64,
+
+    /// Hex-encoded 32-byte master key that wraps auto-trading keys at rest (opt-in auto-resume)
+    pub key_vault_master_key: Option<String>,
+
+    /// File holding the master key (32 raw bytes or hex), used when the env key is unset
+    pub key_vault_master_key_file: Option<String>,
+
+    /// While this file exists, wrapped keys are purged and never resumed
+    pub key_vault_kill_switch: Option<String>,
+
+    /// Max USDC a wallet can withdraw per rolling 24h (default: 5000); wallets may set a lower limit
+    pub withdraw_daily_limit: Decimal,
+
+    /// Hours before a newly allowlisted withdrawal address can receive funds, or a raised
+    /// daily limit takes effect (default: 24)
+    pub withdraw_address_delay_hours: i64,
+
+    /// Hours withdrawals and key export stay locked after a password change (default: 24)
+    pub password_change_cooldown_hours: i64,
+}
+
+/// SMTP relay used by email notification channels (STARTTLS)
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Default: 587
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender address, e.g. "Polymarket Bot <bot@example.com>"
+    pub from: String,
+}
+
+/// Source of UMA assertion/dispute events for the dispute tracker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeSource {
+    /// Goldsky/TheGraph subgraph only
+    Subgraph,
+    /// `eth_getLogs` against `polygon_rpc_url` only
+    Rpc,
+    /// Subgraph first, on-chain logs when the subgraph is down or lagging
+    Auto,
+}
+
+impl FromStr for DisputeSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "subgraph" => Ok(Self::Subgraph),
+            "rpc" | "chain" => Ok(Self::Rpc),
+            "auto" => Ok(Self::Auto),
+            other => anyhow::bail!("Unknown DISPUTE_SOURCE '{}' (expected subgraph, rpc or auto)", other),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -65,6 +165,8 @@ pub struct SniperConfig {
     pub max_favorite_price: f64,
     /// Minimum expected value threshold (default: 0.05)
     pub min_ev: f64,
+    /// Market categories/tags to trade (SNIPER_INCLUDE_CATEGORIES etc.)
+    pub taxonomy: TaxonomyFilter,
 }
 
 impl Default for SniperConfig {
@@ -75,6 +177,7 @@ impl Default for SniperConfig {
             min_favorite_price: 0.70,
             max_favorite_price: 0.90,
             min_ev: 0.05,
+            taxonomy: TaxonomyFilter::default(),
         }
     }
 }
@@ -102,6 +205,8 @@ pub struct MintMakerConfig {
     pub min_minutes_to_close: f64,
     /// Maximum minutes to market close for eligibility
     pub max_minutes_to_close: f64,
+    /// Market categories/tags to trade (MINT_MAKER_INCLUDE_CATEGORIES etc.)
+    pub taxonomy: TaxonomyFilter,
 }
 
 impl Default for MintMakerConfig {
@@ -117,6 +222,37 @@ impl Default for MintMakerConfig {
             rebalance_interval_seconds: 3,
             min_minutes_to_close: 2.0,
             max_minutes_to_close: 14.0,
+            taxonomy: TaxonomyFilter::default(),
+        }
+    }
+}
+
+/// Neg-risk basket arbitrage configuration
+#[derive(Debug, Clone)]
+pub struct NegRiskArbConfig {
+    /// Minimum profit per basket (one share of every leg) after fees, in dollars
+    pub min_profit_per_set: f64,
+    /// Maximum number of baskets bought in one execution
+    pub max_sets: u32,
+    /// Maximum outcomes in an event (every leg is a separate order)
+    pub max_outcomes: usize,
+    /// Minimum liquidity per outcome market (Gamma-reported)
+    pub min_leg_liquidity: f64,
+    /// How often to look for baskets (seconds)
+    pub scan_interval_seconds: u64,
+    /// Event categories/tags to trade (NEG_RISK_ARB_INCLUDE_CATEGORIES etc.)
+    pub taxonomy: TaxonomyFilter,
+}
+
+impl Default for NegRiskArbConfig {
+    fn default() -> Self {
+        Self {
+            min_profit_per_set: 0.01,
+            max_sets: 50,
+            max_outcomes: 12,
+            min_leg_liquidity: 500.0,
+            scan_interval_seconds: 30,
+            taxonomy: TaxonomyFilter::default(),
         }
     }
 }
@@ -132,6 +268,33 @@ impl Config {
         let database_path = env::var("DATABASE_PATH")
             .unwrap_or_else(|_| "polymarket.db".to_string());
 
+        let database_url = env::var("DATABASE_URL").ok().filter(|s| !s.is_empty());
+
+        // Leader election between server instances sharing a database
+        let instance_id = env::var("INSTANCE_ID")
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| {
+                let host = env::var("HOSTNAME").ok().filter(|s| !s.is_empty());
+                format!("{}-{}", host.as_deref().unwrap_or("server"), std::process::id())
+            });
+
+        let leader_lease_seconds = env::var("LEADER_LEASE_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&v: &u64| v >= 3)
+            .unwrap_or(15);
+
+        // Shutdown: cancel resting orders, or hand them over to the next leader
+        let shutdown_cancel_orders = env::var("SHUTDOWN_CANCEL_ORDERS")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let shutdown_timeout_seconds = env::var("SHUTDOWN_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+
         let paper_trading = env::var("PAPER_TRADING")
             .map(|v| v.to_lowercase() == "true")
             .unwrap_or(true); // Default to paper trading for safety
@@ -151,6 +314,11 @@ impl Config {
             .and_then(|v| v.parse().ok())
             .unwrap_or(15);
 
+        let scan_full_reconcile_minutes = env::var("SCAN_FULL_RECONCILE_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+
         let min_liquidity = env::var("MIN_LIQUIDITY")
             .ok()
             .and_then(|v| Decimal::from_str(&v).ok())
@@ -161,6 +329,20 @@ impl Config {
 
         let discord_webhook_url = env::var("DISCORD_WEBHOOK_URL").ok().filter(|s| !s.is_empty());
 
+        // SMTP relay for email notifications, off unless host and sender are set
+        let smtp_host = env::var("SMTP_HOST").ok().filter(|s| !s.is_empty());
+        let smtp_from = env::var("SMTP_FROM").ok().filter(|s| !s.is_empty());
+        let smtp = match (smtp_host, smtp_from) {
+            (Some(host), Some(from)) => Some(SmtpConfig {
+                host,
+                port: env::var("SMTP_PORT").ok().and_then(|v| v.parse().ok()).unwrap_or(587),
+                username: env::var("SMTP_USERNAME").ok().filter(|s| !s.is_empty()),
+                password: env::var("SMTP_PASSWORD").ok().filter(|s| !s.is_empty()),
+                from,
+            }),
+            _ => None,
+        };
+
         // Builder credentials for relay service
         let builder_api_key = env::var("POLY_BUILDER_API_KEY").ok().filter(|s| !s.is_empty());
         let builder_secret = env::var("POLY_BUILDER_SECRET").ok().filter(|s| !s.is_empty());
@@ -177,6 +359,52 @@ impl Config {
             .and_then(|v| v.parse().ok())
             .unwrap_or(0.005); // Default 0.5%
 
+        let dispute_source = env::var("DISPUTE_SOURCE")
+            .ok()
+            .filter(|s| !s.is_empty())
+            .map(|v| DisputeSource::from_str(&v))
+            .transpose()?
+            .unwrap_or(DisputeSource::Auto);
+
+        let neg_risk_sum_tolerance = env::var("NEG_RISK_SUM_TOLERANCE")
+            .ok()
+            .and_then(|v| Decimal::from_str(&v).ok())
+            .unwrap_or_else(|| Decimal::new(2, 2)); // Default 0.02
+
+        // Signer backend for auto-trading keys
+        let signer_socket = env::var("SIGNER_SOCKET").ok().filter(|s| !s.is_empty());
+
+        let key_ttl_seconds = env::var("KEY_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        let key_idle_lock_seconds = env::var("KEY_IDLE_LOCK_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        // Key vault (auto-resume after restart), off unless a master key is configured
+        let key_vault_master_key = env::var("KEY_VAULT_MASTER_KEY").ok().filter(|s| !s.is_empty());
+        let key_vault_master_key_file = env::var("KEY_VAULT_MASTER_KEY_FILE").ok().filter(|s| !s.is_empty());
+        let key_vault_kill_switch = env::var("KEY_VAULT_KILL_SWITCH").ok().filter(|s| !s.is_empty());
+
+        // Withdrawal safeguards
+        let withdraw_daily_limit = env::var("WITHDRAW_DAILY_LIMIT_USDC")
+            .ok()
+            .and_then(|v| Decimal::from_str(&v).ok())
+            .unwrap_or_else(|| Decimal::new(5000, 0));
+
+        let withdraw_address_delay_hours = env::var("WITHDRAW_ADDRESS_DELAY_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24);
+
+        let password_change_cooldown_hours = env::var("PASSWORD_CHANGE_COOLDOWN_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24);
+
         // Validate configuration
         if !paper_trading && private_key.is_none() {
             anyhow::bail!("POLYMARKET_PRIVATE_KEY required for live trading");
@@ -185,20 +413,49 @@ impl Config {
         Ok(Self {
             private_key,
             database_path,
+            database_url,
+            instance_id,
+            leader_lease_seconds,
+            shutdown_cancel_orders,
+            shutdown_timeout_seconds,
             polygon_rpc_url,
             paper_trading,
             max_position_size,
             max_total_exposure,
             scan_interval_seconds,
+            scan_full_reconcile_minutes,
             min_liquidity,
-            sniper: SniperConfig::default(),
-            mint_maker: MintMakerConfig::default(),
+            sniper: SniperConfig {
+                taxonomy: taxonomy_filter_from_env("SNIPER"),
+                ..Default::default()
+            },
+            mint_maker: MintMakerConfig {
+                taxonomy: taxonomy_filter_from_env("MINT_MAKER"),
+                ..Default::default()
+            },
+            neg_risk_arb: NegRiskArbConfig {
+                taxonomy: taxonomy_filter_from_env("NEG_RISK_ARB"),
+                ..Default::default()
+            },
+            mc_taxonomy: taxonomy_filter_from_env("MC"),
             discord_webhook_url,
+            smtp,
             builder_api_key,
             builder_secret,
             builder_passphrase,
             taker_fee_bps,
             slippage_tolerance,
+            dispute_source,
+            neg_risk_sum_tolerance,
+            signer_socket,
+            key_ttl_seconds,
+            key_idle_lock_seconds,
+            key_vault_master_key,
+            key_vault_master_key_file,
+            key_vault_kill_switch,
+            withdraw_daily_limit,
+            withdraw_address_delay_hours,
+            password_change_cooldown_hours,
         })
     }
 
@@ -208,6 +465,22 @@ impl Config {
     }
 }
 
+/// Read `{prefix}_INCLUDE_CATEGORIES`, `{prefix}_EXCLUDE_CATEGORIES` and
+/// `{prefix}_EXCLUDE_TAGS` (comma-separated) into a taxonomy filter
+fn taxonomy_filter_from_env(prefix: &str) -> TaxonomyFilter {
+    let var = |name: &str| env::var(format!("{}_{}", prefix, name)).unwrap_or_default();
+
+    TaxonomyFilter {
+        include: parse_categories(&var("INCLUDE_CATEGORIES")),
+        exclude: parse_categories(&var("EXCLUDE_CATEGORIES")),
+        exclude_tags: var("EXCLUDE_TAGS")
+            .split(',')
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect(),
+    }
+}
+
 /// Gamma API configuration
 pub struct GammaApi;
 
@@ -0,0 +1,1271 @@
+//! Database for tracking positions, orders, and statistics
+//!
+//! `Database` owns the local SQLite file. Every domain except opportunities,
+//! scan history and description hashes is reached through the repository
+//! accessors, which point at PostgreSQL when one is configured. Both backends
+//! implement the repositories from the same SQL in `sql`.
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+mod repository;
+mod sql;
+mod sqlite;
+
+pub use repository::{
+    AuthRepository, AutoTradingRepository, ChainEventRepository, KeyVaultRepository, LeaseRepository,
+    LedgerRepository, MarketIndexRepository, McRepository, MintMakerRepository, NotificationRepository,
+    OnchainJobRepository, OrderRepository, PositionRepository, ReportRepository, WithdrawalRepository,
+};
+pub(crate) use repository::ledger_stats;
+
+use crate::export::{Fill, FillSource};
+use crate::ledger::{self, LedgerTxn};
+use crate::migrations::{self, MigrationStatus};
+use crate::types::{Opportunity, Position};
+use crate::wallet::EncryptedKey;
+use crate::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::collections::HashMap;
+use std::str::FromStr;
+#[cfg(feature = "postgres")]
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Stored wallet information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredWallet {
+    pub id: i64,
+    pub address: String,
+    pub has_encrypted_key: bool,
+    pub created_at: DateTime<Utc>,
+    pub last_active: Option<DateTime<Utc>>,
+}
+
+/// Wallet with encrypted key (for internal use)
+#[derive(Debug, Clone)]
+pub struct WalletWithKey {
+    pub address: String,
+    pub encrypted_key: Option<EncryptedKey>,
+}
+
+/// Session information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub wallet_address: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Long-lived API token for automation (only the SHA-256 hash of the secret is stored)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub wallet_address: String,
+    pub name: String,
+    /// Comma-separated scopes (read, trade, withdraw)
+    pub scopes: String,
+    pub rate_limit_per_minute: i64,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+/// Notification channel of a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationChannel {
+    pub id: i64,
+    pub wallet_address: String,
+    pub name: String,
+    /// JSON `ChannelTarget` (sink type plus URL, bot token or email address)
+    pub target: String,
+    /// Comma-separated subscribed event kinds
+    pub events: String,
+    /// Message template (`{{field}}` placeholders); default layout when None
+    pub template: Option<String>,
+    pub rate_limit_per_minute: i64,
+    pub enabled: bool,
+    pub created_at: String,
+    pub last_sent_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// Partial close response with PnL and remaining info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialCloseResult {
+    /// PnL from this specific sell
+    pub pnl_this_sell: Decimal,
+    /// Whether the position is now fully closed
+    pub is_fully_closed: bool,
+    /// Total realized PnL from all partial sells
+    pub total_realized_pnl: Decimal,
+    /// Shares remaining after this sell
+    pub remaining_shares: Decimal,
+}
+
+/// A live position with its on-chain redemption, for rebuilding its fills
+#[derive(Debug, Clone)]
+pub struct PositionHistory {
+    pub position: Position,
+    pub redeemed_at: Option<DateTime<Utc>>,
+    pub redeem_proceeds: Option<Decimal>,
+}
+
+/// Leader-election lease held by one server instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lease {
+    pub name: String,
+    pub holder: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Lease {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Database connection pool
+pub struct Database {
+    pool: SqlitePool,
+    /// Shared store for the repository domains (None: they live in `pool` too)
+    #[cfg(feature = "postgres")]
+    shared: Option<Arc<postgres::PgStore>>,
+}
+
+impl Database {
+    /// Create a new database connection, migrating the schema to the latest version
+    pub async fn new(path: &str) -> Result<Self> {
+        let db = Self::open(path).await?;
+        db.initialize().await?;
+
+        Ok(db)
+    }
+
+    /// Connect without touching the schema (for inspecting migration status)
+    pub async fn open(path: &str) -> Result<Self> {
+        Self::open_with(path, true).await
+    }
+
+    /// Local tables reference `wallets`, which moves to PostgreSQL when a shared
+    /// store is attached, so foreign keys are only enforced without one
+    async fn open_with(path: &str, foreign_keys: bool) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(path)?
+            .create_if_missing(true)
+            .foreign_keys(foreign_keys)
+            .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await
+            .context("Failed to connect to database")?;
+
+        Ok(Self {
+            pool,
+            #[cfg(feature = "postgres")]
+            shared: None,
+        })
+    }
+
+    /// Open the local database and, when `DATABASE_URL` is set, the shared PostgreSQL store
+    pub async fn connect(config: &Config) -> Result<Self> {
+        let mut db = Self::open_with(&config.database_path, config.database_url.is_none()).await?;
+        db.initialize().await?;
+        if let Some(url) = &config.database_url {
+            db.attach_shared(url).await?;
+        }
+
+        Ok(db)
+    }
+
+    #[cfg(feature = "postgres")]
+    async fn attach_shared(&mut self, url: &str) -> Result<()> {
+        self.shared = Some(Arc::new(postgres::PgStore::connect(url).await?));
+        info!("Shared state (everything but opportunities, scan history and description hashes) in PostgreSQL");
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    async fn attach_shared(&mut self, _url: &str) -> Result<()> {
+        anyhow::bail!("DATABASE_URL is set but this build has no PostgreSQL support (build with --features postgres)")
+    }
+
+    /// Wait for in-flight queries, checkpoint the WAL into the database file and
+    /// close the pools (shutdown)
+    pub async fn close(&self) {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            shared.close().await;
+        }
+
+        if let Err(e) = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&self.pool).await {
+            warn!("WAL checkpoint on close failed: {}", e);
+        }
+        self.pool.close().await;
+    }
+
+    // ==================== REPOSITORIES ====================
+
+    /// Wallets, sessions, API tokens and CLOB credentials
+    pub fn auth(&self) -> &dyn AuthRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Positions and their trailing-stop peaks
+    pub fn positions(&self) -> &dyn PositionRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Order lifecycle
+    pub fn orders(&self) -> &dyn OrderRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Mint Maker settings, pairs, log and analytics
+    pub fn mint_maker(&self) -> &dyn MintMakerRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Millionaires Club
+    pub fn mc(&self) -> &dyn McRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Double-entry ledger
+    pub fn ledger(&self) -> &dyn LedgerRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Leader-election leases
+    pub fn leases(&self) -> &dyn LeaseRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Auto-trading settings and log
+    pub fn auto_trading(&self) -> &dyn AutoTradingRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// On-chain job queue
+    pub fn onchain_jobs(&self) -> &dyn OnchainJobRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Withdrawals and the destination allowlist
+    pub fn withdrawals(&self) -> &dyn WithdrawalRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Wrapped wallet keys and the key audit log
+    pub fn key_vault(&self) -> &dyn KeyVaultRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Notification channels
+    pub fn notifications(&self) -> &dyn NotificationRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Daily and weekly reports
+    pub fn reports(&self) -> &dyn ReportRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Scanner market index and scan costs
+    pub fn market_index(&self) -> &dyn MarketIndexRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// On-chain log cursors and UMA assertions
+    pub fn chain_events(&self) -> &dyn ChainEventRepository {
+        #[cfg(feature = "postgres")]
+        if let Some(shared) = &self.shared {
+            return shared.as_ref();
+        }
+        self
+    }
+
+    /// Add the columns a database created before versioned migrations may lack.
+    /// Runs once, before such a database is recorded at the baseline version;
+    /// tables it doesn't have yet are created by the baseline itself.
+    async fn upgrade_legacy_schema(&self) -> Result<()> {
+        // Check if positions table exists and add columns if missing
+        let table_info: Vec<(i64, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+            "PRAGMA table_info(positions)"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        // Check if wallet_address column exists
+        let has_wallet_address = table_info.iter().any(|(_, name, _, _, _, _)| name == "wallet_address");
+        if !table_info.is_empty() && !has_wallet_address {
+            info!("Migrating positions table: adding wallet_address column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN wallet_address TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if is_paper column exists
+        let has_is_paper = table_info.iter().any(|(_, name, _, _, _, _)| name == "is_paper");
+        if !table_info.is_empty() && !has_is_paper {
+            info!("Migrating positions table: adding is_paper column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN is_paper INTEGER NOT NULL DEFAULT 1")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if end_date column exists
+        let has_end_date = table_info.iter().any(|(_, name, _, _, _, _)| name == "end_date");
+        if !table_info.is_empty() && !has_end_date {
+            info!("Migrating positions table: adding end_date column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN end_date TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if token_id column exists
+        let has_token_id = table_info.iter().any(|(_, name, _, _, _, _)| name == "token_id");
+        if !table_info.is_empty() && !has_token_id {
+            info!("Migrating positions table: adding token_id column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN token_id TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if order_id column exists
+        let has_order_id = table_info.iter().any(|(_, name, _, _, _, _)| name == "order_id");
+        if !table_info.is_empty() && !has_order_id {
+            info!("Migrating positions table: adding order_id column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN order_id TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if slug column exists
+        let has_slug = table_info.iter().any(|(_, name, _, _, _, _)| name == "slug");
+        if !table_info.is_empty() && !has_slug {
+            info!("Migrating positions table: adding slug column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN slug TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if remaining_size column exists (for partial sells)
+        let has_remaining_size = table_info.iter().any(|(_, name, _, _, _, _)| name == "remaining_size");
+        if !table_info.is_empty() && !has_remaining_size {
+            info!("Migrating positions table: adding remaining_size column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN remaining_size TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if realized_pnl column exists (for partial sells)
+        let has_realized_pnl = table_info.iter().any(|(_, name, _, _, _, _)| name == "realized_pnl");
+        if !table_info.is_empty() && !has_realized_pnl {
+            info!("Migrating positions table: adding realized_pnl column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN realized_pnl TEXT DEFAULT '0'")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if total_sold_size column exists (for partial sells)
+        let has_total_sold_size = table_info.iter().any(|(_, name, _, _, _, _)| name == "total_sold_size");
+        if !table_info.is_empty() && !has_total_sold_size {
+            info!("Migrating positions table: adding total_sold_size column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN total_sold_size TEXT DEFAULT '0'")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if avg_exit_price column exists (for partial sells)
+        let has_avg_exit_price = table_info.iter().any(|(_, name, _, _, _, _)| name == "avg_exit_price");
+        if !table_info.is_empty() && !has_avg_exit_price {
+            info!("Migrating positions table: adding avg_exit_price column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN avg_exit_price TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if neg_risk column exists
+        let has_neg_risk = table_info.iter().any(|(_, name, _, _, _, _)| name == "neg_risk");
+        if !table_info.is_empty() && !has_neg_risk {
+            info!("Migrating positions table: adding neg_risk column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN neg_risk INTEGER DEFAULT 0")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if fee_paid column exists
+        let has_fee_paid = table_info.iter().any(|(_, name, _, _, _, _)| name == "fee_paid");
+        if !table_info.is_empty() && !has_fee_paid {
+            info!("Migrating positions table: adding fee_paid column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN fee_paid TEXT DEFAULT '0'")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if redeemed_at column exists (set once winning tokens are redeemed on-chain)
+        let has_redeemed_at = table_info.iter().any(|(_, name, _, _, _, _)| name == "redeemed_at");
+        if !table_info.is_empty() && !has_redeemed_at {
+            info!("Migrating positions table: adding redeemed_at column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN redeemed_at TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if redeem_tx_id column exists (relay transaction that redeemed the position)
+        let has_redeem_tx_id = table_info.iter().any(|(_, name, _, _, _, _)| name == "redeem_tx_id");
+        if !table_info.is_empty() && !has_redeem_tx_id {
+            info!("Migrating positions table: adding redeem_tx_id column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN redeem_tx_id TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // Check if redeem_proceeds column exists (USDC received when the position was redeemed)
+        let has_redeem_proceeds = table_info.iter().any(|(_, name, _, _, _, _)| name == "redeem_proceeds");
+        if !table_info.is_empty() && !has_redeem_proceeds {
+            info!("Migrating positions table: adding redeem_proceeds column");
+            sqlx::query("ALTER TABLE positions ADD COLUMN redeem_proceeds TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // ==================== AUTO-TRADING SETTINGS MIGRATIONS ====================
+        let settings_info: Vec<(i64, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+            "PRAGMA table_info(auto_trading_settings)"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        if !settings_info.is_empty() {
+            let has_dispute_sniper = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_sniper_enabled");
+            if !has_dispute_sniper {
+                info!("Migrating auto_trading_settings: adding dispute sniper columns");
+                sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_sniper_enabled INTEGER DEFAULT 0")
+                    .execute(&self.pool).await?;
+                sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN min_dispute_edge REAL DEFAULT 0.10")
+                    .execute(&self.pool).await?;
+                sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN max_dispute_position_size TEXT DEFAULT '25'")
+                    .execute(&self.pool).await?;
+                sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_exit_on_escalation INTEGER DEFAULT 1")
+                    .execute(&self.pool).await?;
+            }
+        }
+
+        let has_dispute_dvm_enabled = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_dvm_enabled");
+        if !settings_info.is_empty() && !has_dispute_dvm_enabled {
+            info!("Migrating auto_trading_settings: adding dispute_dvm_enabled column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_dvm_enabled INTEGER DEFAULT 0")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_dispute_dvm_min_vote_share = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_dvm_min_vote_share");
+        if !settings_info.is_empty() && !has_dispute_dvm_min_vote_share {
+            info!("Migrating auto_trading_settings: adding dispute_dvm_min_vote_share column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_dvm_min_vote_share REAL DEFAULT 0.65")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_dispute_take_profit_enabled = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_take_profit_enabled");
+        if !settings_info.is_empty() && !has_dispute_take_profit_enabled {
+            info!("Migrating auto_trading_settings: adding dispute_take_profit_enabled column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_take_profit_enabled INTEGER DEFAULT 0")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_dispute_take_profit_price = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_take_profit_price");
+        if !settings_info.is_empty() && !has_dispute_take_profit_price {
+            info!("Migrating auto_trading_settings: adding dispute_take_profit_price column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_take_profit_price REAL DEFAULT 0.97")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_dispute_liveness_exit_enabled = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_liveness_exit_enabled");
+        if !settings_info.is_empty() && !has_dispute_liveness_exit_enabled {
+            info!("Migrating auto_trading_settings: adding dispute_liveness_exit_enabled column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_liveness_exit_enabled INTEGER DEFAULT 0")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_dispute_liveness_exit_minutes = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_liveness_exit_minutes");
+        if !settings_info.is_empty() && !has_dispute_liveness_exit_minutes {
+            info!("Migrating auto_trading_settings: adding dispute_liveness_exit_minutes column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_liveness_exit_minutes INTEGER DEFAULT 15")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_dispute_hold_to_settlement = settings_info.iter().any(|(_, name, _, _, _, _)| name == "dispute_hold_to_settlement");
+        if !settings_info.is_empty() && !has_dispute_hold_to_settlement {
+            info!("Migrating auto_trading_settings: adding dispute_hold_to_settlement column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN dispute_hold_to_settlement INTEGER DEFAULT 0")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let has_market_filter = settings_info.iter().any(|(_, name, _, _, _, _)| name == "market_filter");
+        if !settings_info.is_empty() && !has_market_filter {
+            info!("Migrating auto_trading_settings: adding market_filter column");
+            sqlx::query("ALTER TABLE auto_trading_settings ADD COLUMN market_filter TEXT DEFAULT '{}'")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // ==================== MC_TRADES MIGRATIONS ====================
+        let mc_trades_info: Vec<(i64, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+            "PRAGMA table_info(mc_trades)"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        if !mc_trades_info.is_empty() {
+            let has_end_date = mc_trades_info.iter().any(|(_, name, _, _, _, _)| name == "end_date");
+            if !has_end_date {
+                info!("Migrating mc_trades table: adding end_date column");
+                sqlx::query("ALTER TABLE mc_trades ADD COLUMN end_date TEXT")
+                    .execute(&self.pool).await?;
+            }
+        }
+
+        // ==================== MINT MAKER SETTINGS MIGRATIONS ====================
+        {
+            let mm_info: Vec<(i64, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+                "PRAGMA table_info(mint_maker_settings)"
+            )
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+
+            let has_auto_place = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_place");
+            if !mm_info.is_empty() && !has_auto_place {
+                info!("Migrating mint_maker_settings: adding auto_place column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_place INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_auto_place_size = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_place_size");
+            if !mm_info.is_empty() && !has_auto_place_size {
+                info!("Migrating mint_maker_settings: adding auto_place_size column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_place_size TEXT DEFAULT '2'")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_auto_max_markets = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_max_markets");
+            if !mm_info.is_empty() && !has_auto_max_markets {
+                info!("Migrating mint_maker_settings: adding auto_max_markets column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_max_markets INTEGER DEFAULT 1")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_auto_redeem = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_redeem");
+            if !mm_info.is_empty() && !has_auto_redeem {
+                info!("Migrating mint_maker_settings: adding auto_redeem column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_redeem INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_stop_loss_pct = mm_info.iter().any(|(_, name, _, _, _, _)| name == "stop_loss_pct");
+            if !mm_info.is_empty() && !has_stop_loss_pct {
+                info!("Migrating mint_maker_settings: adding stop_loss_pct column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN stop_loss_pct INTEGER DEFAULT 25")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_stop_loss_delay = mm_info.iter().any(|(_, name, _, _, _, _)| name == "stop_loss_delay_secs");
+            if !mm_info.is_empty() && !has_stop_loss_delay {
+                info!("Migrating mint_maker_settings: adding stop_loss_delay_secs column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN stop_loss_delay_secs INTEGER DEFAULT 30")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_place_delay = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_place_delay_mins");
+            if !mm_info.is_empty() && !has_place_delay {
+                info!("Migrating mint_maker_settings: adding auto_place_delay_mins column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_place_delay_mins INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_auto_size_pct = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_size_pct");
+            if !mm_info.is_empty() && !has_auto_size_pct {
+                info!("Migrating mint_maker_settings: adding auto_size_pct column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_size_pct INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_auto_max_attempts = mm_info.iter().any(|(_, name, _, _, _, _)| name == "auto_max_attempts");
+            if !mm_info.is_empty() && !has_auto_max_attempts {
+                info!("Migrating mint_maker_settings: adding auto_max_attempts column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN auto_max_attempts INTEGER DEFAULT 1")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_balance_reserve = mm_info.iter().any(|(_, name, _, _, _, _)| name == "balance_reserve");
+            if !mm_info.is_empty() && !has_balance_reserve {
+                info!("Migrating mint_maker_settings: adding balance_reserve column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN balance_reserve REAL DEFAULT 0.0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_smart_mode = mm_info.iter().any(|(_, name, _, _, _, _)| name == "smart_mode");
+            if !mm_info.is_empty() && !has_smart_mode {
+                info!("Migrating mint_maker_settings: adding smart_mode column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN smart_mode INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+            let has_pre_place = mm_info.iter().any(|(_, name, _, _, _, _)| name == "pre_place");
+            if !mm_info.is_empty() && !has_pre_place {
+                info!("Migrating mint_maker_settings: adding pre_place column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN pre_place INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_stop_after_profit = mm_info.iter().any(|(_, name, _, _, _, _)| name == "stop_after_profit");
+            if !mm_info.is_empty() && !has_stop_after_profit {
+                info!("Migrating mint_maker_settings: adding stop_after_profit column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN stop_after_profit INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_relay_backoff = mm_info.iter().any(|(_, name, _, _, _, _)| name == "relay_backoff_until");
+            if !mm_info.is_empty() && !has_relay_backoff {
+                info!("Migrating mint_maker_settings: adding relay_backoff_until column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN relay_backoff_until TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_momentum_threshold = mm_info.iter().any(|(_, name, _, _, _, _)| name == "momentum_threshold");
+            if !mm_info.is_empty() && !has_momentum_threshold {
+                info!("Migrating mint_maker_settings: adding momentum_threshold column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN momentum_threshold REAL DEFAULT 0.10")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_depth_check = mm_info.iter().any(|(_, name, _, _, _, _)| name == "depth_check");
+            if !mm_info.is_empty() && !has_depth_check {
+                info!("Migrating mint_maker_settings: adding depth_check column");
+                sqlx::query("ALTER TABLE mint_maker_settings ADD COLUMN depth_check INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+
+        // ==================== MINT MAKER PAIRS MIGRATIONS ====================
+        {
+            let mm_pairs_info: Vec<(i64, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+                "PRAGMA table_info(mint_maker_pairs)"
+            )
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+
+            let has_yes_size = mm_pairs_info.iter().any(|(_, name, _, _, _, _)| name == "yes_size");
+            if !mm_pairs_info.is_empty() && !has_yes_size {
+                info!("Migrating mint_maker_pairs: adding yes_size column");
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN yes_size TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_no_size = mm_pairs_info.iter().any(|(_, name, _, _, _, _)| name == "no_size");
+            if !mm_pairs_info.is_empty() && !has_no_size {
+                info!("Migrating mint_maker_pairs: adding no_size column");
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN no_size TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_slug = mm_pairs_info.iter().any(|(_, name, _, _, _, _)| name == "slug");
+            if !mm_pairs_info.is_empty() && !has_slug {
+                info!("Migrating mint_maker_pairs: adding slug column");
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN slug TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_yes_token_id = mm_pairs_info.iter().any(|(_, name, _, _, _, _)| name == "yes_token_id");
+            if !mm_pairs_info.is_empty() && !has_yes_token_id {
+                info!("Migrating mint_maker_pairs: adding yes_token_id, no_token_id, neg_risk columns");
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN yes_token_id TEXT")
+                    .execute(&self.pool)
+                    .await?;
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN no_token_id TEXT")
+                    .execute(&self.pool)
+                    .await?;
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN neg_risk INTEGER DEFAULT 0")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_stop_loss_order_id = mm_pairs_info.iter().any(|(_, name, _, _, _, _)| name == "stop_loss_order_id");
+            if !mm_pairs_info.is_empty() && !has_stop_loss_order_id {
+                info!("Migrating mint_maker_pairs: adding stop_loss_order_id column");
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN stop_loss_order_id TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_redeem_tx_id = mm_pairs_info.iter().any(|(_, name, _, _, _, _)| name == "redeem_tx_id");
+            if !mm_pairs_info.is_empty() && !has_redeem_tx_id {
+                info!("Migrating mint_maker_pairs: adding redeem_tx_id column");
+                sqlx::query("ALTER TABLE mint_maker_pairs ADD COLUMN redeem_tx_id TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+
+        // ==================== WALLET SAFEGUARD MIGRATIONS ====================
+        {
+            let wallets_info: Vec<(i64, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+                "PRAGMA table_info(wallets)"
+            )
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+
+            let has_password_changed_at = wallets_info.iter().any(|(_, name, _, _, _, _)| name == "password_changed_at");
+            if !wallets_info.is_empty() && !has_password_changed_at {
+                info!("Migrating wallets: adding password_changed_at column");
+                sqlx::query("ALTER TABLE wallets ADD COLUMN password_changed_at TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            let has_withdraw_daily_limit = wallets_info.iter().any(|(_, name, _, _, _, _)| name == "withdraw_daily_limit");
+            if !wallets_info.is_empty() && !has_withdraw_daily_limit {
+                info!("Migrating wallets: adding withdraw_daily_limit column");
+                sqlx::query("ALTER TABLE wallets ADD COLUMN withdraw_daily_limit TEXT")
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+
+        if !table_info.is_empty() {
+            // Fix NULL values in is_paper column - treat all NULL as paper trades (1)
+            sqlx::query("UPDATE positions SET is_paper = 1 WHERE is_paper IS NULL")
+                .execute(&self.pool)
+                .await?;
+
+            // Backfill remaining_size for existing open positions (remaining = original size in shares)
+            // remaining_size stores the number of shares remaining, not USDC amount
+            // For existing positions, we calculate shares as size / entry_price
+            sqlx::query(
+                "UPDATE positions SET remaining_size = CAST(size AS REAL) / CAST(entry_price AS REAL)
+                 WHERE remaining_size IS NULL AND status IN ('Open', 'PendingResolution')"
+            )
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Bring the schema up to date and run one-off data backfills
+    async fn initialize(&self) -> Result<()> {
+        self.migrate().await?;
+
+        // The ledger arrived after trading history: rebuild it once from the existing records
+        self.backfill_ledger().await?;
+
+        info!("Database initialized");
+        Ok(())
+    }
+
+    /// Apply pending migrations, backing an existing database up first.
+    /// Returns the versions applied.
+    pub async fn migrate(&self) -> Result<Vec<i64>> {
+        let status = self.migration_status().await?;
+        if status.current > status.latest {
+            anyhow::bail!(
+                "Database schema is at version {} but this build only knows up to {}",
+                status.current,
+                status.latest
+            );
+        }
+        for version in &status.modified {
+            warn!("Migration {} was changed after it was applied to this database", version);
+        }
+        if status.pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let legacy = status.current == 0 && migrations::table_exists(&self.pool, "wallets").await?;
+        if status.current > 0 || legacy {
+            if let Some(path) = migrations::backup(&self.pool, status.current).await? {
+                info!("Backed up database to {} before migrating", path.display());
+            }
+        }
+        if legacy {
+            info!("Upgrading database created before versioned migrations");
+            self.upgrade_legacy_schema().await?;
+        }
+
+        migrations::ensure_version_table(&self.pool).await?;
+        let mut applied = Vec::new();
+        for migration in status.pending {
+            info!("Applying migration {}", migration.file_name());
+            migrations::apply(&self.pool, migration).await?;
+            applied.push(migration.version);
+        }
+
+        Ok(applied)
+    }
+
+    /// Applied and pending migrations, without changing anything
+    pub async fn migration_status(&self) -> Result<MigrationStatus> {
+        let applied = migrations::applied(&self.pool).await?;
+        Ok(MigrationStatus::new(applied))
+    }
+
+    /// Record an opportunity found
+    pub async fn record_opportunity(&self, opp: &Opportunity) -> Result<i64> {
+        let now = Utc::now().to_rfc3339();
+        let strategy_str = format!("{:?}", opp.strategy);
+        let side_str = format!("{:?}", opp.side);
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO opportunities (market_id, question, slug, strategy, side, entry_price, expected_return, edge, time_to_close_hours, liquidity, found_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&opp.market_id)
+        .bind(&opp.question)
+        .bind(&opp.slug)
+        .bind(strategy_str)
+        .bind(side_str)
+        .bind(opp.entry_price.to_string())
+        .bind(opp.expected_return)
+        .bind(opp.edge)
+        .bind(opp.time_to_close_hours)
+        .bind(opp.liquidity.to_string())
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Record a scan
+    pub async fn record_scan(
+        &self,
+        markets_found: i64,
+        sniper_opps: i64,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO scan_history (scanned_at, markets_found, sniper_opportunities, no_bias_opportunities) VALUES (?, ?, ?, 0)",
+        )
+        .bind(now)
+        .bind(markets_found)
+        .bind(sniper_opps)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Wallets with an open position on a condition. Dispute positions are keyed by
+    /// condition id, the others by Gamma market id (resolved through the market index).
+    pub async fn get_wallets_holding_condition(&self, condition_id: &str) -> Result<Vec<String>> {
+        let mut market_ids = self.market_index().get_condition_market_ids(condition_id).await?;
+        market_ids.push(condition_id.to_string());
+
+        self.positions().get_wallets_holding_markets(&market_ids).await
+    }
+
+    /// Wallets with open live positions, or positions / Mint Maker pairs closed or updated since `since`
+    pub async fn get_wallets_with_activity_since(&self, since: &DateTime<Utc>) -> Result<Vec<String>> {
+        let mut wallets = self.positions().get_wallets_with_positions_since(since).await?;
+        wallets.extend(self.mint_maker().get_wallets_with_pairs_updated_since(since).await?);
+
+        let mut wallets: Vec<String> = wallets.into_iter().map(|w| w.to_lowercase()).collect();
+        wallets.sort();
+        wallets.dedup();
+        Ok(wallets)
+    }
+
+    // ==================== TRADE EXPORT ====================
+
+    /// Every fill of a wallet's live positions and Mint Maker pairs, rebuilt from the
+    /// position, order and Mint Maker repositories (unsorted)
+    pub async fn get_trade_fills(&self, wallet_address: &str) -> Result<Vec<Fill>> {
+        // Latest filled order per position: its id and fill time describe the buy
+        let orders: HashMap<i64, (String, String)> = self
+            .orders()
+            .get_position_fill_orders(wallet_address)
+            .await?
+            .into_iter()
+            .map(|(id, position_id, updated_at)| (position_id, (id, updated_at)))
+            .collect();
+
+        let mut fills = Vec::new();
+
+        for history in self.positions().get_position_history(wallet_address).await? {
+            let position = &history.position;
+            let order = orders.get(&position.id);
+            let source = FillSource {
+                market_id: position.market_id.clone(),
+                question: position.question.clone(),
+                order_id: order.map(|(id, _)| id.clone()),
+                filled_at: order
+                    .and_then(|(_, at)| DateTime::parse_from_rfc3339(at).ok())
+                    .map(|d| d.with_timezone(&Utc)),
+            };
+            let txns = ledger::backfill_position(position, history.redeemed_at, history.redeem_proceeds);
+            fills.extend(txns.iter().flat_map(|txn| Fill::from_txn(txn, &source)));
+        }
+
+        for pair in self.mint_maker().get_mint_maker_pairs(wallet_address).await? {
+            let source = FillSource {
+                market_id: pair.market_id.clone(),
+                question: pair.question.clone(),
+                ..Default::default()
+            };
+            let txns = ledger::backfill_pair(&pair);
+            fills.extend(txns.iter().flat_map(|txn| Fill::from_txn(txn, &source)));
+        }
+
+        Ok(fills)
+    }
+
+    // ==================== DESCRIPTION HASHES (CLARIFICATION MONITOR) ====================
+
+    /// Get stored description hash for a market
+    pub async fn get_description_hash(&self, market_id: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT description_hash FROM description_hashes WHERE market_id = ?"
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|(hash,)| hash))
+    }
+
+    /// Store or update description hash for a market
+    pub async fn upsert_description_hash(&self, market_id: &str, hash: &str) -> Result<()> {
+        let now = Utc::now().timestamp();
+
+        sqlx::query(
+            r#"
+            INSERT INTO description_hashes (market_id, description_hash, last_updated)
+            VALUES (?, ?, ?)
+            ON CONFLICT(market_id) DO UPDATE SET
+                description_hash = excluded.description_hash,
+                last_updated = excluded.last_updated
+            "#,
+        )
+        .bind(market_id)
+        .bind(hash)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get all stored description hashes (for loading into memory on startup)
+    pub async fn get_all_description_hashes(&self) -> Result<Vec<(String, String)>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT market_id, description_hash FROM description_hashes"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Record a submitted deposit into the trading wallet
+    pub async fn record_deposit(&self, wallet_address: &str, amount: Decimal, transaction_id: &str) -> Result<()> {
+        self.ledger()
+            .post_ledger(&LedgerTxn::deposit(wallet_address, amount).key(format!("deposit:{}", transaction_id)))
+            .await?;
+        Ok(())
+    }
+}
+
+// ==================== MARKET INDEX DB TYPES ====================
+
+/// Persisted market index entry
+pub struct IndexedMarketRow {
+    pub id: String,
+    /// Gamma `updatedAt` when the market was indexed
+    pub updated_at: Option<String>,
+    /// Serialized `TrackedMarket`
+    pub market_json: String,
+}
+
+/// Logged scan cycle cost
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanCostRow {
+    pub mode: String,
+    pub started_at: String,
+    pub requests: i64,
+    pub markets_fetched: i64,
+    pub added: i64,
+    pub updated: i64,
+    pub removed: i64,
+    pub duration_ms: i64,
+}
+
+// ==================== WITHDRAWAL SAFEGUARD DB TYPES ====================
+
+/// Allowlisted withdrawal destination
+#[derive(Debug, Clone, Serialize)]
+pub struct WithdrawalAddressRow {
+    pub id: i64,
+    pub address: String,
+    pub label: Option<String>,
+    pub created_at: String,
+    /// When withdrawals to this address unlock
+    pub active_at: String,
+}
+
+/// Outcome of reserving a withdrawal against the rolling daily limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WithdrawalReservation {
+    /// A `pending` withdrawal row was inserted
+    Reserved { id: i64 },
+    /// The amount would exceed the limit; nothing was inserted
+    OverLimit { withdrawn: Decimal },
+}
+
+// ==================== KEY VAULT DB TYPES ====================
+
+/// A wallet key wrapped with the server master key (hex-encoded)
+pub struct WrappedKeyRow {
+    pub wallet_address: String,
+    pub ciphertext: String,
+    pub nonce: String,
+    /// Fingerprint of the master key that wrapped it
+    pub master_key_id: String,
+}
+
+/// Key vault audit log entry
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyAuditEntry {
+    pub id: i64,
+    pub wallet_address: Option<String>,
+    pub action: String,
+    pub detail: Option<String>,
+    pub created_at: String,
+}
+
+/// MC tier history row
+pub struct McTierHistoryRow {
+    pub id: i64,
+    pub from_tier: i32,
+    pub to_tier: i32,
+    pub bankroll: String,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+// ==================== MINT MAKER DB TYPES ====================
+
+/// Mint Maker settings from DB
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintMakerSettingsRow {
+    pub wallet_address: String,
+    pub enabled: bool,
+    pub preset: String,
+    pub bid_offset_cents: i32,
+    pub max_pair_cost: f64,
+    pub min_spread_profit: f64,
+    pub max_pairs_per_market: i32,
+    pub max_total_pairs: i32,
+    pub stale_order_seconds: i64,
+    pub assets: Vec<String>,
+    pub min_minutes_to_close: f64,
+    pub max_minutes_to_close: f64,
+    pub auto_place: bool,
+    pub auto_place_size: String,
+    pub auto_max_markets: i32,
+    pub auto_redeem: bool,
+    pub stop_loss_pct: i32,
+    pub stop_loss_delay_secs: i32,
+    pub auto_place_delay_mins: i32,
+    pub auto_size_pct: i32,
+    pub auto_max_attempts: i32,
+    pub balance_reserve: f64,
+    pub smart_mode: bool,
+    pub pre_place: bool,
+    pub stop_after_profit: bool,
+    /// Relay backoff until (RFC3339 timestamp) - persisted across restarts
+    pub relay_backoff_until: Option<String>,
+    /// Momentum threshold: minimum |price - 0.50| to place (e.g., 0.10 means only enter at 60/40 or more extreme)
+    pub momentum_threshold: f64,
+    /// Whether to check orderbook depth confirms the momentum signal
+    pub depth_check: bool,
+}
+
+/// Mint Maker log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintMakerLogEntry {
+    pub id: i64,
+    pub wallet_address: String,
+    pub action: String,
+    pub market_id: Option<String>,
+    pub question: Option<String>,
+    pub asset: Option<String>,
+    pub yes_price: Option<String>,
+    pub no_price: Option<String>,
+    pub pair_cost: Option<String>,
+    pub profit: Option<String>,
+    pub size: Option<String>,
+    pub details: Option<String>,
+    pub created_at: String,
+}
+
+/// Mint Maker pair row from DB
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintMakerPairRow {
+    pub id: i64,
+    pub wallet_address: String,
+    pub market_id: String,
+    pub condition_id: String,
+    pub question: String,
+    pub asset: String,
+    pub yes_order_id: String,
+    pub no_order_id: String,
+    pub yes_bid_price: String,
+    pub no_bid_price: String,
+    pub yes_fill_price: Option<String>,
+    pub no_fill_price: Option<String>,
+    pub pair_cost: Option<String>,
+    pub profit: Option<String>,
+    pub size: String,
+    pub yes_size: Option<String>,
+    pub no_size: Option<String>,
+    pub slug: Option<String>,
+    pub yes_token_id: Option<String>,
+    pub no_token_id: Option<String>,
+    pub neg_risk: bool,
+    pub status: String,
+    pub merge_tx_id: Option<String>,
+    pub stop_loss_order_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Mint Maker analytics summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintMakerAnalyticsSummary {
+    pub total_pairs: i64,
+    pub merged: i64,
+    pub orphan_wins: i64,
+    pub orphan_losses: i64,
+    pub orphan_pending: i64,
+    pub merge_pnl: f64,
+    pub orphan_win_pnl: f64,
+    pub orphan_loss_pnl: f64,
+}
+
+/// MC trade full row (for API responses)
+pub struct McTradeFullRow {
+    pub id: i64,
+    pub market_id: String,
+    pub condition_id: String,
+    pub question: String,
+    pub slug: String,
+    pub side: String,
+    pub entry_price: String,
+    pub exit_price: Option<String>,
+    pub size: String,
+    pub shares: String,
+    pub pnl: Option<String>,
+    pub certainty_score: i32,
+    pub category: Option<String>,
+    pub status: String,
+    pub tier_at_entry: i32,
+    pub token_id: Option<String>,
+    pub end_date: Option<String>,
+    pub opened_at: String,
+    pub closed_at: Option<String>,
+}
@@ -0,0 +1,116 @@
+//! PostgreSQL store for state shared between server instances
+//!
+//! Built with the `postgres` feature and attached when `DATABASE_URL` is set.
+//! `PgStore` implements the same repository traits as the local SQLite
+//! database, so every instance pointed at the same URL sees the same wallets,
+//! sessions, positions, orders, Mint Maker pairs, MC trades, ledger,
+//! auto-trading settings, on-chain jobs, withdrawals, wrapped keys,
+//! notification channels, reports, market index and chain cursors.
+
+crate::db::sql::repositories!(crate::db::postgres::PgStore, sqlx::PgConnection, sqlx::postgres::PgRow);
+
+use crate::migrations::{AppliedMigration, MigrationStatus, POSTGRES_MIGRATIONS};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use tracing::{info, warn};
+
+/// Advisory lock key held while migrating, so instances starting together
+/// don't apply the same migration twice
+const MIGRATION_LOCK: i64 = 0x706f_6c79_6d6b_7401;
+
+/// Shared PostgreSQL state
+pub struct PgStore {
+    pool: PgPool,
+}
+
+impl PgStore {
+    /// Connect and apply pending migrations
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(10)
+            .connect(url)
+            .await
+            .context("Failed to connect to PostgreSQL")?;
+
+        let store = Self { pool };
+        store.migrate().await?;
+
+        Ok(store)
+    }
+
+    /// Close the pool once in-flight queries finish
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Apply pending migrations in one transaction under the advisory lock
+    pub async fn migrate(&self) -> Result<Vec<i64>> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(MIGRATION_LOCK)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS schema_version (
+                version BIGINT PRIMARY KEY,
+                name TEXT NOT NULL,
+                checksum TEXT NOT NULL,
+                applied_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let rows: Vec<(i64, String, String, String)> = sqlx::query_as(
+            "SELECT version, name, checksum, applied_at FROM schema_version ORDER BY version",
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let applied = rows
+            .into_iter()
+            .map(|(version, name, checksum, applied_at)| AppliedMigration {
+                version,
+                name,
+                checksum,
+                applied_at,
+            })
+            .collect();
+
+        let status = MigrationStatus::from_migrations(POSTGRES_MIGRATIONS, applied);
+        if status.current > status.latest {
+            anyhow::bail!(
+                "PostgreSQL schema is at version {} but this build only knows up to {}",
+                status.current,
+                status.latest
+            );
+        }
+        for version in &status.modified {
+            warn!("PostgreSQL migration {} was changed after it was applied", version);
+        }
+
+        let mut applied = Vec::new();
+        for migration in status.pending {
+            info!("Applying PostgreSQL migration {}", migration.file_name());
+            sqlx::raw_sql(migration.sql)
+                .execute(&mut *tx)
+                .await
+                .with_context(|| format!("PostgreSQL migration {} failed", migration.file_name()))?;
+
+            sqlx::query("INSERT INTO schema_version (version, name, checksum, applied_at) VALUES ($1, $2, $3, $4)")
+                .bind(migration.version)
+                .bind(migration.name)
+                .bind(migration.checksum())
+                .bind(Utc::now().to_rfc3339())
+                .execute(&mut *tx)
+                .await?;
+            applied.push(migration.version);
+        }
+
+        tx.commit().await?;
+        Ok(applied)
+    }
+}
@@ -0,0 +1,976 @@
+//! Domain repositories
+//!
+//! The state several server instances must agree on - wallets and sessions,
+//! positions, orders, Mint Maker, Millionaires Club, the ledger they post to,
+//! auto-trading settings, on-chain jobs, withdrawals, wrapped keys,
+//! notifications, reports, the market index and chain cursors - is reached
+//! through one trait per domain. `Database` implements every trait on its SQLite
+//! pool; with the `postgres` feature and `DATABASE_URL` set, its accessors
+//! (`db.positions()`, `db.auth()`, ...) hand out the shared PostgreSQL store
+//! instead. Only scan bookkeeping (opportunities, scan history, description
+//! hashes) stays in the local SQLite file. Leader-election leases live wherever
+//! the shared domains do.
+
+use super::{
+    ApiToken, IndexedMarketRow, KeyAuditEntry, Lease, McTierHistoryRow, McTradeFullRow, MintMakerAnalyticsSummary,
+    MintMakerLogEntry, MintMakerPairRow, MintMakerSettingsRow, NotificationChannel, PartialCloseResult,
+    PositionHistory, ScanCostRow, Session, StoredWallet, WalletWithKey, WithdrawalAddressRow, WithdrawalReservation,
+    WrappedKeyRow,
+};
+use crate::ledger::{Holding, LedgerEntry, LedgerSummary, LedgerTxn};
+use crate::market_index::ScanCost;
+use crate::services::auto_trader::{AutoTradeLog, AutoTradingSettings, AutoTradingStats};
+use crate::services::reports::Report;
+use crate::services::uma_events::ChainAssertion;
+use crate::types::{BotStats, OnchainJob, OnchainJobKind, OnchainJobStatus, Position, PositionStatus, Side, StrategyType};
+use crate::wallet::safeguards::WithdrawLimitSetting;
+use crate::wallet::EncryptedKey;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Wallets, sessions, API tokens and CLOB credentials
+#[async_trait]
+pub trait AuthRepository: Send + Sync {
+    /// Store API credentials for a wallet
+    async fn store_api_credentials(
+        &self,
+        wallet_address: &str,
+        api_key: &str,
+        api_secret: &str,
+        api_passphrase: &str,
+    ) -> Result<()>;
+
+    /// Get API credentials for a wallet
+    async fn get_api_credentials(
+        &self,
+        wallet_address: &str,
+    ) -> Result<Option<(String, String, String)>>;
+
+    /// Delete stored API credentials for a wallet (forces re-derivation)
+    async fn delete_api_credentials(&self, wallet_address: &str) -> Result<()>;
+
+    /// Get all wallets that have API credentials stored
+    async fn get_wallets_with_api_credentials(&self) -> Result<Vec<(String, String, String, String)>>;
+
+    // ==================== WALLET MANAGEMENT ====================
+
+    /// Create a new wallet record (optionally with encrypted private key)
+    async fn create_wallet(
+        &self,
+        address: &str,
+        encrypted_key: Option<&EncryptedKey>,
+    ) -> Result<i64>;
+
+    /// Get wallet by address
+    async fn get_wallet(&self, address: &str) -> Result<Option<StoredWallet>>;
+
+    /// Get encrypted key for a wallet
+    async fn get_encrypted_key(&self, address: &str) -> Result<Option<EncryptedKey>>;
+
+    /// Update wallet last active time
+    async fn update_wallet_activity(&self, address: &str) -> Result<()>;
+
+    // ==================== SESSION MANAGEMENT ====================
+
+    /// Create a new session for a wallet
+    async fn create_session(&self, wallet_address: &str) -> Result<Session>;
+
+    /// Validate and get session
+    async fn get_session(&self, session_id: &str) -> Result<Option<Session>>;
+
+    /// Delete a session
+    async fn delete_session(&self, session_id: &str) -> Result<()>;
+
+    /// Clean up expired sessions
+    async fn cleanup_expired_sessions(&self) -> Result<u64>;
+
+    /// Get distinct wallet addresses with active (non-expired) sessions
+    async fn get_active_wallet_addresses(&self) -> Result<Vec<String>>;
+
+    // ==================== API TOKENS ====================
+
+    /// Create an API token. `token_hash` is the SHA-256 hex of the secret handed to the user.
+    async fn create_api_token(
+        &self,
+        wallet_address: &str,
+        name: &str,
+        token_hash: &str,
+        scopes: &str,
+        rate_limit_per_minute: i64,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiToken>;
+
+    /// Look up an active (not revoked, not expired) API token by the hash of its secret
+    async fn get_active_api_token(&self, token_hash: &str) -> Result<Option<ApiToken>>;
+
+    /// All API tokens of a wallet, including revoked ones (newest first)
+    async fn list_api_tokens(&self, wallet_address: &str) -> Result<Vec<ApiToken>>;
+
+    /// Revoke a wallet's API token. Returns false if no such active token exists.
+    async fn revoke_api_token(&self, wallet_address: &str, token_id: &str) -> Result<bool>;
+
+    /// Record that an API token was just used
+    async fn touch_api_token(&self, token_id: &str) -> Result<()>;
+
+    /// Get wallet by address (with encrypted key data)
+    async fn get_wallet_by_address(&self, address: &str) -> Result<Option<WalletWithKey>>;
+
+    // ==================== WITHDRAWAL SAFEGUARDS ====================
+
+    /// Replace a wallet's encrypted key (password change) and start the post-change cool-down
+    async fn update_encrypted_key(&self, address: &str, encrypted_key: &EncryptedKey) -> Result<()>;
+
+    /// When the wallet's password last changed
+    async fn get_password_changed_at(&self, address: &str) -> Result<Option<DateTime<Utc>>>;
+
+    /// Wallet's own daily withdrawal limit and any time-locked raise
+    async fn get_withdraw_limit(&self, address: &str) -> Result<WithdrawLimitSetting>;
+
+    /// Replace a wallet's own daily withdrawal limit setting
+    async fn set_withdraw_limit(&self, address: &str, setting: &WithdrawLimitSetting) -> Result<()>;
+}
+
+/// Positions and their trailing-stop peaks
+#[async_trait]
+pub trait PositionRepository: Send + Sync {
+    /// Record a new position
+    async fn create_position(
+        &self,
+        market_id: &str,
+        question: &str,
+        side: Side,
+        entry_price: Decimal,
+        size: Decimal,
+        strategy: StrategyType,
+        is_paper: bool,
+    ) -> Result<i64>;
+
+    /// Update position status
+    async fn update_position_status(&self, id: i64, status: PositionStatus) -> Result<()>;
+
+    /// Close a position with result, posting its ledger entry in the same transaction:
+    /// a sale at `exit_price` when closed by an order, otherwise a resolution at that payout
+    async fn close_position(
+        &self,
+        id: i64,
+        exit_price: Decimal,
+        order_id: Option<&str>,
+    ) -> Result<()>;
+
+    /// Update position end_date (for backfilling existing positions)
+    async fn update_position_end_date(&self, id: i64, end_date: DateTime<Utc>) -> Result<()>;
+
+    /// Get all open positions
+    async fn get_open_positions(&self) -> Result<Vec<Position>>;
+
+    /// Get position by market ID
+    async fn get_position_by_market(&self, market_id: &str) -> Result<Option<Position>>;
+
+    /// Get bot statistics
+    async fn get_stats(&self) -> Result<BotStats>;
+
+    // ==================== POSITION MANAGEMENT (MULTI-USER) ====================
+
+    /// Record a new position for a specific wallet
+    async fn create_position_for_wallet(
+        &self,
+        wallet_address: &str,
+        market_id: &str,
+        question: &str,
+        slug: Option<&str>,
+        side: Side,
+        entry_price: Decimal,
+        size: Decimal,
+        strategy: StrategyType,
+        is_paper: bool,
+        end_date: Option<DateTime<Utc>>,
+        token_id: Option<&str>,
+        order_id: Option<&str>,
+        neg_risk: bool,
+    ) -> Result<i64>;
+
+    /// Get open positions for a specific wallet
+    async fn get_positions_for_wallet(&self, wallet_address: &str) -> Result<Vec<Position>>;
+
+    /// Get open positions for a specific wallet (live trades only)
+    async fn get_open_positions_for_wallet(&self, wallet_address: &str) -> Result<Vec<Position>>;
+
+    /// Get stats for a specific wallet: realized results of every strategy, from the ledger
+    async fn get_stats_for_wallet(&self, wallet_address: &str) -> Result<BotStats>;
+
+    /// Close a position and calculate PnL (with optional fee deduction)
+    async fn close_position_for_wallet(
+        &self,
+        wallet_address: &str,
+        position_id: i64,
+        exit_price: Decimal,
+        fee: Option<Decimal>,
+    ) -> Result<Decimal>;
+
+    /// Partially close a position (sell some shares, keep the rest)
+    /// Returns PnL for this sell and whether position is fully closed
+    async fn partial_close_position_for_wallet(
+        &self,
+        wallet_address: &str,
+        position_id: i64,
+        sell_shares: Decimal,
+        exit_price: Decimal,
+    ) -> Result<PartialCloseResult>;
+
+    /// Update token_id for an existing position (for backfilling)
+    async fn update_position_token_id(
+        &self,
+        wallet_address: &str,
+        position_id: i64,
+        token_id: &str,
+    ) -> Result<()>;
+
+    /// Update entry_price for an existing position (for corrections)
+    async fn update_position_entry_price(
+        &self,
+        wallet_address: &str,
+        position_id: i64,
+        entry_price: &str,
+    ) -> Result<()>;
+
+    /// Get a single position by ID for a wallet
+    async fn get_position_by_id(
+        &self,
+        wallet_address: &str,
+        position_id: i64,
+    ) -> Result<Option<Position>>;
+
+    /// Get all token_ids from open positions (across all wallets)
+    /// Used for real-time price subscriptions
+    async fn get_all_open_position_token_ids(&self) -> Result<Vec<String>>;
+
+    // ==================== POSITION PEAKS (TRAILING STOP) ====================
+
+    /// Update or create peak price for a position
+    async fn update_position_peak(&self, position_id: i64, price: Decimal) -> Result<()>;
+
+    /// Get peak price for a position
+    async fn get_position_peak(&self, position_id: i64) -> Result<Option<Decimal>>;
+
+    /// Delete peak price when position is closed
+    async fn delete_position_peak(&self, position_id: i64) -> Result<()>;
+
+    /// Get all position peaks (for loading into memory on startup)
+    async fn get_all_position_peaks(&self) -> Result<Vec<crate::services::auto_trader::PositionPeak>>;
+
+    // ==================== POSITION QUERIES FOR AUTO-TRADING ====================
+
+    /// Get positions by token_id (for price update handling)
+    async fn get_positions_by_token_id(&self, token_id: &str) -> Result<Vec<Position>>;
+
+    /// Check if wallet has open position in a market
+    async fn has_open_position(&self, wallet_address: &str, market_id: &str) -> Result<bool>;
+
+    /// Check if wallet has open dispute position for a condition_id
+    async fn has_open_dispute_position(&self, wallet_address: &str, condition_id: &str) -> Result<bool>;
+
+    /// Get live positions whose tokens may be redeemable and haven't been checked yet:
+    /// resolved or closed positions, dispute positions, and positions past their end date.
+    /// Positions already redeemed or with no shares left (sold) are excluded.
+    async fn get_redeem_candidates(&self, wallet_address: &str) -> Result<Vec<Position>>;
+
+    /// Record an on-chain redemption: closes the position at the realized payout per share
+    /// (recomputing PnL) and stores the USDC proceeds and relay transaction
+    async fn record_position_redemption(
+        &self,
+        position_id: i64,
+        payout: Decimal,
+        proceeds: Decimal,
+        redeem_tx_id: &str,
+    ) -> Result<()>;
+
+    /// Mark a position's tokens as redeemed (or as having nothing left to redeem)
+    async fn mark_position_redeemed(&self, position_id: i64, redeem_tx_id: Option<&str>) -> Result<()>;
+
+    /// Count open positions for a wallet
+    async fn count_open_positions(&self, wallet_address: &str) -> Result<i32>;
+
+    /// Get total exposure (sum of position sizes) for a wallet
+    async fn get_total_exposure(&self, wallet_address: &str) -> Result<Decimal>;
+
+    /// Get a position by ID (without wallet verification - for internal use)
+    async fn get_position_by_id_internal(&self, position_id: i64) -> Result<Option<Position>>;
+
+    // ==================== REPORTS AND EXPORT ====================
+
+    /// Live positions of a wallet closed or resolved in [from, to)
+    async fn get_closed_positions_between(
+        &self,
+        wallet_address: &str,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> Result<Vec<Position>>;
+
+    /// Wallets with open live positions, or positions closed since `since`
+    async fn get_wallets_with_positions_since(&self, since: &DateTime<Utc>) -> Result<Vec<String>>;
+
+    /// Wallets with an open position on any of these markets
+    async fn get_wallets_holding_markets(&self, market_ids: &[String]) -> Result<Vec<String>>;
+
+    /// Every live position of a wallet with its redemption time and proceeds, oldest first
+    async fn get_position_history(&self, wallet_address: &str) -> Result<Vec<PositionHistory>>;
+}
+
+/// Order lifecycle
+#[async_trait]
+pub trait OrderRepository: Send + Sync {
+    /// Create a new order record
+    async fn create_order(
+        &self,
+        order: &crate::types::Order,
+    ) -> Result<()>;
+
+    /// Update order status (and optionally fill info)
+    async fn update_order_status(
+        &self,
+        order_id: &str,
+        status: crate::types::OrderLifecycleStatus,
+        filled_size: Option<Decimal>,
+        avg_fill_price: Option<Decimal>,
+    ) -> Result<()>;
+
+    /// Get orders for a wallet with optional status filter
+    async fn get_orders_for_wallet(
+        &self,
+        wallet_address: &str,
+        status_filter: Option<&str>,
+    ) -> Result<Vec<crate::types::Order>>;
+
+    /// Get pending orders (for reconciliation)
+    async fn get_pending_orders(&self) -> Result<Vec<crate::types::Order>>;
+
+    /// Filled orders of a wallet that opened positions: (order id, position id, updated_at),
+    /// oldest update first
+    async fn get_position_fill_orders(&self, wallet_address: &str) -> Result<Vec<(String, i64, String)>>;
+}
+
+/// Millionaires Club
+#[async_trait]
+pub trait McRepository: Send + Sync {
+    /// Get MC config (creates default if not exists)
+    async fn mc_get_config(&self) -> Result<crate::services::mc_scanner::McConfig>;
+
+    /// Update MC bankroll and peak
+    async fn mc_update_bankroll(&self, bankroll: &str, peak: &str) -> Result<()>;
+
+    /// Update MC tier
+    async fn mc_update_tier(&self, tier: i32) -> Result<()>;
+
+    /// Update MC mode
+    async fn mc_update_mode(&self, mode: &str) -> Result<()>;
+
+    /// Update MC pause state
+    async fn mc_update_pause_state(&self, state: &str, until: Option<&str>) -> Result<()>;
+
+    /// Insert MC scout log entry
+    async fn mc_insert_scout_log(&self, scout: &crate::services::mc_scanner::McScoutResult) -> Result<()>;
+
+    /// Get MC scout log (paginated)
+    async fn mc_get_scout_log(&self, limit: i64, offset: i64) -> Result<(Vec<crate::services::mc_scanner::McScoutResult>, i64)>;
+
+    /// Insert MC simulated trade
+    async fn mc_insert_trade(
+        &self,
+        market_id: &str,
+        condition_id: &str,
+        question: &str,
+        slug: &str,
+        side: &str,
+        entry_price: &str,
+        size: &str,
+        shares: &str,
+        certainty_score: i32,
+        category: Option<&str>,
+        tier: i32,
+        token_id: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<()>;
+
+    /// Update MC trade resolution
+    async fn mc_update_trade_resolution(&self, trade_id: i64, exit_price: &str, pnl: &str, status: &str) -> Result<()>;
+
+    /// Get MC trades (paginated) — returns full trade data for API
+    async fn mc_get_trades(&self, limit: i64, offset: i64) -> Result<(Vec<McTradeFullRow>, i64)>;
+
+    /// Get MC open trades (for resolution checking)
+    async fn mc_get_open_trades(&self) -> Result<Vec<crate::services::mc_scanner::McTradeRow>>;
+
+    /// Get MC open trade count
+    async fn mc_get_open_trade_count(&self) -> Result<i64>;
+
+    /// Get MC trade stats (total, wins, total_pnl) from the ledger
+    async fn mc_get_trade_stats(&self) -> Result<(i64, i64, f64)>;
+
+    /// Get category trade count (open trades in a category)
+    async fn mc_get_category_trade_count(&self, category: &str) -> Result<i64>;
+
+    /// Insert MC tier history
+    async fn mc_insert_tier_history(&self, from_tier: i32, to_tier: i32, bankroll: &str, reason: &str) -> Result<()>;
+
+    /// Get MC tier history
+    async fn mc_get_tier_history(&self) -> Result<Vec<McTierHistoryRow>>;
+
+    /// Insert MC drawdown event
+    async fn mc_insert_drawdown_event(
+        &self,
+        event_type: &str,
+        peak_bankroll: &str,
+        current_bankroll: &str,
+        drawdown_pct: f64,
+        action_taken: &str,
+    ) -> Result<()>;
+
+    /// Get recent losses in the last N days
+    async fn mc_get_recent_losses(&self, days: i64) -> Result<i64>;
+
+    /// Cleanup old scout logs (keep last N days)
+    async fn mc_cleanup_old_scout_logs(&self, days: i64) -> Result<u64>;
+}
+
+/// Mint Maker settings, pairs, log and analytics
+#[async_trait]
+pub trait MintMakerRepository: Send + Sync {
+    /// Get mint maker settings for a wallet (returns defaults if none exist)
+    async fn get_mint_maker_settings(&self, wallet_address: &str) -> Result<MintMakerSettingsRow>;
+
+    /// Upsert mint maker settings for a wallet
+    async fn upsert_mint_maker_settings(&self, settings: &MintMakerSettingsRow) -> Result<()>;
+
+    /// Update relay backoff timestamp (persisted across restarts)
+    async fn set_mint_maker_relay_backoff(&self, wallet_address: &str, backoff_until: Option<&str>) -> Result<()>;
+
+    /// Replace the saved merge attempt counts with `attempts` (pair id, attempts);
+    /// every other pair goes back to 0
+    async fn save_mint_maker_merge_attempts(&self, attempts: &[(i64, i64)]) -> Result<()>;
+
+    /// Saved merge attempt counts (pair id, attempts) of pairs still matched or merging
+    async fn get_mint_maker_merge_attempts(&self) -> Result<Vec<(i64, i64)>>;
+
+    /// Create a new mint maker pair
+    async fn create_mint_maker_pair(
+        &self,
+        wallet_address: &str,
+        market_id: &str,
+        condition_id: &str,
+        question: &str,
+        asset: &str,
+        yes_order_id: &str,
+        no_order_id: &str,
+        yes_bid_price: &str,
+        no_bid_price: &str,
+        size: &str,
+        yes_size: Option<&str>,
+        no_size: Option<&str>,
+        slug: Option<&str>,
+        yes_token_id: Option<&str>,
+        no_token_id: Option<&str>,
+        neg_risk: bool,
+        status: &str,
+    ) -> Result<i64>;
+
+    /// Update pair status
+    async fn update_mint_maker_pair_status(&self, pair_id: i64, status: &str) -> Result<()>;
+
+    /// Update pair status, pair_cost, and profit on redeem
+    async fn update_mint_maker_pair_redeem(
+        &self,
+        pair_id: i64,
+        status: &str,
+        pair_cost: Option<&str>,
+        profit: Option<&str>,
+        redeem_tx_id: Option<&str>,
+    ) -> Result<()>;
+
+    /// Record the fill of a pair's stop loss sell: `shares` sold at `price`
+    async fn record_mint_maker_stop_loss_fill(&self, pair_id: i64, shares: &str, price: &str) -> Result<()>;
+
+    /// Set the stop loss sell order ID on a pair
+    async fn set_mint_maker_stop_loss_order(&self, pair_id: i64, order_id: &str, status: &str) -> Result<()>;
+
+    /// Get pairs in StopLoss status that have a sell order to track
+    async fn get_mint_maker_stop_loss_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Update pair fill prices and calculate cost/profit
+    async fn update_mint_maker_pair_fill(
+        &self,
+        pair_id: i64,
+        yes_fill: Option<&str>,
+        no_fill: Option<&str>,
+        status: &str,
+    ) -> Result<()>;
+
+    /// After the expensive side fills, set the cheap side order ID and fill price for the expensive side.
+    /// Transitions ExpPlaced → HalfFilled.
+    async fn update_mint_maker_pair_cheap_order(
+        &self,
+        pair_id: i64,
+        cheap_order_id: &str,
+        exp_fill_price: &str,
+        is_yes_expensive: bool,
+    ) -> Result<()>;
+
+    /// Update pair merge size (e.g. to cap at actual filled amount)
+    async fn update_mint_maker_pair_size(&self, pair_id: i64, size: &str) -> Result<()>;
+
+    /// Mark a pair as merged with transaction ID
+    async fn mark_mint_maker_pair_merged(&self, pair_id: i64, merge_tx_id: &str) -> Result<()>;
+
+    /// Get token IDs from all half-filled pairs (across all wallets) for price WebSocket subscription
+    async fn get_mint_maker_half_filled_token_ids(&self) -> Result<Vec<(Option<String>, Option<String>)>>;
+
+    /// Get open pairs for a wallet (Pending, HalfFilled, Matched, Merging, Orphaned, StopLoss)
+    async fn get_mint_maker_open_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Get pairs by status for a wallet
+    async fn get_mint_maker_pairs_by_status(&self, wallet_address: &str, status: &str) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Get all recent pairs for a wallet (for display)
+    async fn get_mint_maker_recent_pairs(&self, wallet_address: &str, limit: i64) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Count open pairs for a specific market
+    async fn count_mint_maker_open_pairs_for_market(&self, wallet_address: &str, market_id: &str) -> Result<i64>;
+
+    /// Count ALL pairs ever placed for a market (regardless of status)
+    async fn count_mint_maker_all_pairs_for_market(&self, wallet_address: &str, market_id: &str) -> Result<i64>;
+
+    /// Count total open pairs for a wallet
+    async fn count_mint_maker_total_open_pairs(&self, wallet_address: &str) -> Result<i64>;
+
+    /// Count pairs with unfilled orders (Pending/HalfFilled only) for a market.
+    /// Matched/Merging pairs are done with the order book and shouldn't block new placements.
+    async fn count_mint_maker_unfilled_pairs_for_market(&self, wallet_address: &str, market_id: &str) -> Result<i64>;
+
+    /// Count total pairs with unfilled orders (Pending/HalfFilled only) across all markets.
+    async fn count_mint_maker_total_unfilled_pairs(&self, wallet_address: &str) -> Result<i64>;
+
+    /// Sum shares from Matched/Merging pairs — capital that will return from merges ($1/share).
+    async fn sum_mint_maker_merging_capital(&self, wallet_address: &str) -> Result<f64>;
+
+    /// Sum USDC already committed to live orders (ExpPlaced/Pending/HalfFilled pairs).
+    /// Uses actual order sizes (yes_size, no_size) for accurate cost calculation.
+    /// This correctly handles inventory-aware pairs where only one side is ordered.
+    async fn sum_mint_maker_committed_capital(&self, wallet_address: &str) -> Result<f64>;
+
+    /// Check if a market already has a successfully merged pair (for stop_after_profit mode).
+    async fn has_mint_maker_merged_pair_for_market(&self, wallet_address: &str, market_id: &str) -> Result<bool>;
+
+    /// Get stale pairs (older than threshold seconds, still pending/half-filled)
+    async fn get_mint_maker_stale_pairs(&self, wallet_address: &str, stale_seconds: i64) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Get pairs eligible for auto-redeem (tokens held but not yet redeemed)
+    async fn get_mint_maker_redeemable_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Get recent fill rate for a specific asset (for smart mode feedback).
+    /// Returns (total_pairs, filled_pairs) from the last N hours.
+    async fn get_mint_maker_asset_fill_rate(&self, wallet_address: &str, asset: &str, hours: i64) -> Result<(i64, i64)>;
+
+    /// Get wallets with mint maker enabled
+    async fn get_mint_maker_enabled_wallets(&self) -> Result<Vec<String>>;
+
+    /// Get wallets that have open mint maker pairs (for stop loss even when disabled)
+    async fn get_mint_maker_wallets_with_open_pairs(&self) -> Result<Vec<String>>;
+
+    /// Get mint maker stats for a wallet: (total, merged, cancelled, realized profit, merged cost, avg spread).
+    /// Profit is the ledger's realized Mint Maker P&L (merges, redemptions and stop losses).
+    async fn get_mint_maker_stats(&self, wallet_address: &str) -> Result<(i64, i64, i64, f64, f64, f64)>;
+
+    /// Log a mint maker action
+    async fn log_mint_maker_action(
+        &self,
+        wallet_address: &str,
+        action: &str,
+        market_id: Option<&str>,
+        question: Option<&str>,
+        asset: Option<&str>,
+        yes_price: Option<&str>,
+        no_price: Option<&str>,
+        pair_cost: Option<&str>,
+        profit: Option<&str>,
+        size: Option<&str>,
+        details: Option<&str>,
+    ) -> Result<()>;
+
+    /// Get mint maker activity log
+    async fn get_mint_maker_log(&self, wallet_address: &str, limit: i64) -> Result<Vec<MintMakerLogEntry>>;
+
+    /// Mint Maker log entries of every wallet with id above `after_id` (oldest first)
+    async fn get_mint_maker_logs_after(&self, after_id: i64, limit: i64) -> Result<Vec<MintMakerLogEntry>>;
+
+    /// Highest Mint Maker log id (0 when empty)
+    async fn get_max_mint_maker_log_id(&self) -> Result<i64>;
+
+    // ==================== MINT MAKER ANALYTICS FUNCTIONS ====================
+
+    /// Create an analytics record when a pair is created
+    async fn create_mint_maker_analytics(
+        &self,
+        wallet_address: &str,
+        pair_id: i64,
+        market_id: &str,
+        asset: Option<&str>,
+        slug: Option<&str>,
+        expensive_side: &str,
+        expensive_price: f64,
+        cheap_price: f64,
+        size: f64,
+    ) -> Result<i64>;
+
+    /// Update analytics when expensive side fills
+    async fn update_analytics_expensive_filled(&self, pair_id: i64) -> Result<()>;
+
+    /// Update analytics when cheap side is placed
+    async fn update_analytics_cheap_placed(&self, pair_id: i64) -> Result<()>;
+
+    /// Update analytics when pair is merged (both sides filled)
+    async fn update_analytics_merged(&self, pair_id: i64, pnl: f64) -> Result<()>;
+
+    /// Update analytics when pair becomes an orphan (only one side filled)
+    async fn update_analytics_orphaned(&self, pair_id: i64, orphan_side: &str) -> Result<()>;
+
+    /// Update analytics when orphan market resolves
+    async fn update_analytics_orphan_resolved(
+        &self,
+        pair_id: i64,
+        market_winner: &str,
+        pnl: f64,
+    ) -> Result<()>;
+
+    /// Get analytics summary for a wallet
+    async fn get_mint_maker_analytics_summary(&self, wallet_address: &str) -> Result<MintMakerAnalyticsSummary>;
+
+    /// Get analytics by asset for a wallet
+    async fn get_mint_maker_analytics_by_asset(&self, wallet_address: &str) -> Result<Vec<(String, i64, i64, i64, i64)>>;
+
+    /// Get pending orphans that need resolution checking
+    async fn get_pending_orphans(&self, wallet_address: &str) -> Result<Vec<(i64, String, String)>>;
+
+    /// Get fill time statistics
+    async fn get_mint_maker_fill_times(&self, wallet_address: &str) -> Result<(f64, f64)>;
+
+    // ==================== REPORTS AND EXPORT ====================
+
+    /// Mint Maker pairs of a wallet merged in [from, to)
+    async fn get_merged_pairs_between(
+        &self,
+        wallet_address: &str,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> Result<Vec<MintMakerPairRow>>;
+
+    /// Mint Maker orphans of a wallet in [from, to):
+    /// (pairs orphaned, cost of their filled sides, P&L of orphans resolved)
+    async fn get_orphan_totals_between(
+        &self,
+        wallet_address: &str,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> Result<(i64, f64, f64)>;
+
+    /// Wallets with Mint Maker pairs updated since `since`
+    async fn get_wallets_with_pairs_updated_since(&self, since: &DateTime<Utc>) -> Result<Vec<String>>;
+
+    /// Every Mint Maker pair of a wallet, oldest first
+    async fn get_mint_maker_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>>;
+}
+
+/// Double-entry ledger
+#[async_trait]
+pub trait LedgerRepository: Send + Sync {
+    /// Record a balanced ledger transaction. Returns None when a transaction with the
+    /// same key is already recorded (the write paths that post can run more than once).
+    async fn post_ledger(&self, txn: &LedgerTxn) -> Result<Option<i64>>;
+
+    /// Token lots still held under a reference (position, pair or MC trade)
+    async fn get_ledger_holdings(&self, reference: &str) -> Result<Vec<Holding>>;
+
+    /// Ledger entries of a wallet (or `ledger::MC_BOOK`), optionally only those since a point in time
+    async fn get_ledger_entries(
+        &self,
+        wallet_address: &str,
+        since: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<LedgerEntry>>;
+
+    /// A wallet's ledger transactions with their postings, newest first
+    async fn get_ledger_transactions(&self, wallet_address: &str, limit: i64, offset: i64) -> Result<Vec<LedgerTxn>>;
+}
+
+/// Leases for leader election between server instances
+#[async_trait]
+pub trait LeaseRepository: Send + Sync {
+    /// Take or renew lease `name` for `holder` until `expires_at`. Succeeds only
+    /// when the lease is free, expired, or already held by `holder`.
+    async fn try_acquire_lease(&self, name: &str, holder: &str, expires_at: DateTime<Utc>) -> Result<bool>;
+
+    /// Give lease `name` up if `holder` still holds it
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<()>;
+
+    /// Current state of lease `name`, expired or not
+    async fn get_lease(&self, name: &str) -> Result<Option<Lease>>;
+}
+
+/// Auto-trading settings and the log of what the auto-trader did
+#[async_trait]
+pub trait AutoTradingRepository: Send + Sync {
+    /// Get auto-trading settings for a wallet (creates default if not exists)
+    async fn get_auto_trading_settings(&self, wallet_address: &str) -> Result<AutoTradingSettings>;
+
+    /// Update auto-trading settings for a wallet
+    async fn update_auto_trading_settings(&self, settings: &AutoTradingSettings) -> Result<()>;
+
+    /// Enable auto-trading for a wallet
+    async fn enable_auto_trading(&self, wallet_address: &str) -> Result<()>;
+
+    /// Disable auto-trading for a wallet
+    async fn disable_auto_trading(&self, wallet_address: &str) -> Result<()>;
+
+    /// Get all wallets with auto-trading enabled
+    async fn get_auto_trading_enabled_wallets(&self) -> Result<Vec<String>>;
+
+    /// Get all wallets with auto-buy enabled
+    async fn get_auto_buy_enabled_wallets(&self) -> Result<Vec<String>>;
+
+    /// Log an auto-trade action
+    async fn log_auto_trade(&self, log: &AutoTradeLog) -> Result<i64>;
+
+    /// Get auto-trade history for a wallet
+    async fn get_auto_trade_history(&self, wallet_address: &str, limit: i64, offset: i64) -> Result<Vec<AutoTradeLog>>;
+
+    /// Auto-trade log entries of every wallet with id above `after_id` (oldest first)
+    async fn get_auto_trade_logs_after(&self, after_id: i64, limit: i64) -> Result<Vec<AutoTradeLog>>;
+
+    /// Highest auto-trade log id (0 when empty)
+    async fn get_max_auto_trade_log_id(&self) -> Result<i64>;
+
+    /// Get auto-trading stats for a wallet
+    async fn get_auto_trading_stats(&self, wallet_address: &str) -> Result<AutoTradingStats>;
+
+    /// Get today's realized PnL (net of fees) from auto-trading for a wallet
+    async fn get_daily_auto_pnl(&self, wallet_address: &str) -> Result<Decimal>;
+}
+
+/// Relay and direct on-chain transactions tracked by the job worker
+#[async_trait]
+pub trait OnchainJobRepository: Send + Sync {
+    /// Record a new on-chain job, returning its ID
+    async fn create_onchain_job(
+        &self,
+        wallet_address: &str,
+        kind: OnchainJobKind,
+        status: OnchainJobStatus,
+        description: &str,
+        reference: Option<&str>,
+        replaces_job_id: Option<i64>,
+    ) -> Result<i64>;
+
+    /// Mark a job submitted with its relay transaction ID and/or transaction hash
+    async fn mark_onchain_job_submitted(
+        &self,
+        id: i64,
+        relay_tx_id: Option<&str>,
+        tx_hash: Option<&str>,
+        payload: Option<&str>,
+    ) -> Result<()>;
+
+    /// Move a job to a new status, optionally recording the mined hash or an error
+    async fn update_onchain_job_status(
+        &self,
+        id: i64,
+        status: OnchainJobStatus,
+        tx_hash: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<()>;
+
+    /// Get a single on-chain job
+    async fn get_onchain_job(&self, id: i64) -> Result<Option<OnchainJob>>;
+
+    /// Get the most recent on-chain jobs for a wallet
+    async fn get_onchain_jobs_for_wallet(&self, wallet_address: &str, limit: i64) -> Result<Vec<OnchainJob>>;
+
+    /// Get all jobs in the given status (for the job worker)
+    async fn get_onchain_jobs_by_status(&self, status: OnchainJobStatus) -> Result<Vec<OnchainJob>>;
+
+    /// Get the job that replaced `id`, if any
+    async fn get_onchain_job_replacement(&self, id: i64) -> Result<Option<OnchainJob>>;
+}
+
+/// Withdrawals and their destination allowlist
+#[async_trait]
+pub trait WithdrawalRepository: Send + Sync {
+    /// Allowlist a withdrawal destination (re-adding restarts its time-lock)
+    async fn add_withdrawal_address(
+        &self,
+        wallet_address: &str,
+        address: &str,
+        label: Option<&str>,
+        active_at: DateTime<Utc>,
+    ) -> Result<WithdrawalAddressRow>;
+
+    /// A wallet's allowlisted withdrawal destinations
+    async fn get_withdrawal_addresses(&self, wallet_address: &str) -> Result<Vec<WithdrawalAddressRow>>;
+
+    /// Remove an allowlisted destination. Returns whether it existed.
+    async fn remove_withdrawal_address(&self, wallet_address: &str, id: i64) -> Result<bool>;
+
+    /// Reserve a `pending` withdrawal unless it would take the wallet's total since `since`
+    /// over `limit`. The wallet row is locked for the check and the insert, so concurrent
+    /// withdrawals can't both pass the limit.
+    async fn reserve_withdrawal(
+        &self,
+        wallet_address: &str,
+        to_address: &str,
+        amount: Decimal,
+        limit: Decimal,
+        since: DateTime<Utc>,
+    ) -> Result<WithdrawalReservation>;
+
+    /// Mark a reserved withdrawal submitted and post it to the ledger in the same transaction
+    async fn record_withdrawal(&self, id: i64, transaction_id: &str) -> Result<()>;
+
+    /// Mark a reserved withdrawal failed so it no longer counts against the limit
+    async fn fail_withdrawal(&self, id: i64) -> Result<()>;
+
+    /// Total USDC a wallet withdrew (or has pending) since a point in time
+    async fn get_withdrawn_since(&self, wallet_address: &str, since: DateTime<Utc>) -> Result<Decimal>;
+}
+
+/// Wallet keys wrapped with the server master key, and the key vault audit log
+#[async_trait]
+pub trait KeyVaultRepository: Send + Sync {
+    /// Store (or replace) a wallet's wrapped key
+    async fn store_wrapped_key(&self, wallet_address: &str, ciphertext: &str, nonce: &str, master_key_id: &str) -> Result<()>;
+
+    /// All wrapped keys
+    async fn get_wrapped_keys(&self) -> Result<Vec<WrappedKeyRow>>;
+
+    /// Delete a wallet's wrapped key. Returns whether one existed.
+    async fn delete_wrapped_key(&self, wallet_address: &str) -> Result<bool>;
+
+    /// Delete every wrapped key. Returns how many were deleted.
+    async fn delete_all_wrapped_keys(&self) -> Result<u64>;
+
+    /// Append a key vault audit entry
+    async fn log_key_audit(&self, wallet_address: Option<&str>, action: &str, detail: Option<&str>) -> Result<()>;
+
+    /// Recent key vault audit entries for a wallet (including vault-wide entries)
+    async fn get_key_audit_log(&self, wallet_address: &str, limit: i64) -> Result<Vec<KeyAuditEntry>>;
+}
+
+/// Notification channels of each wallet
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    /// Create a notification channel. `target` is the JSON `ChannelTarget`, `events` a comma-separated kind list.
+    async fn create_notification_channel(
+        &self,
+        wallet_address: &str,
+        name: &str,
+        target: &str,
+        events: &str,
+        template: Option<&str>,
+        rate_limit_per_minute: i64,
+    ) -> Result<NotificationChannel>;
+
+    /// Notification channels of a wallet (oldest first)
+    async fn get_notification_channels(&self, wallet_address: &str) -> Result<Vec<NotificationChannel>>;
+
+    /// Get one of a wallet's notification channels
+    async fn get_notification_channel(&self, wallet_address: &str, id: i64) -> Result<Option<NotificationChannel>>;
+
+    /// Save a channel's editable fields (name, target, events, template, rate limit, enabled)
+    async fn update_notification_channel(&self, channel: &NotificationChannel) -> Result<()>;
+
+    /// Delete a wallet's notification channel. Returns false if it doesn't exist.
+    async fn delete_notification_channel(&self, wallet_address: &str, id: i64) -> Result<bool>;
+
+    /// Record a delivery attempt (error cleared on success)
+    async fn record_notification_delivery(&self, id: i64, error: Option<&str>) -> Result<()>;
+}
+
+/// Generated daily and weekly reports
+#[async_trait]
+pub trait ReportRepository: Send + Sync {
+    /// Store a report, replacing an earlier one for the same wallet, period and start; returns its id
+    async fn save_report(&self, report: &Report) -> Result<i64>;
+
+    /// A wallet's reports, newest period first (`period` = "daily" / "weekly", None for both)
+    async fn get_reports(&self, wallet_address: &str, period: Option<&str>, limit: i64) -> Result<Vec<(i64, Report)>>;
+
+    /// Get one of a wallet's reports
+    async fn get_report(&self, wallet_address: &str, id: i64) -> Result<Option<(i64, Report)>>;
+
+    /// Wallets that already have a report for a period generated after it ended
+    async fn get_final_report_wallets(
+        &self,
+        period: &str,
+        period_start: NaiveDate,
+        ended_at: &DateTime<Utc>,
+    ) -> Result<Vec<String>>;
+}
+
+/// Incremental scanner's market index and its scan costs
+#[async_trait]
+pub trait MarketIndexRepository: Send + Sync {
+    /// Load every indexed market
+    async fn get_indexed_markets(&self) -> Result<Vec<IndexedMarketRow>>;
+
+    /// Upsert changed markets and delete removed ones in one transaction.
+    /// `upserts` are (id, condition_id, gamma updated_at, market JSON).
+    async fn save_market_index_changes(
+        &self,
+        upserts: &[(String, String, Option<String>, String)],
+        removals: &[String],
+    ) -> Result<()>;
+
+    /// Gamma market ids indexed under a condition
+    async fn get_condition_market_ids(&self, condition_id: &str) -> Result<Vec<String>>;
+
+    /// Record a scan cycle's cost, keeping a week of history
+    async fn record_scan_cost(&self, cost: &ScanCost) -> Result<()>;
+
+    /// Start time of the most recent scan of a mode ("full" / "incremental")
+    async fn get_last_scan_at(&self, mode: &str) -> Result<Option<String>>;
+
+    /// Most recent scan costs, newest first
+    async fn get_scan_costs(&self, limit: i64) -> Result<Vec<ScanCostRow>>;
+}
+
+/// Block cursors of the on-chain log readers and the UMA assertions they read
+#[async_trait]
+pub trait ChainEventRepository: Send + Sync {
+    /// Get the last processed block for an on-chain log reader
+    async fn get_chain_cursor(&self, name: &str) -> Result<Option<i64>>;
+
+    /// Store the last processed block for an on-chain log reader
+    async fn set_chain_cursor(&self, name: &str, last_block: i64) -> Result<()>;
+
+    /// Insert or update an assertion read from chain logs
+    async fn upsert_uma_chain_assertion(&self, assertion: &ChainAssertion) -> Result<()>;
+
+    /// Get chain assertions that have not settled yet (for loading into memory on startup)
+    async fn get_unsettled_uma_chain_assertions(&self) -> Result<Vec<ChainAssertion>>;
+}
+
+// ==================== LEDGER HELPERS ====================
+
+/// Realized results of a wallet's ledger, limited to strategies `keep` accepts (no market marks)
+pub(crate) async fn ledger_stats(
+    ledger: &dyn LedgerRepository,
+    wallet_address: &str,
+    since: Option<&DateTime<Utc>>,
+    keep: impl Fn(Option<&str>) -> bool,
+) -> Result<LedgerSummary> {
+    let entries: Vec<LedgerEntry> = ledger
+        .get_ledger_entries(wallet_address, since)
+        .await?
+        .into_iter()
+        .filter(|e| keep(e.strategy.as_deref()))
+        .collect();
+    Ok(LedgerSummary::from_entries(&entries, &HashMap::new()))
+}
@@ -0,0 +1,479 @@
+//! Wallets, sessions, API tokens and CLOB credentials (AuthRepository)
+
+/// Implement `AuthRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod auth {
+            use crate::db::{ApiToken, AuthRepository, Session, StoredWallet, WalletWithKey};
+            use crate::wallet::safeguards::{PendingLimit, WithdrawLimitSetting};
+            use crate::wallet::EncryptedKey;
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Duration, Utc};
+            use rust_decimal::Decimal;
+            use sqlx::Row;
+            use std::str::FromStr;
+            use uuid::Uuid;
+
+            #[async_trait]
+            impl AuthRepository for $store {
+                async fn store_api_credentials(
+                    &self,
+                    wallet_address: &str,
+                    api_key: &str,
+                    api_secret: &str,
+                    api_passphrase: &str,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        r#"
+                        INSERT INTO api_credentials (wallet_address, api_key, api_secret, api_passphrase, created_at)
+                        VALUES ($1, $2, $3, $4, $5)
+                        ON CONFLICT (wallet_address) DO UPDATE SET
+                            api_key = EXCLUDED.api_key,
+                            api_secret = EXCLUDED.api_secret,
+                            api_passphrase = EXCLUDED.api_passphrase,
+                            created_at = EXCLUDED.created_at
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(api_key)
+                    .bind(api_secret)
+                    .bind(api_passphrase)
+                    .bind(now)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn get_api_credentials(
+                    &self,
+                    wallet_address: &str,
+                ) -> Result<Option<(String, String, String)>> {
+                    let row: Option<(String, String, String)> = sqlx::query_as(
+                        "SELECT api_key, api_secret, api_passphrase FROM api_credentials WHERE wallet_address = $1",
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_optional(&self.pool)
+                    .await?;
+                    Ok(row)
+                }
+
+                async fn delete_api_credentials(&self, wallet_address: &str) -> Result<()> {
+                    sqlx::query("DELETE FROM api_credentials WHERE wallet_address = $1")
+                        .bind(wallet_address.to_lowercase())
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_wallets_with_api_credentials(&self) -> Result<Vec<(String, String, String, String)>> {
+                    let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+                        "SELECT wallet_address, api_key, api_secret, api_passphrase FROM api_credentials",
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+                    Ok(rows)
+                }
+
+                // ==================== WALLET MANAGEMENT ====================
+
+                async fn create_wallet(
+                    &self,
+                    address: &str,
+                    encrypted_key: Option<&EncryptedKey>,
+                ) -> Result<i64> {
+                    let now = Utc::now().to_rfc3339();
+
+                    let (encrypted_private_key, salt, nonce) = match encrypted_key {
+                        Some(ek) => (Some(&ek.ciphertext[..]), Some(&ek.salt[..]), Some(&ek.nonce[..])),
+                        None => (None, None, None),
+                    };
+
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO wallets (address, encrypted_private_key, salt, nonce, created_at)
+                        VALUES ($1, $2, $3, $4, $5)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(address.to_lowercase())
+                    .bind(encrypted_private_key)
+                    .bind(salt)
+                    .bind(nonce)
+                    .bind(now)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(id)
+                }
+
+                async fn get_wallet(&self, address: &str) -> Result<Option<StoredWallet>> {
+                    let row = sqlx::query("SELECT * FROM wallets WHERE address = $1")
+                        .bind(address)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => {
+                            let created_at_str: String = r.get("created_at");
+                            let last_active: Option<String> = r.get("last_active");
+                            let encrypted_key: Option<Vec<u8>> = r.get("encrypted_private_key");
+
+                            Ok(Some(StoredWallet {
+                                id: r.get("id"),
+                                address: r.get("address"),
+                                has_encrypted_key: encrypted_key.is_some(),
+                                created_at: DateTime::parse_from_rfc3339(&created_at_str)?
+                                    .with_timezone(&Utc),
+                                last_active: last_active
+                                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                                    .map(|d| d.with_timezone(&Utc)),
+                            }))
+                        }
+                        None => Ok(None),
+                    }
+                }
+
+                async fn get_encrypted_key(&self, address: &str) -> Result<Option<EncryptedKey>> {
+                    let row = sqlx::query(
+                        "SELECT encrypted_private_key, salt, nonce FROM wallets WHERE LOWER(address) = LOWER($1)",
+                    )
+                    .bind(address)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    match row {
+                        Some(r) => {
+                            let ciphertext: Option<Vec<u8>> = r.get("encrypted_private_key");
+                            let salt: Option<Vec<u8>> = r.get("salt");
+                            let nonce: Option<Vec<u8>> = r.get("nonce");
+
+                            match (ciphertext, salt, nonce) {
+                                (Some(c), Some(s), Some(n)) => Ok(Some(EncryptedKey {
+                                    ciphertext: c,
+                                    salt: s,
+                                    nonce: n,
+                                })),
+                                _ => Ok(None),
+                            }
+                        }
+                        None => Ok(None),
+                    }
+                }
+
+                async fn update_wallet_activity(&self, address: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE wallets SET last_active = $1 WHERE LOWER(address) = LOWER($2)")
+                        .bind(now)
+                        .bind(address)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                // ==================== SESSION MANAGEMENT ====================
+
+                async fn create_session(&self, wallet_address: &str) -> Result<Session> {
+                    let session_id = Uuid::new_v4().to_string();
+                    let now = Utc::now();
+                    let expires_at = now + Duration::hours(24);
+
+                    sqlx::query(
+                        "INSERT INTO sessions (id, wallet_address, created_at, expires_at) VALUES ($1, $2, $3, $4)",
+                    )
+                    .bind(&session_id)
+                    .bind(wallet_address.to_lowercase())
+                    .bind(now.to_rfc3339())
+                    .bind(expires_at.to_rfc3339())
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(Session {
+                        id: session_id,
+                        wallet_address: wallet_address.to_string(),
+                        created_at: now,
+                        expires_at,
+                    })
+                }
+
+                async fn get_session(&self, session_id: &str) -> Result<Option<Session>> {
+                    let row = sqlx::query("SELECT * FROM sessions WHERE id = $1")
+                        .bind(session_id)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => {
+                            let created_at_str: String = r.get("created_at");
+                            let expires_at_str: String = r.get("expires_at");
+                            let expires_at = DateTime::parse_from_rfc3339(&expires_at_str)?
+                                .with_timezone(&Utc);
+
+                            // Check if session is expired
+                            if expires_at < Utc::now() {
+                                // Delete expired session
+                                self.delete_session(session_id).await?;
+                                return Ok(None);
+                            }
+
+                            Ok(Some(Session {
+                                id: r.get("id"),
+                                wallet_address: r.get("wallet_address"),
+                                created_at: DateTime::parse_from_rfc3339(&created_at_str)?
+                                    .with_timezone(&Utc),
+                                expires_at,
+                            }))
+                        }
+                        None => Ok(None),
+                    }
+                }
+
+                async fn delete_session(&self, session_id: &str) -> Result<()> {
+                    sqlx::query("DELETE FROM sessions WHERE id = $1")
+                        .bind(session_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn cleanup_expired_sessions(&self) -> Result<u64> {
+                    let now = Utc::now().to_rfc3339();
+                    let result = sqlx::query("DELETE FROM sessions WHERE expires_at < $1")
+                        .bind(now)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(result.rows_affected())
+                }
+
+                async fn get_active_wallet_addresses(&self) -> Result<Vec<String>> {
+                    let now = Utc::now().to_rfc3339();
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT DISTINCT wallet_address FROM sessions WHERE expires_at > $1"
+                    )
+                    .bind(now)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.into_iter().map(|(addr,)| addr).collect())
+                }
+
+                // ==================== API TOKENS ====================
+
+                async fn create_api_token(
+                    &self,
+                    wallet_address: &str,
+                    name: &str,
+                    token_hash: &str,
+                    scopes: &str,
+                    rate_limit_per_minute: i64,
+                    expires_at: Option<DateTime<Utc>>,
+                ) -> Result<ApiToken> {
+                    let token = ApiToken {
+                        id: Uuid::new_v4().to_string(),
+                        wallet_address: wallet_address.to_lowercase(),
+                        name: name.to_string(),
+                        scopes: scopes.to_string(),
+                        rate_limit_per_minute,
+                        created_at: Utc::now().to_rfc3339(),
+                        expires_at: expires_at.map(|t| t.to_rfc3339()),
+                        last_used_at: None,
+                        revoked_at: None,
+                    };
+
+                    sqlx::query(
+                        r#"
+                        INSERT INTO api_tokens (id, wallet_address, name, token_hash, scopes, rate_limit_per_minute, created_at, expires_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                        "#,
+                    )
+                    .bind(&token.id)
+                    .bind(&token.wallet_address)
+                    .bind(&token.name)
+                    .bind(token_hash)
+                    .bind(&token.scopes)
+                    .bind(token.rate_limit_per_minute)
+                    .bind(&token.created_at)
+                    .bind(&token.expires_at)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(token)
+                }
+
+                async fn get_active_api_token(&self, token_hash: &str) -> Result<Option<ApiToken>> {
+                    let row = sqlx::query(
+                        r#"
+                        SELECT * FROM api_tokens
+                        WHERE token_hash = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires_at > $2)
+                        "#,
+                    )
+                    .bind(token_hash)
+                    .bind(Utc::now().to_rfc3339())
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    Ok(row.map(|r| Self::row_to_api_token(&r)))
+                }
+
+                async fn list_api_tokens(&self, wallet_address: &str) -> Result<Vec<ApiToken>> {
+                    let rows = sqlx::query("SELECT * FROM api_tokens WHERE wallet_address = $1 ORDER BY created_at DESC")
+                        .bind(wallet_address.to_lowercase())
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().map(Self::row_to_api_token).collect())
+                }
+
+                async fn revoke_api_token(&self, wallet_address: &str, token_id: &str) -> Result<bool> {
+                    let result = sqlx::query(
+                        "UPDATE api_tokens SET revoked_at = $1 WHERE id = $2 AND wallet_address = $3 AND revoked_at IS NULL",
+                    )
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(token_id)
+                    .bind(wallet_address.to_lowercase())
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(result.rows_affected() > 0)
+                }
+
+                async fn touch_api_token(&self, token_id: &str) -> Result<()> {
+                    sqlx::query("UPDATE api_tokens SET last_used_at = $1 WHERE id = $2")
+                        .bind(Utc::now().to_rfc3339())
+                        .bind(token_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_wallet_by_address(&self, address: &str) -> Result<Option<WalletWithKey>> {
+                    let row = sqlx::query("SELECT * FROM wallets WHERE address = $1")
+                        .bind(address.to_lowercase())
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => {
+                            let encrypted_key: Option<Vec<u8>> = r.get("encrypted_private_key");
+                            let salt: Option<Vec<u8>> = r.get("salt");
+                            let nonce: Option<Vec<u8>> = r.get("nonce");
+
+                            // For auto-trading, we need the decrypted key
+                            // This returns the encrypted form - the caller must decrypt
+                            let encrypted_key = match (encrypted_key, salt, nonce) {
+                                (Some(c), Some(s), Some(n)) => Some(EncryptedKey {
+                                    ciphertext: c,
+                                    salt: s,
+                                    nonce: n,
+                                }),
+                                _ => None,
+                            };
+
+                            Ok(Some(WalletWithKey {
+                                address: r.get("address"),
+                                encrypted_key,
+                            }))
+                        }
+                        None => Ok(None),
+                    }
+                }
+
+                // ==================== WITHDRAWAL SAFEGUARDS ====================
+
+                async fn update_encrypted_key(&self, address: &str, encrypted_key: &EncryptedKey) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        UPDATE wallets SET encrypted_private_key = $1, salt = $2, nonce = $3, password_changed_at = $4
+                        WHERE LOWER(address) = LOWER($5)
+                        "#,
+                    )
+                    .bind(&encrypted_key.ciphertext[..])
+                    .bind(&encrypted_key.salt[..])
+                    .bind(&encrypted_key.nonce[..])
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(address)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn get_password_changed_at(&self, address: &str) -> Result<Option<DateTime<Utc>>> {
+                    let row: Option<(Option<String>,)> = sqlx::query_as(
+                        "SELECT password_changed_at FROM wallets WHERE LOWER(address) = LOWER($1)",
+                    )
+                    .bind(address)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    Ok(row
+                        .and_then(|(t,)| t)
+                        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                        .map(|t| t.with_timezone(&Utc)))
+                }
+
+                async fn get_withdraw_limit(&self, address: &str) -> Result<WithdrawLimitSetting> {
+                    let row: Option<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
+                        r#"
+                        SELECT withdraw_daily_limit, withdraw_limit_pending, withdraw_limit_pending_at
+                        FROM wallets WHERE LOWER(address) = LOWER($1)
+                        "#,
+                    )
+                    .bind(address)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    let Some((limit, pending, pending_at)) = row else {
+                        return Ok(WithdrawLimitSetting::default());
+                    };
+                    let parse = |l: Option<String>| l.and_then(|l| Decimal::from_str(&l).ok());
+                    Ok(WithdrawLimitSetting {
+                        limit: parse(limit),
+                        pending: pending_at
+                            .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                            .map(|t| PendingLimit {
+                                limit: parse(pending),
+                                active_at: t.with_timezone(&Utc),
+                            }),
+                    })
+                }
+
+                async fn set_withdraw_limit(&self, address: &str, setting: &WithdrawLimitSetting) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        UPDATE wallets
+                        SET withdraw_daily_limit = $1, withdraw_limit_pending = $2, withdraw_limit_pending_at = $3
+                        WHERE LOWER(address) = LOWER($4)
+                        "#,
+                    )
+                    .bind(setting.limit.map(|l| l.to_string()))
+                    .bind(setting.pending.as_ref().and_then(|p| p.limit).map(|l| l.to_string()))
+                    .bind(setting.pending.as_ref().map(|p| p.active_at.to_rfc3339()))
+                    .bind(address)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+            }
+
+            impl $store {
+                pub(super) fn row_to_api_token(row: &$row) -> ApiToken {
+                    ApiToken {
+                        id: row.get("id"),
+                        wallet_address: row.get("wallet_address"),
+                        name: row.get("name"),
+                        scopes: row.get("scopes"),
+                        rate_limit_per_minute: row.get("rate_limit_per_minute"),
+                        created_at: row.get("created_at"),
+                        expires_at: row.get("expires_at"),
+                        last_used_at: row.get("last_used_at"),
+                        revoked_at: row.get("revoked_at"),
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
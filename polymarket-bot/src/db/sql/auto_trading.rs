@@ -0,0 +1,401 @@
+//! Auto-trading settings and log (AutoTradingRepository)
+
+/// Implement `AutoTradingRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod auto_trading {
+            use crate::db::{AutoTradingRepository, ledger_stats};
+            use crate::services::auto_trader::{AutoTradeLog, AutoTradingSettings, AutoTradingStats};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Utc};
+            use rust_decimal::Decimal;
+            use sqlx::Row;
+            use std::str::FromStr;
+
+            #[async_trait]
+            impl AutoTradingRepository for $store {
+                async fn get_auto_trading_settings(&self, wallet_address: &str) -> Result<AutoTradingSettings> {
+                    let row = sqlx::query("SELECT * FROM auto_trading_settings WHERE wallet_address = $1")
+                        .bind(wallet_address.to_lowercase())
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => {
+                            let strategies_json: String = r.get("strategies");
+                            let strategies: Vec<String> = serde_json::from_str(&strategies_json)
+                                .unwrap_or_else(|_| vec!["sniper".to_string()]);
+
+                            Ok(AutoTradingSettings {
+                                wallet_address: r.get("wallet_address"),
+                                enabled: r.get::<i32, _>("enabled") != 0,
+                                auto_buy_enabled: r.get::<i32, _>("auto_buy_enabled") != 0,
+                                position_size: Decimal::from_str(r.get::<&str, _>("max_position_size")).unwrap_or(Decimal::from(50)),
+                                max_total_exposure: Decimal::from_str(r.get::<&str, _>("max_total_exposure")).unwrap_or(Decimal::from(500)),
+                                min_edge: r.get("min_edge"),
+                                strategies,
+                                take_profit_enabled: r.get::<i32, _>("take_profit_enabled") != 0,
+                                take_profit_percent: r.get("take_profit_percent"),
+                                stop_loss_enabled: r.get::<i32, _>("stop_loss_enabled") != 0,
+                                stop_loss_percent: r.get("stop_loss_percent"),
+                                trailing_stop_enabled: r.get::<i32, _>("trailing_stop_enabled") != 0,
+                                trailing_stop_percent: r.get("trailing_stop_percent"),
+                                time_exit_enabled: r.get::<i32, _>("time_exit_enabled") != 0,
+                                time_exit_hours: r.get("time_exit_hours"),
+                                max_positions: r.get("max_positions"),
+                                cooldown_minutes: r.get("cooldown_minutes"),
+                                max_daily_loss: Decimal::from_str(r.get::<&str, _>("max_daily_loss")).unwrap_or(Decimal::from(100)),
+                                market_filter: r.try_get::<Option<String>, _>("market_filter")
+                                    .ok()
+                                    .flatten()
+                                    .and_then(|s| serde_json::from_str(&s).ok())
+                                    .unwrap_or_default(),
+                                dispute_sniper_enabled: r.try_get::<i32, _>("dispute_sniper_enabled").unwrap_or(0) != 0,
+                                min_dispute_edge: r.try_get("min_dispute_edge").unwrap_or(0.10),
+                                dispute_position_size: r.try_get::<&str, _>("max_dispute_position_size")
+                                    .ok()
+                                    .and_then(|s| Decimal::from_str(s).ok())
+                                    .unwrap_or(Decimal::from(25)),
+                                dispute_exit_on_escalation: r.try_get::<i32, _>("dispute_exit_on_escalation").unwrap_or(1) != 0,
+                                dispute_dvm_enabled: r.try_get::<i32, _>("dispute_dvm_enabled").unwrap_or(0) != 0,
+                                dispute_dvm_min_vote_share: r.try_get("dispute_dvm_min_vote_share").unwrap_or(0.65),
+                                dispute_take_profit_enabled: r.try_get::<i32, _>("dispute_take_profit_enabled").unwrap_or(0) != 0,
+                                dispute_take_profit_price: r.try_get("dispute_take_profit_price").unwrap_or(0.97),
+                                dispute_liveness_exit_enabled: r.try_get::<i32, _>("dispute_liveness_exit_enabled").unwrap_or(0) != 0,
+                                dispute_liveness_exit_minutes: r.try_get("dispute_liveness_exit_minutes").unwrap_or(15),
+                                dispute_hold_to_settlement: r.try_get::<i32, _>("dispute_hold_to_settlement").unwrap_or(0) != 0,
+                            })
+                        }
+                        None => {
+                            // Create default settings
+                            let settings = AutoTradingSettings::for_wallet(wallet_address);
+                            self.create_auto_trading_settings(&settings).await?;
+                            Ok(settings)
+                        }
+                    }
+                }
+
+                async fn update_auto_trading_settings(&self, settings: &AutoTradingSettings) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let strategies_json = serde_json::to_string(&settings.strategies)?;
+                    let market_filter_json = serde_json::to_string(&settings.market_filter)?;
+
+                    sqlx::query(
+                        r#"
+                        UPDATE auto_trading_settings SET
+                            enabled = $1, auto_buy_enabled = $2, max_position_size = $3, max_total_exposure = $4,
+                            min_edge = $5, strategies = $6, take_profit_enabled = $7, take_profit_percent = $8,
+                            stop_loss_enabled = $9, stop_loss_percent = $10, trailing_stop_enabled = $11, trailing_stop_percent = $12,
+                            time_exit_enabled = $13, time_exit_hours = $14, max_positions = $15, cooldown_minutes = $16,
+                            max_daily_loss = $17,
+                            dispute_sniper_enabled = $18, min_dispute_edge = $19, max_dispute_position_size = $20,
+                            dispute_exit_on_escalation = $21,
+                            dispute_dvm_enabled = $22, dispute_dvm_min_vote_share = $23,
+                            dispute_take_profit_enabled = $24, dispute_take_profit_price = $25,
+                            dispute_liveness_exit_enabled = $26, dispute_liveness_exit_minutes = $27, dispute_hold_to_settlement = $28,
+                            market_filter = $29, updated_at = $30
+                        WHERE wallet_address = $31
+                        "#,
+                    )
+                    .bind(settings.enabled as i32)
+                    .bind(settings.auto_buy_enabled as i32)
+                    .bind(settings.position_size.to_string())
+                    .bind(settings.max_total_exposure.to_string())
+                    .bind(settings.min_edge)
+                    .bind(&strategies_json)
+                    .bind(settings.take_profit_enabled as i32)
+                    .bind(settings.take_profit_percent)
+                    .bind(settings.stop_loss_enabled as i32)
+                    .bind(settings.stop_loss_percent)
+                    .bind(settings.trailing_stop_enabled as i32)
+                    .bind(settings.trailing_stop_percent)
+                    .bind(settings.time_exit_enabled as i32)
+                    .bind(settings.time_exit_hours)
+                    .bind(settings.max_positions)
+                    .bind(settings.cooldown_minutes)
+                    .bind(settings.max_daily_loss.to_string())
+                    .bind(settings.dispute_sniper_enabled as i32)
+                    .bind(settings.min_dispute_edge)
+                    .bind(settings.dispute_position_size.to_string())
+                    .bind(settings.dispute_exit_on_escalation as i32)
+                    .bind(settings.dispute_dvm_enabled as i32)
+                    .bind(settings.dispute_dvm_min_vote_share)
+                    .bind(settings.dispute_take_profit_enabled as i32)
+                    .bind(settings.dispute_take_profit_price)
+                    .bind(settings.dispute_liveness_exit_enabled as i32)
+                    .bind(settings.dispute_liveness_exit_minutes)
+                    .bind(settings.dispute_hold_to_settlement as i32)
+                    .bind(&market_filter_json)
+                    .bind(&now)
+                    .bind(settings.wallet_address.to_lowercase())
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn enable_auto_trading(&self, wallet_address: &str) -> Result<()> {
+                    // Ensure settings exist first
+                    let _ = self.get_auto_trading_settings(wallet_address).await?;
+
+                    sqlx::query("UPDATE auto_trading_settings SET enabled = 1, updated_at = $1 WHERE wallet_address = $2")
+                        .bind(Utc::now().to_rfc3339())
+                        .bind(wallet_address.to_lowercase())
+                        .execute(&self.pool)
+                        .await?;
+
+                    Ok(())
+                }
+
+                async fn disable_auto_trading(&self, wallet_address: &str) -> Result<()> {
+                    sqlx::query("UPDATE auto_trading_settings SET enabled = 0, updated_at = $1 WHERE wallet_address = $2")
+                        .bind(Utc::now().to_rfc3339())
+                        .bind(wallet_address.to_lowercase())
+                        .execute(&self.pool)
+                        .await?;
+
+                    Ok(())
+                }
+
+                async fn get_auto_trading_enabled_wallets(&self) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> =
+                        sqlx::query_as("SELECT wallet_address FROM auto_trading_settings WHERE enabled = 1")
+                            .fetch_all(&self.pool)
+                            .await?;
+
+                    Ok(rows.into_iter().map(|(addr,)| addr).collect())
+                }
+
+                async fn get_auto_buy_enabled_wallets(&self) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT wallet_address FROM auto_trading_settings WHERE enabled = 1 AND auto_buy_enabled = 1",
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.into_iter().map(|(addr,)| addr).collect())
+                }
+
+                // ==================== AUTO-TRADE LOGGING ====================
+
+                async fn log_auto_trade(&self, log: &AutoTradeLog) -> Result<i64> {
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO auto_trade_log (
+                            wallet_address, position_id, action, market_question, side,
+                            entry_price, exit_price, size, pnl, trigger_reason, created_at
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(&log.wallet_address)
+                    .bind(log.position_id)
+                    .bind(&log.action)
+                    .bind(&log.market_question)
+                    .bind(&log.side)
+                    .bind(log.entry_price.map(|p| p.to_string()))
+                    .bind(log.exit_price.map(|p| p.to_string()))
+                    .bind(log.size.map(|s| s.to_string()))
+                    .bind(log.pnl.map(|p| p.to_string()))
+                    .bind(&log.trigger_reason)
+                    .bind(log.created_at.to_rfc3339())
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(id)
+                }
+
+                async fn get_auto_trade_history(
+                    &self,
+                    wallet_address: &str,
+                    limit: i64,
+                    offset: i64,
+                ) -> Result<Vec<AutoTradeLog>> {
+                    let rows = sqlx::query(
+                        r#"
+                        SELECT * FROM auto_trade_log
+                        WHERE wallet_address = $1
+                        ORDER BY created_at DESC
+                        LIMIT $2 OFFSET $3
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().filter_map(Self::row_to_auto_trade_log).collect())
+                }
+
+                async fn get_auto_trade_logs_after(&self, after_id: i64, limit: i64) -> Result<Vec<AutoTradeLog>> {
+                    let rows = sqlx::query("SELECT * FROM auto_trade_log WHERE id > $1 ORDER BY id LIMIT $2")
+                        .bind(after_id)
+                        .bind(limit)
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().filter_map(Self::row_to_auto_trade_log).collect())
+                }
+
+                async fn get_max_auto_trade_log_id(&self) -> Result<i64> {
+                    let row: (Option<i64>,) = sqlx::query_as("SELECT MAX(id) FROM auto_trade_log")
+                        .fetch_one(&self.pool)
+                        .await?;
+                    Ok(row.0.unwrap_or(0))
+                }
+
+                async fn get_auto_trading_stats(&self, wallet_address: &str) -> Result<AutoTradingStats> {
+                    // Results come from the ledger; the log only says which trigger closed what
+                    let ledger = ledger_stats(self, wallet_address, None, |strategy| strategy != Some("MintMaker")).await?;
+
+                    let row: (i64, f64, i64, f64, i64, f64, i64, f64, i64) = sqlx::query_as(
+                        r#"
+                        SELECT
+                            COALESCE(SUM(CASE WHEN action = 'take_profit' THEN 1 ELSE 0 END), 0) as tp_count,
+                            COALESCE(SUM(CASE WHEN action = 'take_profit' THEN CAST(pnl AS DOUBLE PRECISION) ELSE 0.0 END), 0.0) as tp_pnl,
+                            COALESCE(SUM(CASE WHEN action = 'stop_loss' THEN 1 ELSE 0 END), 0) as sl_count,
+                            COALESCE(SUM(CASE WHEN action = 'stop_loss' THEN CAST(pnl AS DOUBLE PRECISION) ELSE 0.0 END), 0.0) as sl_pnl,
+                            COALESCE(SUM(CASE WHEN action = 'trailing_stop' THEN 1 ELSE 0 END), 0) as ts_count,
+                            COALESCE(SUM(CASE WHEN action = 'trailing_stop' THEN CAST(pnl AS DOUBLE PRECISION) ELSE 0.0 END), 0.0) as ts_pnl,
+                            COALESCE(SUM(CASE WHEN action = 'time_exit' THEN 1 ELSE 0 END), 0) as te_count,
+                            COALESCE(SUM(CASE WHEN action = 'time_exit' THEN CAST(pnl AS DOUBLE PRECISION) ELSE 0.0 END), 0.0) as te_pnl,
+                            COALESCE(SUM(CASE WHEN action = 'auto_buy' THEN 1 ELSE 0 END), 0) as ab_count
+                        FROM auto_trade_log
+                        WHERE wallet_address = $1
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await
+                    .unwrap_or((0, 0.0, 0, 0.0, 0, 0.0, 0, 0.0, 0));
+
+                    Ok(AutoTradingStats {
+                        total_trades: ledger.trades,
+                        win_count: ledger.wins,
+                        loss_count: ledger.losses,
+                        win_rate: ledger.win_rate,
+                        total_pnl: ledger.realized_pnl,
+                        take_profit_count: row.0,
+                        take_profit_pnl: Decimal::from_f64_retain(row.1).unwrap_or_default(),
+                        stop_loss_count: row.2,
+                        stop_loss_pnl: Decimal::from_f64_retain(row.3).unwrap_or_default(),
+                        trailing_stop_count: row.4,
+                        trailing_stop_pnl: Decimal::from_f64_retain(row.5).unwrap_or_default(),
+                        time_exit_count: row.6,
+                        time_exit_pnl: Decimal::from_f64_retain(row.7).unwrap_or_default(),
+                        auto_buy_count: row.8,
+                        best_trade_pnl: ledger.best_trade.map(|t| t.pnl).unwrap_or_default(),
+                        worst_trade_pnl: ledger.worst_trade.map(|t| t.pnl).unwrap_or_default(),
+                        avg_hold_hours: 0.0, // TODO: calculate from position data
+                    })
+                }
+
+                async fn get_daily_auto_pnl(&self, wallet_address: &str) -> Result<Decimal> {
+                    let today_start = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
+                    let today_start = DateTime::<Utc>::from_naive_utc_and_offset(today_start, Utc);
+
+                    let ledger = ledger_stats(self, wallet_address, Some(&today_start), |strategy| {
+                        strategy != Some("MintMaker")
+                    })
+                    .await?;
+
+                    Ok(ledger.realized_pnl)
+                }
+            }
+
+            impl $store {
+                /// Create auto-trading settings for a wallet
+                async fn create_auto_trading_settings(&self, settings: &AutoTradingSettings) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let strategies_json = serde_json::to_string(&settings.strategies)?;
+                    let market_filter_json = serde_json::to_string(&settings.market_filter)?;
+
+                    sqlx::query(
+                        r#"
+                        INSERT INTO auto_trading_settings (
+                            wallet_address, enabled, auto_buy_enabled, max_position_size, max_total_exposure,
+                            min_edge, strategies, take_profit_enabled, take_profit_percent,
+                            stop_loss_enabled, stop_loss_percent, trailing_stop_enabled, trailing_stop_percent,
+                            time_exit_enabled, time_exit_hours, max_positions, cooldown_minutes, max_daily_loss,
+                            dispute_sniper_enabled, min_dispute_edge, max_dispute_position_size, dispute_exit_on_escalation,
+                            dispute_dvm_enabled, dispute_dvm_min_vote_share,
+                            dispute_take_profit_enabled, dispute_take_profit_price,
+                            dispute_liveness_exit_enabled, dispute_liveness_exit_minutes, dispute_hold_to_settlement,
+                            market_filter, created_at, updated_at
+                        ) VALUES (
+                            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                            $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32
+                        )
+                        ON CONFLICT (wallet_address) DO NOTHING
+                        "#,
+                    )
+                    .bind(settings.wallet_address.to_lowercase())
+                    .bind(settings.enabled as i32)
+                    .bind(settings.auto_buy_enabled as i32)
+                    .bind(settings.position_size.to_string())
+                    .bind(settings.max_total_exposure.to_string())
+                    .bind(settings.min_edge)
+                    .bind(&strategies_json)
+                    .bind(settings.take_profit_enabled as i32)
+                    .bind(settings.take_profit_percent)
+                    .bind(settings.stop_loss_enabled as i32)
+                    .bind(settings.stop_loss_percent)
+                    .bind(settings.trailing_stop_enabled as i32)
+                    .bind(settings.trailing_stop_percent)
+                    .bind(settings.time_exit_enabled as i32)
+                    .bind(settings.time_exit_hours)
+                    .bind(settings.max_positions)
+                    .bind(settings.cooldown_minutes)
+                    .bind(settings.max_daily_loss.to_string())
+                    .bind(settings.dispute_sniper_enabled as i32)
+                    .bind(settings.min_dispute_edge)
+                    .bind(settings.dispute_position_size.to_string())
+                    .bind(settings.dispute_exit_on_escalation as i32)
+                    .bind(settings.dispute_dvm_enabled as i32)
+                    .bind(settings.dispute_dvm_min_vote_share)
+                    .bind(settings.dispute_take_profit_enabled as i32)
+                    .bind(settings.dispute_take_profit_price)
+                    .bind(settings.dispute_liveness_exit_enabled as i32)
+                    .bind(settings.dispute_liveness_exit_minutes)
+                    .bind(settings.dispute_hold_to_settlement as i32)
+                    .bind(&market_filter_json)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                fn row_to_auto_trade_log(row: &$row) -> Option<AutoTradeLog> {
+                    let created_at_str: String = row.get("created_at");
+                    let entry_price: Option<String> = row.get("entry_price");
+                    let exit_price: Option<String> = row.get("exit_price");
+                    let size: Option<String> = row.get("size");
+                    let pnl: Option<String> = row.get("pnl");
+
+                    Some(AutoTradeLog {
+                        id: Some(row.get("id")),
+                        wallet_address: row.get("wallet_address"),
+                        position_id: row.get("position_id"),
+                        action: row.get("action"),
+                        market_question: row.get("market_question"),
+                        side: row.get("side"),
+                        entry_price: entry_price.and_then(|s| Decimal::from_str(&s).ok()),
+                        exit_price: exit_price.and_then(|s| Decimal::from_str(&s).ok()),
+                        size: size.and_then(|s| Decimal::from_str(&s).ok()),
+                        pnl: pnl.and_then(|s| Decimal::from_str(&s).ok()),
+                        trigger_reason: row.get("trigger_reason"),
+                        created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                            .ok()?
+                            .with_timezone(&Utc),
+                    })
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
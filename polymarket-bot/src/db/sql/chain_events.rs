@@ -0,0 +1,110 @@
+//! On-chain log reader cursors and UMA assertions (ChainEventRepository)
+
+/// Implement `ChainEventRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod chain_events {
+            use crate::db::ChainEventRepository;
+            use crate::services::uma_events::ChainAssertion;
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::Utc;
+            use sqlx::Row;
+
+            #[async_trait]
+            impl ChainEventRepository for $store {
+                async fn get_chain_cursor(&self, name: &str) -> Result<Option<i64>> {
+                    let row: Option<(i64,)> = sqlx::query_as("SELECT last_block FROM chain_cursors WHERE name = $1")
+                        .bind(name)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    Ok(row.map(|(block,)| block))
+                }
+
+                async fn set_chain_cursor(&self, name: &str, last_block: i64) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO chain_cursors (name, last_block, updated_at)
+                        VALUES ($1, $2, $3)
+                        ON CONFLICT (name) DO UPDATE SET
+                            last_block = excluded.last_block,
+                            updated_at = excluded.updated_at
+                        "#,
+                    )
+                    .bind(name)
+                    .bind(last_block)
+                    .bind(Utc::now().to_rfc3339())
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn upsert_uma_chain_assertion(&self, assertion: &ChainAssertion) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO uma_chain_assertions (assertion_id, domain_id, claim, identifier, callback_recipient, currency, bond, assertion_timestamp, expiration_time, disputer, dispute_timestamp, settlement_resolution, settlement_timestamp, block_number)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                        ON CONFLICT (assertion_id) DO UPDATE SET
+                            disputer = excluded.disputer,
+                            dispute_timestamp = excluded.dispute_timestamp,
+                            settlement_resolution = excluded.settlement_resolution,
+                            settlement_timestamp = excluded.settlement_timestamp
+                        "#,
+                    )
+                    .bind(&assertion.assertion_id)
+                    .bind(&assertion.domain_id)
+                    .bind(&assertion.claim)
+                    .bind(&assertion.identifier)
+                    .bind(&assertion.callback_recipient)
+                    .bind(&assertion.currency)
+                    .bind(&assertion.bond)
+                    .bind(assertion.assertion_timestamp)
+                    .bind(assertion.expiration_time)
+                    .bind(&assertion.disputer)
+                    .bind(assertion.dispute_timestamp)
+                    .bind(assertion.settlement_resolution.map(|r| r as i32))
+                    .bind(assertion.settlement_timestamp)
+                    .bind(assertion.block_number)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn get_unsettled_uma_chain_assertions(&self) -> Result<Vec<ChainAssertion>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM uma_chain_assertions WHERE settlement_timestamp IS NULL ORDER BY block_number",
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows
+                        .iter()
+                        .map(|row| ChainAssertion {
+                            assertion_id: row.get("assertion_id"),
+                            domain_id: row.get("domain_id"),
+                            claim: row.get("claim"),
+                            identifier: row.get("identifier"),
+                            callback_recipient: row.get("callback_recipient"),
+                            currency: row.get("currency"),
+                            bond: row.get("bond"),
+                            assertion_timestamp: row.get("assertion_timestamp"),
+                            expiration_time: row.get("expiration_time"),
+                            disputer: row.get("disputer"),
+                            dispute_timestamp: row.get("dispute_timestamp"),
+                            settlement_resolution: row
+                                .get::<Option<i32>, _>("settlement_resolution")
+                                .map(|r| r != 0),
+                            settlement_timestamp: row.get("settlement_timestamp"),
+                            block_number: row.get("block_number"),
+                        })
+                        .collect())
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
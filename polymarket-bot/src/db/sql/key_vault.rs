@@ -0,0 +1,115 @@
+//! Wrapped wallet keys and the key vault audit log (KeyVaultRepository)
+
+/// Implement `KeyVaultRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod key_vault {
+            use crate::db::{KeyAuditEntry, KeyVaultRepository, WrappedKeyRow};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::Utc;
+
+            #[async_trait]
+            impl KeyVaultRepository for $store {
+                async fn store_wrapped_key(
+                    &self,
+                    wallet_address: &str,
+                    ciphertext: &str,
+                    nonce: &str,
+                    master_key_id: &str,
+                ) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO wrapped_keys (wallet_address, ciphertext, nonce, master_key_id, created_at)
+                        VALUES ($1, $2, $3, $4, $5)
+                        ON CONFLICT (wallet_address) DO UPDATE SET
+                            ciphertext = excluded.ciphertext,
+                            nonce = excluded.nonce,
+                            master_key_id = excluded.master_key_id,
+                            created_at = excluded.created_at
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(ciphertext)
+                    .bind(nonce)
+                    .bind(master_key_id)
+                    .bind(Utc::now().to_rfc3339())
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn get_wrapped_keys(&self) -> Result<Vec<WrappedKeyRow>> {
+                    let rows: Vec<(String, String, String, String)> =
+                        sqlx::query_as("SELECT wallet_address, ciphertext, nonce, master_key_id FROM wrapped_keys")
+                            .fetch_all(&self.pool)
+                            .await?;
+
+                    Ok(rows
+                        .into_iter()
+                        .map(|(wallet_address, ciphertext, nonce, master_key_id)| WrappedKeyRow {
+                            wallet_address,
+                            ciphertext,
+                            nonce,
+                            master_key_id,
+                        })
+                        .collect())
+                }
+
+                async fn delete_wrapped_key(&self, wallet_address: &str) -> Result<bool> {
+                    let result = sqlx::query("DELETE FROM wrapped_keys WHERE wallet_address = $1")
+                        .bind(wallet_address.to_lowercase())
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(result.rows_affected() > 0)
+                }
+
+                async fn delete_all_wrapped_keys(&self) -> Result<u64> {
+                    let result = sqlx::query("DELETE FROM wrapped_keys").execute(&self.pool).await?;
+                    Ok(result.rows_affected())
+                }
+
+                async fn log_key_audit(&self, wallet_address: Option<&str>, action: &str, detail: Option<&str>) -> Result<()> {
+                    sqlx::query("INSERT INTO key_audit_log (wallet_address, action, detail, created_at) VALUES ($1, $2, $3, $4)")
+                        .bind(wallet_address.map(|w| w.to_lowercase()))
+                        .bind(action)
+                        .bind(detail)
+                        .bind(Utc::now().to_rfc3339())
+                        .execute(&self.pool)
+                        .await?;
+
+                    Ok(())
+                }
+
+                async fn get_key_audit_log(&self, wallet_address: &str, limit: i64) -> Result<Vec<KeyAuditEntry>> {
+                    let rows: Vec<(i64, Option<String>, String, Option<String>, String)> = sqlx::query_as(
+                        r#"
+                        SELECT id, wallet_address, action, detail, created_at FROM key_audit_log
+                        WHERE wallet_address = $1 OR wallet_address IS NULL
+                        ORDER BY id DESC
+                        LIMIT $2
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows
+                        .into_iter()
+                        .map(|(id, wallet_address, action, detail, created_at)| KeyAuditEntry {
+                            id,
+                            wallet_address,
+                            action,
+                            detail,
+                            created_at,
+                        })
+                        .collect())
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
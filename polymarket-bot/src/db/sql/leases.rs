@@ -0,0 +1,68 @@
+//! Leader-election leases (LeaseRepository)
+
+/// Implement `LeaseRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod leases {
+            use crate::db::{Lease, LeaseRepository};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Utc};
+
+            #[async_trait]
+            impl LeaseRepository for $store {
+                async fn try_acquire_lease(&self, name: &str, holder: &str, expires_at: DateTime<Utc>) -> Result<bool> {
+                    let result = sqlx::query(
+                        r#"
+                        INSERT INTO leader_leases (name, holder, acquired_at, expires_at)
+                        VALUES ($1, $2, $3, $4)
+                        ON CONFLICT(name) DO UPDATE SET
+                            holder = excluded.holder,
+                            acquired_at = CASE WHEN leader_leases.holder = excluded.holder
+                                THEN leader_leases.acquired_at ELSE excluded.acquired_at END,
+                            expires_at = excluded.expires_at
+                        WHERE leader_leases.holder = excluded.holder OR leader_leases.expires_at <= excluded.acquired_at
+                        "#,
+                    )
+                    .bind(name)
+                    .bind(holder)
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(expires_at.to_rfc3339())
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(result.rows_affected() > 0)
+                }
+
+                async fn release_lease(&self, name: &str, holder: &str) -> Result<()> {
+                    sqlx::query("DELETE FROM leader_leases WHERE name = $1 AND holder = $2")
+                        .bind(name)
+                        .bind(holder)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_lease(&self, name: &str) -> Result<Option<Lease>> {
+                    let row: Option<(String, String, String, String)> =
+                        sqlx::query_as("SELECT name, holder, acquired_at, expires_at FROM leader_leases WHERE name = $1")
+                            .bind(name)
+                            .fetch_optional(&self.pool)
+                            .await?;
+
+                    match row {
+                        Some((name, holder, acquired_at, expires_at)) => Ok(Some(Lease {
+                            name,
+                            holder,
+                            acquired_at: DateTime::parse_from_rfc3339(&acquired_at)?.with_timezone(&Utc),
+                            expires_at: DateTime::parse_from_rfc3339(&expires_at)?.with_timezone(&Utc),
+                        })),
+                        None => Ok(None),
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
@@ -0,0 +1,200 @@
+//! Double-entry ledger (LedgerRepository)
+
+/// Implement `LedgerRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod ledger {
+            use crate::db::LedgerRepository;
+            use crate::ledger::{self, EntryKind, Holding, LedgerEntry, LedgerTxn, Posting};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Utc};
+            use rust_decimal::Decimal;
+            use sqlx::Row;
+            use std::str::FromStr;
+
+            #[async_trait]
+            impl LedgerRepository for $store {
+                async fn post_ledger(&self, txn: &LedgerTxn) -> Result<Option<i64>> {
+                    let mut tx = self.pool.begin().await?;
+                    let txn_id = Self::insert_ledger_txn(&mut tx, txn).await?;
+                    tx.commit().await?;
+                    Ok(txn_id)
+                }
+
+                async fn get_ledger_holdings(&self, reference: &str) -> Result<Vec<Holding>> {
+                    let mut conn = self.pool.acquire().await?;
+                    Self::ledger_holdings(&mut conn, reference).await
+                }
+
+                async fn get_ledger_entries(
+                    &self,
+                    wallet_address: &str,
+                    since: Option<&DateTime<Utc>>,
+                ) -> Result<Vec<LedgerEntry>> {
+                    let since = since.map(|s| s.to_rfc3339());
+                    let rows = sqlx::query(
+                        r#"
+                        SELECT t.id, t.kind, t.strategy, t.reference, t.created_at, p.account, p.amount, p.quantity
+                        FROM ledger_postings p
+                        JOIN ledger_transactions t ON t.id = p.txn_id
+                        WHERE t.wallet_address = $1 AND ($2 IS NULL OR t.created_at >= $3)
+                        ORDER BY t.id, p.id
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(&since)
+                    .bind(&since)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().filter_map(Self::row_to_ledger_entry).collect())
+                }
+
+                async fn get_ledger_transactions(&self, wallet_address: &str, limit: i64, offset: i64) -> Result<Vec<LedgerTxn>> {
+                    let wallet_address = wallet_address.to_lowercase();
+                    let rows = sqlx::query(
+                        "SELECT * FROM ledger_transactions WHERE wallet_address = $1 ORDER BY id DESC LIMIT $2 OFFSET $3",
+                    )
+                    .bind(&wallet_address)
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let mut txns: Vec<LedgerTxn> = rows
+                        .iter()
+                        .filter_map(|row| {
+                            let kind = EntryKind::parse(&row.get::<String, _>("kind"))?;
+                            let created_at: String = row.get("created_at");
+                            Some(LedgerTxn {
+                                id: Some(row.get("id")),
+                                wallet_address: row.get("wallet_address"),
+                                kind,
+                                strategy: row.get("strategy"),
+                                reference: row.get("reference"),
+                                key: row.get("txn_key"),
+                                memo: row.get("memo"),
+                                postings: Vec::new(),
+                                created_at: DateTime::parse_from_rfc3339(&created_at).ok()?.with_timezone(&Utc),
+                            })
+                        })
+                        .collect();
+
+                    let postings: Vec<(i64, String, String, Option<String>)> = sqlx::query_as(
+                        r#"
+                        SELECT txn_id, account, amount, quantity FROM ledger_postings
+                        WHERE txn_id IN (SELECT id FROM ledger_transactions WHERE wallet_address = $1 ORDER BY id DESC LIMIT $2 OFFSET $3)
+                        ORDER BY id
+                        "#,
+                    )
+                    .bind(&wallet_address)
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    for (txn_id, account, amount, quantity) in postings {
+                        if let Some(txn) = txns.iter_mut().find(|t| t.id == Some(txn_id)) {
+                            txn.postings.push(Posting {
+                                account,
+                                amount: Decimal::from_str(&amount).unwrap_or_default(),
+                                quantity: quantity.and_then(|q| Decimal::from_str(&q).ok()),
+                            });
+                        }
+                    }
+
+                    Ok(txns)
+                }
+            }
+
+            impl $store {
+                /// Record a balanced ledger transaction on `conn`, so a write path posts it in the
+                /// same database transaction as its own update. None when its key is already recorded.
+                pub(super) async fn insert_ledger_txn(conn: &mut $conn, txn: &LedgerTxn) -> Result<Option<i64>> {
+                    if !txn.is_balanced() {
+                        anyhow::bail!(
+                            "Unbalanced {} ledger transaction for {}",
+                            txn.kind.as_str(),
+                            txn.wallet_address
+                        );
+                    }
+                    if txn.postings.is_empty() {
+                        return Ok(None);
+                    }
+
+                    let txn_id: Option<i64> = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO ledger_transactions (wallet_address, kind, strategy, reference, txn_key, memo, created_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7)
+                        ON CONFLICT (txn_key) DO NOTHING
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(&txn.wallet_address)
+                    .bind(txn.kind.as_str())
+                    .bind(&txn.strategy)
+                    .bind(&txn.reference)
+                    .bind(&txn.key)
+                    .bind(&txn.memo)
+                    .bind(txn.created_at.to_rfc3339())
+                    .fetch_optional(&mut *conn)
+                    .await?;
+
+                    let Some(txn_id) = txn_id else {
+                        return Ok(None);
+                    };
+
+                    for posting in &txn.postings {
+                        sqlx::query("INSERT INTO ledger_postings (txn_id, account, amount, quantity) VALUES ($1, $2, $3, $4)")
+                            .bind(txn_id)
+                            .bind(&posting.account)
+                            .bind(posting.amount.to_string())
+                            .bind(posting.quantity.map(|q| q.to_string()))
+                            .execute(&mut *conn)
+                            .await?;
+                    }
+
+                    Ok(Some(txn_id))
+                }
+
+                /// Token lots still held under a reference, read on `conn`
+                pub(super) async fn ledger_holdings(conn: &mut $conn, reference: &str) -> Result<Vec<Holding>> {
+                    let rows = sqlx::query(
+                        r#"
+                        SELECT t.id, t.kind, t.strategy, t.reference, t.created_at, p.account, p.amount, p.quantity
+                        FROM ledger_postings p
+                        JOIN ledger_transactions t ON t.id = p.txn_id
+                        WHERE t.reference = $1 AND p.account LIKE 'tokens:%'
+                        ORDER BY p.id
+                        "#,
+                    )
+                    .bind(reference)
+                    .fetch_all(&mut *conn)
+                    .await?;
+
+                    let entries: Vec<LedgerEntry> = rows.iter().filter_map(Self::row_to_ledger_entry).collect();
+                    Ok(ledger::entry_lots(&entries))
+                }
+
+                pub(super) fn row_to_ledger_entry(row: &$row) -> Option<LedgerEntry> {
+                    let created_at: String = row.get("created_at");
+                    let amount: String = row.get("amount");
+                    let quantity: Option<String> = row.get("quantity");
+                    Some(LedgerEntry {
+                        txn_id: row.get("id"),
+                        kind: EntryKind::parse(&row.get::<String, _>("kind"))?,
+                        strategy: row.get("strategy"),
+                        reference: row.get("reference"),
+                        account: row.get("account"),
+                        amount: Decimal::from_str(&amount).ok()?,
+                        quantity: quantity.and_then(|q| Decimal::from_str(&q).ok()),
+                        created_at: DateTime::parse_from_rfc3339(&created_at).ok()?.with_timezone(&Utc),
+                    })
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
@@ -0,0 +1,148 @@
+//! Incremental scanner market index and scan costs (MarketIndexRepository)
+
+/// Implement `MarketIndexRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod market_index {
+            use crate::db::{IndexedMarketRow, MarketIndexRepository, ScanCostRow};
+            use crate::market_index::ScanCost;
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::Utc;
+
+            #[async_trait]
+            impl MarketIndexRepository for $store {
+                async fn get_indexed_markets(&self) -> Result<Vec<IndexedMarketRow>> {
+                    let rows: Vec<(String, Option<String>, String)> =
+                        sqlx::query_as("SELECT id, updated_at, market_json FROM market_index")
+                            .fetch_all(&self.pool)
+                            .await?;
+
+                    Ok(rows
+                        .into_iter()
+                        .map(|(id, updated_at, market_json)| IndexedMarketRow { id, updated_at, market_json })
+                        .collect())
+                }
+
+                async fn save_market_index_changes(
+                    &self,
+                    upserts: &[(String, String, Option<String>, String)],
+                    removals: &[String],
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+
+                    for (id, condition_id, updated_at, market_json) in upserts {
+                        sqlx::query(
+                            r#"
+                            INSERT INTO market_index (id, condition_id, updated_at, market_json, indexed_at)
+                            VALUES ($1, $2, $3, $4, $5)
+                            ON CONFLICT (id) DO UPDATE SET
+                                condition_id = excluded.condition_id,
+                                updated_at = excluded.updated_at,
+                                market_json = excluded.market_json,
+                                indexed_at = excluded.indexed_at
+                            "#,
+                        )
+                        .bind(id)
+                        .bind(condition_id)
+                        .bind(updated_at)
+                        .bind(market_json)
+                        .bind(&now)
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+
+                    for id in removals {
+                        sqlx::query("DELETE FROM market_index WHERE id = $1")
+                            .bind(id)
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn get_condition_market_ids(&self, condition_id: &str) -> Result<Vec<String>> {
+                    let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM market_index WHERE condition_id = $1")
+                        .bind(condition_id)
+                        .fetch_all(&self.pool)
+                        .await?;
+                    Ok(ids)
+                }
+
+                async fn record_scan_cost(&self, cost: &ScanCost) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO market_scan_costs
+                            (mode, started_at, requests, markets_fetched, added, updated, removed, duration_ms)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                        "#,
+                    )
+                    .bind(cost.mode.as_str())
+                    .bind(cost.started_at.to_rfc3339())
+                    .bind(i64::from(cost.requests))
+                    .bind(i64::from(cost.markets_fetched))
+                    .bind(i64::from(cost.added))
+                    .bind(i64::from(cost.updated))
+                    .bind(i64::from(cost.removed))
+                    .bind(cost.duration_ms as i64)
+                    .execute(&self.pool)
+                    .await?;
+
+                    let cutoff = (Utc::now() - chrono::Duration::days(7)).to_rfc3339();
+                    sqlx::query("DELETE FROM market_scan_costs WHERE started_at < $1")
+                        .bind(cutoff)
+                        .execute(&self.pool)
+                        .await?;
+
+                    Ok(())
+                }
+
+                async fn get_last_scan_at(&self, mode: &str) -> Result<Option<String>> {
+                    let row: Option<(String,)> = sqlx::query_as(
+                        "SELECT started_at FROM market_scan_costs WHERE mode = $1 ORDER BY started_at DESC LIMIT 1",
+                    )
+                    .bind(mode)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    Ok(row.map(|(started_at,)| started_at))
+                }
+
+                async fn get_scan_costs(&self, limit: i64) -> Result<Vec<ScanCostRow>> {
+                    let rows: Vec<(String, String, i64, i64, i64, i64, i64, i64)> = sqlx::query_as(
+                        r#"
+                        SELECT mode, started_at, requests, markets_fetched, added, updated, removed, duration_ms
+                        FROM market_scan_costs
+                        ORDER BY id DESC
+                        LIMIT $1
+                        "#,
+                    )
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows
+                        .into_iter()
+                        .map(
+                            |(mode, started_at, requests, markets_fetched, added, updated, removed, duration_ms)| ScanCostRow {
+                                mode,
+                                started_at,
+                                requests,
+                                markets_fetched,
+                                added,
+                                updated,
+                                removed,
+                                duration_ms,
+                            },
+                        )
+                        .collect())
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
@@ -0,0 +1,427 @@
+//! Millionaires Club (McRepository)
+
+/// Implement `McRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod mc {
+            use crate::db::{McRepository, McTierHistoryRow, McTradeFullRow, ledger_stats};
+            use crate::ledger::{self, LedgerTxn};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{Duration, Utc};
+            use rust_decimal::prelude::ToPrimitive;
+            use rust_decimal::Decimal;
+            use sqlx::Row;
+            use std::str::FromStr;
+
+            #[async_trait]
+            impl McRepository for $store {
+                async fn mc_get_config(&self) -> Result<crate::services::mc_scanner::McConfig> {
+                    use crate::services::mc_scanner::McConfig;
+
+                    let row = sqlx::query("SELECT * FROM mc_config WHERE id = 1")
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => Ok(McConfig {
+                            bankroll: r.get("bankroll"),
+                            tier: r.get("tier"),
+                            mode: r.get("mode"),
+                            peak_bankroll: r.get("peak_bankroll"),
+                            pause_state: r.get("pause_state"),
+                            pause_until: r.try_get("pause_until").unwrap_or(None),
+                        }),
+                        None => {
+                            let now = Utc::now().to_rfc3339();
+                            sqlx::query(
+                                "INSERT INTO mc_config (id, bankroll, tier, mode, peak_bankroll, pause_state, created_at, updated_at) VALUES (1, '40', 1, 'observation', '40', 'active', $1, $2) ON CONFLICT (id) DO NOTHING"
+                            )
+                            .bind(&now)
+                            .bind(&now)
+                            .execute(&self.pool)
+                            .await?;
+
+                            Ok(McConfig {
+                                bankroll: "40".to_string(),
+                                tier: 1,
+                                mode: "observation".to_string(),
+                                peak_bankroll: "40".to_string(),
+                                pause_state: "active".to_string(),
+                                pause_until: None,
+                            })
+                        }
+                    }
+                }
+
+                async fn mc_update_bankroll(&self, bankroll: &str, peak: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mc_config SET bankroll = $1, peak_bankroll = $2, updated_at = $3 WHERE id = 1")
+                        .bind(bankroll)
+                        .bind(peak)
+                        .bind(&now)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn mc_update_tier(&self, tier: i32) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mc_config SET tier = $1, updated_at = $2 WHERE id = 1")
+                        .bind(tier)
+                        .bind(&now)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn mc_update_mode(&self, mode: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mc_config SET mode = $1, updated_at = $2 WHERE id = 1")
+                        .bind(mode)
+                        .bind(&now)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn mc_update_pause_state(&self, state: &str, until: Option<&str>) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mc_config SET pause_state = $1, pause_until = $2, updated_at = $3 WHERE id = 1")
+                        .bind(state)
+                        .bind(until)
+                        .bind(&now)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn mc_insert_scout_log(&self, scout: &crate::services::mc_scanner::McScoutResult) -> Result<()> {
+                    let reasons_json = serde_json::to_string(&scout.reasons).unwrap_or_else(|_| "[]".to_string());
+
+                    sqlx::query(
+                        r#"
+                        INSERT INTO mc_scout_log (market_id, condition_id, question, slug, side, price, volume, category, end_date, passed, certainty_score, reasons, slippage_pct, would_trade, token_id, scanned_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                        "#,
+                    )
+                    .bind(&scout.market_id)
+                    .bind(&scout.condition_id)
+                    .bind(&scout.question)
+                    .bind(&scout.slug)
+                    .bind(&scout.side)
+                    .bind(&scout.price)
+                    .bind(&scout.volume)
+                    .bind(&scout.category)
+                    .bind(&scout.end_date)
+                    .bind(scout.passed as i32)
+                    .bind(scout.certainty_score)
+                    .bind(&reasons_json)
+                    .bind(scout.slippage_pct)
+                    .bind(scout.would_trade as i32)
+                    .bind(&scout.token_id)
+                    .bind(&scout.scanned_at)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn mc_get_scout_log(&self, limit: i64, offset: i64) -> Result<(Vec<crate::services::mc_scanner::McScoutResult>, i64)> {
+                    use crate::services::mc_scanner::McScoutResult;
+
+                    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM mc_scout_log")
+                        .fetch_one(&self.pool)
+                        .await?;
+
+                    let rows = sqlx::query(
+                        "SELECT * FROM mc_scout_log ORDER BY scanned_at DESC LIMIT $1 OFFSET $2"
+                    )
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let logs: Vec<McScoutResult> = rows.iter().filter_map(|row| {
+                        let reasons_str: String = row.try_get("reasons").unwrap_or_else(|_| "[]".to_string());
+                        let reasons: Vec<String> = serde_json::from_str(&reasons_str).unwrap_or_default();
+
+                        Some(McScoutResult {
+                            market_id: row.get("market_id"),
+                            condition_id: row.try_get("condition_id").unwrap_or_default(),
+                            question: row.get("question"),
+                            slug: row.try_get("slug").unwrap_or_default(),
+                            side: row.get("side"),
+                            price: row.get("price"),
+                            volume: row.try_get("volume").unwrap_or_default(),
+                            category: row.try_get("category").unwrap_or(None),
+                            end_date: row.try_get("end_date").unwrap_or(None),
+                            passed: row.get::<i32, _>("passed") != 0,
+                            certainty_score: row.get("certainty_score"),
+                            reasons,
+                            slippage_pct: row.try_get("slippage_pct").unwrap_or(None),
+                            would_trade: row.get::<i32, _>("would_trade") != 0,
+                            token_id: row.try_get("token_id").unwrap_or(None),
+                            scanned_at: row.get("scanned_at"),
+                        })
+                    }).collect();
+
+                    Ok((logs, total.0))
+                }
+
+                async fn mc_insert_trade(
+                    &self,
+                    market_id: &str,
+                    condition_id: &str,
+                    question: &str,
+                    slug: &str,
+                    side: &str,
+                    entry_price: &str,
+                    size: &str,
+                    shares: &str,
+                    certainty_score: i32,
+                    category: Option<&str>,
+                    tier: i32,
+                    token_id: Option<&str>,
+                    end_date: Option<&str>,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+
+                    let mut tx = self.pool.begin().await?;
+                    let trade_id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO mc_trades (market_id, condition_id, question, slug, side, entry_price, size, shares, certainty_score, category, tier_at_entry, token_id, end_date, opened_at, status)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 'open')
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(market_id)
+                    .bind(condition_id)
+                    .bind(question)
+                    .bind(slug)
+                    .bind(side)
+                    .bind(entry_price)
+                    .bind(size)
+                    .bind(shares)
+                    .bind(certainty_score)
+                    .bind(category)
+                    .bind(tier)
+                    .bind(token_id)
+                    .bind(end_date)
+                    .bind(&now)
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                    if let (Ok(size), Ok(shares)) = (Decimal::from_str(size), Decimal::from_str(shares)) {
+                        let token = ledger::lot_token(token_id, market_id, side);
+                        let reference = ledger::mc_ref(trade_id);
+                        let buy = LedgerTxn::buy(ledger::MC_BOOK, &token, shares, size, Decimal::ZERO)
+                            .strategy("MillionairesClub")
+                            .key(format!("{}:buy", reference))
+                            .reference(reference);
+                        Self::insert_ledger_txn(&mut tx, &buy).await?;
+                    }
+                    tx.commit().await?;
+
+                    Ok(())
+                }
+
+                async fn mc_update_trade_resolution(&self, trade_id: i64, exit_price: &str, pnl: &str, status: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+                    sqlx::query(
+                        "UPDATE mc_trades SET exit_price = $1, pnl = $2, status = $3, closed_at = $4 WHERE id = $5"
+                    )
+                    .bind(exit_price)
+                    .bind(pnl)
+                    .bind(status)
+                    .bind(&now)
+                    .bind(trade_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    let reference = ledger::mc_ref(trade_id);
+                    let held = Self::ledger_holdings(&mut tx, &reference).await?;
+                    if let (false, Ok(exit)) = (held.is_empty(), Decimal::from_str(exit_price)) {
+                        let payout = (exit * held.iter().map(|l| l.quantity).sum::<Decimal>()).round_dp(6);
+                        let redeem = LedgerTxn::redeem(ledger::MC_BOOK, &held, payout)
+                            .strategy("MillionairesClub")
+                            .key(format!("{}:redeem", reference))
+                            .reference(reference);
+                        Self::insert_ledger_txn(&mut tx, &redeem).await?;
+                    }
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn mc_get_trades(&self, limit: i64, offset: i64) -> Result<(Vec<McTradeFullRow>, i64)> {
+                    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM mc_trades")
+                        .fetch_one(&self.pool)
+                        .await?;
+
+                    let rows = sqlx::query(
+                        "SELECT * FROM mc_trades ORDER BY opened_at DESC LIMIT $1 OFFSET $2"
+                    )
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let trades: Vec<McTradeFullRow> = rows.iter().filter_map(|row| {
+                        Some(McTradeFullRow {
+                            id: row.get("id"),
+                            market_id: row.get("market_id"),
+                            condition_id: row.try_get("condition_id").unwrap_or_default(),
+                            question: row.get("question"),
+                            slug: row.try_get("slug").unwrap_or_default(),
+                            side: row.get("side"),
+                            entry_price: row.get("entry_price"),
+                            exit_price: row.try_get("exit_price").unwrap_or(None),
+                            size: row.try_get("size").unwrap_or_default(),
+                            shares: row.try_get("shares").unwrap_or_default(),
+                            pnl: row.try_get("pnl").unwrap_or(None),
+                            certainty_score: row.try_get("certainty_score").unwrap_or(0),
+                            category: row.try_get("category").unwrap_or(None),
+                            status: row.get("status"),
+                            tier_at_entry: row.try_get("tier_at_entry").unwrap_or(1),
+                            token_id: row.try_get("token_id").unwrap_or(None),
+                            end_date: row.try_get("end_date").unwrap_or(None),
+                            opened_at: row.get("opened_at"),
+                            closed_at: row.try_get("closed_at").unwrap_or(None),
+                        })
+                    }).collect();
+
+                    Ok((trades, total.0))
+                }
+
+                async fn mc_get_open_trades(&self) -> Result<Vec<crate::services::mc_scanner::McTradeRow>> {
+                    use crate::services::mc_scanner::McTradeRow;
+
+                    let rows = sqlx::query(
+                        "SELECT * FROM mc_trades WHERE status = 'open'"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let trades: Vec<McTradeRow> = rows.iter().filter_map(|row| {
+                        Some(McTradeRow {
+                            id: row.get("id"),
+                            market_id: row.get("market_id"),
+                            condition_id: row.try_get("condition_id").unwrap_or_default(),
+                            question: row.get("question"),
+                            side: row.get("side"),
+                            entry_price: row.get("entry_price"),
+                            shares: row.try_get("shares").unwrap_or_default(),
+                            status: row.get("status"),
+                        })
+                    }).collect();
+
+                    Ok(trades)
+                }
+
+                async fn mc_get_open_trade_count(&self) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM mc_trades WHERE status = 'open'")
+                        .fetch_one(&self.pool)
+                        .await?;
+                    Ok(count.0)
+                }
+
+                async fn mc_get_trade_stats(&self) -> Result<(i64, i64, f64)> {
+                    let ledger = ledger_stats(self, ledger::MC_BOOK, None, |_| true).await?;
+                    Ok((ledger.trades, ledger.wins, ledger.realized_pnl.to_f64().unwrap_or(0.0)))
+                }
+
+                async fn mc_get_category_trade_count(&self, category: &str) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mc_trades WHERE category = $1 AND status = 'open'"
+                    )
+                    .bind(category)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn mc_insert_tier_history(&self, from_tier: i32, to_tier: i32, bankroll: &str, reason: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        "INSERT INTO mc_tier_history (from_tier, to_tier, bankroll, reason, timestamp) VALUES ($1, $2, $3, $4, $5)"
+                    )
+                    .bind(from_tier)
+                    .bind(to_tier)
+                    .bind(bankroll)
+                    .bind(reason)
+                    .bind(&now)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn mc_get_tier_history(&self) -> Result<Vec<McTierHistoryRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mc_tier_history ORDER BY timestamp DESC"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let history: Vec<McTierHistoryRow> = rows.iter().filter_map(|row| {
+                        Some(McTierHistoryRow {
+                            id: row.get("id"),
+                            from_tier: row.get("from_tier"),
+                            to_tier: row.get("to_tier"),
+                            bankroll: row.get("bankroll"),
+                            reason: row.get("reason"),
+                            timestamp: row.get("timestamp"),
+                        })
+                    }).collect();
+
+                    Ok(history)
+                }
+
+                async fn mc_insert_drawdown_event(
+                    &self,
+                    event_type: &str,
+                    peak_bankroll: &str,
+                    current_bankroll: &str,
+                    drawdown_pct: f64,
+                    action_taken: &str,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        "INSERT INTO mc_drawdown_events (event_type, peak_bankroll, current_bankroll, drawdown_pct, action_taken, timestamp) VALUES ($1, $2, $3, $4, $5, $6)"
+                    )
+                    .bind(event_type)
+                    .bind(peak_bankroll)
+                    .bind(current_bankroll)
+                    .bind(drawdown_pct)
+                    .bind(action_taken)
+                    .bind(&now)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn mc_get_recent_losses(&self, days: i64) -> Result<i64> {
+                    let cutoff = (Utc::now() - Duration::days(days)).to_rfc3339();
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mc_trades WHERE status = 'lost' AND closed_at >= $1"
+                    )
+                    .bind(&cutoff)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn mc_cleanup_old_scout_logs(&self, days: i64) -> Result<u64> {
+                    let cutoff = (Utc::now() - Duration::days(days)).to_rfc3339();
+                    let result = sqlx::query("DELETE FROM mc_scout_log WHERE scanned_at < $1")
+                        .bind(&cutoff)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(result.rows_affected())
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
@@ -0,0 +1,1215 @@
+//! Mint Maker settings, pairs, log and analytics (MintMakerRepository)
+
+use chrono::DateTime;
+
+/// Implement `MintMakerRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod mint_maker {
+            use crate::db::{
+                MintMakerAnalyticsSummary, MintMakerLogEntry, MintMakerPairRow,
+                MintMakerRepository, MintMakerSettingsRow, ledger_stats,
+            };
+            use crate::ledger::{self, LedgerTxn};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Duration, Utc};
+            use rust_decimal::prelude::ToPrimitive;
+            use rust_decimal::Decimal;
+            use sqlx::Row;
+            use std::str::FromStr;
+
+            #[async_trait]
+            impl MintMakerRepository for $store {
+                async fn get_mint_maker_settings(&self, wallet_address: &str) -> Result<MintMakerSettingsRow> {
+                    let addr = wallet_address.to_lowercase();
+                    let row = sqlx::query(
+                        "SELECT * FROM mint_maker_settings WHERE wallet_address = $1"
+                    )
+                    .bind(&addr)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    match row {
+                        Some(row) => {
+                            let assets_json: String = row.get("assets");
+                            let assets: Vec<String> = serde_json::from_str(&assets_json).unwrap_or_else(|_| vec!["BTC".to_string(), "ETH".to_string(), "SOL".to_string()]);
+                            Ok(MintMakerSettingsRow {
+                                wallet_address: row.get("wallet_address"),
+                                enabled: row.get::<i32, _>("enabled") != 0,
+                                preset: row.get("preset"),
+                                bid_offset_cents: row.get("bid_offset_cents"),
+                                max_pair_cost: row.get("max_pair_cost"),
+                                min_spread_profit: row.get("min_spread_profit"),
+                                max_pairs_per_market: row.get("max_pairs_per_market"),
+                                max_total_pairs: row.get("max_total_pairs"),
+                                stale_order_seconds: row.get("stale_order_seconds"),
+                                assets,
+                                min_minutes_to_close: row.get("min_minutes_to_close"),
+                                max_minutes_to_close: row.get("max_minutes_to_close"),
+                                auto_place: row.try_get::<i32, _>("auto_place").unwrap_or(0) != 0,
+                                auto_place_size: row.try_get::<String, _>("auto_place_size").unwrap_or_else(|_| "2".to_string()),
+                                auto_max_markets: row.try_get::<i32, _>("auto_max_markets").unwrap_or(1),
+                                auto_redeem: row.try_get::<i32, _>("auto_redeem").unwrap_or(0) != 0,
+                                stop_loss_pct: row.try_get::<i32, _>("stop_loss_pct").unwrap_or(25),
+                                stop_loss_delay_secs: row.try_get::<i32, _>("stop_loss_delay_secs").unwrap_or(30),
+                                auto_place_delay_mins: row.try_get::<i32, _>("auto_place_delay_mins").unwrap_or(0),
+                                auto_size_pct: row.try_get::<i32, _>("auto_size_pct").unwrap_or(0),
+                                auto_max_attempts: row.try_get::<i32, _>("auto_max_attempts").unwrap_or(1),
+                                balance_reserve: row.try_get::<f64, _>("balance_reserve").unwrap_or(0.0),
+                                smart_mode: row.try_get::<i32, _>("smart_mode").unwrap_or(0) != 0,
+                                pre_place: row.try_get::<i32, _>("pre_place").unwrap_or(0) != 0,
+                                stop_after_profit: row.try_get::<i32, _>("stop_after_profit").unwrap_or(0) != 0,
+                                relay_backoff_until: row.try_get::<String, _>("relay_backoff_until").ok(),
+                                momentum_threshold: row.try_get::<f64, _>("momentum_threshold").unwrap_or(0.0),
+                                depth_check: row.try_get::<i32, _>("depth_check").unwrap_or(0) != 0,
+                            })
+                        }
+                        None => {
+                            // Return defaults
+                            Ok(MintMakerSettingsRow {
+                                wallet_address: addr,
+                                enabled: false,
+                                preset: "balanced".to_string(),
+                                bid_offset_cents: 2,
+                                max_pair_cost: 0.98,
+                                min_spread_profit: 0.01,
+                                max_pairs_per_market: 5,
+                                max_total_pairs: 20,
+                                stale_order_seconds: 120,
+                                assets: vec!["BTC".to_string(), "ETH".to_string(), "SOL".to_string(), "XRP".to_string()],
+                                min_minutes_to_close: 2.0,
+                                max_minutes_to_close: 14.0,
+                                auto_place: false,
+                                auto_place_size: "2".to_string(),
+                                auto_max_markets: 1,
+                                auto_redeem: false,
+                                stop_loss_pct: 25,
+                                stop_loss_delay_secs: 30,
+                                auto_place_delay_mins: 0,
+                                auto_size_pct: 0,
+                                auto_max_attempts: 1,
+                                balance_reserve: 0.0,
+                                smart_mode: false,
+                                pre_place: false,
+                                stop_after_profit: false,
+                                relay_backoff_until: None,
+                                momentum_threshold: 0.10,
+                                depth_check: false,
+                            })
+                        }
+                    }
+                }
+
+                async fn upsert_mint_maker_settings(&self, settings: &MintMakerSettingsRow) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let assets_json = serde_json::to_string(&settings.assets)?;
+                    sqlx::query(
+                        r#"
+                        INSERT INTO mint_maker_settings (wallet_address, enabled, preset, bid_offset_cents, max_pair_cost,
+                            min_spread_profit, max_pairs_per_market, max_total_pairs, stale_order_seconds, assets,
+                            min_minutes_to_close, max_minutes_to_close, auto_place, auto_place_size, auto_max_markets,
+                            auto_redeem, stop_loss_pct, stop_loss_delay_secs, auto_place_delay_mins, auto_size_pct,
+                            auto_max_attempts, balance_reserve, smart_mode, pre_place, stop_after_profit,
+                            momentum_threshold, depth_check, created_at, updated_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29)
+                        ON CONFLICT(wallet_address) DO UPDATE SET
+                            enabled = excluded.enabled,
+                            preset = excluded.preset,
+                            bid_offset_cents = excluded.bid_offset_cents,
+                            max_pair_cost = excluded.max_pair_cost,
+                            min_spread_profit = excluded.min_spread_profit,
+                            max_pairs_per_market = excluded.max_pairs_per_market,
+                            max_total_pairs = excluded.max_total_pairs,
+                            stale_order_seconds = excluded.stale_order_seconds,
+                            assets = excluded.assets,
+                            min_minutes_to_close = excluded.min_minutes_to_close,
+                            max_minutes_to_close = excluded.max_minutes_to_close,
+                            auto_place = excluded.auto_place,
+                            auto_place_size = excluded.auto_place_size,
+                            auto_max_markets = excluded.auto_max_markets,
+                            auto_redeem = excluded.auto_redeem,
+                            stop_loss_pct = excluded.stop_loss_pct,
+                            stop_loss_delay_secs = excluded.stop_loss_delay_secs,
+                            auto_place_delay_mins = excluded.auto_place_delay_mins,
+                            auto_size_pct = excluded.auto_size_pct,
+                            auto_max_attempts = excluded.auto_max_attempts,
+                            balance_reserve = excluded.balance_reserve,
+                            smart_mode = excluded.smart_mode,
+                            pre_place = excluded.pre_place,
+                            stop_after_profit = excluded.stop_after_profit,
+                            momentum_threshold = excluded.momentum_threshold,
+                            depth_check = excluded.depth_check,
+                            updated_at = excluded.updated_at
+                        "#,
+                    )
+                    .bind(&settings.wallet_address.to_lowercase())
+                    .bind(settings.enabled as i32)
+                    .bind(&settings.preset)
+                    .bind(settings.bid_offset_cents)
+                    .bind(settings.max_pair_cost)
+                    .bind(settings.min_spread_profit)
+                    .bind(settings.max_pairs_per_market)
+                    .bind(settings.max_total_pairs)
+                    .bind(settings.stale_order_seconds)
+                    .bind(&assets_json)
+                    .bind(settings.min_minutes_to_close)
+                    .bind(settings.max_minutes_to_close)
+                    .bind(settings.auto_place as i32)
+                    .bind(&settings.auto_place_size)
+                    .bind(settings.auto_max_markets)
+                    .bind(settings.auto_redeem as i32)
+                    .bind(settings.stop_loss_pct)
+                    .bind(settings.stop_loss_delay_secs)
+                    .bind(settings.auto_place_delay_mins)
+                    .bind(settings.auto_size_pct)
+                    .bind(settings.auto_max_attempts)
+                    .bind(settings.balance_reserve)
+                    .bind(settings.smart_mode as i32)
+                    .bind(settings.pre_place as i32)
+                    .bind(settings.stop_after_profit as i32)
+                    .bind(settings.momentum_threshold)
+                    .bind(settings.depth_check as i32)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn set_mint_maker_relay_backoff(&self, wallet_address: &str, backoff_until: Option<&str>) -> Result<()> {
+                    sqlx::query(
+                        "UPDATE mint_maker_settings SET relay_backoff_until = $1 WHERE wallet_address = $2"
+                    )
+                    .bind(backoff_until)
+                    .bind(wallet_address.to_lowercase())
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn save_mint_maker_merge_attempts(&self, attempts: &[(i64, i64)]) -> Result<()> {
+                    let mut tx = self.pool.begin().await?;
+                    sqlx::query("UPDATE mint_maker_pairs SET merge_attempts = 0 WHERE merge_attempts > 0")
+                        .execute(&mut *tx)
+                        .await?;
+                    for (pair_id, count) in attempts {
+                        sqlx::query("UPDATE mint_maker_pairs SET merge_attempts = $1 WHERE id = $2")
+                            .bind(count)
+                            .bind(pair_id)
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn get_mint_maker_merge_attempts(&self) -> Result<Vec<(i64, i64)>> {
+                    let rows: Vec<(i64, i64)> = sqlx::query_as(
+                        "SELECT id, merge_attempts FROM mint_maker_pairs WHERE merge_attempts > 0 AND status IN ('Matched', 'Merging')"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+                    Ok(rows)
+                }
+
+                async fn create_mint_maker_pair(
+                    &self,
+                    wallet_address: &str,
+                    market_id: &str,
+                    condition_id: &str,
+                    question: &str,
+                    asset: &str,
+                    yes_order_id: &str,
+                    no_order_id: &str,
+                    yes_bid_price: &str,
+                    no_bid_price: &str,
+                    size: &str,
+                    yes_size: Option<&str>,
+                    no_size: Option<&str>,
+                    slug: Option<&str>,
+                    yes_token_id: Option<&str>,
+                    no_token_id: Option<&str>,
+                    neg_risk: bool,
+                    status: &str,
+                ) -> Result<i64> {
+                    let now = Utc::now().to_rfc3339();
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO mint_maker_pairs (wallet_address, market_id, condition_id, question, asset,
+                            yes_order_id, no_order_id, yes_bid_price, no_bid_price, size, yes_size, no_size, slug,
+                            yes_token_id, no_token_id, neg_risk, status, created_at, updated_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(market_id)
+                    .bind(condition_id)
+                    .bind(question)
+                    .bind(asset)
+                    .bind(yes_order_id)
+                    .bind(no_order_id)
+                    .bind(yes_bid_price)
+                    .bind(no_bid_price)
+                    .bind(size)
+                    .bind(yes_size)
+                    .bind(no_size)
+                    .bind(slug)
+                    .bind(yes_token_id)
+                    .bind(no_token_id)
+                    .bind(neg_risk as i32)
+                    .bind(status)
+                    .bind(&now)
+                    .bind(&now)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(id)
+                }
+
+                async fn update_mint_maker_pair_status(&self, pair_id: i64, status: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mint_maker_pairs SET status = $1, updated_at = $2 WHERE id = $3")
+                        .bind(status)
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn update_mint_maker_pair_redeem(
+                    &self,
+                    pair_id: i64,
+                    status: &str,
+                    pair_cost: Option<&str>,
+                    profit: Option<&str>,
+                    redeem_tx_id: Option<&str>,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+                    sqlx::query(
+                        r#"
+                        UPDATE mint_maker_pairs SET
+                            status = $1,
+                            pair_cost = COALESCE($2, pair_cost),
+                            profit = COALESCE($3, profit),
+                            redeem_tx_id = COALESCE($4, redeem_tx_id),
+                            updated_at = $5
+                        WHERE id = $6
+                        "#,
+                    )
+                    .bind(status)
+                    .bind(pair_cost)
+                    .bind(profit)
+                    .bind(redeem_tx_id)
+                    .bind(&now)
+                    .bind(pair_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    if status == "Redeemed" {
+                        if let Some(pair) = Self::mint_maker_pair(&mut tx, pair_id).await? {
+                            let held = Self::ledger_holdings(&mut tx, &ledger::pair_ref(pair_id)).await?;
+                            if let Some(txn) = ledger::pair_redeem(&pair, &held) {
+                                Self::insert_ledger_txn(&mut tx, &txn).await?;
+                            }
+                        }
+                    }
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn record_mint_maker_stop_loss_fill(&self, pair_id: i64, shares: &str, price: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+                    sqlx::query("UPDATE mint_maker_pairs SET status = $1, updated_at = $2 WHERE id = $3")
+                        .bind("StopLossFilled")
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    if let Some(pair) = Self::mint_maker_pair(&mut tx, pair_id).await? {
+                        let held = Self::ledger_holdings(&mut tx, &ledger::pair_ref(pair_id)).await?;
+                        if let (Some(lot), Ok(shares), Ok(price)) = (held.first(), Decimal::from_str(shares), Decimal::from_str(price)) {
+                            let sold = lot.take(shares);
+                            let proceeds = (price * sold.quantity).round_dp(6);
+                            let sale = LedgerTxn::sell(&pair.wallet_address, &sold, proceeds, Decimal::ZERO)
+                                .strategy("MintMaker")
+                                .reference(ledger::pair_ref(pair_id))
+                                .key(format!("{}:stop_loss", ledger::pair_ref(pair_id)));
+                            Self::insert_ledger_txn(&mut tx, &sale).await?;
+                        }
+                    }
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn set_mint_maker_stop_loss_order(&self, pair_id: i64, order_id: &str, status: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mint_maker_pairs SET stop_loss_order_id = $1, status = $2, updated_at = $3 WHERE id = $4")
+                        .bind(order_id)
+                        .bind(status)
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_mint_maker_stop_loss_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 AND status = 'StopLoss' AND stop_loss_order_id IS NOT NULL ORDER BY created_at DESC"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn update_mint_maker_pair_fill(
+                    &self,
+                    pair_id: i64,
+                    yes_fill: Option<&str>,
+                    no_fill: Option<&str>,
+                    status: &str,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+
+                    // Calculate pair_cost and profit if both filled
+                    let (pair_cost, profit) = if let (Some(y), Some(n)) = (yes_fill, no_fill) {
+                        let y_dec: f64 = y.parse().unwrap_or(0.0);
+                        let n_dec: f64 = n.parse().unwrap_or(0.0);
+                        let cost = y_dec + n_dec;
+                        let profit = 1.0 - cost;
+                        (Some(format!("{:.6}", cost)), Some(format!("{:.6}", profit)))
+                    } else {
+                        (None, None)
+                    };
+
+                    sqlx::query(
+                        r#"
+                        UPDATE mint_maker_pairs SET
+                            yes_fill_price = COALESCE($1, yes_fill_price),
+                            no_fill_price = COALESCE($2, no_fill_price),
+                            pair_cost = COALESCE($3, pair_cost),
+                            profit = COALESCE($4, profit),
+                            status = $5,
+                            updated_at = $6
+                        WHERE id = $7
+                        "#,
+                    )
+                    .bind(yes_fill)
+                    .bind(no_fill)
+                    .bind(pair_cost.as_deref())
+                    .bind(profit.as_deref())
+                    .bind(status)
+                    .bind(&now)
+                    .bind(pair_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    Self::post_mint_maker_fills(&mut tx, pair_id).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn update_mint_maker_pair_cheap_order(
+                    &self,
+                    pair_id: i64,
+                    cheap_order_id: &str,
+                    exp_fill_price: &str,
+                    is_yes_expensive: bool,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+                    if is_yes_expensive {
+                        // YES was the expensive side (already placed), NO is the cheap side (newly placed)
+                        sqlx::query(
+                            r#"
+                            UPDATE mint_maker_pairs SET
+                                no_order_id = $1,
+                                yes_fill_price = $2,
+                                status = 'HalfFilled',
+                                updated_at = $3
+                            WHERE id = $4
+                            "#,
+                        )
+                        .bind(cheap_order_id)
+                        .bind(exp_fill_price)
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    } else {
+                        // NO was the expensive side (already placed), YES is the cheap side (newly placed)
+                        sqlx::query(
+                            r#"
+                            UPDATE mint_maker_pairs SET
+                                yes_order_id = $1,
+                                no_fill_price = $2,
+                                status = 'HalfFilled',
+                                updated_at = $3
+                            WHERE id = $4
+                            "#,
+                        )
+                        .bind(cheap_order_id)
+                        .bind(exp_fill_price)
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+
+                    Self::post_mint_maker_fills(&mut tx, pair_id).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn update_mint_maker_pair_size(&self, pair_id: i64, size: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mint_maker_pairs SET size = $1, updated_at = $2 WHERE id = $3")
+                        .bind(size)
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn mark_mint_maker_pair_merged(&self, pair_id: i64, merge_tx_id: &str) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    let mut tx = self.pool.begin().await?;
+                    sqlx::query("UPDATE mint_maker_pairs SET status = 'Merged', merge_tx_id = $1, updated_at = $2 WHERE id = $3")
+                        .bind(merge_tx_id)
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    if let Some(pair) = Self::mint_maker_pair(&mut tx, pair_id).await? {
+                        let held = Self::ledger_holdings(&mut tx, &ledger::pair_ref(pair_id)).await?;
+                        if let Some(txn) = ledger::pair_merge(&pair, &held) {
+                            Self::insert_ledger_txn(&mut tx, &txn.memo(format!("tx {}", merge_tx_id))).await?;
+                        }
+                    }
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn get_mint_maker_half_filled_token_ids(&self) -> Result<Vec<(Option<String>, Option<String>)>> {
+                    let rows = sqlx::query(
+                        "SELECT yes_token_id, no_token_id FROM mint_maker_pairs WHERE status = 'HalfFilled'"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(|r| {
+                        let yes: Option<String> = r.try_get("yes_token_id").unwrap_or(None);
+                        let no: Option<String> = r.try_get("no_token_id").unwrap_or(None);
+                        (yes, no)
+                    }).collect())
+                }
+
+                async fn get_mint_maker_open_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 AND status IN ('Pending', 'ExpPlaced', 'HalfFilled', 'Matched', 'Merging', 'Orphaned', 'StopLoss') ORDER BY created_at DESC"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn get_mint_maker_pairs_by_status(&self, wallet_address: &str, status: &str) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 AND status = $2 ORDER BY created_at DESC"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(status)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn get_mint_maker_recent_pairs(&self, wallet_address: &str, limit: i64) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 ORDER BY created_at DESC LIMIT $2"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn count_mint_maker_open_pairs_for_market(&self, wallet_address: &str, market_id: &str) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mint_maker_pairs WHERE wallet_address = $1 AND market_id = $2 AND status IN ('Pending', 'ExpPlaced', 'HalfFilled', 'Matched', 'Merging')"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(market_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn count_mint_maker_all_pairs_for_market(&self, wallet_address: &str, market_id: &str) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mint_maker_pairs WHERE wallet_address = $1 AND market_id = $2"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(market_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn count_mint_maker_total_open_pairs(&self, wallet_address: &str) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mint_maker_pairs WHERE wallet_address = $1 AND status IN ('Pending', 'ExpPlaced', 'HalfFilled', 'Matched', 'Merging')"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn count_mint_maker_unfilled_pairs_for_market(&self, wallet_address: &str, market_id: &str) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mint_maker_pairs WHERE wallet_address = $1 AND market_id = $2 AND status IN ('Pending', 'ExpPlaced', 'HalfFilled')"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(market_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn count_mint_maker_total_unfilled_pairs(&self, wallet_address: &str) -> Result<i64> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mint_maker_pairs WHERE wallet_address = $1 AND status IN ('Pending', 'ExpPlaced', 'HalfFilled')"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0)
+                }
+
+                async fn sum_mint_maker_merging_capital(&self, wallet_address: &str) -> Result<f64> {
+                    let row: (f64,) = sqlx::query_as(
+                        "SELECT COALESCE(SUM(CAST(size AS DOUBLE PRECISION)), 0.0) FROM mint_maker_pairs WHERE wallet_address = $1 AND status IN ('Matched', 'Merging')"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(row.0)
+                }
+
+                async fn sum_mint_maker_committed_capital(&self, wallet_address: &str) -> Result<f64> {
+                    let row: (f64,) = sqlx::query_as(
+                        "SELECT COALESCE(SUM(
+                            CAST(COALESCE(yes_size, size) AS DOUBLE PRECISION) * CAST(yes_bid_price AS DOUBLE PRECISION) +
+                            CAST(COALESCE(no_size, size) AS DOUBLE PRECISION) * CAST(no_bid_price AS DOUBLE PRECISION)
+                        ), 0.0) \
+                         FROM mint_maker_pairs WHERE wallet_address = $1 AND status IN ('Pending', 'ExpPlaced', 'HalfFilled')"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(row.0)
+                }
+
+                async fn has_mint_maker_merged_pair_for_market(&self, wallet_address: &str, market_id: &str) -> Result<bool> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM mint_maker_pairs WHERE wallet_address = $1 AND market_id = $2 AND status = 'Merged'"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(market_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+                    Ok(count.0 > 0)
+                }
+
+                async fn get_mint_maker_stale_pairs(&self, wallet_address: &str, stale_seconds: i64) -> Result<Vec<MintMakerPairRow>> {
+                    let cutoff = (Utc::now() - Duration::seconds(stale_seconds)).to_rfc3339();
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 AND status = 'Pending' AND created_at < $2"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(&cutoff)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn get_mint_maker_redeemable_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 AND status IN ('Matched', 'HalfFilled', 'Orphaned', 'StopLoss', 'MergeFailed', 'Merging') ORDER BY created_at DESC"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn get_mint_maker_asset_fill_rate(&self, wallet_address: &str, asset: &str, hours: i64) -> Result<(i64, i64)> {
+                    let cutoff = (Utc::now() - Duration::hours(hours)).to_rfc3339();
+                    let row: (i64, i64) = sqlx::query_as(
+                        r#"
+                        SELECT
+                            COUNT(*) as total,
+                            SUM(CASE WHEN status IN ('Matched', 'Merged', 'Merging', 'Redeemed', 'MergeFailed') THEN 1 ELSE 0 END) as filled
+                        FROM mint_maker_pairs
+                        WHERE wallet_address = $1 AND asset = $2 AND created_at > $3
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(asset)
+                    .bind(&cutoff)
+                    .fetch_one(&self.pool)
+                    .await
+                    .unwrap_or((0, 0));
+
+                    Ok(row)
+                }
+
+                async fn get_mint_maker_enabled_wallets(&self) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT wallet_address FROM mint_maker_settings WHERE enabled = 1"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+                    Ok(rows.into_iter().map(|(a,)| a).collect())
+                }
+
+                async fn get_mint_maker_wallets_with_open_pairs(&self) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT DISTINCT wallet_address FROM mint_maker_pairs WHERE status IN ('Pending', 'HalfFilled', 'Matched', 'Merging', 'Orphaned', 'StopLoss')"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+                    Ok(rows.into_iter().map(|(a,)| a).collect())
+                }
+
+                async fn get_mint_maker_stats(&self, wallet_address: &str) -> Result<(i64, i64, i64, f64, f64, f64)> {
+                    let mut row: (i64, i64, i64, f64, f64, f64) = sqlx::query_as(
+                        r#"
+                        SELECT
+                            COUNT(*) as total_pairs,
+                            SUM(CASE WHEN status = 'Merged' THEN 1 ELSE 0 END) as merged_pairs,
+                            SUM(CASE WHEN status = 'Cancelled' THEN 1 ELSE 0 END) as cancelled_pairs,
+                            CAST(0.0 AS DOUBLE PRECISION) as total_profit, -- filled in from the ledger
+                            COALESCE(SUM(CASE WHEN status = 'Merged' THEN CAST(pair_cost AS DOUBLE PRECISION) ELSE 0.0 END), 0.0) as total_cost,
+                            COALESCE(AVG(CASE WHEN status = 'Merged' THEN 1.0 - CAST(pair_cost AS DOUBLE PRECISION) ELSE NULL END), 0.0) as avg_spread
+                        FROM mint_maker_pairs
+                        WHERE wallet_address = $1
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await
+                    .unwrap_or((0, 0, 0, 0.0, 0.0, 0.0));
+
+                    let ledger = ledger_stats(self, wallet_address, None, |strategy| strategy == Some("MintMaker")).await?;
+                    row.3 = ledger.realized_pnl.to_f64().unwrap_or(0.0);
+
+                    Ok(row)
+                }
+
+                async fn log_mint_maker_action(
+                    &self,
+                    wallet_address: &str,
+                    action: &str,
+                    market_id: Option<&str>,
+                    question: Option<&str>,
+                    asset: Option<&str>,
+                    yes_price: Option<&str>,
+                    no_price: Option<&str>,
+                    pair_cost: Option<&str>,
+                    profit: Option<&str>,
+                    size: Option<&str>,
+                    details: Option<&str>,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        r#"
+                        INSERT INTO mint_maker_log (wallet_address, action, market_id, question, asset,
+                            yes_price, no_price, pair_cost, profit, size, details, created_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(action)
+                    .bind(market_id)
+                    .bind(question)
+                    .bind(asset)
+                    .bind(yes_price)
+                    .bind(no_price)
+                    .bind(pair_cost)
+                    .bind(profit)
+                    .bind(size)
+                    .bind(details)
+                    .bind(&now)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn get_mint_maker_log(&self, wallet_address: &str, limit: i64) -> Result<Vec<MintMakerLogEntry>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_log WHERE wallet_address = $1 ORDER BY created_at DESC LIMIT $2"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(|row| MintMakerLogEntry {
+                        id: row.get("id"),
+                        wallet_address: row.get("wallet_address"),
+                        action: row.get("action"),
+                        market_id: row.get("market_id"),
+                        question: row.get("question"),
+                        asset: row.get("asset"),
+                        yes_price: row.get("yes_price"),
+                        no_price: row.get("no_price"),
+                        pair_cost: row.get("pair_cost"),
+                        profit: row.get("profit"),
+                        size: row.get("size"),
+                        details: row.get("details"),
+                        created_at: row.get("created_at"),
+                    }).collect())
+                }
+
+                async fn get_mint_maker_logs_after(&self, after_id: i64, limit: i64) -> Result<Vec<MintMakerLogEntry>> {
+                    let rows = sqlx::query("SELECT * FROM mint_maker_log WHERE id > $1 ORDER BY id LIMIT $2")
+                        .bind(after_id)
+                        .bind(limit)
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().map(|row| MintMakerLogEntry {
+                        id: row.get("id"),
+                        wallet_address: row.get("wallet_address"),
+                        action: row.get("action"),
+                        market_id: row.get("market_id"),
+                        question: row.get("question"),
+                        asset: row.get("asset"),
+                        yes_price: row.get("yes_price"),
+                        no_price: row.get("no_price"),
+                        pair_cost: row.get("pair_cost"),
+                        profit: row.get("profit"),
+                        size: row.get("size"),
+                        details: row.get("details"),
+                        created_at: row.get("created_at"),
+                    }).collect())
+                }
+
+                async fn get_max_mint_maker_log_id(&self) -> Result<i64> {
+                    let row: (Option<i64>,) = sqlx::query_as("SELECT MAX(id) FROM mint_maker_log")
+                        .fetch_one(&self.pool)
+                        .await?;
+                    Ok(row.0.unwrap_or(0))
+                }
+
+                // ==================== MINT MAKER ANALYTICS FUNCTIONS ====================
+
+                async fn create_mint_maker_analytics(
+                    &self,
+                    wallet_address: &str,
+                    pair_id: i64,
+                    market_id: &str,
+                    asset: Option<&str>,
+                    slug: Option<&str>,
+                    expensive_side: &str,
+                    expensive_price: f64,
+                    cheap_price: f64,
+                    size: f64,
+                ) -> Result<i64> {
+                    let now = Utc::now().to_rfc3339();
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO mint_maker_analytics (
+                            wallet_address, pair_id, market_id, asset, slug,
+                            expensive_side, expensive_price, cheap_price, size, created_at
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(pair_id)
+                    .bind(market_id)
+                    .bind(asset)
+                    .bind(slug)
+                    .bind(expensive_side)
+                    .bind(expensive_price)
+                    .bind(cheap_price)
+                    .bind(size)
+                    .bind(&now)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(id)
+                }
+
+                async fn update_analytics_expensive_filled(&self, pair_id: i64) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mint_maker_analytics SET expensive_filled_at = $1 WHERE pair_id = $2")
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn update_analytics_cheap_placed(&self, pair_id: i64) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query("UPDATE mint_maker_analytics SET cheap_placed_at = $1 WHERE pair_id = $2")
+                        .bind(&now)
+                        .bind(pair_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn update_analytics_merged(&self, pair_id: i64, pnl: f64) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        "UPDATE mint_maker_analytics SET cheap_filled_at = $1, outcome = 'merged', pnl = $2, resolved_at = $3 WHERE pair_id = $4"
+                    )
+                    .bind(&now)
+                    .bind(pnl)
+                    .bind(&now)
+                    .bind(pair_id)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn update_analytics_orphaned(&self, pair_id: i64, orphan_side: &str) -> Result<()> {
+                    sqlx::query(
+                        "UPDATE mint_maker_analytics SET outcome = 'orphan_pending', orphan_side = $1 WHERE pair_id = $2"
+                    )
+                    .bind(orphan_side)
+                    .bind(pair_id)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn update_analytics_orphan_resolved(
+                    &self,
+                    pair_id: i64,
+                    market_winner: &str,
+                    pnl: f64,
+                ) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+                    // Determine win/loss based on orphan_side vs market_winner
+                    // orphan_side is YES or NO, market_winner is "Up" or "Down"
+                    // YES = Up, NO = Down
+                    let outcome = sqlx::query_scalar::<_, String>(
+                        "SELECT orphan_side FROM mint_maker_analytics WHERE pair_id = $1"
+                    )
+                    .bind(pair_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    let final_outcome = if let Some(orphan_side) = outcome {
+                        let bet_on_up = orphan_side == "YES";
+                        let market_went_up = market_winner == "Up";
+                        if bet_on_up == market_went_up {
+                            "orphan_win"
+                        } else {
+                            "orphan_loss"
+                        }
+                    } else {
+                        "orphan_unknown"
+                    };
+
+                    sqlx::query(
+                        "UPDATE mint_maker_analytics SET outcome = $1, market_winner = $2, pnl = $3, resolved_at = $4 WHERE pair_id = $5"
+                    )
+                    .bind(final_outcome)
+                    .bind(market_winner)
+                    .bind(pnl)
+                    .bind(&now)
+                    .bind(pair_id)
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn get_mint_maker_analytics_summary(&self, wallet_address: &str) -> Result<MintMakerAnalyticsSummary> {
+                    let row: (i64, i64, i64, i64, i64, f64, f64, f64) = sqlx::query_as(
+                        r#"
+                        SELECT
+                            COUNT(*) as total_pairs,
+                            SUM(CASE WHEN outcome = 'merged' THEN 1 ELSE 0 END) as merged,
+                            SUM(CASE WHEN outcome = 'orphan_win' THEN 1 ELSE 0 END) as orphan_wins,
+                            SUM(CASE WHEN outcome = 'orphan_loss' THEN 1 ELSE 0 END) as orphan_losses,
+                            SUM(CASE WHEN outcome = 'orphan_pending' THEN 1 ELSE 0 END) as orphan_pending,
+                            COALESCE(SUM(CASE WHEN outcome = 'merged' THEN pnl ELSE 0.0 END), 0.0) as merge_pnl,
+                            COALESCE(SUM(CASE WHEN outcome = 'orphan_win' THEN pnl ELSE 0.0 END), 0.0) as orphan_win_pnl,
+                            COALESCE(SUM(CASE WHEN outcome = 'orphan_loss' THEN pnl ELSE 0.0 END), 0.0) as orphan_loss_pnl
+                        FROM mint_maker_analytics
+                        WHERE wallet_address = $1
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await
+                    .unwrap_or((0, 0, 0, 0, 0, 0.0, 0.0, 0.0));
+
+                    Ok(MintMakerAnalyticsSummary {
+                        total_pairs: row.0,
+                        merged: row.1,
+                        orphan_wins: row.2,
+                        orphan_losses: row.3,
+                        orphan_pending: row.4,
+                        merge_pnl: row.5,
+                        orphan_win_pnl: row.6,
+                        orphan_loss_pnl: row.7,
+                    })
+                }
+
+                async fn get_mint_maker_analytics_by_asset(&self, wallet_address: &str) -> Result<Vec<(String, i64, i64, i64, i64)>> {
+                    let rows: Vec<(String, i64, i64, i64, i64)> = sqlx::query_as(
+                        r#"
+                        SELECT
+                            COALESCE(asset, 'unknown') as asset,
+                            COUNT(*) as total,
+                            SUM(CASE WHEN outcome = 'merged' THEN 1 ELSE 0 END) as merged,
+                            SUM(CASE WHEN outcome = 'orphan_win' THEN 1 ELSE 0 END) as wins,
+                            SUM(CASE WHEN outcome = 'orphan_loss' THEN 1 ELSE 0 END) as losses
+                        FROM mint_maker_analytics
+                        WHERE wallet_address = $1
+                        GROUP BY asset
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows)
+                }
+
+                async fn get_pending_orphans(&self, wallet_address: &str) -> Result<Vec<(i64, String, String)>> {
+                    let rows: Vec<(i64, String, String)> = sqlx::query_as(
+                        r#"
+                        SELECT pair_id, slug, orphan_side
+                        FROM mint_maker_analytics
+                        WHERE wallet_address = $1 AND outcome = 'orphan_pending' AND slug IS NOT NULL
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows)
+                }
+
+                async fn get_mint_maker_fill_times(&self, wallet_address: &str) -> Result<(f64, f64)> {
+                    // Average time from creation to expensive fill, and expensive fill to cheap fill
+                    let rows: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+                        "SELECT created_at, expensive_filled_at, cheap_filled_at FROM mint_maker_analytics WHERE wallet_address = $1",
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await
+                    .unwrap_or_default();
+
+                    Ok(crate::db::sql::mint_maker::average_fill_secs(&rows))
+                }
+
+                // ==================== REPORTS AND EXPORT ====================
+
+                async fn get_merged_pairs_between(
+                    &self,
+                    wallet_address: &str,
+                    from: &DateTime<Utc>,
+                    to: &DateTime<Utc>,
+                ) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 AND status = 'Merged' AND updated_at >= $2 AND updated_at < $3 ORDER BY updated_at",
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(from.to_rfc3339())
+                    .bind(to.to_rfc3339())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+
+                async fn get_orphan_totals_between(
+                    &self,
+                    wallet_address: &str,
+                    from: &DateTime<Utc>,
+                    to: &DateTime<Utc>,
+                ) -> Result<(i64, f64, f64)> {
+                    let from = from.to_rfc3339();
+                    let to = to.to_rfc3339();
+                    let row: (i64, f64, f64) = sqlx::query_as(
+                        r#"
+                        SELECT
+                            COALESCE(SUM(CASE WHEN created_at >= $1 AND created_at < $2 THEN 1 ELSE 0 END), 0),
+                            COALESCE(SUM(CASE WHEN created_at >= $3 AND created_at < $4
+                                THEN (CASE WHEN orphan_side = expensive_side THEN expensive_price ELSE cheap_price END) * size
+                                ELSE 0.0 END), 0.0),
+                            COALESCE(SUM(CASE WHEN resolved_at >= $5 AND resolved_at < $6 AND outcome IN ('orphan_win', 'orphan_loss')
+                                THEN COALESCE(pnl, 0.0) ELSE 0.0 END), 0.0)
+                        FROM mint_maker_analytics
+                        WHERE wallet_address = $7 AND outcome LIKE 'orphan%'
+                        "#,
+                    )
+                    .bind(&from)
+                    .bind(&to)
+                    .bind(&from)
+                    .bind(&to)
+                    .bind(&from)
+                    .bind(&to)
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(row)
+                }
+
+                async fn get_wallets_with_pairs_updated_since(&self, since: &DateTime<Utc>) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as("SELECT DISTINCT wallet_address FROM mint_maker_pairs WHERE updated_at >= $1")
+                        .bind(since.to_rfc3339())
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.into_iter().map(|(w,)| w).collect())
+                }
+
+                async fn get_mint_maker_pairs(&self, wallet_address: &str) -> Result<Vec<MintMakerPairRow>> {
+                    let rows = sqlx::query("SELECT * FROM mint_maker_pairs WHERE wallet_address = $1 ORDER BY id")
+                        .bind(wallet_address.to_lowercase())
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().map(Self::row_to_mm_pair).collect())
+                }
+            }
+
+            impl $store {
+                async fn mint_maker_pair(conn: &mut $conn, pair_id: i64) -> Result<Option<MintMakerPairRow>> {
+                    let row = sqlx::query("SELECT * FROM mint_maker_pairs WHERE id = $1")
+                        .bind(pair_id)
+                        .fetch_optional(&mut *conn)
+                        .await?;
+                    Ok(row.as_ref().map(Self::row_to_mm_pair))
+                }
+
+                /// Post a ledger buy for each filled side of a pair (each side once), on `conn`
+                async fn post_mint_maker_fills(conn: &mut $conn, pair_id: i64) -> Result<()> {
+                    if let Some(pair) = Self::mint_maker_pair(conn, pair_id).await? {
+                        for txn in ledger::pair_buys(&pair) {
+                            Self::insert_ledger_txn(conn, &txn.at(Utc::now())).await?;
+                        }
+                    }
+                    Ok(())
+                }
+
+                /// Helper to convert a row to MintMakerPairRow
+                pub(super) fn row_to_mm_pair(row: &$row) -> MintMakerPairRow {
+                    let neg_risk_int: i32 = row.try_get("neg_risk").unwrap_or(0);
+                    MintMakerPairRow {
+                        id: row.get("id"),
+                        wallet_address: row.get("wallet_address"),
+                        market_id: row.get("market_id"),
+                        condition_id: row.get("condition_id"),
+                        question: row.get("question"),
+                        asset: row.get("asset"),
+                        yes_order_id: row.get("yes_order_id"),
+                        no_order_id: row.get("no_order_id"),
+                        yes_bid_price: row.get("yes_bid_price"),
+                        no_bid_price: row.get("no_bid_price"),
+                        yes_fill_price: row.get("yes_fill_price"),
+                        no_fill_price: row.get("no_fill_price"),
+                        pair_cost: row.get("pair_cost"),
+                        profit: row.get("profit"),
+                        size: row.get("size"),
+                        yes_size: row.try_get("yes_size").ok(),
+                        no_size: row.try_get("no_size").ok(),
+                        slug: row.try_get("slug").ok(),
+                        yes_token_id: row.try_get("yes_token_id").ok(),
+                        no_token_id: row.try_get("no_token_id").ok(),
+                        neg_risk: neg_risk_int != 0,
+                        status: row.get("status"),
+                        merge_tx_id: row.get("merge_tx_id"),
+                        stop_loss_order_id: row.try_get("stop_loss_order_id").ok().flatten(),
+                        created_at: row.get("created_at"),
+                        updated_at: row.get("updated_at"),
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
+
+/// Average seconds from creation to the expensive fill and from the expensive
+/// to the cheap fill, over (created_at, expensive_filled_at, cheap_filled_at) rows
+pub(in crate::db) fn average_fill_secs(rows: &[(String, Option<String>, Option<String>)]) -> (f64, f64) {
+    let parse = |s: &str| DateTime::parse_from_rfc3339(s).ok();
+    let average = |secs: Vec<f64>| {
+        if secs.is_empty() {
+            0.0
+        } else {
+            secs.iter().sum::<f64>() / secs.len() as f64
+        }
+    };
+
+    let mut expensive = Vec::new();
+    let mut cheap = Vec::new();
+    for (created_at, expensive_at, cheap_at) in rows {
+        let Some(expensive_at) = expensive_at.as_deref().and_then(parse) else {
+            continue;
+        };
+        if let Some(created_at) = parse(created_at) {
+            expensive.push((expensive_at - created_at).num_milliseconds() as f64 / 1000.0);
+        }
+        if let Some(cheap_at) = cheap_at.as_deref().and_then(parse) {
+            cheap.push((cheap_at - expensive_at).num_milliseconds() as f64 / 1000.0);
+        }
+    }
+
+    (average(expensive), average(cheap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_fill_secs() {
+        let rows = vec![
+            (
+                "2026-01-01T00:00:00+00:00".to_string(),
+                Some("2026-01-01T00:00:10+00:00".to_string()),
+                Some("2026-01-01T00:00:40+00:00".to_string()),
+            ),
+            (
+                "2026-01-01T00:00:00+00:00".to_string(),
+                Some("2026-01-01T00:00:30+00:00".to_string()),
+                None,
+            ),
+            ("2026-01-01T00:00:00+00:00".to_string(), None, None),
+        ];
+        assert_eq!(average_fill_secs(&rows), (20.0, 30.0));
+        assert_eq!(average_fill_secs(&[]), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,51 @@
+//! Repository implementations shared by both database backends
+//!
+//! Each domain module holds one `repository!` macro with the SQL and row
+//! mapping for its trait, and `repositories!` expands them all for a store.
+//! `Database` (SQLite) and `PgStore` (PostgreSQL) each invoke it once with
+//! their own store, connection and row types, so both run the same statements.
+//!
+//! The statements stick to syntax both dialects accept: `$n` placeholders,
+//! `ON CONFLICT ... DO UPDATE` / `DO NOTHING`, `RETURNING id` instead of the
+//! last insert rowid, `NOT is_paper` for flags and `CAST(.. AS DOUBLE PRECISION)`
+//! for float aggregates. Timestamps and decimals are stored as text in both.
+
+pub(super) mod auth;
+pub(super) mod auto_trading;
+pub(super) mod chain_events;
+pub(super) mod key_vault;
+pub(super) mod leases;
+pub(super) mod ledger;
+pub(super) mod market_index;
+pub(super) mod mc;
+pub(super) mod mint_maker;
+pub(super) mod notifications;
+pub(super) mod onchain_jobs;
+pub(super) mod orders;
+pub(super) mod positions;
+pub(super) mod reports;
+pub(super) mod withdrawals;
+
+/// Implement every repository trait for `$store`, whose `pool` runs on
+/// connection type `$conn` and returns rows of type `$row`
+macro_rules! repositories {
+    ($store:ty, $conn:ty, $row:ty) => {
+        $crate::db::sql::auth::repository!($store, $conn, $row);
+        $crate::db::sql::auto_trading::repository!($store, $conn, $row);
+        $crate::db::sql::chain_events::repository!($store, $conn, $row);
+        $crate::db::sql::key_vault::repository!($store, $conn, $row);
+        $crate::db::sql::leases::repository!($store, $conn, $row);
+        $crate::db::sql::ledger::repository!($store, $conn, $row);
+        $crate::db::sql::market_index::repository!($store, $conn, $row);
+        $crate::db::sql::mc::repository!($store, $conn, $row);
+        $crate::db::sql::mint_maker::repository!($store, $conn, $row);
+        $crate::db::sql::notifications::repository!($store, $conn, $row);
+        $crate::db::sql::onchain_jobs::repository!($store, $conn, $row);
+        $crate::db::sql::orders::repository!($store, $conn, $row);
+        $crate::db::sql::positions::repository!($store, $conn, $row);
+        $crate::db::sql::reports::repository!($store, $conn, $row);
+        $crate::db::sql::withdrawals::repository!($store, $conn, $row);
+    };
+}
+
+pub(crate) use repositories;
@@ -0,0 +1,149 @@
+//! Notification channels (NotificationRepository)
+
+/// Implement `NotificationRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod notifications {
+            use crate::db::{NotificationChannel, NotificationRepository};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::Utc;
+            use sqlx::Row;
+
+            #[async_trait]
+            impl NotificationRepository for $store {
+                async fn create_notification_channel(
+                    &self,
+                    wallet_address: &str,
+                    name: &str,
+                    target: &str,
+                    events: &str,
+                    template: Option<&str>,
+                    rate_limit_per_minute: i64,
+                ) -> Result<NotificationChannel> {
+                    let now = Utc::now().to_rfc3339();
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO notification_channels (wallet_address, name, target, events, template, rate_limit_per_minute, enabled, created_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, 1, $7)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(name)
+                    .bind(target)
+                    .bind(events)
+                    .bind(template)
+                    .bind(rate_limit_per_minute)
+                    .bind(&now)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(NotificationChannel {
+                        id,
+                        wallet_address: wallet_address.to_lowercase(),
+                        name: name.to_string(),
+                        target: target.to_string(),
+                        events: events.to_string(),
+                        template: template.map(str::to_string),
+                        rate_limit_per_minute,
+                        enabled: true,
+                        created_at: now,
+                        last_sent_at: None,
+                        last_error: None,
+                    })
+                }
+
+                async fn get_notification_channels(&self, wallet_address: &str) -> Result<Vec<NotificationChannel>> {
+                    let rows = sqlx::query("SELECT * FROM notification_channels WHERE wallet_address = $1 ORDER BY id")
+                        .bind(wallet_address.to_lowercase())
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().map(Self::row_to_notification_channel).collect())
+                }
+
+                async fn get_notification_channel(&self, wallet_address: &str, id: i64) -> Result<Option<NotificationChannel>> {
+                    let row = sqlx::query("SELECT * FROM notification_channels WHERE id = $1 AND wallet_address = $2")
+                        .bind(id)
+                        .bind(wallet_address.to_lowercase())
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    Ok(row.as_ref().map(Self::row_to_notification_channel))
+                }
+
+                async fn update_notification_channel(&self, channel: &NotificationChannel) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        UPDATE notification_channels
+                        SET name = $1, target = $2, events = $3, template = $4, rate_limit_per_minute = $5, enabled = $6
+                        WHERE id = $7 AND wallet_address = $8
+                        "#,
+                    )
+                    .bind(&channel.name)
+                    .bind(&channel.target)
+                    .bind(&channel.events)
+                    .bind(&channel.template)
+                    .bind(channel.rate_limit_per_minute)
+                    .bind(channel.enabled as i32)
+                    .bind(channel.id)
+                    .bind(channel.wallet_address.to_lowercase())
+                    .execute(&self.pool)
+                    .await?;
+                    Ok(())
+                }
+
+                async fn delete_notification_channel(&self, wallet_address: &str, id: i64) -> Result<bool> {
+                    let result = sqlx::query("DELETE FROM notification_channels WHERE id = $1 AND wallet_address = $2")
+                        .bind(id)
+                        .bind(wallet_address.to_lowercase())
+                        .execute(&self.pool)
+                        .await?;
+
+                    Ok(result.rows_affected() > 0)
+                }
+
+                async fn record_notification_delivery(&self, id: i64, error: Option<&str>) -> Result<()> {
+                    match error {
+                        None => {
+                            sqlx::query("UPDATE notification_channels SET last_sent_at = $1, last_error = NULL WHERE id = $2")
+                                .bind(Utc::now().to_rfc3339())
+                                .bind(id)
+                                .execute(&self.pool)
+                                .await?;
+                        }
+                        Some(error) => {
+                            sqlx::query("UPDATE notification_channels SET last_error = $1 WHERE id = $2")
+                                .bind(error)
+                                .bind(id)
+                                .execute(&self.pool)
+                                .await?;
+                        }
+                    }
+                    Ok(())
+                }
+            }
+
+            impl $store {
+                fn row_to_notification_channel(row: &$row) -> NotificationChannel {
+                    NotificationChannel {
+                        id: row.get("id"),
+                        wallet_address: row.get("wallet_address"),
+                        name: row.get("name"),
+                        target: row.get("target"),
+                        events: row.get("events"),
+                        template: row.get("template"),
+                        rate_limit_per_minute: row.get("rate_limit_per_minute"),
+                        enabled: row.get::<i32, _>("enabled") != 0,
+                        created_at: row.get("created_at"),
+                        last_sent_at: row.get("last_sent_at"),
+                        last_error: row.get("last_error"),
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
@@ -0,0 +1,164 @@
+//! On-chain job queue (OnchainJobRepository)
+
+/// Implement `OnchainJobRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod onchain_jobs {
+            use crate::db::OnchainJobRepository;
+            use crate::types::{OnchainJob, OnchainJobKind, OnchainJobStatus};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Utc};
+            use sqlx::Row;
+
+            #[async_trait]
+            impl OnchainJobRepository for $store {
+                async fn create_onchain_job(
+                    &self,
+                    wallet_address: &str,
+                    kind: OnchainJobKind,
+                    status: OnchainJobStatus,
+                    description: &str,
+                    reference: Option<&str>,
+                    replaces_job_id: Option<i64>,
+                ) -> Result<i64> {
+                    let now = Utc::now().to_rfc3339();
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO onchain_jobs (wallet_address, kind, status, description, reference, replaces_job_id, created_at, updated_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(format!("{:?}", kind))
+                    .bind(format!("{:?}", status))
+                    .bind(description)
+                    .bind(reference)
+                    .bind(replaces_job_id)
+                    .bind(&now)
+                    .bind(&now)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(id)
+                }
+
+                async fn mark_onchain_job_submitted(
+                    &self,
+                    id: i64,
+                    relay_tx_id: Option<&str>,
+                    tx_hash: Option<&str>,
+                    payload: Option<&str>,
+                ) -> Result<()> {
+                    sqlx::query(
+                        r#"
+                        UPDATE onchain_jobs
+                        SET status = 'Submitted',
+                            relay_tx_id = COALESCE($1, relay_tx_id),
+                            tx_hash = COALESCE($2, tx_hash),
+                            payload = COALESCE($3, payload),
+                            updated_at = $4
+                        WHERE id = $5
+                        "#,
+                    )
+                    .bind(relay_tx_id)
+                    .bind(tx_hash)
+                    .bind(payload)
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn update_onchain_job_status(
+                    &self,
+                    id: i64,
+                    status: OnchainJobStatus,
+                    tx_hash: Option<&str>,
+                    error: Option<&str>,
+                ) -> Result<()> {
+                    sqlx::query(
+                        "UPDATE onchain_jobs SET status = $1, tx_hash = COALESCE($2, tx_hash), error = $3, updated_at = $4 WHERE id = $5",
+                    )
+                    .bind(format!("{:?}", status))
+                    .bind(tx_hash)
+                    .bind(error)
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn get_onchain_job(&self, id: i64) -> Result<Option<OnchainJob>> {
+                    let row = sqlx::query("SELECT * FROM onchain_jobs WHERE id = $1")
+                        .bind(id)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    Ok(row.as_ref().and_then(Self::row_to_onchain_job))
+                }
+
+                async fn get_onchain_jobs_for_wallet(&self, wallet_address: &str, limit: i64) -> Result<Vec<OnchainJob>> {
+                    let rows = sqlx::query("SELECT * FROM onchain_jobs WHERE wallet_address = $1 ORDER BY id DESC LIMIT $2")
+                        .bind(wallet_address.to_lowercase())
+                        .bind(limit)
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().filter_map(Self::row_to_onchain_job).collect())
+                }
+
+                async fn get_onchain_jobs_by_status(&self, status: OnchainJobStatus) -> Result<Vec<OnchainJob>> {
+                    let rows = sqlx::query("SELECT * FROM onchain_jobs WHERE status = $1 ORDER BY id")
+                        .bind(format!("{:?}", status))
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows.iter().filter_map(Self::row_to_onchain_job).collect())
+                }
+
+                async fn get_onchain_job_replacement(&self, id: i64) -> Result<Option<OnchainJob>> {
+                    let row = sqlx::query("SELECT * FROM onchain_jobs WHERE replaces_job_id = $1 ORDER BY id DESC LIMIT 1")
+                        .bind(id)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    Ok(row.as_ref().and_then(Self::row_to_onchain_job))
+                }
+            }
+
+            impl $store {
+                /// Helper to convert a row to OnchainJob (None if kind/status are unknown)
+                fn row_to_onchain_job(row: &$row) -> Option<OnchainJob> {
+                    let kind: String = row.get("kind");
+                    let status: String = row.get("status");
+                    let created_at: String = row.get("created_at");
+                    let updated_at: String = row.get("updated_at");
+
+                    Some(OnchainJob {
+                        id: row.get("id"),
+                        wallet_address: row.get("wallet_address"),
+                        kind: OnchainJobKind::from_str_opt(&kind)?,
+                        status: OnchainJobStatus::from_str_opt(&status)?,
+                        description: row.get("description"),
+                        reference: row.get("reference"),
+                        relay_tx_id: row.get("relay_tx_id"),
+                        tx_hash: row.get("tx_hash"),
+                        payload: row.get("payload"),
+                        replaces_job_id: row.get("replaces_job_id"),
+                        error: row.get("error"),
+                        created_at: DateTime::parse_from_rfc3339(&created_at).ok()?.with_timezone(&Utc),
+                        updated_at: DateTime::parse_from_rfc3339(&updated_at).ok()?.with_timezone(&Utc),
+                    })
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
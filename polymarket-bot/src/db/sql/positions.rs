@@ -0,0 +1,1098 @@
+//! Positions and their trailing-stop peaks (PositionRepository)
+
+/// Implement `PositionRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod positions {
+            use crate::db::{PartialCloseResult, PositionHistory, PositionRepository, ledger_stats};
+            use crate::ledger::{self, LedgerTxn};
+            use crate::types::{BotStats, Position, PositionStatus, Side, StrategyType};
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Utc};
+            use rust_decimal::Decimal;
+            use sqlx::Row;
+            use std::str::FromStr;
+
+            #[async_trait]
+            impl PositionRepository for $store {
+                async fn create_position(
+                    &self,
+                    market_id: &str,
+                    question: &str,
+                    side: Side,
+                    entry_price: Decimal,
+                    size: Decimal,
+                    strategy: StrategyType,
+                    is_paper: bool,
+                ) -> Result<i64> {
+                    let now = Utc::now().to_rfc3339();
+                    let side_str = format!("{:?}", side);
+                    let strategy_str = format!("{:?}", strategy);
+
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO positions (market_id, question, side, entry_price, size, strategy, opened_at, status, is_paper)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, 'Open', $8)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(market_id)
+                    .bind(question)
+                    .bind(side_str)
+                    .bind(entry_price.to_string())
+                    .bind(size.to_string())
+                    .bind(strategy_str)
+                    .bind(now)
+                    .bind(is_paper)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(id)
+                }
+
+                async fn update_position_status(&self, id: i64, status: PositionStatus) -> Result<()> {
+                    let status_str = format!("{:?}", status);
+                    sqlx::query("UPDATE positions SET status = $1 WHERE id = $2")
+                        .bind(status_str)
+                        .bind(id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn close_position(
+                    &self,
+                    id: i64,
+                    exit_price: Decimal,
+                    order_id: Option<&str>,
+                ) -> Result<()> {
+                    let mut tx = self.pool.begin().await?;
+
+                    // Get position to calculate PnL
+                    let row = sqlx::query("SELECT entry_price, size, wallet_address, strategy, is_paper FROM positions WHERE id = $1")
+                        .bind(id)
+                        .fetch_optional(&mut *tx)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("Position not found"))?;
+
+                    let entry_price_str: String = row.get("entry_price");
+                    let size_str: String = row.get("size");
+                    let wallet_address: Option<String> = row.get("wallet_address");
+                    let strategy: String = row.get("strategy");
+                    let is_paper: bool = row.get("is_paper");
+
+                    let entry_price = Decimal::from_str(&entry_price_str)?;
+                    let size = Decimal::from_str(&size_str)?;
+
+                    // Calculate shares and PnL
+                    let shares = size / entry_price;
+                    let pnl = (exit_price - entry_price) * shares;
+
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        "UPDATE positions SET closed_at = $1, exit_price = $2, pnl = $3, status = 'Closed', order_id = COALESCE($4, order_id) WHERE id = $5",
+                    )
+                    .bind(&now)
+                    .bind(exit_price.to_string())
+                    .bind(pnl.to_string())
+                    .bind(order_id)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    // Clean up position peak if exists
+                    sqlx::query("DELETE FROM position_peaks WHERE position_id = $1")
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    // A close with an order is a sale; without one the market resolved at `exit_price`
+                    if let (Some(wallet_address), false) = (wallet_address, is_paper) {
+                        let held = Self::ledger_holdings(&mut tx, &ledger::position_ref(id)).await?;
+                        let txn = match order_id {
+                            Some(_) => ledger::position_sale(&wallet_address, id, &strategy, &held, None, exit_price, Decimal::ZERO, "close"),
+                            None => ledger::position_resolution(&wallet_address, id, &strategy, &held, exit_price),
+                        };
+                        if let Some(txn) = txn {
+                            Self::insert_ledger_txn(&mut tx, &txn).await?;
+                        }
+                    }
+                    tx.commit().await?;
+
+                    Ok(())
+                }
+
+                async fn update_position_end_date(&self, id: i64, end_date: DateTime<Utc>) -> Result<()> {
+                    sqlx::query("UPDATE positions SET end_date = $1 WHERE id = $2")
+                        .bind(end_date.to_rfc3339())
+                        .bind(id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_open_positions(&self) -> Result<Vec<Position>> {
+                    // Only get live trades (not paper), exclude already resolved
+                    // Note: is_paper is false for live trades
+                    let rows = sqlx::query(
+                        "SELECT * FROM positions WHERE status IN ('Open', 'PendingResolution') AND NOT is_paper",
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let positions = rows
+                        .iter()
+                        .filter_map(|row| self.row_to_position(row).ok())
+                        .collect();
+
+                    Ok(positions)
+                }
+
+                async fn get_position_by_market(&self, market_id: &str) -> Result<Option<Position>> {
+                    let row = sqlx::query("SELECT * FROM positions WHERE market_id = $1 AND status = 'Open'")
+                        .bind(market_id)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => Ok(Some(self.row_to_position(&r)?)),
+                        None => Ok(None),
+                    }
+                }
+
+                async fn get_stats(&self) -> Result<BotStats> {
+                    // Include both 'Resolved' (market resolved) and 'Closed' (manually sold) positions
+                    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM positions WHERE status IN ('Resolved', 'Closed')")
+                        .fetch_one(&self.pool)
+                        .await?;
+
+                    let wins: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM positions WHERE status IN ('Resolved', 'Closed') AND CAST(pnl AS DOUBLE PRECISION) > 0",
+                    )
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    let losses: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM positions WHERE status IN ('Resolved', 'Closed') AND CAST(pnl AS DOUBLE PRECISION) <= 0",
+                    )
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    let pnls: Vec<(Option<String>,)> = sqlx::query_as(
+                        "SELECT pnl FROM positions WHERE status IN ('Resolved', 'Closed')",
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let sniper_trades: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM positions WHERE strategy = 'ResolutionSniper' AND status IN ('Resolved', 'Closed')",
+                    )
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    let sniper_wins: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM positions WHERE strategy = 'ResolutionSniper' AND status IN ('Resolved', 'Closed') AND CAST(pnl AS DOUBLE PRECISION) > 0",
+                    )
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    let pnl_decimal: Decimal = pnls
+                        .iter()
+                        .filter_map(|(pnl,)| pnl.as_deref().and_then(|p| Decimal::from_str(p).ok()))
+                        .sum();
+
+                    Ok(BotStats {
+                        total_trades: total.0,
+                        winning_trades: wins.0,
+                        losing_trades: losses.0,
+                        total_pnl: pnl_decimal,
+                        sniper_trades: sniper_trades.0,
+                        sniper_wins: sniper_wins.0,
+                        avg_hold_time_hours: 0.0, // TODO: calculate from closed_at - opened_at
+                    })
+                }
+
+                // ==================== POSITION MANAGEMENT (MULTI-USER) ====================
+
+                async fn create_position_for_wallet(
+                    &self,
+                    wallet_address: &str,
+                    market_id: &str,
+                    question: &str,
+                    slug: Option<&str>,
+                    side: Side,
+                    entry_price: Decimal,
+                    size: Decimal,
+                    strategy: StrategyType,
+                    is_paper: bool,
+                    end_date: Option<DateTime<Utc>>,
+                    token_id: Option<&str>,
+                    order_id: Option<&str>,
+                    neg_risk: bool,
+                ) -> Result<i64> {
+                    let now = Utc::now().to_rfc3339();
+                    let side_str = format!("{:?}", side);
+                    let strategy_str = format!("{:?}", strategy);
+                    let end_date_str = end_date.map(|d| d.to_rfc3339());
+
+                    // Calculate initial remaining_size as shares (size / entry_price)
+                    let shares = size / entry_price;
+
+                    let mut tx = self.pool.begin().await?;
+                    let position_id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO positions (wallet_address, market_id, question, slug, side, entry_price, size, strategy, opened_at, status, is_paper, end_date, token_id, order_id, remaining_size, realized_pnl, total_sold_size, neg_risk)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'Open', $10, $11, $12, $13, $14, '0', '0', $15)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(wallet_address)
+                    .bind(market_id)
+                    .bind(question)
+                    .bind(slug)
+                    .bind(side_str)
+                    .bind(entry_price.to_string())
+                    .bind(size.to_string())
+                    .bind(strategy_str)
+                    .bind(now)
+                    .bind(is_paper)
+                    .bind(end_date_str)
+                    .bind(token_id)
+                    .bind(order_id)
+                    .bind(shares.to_string())
+                    .bind(neg_risk as i32)
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                    if !is_paper {
+                        let token = ledger::lot_token(token_id, market_id, &format!("{:?}", side));
+                        let reference = ledger::position_ref(position_id);
+                        let buy = LedgerTxn::buy(wallet_address, &token, shares, size, Decimal::ZERO)
+                            .strategy(format!("{:?}", strategy))
+                            .key(format!("{}:buy", reference))
+                            .reference(reference);
+                        Self::insert_ledger_txn(&mut tx, &buy).await?;
+                    }
+                    tx.commit().await?;
+
+                    Ok(position_id)
+                }
+
+                async fn get_positions_for_wallet(&self, wallet_address: &str) -> Result<Vec<Position>> {
+                    // Only return live trades (exclude paper trades)
+                    let rows = sqlx::query(
+                        "SELECT * FROM positions WHERE wallet_address = $1 AND NOT is_paper ORDER BY opened_at DESC",
+                    )
+                    .bind(wallet_address)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let positions = rows
+                        .iter()
+                        .filter_map(|row| self.row_to_position(row).ok())
+                        .collect();
+
+                    Ok(positions)
+                }
+
+                async fn get_open_positions_for_wallet(&self, wallet_address: &str) -> Result<Vec<Position>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM positions WHERE wallet_address = $1 AND status IN ('Open', 'PendingResolution') AND NOT is_paper ORDER BY opened_at DESC",
+                    )
+                    .bind(wallet_address)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let positions = rows
+                        .iter()
+                        .filter_map(|row| self.row_to_position(row).ok())
+                        .collect();
+
+                    Ok(positions)
+                }
+
+                async fn get_stats_for_wallet(&self, wallet_address: &str) -> Result<BotStats> {
+                    let ledger = ledger_stats(self, wallet_address, None, |_| true).await?;
+                    let sniper = ledger.strategy("ResolutionSniper");
+
+                    Ok(BotStats {
+                        total_trades: ledger.trades,
+                        winning_trades: ledger.wins,
+                        losing_trades: ledger.losses,
+                        total_pnl: ledger.realized_pnl,
+                        sniper_trades: sniper.trades,
+                        sniper_wins: sniper.wins,
+                        avg_hold_time_hours: 0.0,
+                    })
+                }
+
+                async fn close_position_for_wallet(
+                    &self,
+                    wallet_address: &str,
+                    position_id: i64,
+                    exit_price: Decimal,
+                    fee: Option<Decimal>,
+                ) -> Result<Decimal> {
+                    let mut tx = self.pool.begin().await?;
+
+                    // First get the position to verify ownership and calculate PnL
+                    let row = sqlx::query(
+                        "SELECT entry_price, size, side, strategy, is_paper FROM positions WHERE id = $1 AND wallet_address = $2",
+                    )
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Position not found or unauthorized"))?;
+
+                    let entry_price: String = row.get("entry_price");
+                    let size: String = row.get("size");
+                    let _side: String = row.get("side");
+                    let strategy: String = row.get("strategy");
+                    let is_paper: bool = row.get("is_paper");
+
+                    let entry = Decimal::from_str(&entry_price)?;
+                    let size_dec = Decimal::from_str(&size)?;
+
+                    // Calculate PnL: (exit_price - entry_price) * shares - fee
+                    // Shares = size / entry_price
+                    let shares = size_dec / entry;
+                    let fee_amount = fee.unwrap_or(Decimal::ZERO);
+
+                    // For both YES and NO positions: profit if you sell at a higher price than you bought
+                    // (You bought tokens at entry_price, sold at exit_price)
+                    let pnl = (exit_price - entry) * shares - fee_amount;
+
+                    // Update the position
+                    sqlx::query(
+                        r#"
+                        UPDATE positions
+                        SET status = 'Closed', exit_price = $1, pnl = $2, closed_at = $3, fee_paid = $4, remaining_size = '0'
+                        WHERE id = $5 AND wallet_address = $6
+                        "#,
+                    )
+                    .bind(exit_price.to_string())
+                    .bind(pnl.to_string())
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(fee_amount.to_string())
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    if !is_paper {
+                        let held = Self::ledger_holdings(&mut tx, &ledger::position_ref(position_id)).await?;
+                        let sale = ledger::position_sale(wallet_address, position_id, &strategy, &held, None, exit_price, fee_amount, "close");
+                        if let Some(txn) = sale {
+                            Self::insert_ledger_txn(&mut tx, &txn).await?;
+                        }
+                    }
+                    tx.commit().await?;
+
+                    Ok(pnl)
+                }
+
+                async fn partial_close_position_for_wallet(
+                    &self,
+                    wallet_address: &str,
+                    position_id: i64,
+                    sell_shares: Decimal,
+                    exit_price: Decimal,
+                ) -> Result<PartialCloseResult> {
+                    let mut tx = self.pool.begin().await?;
+
+                    // Get the position to verify ownership and get current state
+                    let row = sqlx::query(
+                        "SELECT entry_price, size, remaining_size, realized_pnl, total_sold_size, avg_exit_price, strategy, is_paper FROM positions WHERE id = $1 AND wallet_address = $2",
+                    )
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Position not found or unauthorized"))?;
+
+                    let entry_price_str: String = row.get("entry_price");
+                    let size_str: String = row.get("size");
+                    let remaining_size_str: Option<String> = row.get("remaining_size");
+                    let realized_pnl_str: Option<String> = row.get("realized_pnl");
+                    let total_sold_size_str: Option<String> = row.get("total_sold_size");
+                    let avg_exit_price_str: Option<String> = row.get("avg_exit_price");
+                    let strategy: String = row.get("strategy");
+                    let is_paper: bool = row.get("is_paper");
+
+                    let entry_price = Decimal::from_str(&entry_price_str)?;
+                    let size = Decimal::from_str(&size_str)?;
+
+                    // Calculate original total shares
+                    let total_shares = size / entry_price;
+
+                    // Get remaining shares (backward compat: if NULL, use total)
+                    let remaining_shares = remaining_size_str
+                        .and_then(|s| Decimal::from_str(&s).ok())
+                        .unwrap_or(total_shares);
+
+                    // Get cumulative realized PnL
+                    let prev_realized_pnl = realized_pnl_str
+                        .and_then(|s| Decimal::from_str(&s).ok())
+                        .unwrap_or(Decimal::ZERO);
+
+                    // Get total shares sold so far
+                    let prev_sold_size = total_sold_size_str
+                        .and_then(|s| Decimal::from_str(&s).ok())
+                        .unwrap_or(Decimal::ZERO);
+
+                    // Get previous avg exit price
+                    let prev_avg_exit = avg_exit_price_str
+                        .and_then(|s| Decimal::from_str(&s).ok());
+
+                    // Validate we have enough shares to sell
+                    if sell_shares > remaining_shares {
+                        anyhow::bail!("Cannot sell {} shares, only {} remaining", sell_shares, remaining_shares);
+                    }
+
+                    // Calculate PnL for this sell:
+                    // Cost basis for these shares = (sell_shares / total_shares) * original_size_usdc
+                    // But simpler: PnL = (exit_price - entry_price) * sell_shares
+                    let pnl_this_sell = (exit_price - entry_price) * sell_shares;
+
+                    // Update cumulative values
+                    let new_remaining = remaining_shares - sell_shares;
+                    let new_realized_pnl = prev_realized_pnl + pnl_this_sell;
+                    let new_sold_size = prev_sold_size + sell_shares;
+
+                    // Calculate new weighted average exit price
+                    let new_avg_exit = if let Some(prev_avg) = prev_avg_exit {
+                        // Weighted average: (prev_avg * prev_sold + exit_price * sell_shares) / new_sold_size
+                        ((prev_avg * prev_sold_size) + (exit_price * sell_shares)) / new_sold_size
+                    } else {
+                        // First sale, just use exit_price
+                        exit_price
+                    };
+
+                    // Check if fully closed (using small epsilon for floating point comparison)
+                    let is_fully_closed = new_remaining <= Decimal::new(1, 6); // < 0.000001 shares
+
+                    // Update the position
+                    if is_fully_closed {
+                        // Fully closed - set final state
+                        sqlx::query(
+                            r#"
+                            UPDATE positions
+                            SET status = 'Closed',
+                                remaining_size = '0',
+                                realized_pnl = $1,
+                                total_sold_size = $2,
+                                avg_exit_price = $3,
+                                exit_price = $4,
+                                pnl = $5,
+                                closed_at = $6
+                            WHERE id = $7 AND wallet_address = $8
+                            "#,
+                        )
+                        .bind(new_realized_pnl.to_string())
+                        .bind(new_sold_size.to_string())
+                        .bind(new_avg_exit.to_string())
+                        .bind(new_avg_exit.to_string()) // Final exit_price = weighted avg
+                        .bind(new_realized_pnl.to_string()) // Final pnl = total realized
+                        .bind(Utc::now().to_rfc3339())
+                        .bind(position_id)
+                        .bind(wallet_address)
+                        .execute(&mut *tx)
+                        .await?;
+                    } else {
+                        // Partial close - update tracking fields, keep status Open
+                        sqlx::query(
+                            r#"
+                            UPDATE positions
+                            SET remaining_size = $1,
+                                realized_pnl = $2,
+                                total_sold_size = $3,
+                                avg_exit_price = $4
+                            WHERE id = $5 AND wallet_address = $6
+                            "#,
+                        )
+                        .bind(new_remaining.to_string())
+                        .bind(new_realized_pnl.to_string())
+                        .bind(new_sold_size.to_string())
+                        .bind(new_avg_exit.to_string())
+                        .bind(position_id)
+                        .bind(wallet_address)
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+
+                    if !is_paper {
+                        // Each sale gets its own key: the same share count may legitimately be sold twice
+                        let step = format!("sell:{}", new_sold_size);
+                        let held = Self::ledger_holdings(&mut tx, &ledger::position_ref(position_id)).await?;
+                        let sale = ledger::position_sale(
+                            wallet_address,
+                            position_id,
+                            &strategy,
+                            &held,
+                            Some(sell_shares),
+                            exit_price,
+                            Decimal::ZERO,
+                            &step,
+                        );
+                        if let Some(txn) = sale {
+                            Self::insert_ledger_txn(&mut tx, &txn).await?;
+                        }
+                    }
+                    tx.commit().await?;
+
+                    Ok(PartialCloseResult {
+                        pnl_this_sell,
+                        is_fully_closed,
+                        total_realized_pnl: new_realized_pnl,
+                        remaining_shares: new_remaining,
+                    })
+                }
+
+                async fn update_position_token_id(
+                    &self,
+                    wallet_address: &str,
+                    position_id: i64,
+                    token_id: &str,
+                ) -> Result<()> {
+                    let result = sqlx::query(
+                        "UPDATE positions SET token_id = $1 WHERE id = $2 AND wallet_address = $3",
+                    )
+                    .bind(token_id)
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .execute(&self.pool)
+                    .await?;
+
+                    if result.rows_affected() == 0 {
+                        anyhow::bail!("Position not found or unauthorized");
+                    }
+
+                    Ok(())
+                }
+
+                async fn update_position_entry_price(
+                    &self,
+                    wallet_address: &str,
+                    position_id: i64,
+                    entry_price: &str,
+                ) -> Result<()> {
+                    let mut tx = self.pool.begin().await?;
+                    let previous: Option<(String, String, bool)> = sqlx::query_as(
+                        "SELECT entry_price, size, is_paper FROM positions WHERE id = $1 AND wallet_address = $2",
+                    )
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+
+                    let result = sqlx::query(
+                        "UPDATE positions SET entry_price = $1 WHERE id = $2 AND wallet_address = $3",
+                    )
+                    .bind(entry_price)
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    if result.rows_affected() == 0 {
+                        anyhow::bail!("Position not found or unauthorized");
+                    }
+
+                    // Same cost, different share count: correct the tokens the ledger holds
+                    if let Some((old_entry, size, false)) = previous {
+                        let parsed = (
+                            Decimal::from_str(&old_entry),
+                            Decimal::from_str(entry_price),
+                            Decimal::from_str(&size),
+                        );
+                        if let (Ok(old_entry), Ok(new_entry), Ok(size)) = parsed {
+                            let reference = ledger::position_ref(position_id);
+                            let held = Self::ledger_holdings(&mut tx, &reference).await?;
+                            if old_entry > Decimal::ZERO && new_entry > Decimal::ZERO && new_entry != old_entry {
+                                if let Some(lot) = held.first() {
+                                    let delta = size / new_entry - size / old_entry;
+                                    let adjust = LedgerTxn::adjust_shares(wallet_address, &lot.token_id, delta).reference(reference);
+                                    Self::insert_ledger_txn(&mut tx, &adjust).await?;
+                                }
+                            }
+                        }
+                    }
+                    tx.commit().await?;
+
+                    Ok(())
+                }
+
+                async fn get_position_by_id(
+                    &self,
+                    wallet_address: &str,
+                    position_id: i64,
+                ) -> Result<Option<Position>> {
+                    let row = sqlx::query(
+                        "SELECT * FROM positions WHERE id = $1 AND wallet_address = $2",
+                    )
+                    .bind(position_id)
+                    .bind(wallet_address)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    match row {
+                        Some(r) => Ok(Some(self.row_to_position(&r)?)),
+                        None => Ok(None),
+                    }
+                }
+
+                async fn get_all_open_position_token_ids(&self) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT DISTINCT token_id FROM positions
+                         WHERE status IN ('Open', 'PendingResolution')
+                         AND token_id IS NOT NULL
+                         AND NOT is_paper"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.into_iter().map(|(token_id,)| token_id).collect())
+                }
+
+                // ==================== POSITION PEAKS (TRAILING STOP) ====================
+
+                async fn update_position_peak(&self, position_id: i64, price: Decimal) -> Result<()> {
+                    let now = Utc::now().to_rfc3339();
+
+                    // Get current peak
+                    let current: Option<(String,)> = sqlx::query_as(
+                        "SELECT peak_price FROM position_peaks WHERE position_id = $1"
+                    )
+                    .bind(position_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    let should_update = match current {
+                        Some((peak_str,)) => {
+                            let current_peak = Decimal::from_str(&peak_str).unwrap_or_default();
+                            price > current_peak
+                        }
+                        None => true,
+                    };
+
+                    if should_update {
+                        sqlx::query(
+                            r#"
+                            INSERT INTO position_peaks (position_id, peak_price, peak_at)
+                            VALUES ($1, $2, $3)
+                            ON CONFLICT(position_id) DO UPDATE SET
+                                peak_price = excluded.peak_price,
+                                peak_at = excluded.peak_at
+                            "#,
+                        )
+                        .bind(position_id)
+                        .bind(price.to_string())
+                        .bind(&now)
+                        .execute(&self.pool)
+                        .await?;
+                    }
+
+                    Ok(())
+                }
+
+                async fn get_position_peak(&self, position_id: i64) -> Result<Option<Decimal>> {
+                    let row: Option<(String,)> = sqlx::query_as(
+                        "SELECT peak_price FROM position_peaks WHERE position_id = $1"
+                    )
+                    .bind(position_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    Ok(row.and_then(|(s,)| Decimal::from_str(&s).ok()))
+                }
+
+                async fn delete_position_peak(&self, position_id: i64) -> Result<()> {
+                    sqlx::query("DELETE FROM position_peaks WHERE position_id = $1")
+                        .bind(position_id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_all_position_peaks(&self) -> Result<Vec<crate::services::auto_trader::PositionPeak>> {
+                    let rows = sqlx::query(
+                        "SELECT position_id, peak_price, peak_at FROM position_peaks"
+                    )
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let peaks = rows
+                        .iter()
+                        .filter_map(|row| {
+                            let position_id: i64 = row.get("position_id");
+                            let peak_price_str: String = row.get("peak_price");
+                            let peak_at_str: String = row.get("peak_at");
+
+                            Some(crate::services::auto_trader::PositionPeak {
+                                position_id,
+                                peak_price: Decimal::from_str(&peak_price_str).ok()?,
+                                peak_at: DateTime::parse_from_rfc3339(&peak_at_str).ok()?.with_timezone(&Utc),
+                            })
+                        })
+                        .collect();
+
+                    Ok(peaks)
+                }
+
+                // ==================== POSITION QUERIES FOR AUTO-TRADING ====================
+
+                async fn get_positions_by_token_id(&self, token_id: &str) -> Result<Vec<Position>> {
+                    let rows = sqlx::query(
+                        "SELECT * FROM positions WHERE token_id = $1 AND status IN ('Open', 'PendingResolution') AND NOT is_paper"
+                    )
+                    .bind(token_id)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let positions = rows
+                        .iter()
+                        .filter_map(|row| self.row_to_position(row).ok())
+                        .collect();
+
+                    Ok(positions)
+                }
+
+                async fn has_open_position(&self, wallet_address: &str, market_id: &str) -> Result<bool> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM positions WHERE wallet_address = $1 AND market_id = $2 AND status IN ('Open', 'PendingResolution') AND NOT is_paper"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(market_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(count.0 > 0)
+                }
+
+                async fn has_open_dispute_position(&self, wallet_address: &str, condition_id: &str) -> Result<bool> {
+                    let count: (i64,) = sqlx::query_as(
+                        "SELECT COUNT(*) FROM positions WHERE wallet_address = $1 AND market_id = $2 AND strategy = 'Dispute' AND status IN ('Open', 'PendingResolution') AND NOT is_paper"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(condition_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(count.0 > 0)
+                }
+
+                async fn get_redeem_candidates(&self, wallet_address: &str) -> Result<Vec<Position>> {
+                    let rows = sqlx::query(
+                        r#"
+                        SELECT * FROM positions
+                        WHERE wallet_address = $1 AND redeemed_at IS NULL AND NOT is_paper AND token_id IS NOT NULL
+                          AND (remaining_size IS NULL OR CAST(remaining_size AS NUMERIC) > 0)
+                          AND (status IN ('Resolved', 'Closed') OR strategy = 'Dispute'
+                               OR (end_date IS NOT NULL AND end_date < $2))
+                        ORDER BY opened_at
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(Utc::now().to_rfc3339())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    let positions = rows
+                        .iter()
+                        .filter_map(|row| self.row_to_position(row).ok())
+                        .collect();
+
+                    Ok(positions)
+                }
+
+                async fn record_position_redemption(
+                    &self,
+                    position_id: i64,
+                    payout: Decimal,
+                    proceeds: Decimal,
+                    redeem_tx_id: &str,
+                ) -> Result<()> {
+                    let mut tx = self.pool.begin().await?;
+                    let row = sqlx::query("SELECT entry_price, size, wallet_address, strategy, is_paper FROM positions WHERE id = $1")
+                        .bind(position_id)
+                        .fetch_optional(&mut *tx)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("Position not found"))?;
+
+                    let wallet_address: String = row.get("wallet_address");
+                    let strategy: String = row.get("strategy");
+                    let is_paper: bool = row.get("is_paper");
+                    let entry_price = Decimal::from_str(&row.get::<String, _>("entry_price"))?;
+                    let size = Decimal::from_str(&row.get::<String, _>("size"))?;
+                    let pnl = (payout - entry_price) * (size / entry_price);
+
+                    let now = Utc::now().to_rfc3339();
+                    sqlx::query(
+                        r#"
+                        UPDATE positions SET
+                            status = 'Closed',
+                            closed_at = COALESCE(closed_at, $1),
+                            exit_price = $2,
+                            pnl = $3,
+                            redeemed_at = $4,
+                            redeem_tx_id = $5,
+                            redeem_proceeds = $6
+                        WHERE id = $7
+                        "#,
+                    )
+                    .bind(&now)
+                    .bind(payout.to_string())
+                    .bind(pnl.to_string())
+                    .bind(&now)
+                    .bind(redeem_tx_id)
+                    .bind(proceeds.to_string())
+                    .bind(position_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    sqlx::query("DELETE FROM position_peaks WHERE position_id = $1")
+                        .bind(position_id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    if !is_paper {
+                        let held = Self::ledger_holdings(&mut tx, &ledger::position_ref(position_id)).await?;
+                        if let Some(txn) = ledger::position_redeem(&wallet_address, position_id, &strategy, &held, proceeds) {
+                            Self::insert_ledger_txn(&mut tx, &txn).await?;
+                        }
+                    }
+                    tx.commit().await?;
+
+                    Ok(())
+                }
+
+                async fn mark_position_redeemed(&self, position_id: i64, redeem_tx_id: Option<&str>) -> Result<()> {
+                    let mut tx = self.pool.begin().await?;
+                    sqlx::query("UPDATE positions SET redeemed_at = $1, redeem_tx_id = COALESCE($2, redeem_tx_id) WHERE id = $3")
+                        .bind(Utc::now().to_rfc3339())
+                        .bind(redeem_tx_id)
+                        .bind(position_id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    // Tokens the ledger still holds are worthless (lost) or already gone
+                    let row: Option<(String, String, bool)> =
+                        sqlx::query_as("SELECT wallet_address, strategy, is_paper FROM positions WHERE id = $1")
+                            .bind(position_id)
+                            .fetch_optional(&mut *tx)
+                            .await?;
+                    if let Some((wallet_address, strategy, false)) = row {
+                        let held = Self::ledger_holdings(&mut tx, &ledger::position_ref(position_id)).await?;
+                        if let Some(txn) = ledger::position_redeem(&wallet_address, position_id, &strategy, &held, Decimal::ZERO) {
+                            Self::insert_ledger_txn(&mut tx, &txn).await?;
+                        }
+                    }
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn count_open_positions(&self, wallet_address: &str) -> Result<i32> {
+                    let count: (i32,) = sqlx::query_as(
+                        "SELECT CAST(COUNT(*) AS INTEGER) FROM positions WHERE wallet_address = $1 AND status IN ('Open', 'PendingResolution') AND NOT is_paper"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(count.0)
+                }
+
+                async fn get_total_exposure(&self, wallet_address: &str) -> Result<Decimal> {
+                    let sum: Option<(f64,)> = sqlx::query_as(
+                        "SELECT COALESCE(SUM(CAST(size AS DOUBLE PRECISION)), 0) FROM positions WHERE wallet_address = $1 AND status IN ('Open', 'PendingResolution') AND NOT is_paper"
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+                    Ok(Decimal::from_f64_retain(sum.map(|(s,)| s).unwrap_or(0.0)).unwrap_or_default())
+                }
+
+                async fn get_position_by_id_internal(&self, position_id: i64) -> Result<Option<Position>> {
+                    let row = sqlx::query("SELECT * FROM positions WHERE id = $1")
+                        .bind(position_id)
+                        .fetch_optional(&self.pool)
+                        .await?;
+
+                    match row {
+                        Some(r) => Ok(Some(self.row_to_position(&r)?)),
+                        None => Ok(None),
+                    }
+                }
+
+                // ==================== REPORTS AND EXPORT ====================
+
+                async fn get_closed_positions_between(
+                    &self,
+                    wallet_address: &str,
+                    from: &DateTime<Utc>,
+                    to: &DateTime<Utc>,
+                ) -> Result<Vec<Position>> {
+                    let rows = sqlx::query(
+                        r#"
+                        SELECT * FROM positions
+                        WHERE wallet_address = $1 AND status IN ('Closed', 'Resolved') AND NOT is_paper
+                          AND closed_at >= $2 AND closed_at < $3
+                        ORDER BY closed_at
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(from.to_rfc3339())
+                    .bind(to.to_rfc3339())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows
+                        .iter()
+                        .filter_map(|row| self.row_to_position(row).ok())
+                        .collect())
+                }
+
+                async fn get_wallets_with_positions_since(&self, since: &DateTime<Utc>) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        r#"
+                        SELECT DISTINCT wallet_address FROM positions
+                        WHERE wallet_address IS NOT NULL AND NOT is_paper
+                          AND (status IN ('Open', 'PendingResolution') OR closed_at >= $1)
+                        "#,
+                    )
+                    .bind(since.to_rfc3339())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.into_iter().map(|(w,)| w).collect())
+                }
+
+                async fn get_wallets_holding_markets(&self, market_ids: &[String]) -> Result<Vec<String>> {
+                    if market_ids.is_empty() {
+                        return Ok(Vec::new());
+                    }
+
+                    let placeholders: Vec<String> = (1..=market_ids.len()).map(|i| format!("${}", i)).collect();
+                    let sql = format!(
+                        "SELECT DISTINCT wallet_address FROM positions
+                         WHERE wallet_address IS NOT NULL AND status IN ('Open', 'PendingResolution') AND market_id IN ({})",
+                        placeholders.join(", ")
+                    );
+                    let mut query = sqlx::query_as::<_, (String,)>(&sql);
+                    for market_id in market_ids {
+                        query = query.bind(market_id);
+                    }
+                    let rows = query.fetch_all(&self.pool).await?;
+
+                    Ok(rows.into_iter().map(|(w,)| w).collect())
+                }
+
+                async fn get_position_history(&self, wallet_address: &str) -> Result<Vec<PositionHistory>> {
+                    let rows = sqlx::query("SELECT * FROM positions WHERE wallet_address = $1 AND NOT is_paper ORDER BY id")
+                        .bind(wallet_address.to_lowercase())
+                        .fetch_all(&self.pool)
+                        .await?;
+
+                    Ok(rows
+                        .iter()
+                        .filter_map(|row| {
+                            let position = self.row_to_position(row).ok()?;
+                            let redeemed_at: Option<String> = row.try_get("redeemed_at").unwrap_or(None);
+                            let redeem_proceeds: Option<String> = row.try_get("redeem_proceeds").unwrap_or(None);
+                            Some(PositionHistory {
+                                position,
+                                redeemed_at: redeemed_at
+                                    .and_then(|d| DateTime::parse_from_rfc3339(&d).ok())
+                                    .map(|d| d.with_timezone(&Utc)),
+                                redeem_proceeds: redeem_proceeds.and_then(|p| Decimal::from_str(&p).ok()),
+                            })
+                        })
+                        .collect())
+                }
+            }
+
+            impl $store {
+                pub(super) fn row_to_position(&self, row: &$row) -> Result<Position> {
+                    let side_str: String = row.get("side");
+                    let side = match side_str.as_str() {
+                        "Yes" => Side::Yes,
+                        _ => Side::No,
+                    };
+
+                    let strategy_str: String = row.get("strategy");
+                    let strategy = match strategy_str.as_str() {
+                        "Dispute" => StrategyType::Dispute,
+                        "MillionairesClub" => StrategyType::MillionairesClub,
+                        "NegRiskArb" => StrategyType::NegRiskArb,
+                        _ => StrategyType::ResolutionSniper,
+                    };
+
+                    let status_str: String = row.get("status");
+                    let status = match status_str.as_str() {
+                        "Open" => PositionStatus::Open,
+                        "PendingResolution" => PositionStatus::PendingResolution,
+                        "Resolved" => PositionStatus::Resolved,
+                        _ => PositionStatus::Closed,
+                    };
+
+                    let entry_price_str: String = row.get("entry_price");
+                    let size_str: String = row.get("size");
+                    let opened_at_str: String = row.get("opened_at");
+
+                    let exit_price: Option<String> = row.get("exit_price");
+                    let pnl: Option<String> = row.get("pnl");
+                    let closed_at: Option<String> = row.get("closed_at");
+                    let is_paper: bool = row.try_get("is_paper").unwrap_or(true);
+                    let end_date: Option<String> = row.try_get("end_date").unwrap_or(None);
+                    let token_id: Option<String> = row.try_get("token_id").unwrap_or(None);
+                    let order_id: Option<String> = row.try_get("order_id").unwrap_or(None);
+                    let slug: Option<String> = row.try_get("slug").unwrap_or(None);
+                    let remaining_size: Option<String> = row.try_get("remaining_size").unwrap_or(None);
+                    let realized_pnl: Option<String> = row.try_get("realized_pnl").unwrap_or(None);
+                    let total_sold_size: Option<String> = row.try_get("total_sold_size").unwrap_or(None);
+                    let avg_exit_price: Option<String> = row.try_get("avg_exit_price").unwrap_or(None);
+                    let neg_risk: bool = row.try_get::<i32, _>("neg_risk").unwrap_or(0) != 0;
+                    let fee_paid: Option<String> = row.try_get("fee_paid").unwrap_or(None);
+
+                    let wallet_address: Option<String> = row.try_get("wallet_address").unwrap_or(None);
+
+                    Ok(Position {
+                        id: row.get("id"),
+                        wallet_address: wallet_address.unwrap_or_default(),
+                        market_id: row.get("market_id"),
+                        question: row.get("question"),
+                        slug,
+                        side,
+                        entry_price: Decimal::from_str(&entry_price_str)?,
+                        size: Decimal::from_str(&size_str)?,
+                        strategy,
+                        opened_at: DateTime::parse_from_rfc3339(&opened_at_str)?.with_timezone(&Utc),
+                        closed_at: closed_at
+                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                            .map(|d| d.with_timezone(&Utc)),
+                        exit_price: exit_price.and_then(|s| Decimal::from_str(&s).ok()),
+                        pnl: pnl.and_then(|s| Decimal::from_str(&s).ok()),
+                        status,
+                        is_paper,
+                        end_date: end_date
+                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                            .map(|d| d.with_timezone(&Utc)),
+                        token_id,
+                        order_id,
+                        remaining_size: remaining_size.and_then(|s| Decimal::from_str(&s).ok()),
+                        realized_pnl: realized_pnl.and_then(|s| Decimal::from_str(&s).ok()),
+                        total_sold_size: total_sold_size.and_then(|s| Decimal::from_str(&s).ok()),
+                        avg_exit_price: avg_exit_price.and_then(|s| Decimal::from_str(&s).ok()),
+                        neg_risk,
+                        fee_paid: fee_paid.and_then(|s| Decimal::from_str(&s).ok()),
+                    })
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
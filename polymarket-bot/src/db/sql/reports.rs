@@ -0,0 +1,99 @@
+//! Generated reports (ReportRepository)
+
+/// Implement `ReportRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod reports {
+            use crate::db::ReportRepository;
+            use crate::services::reports::Report;
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, NaiveDate, Utc};
+
+            #[async_trait]
+            impl ReportRepository for $store {
+                async fn save_report(&self, report: &Report) -> Result<i64> {
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO reports (wallet_address, period, period_start, period_end, generated_at, realized_pnl, report_json)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7)
+                        ON CONFLICT (wallet_address, period, period_start) DO UPDATE SET
+                            period_end = excluded.period_end,
+                            generated_at = excluded.generated_at,
+                            realized_pnl = excluded.realized_pnl,
+                            report_json = excluded.report_json
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(report.wallet_address.to_lowercase())
+                    .bind(report.period.as_str())
+                    .bind(report.period_start.to_string())
+                    .bind(report.period_end.to_string())
+                    .bind(report.generated_at.to_rfc3339())
+                    .bind(report.realized_pnl.to_string())
+                    .bind(serde_json::to_string(report)?)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(id)
+                }
+
+                async fn get_reports(&self, wallet_address: &str, period: Option<&str>, limit: i64) -> Result<Vec<(i64, Report)>> {
+                    let rows: Vec<(i64, String)> = sqlx::query_as(
+                        r#"
+                        SELECT id, report_json FROM reports
+                        WHERE wallet_address = $1 AND ($2 IS NULL OR period = $3)
+                        ORDER BY period_start DESC, period
+                        LIMIT $4
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(period)
+                    .bind(period)
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows
+                        .into_iter()
+                        .filter_map(|(id, json)| serde_json::from_str(&json).ok().map(|report| (id, report)))
+                        .collect())
+                }
+
+                async fn get_report(&self, wallet_address: &str, id: i64) -> Result<Option<(i64, Report)>> {
+                    let row: Option<(String,)> =
+                        sqlx::query_as("SELECT report_json FROM reports WHERE id = $1 AND wallet_address = $2")
+                            .bind(id)
+                            .bind(wallet_address.to_lowercase())
+                            .fetch_optional(&self.pool)
+                            .await?;
+
+                    match row {
+                        Some((json,)) => Ok(Some((id, serde_json::from_str(&json)?))),
+                        None => Ok(None),
+                    }
+                }
+
+                async fn get_final_report_wallets(
+                    &self,
+                    period: &str,
+                    period_start: NaiveDate,
+                    ended_at: &DateTime<Utc>,
+                ) -> Result<Vec<String>> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT wallet_address FROM reports WHERE period = $1 AND period_start = $2 AND generated_at >= $3",
+                    )
+                    .bind(period)
+                    .bind(period_start.to_string())
+                    .bind(ended_at.to_rfc3339())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.into_iter().map(|(w,)| w).collect())
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
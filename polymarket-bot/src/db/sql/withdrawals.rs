@@ -0,0 +1,188 @@
+//! Withdrawals and their destination allowlist (WithdrawalRepository)
+
+/// Implement `WithdrawalRepository` for a store (see the module docs)
+macro_rules! repository {
+    ($store:ty, $conn:ty, $row:ty) => {
+        mod withdrawals {
+            use crate::db::{WithdrawalAddressRow, WithdrawalRepository, WithdrawalReservation};
+            use crate::ledger::LedgerTxn;
+            use crate::wallet::safeguards;
+            use anyhow::Result;
+            use async_trait::async_trait;
+            use chrono::{DateTime, Utc};
+            use rust_decimal::Decimal;
+            use std::str::FromStr;
+
+            #[async_trait]
+            impl WithdrawalRepository for $store {
+                async fn add_withdrawal_address(
+                    &self,
+                    wallet_address: &str,
+                    address: &str,
+                    label: Option<&str>,
+                    active_at: DateTime<Utc>,
+                ) -> Result<WithdrawalAddressRow> {
+                    let created_at = Utc::now().to_rfc3339();
+                    let active_at = active_at.to_rfc3339();
+
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO withdrawal_allowlist (wallet_address, address, label, created_at, active_at)
+                        VALUES ($1, $2, $3, $4, $5)
+                        ON CONFLICT (wallet_address, address) DO UPDATE SET
+                            label = excluded.label,
+                            created_at = excluded.created_at,
+                            active_at = excluded.active_at
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(address.to_lowercase())
+                    .bind(label)
+                    .bind(&created_at)
+                    .bind(&active_at)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                    Ok(WithdrawalAddressRow {
+                        id,
+                        address: address.to_lowercase(),
+                        label: label.map(str::to_string),
+                        created_at,
+                        active_at,
+                    })
+                }
+
+                async fn get_withdrawal_addresses(&self, wallet_address: &str) -> Result<Vec<WithdrawalAddressRow>> {
+                    let rows: Vec<(i64, String, Option<String>, String, String)> = sqlx::query_as(
+                        r#"
+                        SELECT id, address, label, created_at, active_at FROM withdrawal_allowlist
+                        WHERE wallet_address = $1
+                        ORDER BY created_at ASC
+                        "#,
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows
+                        .into_iter()
+                        .map(|(id, address, label, created_at, active_at)| WithdrawalAddressRow {
+                            id,
+                            address,
+                            label,
+                            created_at,
+                            active_at,
+                        })
+                        .collect())
+                }
+
+                async fn remove_withdrawal_address(&self, wallet_address: &str, id: i64) -> Result<bool> {
+                    let result = sqlx::query("DELETE FROM withdrawal_allowlist WHERE id = $1 AND wallet_address = $2")
+                        .bind(id)
+                        .bind(wallet_address.to_lowercase())
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(result.rows_affected() > 0)
+                }
+
+                async fn reserve_withdrawal(
+                    &self,
+                    wallet_address: &str,
+                    to_address: &str,
+                    amount: Decimal,
+                    limit: Decimal,
+                    since: DateTime<Utc>,
+                ) -> Result<WithdrawalReservation> {
+                    let wallet_address = wallet_address.to_lowercase();
+                    let mut tx = self.pool.begin().await?;
+
+                    // Take the wallet's row lock (the write lock on SQLite) before reading the
+                    // total, so a concurrent reservation waits for this one to commit
+                    sqlx::query("UPDATE wallets SET address = address WHERE address = $1")
+                        .bind(&wallet_address)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT amount FROM withdrawals WHERE wallet_address = $1 AND created_at >= $2 AND status != 'failed'",
+                    )
+                    .bind(&wallet_address)
+                    .bind(since.to_rfc3339())
+                    .fetch_all(&mut *tx)
+                    .await?;
+                    let withdrawn: Decimal = rows.iter().filter_map(|(a,)| Decimal::from_str(a).ok()).sum();
+                    if safeguards::check_daily_limit(amount, withdrawn, limit).is_err() {
+                        return Ok(WithdrawalReservation::OverLimit { withdrawn });
+                    }
+
+                    let id: i64 = sqlx::query_scalar(
+                        r#"
+                        INSERT INTO withdrawals (wallet_address, to_address, amount, status, created_at)
+                        VALUES ($1, $2, $3, 'pending', $4)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(&wallet_address)
+                    .bind(to_address.to_lowercase())
+                    .bind(amount.to_string())
+                    .bind(Utc::now().to_rfc3339())
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                    tx.commit().await?;
+                    Ok(WithdrawalReservation::Reserved { id })
+                }
+
+                async fn record_withdrawal(&self, id: i64, transaction_id: &str) -> Result<()> {
+                    let mut tx = self.pool.begin().await?;
+
+                    let row: Option<(String, String, String)> = sqlx::query_as(
+                        r#"
+                        UPDATE withdrawals SET status = 'submitted', transaction_id = $1
+                        WHERE id = $2 AND status = 'pending'
+                        RETURNING wallet_address, to_address, amount
+                        "#,
+                    )
+                    .bind(transaction_id)
+                    .bind(id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+                    let Some((wallet_address, to_address, amount)) = row else {
+                        anyhow::bail!("Withdrawal {} is not pending", id);
+                    };
+
+                    let txn = LedgerTxn::withdrawal(&wallet_address, Decimal::from_str(&amount)?)
+                        .key(format!("withdrawal:{}", transaction_id))
+                        .memo(format!("to {}", to_address));
+                    Self::insert_ledger_txn(&mut tx, &txn).await?;
+
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn fail_withdrawal(&self, id: i64) -> Result<()> {
+                    sqlx::query("UPDATE withdrawals SET status = 'failed' WHERE id = $1 AND status = 'pending'")
+                        .bind(id)
+                        .execute(&self.pool)
+                        .await?;
+                    Ok(())
+                }
+
+                async fn get_withdrawn_since(&self, wallet_address: &str, since: DateTime<Utc>) -> Result<Decimal> {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT amount FROM withdrawals WHERE wallet_address = $1 AND created_at >= $2 AND status != 'failed'",
+                    )
+                    .bind(wallet_address.to_lowercase())
+                    .bind(since.to_rfc3339())
+                    .fetch_all(&self.pool)
+                    .await?;
+
+                    Ok(rows.iter().filter_map(|(a,)| Decimal::from_str(a).ok()).sum())
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use repository;
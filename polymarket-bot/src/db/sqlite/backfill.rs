@@ -0,0 +1,123 @@
+//! One-off ledger backfill for SQLite databases created before the ledger
+
+use crate::db::{Database, LedgerRepository};
+use crate::ledger::{self, LedgerTxn};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::Row;
+use std::str::FromStr;
+use tracing::info;
+
+impl Database {
+    /// Rebuild the ledger from positions, Mint Maker pairs, MC trades and withdrawals
+    /// recorded before it existed. Runs once, while the ledger is still empty.
+    pub(in crate::db) async fn backfill_ledger(&self) -> Result<()> {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ledger_transactions")
+            .fetch_one(&self.pool)
+            .await?;
+        if count > 0 {
+            return Ok(());
+        }
+
+        let mut txns: Vec<LedgerTxn> = Vec::new();
+
+        let rows = sqlx::query("SELECT * FROM positions WHERE is_paper = 0 AND wallet_address IS NOT NULL ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        for row in &rows {
+            let Ok(position) = self.row_to_position(row) else {
+                continue;
+            };
+            let redeemed_at: Option<String> = row.try_get("redeemed_at").unwrap_or(None);
+            let redeem_proceeds: Option<String> = row.try_get("redeem_proceeds").unwrap_or(None);
+            txns.extend(ledger::backfill_position(
+                &position,
+                redeemed_at
+                    .and_then(|d| DateTime::parse_from_rfc3339(&d).ok())
+                    .map(|d| d.with_timezone(&Utc)),
+                redeem_proceeds.and_then(|p| Decimal::from_str(&p).ok()),
+            ));
+        }
+
+        let rows = sqlx::query("SELECT * FROM mint_maker_pairs ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        for pair in rows.iter().map(Self::row_to_mm_pair) {
+            txns.extend(ledger::backfill_pair(&pair));
+        }
+
+        let trades: Vec<(i64, String, String, Option<String>, String, String, Option<String>, String, Option<String>)> =
+            sqlx::query_as(
+                "SELECT id, market_id, side, token_id, size, shares, exit_price, opened_at, closed_at FROM mc_trades ORDER BY id",
+            )
+            .fetch_all(&self.pool)
+            .await?;
+        for (id, market_id, side, token_id, size, shares, exit_price, opened_at, closed_at) in trades {
+            let (Ok(size), Ok(shares), Ok(opened_at)) = (
+                Decimal::from_str(&size),
+                Decimal::from_str(&shares),
+                DateTime::parse_from_rfc3339(&opened_at),
+            ) else {
+                continue;
+            };
+            let reference = ledger::mc_ref(id);
+            let token = ledger::lot_token(token_id.as_deref(), &market_id, &side);
+            let buy = LedgerTxn::buy(ledger::MC_BOOK, &token, shares, size, Decimal::ZERO)
+                .strategy("MillionairesClub")
+                .key(format!("{}:buy", reference))
+                .reference(reference.as_str())
+                .memo("backfill")
+                .at(opened_at.with_timezone(&Utc));
+            let lot = ledger::lots(std::slice::from_ref(&buy));
+            txns.push(buy);
+
+            let exit = exit_price.and_then(|e| Decimal::from_str(&e).ok());
+            let closed_at = closed_at
+                .and_then(|d| DateTime::parse_from_rfc3339(&d).ok())
+                .map(|d| d.with_timezone(&Utc));
+            if let (Some(exit), Some(closed_at)) = (exit, closed_at) {
+                txns.push(
+                    LedgerTxn::redeem(ledger::MC_BOOK, &lot, (exit * shares).round_dp(6))
+                        .strategy("MillionairesClub")
+                        .key(format!("{}:redeem", reference))
+                        .reference(reference)
+                        .memo("backfill")
+                        .at(closed_at),
+                );
+            }
+        }
+
+        let withdrawals: Vec<(String, String, Option<String>, String)> = sqlx::query_as(
+            "SELECT wallet_address, amount, transaction_id, created_at FROM withdrawals WHERE status = 'submitted' ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for (wallet_address, amount, transaction_id, created_at) in withdrawals {
+            if let (Ok(amount), Ok(created_at)) = (Decimal::from_str(&amount), DateTime::parse_from_rfc3339(&created_at)) {
+                let mut txn = LedgerTxn::withdrawal(&wallet_address, amount)
+                    .memo("backfill")
+                    .at(created_at.with_timezone(&Utc));
+                if let Some(transaction_id) = transaction_id {
+                    txn = txn.key(format!("withdrawal:{}", transaction_id));
+                }
+                txns.push(txn);
+            }
+        }
+
+        if txns.is_empty() {
+            return Ok(());
+        }
+        // Post in time order so ids follow the history
+        txns.sort_by_key(|t| t.created_at);
+        let mut posted = 0;
+        for txn in &txns {
+            if self.post_ledger(txn).await?.is_some() {
+                posted += 1;
+            }
+        }
+        info!("Ledger: backfilled {} transactions from existing history", posted);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+//! SQLite implementations of the repository traits, on `Database`'s own pool
+
+mod backfill;
+
+super::sql::repositories!(crate::db::Database, sqlx::SqliteConnection, sqlx::sqlite::SqliteRow);
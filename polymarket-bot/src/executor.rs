@@ -37,7 +37,7 @@ impl Executor {
     /// Execute an opportunity (paper trade or real)
     pub async fn execute(&self, opportunity: &Opportunity) -> Result<ExecutionResult> {
         // Check if we already have a position in this market
-        if let Some(existing) = self.db.get_position_by_market(&opportunity.market_id).await? {
+        if let Some(existing) = self.db.positions().get_position_by_market(&opportunity.market_id).await? {
             return Ok(ExecutionResult::Skipped {
                 reason: format!("Already have position in market: {}", existing.market_id),
             });
@@ -77,7 +77,7 @@ impl Executor {
         // Record the position in database
         let position_id = self
             .db
-            .create_position(
+            .positions().create_position(
                 &opportunity.market_id,
                 &opportunity.question,
                 opportunity.side,
@@ -178,7 +178,7 @@ impl Executor {
         // Record the position in database
         let position_id = self
             .db
-            .create_position(
+            .positions().create_position(
                 &opportunity.market_id,
                 &opportunity.question,
                 opportunity.side,
@@ -219,7 +219,7 @@ impl Executor {
 
     /// Check current exposure against limits
     pub async fn check_exposure(&self) -> Result<ExposureStatus> {
-        let positions = self.db.get_open_positions().await?;
+        let positions = self.db.positions().get_open_positions().await?;
 
         let total_exposure: Decimal = positions.iter().map(|p| p.size).sum();
 
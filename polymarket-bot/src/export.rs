@@ -0,0 +1,584 @@
+//! Tax-lot tracking and trade export
+//!
+//! Every buy opens a lot; sales, merges and redemptions dispose of shares from
+//! open lots of the same token and realize a gain per lot. Lots are matched
+//! either FIFO across the wallet (oldest lot of the token first) or as specific
+//! lots (the position or pair that bought the tokens). Fills are rebuilt from
+//! the positions, orders and Mint Maker pairs tables, so exports can cover any
+//! period, including history from before the ledger.
+//!
+//! Partial sells of a position are stored as a running total, not per sale;
+//! they are dated at the position's close (or its open while still open).
+
+use crate::db::Database;
+use crate::ledger::{self, EntryKind, LedgerTxn};
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How disposals are matched to lots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LotMethod {
+    /// Oldest open lot of the token first, across positions and pairs
+    #[default]
+    Fifo,
+    /// The lot bought by the same position or pair
+    Specific,
+}
+
+impl LotMethod {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "fifo" => Some(LotMethod::Fifo),
+            "specific" => Some(LotMethod::Specific),
+            _ => None,
+        }
+    }
+}
+
+/// What a fill did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FillAction {
+    Buy,
+    Sell,
+    Merge,
+    Redeem,
+}
+
+impl FillAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FillAction::Buy => "buy",
+            FillAction::Sell => "sell",
+            FillAction::Merge => "merge",
+            FillAction::Redeem => "redeem",
+        }
+    }
+
+    fn is_buy(&self) -> bool {
+        *self == FillAction::Buy
+    }
+}
+
+/// Market and order a position or pair belongs to
+#[derive(Debug, Clone, Default)]
+pub struct FillSource {
+    pub market_id: String,
+    pub question: String,
+    /// Order that filled the buy, when the orders table has it
+    pub order_id: Option<String>,
+    /// Fill time of that order (replaces the position's open time)
+    pub filled_at: Option<DateTime<Utc>>,
+}
+
+/// One movement of tokens against USDC
+#[derive(Debug, Clone, Serialize)]
+pub struct Fill {
+    pub at: DateTime<Utc>,
+    /// Position or pair (`position:<id>`, `pair:<id>`)
+    pub reference: String,
+    pub strategy: String,
+    pub market_id: String,
+    pub question: String,
+    pub order_id: Option<String>,
+    pub token_id: String,
+    pub action: FillAction,
+    pub shares: Decimal,
+    pub price: Decimal,
+    /// USDC paid (buys) or received (everything else), before fees
+    pub amount: Decimal,
+    pub fee: Decimal,
+}
+
+impl Fill {
+    /// Fills of a ledger transaction built from a position or pair. The cash of a
+    /// merge or redemption is split across its tokens in proportion to their cost.
+    pub fn from_txn(txn: &LedgerTxn, source: &FillSource) -> Vec<Fill> {
+        let action = match txn.kind {
+            EntryKind::Buy => FillAction::Buy,
+            EntryKind::Sell => FillAction::Sell,
+            EntryKind::Merge => FillAction::Merge,
+            EntryKind::Redeem => FillAction::Redeem,
+            _ => return Vec::new(),
+        };
+        let legs: Vec<_> = txn
+            .postings
+            .iter()
+            .filter_map(|p| {
+                let token_id = p.account.strip_prefix(ledger::TOKENS_PREFIX)?;
+                Some((token_id, p.quantity.unwrap_or_default().abs(), p.amount.abs()))
+            })
+            .filter(|(_, shares, _)| !shares.is_zero())
+            .collect();
+        let fee: Decimal = txn.postings.iter().filter(|p| p.account == ledger::FEES).map(|p| p.amount).sum();
+        let cash_in: Decimal = txn
+            .postings
+            .iter()
+            .filter(|p| p.account == ledger::CASH)
+            .map(|p| p.amount)
+            .sum::<Decimal>()
+            + fee;
+
+        let total_cost: Decimal = legs.iter().map(|(_, _, cost)| *cost).sum();
+        let total_shares: Decimal = legs.iter().map(|(_, shares, _)| *shares).sum();
+        let at = match (action, source.filled_at) {
+            (FillAction::Buy, Some(filled_at)) => filled_at,
+            _ => txn.created_at,
+        };
+
+        legs.iter()
+            .enumerate()
+            .map(|(i, (token_id, shares, cost))| {
+                let amount = match action {
+                    FillAction::Buy => *cost,
+                    _ if legs.len() == 1 => cash_in,
+                    _ if total_cost > Decimal::ZERO => (cash_in * cost / total_cost).round_dp(6),
+                    _ => (cash_in * shares / total_shares).round_dp(6),
+                };
+                Fill {
+                    at,
+                    reference: txn.reference.clone().unwrap_or_default(),
+                    strategy: txn.strategy.clone().unwrap_or_default(),
+                    market_id: source.market_id.clone(),
+                    question: source.question.clone(),
+                    order_id: source.order_id.clone(),
+                    token_id: token_id.to_string(),
+                    action,
+                    shares: *shares,
+                    price: (amount / shares).round_dp(6),
+                    amount,
+                    // A transaction's fee is charged once, on its first leg
+                    fee: if i == 0 { fee } else { Decimal::ZERO },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Shares bought by one fill
+#[derive(Debug, Clone, Serialize)]
+pub struct Lot {
+    /// `<reference>:<token_id>` (a position or pair side buys one lot)
+    pub lot_id: String,
+    pub reference: String,
+    pub strategy: String,
+    pub question: String,
+    pub token_id: String,
+    pub acquired_at: DateTime<Utc>,
+    pub shares: Decimal,
+    pub cost: Decimal,
+    pub remaining_shares: Decimal,
+    pub remaining_cost: Decimal,
+    /// When the last share was disposed of
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// Gain realized on (part of) one lot
+#[derive(Debug, Clone, Serialize)]
+pub struct RealizedGain {
+    /// None when no open lot covered the shares (bought outside the tracked history)
+    pub lot_id: Option<String>,
+    pub reference: String,
+    pub strategy: String,
+    pub question: String,
+    pub token_id: String,
+    pub action: FillAction,
+    pub acquired_at: Option<DateTime<Utc>>,
+    pub disposed_at: DateTime<Utc>,
+    pub shares: Decimal,
+    pub cost_basis: Decimal,
+    pub proceeds: Decimal,
+    pub fee: Decimal,
+    /// proceeds - cost_basis - fee
+    pub gain: Decimal,
+}
+
+/// Realized gains of one day (UTC)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DailyGain {
+    pub date: NaiveDate,
+    pub disposals: i64,
+    pub proceeds: Decimal,
+    pub cost_basis: Decimal,
+    pub fees: Decimal,
+    pub gain: Decimal,
+}
+
+/// Totals of an export
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportTotals {
+    pub bought: Decimal,
+    pub proceeds: Decimal,
+    pub cost_basis: Decimal,
+    pub fees: Decimal,
+    pub gain: Decimal,
+    /// Of which from redemptions
+    pub redeemed: Decimal,
+}
+
+/// Fills, lots and realized gains of a wallet over [from, to] (inclusive days)
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeExport {
+    pub wallet_address: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub method: LotMethod,
+    pub fills: Vec<Fill>,
+    /// Lots open at some point during the range
+    pub lots: Vec<Lot>,
+    pub realized: Vec<RealizedGain>,
+    pub daily: Vec<DailyGain>,
+    pub totals: ExportTotals,
+}
+
+/// Section of an export written as CSV (one table per file)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportSection {
+    #[default]
+    Fills,
+    Lots,
+    Gains,
+    Daily,
+}
+
+impl ExportSection {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "fills" => Some(ExportSection::Fills),
+            "lots" => Some(ExportSection::Lots),
+            "gains" => Some(ExportSection::Gains),
+            "daily" => Some(ExportSection::Daily),
+            _ => None,
+        }
+    }
+}
+
+impl TradeExport {
+    /// Load a wallet's fills and build its export
+    pub async fn generate(db: &Database, wallet_address: &str, from: NaiveDate, to: NaiveDate, method: LotMethod) -> Result<Self> {
+        let fills = db.get_trade_fills(wallet_address).await?;
+        Ok(Self::build(wallet_address, from, to, method, fills))
+    }
+
+    /// Match the wallet's fills (its whole history up to `to`, in any order) to lots
+    pub fn build(wallet_address: &str, from: NaiveDate, to: NaiveDate, method: LotMethod, mut fills: Vec<Fill>) -> Self {
+        fills.sort_by(|a, b| a.at.cmp(&b.at).then(a.action.is_buy().cmp(&b.action.is_buy()).reverse()));
+        let in_range = |at: &DateTime<Utc>| {
+            let date = at.date_naive();
+            date >= from && date <= to
+        };
+
+        let mut lots: Vec<Lot> = Vec::new();
+        let mut realized: Vec<RealizedGain> = Vec::new();
+        for fill in fills.iter().filter(|f| f.at.date_naive() <= to) {
+            if fill.action == FillAction::Buy {
+                lots.push(Lot {
+                    lot_id: format!("{}:{}", fill.reference, fill.token_id),
+                    reference: fill.reference.clone(),
+                    strategy: fill.strategy.clone(),
+                    question: fill.question.clone(),
+                    token_id: fill.token_id.clone(),
+                    acquired_at: fill.at,
+                    shares: fill.shares,
+                    cost: fill.amount + fill.fee,
+                    remaining_shares: fill.shares,
+                    remaining_cost: fill.amount + fill.fee,
+                    closed_at: None,
+                });
+                continue;
+            }
+            realized.extend(dispose(&mut lots, fill, method));
+        }
+
+        let fills: Vec<Fill> = fills.into_iter().filter(|f| in_range(&f.at)).collect();
+        let realized: Vec<RealizedGain> = realized.into_iter().filter(|g| in_range(&g.disposed_at)).collect();
+        let lots: Vec<Lot> = lots
+            .into_iter()
+            .filter(|l| l.acquired_at.date_naive() <= to && !matches!(l.closed_at, Some(c) if c.date_naive() < from))
+            .collect();
+
+        let mut daily: BTreeMap<NaiveDate, DailyGain> = BTreeMap::new();
+        for gain in &realized {
+            let date = gain.disposed_at.date_naive();
+            let day = daily.entry(date).or_insert_with(|| DailyGain {
+                date,
+                ..Default::default()
+            });
+            day.disposals += 1;
+            day.proceeds += gain.proceeds;
+            day.cost_basis += gain.cost_basis;
+            day.fees += gain.fee;
+            day.gain += gain.gain;
+        }
+
+        let totals = ExportTotals {
+            bought: fills.iter().filter(|f| f.action == FillAction::Buy).map(|f| f.amount).sum(),
+            proceeds: realized.iter().map(|g| g.proceeds).sum(),
+            cost_basis: realized.iter().map(|g| g.cost_basis).sum(),
+            fees: fills.iter().map(|f| f.fee).sum(),
+            gain: realized.iter().map(|g| g.gain).sum(),
+            redeemed: realized
+                .iter()
+                .filter(|g| g.action == FillAction::Redeem)
+                .map(|g| g.proceeds)
+                .sum(),
+        };
+
+        Self {
+            wallet_address: wallet_address.to_lowercase(),
+            from,
+            to,
+            method,
+            fills,
+            lots,
+            realized,
+            daily: daily.into_values().collect(),
+            totals,
+        }
+    }
+
+    /// One section as CSV (header row included)
+    pub fn to_csv(&self, section: ExportSection) -> String {
+        let mut out = String::new();
+        let mut row = |cells: &[String]| {
+            let line: Vec<String> = cells.iter().map(|c| csv_cell(c)).collect();
+            out.push_str(&line.join(","));
+            out.push('\n');
+        };
+        let opt_time = |t: &Option<DateTime<Utc>>| t.map(|t| t.to_rfc3339()).unwrap_or_default();
+
+        match section {
+            ExportSection::Fills => {
+                row(&cells(&["time", "action", "reference", "strategy", "market_id", "question", "order_id", "token_id", "shares", "price", "amount", "fee"]));
+                for f in &self.fills {
+                    row(&[
+                        f.at.to_rfc3339(),
+                        f.action.as_str().to_string(),
+                        f.reference.clone(),
+                        f.strategy.clone(),
+                        f.market_id.clone(),
+                        f.question.clone(),
+                        f.order_id.clone().unwrap_or_default(),
+                        f.token_id.clone(),
+                        f.shares.to_string(),
+                        f.price.to_string(),
+                        f.amount.to_string(),
+                        f.fee.to_string(),
+                    ]);
+                }
+            }
+            ExportSection::Lots => {
+                row(&cells(&["lot_id", "strategy", "question", "token_id", "acquired_at", "shares", "cost", "remaining_shares", "remaining_cost", "closed_at"]));
+                for l in &self.lots {
+                    row(&[
+                        l.lot_id.clone(),
+                        l.strategy.clone(),
+                        l.question.clone(),
+                        l.token_id.clone(),
+                        l.acquired_at.to_rfc3339(),
+                        l.shares.to_string(),
+                        l.cost.to_string(),
+                        l.remaining_shares.to_string(),
+                        l.remaining_cost.to_string(),
+                        opt_time(&l.closed_at),
+                    ]);
+                }
+            }
+            ExportSection::Gains => {
+                row(&cells(&["disposed_at", "action", "lot_id", "strategy", "question", "token_id", "acquired_at", "shares", "proceeds", "cost_basis", "fee", "gain"]));
+                for g in &self.realized {
+                    row(&[
+                        g.disposed_at.to_rfc3339(),
+                        g.action.as_str().to_string(),
+                        g.lot_id.clone().unwrap_or_default(),
+                        g.strategy.clone(),
+                        g.question.clone(),
+                        g.token_id.clone(),
+                        opt_time(&g.acquired_at),
+                        g.shares.to_string(),
+                        g.proceeds.to_string(),
+                        g.cost_basis.to_string(),
+                        g.fee.to_string(),
+                        g.gain.to_string(),
+                    ]);
+                }
+            }
+            ExportSection::Daily => {
+                row(&cells(&["date", "disposals", "proceeds", "cost_basis", "fees", "gain"]));
+                for d in &self.daily {
+                    row(&[
+                        d.date.to_string(),
+                        d.disposals.to_string(),
+                        d.proceeds.to_string(),
+                        d.cost_basis.to_string(),
+                        d.fees.to_string(),
+                        d.gain.to_string(),
+                    ]);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Take a disposal's shares out of matching open lots, oldest first
+fn dispose(lots: &mut [Lot], fill: &Fill, method: LotMethod) -> Vec<RealizedGain> {
+    let mut gains = Vec::new();
+    let mut left = fill.shares;
+    let per_share = |total: Decimal, shares: Decimal| {
+        if fill.shares.is_zero() {
+            Decimal::ZERO
+        } else {
+            (total * shares / fill.shares).round_dp(6)
+        }
+    };
+
+    let matching = lots.iter_mut().filter(|l| {
+        l.token_id == fill.token_id
+            && !l.remaining_shares.is_zero()
+            && (method == LotMethod::Fifo || l.reference == fill.reference)
+    });
+    for lot in matching {
+        if left.is_zero() {
+            break;
+        }
+        let shares = left.min(lot.remaining_shares);
+        let cost_basis = if shares == lot.remaining_shares {
+            lot.remaining_cost
+        } else {
+            (lot.remaining_cost * shares / lot.remaining_shares).round_dp(6)
+        };
+        lot.remaining_shares -= shares;
+        lot.remaining_cost -= cost_basis;
+        if lot.remaining_shares.is_zero() {
+            lot.closed_at = Some(fill.at);
+        }
+        left -= shares;
+
+        let proceeds = per_share(fill.amount, shares);
+        let fee = per_share(fill.fee, shares);
+        gains.push(RealizedGain {
+            lot_id: Some(lot.lot_id.clone()),
+            reference: fill.reference.clone(),
+            strategy: fill.strategy.clone(),
+            question: fill.question.clone(),
+            token_id: fill.token_id.clone(),
+            action: fill.action,
+            acquired_at: Some(lot.acquired_at),
+            disposed_at: fill.at,
+            shares,
+            cost_basis,
+            proceeds,
+            fee,
+            gain: proceeds - cost_basis - fee,
+        });
+    }
+
+    if !left.is_zero() {
+        let proceeds = per_share(fill.amount, left);
+        let fee = per_share(fill.fee, left);
+        gains.push(RealizedGain {
+            lot_id: None,
+            reference: fill.reference.clone(),
+            strategy: fill.strategy.clone(),
+            question: fill.question.clone(),
+            token_id: fill.token_id.clone(),
+            action: fill.action,
+            acquired_at: None,
+            disposed_at: fill.at,
+            shares: left,
+            cost_basis: Decimal::ZERO,
+            proceeds,
+            fee,
+            gain: proceeds - fee,
+        });
+    }
+    gains
+}
+
+fn cells(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+/// Quote a CSV cell when it contains a separator, quote or line break
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn fill(day: u32, reference: &str, action: FillAction, shares: Decimal, amount: Decimal) -> Fill {
+        Fill {
+            at: Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap(),
+            reference: reference.to_string(),
+            strategy: "ResolutionSniper".to_string(),
+            market_id: "m".to_string(),
+            question: "Will it rain, today?".to_string(),
+            order_id: None,
+            token_id: "t1".to_string(),
+            action,
+            shares,
+            price: amount / shares,
+            amount,
+            fee: Decimal::ZERO,
+        }
+    }
+
+    fn fills() -> Vec<Fill> {
+        vec![
+            fill(1, "position:1", FillAction::Buy, dec!(10), dec!(4)),
+            fill(2, "position:2", FillAction::Buy, dec!(10), dec!(6)),
+            fill(3, "position:2", FillAction::Sell, dec!(10), dec!(7)),
+        ]
+    }
+
+    #[test]
+    fn test_fifo_and_specific_lots() {
+        let from = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 10, 31).unwrap();
+
+        // FIFO sells the older, cheaper lot of the same token
+        let fifo = TradeExport::build("0xabc", from, to, LotMethod::Fifo, fills());
+        assert_eq!(fifo.realized.len(), 1);
+        assert_eq!(fifo.realized[0].lot_id.as_deref(), Some("position:1:t1"));
+        assert_eq!(fifo.totals.gain, dec!(3));
+
+        // Specific lots sell the position's own lot
+        let specific = TradeExport::build("0xabc", from, to, LotMethod::Specific, fills());
+        assert_eq!(specific.realized[0].lot_id.as_deref(), Some("position:2:t1"));
+        assert_eq!(specific.totals.gain, dec!(1));
+        assert_eq!(specific.daily.len(), 1);
+        assert_eq!(specific.daily[0].gain, dec!(1));
+
+        // A range after the sale keeps the lot still open but no fills or gains
+        let later = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+        let export = TradeExport::build("0xabc", later, to, LotMethod::Fifo, fills());
+        assert!(export.fills.is_empty() && export.realized.is_empty());
+        assert_eq!(export.lots.len(), 1);
+        assert_eq!(export.lots[0].remaining_shares, dec!(10));
+    }
+
+    #[test]
+    fn test_csv() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let export = TradeExport::build("0xabc", day, day, LotMethod::Fifo, fills());
+        let csv = export.to_csv(ExportSection::Fills);
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("time,action,reference"));
+        assert!(lines.next().unwrap().contains(",\"Will it rain, today?\","));
+        assert!(lines.next().is_none());
+    }
+}
@@ -0,0 +1,846 @@
+//! Double-entry accounting ledger
+//!
+//! Every cash and token movement of a wallet is recorded as a balanced
+//! transaction: postings whose USDC amounts sum to zero (debits positive,
+//! credits negative). Accounts:
+//! - `cash` - USDC held by the trading wallet
+//! - `tokens:<token_id>` - outcome tokens at cost; postings also carry shares
+//! - `fees` - trading fees paid
+//! - `pnl` - realized trading results (a credit is a gain)
+//! - `equity` - capital deposited and withdrawn
+//!
+//! Token postings are tagged with the position, pair or trade they belong to
+//! (`position:<id>`, `pair:<id>`, `mc:<id>`), so a sale, merge or redemption
+//! releases the cost of exactly the lot it came from. Stats are derived from
+//! the ledger through `LedgerSummary`; open holdings are marked to market with
+//! prices from the `PriceCache`.
+
+use crate::db::MintMakerPairRow;
+use crate::types::{Position, PositionStatus};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// USDC held by the wallet
+pub const CASH: &str = "cash";
+/// Trading fees paid
+pub const FEES: &str = "fees";
+/// Realized trading results
+pub const PNL: &str = "pnl";
+/// Capital deposited and withdrawn
+pub const EQUITY: &str = "equity";
+/// Prefix of outcome token accounts
+pub const TOKENS_PREFIX: &str = "tokens:";
+/// Ledger owner of the Millionaires Club observation trades (not a real wallet)
+pub const MC_BOOK: &str = "mc";
+
+/// Holdings below this many shares count as closed
+const DUST_SHARES: Decimal = Decimal::from_parts(1, 0, 0, false, 6);
+
+/// Account holding an outcome token
+pub fn token_account(token_id: &str) -> String {
+    format!("{}{}", TOKENS_PREFIX, token_id)
+}
+
+pub fn position_ref(position_id: i64) -> String {
+    format!("position:{}", position_id)
+}
+
+pub fn pair_ref(pair_id: i64) -> String {
+    format!("pair:{}", pair_id)
+}
+
+pub fn mc_ref(trade_id: i64) -> String {
+    format!("mc:{}", trade_id)
+}
+
+/// Token a lot is booked under; positions opened before their token id was known
+/// fall back to `<market_id>:<side>`
+pub fn lot_token(token_id: Option<&str>, market_id: &str, side: &str) -> String {
+    match token_id {
+        Some(token_id) if !token_id.is_empty() => token_id.to_string(),
+        _ => format!("{}:{}", market_id, side),
+    }
+}
+
+/// Kind of ledger transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Buy,
+    Sell,
+    Fee,
+    Merge,
+    Split,
+    Redeem,
+    Deposit,
+    Withdrawal,
+}
+
+impl EntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryKind::Buy => "buy",
+            EntryKind::Sell => "sell",
+            EntryKind::Fee => "fee",
+            EntryKind::Merge => "merge",
+            EntryKind::Split => "split",
+            EntryKind::Redeem => "redeem",
+            EntryKind::Deposit => "deposit",
+            EntryKind::Withdrawal => "withdrawal",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "buy" => Some(EntryKind::Buy),
+            "sell" => Some(EntryKind::Sell),
+            "fee" => Some(EntryKind::Fee),
+            "merge" => Some(EntryKind::Merge),
+            "split" => Some(EntryKind::Split),
+            "redeem" => Some(EntryKind::Redeem),
+            "deposit" => Some(EntryKind::Deposit),
+            "withdrawal" => Some(EntryKind::Withdrawal),
+            _ => None,
+        }
+    }
+
+    /// Whether the transaction closes (part of) a position and realizes P&L
+    pub fn is_closing(&self) -> bool {
+        matches!(self, EntryKind::Sell | EntryKind::Merge | EntryKind::Redeem)
+    }
+}
+
+/// One side of a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Posting {
+    pub account: String,
+    /// USDC value (debit positive, credit negative)
+    pub amount: Decimal,
+    /// Shares moved (token accounts only)
+    pub quantity: Option<Decimal>,
+}
+
+/// Tokens of one lot: shares held and their remaining cost
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Holding {
+    pub token_id: String,
+    pub quantity: Decimal,
+    pub cost: Decimal,
+}
+
+impl Holding {
+    /// Cost of `shares` at the lot's average cost (the whole cost when selling out)
+    pub fn cost_of(&self, shares: Decimal) -> Decimal {
+        if self.quantity <= Decimal::ZERO || shares >= self.quantity {
+            self.cost
+        } else {
+            (self.cost * shares / self.quantity).round_dp(6)
+        }
+    }
+
+    /// The first `shares` of this lot, as a closing leg
+    pub fn take(&self, shares: Decimal) -> Holding {
+        Holding {
+            token_id: self.token_id.clone(),
+            quantity: shares.min(self.quantity),
+            cost: self.cost_of(shares),
+        }
+    }
+
+    pub fn is_dust(&self) -> bool {
+        self.quantity < DUST_SHARES
+    }
+}
+
+/// Net token lots left by a set of transactions
+pub fn lots(txns: &[LedgerTxn]) -> Vec<Holding> {
+    net_lots(
+        txns.iter()
+            .flat_map(|t| &t.postings)
+            .map(|p| (p.account.as_str(), p.amount, p.quantity)),
+    )
+}
+
+/// Net token lots left by a set of stored entries
+pub fn entry_lots(entries: &[LedgerEntry]) -> Vec<Holding> {
+    net_lots(entries.iter().map(|e| (e.account.as_str(), e.amount, e.quantity)))
+}
+
+/// Sum (account, amount, quantity) legs into lots per token, dropping dust
+fn net_lots<'a>(legs: impl Iterator<Item = (&'a str, Decimal, Option<Decimal>)>) -> Vec<Holding> {
+    let mut lots: Vec<Holding> = Vec::new();
+    for (account, amount, quantity) in legs {
+        let Some(token_id) = account.strip_prefix(TOKENS_PREFIX) else {
+            continue;
+        };
+        let quantity = quantity.unwrap_or_default();
+        match lots.iter_mut().find(|l| l.token_id == token_id) {
+            Some(lot) => {
+                lot.quantity += quantity;
+                lot.cost += amount;
+            }
+            None => lots.push(Holding {
+                token_id: token_id.to_string(),
+                quantity,
+                cost: amount,
+            }),
+        }
+    }
+    lots.retain(|l| !l.is_dust());
+    lots
+}
+
+/// A balanced set of postings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerTxn {
+    pub id: Option<i64>,
+    pub wallet_address: String,
+    pub kind: EntryKind,
+    /// Strategy the movement belongs to (`StrategyType` debug name)
+    pub strategy: Option<String>,
+    /// Position, pair or trade the tokens belong to
+    pub reference: Option<String>,
+    /// Idempotency key: a transaction with a key already in the ledger is skipped
+    #[serde(skip)]
+    pub key: Option<String>,
+    pub memo: Option<String>,
+    pub postings: Vec<Posting>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LedgerTxn {
+    pub fn new(wallet_address: &str, kind: EntryKind) -> Self {
+        Self {
+            id: None,
+            wallet_address: wallet_address.to_lowercase(),
+            kind,
+            strategy: None,
+            reference: None,
+            key: None,
+            memo: None,
+            postings: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn strategy(mut self, strategy: impl Into<String>) -> Self {
+        self.strategy = Some(strategy.into());
+        self
+    }
+
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    pub fn at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    fn post(mut self, account: &str, amount: Decimal) -> Self {
+        if !amount.is_zero() {
+            self.postings.push(Posting {
+                account: account.to_string(),
+                amount,
+                quantity: None,
+            });
+        }
+        self
+    }
+
+    fn post_tokens(mut self, token_id: &str, amount: Decimal, quantity: Decimal) -> Self {
+        self.postings.push(Posting {
+            account: token_account(token_id),
+            amount,
+            quantity: Some(quantity),
+        });
+        self
+    }
+
+    /// Whether debits equal credits
+    pub fn is_balanced(&self) -> bool {
+        self.postings.iter().map(|p| p.amount).sum::<Decimal>().is_zero()
+    }
+
+    /// Bought `shares` of a token for `cost` USDC plus `fee`
+    pub fn buy(wallet_address: &str, token_id: &str, shares: Decimal, cost: Decimal, fee: Decimal) -> Self {
+        Self::new(wallet_address, EntryKind::Buy)
+            .post_tokens(token_id, cost, shares)
+            .post(FEES, fee)
+            .post(CASH, -(cost + fee))
+    }
+
+    /// Sold a lot for `proceeds` USDC, paying `fee`
+    pub fn sell(wallet_address: &str, lot: &Holding, proceeds: Decimal, fee: Decimal) -> Self {
+        Self::close(wallet_address, EntryKind::Sell, std::slice::from_ref(lot), proceeds).post(FEES, fee).post(CASH, -fee)
+    }
+
+    /// Redeemed resolved tokens for `payout` USDC (zero for a losing side)
+    pub fn redeem(wallet_address: &str, lots: &[Holding], payout: Decimal) -> Self {
+        Self::close(wallet_address, EntryKind::Redeem, lots, payout)
+    }
+
+    /// Merged complete sets back into `amount` USDC
+    pub fn merge(wallet_address: &str, lots: &[Holding], amount: Decimal) -> Self {
+        Self::close(wallet_address, EntryKind::Merge, lots, amount)
+    }
+
+    /// Split `amount` USDC into `amount` of each outcome token; the cost is divided evenly
+    pub fn split(wallet_address: &str, token_ids: &[&str], amount: Decimal) -> Self {
+        let mut txn = Self::new(wallet_address, EntryKind::Split).post(CASH, -amount);
+        if let Some(last) = token_ids.len().checked_sub(1) {
+            let share = (amount / Decimal::from(token_ids.len())).round_dp(6);
+            for (i, token_id) in token_ids.iter().enumerate() {
+                // The last leg takes the rounding remainder
+                let cost = if i == last { amount - share * Decimal::from(last) } else { share };
+                txn = txn.post_tokens(token_id, cost, amount);
+            }
+        }
+        txn
+    }
+
+    pub fn deposit(wallet_address: &str, amount: Decimal) -> Self {
+        Self::new(wallet_address, EntryKind::Deposit).post(CASH, amount).post(EQUITY, -amount)
+    }
+
+    pub fn withdrawal(wallet_address: &str, amount: Decimal) -> Self {
+        Self::new(wallet_address, EntryKind::Withdrawal).post(CASH, -amount).post(EQUITY, amount)
+    }
+
+    pub fn fee(wallet_address: &str, amount: Decimal) -> Self {
+        Self::new(wallet_address, EntryKind::Fee).post(FEES, amount).post(CASH, -amount)
+    }
+
+    /// Correct the shares a buy received (same cost, e.g. once the actual fill price is known)
+    pub fn adjust_shares(wallet_address: &str, token_id: &str, delta: Decimal) -> Self {
+        Self::new(wallet_address, EntryKind::Buy)
+            .post_tokens(token_id, Decimal::ZERO, delta)
+            .memo("fill price correction")
+    }
+
+    /// Release lots for `cash_in` USDC, booking the difference to their cost as P&L
+    fn close(wallet_address: &str, kind: EntryKind, lots: &[Holding], cash_in: Decimal) -> Self {
+        let mut txn = Self::new(wallet_address, kind).post(CASH, cash_in);
+        let mut cost = Decimal::ZERO;
+        for lot in lots {
+            txn = txn.post_tokens(&lot.token_id, -lot.cost, -lot.quantity);
+            cost += lot.cost;
+        }
+        txn.post(PNL, cost - cash_in)
+    }
+}
+
+/// A stored posting with its transaction's fields
+#[derive(Debug, Clone)]
+pub struct LedgerEntry {
+    pub txn_id: i64,
+    pub kind: EntryKind,
+    pub strategy: Option<String>,
+    pub reference: Option<String>,
+    pub account: String,
+    pub amount: Decimal,
+    pub quantity: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Realized result of one position, pair or trade
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeResult {
+    pub reference: String,
+    pub strategy: Option<String>,
+    /// Realized P&L net of fees
+    pub pnl: Decimal,
+    pub fees: Decimal,
+    pub closed_at: DateTime<Utc>,
+}
+
+/// Realized results of one strategy
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StrategyPnl {
+    pub strategy: String,
+    pub trades: i64,
+    pub wins: i64,
+    /// Net of fees
+    pub realized_pnl: Decimal,
+    pub fees: Decimal,
+}
+
+/// A token holding valued at the latest price
+#[derive(Debug, Clone, Serialize)]
+pub struct MarkedHolding {
+    pub token_id: String,
+    pub quantity: Decimal,
+    pub cost: Decimal,
+    /// None when no price has been seen (valued at cost)
+    pub mark: Option<Decimal>,
+    pub value: Decimal,
+}
+
+/// Balances and trade statistics derived from ledger entries
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LedgerSummary {
+    pub cash: Decimal,
+    /// Deposits minus withdrawals
+    pub net_deposits: Decimal,
+    /// Realized P&L before fees
+    pub gross_pnl: Decimal,
+    pub fees: Decimal,
+    /// Realized P&L after fees
+    pub realized_pnl: Decimal,
+    /// Positions, pairs and trades with at least one sale, merge or redemption
+    pub trades: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub win_rate: f64,
+    pub best_trade: Option<TradeResult>,
+    pub worst_trade: Option<TradeResult>,
+    pub strategies: Vec<StrategyPnl>,
+    pub holdings: Vec<MarkedHolding>,
+    pub cost_basis: Decimal,
+    pub market_value: Decimal,
+    pub unrealized_pnl: Decimal,
+    /// Cash plus holdings at market value
+    pub equity: Decimal,
+}
+
+impl LedgerSummary {
+    /// Summarize entries; `marks` holds the latest price per token id
+    pub fn from_entries(entries: &[LedgerEntry], marks: &HashMap<String, Decimal>) -> Self {
+        let mut summary = LedgerSummary::default();
+        let mut trades: HashMap<&str, TradeResult> = HashMap::new();
+        let mut closed: HashMap<&str, bool> = HashMap::new();
+        let mut holdings: HashMap<&str, (Decimal, Decimal)> = HashMap::new();
+
+        for entry in entries {
+            match entry.account.as_str() {
+                CASH => summary.cash += entry.amount,
+                EQUITY => summary.net_deposits -= entry.amount,
+                FEES => summary.fees += entry.amount,
+                PNL => summary.gross_pnl -= entry.amount,
+                account => {
+                    if let Some(token_id) = account.strip_prefix(TOKENS_PREFIX) {
+                        let holding = holdings.entry(token_id).or_default();
+                        holding.0 += entry.quantity.unwrap_or_default();
+                        holding.1 += entry.amount;
+                    }
+                }
+            }
+
+            let Some(reference) = entry.reference.as_deref() else {
+                continue;
+            };
+            if entry.kind.is_closing() {
+                closed.insert(reference, true);
+            }
+            if entry.account == PNL || entry.account == FEES {
+                let trade = trades.entry(reference).or_insert_with(|| TradeResult {
+                    reference: reference.to_string(),
+                    strategy: entry.strategy.clone(),
+                    pnl: Decimal::ZERO,
+                    fees: Decimal::ZERO,
+                    closed_at: entry.created_at,
+                });
+                // P&L credits are gains, fee debits are costs: both lower net P&L when positive
+                trade.pnl -= entry.amount;
+                if entry.account == FEES {
+                    trade.fees += entry.amount;
+                }
+                trade.closed_at = trade.closed_at.max(entry.created_at);
+            }
+        }
+        summary.realized_pnl = summary.gross_pnl - summary.fees;
+
+        let mut results: Vec<TradeResult> = trades
+            .into_values()
+            .filter(|t| closed.contains_key(t.reference.as_str()))
+            .collect();
+        results.sort_by(|a, b| a.closed_at.cmp(&b.closed_at));
+
+        for trade in &results {
+            let strategy = trade.strategy.clone().unwrap_or_else(|| "Unknown".to_string());
+            let index = match summary.strategies.iter().position(|s| s.strategy == strategy) {
+                Some(index) => index,
+                None => {
+                    summary.strategies.push(StrategyPnl {
+                        strategy,
+                        ..Default::default()
+                    });
+                    summary.strategies.len() - 1
+                }
+            };
+            let entry = &mut summary.strategies[index];
+            entry.trades += 1;
+            if trade.pnl > Decimal::ZERO {
+                entry.wins += 1;
+            }
+            entry.realized_pnl += trade.pnl;
+            entry.fees += trade.fees;
+        }
+        summary.strategies.sort_by(|a, b| a.strategy.cmp(&b.strategy));
+
+        summary.trades = results.len() as i64;
+        summary.wins = results.iter().filter(|t| t.pnl > Decimal::ZERO).count() as i64;
+        summary.losses = summary.trades - summary.wins;
+        summary.win_rate = if summary.trades > 0 {
+            summary.wins as f64 / summary.trades as f64
+        } else {
+            0.0
+        };
+        summary.best_trade = results.iter().max_by_key(|t| t.pnl).cloned();
+        summary.worst_trade = results.iter().min_by_key(|t| t.pnl).cloned();
+
+        let mut marked: Vec<MarkedHolding> = holdings
+            .into_iter()
+            .filter(|(_, (quantity, _))| *quantity > Decimal::new(1, 6))
+            .map(|(token_id, (quantity, cost))| {
+                let mark = marks.get(token_id).copied();
+                MarkedHolding {
+                    token_id: token_id.to_string(),
+                    quantity,
+                    cost,
+                    mark,
+                    value: mark.map(|m| (m * quantity).round_dp(6)).unwrap_or(cost),
+                }
+            })
+            .collect();
+        marked.sort_by(|a, b| b.value.cmp(&a.value));
+
+        summary.cost_basis = marked.iter().map(|h| h.cost).sum();
+        summary.market_value = marked.iter().map(|h| h.value).sum();
+        summary.unrealized_pnl = summary.market_value - summary.cost_basis;
+        summary.equity = summary.cash + summary.market_value;
+        summary.holdings = marked;
+        summary
+    }
+
+    /// Results of one strategy (`StrategyType` debug name)
+    pub fn strategy(&self, strategy: &str) -> StrategyPnl {
+        self.strategies
+            .iter()
+            .find(|s| s.strategy == strategy)
+            .cloned()
+            .unwrap_or_else(|| StrategyPnl {
+                strategy: strategy.to_string(),
+                ..Default::default()
+            })
+    }
+}
+
+// ==================== TRANSACTIONS OF EXISTING RECORDS ====================
+
+/// Buys of a Mint Maker pair's filled sides
+pub fn pair_buys(pair: &MintMakerPairRow) -> Vec<LedgerTxn> {
+    let size = parse(Some(&pair.size)).unwrap_or_default();
+    let created_at = parse_time(&pair.created_at);
+    let sides = [
+        ("yes", "Yes", &pair.yes_fill_price, &pair.yes_size, &pair.yes_token_id),
+        ("no", "No", &pair.no_fill_price, &pair.no_size, &pair.no_token_id),
+    ];
+
+    sides
+        .into_iter()
+        .filter_map(|(key, side, fill, side_size, token_id)| {
+            let price = parse(fill.as_deref())?;
+            let shares = parse(side_size.as_deref()).unwrap_or(size);
+            let token = lot_token(token_id.as_deref(), &pair.market_id, side);
+            Some(
+                LedgerTxn::buy(&pair.wallet_address, &token, shares, (price * shares).round_dp(6), Decimal::ZERO)
+                    .strategy("MintMaker")
+                    .reference(pair_ref(pair.id))
+                    .key(format!("{}:buy:{}", pair_ref(pair.id), key))
+                    .at(created_at),
+            )
+        })
+        .collect()
+}
+
+/// Merge of a pair's complete sets (None unless both sides are held)
+pub fn pair_merge(pair: &MintMakerPairRow, held: &[Holding]) -> Option<LedgerTxn> {
+    if held.len() < 2 {
+        return None;
+    }
+    let shares = parse(Some(&pair.size))?.min(held.iter().map(|l| l.quantity).min()?);
+    let legs: Vec<Holding> = held.iter().map(|l| l.take(shares)).collect();
+    Some(
+        LedgerTxn::merge(&pair.wallet_address, &legs, shares)
+            .strategy("MintMaker")
+            .reference(pair_ref(pair.id))
+            .key(format!("{}:merge", pair_ref(pair.id))),
+    )
+}
+
+/// Redemption of a pair's remaining tokens. Complete sets pay $1 each; a half-filled
+/// pair records its total cost and result, so the payout is their sum.
+pub fn pair_redeem(pair: &MintMakerPairRow, held: &[Holding]) -> Option<LedgerTxn> {
+    let payout = match held {
+        [] => return None,
+        [_] => parse(pair.pair_cost.as_deref())? + parse(pair.profit.as_deref())?,
+        _ => held.iter().map(|l| l.quantity).min()?,
+    };
+    Some(
+        LedgerTxn::redeem(&pair.wallet_address, held, payout.max(Decimal::ZERO))
+            .strategy("MintMaker")
+            .reference(pair_ref(pair.id))
+            .key(format!("{}:redeem", pair_ref(pair.id))),
+    )
+}
+
+/// Sale of `shares` of a position's lot (all of it when None)
+pub fn position_sale(
+    wallet_address: &str,
+    position_id: i64,
+    strategy: &str,
+    held: &[Holding],
+    shares: Option<Decimal>,
+    price: Decimal,
+    fee: Decimal,
+    step: &str,
+) -> Option<LedgerTxn> {
+    let lot = held.first()?;
+    let sold = lot.take(shares.unwrap_or(lot.quantity));
+    let proceeds = (price * sold.quantity).round_dp(6);
+    let reference = position_ref(position_id);
+    Some(
+        LedgerTxn::sell(wallet_address, &sold, proceeds, fee)
+            .strategy(strategy)
+            .key(format!("{}:{}", reference, step))
+            .reference(reference),
+    )
+}
+
+/// Redemption of whatever a position still holds for `proceeds`
+pub fn position_redeem(
+    wallet_address: &str,
+    position_id: i64,
+    strategy: &str,
+    held: &[Holding],
+    proceeds: Decimal,
+) -> Option<LedgerTxn> {
+    if held.is_empty() {
+        return None;
+    }
+    let reference = position_ref(position_id);
+    Some(
+        LedgerTxn::redeem(wallet_address, held, proceeds)
+            .strategy(strategy)
+            .key(format!("{}:redeem", reference))
+            .reference(reference),
+    )
+}
+
+/// Resolution of a position at `payout` per share: what it still holds is settled at
+/// that value, so a later redemption of the same tokens posts nothing
+pub fn position_resolution(
+    wallet_address: &str,
+    position_id: i64,
+    strategy: &str,
+    held: &[Holding],
+    payout: Decimal,
+) -> Option<LedgerTxn> {
+    let shares: Decimal = held.iter().map(|l| l.quantity).sum();
+    position_redeem(wallet_address, position_id, strategy, held, (payout * shares).round_dp(6))
+}
+
+/// Transactions of a pair recorded before the ledger existed
+pub fn backfill_pair(pair: &MintMakerPairRow) -> Vec<LedgerTxn> {
+    // The sale price of a filled stop loss was never stored; leave such pairs out
+    if pair.status == "StopLossFilled" {
+        return Vec::new();
+    }
+    let mut txns = pair_buys(pair);
+    let updated_at = parse_time(&pair.updated_at);
+    let closing = match pair.status.as_str() {
+        "Merged" => pair_merge(pair, &lots(&txns)),
+        "Redeemed" => pair_redeem(pair, &lots(&txns)),
+        _ => None,
+    };
+    txns.extend(closing.map(|t| t.at(updated_at)));
+    txns.into_iter().map(|t| t.memo("backfill")).collect()
+}
+
+/// Transactions of a live position recorded before the ledger existed.
+/// A position closed at 0 or 1 without a redemption resolved but still holds its tokens.
+pub fn backfill_position(
+    position: &Position,
+    redeemed_at: Option<DateTime<Utc>>,
+    redeem_proceeds: Option<Decimal>,
+) -> Vec<LedgerTxn> {
+    if position.entry_price <= Decimal::ZERO {
+        return Vec::new();
+    }
+    let reference = position_ref(position.id);
+    let strategy = format!("{:?}", position.strategy);
+    let token = lot_token(position.token_id.as_deref(), &position.market_id, &format!("{:?}", position.side));
+    let tag = |txn: LedgerTxn, step: &str| {
+        txn.strategy(strategy.as_str())
+            .reference(reference.as_str())
+            .key(format!("{}:{}", reference, step))
+            .memo("backfill")
+    };
+
+    let shares = position.size / position.entry_price;
+    let mut txns = vec![tag(
+        LedgerTxn::buy(&position.wallet_address, &token, shares, position.size, Decimal::ZERO).at(position.opened_at),
+        "buy",
+    )];
+    let closed_at = position.closed_at.unwrap_or(position.opened_at);
+
+    let sold = position.total_sold_size.unwrap_or_default().min(shares);
+    if let (true, Some(held)) = (sold > Decimal::ZERO, lots(&txns).first()) {
+        let price = position.avg_exit_price.or(position.exit_price).unwrap_or(position.entry_price);
+        let lot = held.take(sold);
+        txns.push(tag(
+            LedgerTxn::sell(&position.wallet_address, &lot, (price * sold).round_dp(6), Decimal::ZERO).at(closed_at),
+            "sell",
+        ));
+    }
+
+    let Some(lot) = lots(&txns).into_iter().next() else {
+        return txns;
+    };
+    if !matches!(position.status, PositionStatus::Closed | PositionStatus::Resolved) {
+        return txns;
+    }
+    let exit = position.exit_price.unwrap_or_default();
+    if let Some(redeemed_at) = redeemed_at {
+        txns.push(tag(
+            LedgerTxn::redeem(&position.wallet_address, &[lot], redeem_proceeds.unwrap_or_default()).at(redeemed_at),
+            "redeem",
+        ));
+    } else if exit != Decimal::ZERO && exit != Decimal::ONE {
+        let proceeds = (exit * lot.quantity).round_dp(6);
+        let fee = position.fee_paid.unwrap_or_default();
+        txns.push(tag(
+            LedgerTxn::sell(&position.wallet_address, &lot, proceeds, fee).at(closed_at),
+            "close",
+        ));
+    }
+    txns
+}
+
+fn parse(value: Option<&str>) -> Option<Decimal> {
+    value.and_then(|v| Decimal::from_str(v).ok())
+}
+
+fn parse_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn entries(txns: &[LedgerTxn]) -> Vec<LedgerEntry> {
+        txns.iter()
+            .enumerate()
+            .flat_map(|(i, txn)| {
+                txn.postings.iter().map(move |p| LedgerEntry {
+                    txn_id: i as i64,
+                    kind: txn.kind,
+                    strategy: txn.strategy.clone(),
+                    reference: txn.reference.clone(),
+                    account: p.account.clone(),
+                    amount: p.amount,
+                    quantity: p.quantity,
+                    created_at: txn.created_at,
+                })
+            })
+            .collect()
+    }
+
+    fn lot(token_id: &str, quantity: Decimal, cost: Decimal) -> Holding {
+        Holding {
+            token_id: token_id.to_string(),
+            quantity,
+            cost,
+        }
+    }
+
+    #[test]
+    fn test_transactions_balance() {
+        let held = lot("yes", dec!(10), dec!(6));
+        let txns = [
+            LedgerTxn::buy("0xA", "yes", dec!(10), dec!(6), dec!(0.05)),
+            LedgerTxn::sell("0xA", &held.take(dec!(4)), dec!(3), dec!(0.02)),
+            LedgerTxn::redeem("0xA", &[held.take(dec!(6))], dec!(0)),
+            LedgerTxn::merge("0xA", &[lot("yes", dec!(5), dec!(2.4)), lot("no", dec!(5), dec!(2.5))], dec!(5)),
+            LedgerTxn::split("0xA", &["yes", "no", "maybe"], dec!(10)),
+            LedgerTxn::deposit("0xA", dec!(100)),
+            LedgerTxn::withdrawal("0xA", dec!(40)),
+            LedgerTxn::fee("0xA", dec!(0.1)),
+        ];
+        for txn in &txns {
+            assert!(txn.is_balanced(), "{:?} is unbalanced", txn.kind);
+        }
+        assert_eq!(held.take(dec!(4)).cost, dec!(2.4));
+    }
+
+    #[test]
+    fn test_summary() {
+        let position = lot("yes", dec!(10), dec!(6));
+        let txns = vec![
+            LedgerTxn::deposit("0xA", dec!(100)),
+            // Winner: bought 10 @ 0.60, sold 4 @ 0.75 and redeemed 6 @ 1
+            LedgerTxn::buy("0xA", "yes", dec!(10), dec!(6), dec!(0)).strategy("ResolutionSniper").reference("position:1"),
+            LedgerTxn::sell("0xA", &position.take(dec!(4)), dec!(3), dec!(0.1)).strategy("ResolutionSniper").reference("position:1"),
+            LedgerTxn::redeem("0xA", &[lot("yes", dec!(6), dec!(3.6))], dec!(6)).strategy("ResolutionSniper").reference("position:1"),
+            // Loser: 5 @ 0.80 expired worthless
+            LedgerTxn::buy("0xA", "b", dec!(5), dec!(4), dec!(0)).strategy("Dispute").reference("position:2"),
+            LedgerTxn::redeem("0xA", &[lot("b", dec!(5), dec!(4))], dec!(0)).strategy("Dispute").reference("position:2"),
+            // Still open: 20 @ 0.50, marked at 0.55
+            LedgerTxn::buy("0xA", "c", dec!(20), dec!(10), dec!(0)).strategy("ResolutionSniper").reference("position:3"),
+        ];
+        let marks = HashMap::from([("c".to_string(), dec!(0.55))]);
+        let summary = LedgerSummary::from_entries(&entries(&txns), &marks);
+
+        assert_eq!(summary.net_deposits, dec!(100));
+        assert_eq!(summary.cash, dec!(100) - dec!(6) + dec!(2.9) + dec!(6) - dec!(4) - dec!(10));
+        assert_eq!(summary.gross_pnl, dec!(0.6) + dec!(2.4) - dec!(4));
+        assert_eq!(summary.fees, dec!(0.1));
+        assert_eq!(summary.realized_pnl, dec!(-1.1));
+        assert_eq!((summary.trades, summary.wins, summary.losses), (2, 1, 1));
+        assert_eq!(summary.best_trade.as_ref().unwrap().pnl, dec!(2.9));
+        assert_eq!(summary.worst_trade.as_ref().unwrap().reference, "position:2");
+        assert_eq!(summary.strategy("ResolutionSniper").realized_pnl, dec!(2.9));
+        assert_eq!(summary.strategy("Dispute").trades, 1);
+
+        assert_eq!(summary.holdings.len(), 1);
+        assert_eq!(summary.cost_basis, dec!(10));
+        assert_eq!(summary.market_value, dec!(11));
+        assert_eq!(summary.unrealized_pnl, dec!(1));
+        assert_eq!(summary.equity, summary.cash + dec!(11));
+    }
+
+    #[test]
+    fn test_position_resolution() {
+        let buy = LedgerTxn::buy("0xA", "yes", dec!(10), dec!(6), dec!(0)).reference("position:1");
+        let held = lots(std::slice::from_ref(&buy));
+        let sale = position_sale("0xA", 1, "ResolutionSniper", &held, Some(dec!(4)), dec!(0.75), dec!(0), "sell:4").unwrap();
+        let held = lots(&[buy, sale]);
+        assert_eq!(held, vec![lot("yes", dec!(6), dec!(3.6))]);
+
+        // The resolution settles only the shares still held, at the payout
+        let resolved = position_resolution("0xA", 1, "ResolutionSniper", &held, dec!(1)).unwrap();
+        assert!(resolved.is_balanced());
+        assert_eq!(resolved.key.as_deref(), Some("position:1:redeem"));
+        assert!(lots(&[resolved]).is_empty());
+        assert!(position_resolution("0xA", 1, "ResolutionSniper", &[], dec!(1)).is_none());
+    }
+}
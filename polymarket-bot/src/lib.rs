@@ -10,9 +10,14 @@ pub mod api;
 pub mod config;
 pub mod db;
 pub mod executor;
+pub mod export;
+pub mod ledger;
+pub mod market_index;
+pub mod migrations;
 pub mod scanner;
 pub mod services;
 pub mod strategies;
+pub mod taxonomy;
 pub mod types;
 pub mod wallet;
 pub mod webhook;
@@ -20,6 +25,7 @@ pub mod webhook;
 pub use config::Config;
 pub use db::Database;
 pub use executor::Executor;
+pub use market_index::{MarketDelta, MarketIndex, MarketsUpdate, ScanCost};
 pub use scanner::Scanner;
 pub use services::{EndpointClass, McScanner, McStatusUpdate, McScoutResult, Metrics, MintMakerRunner, MintMakerStatusUpdate, OrderEvent, PriceUpdate, PriceUpdateTx, PriceWebSocket, RateLimiter, ResolutionTracker, TickSizeCache, UserWebSocket};
 pub use strategies::{AllOpportunities, MintMakerStrategy, SniperStrategy, StrategyRunner};
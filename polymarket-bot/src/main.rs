@@ -3,9 +3,12 @@
 //! A trading bot for Polymarket prediction markets.
 
 use anyhow::Result;
+use chrono::{Datelike, NaiveDate, Utc};
 use clap::{Parser, Subcommand};
+use polymarket_bot::export::{ExportSection, LotMethod, TradeExport};
 use polymarket_bot::{Config, Database, DiscordWebhook, Executor, Scanner, StrategyRunner};
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::time::Duration;
 use tracing::{error, info, Level};
 use tracing_subscriber::FmtSubscriber;
@@ -64,6 +67,53 @@ enum Commands {
 
     /// Show bot statistics
     Stats,
+
+    /// Export fills, tax lots and realized gains of a wallet
+    Export {
+        /// Wallet address
+        #[arg(short, long)]
+        wallet: String,
+
+        /// First day (YYYY-MM-DD, defaults to January 1 of the last day's year)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+
+        /// Last day, inclusive (YYYY-MM-DD, defaults to today)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+
+        /// Output format: json or csv
+        #[arg(short, long, default_value = "json")]
+        format: String,
+
+        /// Lot matching: fifo or specific
+        #[arg(short, long, default_value = "fifo")]
+        method: String,
+
+        /// CSV table: fills, lots, gains or daily
+        #[arg(short, long, default_value = "fills")]
+        section: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// Apply pending schema migrations (backs the database up first)
+    Migrate {
+        /// Only show applied and pending migrations
+        #[arg(long)]
+        status: bool,
+    },
 }
 
 #[tokio::main]
@@ -86,6 +136,10 @@ async fn main() -> Result<()> {
         Commands::Snipe { max_hours, limit, no_sports } => snipe_markets(&config, max_hours, limit, no_sports).await?,
         Commands::Run { interval, auto_execute } => run_bot(&config, interval, auto_execute).await?,
         Commands::Stats => show_stats(&config).await?,
+        Commands::Export { wallet, from, to, format, method, section, output } => {
+            export_trades(&config, &wallet, from, to, &format, &method, &section, output).await?
+        }
+        Commands::Db { command: DbCommand::Migrate { status } } => migrate_db(&config, status).await?,
     }
 
     Ok(())
@@ -111,7 +165,7 @@ async fn scan_markets(config: &Config, limit: usize, no_sports: bool) -> Result<
     // Filter out sports if requested
     if no_sports {
         opportunities.sniper = opportunities.sniper.into_iter()
-            .filter(|o| !is_sports_market(&o.question, o.category.as_deref()))
+            .filter(|o| !o.is_sports())
             .collect();
     }
 
@@ -126,7 +180,7 @@ async fn scan_markets(config: &Config, limit: usize, no_sports: bool) -> Result<
     );
 
     // Save to database
-    let db = Database::new(&config.database_path).await?;
+    let db = Database::connect(config).await?;
     db.record_scan(
         markets.len() as i64,
         opportunities.sniper.len() as i64,
@@ -154,7 +208,7 @@ async fn snipe_markets(config: &Config, max_hours: f64, limit: usize, no_sports:
     // Filter out sports if requested
     if no_sports {
         opportunities = opportunities.into_iter()
-            .filter(|o| !is_sports_market(&o.question, o.category.as_deref()))
+            .filter(|o| !o.is_sports())
             .collect();
         println!("After filtering sports: {} opportunities\n", opportunities.len());
     }
@@ -164,47 +218,6 @@ async fn snipe_markets(config: &Config, max_hours: f64, limit: usize, no_sports:
     Ok(())
 }
 
-/// Check if a market is sports-related based on question text and category
-fn is_sports_market(question: &str, category: Option<&str>) -> bool {
-    let text = question.to_lowercase();
-
-    // Check category first
-    if let Some(cat) = category {
-        let cat_lower = cat.to_lowercase();
-        if cat_lower.contains("sport") || cat_lower.contains("nba") || cat_lower.contains("nfl")
-            || cat_lower.contains("mlb") || cat_lower.contains("soccer") || cat_lower.contains("football") {
-            return true;
-        }
-    }
-
-    // Sports keywords
-    let keywords = [
-        "win on 2026", "win on 2025", " vs ", " vs. ", "match", "game",
-        "nba", "nfl", "mlb", "nhl", "mls", "premier league", "la liga",
-        "bundesliga", "serie a", "ligue 1", "champions league", "playoffs",
-        "eredivisie", "liga portugal", "saudi pro", "super bowl",
-        "lakers", "celtics", "warriors", "yankees", "dodgers",
-        "soccer", "football", "basketball", "baseball", "hockey",
-        "tennis", "golf", "formula 1", "f1", "ufc", "boxing", "mma",
-        "esports", "call of duty", "league of legends",
-        "feyenoord", "psv", "ajax", "barcelona", "real madrid",
-        "manchester", "liverpool", "arsenal", "chelsea",
-        "end in a draw", "will .* win on",
-        "spread:", "over/under", "total goals", "total points",
-        "marseille", "river plate", "rosario", "racing club", "boca juniors",
-        "olimpia", "flamengo", "palmeiras", "corinthians", "santos",
-        "ligaprofesional", "ligue1", "laliga", "seriea",
-    ];
-
-    for keyword in keywords {
-        if text.contains(keyword) {
-            return true;
-        }
-    }
-
-    false
-}
-
 async fn run_bot(config: &Config, interval: u64, auto_execute: Option<f64>) -> Result<()> {
     println!("\n{}", "=".repeat(70));
     println!("  CONTINUOUS MODE");
@@ -217,7 +230,7 @@ async fn run_bot(config: &Config, interval: u64, auto_execute: Option<f64>) -> R
 
     let scanner = Scanner::new(config.clone());
     let runner = StrategyRunner::new(config);
-    let db = Database::new(&config.database_path).await?;
+    let db = Database::connect(config).await?;
     let executor = Executor::new(config.clone(), db);
 
     // Set up Discord webhook if configured
@@ -280,8 +293,8 @@ async fn run_bot(config: &Config, interval: u64, auto_execute: Option<f64>) -> R
 }
 
 async fn show_stats(config: &Config) -> Result<()> {
-    let db = Database::new(&config.database_path).await?;
-    let stats = db.get_stats().await?;
+    let db = Database::connect(config).await?;
+    let stats = db.positions().get_stats().await?;
 
     println!("\n{}", "=".repeat(70));
     println!("  BOT STATISTICS");
@@ -300,7 +313,7 @@ async fn show_stats(config: &Config) -> Result<()> {
         stats.sniper_trades, stats.sniper_wins, stats.sniper_win_rate());
 
     // Show open positions
-    let positions = db.get_open_positions().await?;
+    let positions = db.positions().get_open_positions().await?;
     if !positions.is_empty() {
         println!("\nOpen Positions ({}):", positions.len());
         for pos in positions.iter().take(10) {
@@ -318,6 +331,89 @@ async fn show_stats(config: &Config) -> Result<()> {
     Ok(())
 }
 
+async fn export_trades(
+    config: &Config,
+    wallet: &str,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    format: &str,
+    method: &str,
+    section: &str,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = from.unwrap_or_else(|| NaiveDate::from_ymd_opt(to.year(), 1, 1).unwrap_or(to));
+    if from > to {
+        anyhow::bail!("--from must not be after --to");
+    }
+    let method = LotMethod::parse(method).ok_or_else(|| anyhow::anyhow!("--method must be 'fifo' or 'specific'"))?;
+    let section = ExportSection::parse(section)
+        .ok_or_else(|| anyhow::anyhow!("--section must be 'fills', 'lots', 'gains' or 'daily'"))?;
+
+    let db = Database::connect(config).await?;
+    let export = TradeExport::generate(&db, wallet, from, to, method).await?;
+
+    let body = match format {
+        "json" => serde_json::to_string_pretty(&export)?,
+        "csv" => export.to_csv(section),
+        _ => anyhow::bail!("--format must be 'json' or 'csv'"),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, body)?;
+            info!(
+                "Exported {} fills, {} lots, {} realized gains ({} to {}) to {}",
+                export.fills.len(), export.lots.len(), export.realized.len(), from, to, path.display()
+            );
+        }
+        None => println!("{}", body),
+    }
+
+    Ok(())
+}
+
+async fn migrate_db(config: &Config, status_only: bool) -> Result<()> {
+    let db = Database::open(&config.database_path).await?;
+
+    if !status_only {
+        let applied = db.migrate().await?;
+        if applied.is_empty() {
+            println!("Database is up to date");
+        } else {
+            println!("Applied migrations: {:?}", applied);
+        }
+    }
+
+    let status = db.migration_status().await?;
+    println!("\nSchema version: {} (latest {})", status.current, status.latest);
+
+    if !status.applied.is_empty() {
+        println!("\nApplied:");
+        for m in &status.applied {
+            let flag = if status.modified.contains(&m.version) {
+                "  [modified since applied]"
+            } else if status.unknown.contains(&m.version) {
+                "  [unknown to this build]"
+            } else {
+                ""
+            };
+            println!("  {:04}_{}  {}{}", m.version, m.name, m.applied_at, flag);
+        }
+    }
+
+    if status.pending.is_empty() {
+        println!("\nNo pending migrations");
+    } else {
+        println!("\nPending:");
+        for m in &status.pending {
+            println!("  {}", m.file_name());
+        }
+    }
+
+    Ok(())
+}
+
 fn print_sniper_opportunities(opportunities: &[polymarket_bot::Opportunity], limit: usize) {
     if opportunities.is_empty() {
         println!("No sniper opportunities found.\n");
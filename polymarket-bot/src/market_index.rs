@@ -0,0 +1,386 @@
+//! Incremental market index
+//!
+//! Keeps the scanner's view of tradeable markets between cycles so a scan only
+//! has to fetch what changed. Each market carries a fingerprint of the fields
+//! the strategies depend on (Gamma `updatedAt`, end date, prices); applying a
+//! freshly fetched market yields an add/update delta only when it moved. The
+//! index is mirrored to SQLite so a restart resumes incrementally.
+
+use crate::db::Database;
+use crate::types::TrackedMarket;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
+
+/// Fields whose change makes a market worth re-broadcasting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketFingerprint {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub yes_price: Decimal,
+    pub no_price: Decimal,
+}
+
+impl MarketFingerprint {
+    pub fn new(updated_at: Option<DateTime<Utc>>, market: &TrackedMarket) -> Self {
+        Self {
+            updated_at,
+            end_date: market.end_date,
+            yes_price: market.yes_price,
+            no_price: market.no_price,
+        }
+    }
+}
+
+/// Change to the set of tracked markets
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MarketDelta {
+    Added(TrackedMarket),
+    Updated(TrackedMarket),
+    Removed { id: String },
+}
+
+impl MarketDelta {
+    pub fn market_id(&self) -> &str {
+        match self {
+            MarketDelta::Added(m) | MarketDelta::Updated(m) => &m.id,
+            MarketDelta::Removed { id } => id,
+        }
+    }
+}
+
+/// Whether a scan cycle paged through every market or only through changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanMode {
+    Full,
+    Incremental,
+}
+
+impl ScanMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScanMode::Full => "full",
+            ScanMode::Incremental => "incremental",
+        }
+    }
+}
+
+/// What one scan cycle cost
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanCost {
+    pub mode: ScanMode,
+    pub started_at: DateTime<Utc>,
+    /// Gamma API requests made
+    pub requests: u32,
+    /// Markets returned across all pages
+    pub markets_fetched: u32,
+    pub added: u32,
+    pub updated: u32,
+    pub removed: u32,
+    pub duration_ms: u64,
+    #[serde(skip)]
+    timer: Option<Instant>,
+}
+
+impl ScanCost {
+    pub fn start(mode: ScanMode) -> Self {
+        Self {
+            mode,
+            started_at: Utc::now(),
+            requests: 0,
+            markets_fetched: 0,
+            added: 0,
+            updated: 0,
+            removed: 0,
+            duration_ms: 0,
+            timer: Some(Instant::now()),
+        }
+    }
+
+    /// Count one fetched page
+    pub fn record_page(&mut self, markets: usize) {
+        self.requests += 1;
+        self.markets_fetched += markets as u32;
+    }
+
+    /// Tally the cycle's deltas and stop the clock
+    pub fn finish(&mut self, deltas: &[MarketDelta]) {
+        for delta in deltas {
+            match delta {
+                MarketDelta::Added(_) => self.added += 1,
+                MarketDelta::Updated(_) => self.updated += 1,
+                MarketDelta::Removed { .. } => self.removed += 1,
+            }
+        }
+        if let Some(timer) = self.timer.take() {
+            self.duration_ms = timer.elapsed().as_millis() as u64;
+        }
+    }
+}
+
+/// Result of a scan cycle, sent on the markets broadcast
+#[derive(Debug, Clone)]
+pub struct MarketsUpdate {
+    /// Every tracked market after this cycle
+    pub markets: Arc<Vec<TrackedMarket>>,
+    /// What changed since the previous cycle
+    pub deltas: Arc<Vec<MarketDelta>>,
+    pub cost: ScanCost,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedMarket {
+    market: TrackedMarket,
+    fingerprint: MarketFingerprint,
+}
+
+/// Tracked markets keyed by Gamma market ID
+#[derive(Debug, Default)]
+pub struct MarketIndex {
+    markets: HashMap<String, IndexedMarket>,
+    /// Newest Gamma `updatedAt` seen; incremental pages stop once they reach it
+    high_water: Option<DateTime<Utc>>,
+    last_full_scan: Option<DateTime<Utc>>,
+    /// Set when an incremental cycle gave up before reaching the high-water mark
+    full_scan_requested: bool,
+}
+
+impl MarketIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restore the index persisted by previous cycles
+    pub async fn load(db: &Database) -> Result<Self> {
+        let mut index = Self::new();
+
+        for row in db.market_index().get_indexed_markets().await? {
+            let market: TrackedMarket = match serde_json::from_str(&row.market_json) {
+                Ok(m) => m,
+                Err(e) => {
+                    warn!("Dropping unreadable market index row {}: {}", row.id, e);
+                    continue;
+                }
+            };
+            let updated_at = row
+                .updated_at
+                .as_deref()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|d| d.with_timezone(&Utc));
+            index.insert(market, updated_at);
+        }
+
+        index.last_full_scan = db.market_index()
+            .get_last_scan_at(ScanMode::Full.as_str())
+            .await?
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|d| d.with_timezone(&Utc));
+
+        Ok(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.markets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.markets.is_empty()
+    }
+
+    pub fn high_water(&self) -> Option<DateTime<Utc>> {
+        self.high_water
+    }
+
+    /// Whether the next cycle should page through every market
+    pub fn needs_full_scan(&self, interval: Duration, now: DateTime<Utc>) -> bool {
+        self.full_scan_requested
+            || self.markets.is_empty()
+            || self.high_water.is_none()
+            || self.last_full_scan.map_or(true, |t| now - t >= interval)
+    }
+
+    /// Force the next cycle to be a full scan
+    pub fn request_full_scan(&mut self) {
+        self.full_scan_requested = true;
+    }
+
+    /// Advance the high-water mark past a fetched market, tracked or not
+    pub fn observe_update(&mut self, updated_at: Option<DateTime<Utc>>) {
+        if updated_at > self.high_water {
+            self.high_water = updated_at;
+        }
+    }
+
+    fn insert(&mut self, market: TrackedMarket, updated_at: Option<DateTime<Utc>>) {
+        self.observe_update(updated_at);
+        let fingerprint = MarketFingerprint::new(updated_at, &market);
+        self.markets
+            .insert(market.id.clone(), IndexedMarket { market, fingerprint });
+    }
+
+    /// Apply a freshly fetched market that qualifies for tracking.
+    /// Returns a delta when it is new or its fingerprint changed; otherwise the
+    /// stored copy is refreshed silently (volume, liquidity, ...).
+    pub fn apply(&mut self, market: TrackedMarket, updated_at: Option<DateTime<Utc>>) -> Option<MarketDelta> {
+        let fingerprint = MarketFingerprint::new(updated_at, &market);
+        let delta = match self.markets.get(&market.id) {
+            Some(existing) if existing.fingerprint == fingerprint => None,
+            Some(_) => Some(MarketDelta::Updated(market.clone())),
+            None => Some(MarketDelta::Added(market.clone())),
+        };
+        self.insert(market, updated_at);
+        delta
+    }
+
+    /// Drop a market that closed or no longer qualifies
+    pub fn remove(&mut self, id: &str) -> Option<MarketDelta> {
+        self.markets
+            .remove(id)
+            .map(|_| MarketDelta::Removed { id: id.to_string() })
+    }
+
+    /// Finish a full scan: drop every market it did not return
+    pub fn complete_full_scan(&mut self, seen: &HashSet<String>, now: DateTime<Utc>) -> Vec<MarketDelta> {
+        let stale: Vec<String> = self
+            .markets
+            .keys()
+            .filter(|id| !seen.contains(*id))
+            .cloned()
+            .collect();
+
+        self.last_full_scan = Some(now);
+        self.full_scan_requested = false;
+        stale.iter().filter_map(|id| self.remove(id)).collect()
+    }
+
+    /// Recompute the time-dependent `hours_until_close` of every market
+    pub fn refresh_hours_until_close(&mut self, now: DateTime<Utc>) {
+        for entry in self.markets.values_mut() {
+            entry.market.hours_until_close = entry
+                .market
+                .end_date
+                .map(|end| (end - now).num_minutes() as f64 / 60.0);
+        }
+    }
+
+    /// Snapshot of every tracked market
+    pub fn markets(&self) -> Vec<TrackedMarket> {
+        self.markets.values().map(|e| e.market.clone()).collect()
+    }
+
+    /// Mirror a cycle's deltas to SQLite
+    pub async fn persist(&self, db: &Database, deltas: &[MarketDelta]) -> Result<()> {
+        let mut upserts = Vec::new();
+        let mut removals = Vec::new();
+
+        for delta in deltas {
+            match delta {
+                MarketDelta::Added(m) | MarketDelta::Updated(m) => {
+                    let Some(entry) = self.markets.get(&m.id) else {
+                        continue;
+                    };
+                    upserts.push((
+                        m.id.clone(),
+                        m.condition_id.clone(),
+                        entry.fingerprint.updated_at.map(|d| d.to_rfc3339()),
+                        serde_json::to_string(&entry.market)?,
+                    ));
+                }
+                MarketDelta::Removed { id } => removals.push(id.clone()),
+            }
+        }
+
+        if upserts.is_empty() && removals.is_empty() {
+            return Ok(());
+        }
+        db.market_index().save_market_index_changes(&upserts, &removals).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn market(id: &str, yes: &str) -> TrackedMarket {
+        let yes = Decimal::from_str(yes).unwrap();
+        TrackedMarket {
+            id: id.to_string(),
+            condition_id: format!("0x{}", id),
+            question: format!("Will {} happen?", id),
+            slug: id.to_string(),
+            resolution_source: None,
+            description: None,
+            end_date: None,
+            yes_price: yes,
+            no_price: Decimal::ONE - yes,
+            volume: Decimal::ZERO,
+            liquidity: Decimal::ZERO,
+            category: None,
+            active: true,
+            closed: false,
+            yes_token_id: None,
+            no_token_id: None,
+            hours_until_close: None,
+            neg_risk: false,
+            event_id: None,
+            neg_risk_market_id: None,
+            group_item_title: None,
+            question_id: None,
+            taxonomy: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_apply_emits_deltas_only_on_change() {
+        let t0 = Utc::now() - Duration::minutes(5);
+        let t1 = Utc::now();
+        let mut index = MarketIndex::new();
+
+        assert!(matches!(index.apply(market("a", "0.60"), Some(t0)), Some(MarketDelta::Added(_))));
+        assert!(index.apply(market("a", "0.60"), Some(t0)).is_none());
+        assert!(matches!(index.apply(market("a", "0.65"), Some(t0)), Some(MarketDelta::Updated(_))));
+        assert!(matches!(index.apply(market("a", "0.65"), Some(t1)), Some(MarketDelta::Updated(_))));
+        assert_eq!(index.high_water(), Some(t1));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_full_scan_removes_unseen() {
+        let now = Utc::now();
+        let mut index = MarketIndex::new();
+        index.apply(market("a", "0.50"), Some(now));
+        index.apply(market("b", "0.50"), Some(now));
+        assert!(index.needs_full_scan(Duration::minutes(30), now));
+
+        let seen: HashSet<String> = ["a".to_string()].into_iter().collect();
+        let deltas = index.complete_full_scan(&seen, now);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].market_id(), "b");
+        assert!(!index.needs_full_scan(Duration::minutes(30), now));
+        assert!(index.needs_full_scan(Duration::minutes(30), now + Duration::minutes(30)));
+
+        index.request_full_scan();
+        assert!(index.needs_full_scan(Duration::minutes(30), now));
+    }
+
+    #[test]
+    fn test_scan_cost_tally() {
+        let mut cost = ScanCost::start(ScanMode::Incremental);
+        cost.record_page(100);
+        cost.record_page(20);
+        cost.finish(&[
+            MarketDelta::Added(market("a", "0.5")),
+            MarketDelta::Removed { id: "b".to_string() },
+        ]);
+        assert_eq!((cost.requests, cost.markets_fetched), (2, 120));
+        assert_eq!((cost.added, cost.updated, cost.removed), (1, 0, 1));
+    }
+}
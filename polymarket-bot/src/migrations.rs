@@ -0,0 +1,320 @@
+//! Versioned schema migrations
+//!
+//! Migrations are the numbered SQL files under `migrations/`, compiled into
+//! the binary. The `schema_version` table records every migration applied to
+//! a database together with a checksum of its SQL, so a migration edited after
+//! release is reported instead of silently diverging. `Database::new` applies
+//! pending migrations on startup after backing the file up with `VACUUM INTO`;
+//! `polymarket-bot db migrate --status` shows where a database stands. The
+//! shared PostgreSQL store has its own set under `migrations/postgres/`.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+
+/// A numbered schema change
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Every migration, in version order. Never edit a released entry - add a new one.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../migrations/0001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "leader_leases",
+        sql: include_str!("../migrations/0002_leader_leases.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "mint_maker_merge_attempts",
+        sql: include_str!("../migrations/0003_mint_maker_merge_attempts.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "withdrawal_status",
+        sql: include_str!("../migrations/0004_withdrawal_status.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "withdraw_limit_timelock",
+        sql: include_str!("../migrations/0005_withdraw_limit_timelock.sql"),
+    },
+];
+
+/// Migrations of the shared PostgreSQL store (`migrations/postgres/`), versioned separately
+#[cfg(feature = "postgres")]
+pub const POSTGRES_MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../migrations/postgres/0001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "leader_leases",
+        sql: include_str!("../migrations/postgres/0002_leader_leases.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "mint_maker_merge_attempts",
+        sql: include_str!("../migrations/postgres/0003_mint_maker_merge_attempts.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "service_state",
+        sql: include_str!("../migrations/postgres/0004_service_state.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "withdrawal_status",
+        sql: include_str!("../migrations/postgres/0005_withdrawal_status.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "withdraw_limit_timelock",
+        sql: include_str!("../migrations/postgres/0006_withdraw_limit_timelock.sql"),
+    },
+];
+
+impl Migration {
+    /// File name under `migrations/`
+    pub fn file_name(&self) -> String {
+        format!("{:04}_{}.sql", self.version, self.name)
+    }
+
+    /// SHA-256 of the SQL, hex encoded
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+/// Highest version this build knows about
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// A row of `schema_version`
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: String,
+}
+
+/// Where a database stands relative to `MIGRATIONS`
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    /// Highest applied version (0 for a fresh or pre-migration database)
+    pub current: i64,
+    pub latest: i64,
+    pub applied: Vec<AppliedMigration>,
+    /// Known migrations not yet applied, in order
+    pub pending: Vec<&'static Migration>,
+    /// Applied versions whose SQL has changed since
+    pub modified: Vec<i64>,
+    /// Applied versions this build doesn't know (database from a newer build)
+    pub unknown: Vec<i64>,
+}
+
+impl MigrationStatus {
+    pub fn new(applied: Vec<AppliedMigration>) -> Self {
+        Self::from_migrations(MIGRATIONS, applied)
+    }
+
+    /// Status against another migration set (the PostgreSQL store keeps its own)
+    pub fn from_migrations(migrations: &'static [Migration], applied: Vec<AppliedMigration>) -> Self {
+        let current = applied.iter().map(|a| a.version).max().unwrap_or(0);
+        let latest = migrations.last().map(|m| m.version).unwrap_or(0);
+
+        let pending = migrations
+            .iter()
+            .filter(|m| !applied.iter().any(|a| a.version == m.version))
+            .collect();
+        let modified = applied
+            .iter()
+            .filter(|a| {
+                migrations
+                    .iter()
+                    .any(|m| m.version == a.version && m.checksum() != a.checksum)
+            })
+            .map(|a| a.version)
+            .collect();
+        let unknown = applied
+            .iter()
+            .filter(|a| !migrations.iter().any(|m| m.version == a.version))
+            .map(|a| a.version)
+            .collect();
+
+        Self {
+            current,
+            latest,
+            applied,
+            pending,
+            modified,
+            unknown,
+        }
+    }
+}
+
+/// Whether a table exists
+pub async fn table_exists(pool: &SqlitePool, table: &str) -> Result<bool> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.is_some())
+}
+
+pub async fn ensure_version_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Applied migrations, oldest first (empty if the database predates `schema_version`)
+pub async fn applied(pool: &SqlitePool) -> Result<Vec<AppliedMigration>> {
+    if !table_exists(pool, "schema_version").await? {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<(i64, String, String, String)> = sqlx::query_as(
+        "SELECT version, name, checksum, applied_at FROM schema_version ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(version, name, checksum, applied_at)| AppliedMigration {
+            version,
+            name,
+            checksum,
+            applied_at,
+        })
+        .collect())
+}
+
+/// Run one migration and record it, atomically
+pub async fn apply(pool: &SqlitePool, migration: &Migration) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::raw_sql(migration.sql)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| format!("Migration {} failed", migration.file_name()))?;
+
+    sqlx::query("INSERT INTO schema_version (version, name, checksum, applied_at) VALUES (?, ?, ?, ?)")
+        .bind(migration.version)
+        .bind(migration.name)
+        .bind(migration.checksum())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Copy the database next to itself as `<file>.v<version>-<timestamp>.bak`.
+/// Returns `None` for in-memory databases.
+pub async fn backup(pool: &SqlitePool, version: i64) -> Result<Option<PathBuf>> {
+    let filename = pool.connect_options().get_filename().to_path_buf();
+    let name = filename.to_string_lossy();
+    if name.is_empty() || name == ":memory:" {
+        return Ok(None);
+    }
+
+    let target = PathBuf::from(format!(
+        "{}.v{}-{}.bak",
+        name,
+        version,
+        Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    let quoted = target.to_string_lossy().replace('\'', "''");
+
+    sqlx::query(&format!("VACUUM INTO '{}'", quoted))
+        .execute(pool)
+        .await
+        .context("Failed to back up database before migrating")?;
+
+    Ok(Some(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied_row(m: &Migration, checksum: Option<&str>) -> AppliedMigration {
+        AppliedMigration {
+            version: m.version,
+            name: m.name.to_string(),
+            checksum: checksum.map(str::to_string).unwrap_or_else(|| m.checksum()),
+            applied_at: "2026-01-01T00:00:00+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        assert!(!MIGRATIONS.is_empty());
+        assert_eq!(MIGRATIONS[0].version, 1);
+        for pair in MIGRATIONS.windows(2) {
+            assert_eq!(pair[1].version, pair[0].version + 1);
+        }
+        assert_eq!(latest_version(), MIGRATIONS.len() as i64);
+        assert_eq!(MIGRATIONS[0].file_name(), "0001_initial.sql");
+    }
+
+    #[test]
+    fn test_status() {
+        static TWO: &[Migration] = &[
+            Migration { version: 1, name: "a", sql: "CREATE TABLE a (x INTEGER);" },
+            Migration { version: 2, name: "b", sql: "CREATE TABLE b (x INTEGER);" },
+        ];
+
+        let fresh = MigrationStatus::from_migrations(TWO, Vec::new());
+        assert_eq!(fresh.current, 0);
+        assert_eq!(fresh.latest, 2);
+        assert_eq!(fresh.pending.len(), 2);
+
+        let partial = MigrationStatus::from_migrations(TWO, vec![applied_row(&TWO[0], None)]);
+        assert_eq!(partial.current, 1);
+        assert_eq!(partial.pending.iter().map(|m| m.version).collect::<Vec<_>>(), vec![2]);
+        assert!(partial.modified.is_empty());
+
+        let edited = MigrationStatus::from_migrations(
+            TWO,
+            vec![applied_row(&TWO[0], Some("stale")), applied_row(&TWO[1], None)],
+        );
+        assert!(edited.pending.is_empty());
+        assert_eq!(edited.modified, vec![1]);
+
+        let mut newer = applied_row(&TWO[1], None);
+        newer.version = 3;
+        let ahead = MigrationStatus::from_migrations(TWO, vec![applied_row(&TWO[0], None), newer]);
+        assert_eq!(ahead.current, 3);
+        assert_eq!(ahead.unknown, vec![3]);
+    }
+}
@@ -1,22 +1,43 @@
 //! Market scanner for Polymarket Gamma API
 
 use crate::config::{Config, GammaApi};
-use crate::types::{MarketHolder, MarketHolders, TrackedMarket};
+use crate::market_index::{MarketDelta, MarketIndex, ScanCost, ScanMode};
+use crate::taxonomy::{classify, MarketSeries, MarketTag};
+use crate::types::{neg_risk_question_index, EventOutcome, MarketHolder, MarketHolders, NegRiskEvent, TrackedMarket};
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use chrono_tz::Tz;
 use regex::Regex;
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::str::FromStr;
+use std::sync::RwLock;
 use tracing::{debug, info, warn};
 
+/// Page size for Gamma market listings
+const PAGE_LIMIT: usize = 100;
+
+/// Most pages an incremental scan reads before falling back to a full scan
+const MAX_INCREMENTAL_PAGES: usize = 50;
+
+/// Most unknown events looked up one by one per incremental scan
+const MAX_EVENT_LOOKUPS: usize = 25;
+
 /// Scanner for fetching and processing Polymarket markets
 pub struct Scanner {
     client: Client,
     config: Config,
+    /// Tags and series per Gamma event ID, used to classify markets
+    event_taxonomy: RwLock<HashMap<String, EventTaxonomy>>,
+}
+
+/// Tags and series of a Gamma event
+#[derive(Debug, Clone, Default)]
+struct EventTaxonomy {
+    tags: Vec<MarketTag>,
+    series: Option<MarketSeries>,
 }
 
 /// Raw market response from Gamma API
@@ -62,26 +83,75 @@ struct GammaMarket {
     /// Whether this is a neg-risk market (uses different exchange contract)
     #[serde(default, rename = "negRisk")]
     neg_risk: bool,
+    /// NegRiskAdapter market ID shared by the outcomes of a neg-risk event
+    #[serde(default, rename = "negRiskMarketID")]
+    neg_risk_market_id: Option<String>,
+    /// Outcome label within a multi-outcome event
+    #[serde(default)]
+    group_item_title: Option<String>,
+    #[serde(default, rename = "questionID")]
+    question_id: Option<String>,
+    /// Last time Gamma changed this market (drives incremental scans)
+    #[serde(default)]
+    updated_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct GammaTag {
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct GammaTag {
+    /// Numeric or string depending on the endpoint
+    #[serde(default)]
+    id: Option<serde_json::Value>,
     #[serde(default)]
     label: Option<String>,
     #[serde(default)]
-    #[allow(dead_code)]
     slug: Option<String>,
 }
 
+/// Series (recurring event group) from Gamma
+#[derive(Debug, Deserialize)]
+struct GammaSeries {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    #[serde(default)]
+    slug: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+}
+
 /// Event data from Gamma API (parent of markets)
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GammaEvent {
+    #[serde(default)]
+    id: Option<String>,
     #[serde(default)]
     slug: Option<String>,
     #[serde(default)]
     #[allow(dead_code)]
     title: Option<String>,
+    #[serde(default)]
+    tags: Option<Vec<GammaTag>>,
+    #[serde(default)]
+    series: Option<Vec<GammaSeries>>,
+}
+
+/// Event with nested markets from the Gamma /events endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GammaEventWithMarkets {
+    id: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    slug: Option<String>,
+    #[serde(default, rename = "negRiskMarketID")]
+    neg_risk_market_id: Option<String>,
+    #[serde(default)]
+    tags: Option<Vec<GammaTag>>,
+    #[serde(default)]
+    series: Option<Vec<GammaSeries>>,
+    #[serde(default)]
+    markets: Vec<GammaMarket>,
 }
 
 /// Response from the Polymarket Data API /holders endpoint
@@ -112,11 +182,16 @@ impl Scanner {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client, config }
+        Self {
+            client,
+            config,
+            event_taxonomy: RwLock::new(HashMap::new()),
+        }
     }
 
     /// Fetch all active markets from Gamma API
     pub async fn fetch_markets(&self) -> Result<Vec<TrackedMarket>> {
+        self.ensure_event_taxonomy().await;
         let mut all_markets = Vec::new();
         let mut offset = 0;
         let limit = 100;
@@ -175,6 +250,299 @@ impl Scanner {
         Ok(all_markets)
     }
 
+    /// Bring a market index up to date and return what changed.
+    ///
+    /// A full scan pages through every active market like `fetch_markets` and
+    /// drops whatever it no longer returns. An incremental scan pages through
+    /// markets newest `updatedAt` first (closed ones included, so closures show
+    /// up as removals) until it reaches the index's high-water mark, then
+    /// refetches the sniper window so markets entering it are current even if
+    /// Gamma hasn't touched them.
+    pub async fn scan(&self, index: &mut MarketIndex) -> Result<(Vec<MarketDelta>, ScanCost)> {
+        let now = Utc::now();
+        let reconcile_interval = chrono::Duration::minutes(self.config.scan_full_reconcile_minutes as i64);
+        let mode = if index.needs_full_scan(reconcile_interval, now) {
+            ScanMode::Full
+        } else {
+            ScanMode::Incremental
+        };
+        let mut cost = ScanCost::start(mode);
+
+        // Fetch everything before touching the index so a failed page can't
+        // leave it half-updated with deltas nobody saw
+        let fetched = match mode {
+            ScanMode::Full => self.fetch_all_gamma_markets(&mut cost).await?,
+            ScanMode::Incremental => {
+                let high_water = index.high_water();
+                let mut fetched = Vec::new();
+                let mut offset = 0;
+                loop {
+                    let page = self
+                        .fetch_gamma_page("active=true&order=updatedAt&ascending=false", offset, &mut cost)
+                        .await?;
+                    let batch_size = page.len();
+                    let reached = page
+                        .iter()
+                        .any(|gm| parse_timestamp(gm.updated_at.as_deref()).is_some_and(|t| Some(t) < high_water));
+                    fetched.extend(page);
+
+                    if reached || batch_size < PAGE_LIMIT {
+                        break;
+                    }
+                    offset += PAGE_LIMIT;
+                    if offset >= PAGE_LIMIT * MAX_INCREMENTAL_PAGES {
+                        warn!("Incremental scan did not reach last cycle's changes, next scan will be full");
+                        index.request_full_scan();
+                        break;
+                    }
+                }
+
+                let end_date_max = (now + chrono::Duration::hours(self.config.sniper.max_hours as i64))
+                    .format("%Y-%m-%dT%H:%M:%SZ")
+                    .to_string();
+                let window_query = format!(
+                    "active=true&closed=false&liquidity_num_min={}&end_date_max={}",
+                    self.config.min_liquidity, end_date_max
+                );
+                let mut offset = 0;
+                loop {
+                    let page = self.fetch_gamma_page(&window_query, offset, &mut cost).await?;
+                    let batch_size = page.len();
+                    fetched.extend(page);
+                    if batch_size < PAGE_LIMIT || offset > 5000 {
+                        break;
+                    }
+                    offset += PAGE_LIMIT;
+                }
+                fetched
+            }
+        };
+
+        // Classification needs each market's event tags and series
+        match mode {
+            ScanMode::Full => {
+                if let Err(e) = self.refresh_event_taxonomy(&mut cost).await {
+                    warn!("Failed to refresh event taxonomy: {}", e);
+                }
+            }
+            ScanMode::Incremental => {
+                let missing: HashSet<String> = {
+                    let known = self.event_taxonomy.read().unwrap_or_else(|e| e.into_inner());
+                    fetched
+                        .iter()
+                        .filter_map(|gm| gm.events.as_ref()?.first()?.id.clone())
+                        .filter(|id| !known.contains_key(id))
+                        .collect()
+                };
+                self.fetch_event_taxonomy(missing.into_iter().take(MAX_EVENT_LOOKUPS), &mut cost)
+                    .await;
+            }
+        }
+
+        let mut deltas = Vec::new();
+        let mut seen = HashSet::new();
+        for gm in fetched {
+            seen.insert(gm.id.clone());
+            deltas.extend(self.apply_gamma_market(index, gm));
+        }
+        if mode == ScanMode::Full {
+            deltas.extend(index.complete_full_scan(&seen, now));
+        }
+        index.refresh_hours_until_close(now);
+
+        cost.finish(&deltas);
+        info!(
+            "{} scan: {} requests, {} markets fetched, +{} ~{} -{} ({} tracked) in {}ms",
+            mode.as_str(),
+            cost.requests,
+            cost.markets_fetched,
+            cost.added,
+            cost.updated,
+            cost.removed,
+            index.len(),
+            cost.duration_ms
+        );
+        Ok((deltas, cost))
+    }
+
+    /// Apply one raw market to the index, removing it once closed or below the liquidity floor
+    fn apply_gamma_market(&self, index: &mut MarketIndex, gm: GammaMarket) -> Option<MarketDelta> {
+        let id = gm.id.clone();
+        let updated_at = parse_timestamp(gm.updated_at.as_deref());
+        index.observe_update(updated_at);
+
+        if gm.closed || !gm.active {
+            return index.remove(&id);
+        }
+        match self.parse_market(gm) {
+            Some(market) => index.apply(market, updated_at),
+            None => index.remove(&id),
+        }
+    }
+
+    /// Reload tags and series for every active event
+    async fn refresh_event_taxonomy(&self, cost: &mut ScanCost) -> Result<()> {
+        let mut taxonomy = HashMap::new();
+        let mut offset = 0;
+
+        loop {
+            let url = format!(
+                "{}?active=true&closed=false&limit={}&offset={}",
+                GammaApi::events_url(),
+                PAGE_LIMIT,
+                offset
+            );
+
+            debug!("Fetching event taxonomy from: {}", url);
+
+            let response = self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .context("Failed to fetch events")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API error {}: {}", status, body);
+            }
+
+            let events: Vec<GammaEvent> = response
+                .json()
+                .await
+                .context("Failed to parse event response")?;
+
+            let batch_size = events.len();
+            cost.record_page(0);
+
+            for event in events {
+                if let Some(id) = event.id.clone() {
+                    taxonomy.insert(id, event_taxonomy(event.tags, event.series));
+                }
+            }
+
+            if batch_size < PAGE_LIMIT {
+                break;
+            }
+            offset += PAGE_LIMIT;
+
+            // Safety limit
+            if offset > 20000 {
+                warn!("Reached safety limit for event taxonomy");
+                break;
+            }
+        }
+
+        debug!("Event taxonomy refreshed for {} events", taxonomy.len());
+        *self.event_taxonomy.write().unwrap_or_else(|e| e.into_inner()) = taxonomy;
+        Ok(())
+    }
+
+    /// Load the event taxonomy once for callers outside the incremental scan
+    async fn ensure_event_taxonomy(&self) {
+        if !self.event_taxonomy.read().unwrap_or_else(|e| e.into_inner()).is_empty() {
+            return;
+        }
+        if let Err(e) = self.refresh_event_taxonomy(&mut ScanCost::start(ScanMode::Full)).await {
+            warn!("Failed to load event taxonomy: {}", e);
+        }
+    }
+
+    /// Look up events the taxonomy cache doesn't know yet, one request each
+    async fn fetch_event_taxonomy(&self, event_ids: impl Iterator<Item = String>, cost: &mut ScanCost) {
+        for event_id in event_ids {
+            let url = format!("{}/{}", GammaApi::events_url(), event_id);
+            let event: GammaEvent = match self.client.get(&url).send().await {
+                Ok(response) if response.status().is_success() => match response.json().await {
+                    Ok(event) => event,
+                    Err(e) => {
+                        debug!("Failed to parse event {}: {}", event_id, e);
+                        continue;
+                    }
+                },
+                Ok(response) => {
+                    debug!("Event {} lookup returned {}", event_id, response.status());
+                    continue;
+                }
+                Err(e) => {
+                    debug!("Event {} lookup failed: {}", event_id, e);
+                    continue;
+                }
+            };
+            cost.record_page(0);
+            self.cache_event_taxonomy(event_id, event.tags, event.series);
+        }
+    }
+
+    fn cache_event_taxonomy(&self, event_id: String, tags: Option<Vec<GammaTag>>, series: Option<Vec<GammaSeries>>) {
+        self.event_taxonomy
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(event_id, event_taxonomy(tags, series));
+    }
+
+    /// Page through every active market without parsing
+    async fn fetch_all_gamma_markets(&self, cost: &mut ScanCost) -> Result<Vec<GammaMarket>> {
+        let mut all = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page = self
+                .fetch_gamma_page("active=true&closed=false", offset, cost)
+                .await?;
+            let batch_size = page.len();
+            all.extend(page);
+
+            if batch_size < PAGE_LIMIT {
+                break;
+            }
+            offset += PAGE_LIMIT;
+
+            // Safety limit to avoid infinite loops
+            if offset > 35000 {
+                warn!("Reached safety limit of 35000 markets");
+                break;
+            }
+        }
+
+        Ok(all)
+    }
+
+    /// Fetch one page of the Gamma market listing
+    async fn fetch_gamma_page(&self, query: &str, offset: usize, cost: &mut ScanCost) -> Result<Vec<GammaMarket>> {
+        let url = format!(
+            "{}?{}&limit={}&offset={}",
+            GammaApi::markets_url(),
+            query,
+            PAGE_LIMIT,
+            offset
+        );
+
+        debug!("Fetching markets from: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch markets")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("API error {}: {}", status, body);
+        }
+
+        let page: Vec<GammaMarket> = response
+            .json()
+            .await
+            .context("Failed to parse market response")?;
+
+        cost.record_page(page.len());
+        Ok(page)
+    }
+
     /// Fetch markets with server-side filters optimized for the sniper strategy.
     /// Uses Gamma API's `liquidity_num_min` and `end_date_max` to reduce results
     /// from ~25K to ~100-200, cutting API calls from ~260 to 1-2.
@@ -189,6 +557,7 @@ impl Scanner {
             .to_string();
 
         let min_liquidity = self.config.min_liquidity;
+        self.ensure_event_taxonomy().await;
 
         loop {
             let url = format!(
@@ -263,6 +632,103 @@ impl Scanner {
         Ok(closing_soon)
     }
 
+    /// Fetch active neg-risk events with all of their outcome markets.
+    ///
+    /// Unlike `fetch_markets`, outcomes are not filtered by liquidity: an event's
+    /// price sum is only meaningful when every outcome is present.
+    pub async fn fetch_neg_risk_events(&self) -> Result<Vec<NegRiskEvent>> {
+        let mut markets = Vec::new();
+        let mut titles: HashMap<String, String> = HashMap::new();
+        // Outcome markets left out per event (an incomplete event can't be arbed)
+        let mut dropped: HashMap<String, usize> = HashMap::new();
+        let mut offset = 0;
+        let limit = 100;
+
+        loop {
+            let url = format!(
+                "{}?active=true&closed=false&neg_risk=true&limit={}&offset={}",
+                GammaApi::events_url(),
+                limit,
+                offset
+            );
+
+            debug!("Fetching neg-risk events from: {}", url);
+
+            let response = self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .context("Failed to fetch neg-risk events")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API error {}: {}", status, body);
+            }
+
+            let events: Vec<GammaEventWithMarkets> = response
+                .json()
+                .await
+                .context("Failed to parse neg-risk event response")?;
+
+            let batch_size = events.len();
+
+            for mut event in events {
+                if let Some(title) = &event.title {
+                    titles.insert(event.id.clone(), title.clone());
+                }
+                self.cache_event_taxonomy(event.id.clone(), event.tags.take(), event.series.take());
+                for gm in event.markets {
+                    if !gm.active || gm.closed {
+                        *dropped.entry(event.id.clone()).or_default() += 1;
+                        continue;
+                    }
+                    let Some(mut market) = self.build_market(gm) else {
+                        *dropped.entry(event.id.clone()).or_default() += 1;
+                        continue;
+                    };
+                    // Nested markets don't carry their parent event, so take it from here
+                    market.event_id = Some(event.id.clone());
+                    market.neg_risk = true;
+                    if market.neg_risk_market_id.is_none() {
+                        market.neg_risk_market_id = event.neg_risk_market_id.clone();
+                    }
+                    if let Some(slug) = &event.slug {
+                        market.slug = slug.clone();
+                    }
+                    markets.push(market);
+                }
+            }
+
+            if batch_size < limit {
+                break;
+            }
+
+            offset += limit;
+
+            // Safety limit
+            if offset > 5000 {
+                warn!("Reached safety limit for neg-risk events");
+                break;
+            }
+        }
+
+        let mut events = group_neg_risk_events(&markets);
+        for event in &mut events {
+            if let Some(title) = titles.remove(&event.event_id) {
+                event.title = title;
+            }
+            event.dropped_outcomes = dropped.remove(&event.event_id).unwrap_or(0);
+        }
+        info!(
+            "Neg-risk events fetched: {} ({} outcome markets)",
+            events.len(),
+            markets.len()
+        );
+        Ok(events)
+    }
+
     /// Fetch top holders for a batch of markets from the Polymarket Data API.
     /// Returns a map of condition_id -> MarketHolders.
     ///
@@ -375,8 +841,14 @@ impl Scanner {
         Ok(all_results)
     }
 
-    /// Parse a Gamma API market into our TrackedMarket type
+    /// Parse a Gamma API market into our TrackedMarket type, skipping low liquidity markets
     fn parse_market(&self, gm: GammaMarket) -> Option<TrackedMarket> {
+        self.build_market(gm)
+            .filter(|m| m.liquidity >= self.config.min_liquidity)
+    }
+
+    /// Convert a Gamma API market into our TrackedMarket type
+    fn build_market(&self, gm: GammaMarket) -> Option<TrackedMarket> {
         // Parse end date from API - try end_date first (full ISO timestamp), then end_date_iso (just date)
         let api_end_date = gm
             .end_date
@@ -459,30 +931,34 @@ impl Scanner {
             .and_then(|v| Decimal::from_str(v).ok())
             .unwrap_or_default();
 
-        // Skip low liquidity markets
-        if liquidity < self.config.min_liquidity {
-            return None;
-        }
-
         // Parse token IDs
         let (yes_token_id, no_token_id) = self.parse_token_ids(&gm.clob_token_ids);
 
         // Get event slug (correct URL slug) - fall back to market slug if no event
-        let event_slug = gm.events
-            .as_ref()
-            .and_then(|events| events.first())
+        let event = gm.events.as_ref().and_then(|events| events.first());
+        let event_id = event.and_then(|e| e.id.clone());
+        let event_slug = event
             .and_then(|e| e.slug.clone())
             .unwrap_or_else(|| gm.slug.clone());
 
-        // Detect category from tags, question text, or slug
-        let category = gm.category
-            .or_else(|| {
-                gm.tags
-                    .as_ref()
-                    .and_then(|tags| tags.first())
-                    .and_then(|t| t.label.clone())
+        // Classify from the event's tags and series plus the market's own tags
+        let EventTaxonomy { mut tags, series } = event_id
+            .as_deref()
+            .and_then(|id| {
+                self.event_taxonomy
+                    .read()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(id)
+                    .cloned()
             })
-            .or_else(|| self.detect_category_from_text(&gm.question, &event_slug));
+            .unwrap_or_default();
+        for tag in market_tags(gm.tags) {
+            if !tags.iter().any(|t| t.slug == tag.slug) {
+                tags.push(tag);
+            }
+        }
+        let taxonomy = classify(tags, series, gm.category.as_deref());
+        let category = taxonomy.category_label();
 
         Some(TrackedMarket {
             id: gm.id,
@@ -503,50 +979,14 @@ impl Scanner {
             no_token_id,
             hours_until_close,
             neg_risk: gm.neg_risk,
+            event_id,
+            neg_risk_market_id: gm.neg_risk_market_id,
+            group_item_title: gm.group_item_title,
+            question_id: gm.question_id,
+            taxonomy,
         })
     }
 
-    /// Detect category from question text and slug using keyword matching
-    fn detect_category_from_text(&self, question: &str, slug: &str) -> Option<String> {
-        let text = format!("{} {}", question.to_lowercase(), slug.to_lowercase());
-
-        // Sports keywords
-        let sports_keywords = [
-            "nba", "nfl", "mlb", "nhl", "mls", "premier league", "la liga",
-            "bundesliga", "serie a", "ligue 1", "champions league", "playoffs",
-            "super bowl", "world cup", "world series", "stanley cup",
-            "lakers", "celtics", "warriors", "yankees", "dodgers", "cowboys",
-            "patriots", "chiefs", "bills", "eagles", "49ers", "packers",
-            "soccer", "football", "basketball", "baseball", "hockey",
-            "tennis", "golf", "formula 1", "f1", "ufc", "boxing", "mma",
-            "win on 2026", "win on 2025", "match", "game", "vs", " vs ",
-            "feyenoord", "galatasaray", "barcelona", "real madrid", "bayern",
-            "manchester", "liverpool", "arsenal", "chelsea", "tottenham",
-            "esports", "call of duty", "league of legends", "valorant", "csgo",
-        ];
-
-        for keyword in sports_keywords {
-            if text.contains(keyword) {
-                return Some("Sports".to_string());
-            }
-        }
-
-        // Crypto keywords
-        let crypto_keywords = [
-            "bitcoin", "btc", "ethereum", "eth", "solana", "sol",
-            "crypto", "token", "defi", "nft", "blockchain",
-            "above $", "below $", "price", "market cap",
-        ];
-
-        for keyword in crypto_keywords {
-            if text.contains(keyword) {
-                return Some("Crypto".to_string());
-            }
-        }
-
-        None
-    }
-
     /// Parse outcome prices from JSON string
     fn parse_outcome_prices(&self, prices_str: &Option<String>) -> Option<(Decimal, Decimal)> {
         let prices_str = prices_str.as_ref()?;
@@ -674,6 +1114,104 @@ impl Scanner {
     }
 }
 
+/// Group neg-risk markets into multi-outcome events by their Gamma event.
+///
+/// Events with fewer than two outcomes are dropped (nothing to compare).
+pub fn group_neg_risk_events(markets: &[TrackedMarket]) -> Vec<NegRiskEvent> {
+    let mut grouped: HashMap<&str, Vec<&TrackedMarket>> = HashMap::new();
+    for market in markets.iter().filter(|m| m.neg_risk) {
+        if let Some(event_id) = &market.event_id {
+            grouped.entry(event_id.as_str()).or_default().push(market);
+        }
+    }
+
+    let mut events: Vec<NegRiskEvent> = grouped
+        .into_iter()
+        .filter(|(_, outcomes)| outcomes.len() >= 2)
+        .map(|(event_id, outcomes)| {
+            let first = outcomes[0];
+            let end_date = outcomes.iter().filter_map(|m| m.end_date).max();
+            let outcomes = outcomes
+                .iter()
+                .map(|m| EventOutcome {
+                    market_id: m.id.clone(),
+                    condition_id: m.condition_id.clone(),
+                    label: m.group_item_title.clone().unwrap_or_else(|| m.question.clone()),
+                    question_index: match (&m.neg_risk_market_id, &m.question_id) {
+                        (Some(market_id), Some(question_id)) => neg_risk_question_index(market_id, question_id),
+                        _ => None,
+                    },
+                    yes_price: m.yes_price,
+                    no_price: m.no_price,
+                    yes_token_id: m.yes_token_id.clone(),
+                    no_token_id: m.no_token_id.clone(),
+                    liquidity: m.liquidity,
+                })
+                .collect();
+
+            // Markets don't carry the event title; callers with event data overwrite it
+            let mut event = NegRiskEvent::new(
+                event_id.to_string(),
+                first.neg_risk_market_id.clone(),
+                first.slug.clone(),
+                first.slug.clone(),
+                end_date,
+                outcomes,
+            );
+            event.taxonomy = first.taxonomy.clone();
+            event
+        })
+        .collect();
+
+    // Most mispriced first
+    events.sort_by(|a, b| b.price_deviation().abs().cmp(&a.price_deviation().abs()));
+    events
+}
+
+/// Convert Gamma tags, dropping ones without a slug or label
+pub(crate) fn market_tags(tags: Option<Vec<GammaTag>>) -> Vec<MarketTag> {
+    tags.unwrap_or_default()
+        .into_iter()
+        .filter_map(|t| {
+            let slug = t.slug.or_else(|| t.label.as_ref().map(|l| l.to_lowercase()))?;
+            Some(MarketTag {
+                id: t.id.map(json_id),
+                label: t.label.unwrap_or_else(|| slug.clone()),
+                slug,
+            })
+        })
+        .collect()
+}
+
+fn event_taxonomy(tags: Option<Vec<GammaTag>>, series: Option<Vec<GammaSeries>>) -> EventTaxonomy {
+    EventTaxonomy {
+        tags: market_tags(tags),
+        series: series
+            .and_then(|s| s.into_iter().next())
+            .and_then(|s| {
+                Some(MarketSeries {
+                    id: s.id.map(json_id)?,
+                    slug: s.slug,
+                    title: s.title,
+                })
+            }),
+    }
+}
+
+/// Gamma IDs come back as strings or numbers depending on the endpoint
+fn json_id(id: serde_json::Value) -> String {
+    match id {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Parse a Gamma RFC 3339 timestamp
+fn parse_timestamp(s: Option<&str>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|d| d.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -740,4 +1278,54 @@ mod tests {
         let result = scanner.parse_resolution_date_from_description(desc);
         assert!(result.is_none(), "Past dates should return None");
     }
+
+    fn outcome(event_id: &str, id: &str, yes: &str) -> TrackedMarket {
+        let yes = Decimal::from_str(yes).unwrap();
+        TrackedMarket {
+            id: id.to_string(),
+            condition_id: format!("0x{}", id),
+            question: format!("Will {} win?", id),
+            slug: "event".to_string(),
+            resolution_source: None,
+            description: None,
+            end_date: None,
+            yes_price: yes,
+            no_price: Decimal::ONE - yes,
+            volume: Decimal::ZERO,
+            liquidity: Decimal::ZERO,
+            category: None,
+            active: true,
+            closed: false,
+            yes_token_id: None,
+            no_token_id: None,
+            hours_until_close: None,
+            neg_risk: true,
+            event_id: Some(event_id.to_string()),
+            neg_risk_market_id: Some(format!("0x{}", event_id)),
+            group_item_title: Some(id.to_string()),
+            question_id: None,
+            taxonomy: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_group_neg_risk_events() {
+        let markets = vec![
+            outcome("e1", "a", "0.50"),
+            outcome("e1", "b", "0.30"),
+            outcome("e1", "c", "0.15"),
+            // Single-outcome group is dropped
+            outcome("e2", "d", "0.40"),
+        ];
+
+        let events = group_neg_risk_events(&markets);
+        assert_eq!(events.len(), 1);
+
+        let event = &events[0];
+        assert_eq!(event.outcomes.len(), 3);
+        assert_eq!(event.yes_price_sum, Decimal::from_str("0.95").unwrap());
+        assert_eq!(event.price_deviation(), Decimal::from_str("-0.05").unwrap());
+        assert!(event.is_mispriced(Decimal::from_str("0.02").unwrap()));
+        assert!(!event.is_mispriced(Decimal::from_str("0.05").unwrap()));
+    }
 }
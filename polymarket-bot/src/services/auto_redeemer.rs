@@ -0,0 +1,597 @@
+//! Auto Redeemer - redeems resolved winning tokens for every strategy
+//!
+//! Wallet-level service that listens to ResolutionTracker outcomes and sweeps
+//! periodically (to catch anything resolved while the bot was offline). For each
+//! wallet with a loaded key it:
+//! - Groups candidate positions by condition and reads each condition's payout once
+//! - Checks the Safe's balance of each token once (sold or losing positions have
+//!   nothing to redeem); positions sharing a token split it by their shares
+//! - Redeems every winning balance in one MultiSend relay transaction, one call
+//!   per condition
+//! - Once the relay job is mined, records the USDC proceeds and closes the
+//!   position at the realized payout
+//!
+//! Mint Maker pairs are not positions; the Mint Maker runner redeems those.
+
+use super::auto_trader::{AutoTradeLog, KeyStore};
+use super::safe_activation::BuilderCredentials;
+use super::{CtfCall, CtfService, OnchainJobTracker, WalletSigner};
+use crate::db::Database;
+use crate::types::{OnchainJobStatus, Position, PositionStatus, ResolutionOutcome, Side};
+use anyhow::Result;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, info, warn};
+
+/// How often every loaded wallet is swept for redeemable positions
+const SWEEP_INTERVAL: Duration = Duration::from_secs(300);
+/// Max redeem calls bundled into one relay transaction per wallet per pass
+const REDEEM_BATCH_CAP: usize = 10;
+/// Max conditions checked on-chain per wallet per pass; the rest wait for the next
+const CONDITION_CHECK_CAP: usize = 25;
+/// Max Gamma lookups for unknown condition IDs per wallet per pass
+const GAMMA_LOOKUP_CAP: usize = 25;
+
+/// Gamma market lookup (only the condition ID is needed)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GammaMarketRef {
+    condition_id: Option<String>,
+}
+
+/// A submitted redeem batch, recorded once its job is mined
+struct RedeemBatch {
+    wallet_address: String,
+    conditions: Vec<String>,
+    /// Position, payout per share and shares redeemed
+    positions: Vec<(Position, Decimal, Decimal)>,
+}
+
+/// What to do with the candidate positions of one resolved condition
+#[derive(Debug, Default)]
+struct ConditionPlan {
+    /// Positions with nothing to redeem (lost, or tokens already gone) and their payout
+    settle: Vec<(Position, Decimal)>,
+    /// Winning positions: payout per share and the shares each redeems
+    redeem: Vec<(Position, Decimal, Decimal)>,
+    /// Winning token balances to redeem
+    yes_amount: Decimal,
+    no_amount: Decimal,
+}
+
+/// Auto Redeemer service
+pub struct AutoRedeemer {
+    db: Arc<Database>,
+    key_store: KeyStore,
+    onchain_jobs: Arc<OnchainJobTracker>,
+    ctf: CtfService,
+    builder_creds: Option<BuilderCredentials>,
+    client: reqwest::Client,
+    /// Condition IDs reported by the ResolutionTracker, per position
+    condition_hints: HashMap<i64, String>,
+    /// Condition IDs looked up from Gamma, per market ID
+    condition_cache: HashMap<String, String>,
+    /// Submitted redeem batches by on-chain job ID
+    batches: HashMap<i64, RedeemBatch>,
+}
+
+impl AutoRedeemer {
+    pub fn new(
+        db: Arc<Database>,
+        key_store: KeyStore,
+        onchain_jobs: Arc<OnchainJobTracker>,
+        builder_creds: Option<BuilderCredentials>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            db,
+            key_store,
+            ctf: CtfService::new(onchain_jobs.clone()),
+            onchain_jobs,
+            builder_creds,
+            client,
+            condition_hints: HashMap::new(),
+            condition_cache: HashMap::new(),
+            batches: HashMap::new(),
+        }
+    }
+
+    /// Run the redeemer, reacting to resolutions and sweeping on an interval until `stop` is set
+    pub async fn run(mut self, mut outcome_rx: broadcast::Receiver<ResolutionOutcome>, mut stop: watch::Receiver<bool>) {
+        info!("Auto redeemer started (sweep interval: {:?})", SWEEP_INTERVAL);
+        let mut sweep = tokio::time::interval(SWEEP_INTERVAL);
+        let mut job_updates = self.onchain_jobs.subscribe();
+        let mut jobs_open = true;
+
+        loop {
+            tokio::select! {
+                _ = stop.changed() => break,
+                _ = sweep.tick() => {
+                    self.settle_batches().await;
+                    for wallet_address in self.key_store.wallets().await {
+                        if *stop.borrow() {
+                            break;
+                        }
+                        if let Err(e) = self.redeem_wallet(&wallet_address).await {
+                            warn!("[Auto Redeemer] Sweep failed for {}: {}", wallet_address, e);
+                        }
+                    }
+                }
+                result = outcome_rx.recv() => {
+                    match result {
+                        Ok(outcome) => {
+                            if let Some(condition_id) = &outcome.condition_id {
+                                self.condition_hints.insert(outcome.position_id, condition_id.clone());
+                            }
+                            if !outcome.won {
+                                continue; // Losing positions are settled by the next sweep
+                            }
+                            if let Err(e) = self.redeem_wallet(&outcome.wallet_address).await {
+                                warn!(
+                                    "[Auto Redeemer] Redeem after resolution of position {} failed: {}",
+                                    outcome.position_id, e
+                                );
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            debug!("Auto redeemer lagged {} resolution outcomes", n);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            info!("Resolution channel closed, shutting down auto redeemer");
+                            break;
+                        }
+                    }
+                }
+                update = job_updates.recv(), if jobs_open => match update {
+                    Ok(job) if !job.status.is_terminal() => {}
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => self.settle_batches().await,
+                    Err(broadcast::error::RecvError::Closed) => jobs_open = false,
+                },
+            }
+        }
+    }
+
+    /// Redeem every resolved winning position a wallet still holds tokens for
+    async fn redeem_wallet(&mut self, wallet_address: &str) -> Result<()> {
+        let Some(creds) = self.builder_creds.clone() else {
+            debug!("[Auto Redeemer] Builder credentials not configured, skipping redeem");
+            return Ok(());
+        };
+        let Some(signer) = self.key_store.signer(wallet_address).await else {
+            debug!("[Auto Redeemer] No unlocked key for {}, skipping redeem", wallet_address);
+            return Ok(());
+        };
+
+        let positions = self.db.positions().get_redeem_candidates(wallet_address).await?;
+        if positions.is_empty() {
+            return Ok(());
+        }
+
+        let mut gamma_budget = GAMMA_LOOKUP_CAP;
+        let mut with_conditions = Vec::with_capacity(positions.len());
+        for pos in positions {
+            match self.condition_id_for(&pos, &mut gamma_budget).await {
+                Some(condition_id) => with_conditions.push((condition_id, pos)),
+                None => debug!("[Auto Redeemer] No condition ID for position {}, skipping", pos.id),
+            }
+        }
+        // Conditions in a batch that hasn't settled yet wait for it
+        let in_flight: HashSet<&str> = self
+            .batches
+            .values()
+            .flat_map(|b| b.conditions.iter().map(String::as_str))
+            .collect();
+        let groups: Vec<(String, Vec<Position>)> = group_by_condition(with_conditions)
+            .into_iter()
+            .filter(|(condition_id, _)| !in_flight.contains(condition_id.as_str()))
+            .take(CONDITION_CHECK_CAP)
+            .collect();
+
+        let mut calls: Vec<CtfCall> = Vec::new();
+        let mut batch = RedeemBatch {
+            wallet_address: wallet_address.to_string(),
+            conditions: Vec::new(),
+            positions: Vec::new(),
+        };
+        for (condition_id, positions) in &groups {
+            if calls.len() >= REDEEM_BATCH_CAP {
+                break;
+            }
+            // One bad condition doesn't hold up the rest of the wallet
+            match self.plan_redeem(&signer, condition_id, positions).await {
+                Ok(Some(plan)) => {
+                    if let Some(call) = self.apply_plan(condition_id, positions, plan, &mut batch).await {
+                        calls.push(call);
+                    }
+                }
+                Ok(None) => {} // Condition not reported on-chain yet
+                Err(e) => warn!("[Auto Redeemer] Skipping condition {} for {}: {}", condition_id, wallet_address, e),
+            }
+        }
+
+        if calls.is_empty() {
+            return Ok(());
+        }
+
+        match self
+            .ctf
+            .submit_calls(&calls, &signer, &creds.api_key, &creds.secret, &creds.passphrase)
+            .await
+        {
+            Ok(job_id) => {
+                self.batches.insert(job_id, batch);
+            }
+            Err(e) => warn!(
+                "[Auto Redeemer] Redeem batch of {} positions failed for {}: {:#}",
+                batch.positions.len(),
+                wallet_address,
+                e
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Read a condition's payouts and the Safe's balance of each token its
+    /// positions hold; None if the condition isn't reported on-chain yet
+    async fn plan_redeem(
+        &self,
+        signer: &WalletSigner,
+        condition_id: &str,
+        positions: &[Position],
+    ) -> Result<Option<ConditionPlan>> {
+        let Some(payouts) = self.ctf.get_payouts(condition_id).await? else {
+            return Ok(None);
+        };
+
+        let mut balances: HashMap<String, Decimal> = HashMap::new();
+        for token_id in positions.iter().filter_map(|p| p.token_id.as_deref()) {
+            if !balances.contains_key(token_id) {
+                let balance = self.ctf.get_token_balance(signer, token_id).await?;
+                balances.insert(token_id.to_string(), balance);
+            }
+        }
+
+        Ok(Some(plan_condition(positions, payouts, &balances)))
+    }
+
+    /// Settle the positions with nothing to redeem and add the rest to `batch`,
+    /// returning the condition's redeem call if there is anything to redeem
+    async fn apply_plan(
+        &self,
+        condition_id: &str,
+        positions: &[Position],
+        plan: ConditionPlan,
+        batch: &mut RedeemBatch,
+    ) -> Option<CtfCall> {
+        for (pos, payout) in &plan.settle {
+            if let Err(e) = self.settle_position(pos, *payout).await {
+                warn!("[Auto Redeemer] Failed to settle position {}: {}", pos.id, e);
+            }
+        }
+        if plan.redeem.is_empty() {
+            return None;
+        }
+
+        // The NegRisk Adapter redeems explicit amounts, the CTF whole index sets;
+        // either way one call per condition covers every position on it
+        let call = if positions.iter().any(|p| p.neg_risk) {
+            CtfService::redeem_neg_risk_call(condition_id, plan.yes_amount, plan.no_amount)
+        } else {
+            CtfService::redeem_call(condition_id, &[1, 2], false)
+        };
+        match call {
+            Ok(call) => {
+                for (pos, payout, shares) in &plan.redeem {
+                    info!(
+                        "[Auto Redeemer] Redeeming {} shares at {} for position {} ({})",
+                        shares, payout, pos.id, pos.question
+                    );
+                }
+                batch.conditions.push(condition_id.to_string());
+                batch.positions.extend(plan.redeem);
+                Some(call)
+            }
+            Err(e) => {
+                warn!("[Auto Redeemer] Bad condition {}: {}", condition_id, e);
+                None
+            }
+        }
+    }
+
+    /// Close a position with nothing left to redeem at its payout
+    async fn settle_position(&self, pos: &Position, payout: Decimal) -> Result<()> {
+        if matches!(pos.status, PositionStatus::Open | PositionStatus::PendingResolution) {
+            self.db.positions().close_position(pos.id, payout, None).await?;
+        }
+        self.db.positions().mark_position_redeemed(pos.id, None).await
+    }
+
+    /// Record every submitted batch whose job has reached a final state
+    async fn settle_batches(&mut self) {
+        let job_ids: Vec<i64> = self.batches.keys().copied().collect();
+        for job_id in job_ids {
+            let job = match self.onchain_jobs.outcome(job_id).await {
+                Ok(Some(job)) => job,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!("[Auto Redeemer] Job #{} lookup failed: {}", job_id, e);
+                    continue;
+                }
+            };
+            let Some(batch) = self.batches.remove(&job_id) else {
+                continue;
+            };
+
+            if job.status != OnchainJobStatus::Mined {
+                // The positions are picked up again by the next sweep
+                warn!(
+                    "[Auto Redeemer] Redeem batch of {} failed for {}: {}",
+                    batch.positions.len(),
+                    batch.wallet_address,
+                    job.error.unwrap_or_else(|| "unknown error".to_string())
+                );
+                continue;
+            }
+            let tx_id = job.tx_hash.unwrap_or_else(|| format!("job #{}", job.id));
+            if let Err(e) = self.record_batch(&batch, &tx_id).await {
+                warn!("[Auto Redeemer] Failed to record redeem batch {}: {}", tx_id, e);
+            }
+        }
+    }
+
+    async fn record_batch(&self, batch: &RedeemBatch, tx_id: &str) -> Result<()> {
+        let wallet_address = batch.wallet_address.as_str();
+        for (pos, payout, shares) in &batch.positions {
+            let (payout, shares) = (*payout, *shares);
+            let proceeds = shares * payout;
+            self.db.positions().record_position_redemption(pos.id, payout, proceeds, tx_id).await?;
+            info!(
+                "[Auto Redeemer] Position {} redeemed for ${} (tx: {})",
+                pos.id, proceeds, tx_id
+            );
+
+            let log = AutoTradeLog {
+                id: None,
+                wallet_address: wallet_address.to_string(),
+                position_id: Some(pos.id),
+                action: "auto_redeem".to_string(),
+                market_question: Some(pos.question.clone()),
+                side: Some(format!("{:?}", pos.side)),
+                entry_price: Some(pos.entry_price),
+                exit_price: Some(payout),
+                size: Some(shares),
+                pnl: Some(proceeds - pos.size),
+                trigger_reason: Some(format!("Market resolved, redeemed ${} (tx: {})", proceeds, tx_id)),
+                created_at: Utc::now(),
+            };
+            self.db.auto_trading().log_auto_trade(&log).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Condition ID for a position: from the resolution outcome, the market ID
+    /// itself when it already is one, or a Gamma lookup while `gamma_budget` lasts
+    async fn condition_id_for(&mut self, pos: &Position, gamma_budget: &mut usize) -> Option<String> {
+        if let Some(condition_id) = self.condition_hints.get(&pos.id) {
+            return Some(condition_id.clone());
+        }
+        if is_condition_id(&pos.market_id) {
+            return Some(pos.market_id.clone());
+        }
+        if let Some(condition_id) = self.condition_cache.get(&pos.market_id) {
+            return Some(condition_id.clone());
+        }
+        if *gamma_budget == 0 {
+            return None;
+        }
+        *gamma_budget -= 1;
+
+        let mut urls = vec![format!(
+            "https://gamma-api.polymarket.com/markets?id={}",
+            pos.market_id
+        )];
+        if let Some(slug) = &pos.slug {
+            urls.push(format!("https://gamma-api.polymarket.com/markets?slug={}", slug));
+        }
+
+        for url in urls {
+            let Ok(resp) = self.client.get(&url).send().await else {
+                continue;
+            };
+            let Ok(markets) = resp.json::<Vec<GammaMarketRef>>().await else {
+                continue;
+            };
+            if let Some(condition_id) = markets.into_iter().find_map(|m| m.condition_id) {
+                self.condition_cache.insert(pos.market_id.clone(), condition_id.clone());
+                return Some(condition_id);
+            }
+        }
+
+        None
+    }
+}
+
+/// Group positions by condition, keeping the order conditions first appear in
+fn group_by_condition(positions: Vec<(String, Position)>) -> Vec<(String, Vec<Position>)> {
+    let mut groups: Vec<(String, Vec<Position>)> = Vec::new();
+    for (condition_id, pos) in positions {
+        match groups.iter_mut().find(|(c, _)| *c == condition_id) {
+            Some((_, group)) => group.push(pos),
+            None => groups.push((condition_id, vec![pos])),
+        }
+    }
+    groups
+}
+
+/// Shares a position still holds
+fn position_shares(pos: &Position) -> Decimal {
+    pos.remaining_size.unwrap_or_else(|| {
+        if pos.entry_price.is_zero() {
+            Decimal::ZERO
+        } else {
+            pos.size / pos.entry_price
+        }
+    })
+}
+
+/// Split a token balance across the positions holding it, in proportion to
+/// their shares and never more than each one holds
+fn split_balance(balance: Decimal, shares: &[Decimal]) -> Vec<Decimal> {
+    let total: Decimal = shares.iter().copied().sum();
+    if total.is_zero() {
+        return vec![Decimal::ZERO; shares.len()];
+    }
+    let held = balance.min(total);
+    shares.iter().map(|s| (*s * held / total).round_dp(6)).collect()
+}
+
+/// Decide what to redeem for one condition given its [YES, NO] payouts and
+/// the Safe's balance per token
+fn plan_condition(
+    positions: &[Position],
+    (yes_payout, no_payout): (Decimal, Decimal),
+    balances: &HashMap<String, Decimal>,
+) -> ConditionPlan {
+    let mut by_token: Vec<(&str, Vec<&Position>)> = Vec::new();
+    for pos in positions {
+        let token_id = pos.token_id.as_deref().unwrap_or_default();
+        match by_token.iter_mut().find(|(t, _)| *t == token_id) {
+            Some((_, group)) => group.push(pos),
+            None => by_token.push((token_id, vec![pos])),
+        }
+    }
+
+    let mut plan = ConditionPlan::default();
+    for (token_id, group) in by_token {
+        let side = group[0].side;
+        let payout = match side {
+            Side::Yes => yes_payout,
+            Side::No => no_payout,
+        };
+        let balance = balances.get(token_id).copied().unwrap_or_default();
+        if payout.is_zero() || balance.is_zero() {
+            plan.settle.extend(group.into_iter().map(|p| (p.clone(), payout)));
+            continue;
+        }
+
+        match side {
+            Side::Yes => plan.yes_amount += balance,
+            Side::No => plan.no_amount += balance,
+        }
+        let shares: Vec<Decimal> = group.iter().map(|p| position_shares(p)).collect();
+        for (pos, redeemed) in group.into_iter().zip(split_balance(balance, &shares)) {
+            plan.redeem.push((pos.clone(), payout, redeemed));
+        }
+    }
+    plan
+}
+
+/// Whether a string is a 0x-prefixed 32-byte hex condition ID
+fn is_condition_id(s: &str) -> bool {
+    s.strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StrategyType;
+    use rust_decimal_macros::dec;
+
+    fn position(id: i64, side: Side, token_id: &str, shares: Decimal) -> Position {
+        Position {
+            id,
+            wallet_address: "0xabc".to_string(),
+            market_id: "m1".to_string(),
+            question: "Will it happen?".to_string(),
+            slug: None,
+            side,
+            entry_price: dec!(0.5),
+            size: shares * dec!(0.5),
+            strategy: StrategyType::ResolutionSniper,
+            opened_at: Utc::now(),
+            closed_at: None,
+            exit_price: None,
+            pnl: None,
+            status: PositionStatus::Resolved,
+            is_paper: false,
+            end_date: None,
+            token_id: Some(token_id.to_string()),
+            order_id: None,
+            remaining_size: Some(shares),
+            realized_pnl: None,
+            total_sold_size: None,
+            avg_exit_price: None,
+            neg_risk: true,
+            fee_paid: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_condition() {
+        let groups = group_by_condition(vec![
+            ("c1".to_string(), position(1, Side::Yes, "y1", dec!(10))),
+            ("c2".to_string(), position(2, Side::Yes, "y2", dec!(10))),
+            ("c1".to_string(), position(3, Side::No, "n1", dec!(10))),
+        ]);
+        let ids: Vec<(&str, Vec<i64>)> = groups
+            .iter()
+            .map(|(c, ps)| (c.as_str(), ps.iter().map(|p| p.id).collect()))
+            .collect();
+        assert_eq!(ids, vec![("c1", vec![1, 3]), ("c2", vec![2])]);
+    }
+
+    #[test]
+    fn test_plan_dedups_shared_token() {
+        // Two positions on the same YES token: the balance is redeemed once
+        let positions = [position(1, Side::Yes, "y", dec!(30)), position(2, Side::Yes, "y", dec!(10))];
+        let balances = HashMap::from([("y".to_string(), dec!(40))]);
+        let plan = plan_condition(&positions, (dec!(1), dec!(0)), &balances);
+
+        assert_eq!(plan.yes_amount, dec!(40));
+        assert_eq!(plan.no_amount, Decimal::ZERO);
+        let redeemed: Vec<(i64, Decimal)> = plan.redeem.iter().map(|(p, _, s)| (p.id, *s)).collect();
+        assert_eq!(redeemed, vec![(1, dec!(30)), (2, dec!(10))]);
+        assert!(plan.settle.is_empty());
+    }
+
+    #[test]
+    fn test_plan_settles_losing_and_empty_tokens() {
+        let positions = [position(1, Side::Yes, "y", dec!(10)), position(2, Side::No, "n", dec!(10))];
+        // YES lost; NO won but the tokens were already sold
+        let balances = HashMap::from([("y".to_string(), dec!(10)), ("n".to_string(), Decimal::ZERO)]);
+        let plan = plan_condition(&positions, (dec!(0), dec!(1)), &balances);
+
+        assert!(plan.redeem.is_empty());
+        let settled: Vec<(i64, Decimal)> = plan.settle.iter().map(|(p, payout)| (p.id, *payout)).collect();
+        assert_eq!(settled, vec![(1, dec!(0)), (2, dec!(1))]);
+    }
+
+    #[test]
+    fn test_split_balance_by_shares() {
+        // Balance short of what the positions hold: split pro rata
+        assert_eq!(split_balance(dec!(20), &[dec!(30), dec!(10)]), vec![dec!(15), dec!(5)]);
+        // Extra tokens from elsewhere aren't credited to the positions
+        assert_eq!(split_balance(dec!(100), &[dec!(30), dec!(10)]), vec![dec!(30), dec!(10)]);
+        assert_eq!(split_balance(dec!(5), &[Decimal::ZERO]), vec![Decimal::ZERO]);
+    }
+
+    #[test]
+    fn test_is_condition_id() {
+        assert!(is_condition_id(&format!("0x{}", "ab".repeat(32))));
+        assert!(!is_condition_id("1273344"));
+        assert!(!is_condition_id(&format!("0x{}", "ab".repeat(31))));
+        assert!(!is_condition_id(&format!("0x{}", "zz".repeat(32))));
+    }
+}
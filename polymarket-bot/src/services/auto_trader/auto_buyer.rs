@@ -9,6 +9,9 @@
 use super::key_store::KeyStore;
 use super::types::AutoTradeLog;
 use crate::db::Database;
+use crate::services::clob_pool::{ClobAccount, ClobClientPool};
+use crate::services::rate_limiter::EndpointClass;
+use crate::services::signer::WalletSigner;
 use crate::types::Opportunity;
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -16,17 +19,12 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, RwLock};
 use tracing::{debug, info, warn};
 
 use alloy::primitives::U256;
-use alloy::signers::{local::PrivateKeySigner, Signer};
-use polymarket_client_sdk::clob::{Client as ClobClient, Config as ClobConfig};
 use polymarket_client_sdk::clob::types::{Amount, OrderType, Side as ClobSide};
 
-const POLYGON_CHAIN_ID: u64 = 137;
-const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
-
 /// USDC.e (bridged) contract address on Polygon - used by Polymarket
 const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
 
@@ -37,6 +35,7 @@ const MIN_TRADE_BALANCE: &str = "1.00";
 pub struct AutoBuyer {
     db: Arc<Database>,
     key_store: KeyStore,
+    clob_pool: Arc<ClobClientPool>,
     /// Shared list of current opportunities
     #[allow(dead_code)]
     opportunities: Arc<RwLock<Vec<Opportunity>>>,
@@ -50,19 +49,28 @@ impl AutoBuyer {
     pub fn new(
         db: Arc<Database>,
         key_store: KeyStore,
+        clob_pool: Arc<ClobClientPool>,
         opportunities: Arc<RwLock<Vec<Opportunity>>>,
         polygon_rpc_url: String,
         slippage_tolerance: f64,
     ) -> Self {
-        Self { db, key_store, opportunities, polygon_rpc_url, slippage_tolerance }
+        Self { db, key_store, clob_pool, opportunities, polygon_rpc_url, slippage_tolerance }
     }
 
-    /// Run the auto-buyer, listening for opportunity updates
-    pub async fn run(&self, mut opportunity_rx: broadcast::Receiver<Vec<Opportunity>>) {
+    /// Run the auto-buyer, listening for opportunity updates until `stop` is set
+    pub async fn run(
+        &self,
+        mut opportunity_rx: broadcast::Receiver<Vec<Opportunity>>,
+        mut stop: watch::Receiver<bool>,
+    ) {
         info!("Auto-buyer started");
 
         loop {
-            match opportunity_rx.recv().await {
+            let received = tokio::select! {
+                received = opportunity_rx.recv() => received,
+                _ = stop.changed() => break,
+            };
+            match received {
                 Ok(opportunities) => {
                     if let Err(e) = self.process_opportunities(&opportunities).await {
                         warn!("Error processing opportunities: {}", e);
@@ -82,7 +90,7 @@ impl AutoBuyer {
     /// Process new opportunities and execute auto-buys where appropriate
     async fn process_opportunities(&self, opportunities: &[Opportunity]) -> Result<()> {
         // Get all wallets with auto-buy enabled
-        let wallets = self.db.get_auto_buy_enabled_wallets().await?;
+        let wallets = self.db.auto_trading().get_auto_buy_enabled_wallets().await?;
 
         if wallets.is_empty() {
             return Ok(());
@@ -103,14 +111,14 @@ impl AutoBuyer {
         wallet_address: &str,
         opportunities: &[Opportunity],
     ) -> Result<()> {
-        let settings = self.db.get_auto_trading_settings(wallet_address).await?;
+        let settings = self.db.auto_trading().get_auto_trading_settings(wallet_address).await?;
 
         if !settings.enabled || !settings.auto_buy_enabled {
             return Ok(());
         }
 
         // Check position limits
-        let open_count = self.db.count_open_positions(wallet_address).await?;
+        let open_count = self.db.positions().count_open_positions(wallet_address).await?;
         if open_count >= settings.max_positions {
             debug!(
                 "Wallet {} at max positions ({}/{})",
@@ -120,7 +128,7 @@ impl AutoBuyer {
         }
 
         // Check exposure limits
-        let current_exposure = self.db.get_total_exposure(wallet_address).await?;
+        let current_exposure = self.db.positions().get_total_exposure(wallet_address).await?;
         let max_exposure = settings.max_total_exposure;
         if current_exposure >= max_exposure {
             debug!(
@@ -131,7 +139,7 @@ impl AutoBuyer {
         }
 
         // Check daily loss limit
-        let daily_pnl = self.db.get_daily_auto_pnl(wallet_address).await?;
+        let daily_pnl = self.db.auto_trading().get_daily_auto_pnl(wallet_address).await?;
         if daily_pnl <= -settings.max_daily_loss {
             debug!(
                 "Wallet {} hit daily loss limit (${} vs -${})",
@@ -163,7 +171,7 @@ impl AutoBuyer {
         // Find matching opportunities
         for opp in opportunities {
             // Check if already has position in this market
-            if self.db.has_open_position(wallet_address, &opp.market_id).await? {
+            if self.db.positions().has_open_position(wallet_address, &opp.market_id).await? {
                 continue;
             }
 
@@ -173,12 +181,18 @@ impl AutoBuyer {
                 crate::types::StrategyType::Dispute => false, // Handled by DisputeSniper
                 crate::types::StrategyType::MillionairesClub => false, // Handled by McScanner
                 crate::types::StrategyType::MintMaker => false, // Handled by MintMaker service
+                crate::types::StrategyType::NegRiskArb => false, // Handled by NegRiskArbExecutor
             };
 
             if !strategy_enabled {
                 continue;
             }
 
+            // Check market category/tag filter
+            if !settings.market_filter.allows(&opp.taxonomy) {
+                continue;
+            }
+
             // Check minimum edge
             if opp.edge < settings.min_edge as f64 {
                 continue;
@@ -209,8 +223,8 @@ impl AutoBuyer {
                 None => continue, // Can't trade without token_id
             };
 
-            // Get the decrypted key from the key store
-            let private_key = match self.key_store.get_key(wallet_address).await {
+            // Get the wallet's signer from the key store
+            let signer = match self.key_store.signer(wallet_address).await {
                 Some(k) => k,
                 None => {
                     debug!("No key in KeyStore for wallet {}", wallet_address);
@@ -228,7 +242,7 @@ impl AutoBuyer {
             );
 
             // Execute live buy order with slippage protection
-            let order_id = match self.execute_buy(&private_key, &token_id, position_size, opp.entry_price, self.slippage_tolerance).await {
+            let order_id = match self.execute_buy(&signer, &token_id, position_size, opp.entry_price, self.slippage_tolerance).await {
                 Ok(id) => Some(id),
                 Err(e) => {
                     warn!("[Auto-Buy] Failed to execute buy: {}", e);
@@ -239,7 +253,7 @@ impl AutoBuyer {
             // Create the position
             let position_id = self
                 .db
-                .create_position_for_wallet(
+                .positions().create_position_for_wallet(
                     wallet_address,
                     &opp.market_id,
                     &opp.question,
@@ -275,7 +289,7 @@ impl AutoBuyer {
                 )),
                 created_at: Utc::now(),
             };
-            self.db.log_auto_trade(&log).await?;
+            self.db.auto_trading().log_auto_trade(&log).await?;
 
             info!(
                 "[Auto-Buy] Created position {} for ${} on {}",
@@ -338,21 +352,7 @@ impl AutoBuyer {
     }
 
     /// Execute a buy order via CLOB API with slippage protection
-    async fn execute_buy(&self, private_key: &str, token_id: &str, size: Decimal, entry_price: Decimal, slippage: f64) -> Result<String> {
-        // Create signer from private key
-        let signer: PrivateKeySigner = private_key.parse()
-            .context("Failed to parse private key")?;
-        let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-        // Create CLOB client and authenticate
-        let clob_config = ClobConfig::builder().use_server_time(true).build();
-        let client = ClobClient::new(CLOB_ENDPOINT, clob_config)
-            .context("Failed to create CLOB client")?
-            .authentication_builder(&signer)
-            .authenticate()
-            .await
-            .context("Failed to authenticate with CLOB")?;
-
+    async fn execute_buy(&self, signer: &WalletSigner, token_id: &str, size: Decimal, entry_price: Decimal, slippage: f64) -> Result<String> {
         // Convert token_id to U256
         let token_id_u256 = U256::from_str_radix(token_id, 10)
             .context("Failed to parse token ID")?;
@@ -361,30 +361,35 @@ impl AutoBuyer {
         let slippage_dec = Decimal::try_from(slippage).unwrap_or(Decimal::new(5, 3));
         let worst_case_price = entry_price * (Decimal::ONE + slippage_dec);
 
-        // Create buy order with slippage protection
-        let order = client
-            .market_order()
-            .token_id(token_id_u256)
-            .amount(Amount::usdc(size).context("Failed to create USDC amount")?)
-            .side(ClobSide::Buy)
-            .price(worst_case_price)
-            .order_type(OrderType::FOK)
-            .build()
-            .await
-            .context("Failed to build order")?;
-
-        // Sign and submit
-        let signed_order = client
-            .sign(&signer, order)
-            .await
-            .context("Failed to sign order")?;
-
-        let response = client
-            .post_order(signed_order)
-            .await
-            .context("Failed to submit order")?;
-
-        let order_id = format!("{:?}", response);
+        let response = self
+            .clob_pool
+            .run(signer, ClobAccount::Eoa, EndpointClass::PostOrder, |c| async move {
+                // Create buy order with slippage protection
+                let order = c.client
+                    .market_order()
+                    .token_id(token_id_u256)
+                    .amount(Amount::usdc(size).context("Failed to create USDC amount")?)
+                    .side(ClobSide::Buy)
+                    .price(worst_case_price)
+                    .order_type(OrderType::FOK)
+                    .build()
+                    .await
+                    .context("Failed to build order")?;
+
+                // Sign and submit
+                let signed_order = c.client
+                    .sign(&c.signer, order)
+                    .await
+                    .context("Failed to sign order")?;
+
+                c.client
+                    .post_order(signed_order)
+                    .await
+                    .context("Failed to submit order")
+            })
+            .await?;
+
+        let order_id = response.order_id;
         info!("[Auto-Buy] Order submitted: {}", order_id);
 
         Ok(order_id)
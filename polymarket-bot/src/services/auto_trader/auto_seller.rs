@@ -1,42 +1,105 @@
 //! Auto-Seller - executes sell orders from position monitor triggers
 //!
-//! Receives sell signals and places market sell orders via CLOB API
+//! Receives sell signals and places market sell orders via CLOB API.
+//! A position is only closed for the quantity that actually filled, at the
+//! average fill price:
+//! - FOK market sell with a price floor, confirmed via user WS `OrderEvent`s
+//!   or `check_order_status` polling
+//! - On FOK rejection, retry with progressively wider slippage
+//! - If every FOK attempt is rejected, fall back to a GTC limit sell at the
+//!   widest floor and cancel whatever is still resting after a timeout
 
 use super::key_store::KeyStore;
 use super::position_monitor::SellSignal;
 use super::types::AutoTradeLog;
 use crate::db::Database;
+use crate::services::clob_errors::ClobError;
+use crate::services::clob_pool::{ClobAccount, ClobClientPool};
+use crate::services::mint_maker::order_manager::{check_order_status, FillStatus};
+use crate::services::rate_limiter::EndpointClass;
+use crate::services::signer::WalletSigner;
+use crate::services::user_ws::OrderEvent;
 use anyhow::{Context, Result};
 use chrono::Utc;
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tracing::{error, info, warn};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::{interval_at, Duration, Instant, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
 
 use alloy::primitives::U256;
-use alloy::signers::{local::PrivateKeySigner, Signer};
-use polymarket_client_sdk::clob::{Client as ClobClient, Config as ClobConfig};
 use polymarket_client_sdk::clob::types::{Amount, OrderType, Side as ClobSide};
 
-const POLYGON_CHAIN_ID: u64 = 137;
-const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
+/// Slippage below the signal price for each FOK attempt (widest last)
+const FOK_SLIPPAGE_STEPS: [Decimal; 3] = [dec!(0.005), dec!(0.02), dec!(0.05)];
+/// How long to wait for a FOK fill to be confirmed
+const FOK_CONFIRM_TIMEOUT: Duration = Duration::from_secs(15);
+/// How long a fallback limit sell may rest before the remainder is cancelled
+const LIMIT_FALLBACK_TIMEOUT: Duration = Duration::from_secs(60);
+/// Interval between `check_order_status` polls while waiting for a fill
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Lowest price the CLOB accepts
+const MIN_PRICE: Decimal = dec!(0.01);
+
+/// Shares that actually filled for a sell, and at what average price
+#[derive(Debug, Clone, PartialEq)]
+pub struct SellFill {
+    pub order_id: String,
+    pub shares: Decimal,
+    pub avg_price: Decimal,
+}
+
+/// Accumulates trade events for one order into a VWAP
+#[derive(Debug, Default)]
+struct FillTracker {
+    shares: Decimal,
+    notional: Decimal,
+}
+
+impl FillTracker {
+    fn record(&mut self, size: Decimal, price: Decimal) {
+        self.shares += size;
+        self.notional += size * price;
+    }
+
+    fn avg_price(&self) -> Option<Decimal> {
+        (self.shares > Decimal::ZERO).then(|| self.notional / self.shares)
+    }
+}
 
 /// Auto-Seller service
 pub struct AutoSeller {
     db: Arc<Database>,
     key_store: KeyStore,
+    clob_pool: Arc<ClobClientPool>,
+    /// Order events from the user WebSocket (fills, cancellations)
+    order_events: broadcast::Sender<OrderEvent>,
 }
 
 impl AutoSeller {
-    pub fn new(db: Arc<Database>, key_store: KeyStore) -> Self {
-        Self { db, key_store }
+    pub fn new(
+        db: Arc<Database>,
+        key_store: KeyStore,
+        clob_pool: Arc<ClobClientPool>,
+        order_events: broadcast::Sender<OrderEvent>,
+    ) -> Self {
+        Self { db, key_store, clob_pool, order_events }
     }
 
-    /// Run the auto-seller, processing sell signals
-    pub async fn run(&self, mut signal_rx: mpsc::Receiver<SellSignal>) {
+    /// Run the auto-seller, processing sell signals until `stop` is set
+    pub async fn run(&self, mut signal_rx: mpsc::Receiver<SellSignal>, mut stop: watch::Receiver<bool>) {
         info!("Auto-seller started");
 
-        while let Some(signal) = signal_rx.recv().await {
+        loop {
+            let signal = tokio::select! {
+                signal = signal_rx.recv() => signal,
+                _ = stop.changed() => break,
+            };
+            let Some(signal) = signal else {
+                break;
+            };
             if let Err(e) = self.process_signal(&signal).await {
                 error!(
                     "Failed to process sell signal for position {}: {}",
@@ -60,124 +123,411 @@ impl AutoSeller {
         // Get the position to check if it's paper or live
         let position = self
             .db
-            .get_position_by_id_internal(signal.position_id)
+            .positions().get_position_by_id_internal(signal.position_id)
             .await?
             .context("Position not found")?;
 
-        // Calculate PnL
-        let pnl = (signal.current_price - position.entry_price) * signal.size;
-
         // Execute live sell
-        self.execute_sell(signal, &position, pnl).await?;
+        self.execute_sell(signal, &position).await?;
 
         Ok(())
     }
 
-    /// Execute sell order via CLOB API
-    async fn execute_sell(&self, signal: &SellSignal, position: &crate::types::Position, pnl: Decimal) -> Result<()> {
+    /// Execute sell order via CLOB API and close the filled quantity
+    async fn execute_sell(&self, signal: &SellSignal, position: &crate::types::Position) -> Result<()> {
+        // Get the wallet's signer from the key store
+        let Some(signer) = self.key_store.signer(&signal.wallet_address).await else {
+            warn!(
+                "[Auto-Sell] No key in KeyStore for wallet {}. Auto-trading may not be enabled.",
+                signal.wallet_address
+            );
+            return Ok(());
+        };
+
+        // Get token ID from position
+        let token_id = position.token_id.as_ref()
+            .context("Position missing token_id for sell")?;
+
+        // Sell what is still held (remaining_size is in shares)
+        let shares = position
+            .remaining_size
+            .unwrap_or(position.size / position.entry_price);
+        if shares <= Decimal::ZERO {
+            warn!("[Auto-Sell] Position {} has no shares left to sell", signal.position_id);
+            return Ok(());
+        }
+
         info!(
-            "[Auto-Sell] Executing sell for position {} at {} (PnL: ${:.2})",
-            signal.position_id, signal.current_price, pnl
+            "[Auto-Sell] Selling {} shares for position {} at ~{}",
+            shares, signal.position_id, signal.current_price
         );
 
-        // Get the decrypted key from the key store
-        let private_key = self.key_store.get_key(&signal.wallet_address).await;
+        let fill = self
+            .sell_with_escalation(&signer, &signal.wallet_address, token_id, shares, signal.current_price)
+            .await?;
 
-        match private_key {
-            Some(key) => {
-                // Get token ID from position
-                let token_id = position.token_id.as_ref()
-                    .context("Position missing token_id for sell")?;
+        let Some(fill) = fill else {
+            warn!(
+                "[Auto-Sell] Position {} not sold: no fill after FOK retries and limit fallback",
+                signal.position_id
+            );
+            let log = AutoTradeLog {
+                id: None,
+                wallet_address: signal.wallet_address.clone(),
+                position_id: Some(signal.position_id),
+                action: "sell_failed".to_string(),
+                market_question: Some(signal.market_question.clone()),
+                side: Some("Sell".to_string()),
+                entry_price: Some(position.entry_price),
+                exit_price: None,
+                size: Some(signal.size),
+                pnl: None,
+                trigger_reason: Some(format!("{} - no fill", signal.trigger.reason())),
+                created_at: Utc::now(),
+            };
+            self.db.auto_trading().log_auto_trade(&log).await?;
+            return Ok(());
+        };
 
-                info!(
-                    "[Auto-Sell] Submitting sell order to CLOB for position {}",
-                    signal.position_id
-                );
+        // Close only what filled, at the actual average fill price
+        let result = self
+            .db
+            .positions().partial_close_position_for_wallet(
+                &signal.wallet_address,
+                signal.position_id,
+                fill.shares.min(shares),
+                fill.avg_price,
+            )
+            .await?;
 
-                // Calculate shares from size and entry price
-                let shares = signal.size / position.entry_price;
+        let log = AutoTradeLog {
+            id: None,
+            wallet_address: signal.wallet_address.clone(),
+            position_id: Some(signal.position_id),
+            action: signal.trigger.action_name(),
+            market_question: Some(signal.market_question.clone()),
+            side: Some("Sell".to_string()),
+            entry_price: Some(position.entry_price),
+            exit_price: Some(fill.avg_price),
+            size: Some(fill.shares * position.entry_price),
+            pnl: Some(result.pnl_this_sell),
+            trigger_reason: Some(if result.is_fully_closed {
+                format!("{} (order {})", signal.trigger.reason(), fill.order_id)
+            } else {
+                format!(
+                    "{} (order {}, partial fill {}/{} shares)",
+                    signal.trigger.reason(), fill.order_id, fill.shares, shares
+                )
+            }),
+            created_at: Utc::now(),
+        };
+        self.db.auto_trading().log_auto_trade(&log).await?;
 
-                // Create signer from private key
-                let signer: PrivateKeySigner = key.parse()
-                    .context("Failed to parse private key")?;
-                let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
+        info!(
+            "[Auto-Sell] Position {} {}: {} {} shares at {} (PnL: ${:.2})",
+            signal.position_id,
+            if result.is_fully_closed { "closed" } else { "partially closed" },
+            signal.trigger.action_name(),
+            fill.shares,
+            fill.avg_price,
+            result.pnl_this_sell
+        );
 
-                // Create CLOB client and authenticate
-                let clob_config = ClobConfig::builder().use_server_time(true).build();
-                let client = ClobClient::new(CLOB_ENDPOINT, clob_config)
-                    .context("Failed to create CLOB client")?
-                    .authentication_builder(&signer)
-                    .authenticate()
-                    .await
-                    .context("Failed to authenticate with CLOB")?;
+        Ok(())
+    }
+
+    /// FOK sells with widening slippage, then a resting limit sell as last resort.
+    ///
+    /// Returns the confirmed fill, or None if nothing filled.
+    async fn sell_with_escalation(
+        &self,
+        signer: &WalletSigner,
+        wallet_address: &str,
+        token_id: &str,
+        shares: Decimal,
+        price: Decimal,
+    ) -> Result<Option<SellFill>> {
+        let token_id_u256 = U256::from_str_radix(token_id, 10)
+            .context("Failed to parse token ID")?;
+
+        for slippage in FOK_SLIPPAGE_STEPS {
+            let floor = sell_floor(price, slippage);
 
-                // Convert token_id to U256
-                let token_id_u256 = U256::from_str_radix(token_id, 10)
-                    .context("Failed to parse token ID")?;
+            // Subscribe before posting so no fill event is missed
+            let events = self.order_events.subscribe();
 
-                // Create sell order
-                let order = client
-                    .market_order()
+            let posted = self
+                .clob_pool
+                .run(signer, ClobAccount::Eoa, EndpointClass::PostOrder, |c| async move {
+                    let order = c.client
+                        .market_order()
+                        .token_id(token_id_u256)
+                        .amount(Amount::shares(shares).context("Failed to create shares amount")?)
+                        .side(ClobSide::Sell)
+                        .price(floor)
+                        .order_type(OrderType::FOK)
+                        .build()
+                        .await
+                        .context("Failed to build sell order")?;
+                    let signed_order = c.client
+                        .sign(&c.signer, order)
+                        .await
+                        .context("Failed to sign order")?;
+                    c.client
+                        .post_order(signed_order)
+                        .await
+                        .context("Failed to submit sell order")
+                })
+                .await;
+
+            let response = match posted {
+                Ok(r) if r.success => r,
+                Ok(r) => {
+                    let reason = r.error_msg.unwrap_or_else(|| "rejected".to_string());
+                    if !Self::should_escalate(&reason) {
+                        anyhow::bail!("FOK sell rejected: {}", reason);
+                    }
+                    warn!("[Auto-Sell] FOK sell at floor {} rejected: {}", floor, reason);
+                    continue;
+                }
+                Err(e) => {
+                    if !Self::should_escalate(&format!("{:#}", e)) {
+                        return Err(e);
+                    }
+                    warn!("[Auto-Sell] FOK sell at floor {} failed: {:#}", floor, e);
+                    continue;
+                }
+            };
+
+            info!("[Auto-Sell] FOK sell accepted: id={} floor={}", response.order_id, floor);
+
+            // For a sell, making = shares given up, taking = USDC received
+            let reported = fill_from_amounts(&response.order_id, response.making_amount, response.taking_amount);
+            let fill = self
+                .await_fill(wallet_address, &response.order_id, events, reported, FOK_CONFIRM_TIMEOUT)
+                .await;
+            if fill.is_some() {
+                return Ok(fill);
+            }
+            warn!("[Auto-Sell] FOK sell {} accepted but no fill confirmed", response.order_id);
+        }
+
+        // Limit fallback at the widest floor
+        let floor = sell_floor(price, FOK_SLIPPAGE_STEPS[FOK_SLIPPAGE_STEPS.len() - 1]);
+        info!("[Auto-Sell] FOK exhausted, resting GTC limit sell for {} shares at {}", shares, floor);
+
+        let events = self.order_events.subscribe();
+        let response = self
+            .clob_pool
+            .run(signer, ClobAccount::Eoa, EndpointClass::PostOrder, |c| async move {
+                let order = c.client
+                    .limit_order()
                     .token_id(token_id_u256)
-                    .amount(Amount::shares(shares).context("Failed to create shares amount")?)
+                    .size(shares)
                     .side(ClobSide::Sell)
-                    .order_type(OrderType::FOK)
+                    .price(floor)
+                    .order_type(OrderType::GTC)
                     .build()
                     .await
-                    .context("Failed to build sell order")?;
-
-                // Sign and submit
-                let signed_order = client
-                    .sign(&signer, order)
+                    .context("Failed to build limit sell order")?;
+                let signed_order = c.client
+                    .sign(&c.signer, order)
                     .await
                     .context("Failed to sign order")?;
-
-                let response = client
+                c.client
                     .post_order(signed_order)
                     .await
-                    .context("Failed to submit sell order")?;
-
-                let order_id = format!("{:?}", response);
-                info!("[Auto-Sell] Order submitted: {}", order_id);
-
-                // Close the position
-                self.db
-                    .close_position(signal.position_id, signal.current_price, Some(&order_id))
-                    .await?;
-
-                // Log the auto-trade
-                let log = AutoTradeLog {
-                    id: None,
-                    wallet_address: signal.wallet_address.clone(),
-                    position_id: Some(signal.position_id),
-                    action: signal.trigger.action_name(),
-                    market_question: Some(signal.market_question.clone()),
-                    side: Some("Sell".to_string()),
-                    entry_price: Some(position.entry_price),
-                    exit_price: Some(signal.current_price),
-                    size: Some(signal.size),
-                    pnl: Some(pnl),
-                    trigger_reason: Some(signal.trigger.reason()),
-                    created_at: Utc::now(),
-                };
-                self.db.log_auto_trade(&log).await?;
-
-                info!(
-                    "[Auto-Sell] Position {} closed: {} at {} (PnL: ${:.2})",
-                    signal.position_id,
-                    signal.trigger.action_name(),
-                    signal.current_price,
-                    pnl
-                );
+                    .context("Failed to submit limit sell order")
+            })
+            .await?;
+        if !response.success {
+            warn!(
+                "[Auto-Sell] Limit sell rejected: {}",
+                response.error_msg.unwrap_or_else(|| "rejected".to_string())
+            );
+            return Ok(None);
+        }
+
+        let reported = fill_from_amounts(&response.order_id, response.making_amount, response.taking_amount);
+        let fill = self
+            .await_fill(wallet_address, &response.order_id, events, reported, LIMIT_FALLBACK_TIMEOUT)
+            .await;
+
+        // Whatever did not fill in time should not keep resting
+        if fill.as_ref().map(|f| f.shares < shares).unwrap_or(true) {
+            let order_id = response.order_id.clone();
+            let cancelled = self
+                .clob_pool
+                .run(signer, ClobAccount::Eoa, EndpointClass::DeleteOrder, |c| {
+                    let order_id = order_id.clone();
+                    async move { c.client.cancel_order(&order_id).await.map_err(anyhow::Error::from) }
+                })
+                .await;
+            if let Err(e) = cancelled {
+                warn!("[Auto-Sell] Failed to cancel rest of limit sell {}: {}", response.order_id, e);
             }
-            None => {
-                warn!(
-                    "[Auto-Sell] No key in KeyStore for wallet {}. Auto-trading may not be enabled.",
-                    signal.wallet_address
-                );
+        }
+
+        Ok(fill)
+    }
+
+    /// Whether a rejected FOK is worth retrying at a wider price
+    fn should_escalate(reason: &str) -> bool {
+        let lower = reason.to_lowercase();
+        // FOK "couldn't be fully filled" means not enough liquidity above the floor
+        if lower.contains("fill") || lower.contains("liquidity") || lower.contains("match") {
+            return true;
+        }
+        // Anything unrecognised is not known to clear up at a lower price
+        ClobError::from_response(400, reason).is_retryable()
+    }
+
+    /// Wait for an order's fill via user WS events, falling back to status polling.
+    ///
+    /// `reported` is the fill from the post-order response; it is used when the
+    /// order is known to be matched but no trade event carried a price.
+    async fn await_fill(
+        &self,
+        wallet_address: &str,
+        order_id: &str,
+        mut events: broadcast::Receiver<OrderEvent>,
+        reported: Option<SellFill>,
+        timeout: Duration,
+    ) -> Option<SellFill> {
+        let creds = self.db.auth().get_api_credentials(wallet_address).await.ok().flatten();
+        let deadline = Instant::now() + timeout;
+        let mut trades = FillTracker::default();
+        let mut matched: Option<Decimal> = None;
+        let mut limit_price: Option<Decimal> = None;
+
+        let resolve = |trades: &FillTracker, matched: Option<Decimal>, limit_price: Option<Decimal>| {
+            let shares = matched.unwrap_or(trades.shares).max(trades.shares);
+            if shares <= Decimal::ZERO {
+                return None;
+            }
+            let avg_price = trades
+                .avg_price()
+                .or_else(|| reported.as_ref().map(|r| r.avg_price))
+                .or(limit_price)?;
+            Some(SellFill { order_id: order_id.to_string(), shares, avg_price })
+        };
+
+        // One interval for the whole wait, so unrelated order events don't keep
+        // pushing the next status poll back
+        let mut poll = interval_at(Instant::now() + STATUS_POLL_INTERVAL, STATUS_POLL_INTERVAL);
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut events_open = true;
+
+        while Instant::now() < deadline {
+            tokio::select! {
+                event = events.recv(), if events_open => {
+                    let event = match event {
+                        Ok(e) => e,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            debug!("[Auto-Sell] Order events lagged {} messages", n);
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            // The user WS is gone for good; only polling can settle the order now
+                            events_open = false;
+                            continue;
+                        }
+                    };
+                    if event.order_id != order_id {
+                        continue;
+                    }
+                    if event.event_type.eq_ignore_ascii_case("trade") {
+                        let size = event.fill_size.as_deref().and_then(|s| Decimal::from_str(s).ok());
+                        let price = event.fill_price.as_deref().and_then(|s| Decimal::from_str(s).ok());
+                        if let (Some(size), Some(price)) = (size, price) {
+                            trades.record(size, price);
+                        }
+                    }
+                    match event.status.to_uppercase().as_str() {
+                        "MATCHED" | "MINED" | "CONFIRMED" if trades.shares > Decimal::ZERO => {
+                            return resolve(&trades, matched, limit_price);
+                        }
+                        "CANCELLED" | "CANCELED" | "FAILED" => {
+                            return resolve(&trades, matched, limit_price);
+                        }
+                        _ => {}
+                    }
+                }
+                _ = poll.tick() => {
+                    let Some((key, secret, passphrase)) = creds.as_ref() else {
+                        continue;
+                    };
+                    match check_order_status(wallet_address, order_id, key, secret, passphrase).await {
+                        Ok(status) => {
+                            matched = Decimal::from_str(&status.size_matched).ok();
+                            limit_price = status.fill_price.as_deref().and_then(|p| Decimal::from_str(p).ok());
+                            match status.fill_status {
+                                FillStatus::Filled | FillStatus::Cancelled => {
+                                    return resolve(&trades, matched, limit_price);
+                                }
+                                _ => {}
+                            }
+                        }
+                        Err(e) => debug!("[Auto-Sell] Status check for {} failed: {}", order_id, e),
+                    }
+                }
             }
         }
 
-        Ok(())
+        // Timed out: trust what we saw, else what the CLOB reported at submission
+        resolve(&trades, matched, limit_price).or(reported)
+    }
+}
+
+/// Lowest acceptable sell price for a given slippage, on the 1c tick grid
+fn sell_floor(price: Decimal, slippage: Decimal) -> Decimal {
+    (price * (Decimal::ONE - slippage)).trunc_with_scale(2).max(MIN_PRICE)
+}
+
+/// Fill implied by a post-order response (sell: making = shares, taking = USDC)
+fn fill_from_amounts(order_id: &str, making: Decimal, taking: Decimal) -> Option<SellFill> {
+    (making > Decimal::ZERO && taking > Decimal::ZERO).then(|| SellFill {
+        order_id: order_id.to_string(),
+        shares: making,
+        avg_price: taking / making,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sell_floor_rounds_down_and_clamps() {
+        assert_eq!(sell_floor(dec!(0.80), dec!(0.02)), dec!(0.78));
+        assert_eq!(sell_floor(dec!(0.555), dec!(0.005)), dec!(0.55));
+        assert_eq!(sell_floor(dec!(0.01), dec!(0.05)), MIN_PRICE);
+    }
+
+    #[test]
+    fn test_fill_from_amounts() {
+        let fill = fill_from_amounts("0xabc", dec!(100), dec!(78)).unwrap();
+        assert_eq!(fill.shares, dec!(100));
+        assert_eq!(fill.avg_price, dec!(0.78));
+        assert!(fill_from_amounts("0xabc", Decimal::ZERO, Decimal::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_fill_tracker_vwap() {
+        let mut t = FillTracker::default();
+        assert!(t.avg_price().is_none());
+        t.record(dec!(60), dec!(0.80));
+        t.record(dec!(40), dec!(0.75));
+        assert_eq!(t.shares, dec!(100));
+        assert_eq!(t.avg_price(), Some(dec!(0.78)));
+    }
+
+    #[test]
+    fn test_should_escalate() {
+        assert!(AutoSeller::should_escalate("order couldn't be fully filled, FOK orders are fully filled or killed"));
+        assert!(AutoSeller::should_escalate("price has moved"));
+        assert!(!AutoSeller::should_escalate("not enough balance / allowance"));
+        assert!(!AutoSeller::should_escalate("market is closed"));
+        assert!(!AutoSeller::should_escalate("invalid order payload"));
     }
 }
@@ -1,5 +1,6 @@
 //! Auto-trading configuration and settings
 
+use crate::taxonomy::TaxonomyFilter;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -54,6 +55,8 @@ pub struct AutoTradingSettings {
     pub cooldown_minutes: i32,
     /// Maximum daily loss before pausing auto-trading
     pub max_daily_loss: Decimal,
+    /// Market categories/tags auto-buy and the dispute sniper may trade (everything when empty)
+    pub market_filter: TaxonomyFilter,
 
     // === Dispute Sniper ===
     /// Enable dispute sniping (auto-buy proposed outcomes with edge)
@@ -64,6 +67,20 @@ pub struct AutoTradingSettings {
     pub dispute_position_size: Decimal,
     /// Auto-exit if dispute escalates from Proposed to Disputed/DvmVote
     pub dispute_exit_on_escalation: bool,
+    /// Trade disputes in DVM vote on the revealed tally (entry and adverse-tally exit)
+    pub dispute_dvm_enabled: bool,
+    /// Revealed DVM vote share required to enter; exit when the other side reaches it
+    pub dispute_dvm_min_vote_share: f64,
+    /// Sell once the held outcome converges toward $1 (take profit before settlement)
+    pub dispute_take_profit_enabled: bool,
+    /// Held-side price at which to take profit (e.g., 0.97)
+    pub dispute_take_profit_price: f64,
+    /// Exit if a counter-proposal for the other side is about to pass liveness
+    pub dispute_liveness_exit_enabled: bool,
+    /// Minutes before counter-proposal liveness expiry to exit
+    pub dispute_liveness_exit_minutes: i32,
+    /// Hold dispute positions until the assertion settles, then redeem automatically
+    pub dispute_hold_to_settlement: bool,
 }
 
 impl Default for AutoTradingSettings {
@@ -99,12 +116,20 @@ impl Default for AutoTradingSettings {
             max_positions: 10,
             cooldown_minutes: 5,
             max_daily_loss: Decimal::from(100),
+            market_filter: TaxonomyFilter::default(),
 
             // Dispute sniper
             dispute_sniper_enabled: false,
             min_dispute_edge: 0.10,
             dispute_position_size: Decimal::from(25),
             dispute_exit_on_escalation: true,
+            dispute_dvm_enabled: false,
+            dispute_dvm_min_vote_share: 0.65,
+            dispute_take_profit_enabled: false,
+            dispute_take_profit_price: 0.97,
+            dispute_liveness_exit_enabled: false,
+            dispute_liveness_exit_minutes: 15,
+            dispute_hold_to_settlement: false,
         }
     }
 }
@@ -147,8 +172,16 @@ pub struct UpdateSettingsRequest {
     pub max_positions: Option<i32>,
     pub cooldown_minutes: Option<i32>,
     pub max_daily_loss: Option<String>,
+    pub market_filter: Option<TaxonomyFilter>,
     pub dispute_sniper_enabled: Option<bool>,
     pub min_dispute_edge: Option<f64>,
     pub dispute_position_size: Option<String>,
     pub dispute_exit_on_escalation: Option<bool>,
+    pub dispute_dvm_enabled: Option<bool>,
+    pub dispute_dvm_min_vote_share: Option<f64>,
+    pub dispute_take_profit_enabled: Option<bool>,
+    pub dispute_take_profit_price: Option<f64>,
+    pub dispute_liveness_exit_enabled: Option<bool>,
+    pub dispute_liveness_exit_minutes: Option<i32>,
+    pub dispute_hold_to_settlement: Option<bool>,
 }
@@ -3,12 +3,25 @@
 //! Listens to DisputeTracker broadcast alerts and:
 //! - Buys the proposed outcome side when edge >= threshold (status = Proposed)
 //! - Auto-exits if a dispute escalates from Proposed to Disputed/DvmVote
+//! - Optionally trades DVM-stage disputes on the revealed vote tally: buys the
+//!   proposed outcome when the tally favors it, exits when the tally turns against
+//! - Optional take profit once the held outcome converges toward $1
+//! - Optional exit when a counter-proposal for the other side is about to pass liveness
+//!
+//! With hold-to-settlement, dispute positions are never sold on price; the
+//! AutoRedeemer redeems the winning tokens once the condition is reported on-chain.
 
 use super::key_store::KeyStore;
 use super::position_monitor::SellSignal;
 use super::types::{AutoTradeLog, ExitTrigger};
+use super::AutoTradingSettings;
 use crate::db::Database;
-use crate::types::{DisputeAlert, DisputeStatus, Order, OrderLifecycleStatus, Side, StrategyType};
+use crate::services::clob_pool::{ClobAccount, ClobClientPool};
+use crate::services::rate_limiter::EndpointClass;
+use crate::services::signer::WalletSigner;
+use crate::types::{
+    DisputeAlert, DisputeStatus, DvmPhase, Order, OrderLifecycleStatus, Side, StrategyType,
+};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use rust_decimal::Decimal;
@@ -16,48 +29,64 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tracing::{debug, info, warn};
 
 use alloy::primitives::U256;
-use alloy::signers::{local::PrivateKeySigner, Signer};
-use polymarket_client_sdk::clob::{Client as ClobClient, Config as ClobConfig};
 use polymarket_client_sdk::clob::types::{Amount, OrderType, Side as ClobSide};
 
-const POLYGON_CHAIN_ID: u64 = 137;
-const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
 const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
 const MIN_TRADE_BALANCE: &str = "1.00";
+/// Seconds before re-sending a state-based exit signal for a position that is still open
+const EXIT_RETRY_SECS: i64 = 300;
 
 /// Dispute Sniper service
 pub struct DisputeSniper {
     db: Arc<Database>,
     key_store: KeyStore,
+    clob_pool: Arc<ClobClientPool>,
     polygon_rpc_url: String,
     /// Track last-seen status per assertion_id to detect escalations
     last_status: HashMap<String, DisputeStatus>,
+    /// Track last-seen DVM reveal share per assertion_id to detect the tally turning
+    last_dvm_share: HashMap<String, f64>,
+    /// When an exit signal was last sent per position_id (avoids re-sending every pass)
+    exit_sent_at: HashMap<i64, i64>,
 }
 
 impl DisputeSniper {
-    pub fn new(db: Arc<Database>, key_store: KeyStore, polygon_rpc_url: String) -> Self {
+    pub fn new(
+        db: Arc<Database>,
+        key_store: KeyStore,
+        clob_pool: Arc<ClobClientPool>,
+        polygon_rpc_url: String,
+    ) -> Self {
         Self {
             db,
             key_store,
+            clob_pool,
             polygon_rpc_url,
             last_status: HashMap::new(),
+            last_dvm_share: HashMap::new(),
+            exit_sent_at: HashMap::new(),
         }
     }
 
-    /// Run the dispute sniper, listening for dispute alerts
+    /// Run the dispute sniper, listening for dispute alerts until `stop` is set
     pub async fn run(
         mut self,
         mut dispute_rx: broadcast::Receiver<Vec<DisputeAlert>>,
         sell_tx: mpsc::Sender<SellSignal>,
+        mut stop: watch::Receiver<bool>,
     ) {
         info!("Dispute sniper started");
 
         loop {
-            match dispute_rx.recv().await {
+            let received = tokio::select! {
+                received = dispute_rx.recv() => received,
+                _ = stop.changed() => break,
+            };
+            match received {
                 Ok(alerts) => {
                     if let Err(e) = self.process_disputes(&alerts, &sell_tx).await {
                         warn!("Error processing disputes: {}", e);
@@ -80,13 +109,11 @@ impl DisputeSniper {
         alerts: &[DisputeAlert],
         sell_tx: &mpsc::Sender<SellSignal>,
     ) -> Result<()> {
-        let wallets = self.db.get_auto_trading_enabled_wallets().await?;
+        let wallets = self.db.auto_trading().get_auto_trading_enabled_wallets().await?;
 
         if wallets.is_empty() {
             // Still update last_status for tracking even if no wallets
-            for alert in alerts {
-                self.last_status.insert(alert.assertion_id.clone(), alert.dispute_status);
-            }
+            self.record_seen(alerts);
             return Ok(());
         }
 
@@ -97,28 +124,215 @@ impl DisputeSniper {
         }
 
         // Update last_status after processing
+        self.record_seen(alerts);
+
+        Ok(())
+    }
+
+    /// Remember status and DVM tally per assertion for transition detection
+    fn record_seen(&mut self, alerts: &[DisputeAlert]) {
         for alert in alerts {
             self.last_status.insert(alert.assertion_id.clone(), alert.dispute_status);
+            if let Some(share) = Self::revealed_share(alert) {
+                self.last_dvm_share.insert(alert.assertion_id.clone(), share);
+            }
         }
+    }
+
+    /// Share of revealed DVM votes for the proposed outcome, from reveal until the
+    /// DVM publishes a price (a rolled request is back in Queued/Commit with no tally)
+    fn revealed_share(alert: &DisputeAlert) -> Option<f64> {
+        alert.dvm
+            .as_ref()
+            .filter(|d| matches!(d.phase, DvmPhase::Reveal | DvmPhase::AwaitingSettlement))
+            .and_then(|d| d.proposed_vote_share)
+    }
+
+    /// Send sell signals for open dispute positions that hit an exit condition
+    async fn check_exits(
+        &mut self,
+        wallet_address: &str,
+        alerts: &[DisputeAlert],
+        settings: &AutoTradingSettings,
+        sell_tx: &mpsc::Sender<SellSignal>,
+    ) -> Result<()> {
+        let positions = self.db.positions().get_open_positions_for_wallet(wallet_address).await?;
+        let now = Utc::now().timestamp();
+
+        for pos in positions.iter().filter(|p| p.strategy == StrategyType::Dispute) {
+            if let Some(sent_at) = self.exit_sent_at.get(&pos.id) {
+                if now - sent_at < EXIT_RETRY_SECS {
+                    continue;
+                }
+            }
+
+            let Some((alert, trigger)) = alerts
+                .iter()
+                .filter(|a| a.condition_id == pos.market_id)
+                .find_map(|a| {
+                    Self::exit_trigger(
+                        a,
+                        pos.side,
+                        settings,
+                        self.last_status.get(&a.assertion_id).copied(),
+                        self.last_dvm_share.get(&a.assertion_id).copied(),
+                        now,
+                    )
+                    .map(|t| (a, t))
+                })
+            else {
+                continue;
+            };
+
+            let Some(token_id) = pos.token_id.clone() else {
+                warn!("[Dispute Sniper] Position {} has no token_id", pos.id);
+                continue;
+            };
+
+            info!(
+                "[Dispute Sniper] EXIT position {} - {} for {}",
+                pos.id, trigger.reason(), alert.question
+            );
+
+            let log = AutoTradeLog {
+                id: None,
+                wallet_address: wallet_address.to_string(),
+                position_id: Some(pos.id),
+                action: trigger.action_name(),
+                market_question: Some(alert.question.clone()),
+                side: Some(format!("{:?}", pos.side)),
+                entry_price: Some(pos.entry_price),
+                exit_price: Some(trigger.price()),
+                size: Some(pos.size),
+                pnl: None, // Will be calculated by AutoSeller
+                trigger_reason: Some(trigger.reason()),
+                created_at: Utc::now(),
+            };
+
+            let signal = SellSignal {
+                position_id: pos.id,
+                wallet_address: wallet_address.to_string(),
+                token_id,
+                current_price: trigger.price(),
+                trigger,
+                size: pos.size,
+                market_question: pos.question.clone(),
+            };
+
+            if sell_tx.send(signal).await.is_err() {
+                warn!("[Dispute Sniper] Failed to send sell signal - channel closed");
+            }
+            self.exit_sent_at.insert(pos.id, now);
+            self.db.auto_trading().log_auto_trade(&log).await?;
+        }
+
+        // Forget positions that are no longer open
+        self.exit_sent_at.retain(|id, _| positions.iter().any(|p| p.id == *id));
 
         Ok(())
     }
 
+    /// Decide whether an alert on the market of a position held on `side` should
+    /// close it. `last_status` and `last_dvm_share` are what the previous pass saw
+    /// for the same assertion.
+    fn exit_trigger(
+        alert: &DisputeAlert,
+        side: Side,
+        settings: &AutoTradingSettings,
+        last_status: Option<DisputeStatus>,
+        last_dvm_share: Option<f64>,
+        now: i64,
+    ) -> Option<ExitTrigger> {
+        // Held to settlement: never sold, the AutoRedeemer collects the winnings
+        if settings.dispute_hold_to_settlement {
+            return None;
+        }
+
+        let current_price = match side {
+            Side::Yes => alert.current_yes_price,
+            Side::No => alert.current_no_price,
+        };
+        let holds_proposed = format!("{:?}", side) == alert.proposed_outcome;
+
+        // Escalation from Proposed to Disputed/DvmVote
+        let was_proposed = last_status == Some(DisputeStatus::Proposed);
+        if settings.dispute_exit_on_escalation
+            && was_proposed
+            && matches!(alert.dispute_status, DisputeStatus::Disputed | DisputeStatus::DvmVote)
+        {
+            return Some(ExitTrigger::DisputeEscalation {
+                price: current_price,
+                new_status: alert.dispute_status.to_string(),
+            });
+        }
+
+        // DVM reveal tally turned against the position
+        if settings.dispute_dvm_enabled && alert.dispute_status == DisputeStatus::DvmVote {
+            if let Some(share) = Self::revealed_share(alert) {
+                let side_share = |s: f64| if holds_proposed { s } else { 1.0 - s };
+                let exit_below = 1.0 - settings.dispute_dvm_min_vote_share;
+
+                // Only act when the tally crosses the line, not on every scan below it
+                let was_above = last_dvm_share
+                    .map(|prev| side_share(prev) > exit_below)
+                    .unwrap_or(true);
+
+                if was_above && side_share(share) <= exit_below {
+                    return Some(ExitTrigger::DisputeDvmTally {
+                        price: current_price,
+                        vote_share: side_share(share),
+                    });
+                }
+            }
+        }
+
+        // Counter-proposal for the other side is about to pass liveness undisputed
+        if settings.dispute_liveness_exit_enabled
+            && alert.dispute_status == DisputeStatus::Proposed
+            && !holds_proposed
+        {
+            let secs_left = alert.estimated_resolution - now;
+            if secs_left >= 0 && secs_left <= settings.dispute_liveness_exit_minutes as i64 * 60 {
+                return Some(ExitTrigger::DisputeCounterProposal {
+                    price: current_price,
+                    counter_outcome: alert.proposed_outcome.clone(),
+                    minutes_left: secs_left / 60,
+                });
+            }
+        }
+
+        // Take profit once the held outcome has converged
+        if settings.dispute_take_profit_enabled {
+            let target = Decimal::try_from(settings.dispute_take_profit_price).ok()?;
+            if current_price >= target {
+                return Some(ExitTrigger::DisputeConvergence {
+                    price: current_price,
+                    target,
+                });
+            }
+        }
+
+        None
+    }
+
     /// Process dispute alerts for a specific wallet
     async fn process_for_wallet(
-        &self,
+        &mut self,
         wallet_address: &str,
         alerts: &[DisputeAlert],
         sell_tx: &mpsc::Sender<SellSignal>,
     ) -> Result<()> {
-        let settings = self.db.get_auto_trading_settings(wallet_address).await?;
+        let settings = self.db.auto_trading().get_auto_trading_settings(wallet_address).await?;
 
         if !settings.enabled || !settings.dispute_sniper_enabled {
             return Ok(());
         }
 
+        // Exits run before the entry limits so a full wallet can still unwind
+        self.check_exits(wallet_address, alerts, &settings, sell_tx).await?;
+
         // Global limits check
-        let open_count = self.db.count_open_positions(wallet_address).await?;
+        let open_count = self.db.positions().count_open_positions(wallet_address).await?;
         if open_count >= settings.max_positions {
             debug!(
                 "[Dispute Sniper] Wallet {} at max positions ({}/{})",
@@ -127,7 +341,7 @@ impl DisputeSniper {
             return Ok(());
         }
 
-        let current_exposure = self.db.get_total_exposure(wallet_address).await?;
+        let current_exposure = self.db.positions().get_total_exposure(wallet_address).await?;
         if current_exposure >= settings.max_total_exposure {
             debug!(
                 "[Dispute Sniper] Wallet {} at max exposure ({}/{})",
@@ -136,7 +350,7 @@ impl DisputeSniper {
             return Ok(());
         }
 
-        let daily_pnl = self.db.get_daily_auto_pnl(wallet_address).await?;
+        let daily_pnl = self.db.auto_trading().get_daily_auto_pnl(wallet_address).await?;
         if daily_pnl <= -settings.max_daily_loss {
             debug!(
                 "[Dispute Sniper] Wallet {} hit daily loss limit (${} vs -${})",
@@ -160,16 +374,30 @@ impl DisputeSniper {
         }
 
         for alert in alerts {
-            // === BUY: Proposed status with sufficient edge ===
-            if alert.dispute_status == DisputeStatus::Proposed {
-                // Use expected value if available (accounts for 50-50 outcome), else raw edge
-                let edge_f64 = alert.expected_value
-                    .or(alert.edge)
+            // Entries only; exits above still cover filtered-out markets
+            if !settings.market_filter.allows(&alert.taxonomy) {
+                continue;
+            }
+
+            let dvm_share = Self::revealed_share(alert);
+
+            // DVM entry: revealed tally already favors the proposed outcome
+            let dvm_entry = alert.dispute_status == DisputeStatus::DvmVote
+                && settings.dispute_dvm_enabled
+                && dvm_share
+                    .map(|share| share >= settings.dispute_dvm_min_vote_share)
+                    .unwrap_or(false);
+
+            // === BUY: Proposed status (or DVM reveal tally) with sufficient edge ===
+            if alert.dispute_status == DisputeStatus::Proposed || dvm_entry {
+                // Use expected value if available (accounts for 50-50 outcome), else raw edge.
+                // The EV priors are for the proposal stage, so DVM entries use raw edge.
+                let edge_f64 = if dvm_entry { alert.edge } else { alert.expected_value.or(alert.edge) }
                     .and_then(|e| e.to_string().parse::<f64>().ok())
                     .unwrap_or(0.0);
 
                 // Round 2 re-proposals are higher conviction - reduce threshold by 20%
-                let effective_threshold = if alert.dispute_round >= 2 {
+                let effective_threshold = if alert.dispute_round >= 2 && !dvm_entry {
                     settings.min_dispute_edge * 0.8
                 } else {
                     settings.min_dispute_edge
@@ -209,7 +437,7 @@ impl DisputeSniper {
                 };
 
                 // Check for existing dispute position
-                if self.db.has_open_dispute_position(wallet_address, &alert.condition_id).await? {
+                if self.db.positions().has_open_dispute_position(wallet_address, &alert.condition_id).await? {
                     debug!(
                         "[Dispute Sniper] Already has dispute position for {}",
                         alert.question
@@ -231,8 +459,8 @@ impl DisputeSniper {
                     continue;
                 }
 
-                // Get the decrypted key
-                let private_key = match self.key_store.get_key(wallet_address).await {
+                // Get the wallet's signer
+                let signer = match self.key_store.signer(wallet_address).await {
                     Some(k) => k,
                     None => {
                         debug!("[Dispute Sniper] No key in KeyStore for {}", wallet_address);
@@ -252,7 +480,7 @@ impl DisputeSniper {
                 );
 
                 // Execute buy
-                let order_id = match self.execute_buy(&private_key, &token_id, position_size).await {
+                let order_id = match self.execute_buy(&signer, &token_id, position_size).await {
                     Ok(id) => Some(id),
                     Err(e) => {
                         warn!("[Dispute Sniper] Failed to execute buy: {}", e);
@@ -279,7 +507,7 @@ impl DisputeSniper {
                         created_at: Utc::now(),
                         updated_at: Utc::now(),
                     };
-                    if let Err(e) = self.db.create_order(&order).await {
+                    if let Err(e) = self.db.orders().create_order(&order).await {
                         warn!("[Dispute Sniper] Failed to create order record: {}", e);
                     }
                 }
@@ -287,7 +515,7 @@ impl DisputeSniper {
                 // Create position with Dispute strategy
                 let position_id = self
                     .db
-                    .create_position_for_wallet(
+                    .positions().create_position_for_wallet(
                         wallet_address,
                         &alert.condition_id,
                         &alert.question,
@@ -316,17 +544,27 @@ impl DisputeSniper {
                     exit_price: None,
                     size: Some(position_size),
                     pnl: None,
-                    trigger_reason: Some(format!(
-                        "Dispute EV {:.1}% >= {:.1}% threshold (proposed: {}, round: {}, bond: {:?})",
-                        edge_f64 * 100.0,
-                        effective_threshold * 100.0,
-                        alert.proposed_outcome,
-                        alert.dispute_round,
-                        alert.proposer_bond
-                    )),
+                    trigger_reason: Some(if dvm_entry {
+                        format!(
+                            "DVM reveal tally {:.0}% for proposed {}, edge {:.1}% >= {:.1}% threshold",
+                            dvm_share.unwrap_or(0.0) * 100.0,
+                            alert.proposed_outcome,
+                            edge_f64 * 100.0,
+                            effective_threshold * 100.0
+                        )
+                    } else {
+                        format!(
+                            "Dispute EV {:.1}% >= {:.1}% threshold (proposed: {}, round: {}, bond: {:?})",
+                            edge_f64 * 100.0,
+                            effective_threshold * 100.0,
+                            alert.proposed_outcome,
+                            alert.dispute_round,
+                            alert.proposer_bond
+                        )
+                    }),
                     created_at: Utc::now(),
                 };
-                self.db.log_auto_trade(&log).await?;
+                self.db.auto_trading().log_auto_trade(&log).await?;
 
                 info!(
                     "[Dispute Sniper] Created position {} for ${} on {}",
@@ -336,86 +574,6 @@ impl DisputeSniper {
                 // Only buy one per pass
                 break;
             }
-
-            // === EXIT: Escalation from Proposed to Disputed/DvmVote ===
-            if (alert.dispute_status == DisputeStatus::Disputed
-                || alert.dispute_status == DisputeStatus::DvmVote)
-                && settings.dispute_exit_on_escalation
-            {
-                // Check if this is an escalation (was previously Proposed)
-                let was_proposed = self.last_status
-                    .get(&alert.assertion_id)
-                    .map(|s| *s == DisputeStatus::Proposed)
-                    .unwrap_or(false);
-
-                if !was_proposed {
-                    continue;
-                }
-
-                // Find open dispute position for this condition_id
-                let positions = self.db.get_open_positions_for_wallet(wallet_address).await?;
-                let dispute_position = positions.iter().find(|p| {
-                    p.market_id == alert.condition_id && p.strategy == StrategyType::Dispute
-                });
-
-                if let Some(pos) = dispute_position {
-                    let token_id = match &pos.token_id {
-                        Some(t) => t.clone(),
-                        None => {
-                            warn!("[Dispute Sniper] Position {} has no token_id", pos.id);
-                            continue;
-                        }
-                    };
-
-                    // Determine current price for the position's side
-                    let current_price = match pos.side {
-                        Side::Yes => alert.current_yes_price,
-                        Side::No => alert.current_no_price,
-                    };
-
-                    info!(
-                        "[Dispute Sniper] EXIT position {} - dispute escalated to {} for {}",
-                        pos.id, alert.dispute_status, alert.question
-                    );
-
-                    let signal = SellSignal {
-                        position_id: pos.id,
-                        wallet_address: wallet_address.to_string(),
-                        token_id,
-                        current_price,
-                        trigger: ExitTrigger::DisputeEscalation {
-                            price: current_price,
-                            new_status: format!("{}", alert.dispute_status),
-                        },
-                        size: pos.size,
-                        market_question: pos.question.clone(),
-                    };
-
-                    if sell_tx.send(signal).await.is_err() {
-                        warn!("[Dispute Sniper] Failed to send sell signal - channel closed");
-                    }
-
-                    // Log the exit
-                    let log = AutoTradeLog {
-                        id: None,
-                        wallet_address: wallet_address.to_string(),
-                        position_id: Some(pos.id),
-                        action: "dispute_exit".to_string(),
-                        market_question: Some(alert.question.clone()),
-                        side: Some(format!("{:?}", pos.side)),
-                        entry_price: Some(pos.entry_price),
-                        exit_price: Some(current_price),
-                        size: Some(pos.size),
-                        pnl: None, // Will be calculated by AutoSeller
-                        trigger_reason: Some(format!(
-                            "Dispute escalated from Proposed to {}",
-                            alert.dispute_status
-                        )),
-                        created_at: Utc::now(),
-                    };
-                    self.db.log_auto_trade(&log).await?;
-                }
-            }
         }
 
         Ok(())
@@ -468,43 +626,36 @@ impl DisputeSniper {
     }
 
     /// Execute a buy order via CLOB API
-    async fn execute_buy(&self, private_key: &str, token_id: &str, size: Decimal) -> Result<String> {
-        let signer: PrivateKeySigner = private_key.parse()
-            .context("Failed to parse private key")?;
-        let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-        let clob_config = ClobConfig::builder().use_server_time(true).build();
-        let client = ClobClient::new(CLOB_ENDPOINT, clob_config)
-            .context("Failed to create CLOB client")?
-            .authentication_builder(&signer)
-            .authenticate()
-            .await
-            .context("Failed to authenticate with CLOB")?;
-
+    async fn execute_buy(&self, signer: &WalletSigner, token_id: &str, size: Decimal) -> Result<String> {
         let token_id_u256 = U256::from_str_radix(token_id, 10)
             .context("Failed to parse token ID")?;
 
-        let order = client
-            .market_order()
-            .token_id(token_id_u256)
-            .amount(Amount::usdc(size).context("Failed to create USDC amount")?)
-            .side(ClobSide::Buy)
-            .order_type(OrderType::FOK)
-            .build()
-            .await
-            .context("Failed to build order")?;
-
-        let signed_order = client
-            .sign(&signer, order)
-            .await
-            .context("Failed to sign order")?;
-
-        let response = client
-            .post_order(signed_order)
-            .await
-            .context("Failed to submit order")?;
-
-        let order_id = format!("{:?}", response);
+        let response = self
+            .clob_pool
+            .run(signer, ClobAccount::Eoa, EndpointClass::PostOrder, |c| async move {
+                let order = c.client
+                    .market_order()
+                    .token_id(token_id_u256)
+                    .amount(Amount::usdc(size).context("Failed to create USDC amount")?)
+                    .side(ClobSide::Buy)
+                    .order_type(OrderType::FOK)
+                    .build()
+                    .await
+                    .context("Failed to build order")?;
+
+                let signed_order = c.client
+                    .sign(&c.signer, order)
+                    .await
+                    .context("Failed to sign order")?;
+
+                c.client
+                    .post_order(signed_order)
+                    .await
+                    .context("Failed to submit order")
+            })
+            .await?;
+
+        let order_id = response.order_id;
         info!("[Dispute Sniper] Order submitted: {}", order_id);
 
         Ok(order_id)
@@ -515,3 +666,170 @@ impl DisputeSniper {
 struct JsonRpcResponse {
     result: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taxonomy::MarketTaxonomy;
+    use crate::types::DvmVoteInfo;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn alert(status: DisputeStatus, proposed: &str) -> DisputeAlert {
+        DisputeAlert {
+            assertion_id: "0xassertion".to_string(),
+            condition_id: "0xcondition".to_string(),
+            question: "Will it happen?".to_string(),
+            slug: "will-it-happen".to_string(),
+            dispute_status: status,
+            proposed_outcome: proposed.to_string(),
+            dispute_timestamp: NOW - 3600,
+            estimated_resolution: NOW + 600,
+            current_yes_price: Decimal::new(80, 2),
+            current_no_price: Decimal::new(20, 2),
+            liquidity: Decimal::from(10_000),
+            yes_token_id: Some("yes".to_string()),
+            no_token_id: Some("no".to_string()),
+            edge: None,
+            dispute_round: 1,
+            proposer_bond: None,
+            adapter_version: None,
+            liveness_seconds: Some(7200),
+            expected_value: None,
+            dvm: None,
+            taxonomy: MarketTaxonomy::default(),
+        }
+    }
+
+    fn dvm_reveal(share: f64) -> DvmVoteInfo {
+        DvmVoteInfo {
+            phase: DvmPhase::Reveal,
+            round_id: 1,
+            commit_ends_at: NOW - 3600,
+            reveal_ends_at: NOW + 3600,
+            roll_count: 0,
+            total_votes_revealed: None,
+            proposed_vote_share: Some(share),
+            proposed_upheld: None,
+        }
+    }
+
+    fn settings() -> AutoTradingSettings {
+        AutoTradingSettings {
+            dispute_exit_on_escalation: true,
+            dispute_dvm_enabled: true,
+            dispute_take_profit_enabled: true,
+            dispute_liveness_exit_enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exit_on_escalation() {
+        let disputed = alert(DisputeStatus::Disputed, "Yes");
+        let trigger = DisputeSniper::exit_trigger(
+            &disputed, Side::Yes, &settings(), Some(DisputeStatus::Proposed), None, NOW,
+        );
+        assert!(matches!(trigger, Some(ExitTrigger::DisputeEscalation { .. })));
+
+        // Already disputed on the previous pass: not a new escalation
+        let trigger = DisputeSniper::exit_trigger(
+            &disputed, Side::Yes, &settings(), Some(DisputeStatus::Disputed), None, NOW,
+        );
+        assert!(trigger.is_none());
+    }
+
+    #[test]
+    fn test_exit_when_dvm_tally_turns() {
+        let mut vote = alert(DisputeStatus::DvmVote, "Yes");
+        vote.dvm = Some(dvm_reveal(0.30));
+        let no_escalation = AutoTradingSettings {
+            dispute_exit_on_escalation: false,
+            ..settings()
+        };
+
+        let trigger = DisputeSniper::exit_trigger(
+            &vote, Side::Yes, &no_escalation, Some(DisputeStatus::DvmVote), Some(0.60), NOW,
+        );
+        assert!(matches!(trigger, Some(ExitTrigger::DisputeDvmTally { .. })));
+
+        // Below the line on the previous pass too: signalled once, not every scan
+        let trigger = DisputeSniper::exit_trigger(
+            &vote, Side::Yes, &no_escalation, Some(DisputeStatus::DvmVote), Some(0.32), NOW,
+        );
+        assert!(trigger.is_none());
+    }
+
+    #[test]
+    fn test_dvm_tally_exit_follows_the_phase() {
+        let no_escalation = AutoTradingSettings {
+            dispute_exit_on_escalation: false,
+            ..settings()
+        };
+
+        // Round over but no price yet: the final tally still counts
+        let mut vote = alert(DisputeStatus::DvmVote, "Yes");
+        vote.dvm = Some(DvmVoteInfo { phase: DvmPhase::AwaitingSettlement, ..dvm_reveal(0.30) });
+        let trigger = DisputeSniper::exit_trigger(
+            &vote, Side::Yes, &no_escalation, Some(DisputeStatus::DvmVote), Some(0.60), NOW,
+        );
+        assert!(matches!(trigger, Some(ExitTrigger::DisputeDvmTally { .. })));
+
+        // Rolled into the next round's commit phase: the old tally is no signal
+        vote.dvm = Some(DvmVoteInfo { phase: DvmPhase::Commit, roll_count: 1, ..dvm_reveal(0.30) });
+        let trigger = DisputeSniper::exit_trigger(
+            &vote, Side::Yes, &no_escalation, Some(DisputeStatus::DvmVote), Some(0.60), NOW,
+        );
+        assert!(trigger.is_none());
+    }
+
+    #[test]
+    fn test_exit_on_counter_proposal() {
+        // Holding No while Yes is proposed with 10 minutes of liveness left
+        let proposed = alert(DisputeStatus::Proposed, "Yes");
+        let trigger = DisputeSniper::exit_trigger(
+            &proposed, Side::No, &settings(), Some(DisputeStatus::Proposed), None, NOW,
+        );
+        assert!(matches!(trigger, Some(ExitTrigger::DisputeCounterProposal { minutes_left: 10, .. })));
+
+        // Holding the proposed side is not a counter-proposal
+        let trigger = DisputeSniper::exit_trigger(
+            &proposed, Side::Yes, &settings(), Some(DisputeStatus::Proposed), None, NOW,
+        );
+        assert!(trigger.is_none());
+    }
+
+    #[test]
+    fn test_take_profit_on_convergence() {
+        let mut converged = alert(DisputeStatus::Proposed, "Yes");
+        converged.current_yes_price = Decimal::new(98, 2);
+        let trigger = DisputeSniper::exit_trigger(
+            &converged, Side::Yes, &settings(), Some(DisputeStatus::Proposed), None, NOW,
+        );
+        assert!(matches!(trigger, Some(ExitTrigger::DisputeConvergence { .. })));
+    }
+
+    #[test]
+    fn test_hold_to_settlement_skips_every_exit() {
+        let hold = AutoTradingSettings {
+            dispute_hold_to_settlement: true,
+            ..settings()
+        };
+
+        let disputed = alert(DisputeStatus::Disputed, "Yes");
+        let mut vote = alert(DisputeStatus::DvmVote, "Yes");
+        vote.dvm = Some(dvm_reveal(0.30));
+        let mut converged = alert(DisputeStatus::Proposed, "Yes");
+        converged.current_yes_price = Decimal::new(98, 2);
+
+        let cases = [
+            (&disputed, Side::Yes, Some(DisputeStatus::Proposed), None),
+            (&vote, Side::Yes, Some(DisputeStatus::DvmVote), Some(0.60)),
+            (&converged, Side::No, Some(DisputeStatus::Proposed), None),
+            (&converged, Side::Yes, Some(DisputeStatus::Proposed), None),
+        ];
+        for (alert, side, last_status, last_share) in cases {
+            assert!(DisputeSniper::exit_trigger(alert, side, &hold, last_status, last_share, NOW).is_none());
+        }
+    }
+}
@@ -44,7 +44,7 @@ impl AutoTradingExecutor {
         // Get encrypted key and decrypt
         let encrypted_key = self
             .db
-            .get_encrypted_key(wallet_address)
+            .auth().get_encrypted_key(wallet_address)
             .await?
             .ok_or_else(|| anyhow!("No encrypted key found for wallet"))?;
 
@@ -125,7 +125,7 @@ impl AutoTradingExecutor {
         // Get encrypted key and decrypt
         let encrypted_key = self
             .db
-            .get_encrypted_key(wallet_address)
+            .auth().get_encrypted_key(wallet_address)
             .await?
             .ok_or_else(|| anyhow!("No encrypted key found for wallet"))?;
 
@@ -1,66 +1,320 @@
-//! In-memory key store for auto-trading
+//! Key store for auto-trading
 //!
-//! Holds decrypted private keys in memory while auto-trading is enabled.
-//! Keys are cleared when auto-trading is disabled.
+//! Unlocks decrypted private keys into a signer backend while auto-trading is
+//! enabled and hands services a `WalletSigner` per wallet. Services never see the
+//! raw key. Keys are locked once neither auto-trading nor Mint Maker is enabled,
+//! or when the TTL set with `KEY_TTL_SECONDS` runs out (none by default); the
+//! expiry watch then disables the wallet's bots and notifies the user.
+//!
+//! With a `KeyVault` attached, unlocked keys are also wrapped at rest so enabled
+//! wallets resume after a restart (see `resume`). An external signer keeps its
+//! keys across server restarts, so it never gets a vault.
 
-use std::collections::HashMap;
+use super::key_vault::KeyVault;
+use crate::db::Database;
+use crate::services::signer::{LocalSignerBackend, RemoteSignerBackend, SignerBackend, WalletSigner};
+use crate::services::{Notification, NotificationKind, Notifier};
+use crate::Config;
+use anyhow::Result;
+use std::collections::HashSet;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+use tracing::{info, warn};
+use zeroize::Zeroizing;
+
+/// How often the key vault kill-switch file is checked
+const KILL_SWITCH_POLL: Duration = Duration::from_secs(10);
+/// How often bot keys are checked for having locked on their own
+const EXPIRY_POLL: Duration = Duration::from_secs(60);
 
-/// Thread-safe store for decrypted private keys
-#[derive(Clone, Default)]
+/// Thread-safe handle to the signer backend holding unlocked keys
+#[derive(Clone)]
 pub struct KeyStore {
-    keys: Arc<RwLock<HashMap<String, String>>>,
+    backend: Arc<dyn SignerBackend>,
+    vault: Option<Arc<KeyVault>>,
+    /// Wallets unlocked through this store and not locked by it since
+    unlocked: Arc<RwLock<HashSet<String>>>,
+}
+
+impl Default for KeyStore {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl KeyStore {
+    /// In-process key store without TTL or idle lock
     pub fn new() -> Self {
+        Self::local(None, None)
+    }
+
+    /// In-process key store; expired and idle keys are auto-locked in the background
+    pub fn local(ttl: Option<Duration>, idle_lock: Option<Duration>) -> Self {
+        let backend = Arc::new(LocalSignerBackend::new(ttl, idle_lock));
+        backend.spawn_auto_lock();
         Self {
-            keys: Arc::new(RwLock::new(HashMap::new())),
+            backend,
+            vault: None,
+            unlocked: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Key store backed by an external signer process
+    pub fn remote(socket_path: &str) -> Self {
+        Self {
+            backend: Arc::new(RemoteSignerBackend::new(socket_path)),
+            vault: None,
+            unlocked: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Signer backend chosen by `SIGNER_SOCKET` / `KEY_TTL_SECONDS` / `KEY_IDLE_LOCK_SECONDS`
+    pub fn from_config(config: &Config) -> Self {
+        match &config.signer_socket {
+            Some(socket_path) => {
+                info!("[KeyStore] Using external signer at {}", socket_path);
+                Self::remote(socket_path)
+            }
+            None => Self::local(
+                (config.key_ttl_seconds > 0).then(|| Duration::from_secs(config.key_ttl_seconds)),
+                (config.key_idle_lock_seconds > 0)
+                    .then(|| Duration::from_secs(config.key_idle_lock_seconds)),
+            ),
         }
     }
 
-    /// Store a decrypted private key for a wallet
-    pub async fn store_key(&self, wallet_address: &str, private_key: String) {
-        let mut keys = self.keys.write().await;
-        keys.insert(wallet_address.to_lowercase(), private_key);
+    /// Also wrap unlocked keys at rest so they survive restarts
+    pub fn with_vault(mut self, vault: Arc<KeyVault>) -> Self {
+        self.vault = Some(vault);
+        self
+    }
+
+    /// Unlock a decrypted private key for a wallet. The key string is wiped afterwards.
+    pub async fn store_key(&self, wallet_address: &str, private_key: String) -> Result<()> {
+        let private_key = Zeroizing::new(private_key);
+        self.backend
+            .unlock(wallet_address, private_key.clone(), None)
+            .await?;
+        self.unlocked.write().await.insert(wallet_address.to_lowercase());
         info!(
             "[KeyStore] Stored key for wallet {} (auto-trading enabled)",
             wallet_address
         );
+
+        if let Some(vault) = &self.vault {
+            // Auto-resume is best effort; the key is already unlocked for this run
+            if let Err(e) = vault.wrap(wallet_address, &private_key).await {
+                warn!("[KeyStore] Failed to wrap key for {}: {}", wallet_address, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Unlock the wrapped keys of wallets that still have auto-trading or Mint
+    /// Maker enabled (startup, and taking over as leader, since another instance
+    /// may have wrapped keys since). Returns how many wallets were resumed.
+    pub async fn resume(&self, reason: &str) -> Result<usize> {
+        let Some(vault) = &self.vault else {
+            return Ok(0);
+        };
+        if vault.kill_switch_engaged() {
+            vault.purge(&format!("kill switch present on {}", reason)).await?;
+            return Ok(0);
+        }
+
+        let mut resumed = 0;
+        for (wallet_address, private_key) in vault.unwrap_enabled(reason).await? {
+            match self.backend.unlock(&wallet_address, private_key, None).await {
+                Ok(_) => {
+                    self.unlocked.write().await.insert(wallet_address.to_lowercase());
+                    resumed += 1;
+                }
+                Err(e) => warn!("[KeyStore] Failed to resume key for {}: {}", wallet_address, e),
+            }
+        }
+        if resumed > 0 {
+            info!("[KeyStore] Resumed {} wallet keys from the key vault", resumed);
+        }
+        Ok(resumed)
     }
 
-    /// Get a stored private key
-    pub async fn get_key(&self, wallet_address: &str) -> Option<String> {
-        let keys = self.keys.read().await;
-        keys.get(&wallet_address.to_lowercase()).cloned()
+    /// Watch the vault's kill-switch file; when it appears, purge every wrapped
+    /// key and lock every unlocked key
+    pub fn spawn_kill_switch_watch(&self) {
+        let Some(vault) = self.vault.clone() else {
+            return;
+        };
+        let Some(path) = vault.kill_switch().map(|p| p.to_path_buf()) else {
+            return;
+        };
+
+        let key_store = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(KILL_SWITCH_POLL);
+            let mut engaged = false;
+            loop {
+                interval.tick().await;
+                let present = path.exists();
+                if present && !engaged {
+                    warn!("[KeyStore] Kill switch {} engaged, purging keys", path.display());
+                    if let Err(e) = vault.purge("kill switch engaged").await {
+                        warn!("[KeyStore] Failed to purge wrapped keys: {}", e);
+                    }
+                    key_store.clear().await;
+                } else if !present && engaged {
+                    info!("[KeyStore] Kill switch {} released", path.display());
+                }
+                engaged = present;
+            }
+        });
+    }
+
+    /// Signing handle for a wallet, `None` when its key is locked
+    pub async fn signer(&self, wallet_address: &str) -> Option<WalletSigner> {
+        match self.backend.address(wallet_address).await {
+            Ok(Some(address)) => Some(WalletSigner::new(wallet_address, address, self.backend.clone())),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("[KeyStore] Signer unavailable for {}: {}", wallet_address, e);
+                None
+            }
+        }
     }
 
-    /// Remove a stored key when auto-trading is disabled
+    /// Lock a stored key and delete its wrapped copy. Callers check that no
+    /// service still needs it first (see `AppState::release_key`).
     pub async fn remove_key(&self, wallet_address: &str) {
-        let mut keys = self.keys.write().await;
-        if keys.remove(&wallet_address.to_lowercase()).is_some() {
-            info!(
-                "[KeyStore] Removed key for wallet {} (auto-trading disabled)",
+        self.unlocked.write().await.remove(&wallet_address.to_lowercase());
+        match self.backend.lock(wallet_address).await {
+            Ok(()) => info!(
+                "[KeyStore] Removed key for wallet {} (no auto feature enabled)",
                 wallet_address
-            );
+            ),
+            Err(e) => warn!("[KeyStore] Failed to lock key for {}: {}", wallet_address, e),
+        }
+        if let Some(vault) = &self.vault {
+            if let Err(e) = vault.forget(wallet_address).await {
+                warn!("[KeyStore] Failed to delete wrapped key for {}: {}", wallet_address, e);
+            }
+        }
+    }
+
+    /// Wallets unlocked through this store whose key has since locked on its own
+    /// (TTL or idle lock). Each is returned once.
+    pub async fn take_expired(&self) -> Vec<String> {
+        let tracked: Vec<String> = self.unlocked.read().await.iter().cloned().collect();
+        let mut expired = Vec::new();
+        for wallet_address in tracked {
+            // A signer that can't be reached says nothing about the key
+            if let Ok(None) = self.backend.address(&wallet_address).await {
+                expired.push(wallet_address);
+            }
+        }
+
+        let mut unlocked = self.unlocked.write().await;
+        for wallet_address in &expired {
+            unlocked.remove(wallet_address);
+        }
+        expired
+    }
+
+    /// Disable auto-trading and Mint Maker for wallets whose key locked on its
+    /// own, and tell their owners, until `stop` is set. Nothing would unlock the
+    /// key again, so the bots would otherwise fail every trade.
+    pub async fn run_expiry_watch(&self, db: Arc<Database>, notifier: Notifier, mut stop: watch::Receiver<bool>) {
+        let mut interval = tokio::time::interval(EXPIRY_POLL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = stop.changed() => break,
+            }
+
+            for wallet_address in self.take_expired().await {
+                warn!("[KeyStore] Key for {} locked on its own, disabling its bots", wallet_address);
+                if let Err(e) = disable_bots(&db, &wallet_address).await {
+                    warn!("[KeyStore] Failed to disable bots for {}: {}", wallet_address, e);
+                }
+                if let Some(vault) = &self.vault {
+                    if let Err(e) = vault.forget(&wallet_address).await {
+                        warn!("[KeyStore] Failed to delete wrapped key for {}: {}", wallet_address, e);
+                    }
+                }
+                notifier.notify(
+                    Notification::new(NotificationKind::Security, Some(&wallet_address), "Auto-trading key locked")
+                        .field("reason", "The unlocked key expired")
+                        .field("action", "Auto-trading and Mint Maker were disabled; enable them again to unlock the key"),
+                );
+            }
         }
     }
 
-    /// Check if a key is stored
+    /// Check if a key is unlocked
     pub async fn has_key(&self, wallet_address: &str) -> bool {
-        let keys = self.keys.read().await;
-        keys.contains_key(&wallet_address.to_lowercase())
+        matches!(self.backend.address(wallet_address).await, Ok(Some(_)))
     }
 
-    /// Clear all stored keys (for shutdown)
+    /// Wallets that currently have a key unlocked
+    pub async fn wallets(&self) -> Vec<String> {
+        self.backend.wallets().await.unwrap_or_else(|e| {
+            warn!("[KeyStore] Failed to list wallets: {}", e);
+            Vec::new()
+        })
+    }
+
+    /// Lock all stored keys (for shutdown)
     pub async fn clear(&self) {
-        let mut keys = self.keys.write().await;
-        let count = keys.len();
-        keys.clear();
-        if count > 0 {
-            info!("[KeyStore] Cleared {} stored keys", count);
+        self.unlocked.write().await.clear();
+        match self.backend.lock_all().await {
+            Ok(count) if count > 0 => info!("[KeyStore] Cleared {} stored keys", count),
+            Ok(_) => {}
+            Err(e) => warn!("[KeyStore] Failed to clear keys: {}", e),
         }
     }
 }
+
+/// Turn off every bot that signs with a wallet's key
+async fn disable_bots(db: &Database, wallet_address: &str) -> Result<()> {
+    db.auto_trading().disable_auto_trading(wallet_address).await?;
+    let mut settings = db.mint_maker().get_mint_maker_settings(wallet_address).await?;
+    if settings.enabled {
+        settings.enabled = false;
+        db.mint_maker().upsert_mint_maker_settings(&settings).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Anvil's first default account
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn test_bot_key_has_no_ttl_by_default() {
+        let key_store = KeyStore::new();
+        key_store.store_key("0xWallet", TEST_KEY.to_string()).await.unwrap();
+
+        assert!(key_store.has_key("0xwallet").await);
+        assert!(key_store.take_expired().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expired_key_is_reported_once() {
+        let key_store = KeyStore::local(Some(Duration::ZERO), None);
+        key_store.store_key("0xWallet", TEST_KEY.to_string()).await.unwrap();
+
+        assert_eq!(key_store.take_expired().await, vec!["0xwallet".to_string()]);
+        assert!(key_store.take_expired().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_removed_key_is_not_reported() {
+        let key_store = KeyStore::local(Some(Duration::ZERO), None);
+        key_store.store_key("0xwallet", TEST_KEY.to_string()).await.unwrap();
+        key_store.remove_key("0xwallet").await;
+
+        assert!(key_store.take_expired().await.is_empty());
+    }
+}
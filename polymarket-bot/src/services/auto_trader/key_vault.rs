@@ -0,0 +1,272 @@
+//! Key vault - opt-in at-rest wrapping of auto-trading keys
+//!
+//! `KeyStore` only holds keys in memory, so a restart stops auto-trading and Mint
+//! Maker until every user unlocks again. With a server master key configured
+//! (`KEY_VAULT_MASTER_KEY` or `KEY_VAULT_MASTER_KEY_FILE`), each unlocked key is
+//! also wrapped with AES-256-GCM and persisted, and wallets that still have a
+//! feature enabled are unlocked again at startup.
+//!
+//! Every wrap, unwrap, discard and purge is written to `key_audit_log`. While the
+//! kill-switch file (`KEY_VAULT_KILL_SWITCH`) exists, all wrapped keys are purged
+//! and nothing is wrapped or resumed.
+
+use crate::db::Database;
+use crate::Config;
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, Key, KeyInit, Nonce,
+};
+use anyhow::{Context, Result};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+use zeroize::Zeroizing;
+
+/// Server master key wrapping per-wallet keys at rest
+pub struct KeyVault {
+    db: Arc<Database>,
+    master_key: Zeroizing<[u8; 32]>,
+    /// Fingerprint of the master key, stored with each wrapped key
+    master_key_id: String,
+    kill_switch: Option<PathBuf>,
+}
+
+impl KeyVault {
+    pub fn new(db: Arc<Database>, master_key: Zeroizing<[u8; 32]>, kill_switch: Option<PathBuf>) -> Self {
+        let master_key_id = fingerprint(&master_key);
+        Self {
+            db,
+            master_key,
+            master_key_id,
+            kill_switch,
+        }
+    }
+
+    /// Vault from `KEY_VAULT_MASTER_KEY` / `KEY_VAULT_MASTER_KEY_FILE`, `None` when neither is set
+    pub fn from_config(config: &Config, db: Arc<Database>) -> Result<Option<Self>> {
+        let raw: Zeroizing<Vec<u8>> = if let Some(hex_key) = &config.key_vault_master_key {
+            Zeroizing::new(hex_key.as_bytes().to_vec())
+        } else if let Some(path) = &config.key_vault_master_key_file {
+            Zeroizing::new(
+                std::fs::read(path)
+                    .with_context(|| format!("Failed to read key vault master key file {}", path))?,
+            )
+        } else {
+            return Ok(None);
+        };
+
+        let master_key = parse_master_key(&raw)?;
+        let vault = Self::new(db, master_key, config.key_vault_kill_switch.as_ref().map(PathBuf::from));
+        info!(
+            "[KeyVault] Enabled with master key {} (kill switch: {})",
+            vault.master_key_id,
+            vault
+                .kill_switch
+                .as_deref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "none".to_string())
+        );
+        Ok(Some(vault))
+    }
+
+    /// Kill-switch file path, if configured
+    pub fn kill_switch(&self) -> Option<&Path> {
+        self.kill_switch.as_deref()
+    }
+
+    /// Whether the kill-switch file is present
+    pub fn kill_switch_engaged(&self) -> bool {
+        self.kill_switch.as_deref().is_some_and(Path::exists)
+    }
+
+    /// Wrap and persist a wallet's key
+    pub async fn wrap(&self, wallet_address: &str, private_key: &str) -> Result<()> {
+        if self.kill_switch_engaged() {
+            warn!("[KeyVault] Kill switch engaged, not wrapping key for {}", wallet_address);
+            return Ok(());
+        }
+
+        let (ciphertext, nonce) = seal(&self.master_key, wallet_address, private_key)?;
+        self.db.key_vault()
+            .store_wrapped_key(wallet_address, &ciphertext, &nonce, &self.master_key_id)
+            .await?;
+        self.db.key_vault()
+            .log_key_audit(Some(wallet_address), "wrap", Some(&self.master_key_id))
+            .await?;
+        info!("[KeyVault] Wrapped key for {}", wallet_address);
+        Ok(())
+    }
+
+    /// Delete a wallet's wrapped key
+    pub async fn forget(&self, wallet_address: &str) -> Result<()> {
+        if self.db.key_vault().delete_wrapped_key(wallet_address).await? {
+            self.db.key_vault().log_key_audit(Some(wallet_address), "delete", None).await?;
+            info!("[KeyVault] Deleted wrapped key for {}", wallet_address);
+        }
+        Ok(())
+    }
+
+    /// Unwrap the keys of every wallet that still has auto-trading or Mint Maker
+    /// enabled (`reason` goes to the audit log). Wrapped keys of other wallets are
+    /// discarded without unwrapping.
+    pub async fn unwrap_enabled(&self, reason: &str) -> Result<Vec<(String, Zeroizing<String>)>> {
+        let mut enabled: HashSet<String> = HashSet::new();
+        enabled.extend(self.db.auto_trading().get_auto_trading_enabled_wallets().await?);
+        enabled.extend(self.db.mint_maker().get_mint_maker_enabled_wallets().await?);
+        let enabled: HashSet<String> = enabled.into_iter().map(|w| w.to_lowercase()).collect();
+
+        let mut keys = Vec::new();
+        for row in self.db.key_vault().get_wrapped_keys().await? {
+            let wallet_address = row.wallet_address;
+
+            if !enabled.contains(&wallet_address) {
+                self.db.key_vault().delete_wrapped_key(&wallet_address).await?;
+                self.db.key_vault()
+                    .log_key_audit(Some(&wallet_address), "discard", Some("no auto feature enabled"))
+                    .await?;
+                info!("[KeyVault] Discarded wrapped key for {} (nothing enabled)", wallet_address);
+                continue;
+            }
+
+            if row.master_key_id != self.master_key_id {
+                let detail = format!("wrapped with master key {}", row.master_key_id);
+                self.db.key_vault()
+                    .log_key_audit(Some(&wallet_address), "unwrap_failed", Some(&detail))
+                    .await?;
+                warn!("[KeyVault] Cannot unwrap key for {}: {}", wallet_address, detail);
+                continue;
+            }
+
+            match open(&self.master_key, &wallet_address, &row.ciphertext, &row.nonce) {
+                Ok(private_key) => {
+                    self.db.key_vault()
+                        .log_key_audit(Some(&wallet_address), "unwrap", Some(reason))
+                        .await?;
+                    warn!("[KeyVault] Unwrapped key for {} ({})", wallet_address, reason);
+                    keys.push((wallet_address, private_key));
+                }
+                Err(e) => {
+                    self.db.key_vault()
+                        .log_key_audit(Some(&wallet_address), "unwrap_failed", Some(&e.to_string()))
+                        .await?;
+                    warn!("[KeyVault] Failed to unwrap key for {}: {}", wallet_address, e);
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Delete every wrapped key. Returns how many were purged.
+    pub async fn purge(&self, reason: &str) -> Result<u64> {
+        let count = self.db.key_vault().delete_all_wrapped_keys().await?;
+        let detail = format!("{} ({} keys)", reason, count);
+        self.db.key_vault().log_key_audit(None, "purge", Some(&detail)).await?;
+        warn!("[KeyVault] Purged {} wrapped keys: {}", count, reason);
+        Ok(count)
+    }
+}
+
+/// Parse a master key: 32 raw bytes, or 64 hex chars (optionally 0x-prefixed, surrounding whitespace ignored)
+fn parse_master_key(raw: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; 32]);
+
+    if raw.len() == 32 {
+        key.copy_from_slice(raw);
+        return Ok(key);
+    }
+
+    let text = std::str::from_utf8(raw).context("Master key is neither 32 raw bytes nor hex")?;
+    let text = text.trim();
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    let decoded = Zeroizing::new(hex::decode(text).context("Master key is not valid hex")?);
+    if decoded.len() != 32 {
+        anyhow::bail!("Master key must be 32 bytes, got {}", decoded.len());
+    }
+    key.copy_from_slice(&decoded);
+    Ok(key)
+}
+
+/// Short fingerprint identifying a master key (never the key itself)
+fn fingerprint(master_key: &[u8; 32]) -> String {
+    let digest = Sha256::digest(master_key);
+    hex::encode(&digest[..8])
+}
+
+/// Encrypt a private key. The wallet address is bound as associated data, so a
+/// wrapped key can't be swapped onto another wallet's row.
+fn seal(master_key: &[u8; 32], wallet_address: &str, private_key: &str) -> Result<(String, String)> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(master_key));
+
+    let mut nonce_bytes = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let aad = wallet_address.to_lowercase();
+    let ciphertext = cipher
+        .encrypt(nonce, Payload { msg: private_key.as_bytes(), aad: aad.as_bytes() })
+        .map_err(|e| anyhow::anyhow!("Failed to wrap key: {}", e))?;
+
+    Ok((hex::encode(ciphertext), hex::encode(nonce_bytes)))
+}
+
+/// Decrypt a key produced by `seal`
+fn open(master_key: &[u8; 32], wallet_address: &str, ciphertext: &str, nonce: &str) -> Result<Zeroizing<String>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(master_key));
+
+    let ciphertext = hex::decode(ciphertext).context("Invalid wrapped key encoding")?;
+    let nonce_bytes = hex::decode(nonce).context("Invalid nonce encoding")?;
+    if nonce_bytes.len() != 12 {
+        anyhow::bail!("Invalid nonce length");
+    }
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let aad = wallet_address.to_lowercase();
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(nonce, Payload { msg: &ciphertext, aad: aad.as_bytes() })
+            .map_err(|_| anyhow::anyhow!("Wrapped key failed authentication"))?,
+    );
+
+    Ok(Zeroizing::new(
+        String::from_utf8(plaintext.to_vec()).context("Wrapped key is not valid UTF-8")?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_seal_open_round_trip() {
+        let master = [7u8; 32];
+        let (ciphertext, nonce) = seal(&master, "0xABC", TEST_KEY).unwrap();
+        let opened = open(&master, "0xabc", &ciphertext, &nonce).unwrap();
+        assert_eq!(opened.as_str(), TEST_KEY);
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_wallet_or_key() {
+        let master = [7u8; 32];
+        let (ciphertext, nonce) = seal(&master, "0xabc", TEST_KEY).unwrap();
+        assert!(open(&master, "0xdef", &ciphertext, &nonce).is_err());
+        assert!(open(&[8u8; 32], "0xabc", &ciphertext, &nonce).is_err());
+    }
+
+    #[test]
+    fn test_parse_master_key() {
+        let hex_key = "11".repeat(32);
+        assert_eq!(*parse_master_key(hex_key.as_bytes()).unwrap(), [0x11u8; 32]);
+        assert_eq!(
+            *parse_master_key(format!("0x{}\n", hex_key).as_bytes()).unwrap(),
+            [0x11u8; 32]
+        );
+        assert_eq!(*parse_master_key(&[5u8; 32]).unwrap(), [5u8; 32]);
+        assert!(parse_master_key(b"abcd").is_err());
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides automated trading functionality:
 //! - Position monitoring for take-profit/stop-loss/trailing-stop
 //! - Auto-buying opportunities based on configured criteria
+//! - Neg-risk basket arbitrage with leg rollback
 //! - Activity logging and statistics
 
 pub mod auto_buyer;
@@ -11,6 +12,8 @@ pub mod config;
 pub mod dispute_sniper;
 pub mod executor;
 pub mod key_store;
+pub mod key_vault;
+pub mod neg_risk_arb;
 pub mod position_monitor;
 pub mod types;
 
@@ -20,5 +23,7 @@ pub use dispute_sniper::DisputeSniper;
 pub use config::{AutoTradingSettings, UpdateSettingsRequest};
 pub use executor::AutoTradingExecutor;
 pub use key_store::KeyStore;
+pub use key_vault::KeyVault;
+pub use neg_risk_arb::NegRiskArbExecutor;
 pub use position_monitor::{PositionMonitor, SellSignal};
 pub use types::{AutoTradeLog, AutoTradingStats, ExitTrigger, PositionPeak};
@@ -0,0 +1,580 @@
+//! Neg-Risk Arb Executor - buys mispriced neg-risk baskets
+//!
+//! Periodically screens the cached neg-risk events for YES/NO baskets that pay
+//! more than they cost after fees, then for each wallet with auto-buy on and the
+//! "neg_risk_arb" strategy selected:
+//! - Re-prices every leg against the CLOB asks and sizes the basket by depth and budget
+//! - Buys the same share count on every leg with FOK limit orders at the sized
+//!   worst ask, thinnest book first, and sells any excess a leg filled
+//! - If a leg fails, sells back the legs already filled so no naked leg is left
+//!
+//! Basket legs are recorded as NegRiskArb positions, which the position monitor
+//! never sells on price; they are held to resolution.
+
+use super::key_store::KeyStore;
+use super::types::AutoTradeLog;
+use crate::db::Database;
+use crate::services::clob_pool::{ClobAccount, ClobClientPool};
+use crate::services::rate_limiter::EndpointClass;
+use crate::services::signer::WalletSigner;
+use crate::strategies::neg_risk_arb::{fill_cost, NegRiskArbStrategy, SizedBasket};
+use crate::types::{
+    ArbBasket, BasketLeg, NegRiskEvent, Order, OrderLifecycleStatus, StrategyType,
+};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+use tracing::{debug, info, warn};
+
+use alloy::primitives::U256;
+use polymarket_client_sdk::clob::types::{Amount, OrderType, Side as ClobSide};
+
+const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
+/// Strategy name wallets opt in with (settings.strategies)
+const STRATEGY_NAME: &str = "neg_risk_arb";
+
+/// What a FOK order actually matched
+#[derive(Debug)]
+struct LegFill {
+    order_id: String,
+    shares: Decimal,
+    usdc: Decimal,
+}
+
+/// A FOK order for one leg
+#[derive(Debug, Clone, Copy)]
+enum LegOrder {
+    /// Buy exactly `shares`, paying at most `limit_price` each (the sized worst ask)
+    Buy { shares: Decimal, limit_price: Decimal },
+    /// Sell `shares` at market
+    Sell { shares: Decimal },
+}
+
+/// A leg bought so far, kept for rollback
+struct FilledLeg {
+    leg: BasketLeg,
+    /// USDC actually spent
+    cost: Decimal,
+    /// Shares actually received
+    shares: Decimal,
+    order_id: String,
+}
+
+/// Neg-risk basket arbitrage executor
+pub struct NegRiskArbExecutor {
+    db: Arc<Database>,
+    key_store: KeyStore,
+    clob_pool: Arc<ClobClientPool>,
+    strategy: NegRiskArbStrategy,
+    events: Arc<RwLock<Vec<NegRiskEvent>>>,
+    client: reqwest::Client,
+}
+
+impl NegRiskArbExecutor {
+    pub fn new(
+        db: Arc<Database>,
+        key_store: KeyStore,
+        clob_pool: Arc<ClobClientPool>,
+        strategy: NegRiskArbStrategy,
+        events: Arc<RwLock<Vec<NegRiskEvent>>>,
+    ) -> Self {
+        Self {
+            db,
+            key_store,
+            clob_pool,
+            strategy,
+            events,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Run the executor loop until `stop` is set
+    pub async fn run(self, mut stop: watch::Receiver<bool>) {
+        let interval = Duration::from_secs(self.strategy.config().scan_interval_seconds);
+        info!("Neg-risk arb executor started (every {}s)", interval.as_secs());
+
+        loop {
+            if let Err(e) = self.scan().await {
+                warn!("[NegRisk Arb] Scan error: {}", e);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = stop.changed() => break,
+            }
+        }
+    }
+
+    /// Screen cached events and try the best basket for each enabled wallet
+    async fn scan(&self) -> Result<()> {
+        let baskets = {
+            let events = self.events.read().await;
+            self.strategy.find_baskets(&events)
+        };
+        if baskets.is_empty() {
+            return Ok(());
+        }
+        debug!("[NegRisk Arb] {} candidate baskets", baskets.len());
+
+        for wallet_address in self.db.auto_trading().get_auto_trading_enabled_wallets().await? {
+            if let Err(e) = self.process_for_wallet(&wallet_address, &baskets).await {
+                warn!("[NegRisk Arb] Error for wallet {}: {}", wallet_address, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn process_for_wallet(&self, wallet_address: &str, baskets: &[ArbBasket]) -> Result<()> {
+        let settings = self.db.auto_trading().get_auto_trading_settings(wallet_address).await?;
+        if !settings.enabled
+            || !settings.auto_buy_enabled
+            || !settings.strategies.iter().any(|s| s == STRATEGY_NAME) {
+            return Ok(());
+        }
+
+        let current_exposure = self.db.positions().get_total_exposure(wallet_address).await?;
+        let budget = settings
+            .position_size
+            .min(settings.max_total_exposure - current_exposure);
+        if budget <= Decimal::ZERO {
+            debug!("[NegRisk Arb] Wallet {} at max exposure", wallet_address);
+            return Ok(());
+        }
+
+        let Some(signer) = self.key_store.signer(wallet_address).await else {
+            debug!("[NegRisk Arb] No key in KeyStore for {}", wallet_address);
+            return Ok(());
+        };
+
+        for basket in baskets {
+            // Skip events where this wallet already holds a leg
+            let mut held = false;
+            for leg in &basket.legs {
+                if self.db.positions().has_open_position(wallet_address, &leg.condition_id).await? {
+                    held = true;
+                    break;
+                }
+            }
+            if held {
+                continue;
+            }
+
+            let Some(asks) = self.fetch_asks(&basket.legs).await else {
+                debug!("[NegRisk Arb] Missing orderbook for a leg of {}", basket.title);
+                continue;
+            };
+            let Some(sized) = self.strategy.size_basket(basket, &asks, budget) else {
+                debug!("[NegRisk Arb] {} {:?} basket no longer profitable at depth", basket.title, basket.side);
+                continue;
+            };
+
+            info!(
+                "[NegRisk Arb] BUY {:?} basket on {} ({} legs x {} shares, cost ${:.2} + ${:.2} fees, expected profit ${:.2})",
+                basket.side,
+                basket.title,
+                basket.legs.len(),
+                sized.shares,
+                sized.total_cost,
+                sized.total_fees,
+                sized.expected_profit
+            );
+
+            self.execute_basket(wallet_address, &signer, basket, &sized, &asks).await?;
+
+            // Only one basket per pass
+            break;
+        }
+
+        Ok(())
+    }
+
+    /// Buy every leg, thinnest book first; roll back filled legs if one fails
+    async fn execute_basket(
+        &self,
+        wallet_address: &str,
+        signer: &WalletSigner,
+        basket: &ArbBasket,
+        sized: &SizedBasket,
+        asks: &[Vec<(Decimal, Decimal)>],
+    ) -> Result<()> {
+        // The leg most likely to fail goes first, so a failure costs the fewest rollbacks
+        let mut leg_order: Vec<usize> = (0..basket.legs.len()).collect();
+        leg_order.sort_by_key(|&i| asks[i].iter().map(|(_, size)| *size).sum::<Decimal>());
+
+        let mut filled: Vec<FilledLeg> = Vec::with_capacity(leg_order.len());
+        for i in leg_order {
+            let leg = &basket.legs[i];
+            let order = LegOrder::Buy {
+                shares: sized.shares,
+                limit_price: sized.leg_limits[i],
+            };
+
+            match self.post_fok(signer, &leg.token_id, order).await {
+                Ok(fill) => {
+                    info!(
+                        "[NegRisk Arb] Leg {} filled: {} ({} shares for ${:.4})",
+                        leg.label, fill.order_id, fill.shares, fill.usdc
+                    );
+                    filled.push(FilledLeg {
+                        leg: leg.clone(),
+                        cost: fill.usdc,
+                        shares: fill.shares,
+                        order_id: fill.order_id,
+                    });
+                }
+                Err(e) => {
+                    warn!(
+                        "[NegRisk Arb] Leg {} failed after {} of {} legs: {} - rolling back",
+                        leg.label, filled.len(), basket.legs.len(), e
+                    );
+                    let reason = e.to_string();
+
+                    // Sell back what already filled so no naked leg is left
+                    for f in &filled {
+                        let sold = self
+                            .post_fok(signer, &f.leg.token_id, LegOrder::Sell { shares: f.shares })
+                            .await;
+                        self.record_rollback(wallet_address, basket, f, &reason, sold).await?;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+
+        // The payout only covers as many sets as the smallest leg holds; sell the rest
+        let fills: Vec<Decimal> = filled.iter().map(|f| f.shares).collect();
+        for (f, excess) in filled.iter_mut().zip(excess_shares(&fills)) {
+            if excess <= Decimal::ZERO {
+                continue;
+            }
+            match self.post_fok(signer, &f.leg.token_id, LegOrder::Sell { shares: excess }).await {
+                Ok(sold) => {
+                    let trimmed = FilledLeg {
+                        leg: f.leg.clone(),
+                        cost: f.cost * sold.shares / f.shares,
+                        shares: sold.shares,
+                        order_id: f.order_id.clone(),
+                    };
+                    self.record_sell_back(
+                        wallet_address,
+                        basket,
+                        &trimmed,
+                        "neg_risk_arb_trim",
+                        &sold,
+                        format!("Leg filled {} more shares than the basket", excess),
+                    )
+                    .await?;
+                    f.cost -= trimmed.cost;
+                    f.shares -= trimmed.shares;
+                }
+                Err(e) => warn!(
+                    "[NegRisk Arb] Failed to trim {} excess shares of leg {}: {} - keeping them",
+                    excess, f.leg.label, e
+                ),
+            }
+        }
+
+        for f in &filled {
+            self.record_leg(wallet_address, basket, f, "neg_risk_arb_buy", format!(
+                "{:?} basket on {}: {} legs cost ${:.4}/set vs ${} payout, expected profit ${:.2}",
+                basket.side,
+                basket.title,
+                basket.legs.len(),
+                (sized.total_cost + sized.total_fees) / sized.shares,
+                basket.payout_per_set,
+                sized.expected_profit
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Post a FOK order for a leg. Errors unless the order went through and matched something.
+    async fn post_fok(&self, signer: &WalletSigner, token_id: &str, order: LegOrder) -> Result<LegFill> {
+        let token_id = U256::from_str_radix(token_id, 10)
+            .context("Failed to parse token ID")?;
+
+        let response = self
+            .clob_pool
+            .run(signer, ClobAccount::Eoa, EndpointClass::PostOrder, |c| async move {
+                let order = match order {
+                    LegOrder::Buy { shares, limit_price } => c.client
+                        .limit_order()
+                        .token_id(token_id)
+                        .size(shares)
+                        .price(limit_price)
+                        .side(ClobSide::Buy)
+                        .order_type(OrderType::FOK)
+                        .build()
+                        .await
+                        .context("Failed to build order")?,
+                    LegOrder::Sell { shares } => c.client
+                        .market_order()
+                        .token_id(token_id)
+                        .amount(Amount::shares(shares).context("Failed to create shares amount")?)
+                        .side(ClobSide::Sell)
+                        .order_type(OrderType::FOK)
+                        .build()
+                        .await
+                        .context("Failed to build order")?,
+                };
+                let signed_order = c.client
+                    .sign(&c.signer, order)
+                    .await
+                    .context("Failed to sign order")?;
+                c.client
+                    .post_order(signed_order)
+                    .await
+                    .context("Failed to submit order")
+            })
+            .await?;
+
+        if !response.success {
+            anyhow::bail!(
+                "Order rejected: {}",
+                response.error_msg.unwrap_or_else(|| "unknown error".to_string())
+            );
+        }
+        let buy = matches!(order, LegOrder::Buy { .. });
+        leg_fill(&response.order_id, buy, response.making_amount, response.taking_amount)
+            .with_context(|| format!("Order {} matched nothing", response.order_id))
+    }
+
+    /// Log a rolled-back leg with what selling it back realised, or keep it as a
+    /// position if the sell-back failed
+    async fn record_rollback(
+        &self,
+        wallet_address: &str,
+        basket: &ArbBasket,
+        f: &FilledLeg,
+        reason: &str,
+        sold: Result<LegFill>,
+    ) -> Result<()> {
+        match sold {
+            Ok(sold) => {
+                info!(
+                    "[NegRisk Arb] Rolled back leg {} (${:.4} back for ${:.4})",
+                    f.leg.label, sold.usdc, f.cost
+                );
+                self.record_sell_back(
+                    wallet_address,
+                    basket,
+                    f,
+                    "neg_risk_arb_rollback",
+                    &sold,
+                    format!("Basket leg failed: {}", reason),
+                )
+                .await
+            }
+            Err(e) => {
+                warn!("[NegRisk Arb] Rollback of leg {} failed: {} - keeping as position", f.leg.label, e);
+                self.record_leg(
+                    wallet_address,
+                    basket,
+                    f,
+                    "neg_risk_arb_rollback_failed",
+                    format!("Basket leg failed ({}), rollback sell failed: {}", reason, e),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Log leg shares `f` sold back as `sold`; the pnl is the spread and fees
+    /// lost on the round trip
+    async fn record_sell_back(
+        &self,
+        wallet_address: &str,
+        basket: &ArbBasket,
+        f: &FilledLeg,
+        action: &str,
+        sold: &LegFill,
+        reason: String,
+    ) -> Result<()> {
+        let log = AutoTradeLog {
+            id: None,
+            wallet_address: wallet_address.to_string(),
+            position_id: None,
+            action: action.to_string(),
+            market_question: Some(format!("{} - {}", basket.title, f.leg.label)),
+            side: Some(format!("{:?}", basket.side)),
+            entry_price: Some(f.cost / f.shares),
+            exit_price: Some(sold.usdc / sold.shares),
+            size: Some(f.cost),
+            pnl: Some(sold.usdc - f.cost),
+            trigger_reason: Some(reason),
+            created_at: Utc::now(),
+        };
+        self.db.auto_trading().log_auto_trade(&log).await?;
+        Ok(())
+    }
+
+    /// Record a filled leg as order + position + log entry
+    async fn record_leg(
+        &self,
+        wallet_address: &str,
+        basket: &ArbBasket,
+        f: &FilledLeg,
+        action: &str,
+        reason: String,
+    ) -> Result<()> {
+        let entry_price = f.cost / f.shares;
+        let question = format!("{} - {}", basket.title, f.leg.label);
+
+        let order = Order {
+            id: f.order_id.clone(),
+            wallet_address: wallet_address.to_string(),
+            token_id: f.leg.token_id.clone(),
+            market_id: Some(f.leg.condition_id.clone()),
+            side: basket.side,
+            order_type: "FOK".to_string(),
+            price: entry_price,
+            original_size: f.cost,
+            filled_size: f.cost, // What the order actually matched
+            avg_fill_price: Some(entry_price),
+            status: OrderLifecycleStatus::Confirmed,
+            position_id: None,
+            neg_risk: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        if let Err(e) = self.db.orders().create_order(&order).await {
+            warn!("[NegRisk Arb] Failed to create order record: {}", e);
+        }
+
+        let position_id = self
+            .db
+            .positions().create_position_for_wallet(
+                wallet_address,
+                &f.leg.condition_id,
+                &question,
+                None,
+                basket.side,
+                entry_price,
+                f.cost,
+                StrategyType::NegRiskArb,
+                false, // Live trade
+                None,  // end_date
+                Some(&f.leg.token_id),
+                Some(&f.order_id),
+                true,
+            )
+            .await?;
+
+        let log = AutoTradeLog {
+            id: None,
+            wallet_address: wallet_address.to_string(),
+            position_id: Some(position_id),
+            action: action.to_string(),
+            market_question: Some(question),
+            side: Some(format!("{:?}", basket.side)),
+            entry_price: Some(entry_price),
+            exit_price: None,
+            size: Some(f.cost),
+            pnl: None,
+            trigger_reason: Some(reason),
+            created_at: Utc::now(),
+        };
+        self.db.auto_trading().log_auto_trade(&log).await?;
+
+        Ok(())
+    }
+
+    /// Fetch ask levels (best first) for every leg; None if any book is unavailable or empty
+    async fn fetch_asks(&self, legs: &[BasketLeg]) -> Option<Vec<Vec<(Decimal, Decimal)>>> {
+        let mut books = Vec::with_capacity(legs.len());
+        for leg in legs {
+            let url = format!("{}/book?token_id={}", CLOB_ENDPOINT, leg.token_id);
+            let resp = self.client
+                .get(&url)
+                .timeout(Duration::from_secs(5))
+                .send()
+                .await
+                .ok()?;
+            if !resp.status().is_success() {
+                return None;
+            }
+            let book: OrderbookResponse = resp.json().await.ok()?;
+
+            let mut asks: Vec<(Decimal, Decimal)> = book
+                .asks
+                .iter()
+                .filter_map(|l| Some((Decimal::from_str(&l.price).ok()?, Decimal::from_str(&l.size).ok()?)))
+                .filter(|(price, size)| *price > Decimal::ZERO && *size > Decimal::ZERO)
+                .collect();
+            asks.sort_by(|a, b| a.0.cmp(&b.0));
+
+            // Need at least one share to buy
+            fill_cost(&asks, Decimal::ONE)?;
+            books.push(asks);
+        }
+        Some(books)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderbookResponse {
+    #[serde(default)]
+    asks: Vec<OrderbookLevel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderbookLevel {
+    price: String,
+    size: String,
+}
+
+/// Fill implied by a post-order response (buy: making = USDC, taking = shares;
+/// sell: the reverse). None unless both sides matched something.
+fn leg_fill(order_id: &str, buy: bool, making: Decimal, taking: Decimal) -> Option<LegFill> {
+    let (usdc, shares) = if buy { (making, taking) } else { (taking, making) };
+    (usdc > Decimal::ZERO && shares > Decimal::ZERO).then(|| LegFill {
+        order_id: order_id.to_string(),
+        shares,
+        usdc,
+    })
+}
+
+/// Shares each leg holds beyond the smallest leg, which the basket payout doesn't cover
+fn excess_shares(shares: &[Decimal]) -> Vec<Decimal> {
+    let sets = shares.iter().copied().min().unwrap_or_default();
+    shares.iter().map(|s| *s - sets).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_leg_fill() {
+        let buy = leg_fill("0xabc", true, dec!(4.60), dec!(15)).unwrap();
+        assert_eq!(buy.order_id, "0xabc");
+        assert_eq!(buy.usdc, dec!(4.60));
+        assert_eq!(buy.shares, dec!(15));
+
+        let sell = leg_fill("0xdef", false, dec!(15), dec!(4.20)).unwrap();
+        assert_eq!(sell.shares, dec!(15));
+        assert_eq!(sell.usdc, dec!(4.20));
+
+        // Accepted but nothing matched
+        assert!(leg_fill("0xabc", true, Decimal::ZERO, Decimal::ZERO).is_none());
+        assert!(leg_fill("0xabc", true, dec!(5), Decimal::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_excess_shares_over_smallest_leg() {
+        assert_eq!(excess_shares(&[dec!(50), dec!(50), dec!(50)]), vec![Decimal::ZERO; 3]);
+        assert_eq!(
+            excess_shares(&[dec!(50), dec!(48.5), dec!(51)]),
+            vec![dec!(1.5), Decimal::ZERO, dec!(2.5)]
+        );
+        assert!(excess_shares(&[]).is_empty());
+    }
+}
@@ -9,12 +9,13 @@
 use super::types::{ExitTrigger, PositionPeak};
 use crate::db::Database;
 use crate::services::price_ws::PriceUpdate;
+use crate::types::StrategyType;
 use anyhow::Result;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, RwLock};
 use tracing::{debug, info, warn};
 
 /// Sell signal sent to the auto-seller
@@ -47,15 +48,21 @@ impl PositionMonitor {
     /// Run the position monitor
     ///
     /// Listens for price updates and checks all positions against their triggers
+    /// until `stop` is set
     pub async fn run(
         &self,
         mut price_rx: broadcast::Receiver<PriceUpdate>,
         sell_tx: mpsc::Sender<SellSignal>,
+        mut stop: watch::Receiver<bool>,
     ) {
         info!("Position monitor started");
 
         loop {
-            match price_rx.recv().await {
+            let received = tokio::select! {
+                received = price_rx.recv() => received,
+                _ = stop.changed() => break,
+            };
+            match received {
                 Ok(update) => {
                     if let Err(e) = self.check_positions(&update, &sell_tx).await {
                         warn!("Error checking positions: {}", e);
@@ -81,15 +88,25 @@ impl PositionMonitor {
         let current_price = Decimal::from_str(&update.price)?;
 
         // Get all open positions with this token_id
-        let positions = self.db.get_positions_by_token_id(&update.token_id).await?;
+        let positions = self.db.positions().get_positions_by_token_id(&update.token_id).await?;
 
         for position in positions {
             // Get auto-trading settings for this wallet
-            let settings = self.db.get_auto_trading_settings(&position.wallet_address).await?;
+            let settings = self.db.auto_trading().get_auto_trading_settings(&position.wallet_address).await?;
             if !settings.enabled {
                 continue; // Auto-trading not enabled for this wallet
             }
 
+            // Dispute positions held to settlement are redeemed by the DisputeSniper, not sold
+            if position.strategy == StrategyType::Dispute && settings.dispute_hold_to_settlement {
+                continue;
+            }
+
+            // Basket legs only pay off together; selling one on price would unhedge the rest
+            if position.strategy == StrategyType::NegRiskArb {
+                continue;
+            }
+
             let entry_price = position.entry_price;
             let pnl_percent = (current_price - entry_price) / entry_price;
 
@@ -171,7 +188,7 @@ impl PositionMonitor {
                     peak.peak_at = chrono::Utc::now();
 
                     // Also persist to DB
-                    if let Err(e) = self.db.update_position_peak(position.id, current_price).await {
+                    if let Err(e) = self.db.positions().update_position_peak(position.id, current_price).await {
                         warn!("Failed to persist peak price: {}", e);
                     }
                 }
@@ -245,7 +262,7 @@ impl PositionMonitor {
 
     /// Load peak prices from database on startup
     pub async fn load_peaks(&self) -> Result<()> {
-        let db_peaks = self.db.get_all_position_peaks().await?;
+        let db_peaks = self.db.positions().get_all_position_peaks().await?;
         let mut peaks = self.peaks.write().await;
 
         for p in db_peaks {
@@ -29,6 +29,21 @@ pub enum ExitTrigger {
         price: Decimal,
         new_status: String,
     },
+    DisputeDvmTally {
+        price: Decimal,
+        /// Share of revealed DVM votes supporting the position's side
+        vote_share: f64,
+    },
+    DisputeConvergence {
+        price: Decimal,
+        target: Decimal,
+    },
+    DisputeCounterProposal {
+        price: Decimal,
+        /// Outcome the counter-proposal asserts
+        counter_outcome: String,
+        minutes_left: i64,
+    },
 }
 
 impl ExitTrigger {
@@ -40,6 +55,9 @@ impl ExitTrigger {
             ExitTrigger::TrailingStop { .. } => "trailing_stop".to_string(),
             ExitTrigger::TimeExit { .. } => "time_exit".to_string(),
             ExitTrigger::DisputeEscalation { .. } => "dispute_exit".to_string(),
+            ExitTrigger::DisputeDvmTally { .. } => "dispute_dvm_exit".to_string(),
+            ExitTrigger::DisputeConvergence { .. } => "dispute_take_profit".to_string(),
+            ExitTrigger::DisputeCounterProposal { .. } => "dispute_liveness_exit".to_string(),
         }
     }
 
@@ -65,6 +83,18 @@ impl ExitTrigger {
             ExitTrigger::DisputeEscalation { new_status, .. } => {
                 format!("Dispute escalated to {}", new_status)
             }
+            ExitTrigger::DisputeDvmTally { vote_share, .. } => {
+                format!("DVM reveal tally only {:.0}% for position side", vote_share * 100.0)
+            }
+            ExitTrigger::DisputeConvergence { price, target } => {
+                format!("Held outcome converged to {} (target {})", price, target)
+            }
+            ExitTrigger::DisputeCounterProposal { counter_outcome, minutes_left, .. } => {
+                format!(
+                    "Counter-proposal for {} passes liveness in {} min",
+                    counter_outcome, minutes_left
+                )
+            }
         }
     }
 
@@ -76,6 +106,9 @@ impl ExitTrigger {
             ExitTrigger::TrailingStop { price, .. } => *price,
             ExitTrigger::TimeExit { price, .. } => *price,
             ExitTrigger::DisputeEscalation { price, .. } => *price,
+            ExitTrigger::DisputeDvmTally { price, .. } => *price,
+            ExitTrigger::DisputeConvergence { price, .. } => *price,
+            ExitTrigger::DisputeCounterProposal { price, .. } => *price,
         }
     }
 }
@@ -0,0 +1,323 @@
+//! CLOB Client Pool - authenticated CLOB clients shared across services
+//!
+//! Authenticating a CLOB client derives (or creates) L2 API credentials, which
+//! costs an extra round-trip per order when done on every trade. The pool keeps
+//! one authenticated client per wallet and signature type:
+//! - Built lazily on first use, seeded from stored `api_credentials` when present
+//! - Newly derived credentials are stored for the next restart
+//! - On `ClobError::AuthenticationFailed` the client and stored credentials are
+//!   dropped and re-derived once before giving up
+//! - Every call goes through the shared `RateLimiter`
+
+use super::clob_errors::ClobError;
+use super::mint_maker::order_manager::ensure_clob_api_credentials;
+use super::rate_limiter::{EndpointClass, RateLimiter};
+use super::signer::WalletSigner;
+use crate::db::Database;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, warn};
+
+use alloy::primitives::Address;
+use alloy::signers::Signer;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::{Credentials, Normal};
+use polymarket_client_sdk::clob::types::SignatureType;
+use polymarket_client_sdk::clob::{Client as ClobClient, Config as ClobConfig};
+
+const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
+
+/// An authenticated CLOB client
+pub type AuthenticatedClobClient = ClobClient<Authenticated<Normal>>;
+
+/// Which address the CLOB treats as the order maker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClobAccount {
+    /// Orders signed and funded by the EOA itself
+    Eoa,
+    /// Orders funded by the wallet's Gnosis Safe proxy (Mint Maker)
+    GnosisSafe,
+}
+
+/// A pooled client paired with the caller's signer (the pool never caches signers)
+pub struct PooledClient {
+    pub client: Arc<AuthenticatedClobClient>,
+    pub signer: WalletSigner,
+}
+
+/// Clients keyed by EOA address and account type
+struct ClientCache<C> {
+    clients: RwLock<HashMap<(Address, ClobAccount), Arc<C>>>,
+    /// Serializes authentication so concurrent callers don't derive keys twice
+    auth_lock: Mutex<()>,
+}
+
+impl<C> ClientCache<C> {
+    fn new() -> Self {
+        Self {
+            clients: RwLock::new(HashMap::new()),
+            auth_lock: Mutex::new(()),
+        }
+    }
+
+    /// The cached client for `key`, or one built by `authenticate` on first use
+    async fn get_or_authenticate<F, Fut>(&self, key: (Address, ClobAccount), authenticate: F) -> Result<Arc<C>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<C>>,
+    {
+        if let Some(client) = self.clients.read().await.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let _guard = self.auth_lock.lock().await;
+        // Another caller may have authenticated while we waited
+        if let Some(client) = self.clients.read().await.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let client = Arc::new(authenticate().await?);
+        self.clients.write().await.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Drop every account's client for an address
+    async fn evict(&self, address: Address) {
+        self.clients.write().await.retain(|(a, _), _| *a != address);
+    }
+}
+
+/// Pool of authenticated CLOB clients keyed by EOA address and account type
+pub struct ClobClientPool {
+    db: Arc<Database>,
+    rate_limiter: Arc<RateLimiter>,
+    clients: ClientCache<AuthenticatedClobClient>,
+}
+
+impl ClobClientPool {
+    pub fn new(db: Arc<Database>, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            db,
+            rate_limiter,
+            clients: ClientCache::new(),
+        }
+    }
+
+    /// Get the authenticated client for a wallet signer, authenticating on first use
+    pub async fn client(&self, signer: &WalletSigner, account: ClobAccount) -> Result<Arc<PooledClient>> {
+        let client = self
+            .clients
+            .get_or_authenticate((signer.address(), account), || self.authenticate(signer, account))
+            .await?;
+        Ok(Arc::new(PooledClient { client, signer: signer.clone() }))
+    }
+
+    /// Drop the cached client and stored credentials for a wallet so the next call re-derives them
+    pub async fn invalidate(&self, signer: &WalletSigner) -> Result<()> {
+        let address = signer.address();
+        self.clients.evict(address).await;
+        self.db.auth().delete_api_credentials(&format!("{:?}", address)).await?;
+        info!("[CLOB Pool] Invalidated credentials for {:?}", address);
+        Ok(())
+    }
+
+    /// Invalidate a wallet's credentials if `error` is an authentication rejection.
+    ///
+    /// For callers that use `client` directly instead of `run`.
+    pub async fn invalidate_on_auth_error(&self, signer: &WalletSigner, error: &str) {
+        if !Self::is_auth_message(error) {
+            return;
+        }
+        if let Err(e) = self.invalidate(signer).await {
+            warn!("[CLOB Pool] Failed to invalidate credentials: {}", e);
+        }
+    }
+
+    /// Run a CLOB call with a pooled client, rate limited by `class`.
+    ///
+    /// If the call fails authentication, credentials are refreshed and the call
+    /// is retried once.
+    pub async fn run<T, F, Fut>(
+        &self,
+        signer: &WalletSigner,
+        account: ClobAccount,
+        class: EndpointClass,
+        f: F,
+    ) -> Result<T>
+    where
+        F: Fn(Arc<PooledClient>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let client = self.client(signer, account).await?;
+        self.rate_limiter.acquire(class).await;
+
+        match f(client).await {
+            Err(e) if Self::is_auth_error(&e) => {
+                warn!("[CLOB Pool] Authentication rejected ({}), refreshing credentials", e);
+                self.invalidate(signer).await?;
+                let client = self.client(signer, account).await?;
+                self.rate_limiter.acquire(class).await;
+                f(client).await
+            }
+            result => result,
+        }
+    }
+
+    async fn authenticate(
+        &self,
+        signer: &WalletSigner,
+        account: ClobAccount,
+    ) -> Result<AuthenticatedClobClient> {
+        let wallet_address = format!("{:?}", signer.address());
+        let (api_key, api_secret, api_passphrase) =
+            ensure_clob_api_credentials(signer, &self.db, &wallet_address)
+                .await
+                .context("Failed to load CLOB API credentials")?;
+        let credentials = Credentials::new(
+            uuid::Uuid::from_str(&api_key).context("Stored CLOB API key is not a UUID")?,
+            api_secret,
+            api_passphrase,
+        );
+
+        debug!("[CLOB Pool] Authenticating {} ({:?})", wallet_address, account);
+        self.rate_limiter.acquire(EndpointClass::General).await;
+
+        let clob_config = ClobConfig::builder().use_server_time(true).build();
+        let builder = ClobClient::new(CLOB_ENDPOINT, clob_config)
+            .context("Failed to create CLOB client")?
+            .authentication_builder(signer)
+            .credentials(credentials);
+        let builder = match account {
+            ClobAccount::Eoa => builder,
+            ClobAccount::GnosisSafe => builder.signature_type(SignatureType::GnosisSafe),
+        };
+        let client = builder
+            .authenticate()
+            .await
+            .context("Failed to authenticate with CLOB")?;
+
+        info!("[CLOB Pool] Authenticated {} ({:?})", wallet_address, account);
+        Ok(client)
+    }
+
+    fn is_auth_error(e: &anyhow::Error) -> bool {
+        Self::is_auth_message(&format!("{:#}", e))
+    }
+
+    fn is_auth_message(message: &str) -> bool {
+        matches!(
+            ClobError::from_response(0, message),
+            ClobError::AuthenticationFailed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    /// Stand-in client recording which authentication built it
+    struct Counted {
+        auths: Arc<AtomicU32>,
+    }
+
+    impl Counted {
+        fn cache() -> (ClientCache<u32>, Self) {
+            (ClientCache::new(), Self { auths: Arc::new(AtomicU32::new(0)) })
+        }
+
+        async fn get(&self, cache: &ClientCache<u32>, address: Address, account: ClobAccount) -> u32 {
+            let auths = self.auths.clone();
+            *cache
+                .get_or_authenticate((address, account), || async move { Ok(auths.fetch_add(1, Ordering::SeqCst) + 1) })
+                .await
+                .unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_reused_per_signer_and_account() {
+        let (cache, counted) = Counted::cache();
+
+        let first = counted.get(&cache, ALICE, ClobAccount::Eoa).await;
+        assert_eq!(counted.get(&cache, ALICE, ClobAccount::Eoa).await, first);
+        assert_eq!(counted.auths.load(Ordering::SeqCst), 1);
+
+        // Each (address, account) pair authenticates separately
+        let safe = counted.get(&cache, ALICE, ClobAccount::GnosisSafe).await;
+        let other = counted.get(&cache, BOB, ClobAccount::Eoa).await;
+        assert!(safe != first && other != first && safe != other);
+        assert_eq!(counted.auths.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_evict_drops_every_account_of_one_signer() {
+        let (cache, counted) = Counted::cache();
+        let eoa = counted.get(&cache, ALICE, ClobAccount::Eoa).await;
+        let safe = counted.get(&cache, ALICE, ClobAccount::GnosisSafe).await;
+        let bob = counted.get(&cache, BOB, ClobAccount::Eoa).await;
+
+        cache.evict(ALICE).await;
+
+        assert_ne!(counted.get(&cache, ALICE, ClobAccount::Eoa).await, eoa);
+        assert_ne!(counted.get(&cache, ALICE, ClobAccount::GnosisSafe).await, safe);
+        assert_eq!(counted.get(&cache, BOB, ClobAccount::Eoa).await, bob);
+        assert_eq!(counted.auths.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_callers_authenticate_once() {
+        let (cache, counted) = Counted::cache();
+        let (a, b) = tokio::join!(
+            counted.get(&cache, ALICE, ClobAccount::Eoa),
+            counted.get(&cache, ALICE, ClobAccount::Eoa),
+        );
+        assert_eq!(a, b);
+        assert_eq!(counted.auths.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_authentication_is_not_cached() {
+        let cache: ClientCache<u32> = ClientCache::new();
+        let failed = cache
+            .get_or_authenticate((ALICE, ClobAccount::Eoa), || async { anyhow::bail!("Unauthorized") })
+            .await;
+        assert!(failed.is_err());
+
+        let client = cache
+            .get_or_authenticate((ALICE, ClobAccount::Eoa), || async { Ok(7) })
+            .await
+            .unwrap();
+        assert_eq!(*client, 7);
+    }
+
+    #[tokio::test]
+    async fn test_order_burst_throttles_only_its_endpoint_class() {
+        let limiter = RateLimiter::new();
+        while limiter.try_acquire(EndpointClass::PostOrder).await {}
+
+        // Reads and cancels keep their own budget while order posts are exhausted
+        assert!(!limiter.try_acquire(EndpointClass::PostOrder).await);
+        assert!(limiter.try_acquire(EndpointClass::General).await);
+        assert!(limiter.try_acquire(EndpointClass::DeleteOrder).await);
+
+        // The next post waits for a refill instead of failing
+        assert!(limiter.acquire(EndpointClass::PostOrder).await);
+        assert!(!limiter.acquire(EndpointClass::DeleteOrder).await);
+    }
+
+    #[test]
+    fn test_auth_rejection_detected() {
+        assert!(ClobClientPool::is_auth_message(r#"{"message":"Unauthorized"}"#));
+        assert!(ClobClientPool::is_auth_message("invalid api key"));
+        assert!(!ClobClientPool::is_auth_message("not enough balance / allowance"));
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Supports splitting USDC collateral into YES + NO token pairs,
 //! merging YES + NO tokens back into USDC, and redeeming after resolution.
+//! For neg-risk events, also converts NO positions through the NegRisk Adapter.
 //!
 //! Uses the Polymarket relay service for gasless execution via Safe transactions.
 //! The relay does NOT have dedicated /split, /merge, /redeem endpoints.
@@ -9,7 +10,7 @@
 //! signed Safe transaction, and submit via the relay's /submit endpoint.
 
 use alloy::primitives::{keccak256, Address, B256, U256};
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use alloy::signers::Signer;
 use alloy::sol;
 use alloy::sol_types::SolCall;
 use anyhow::{Context, Result};
@@ -19,8 +20,14 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use sha2::Sha256;
 use std::str::FromStr;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use super::onchain_jobs::OnchainJobTracker;
+use super::safe_activation;
+use super::signer::WalletSigner;
+use crate::types::OnchainJobKind;
+
 type HmacSha256 = Hmac<Sha256>;
 
 const RELAY_URL: &str = "https://relayer-v2.polymarket.com";
@@ -60,6 +67,10 @@ sol! {
     );
 
     function balanceOf(address account, uint256 id) external view returns (uint256);
+
+    function payoutDenominator(bytes32 conditionId) external view returns (uint256);
+
+    function payoutNumerators(bytes32 conditionId, uint256 index) external view returns (uint256);
 }
 
 // NegRisk Adapter has a simplified mergePositions(bytes32, uint256) signature.
@@ -68,31 +79,37 @@ mod neg_risk_abi {
     alloy::sol! {
         function mergePositions(bytes32 conditionId, uint256 amount);
         function redeemPositions(bytes32 conditionId, uint256[] amounts);
+        function convertPositions(bytes32 marketId, uint256 indexSet, uint256 amount);
     }
 }
 
-/// Response from CTF operations
-#[derive(Debug, Deserialize)]
-pub struct CtfResponse {
-    pub success: bool,
-    #[serde(default)]
-    pub transaction_id: Option<String>,
-    #[serde(default)]
-    pub error: Option<String>,
+/// A single CTF / NegRisk Adapter call, sent alone or bundled into a MultiSend batch
+#[derive(Debug, Clone)]
+pub struct CtfCall {
+    pub to: Address,
+    pub data: Vec<u8>,
+    pub kind: OnchainJobKind,
+    /// Condition (or neg-risk market) the call acts on
+    pub reference: String,
+    /// Relay metadata / job description
+    pub description: String,
 }
 
 /// CTF service for split/merge/redeem operations via Safe relay
 pub struct CtfService {
     client: reqwest::Client,
+    /// Submitted relay transactions are tracked through the job queue
+    jobs: Arc<OnchainJobTracker>,
 }
 
 impl CtfService {
-    pub fn new() -> Self {
+    pub fn new(jobs: Arc<OnchainJobTracker>) -> Self {
         Self {
             client: reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(90))
                 .build()
                 .expect("Failed to create HTTP client"),
+            jobs,
         }
     }
 
@@ -101,11 +118,18 @@ impl CtfService {
         &self,
         condition_id: &str,
         amount: rust_decimal::Decimal,
-        private_key: &str,
+        signer: &WalletSigner,
         builder_api_key: &str,
         builder_secret: &str,
         builder_passphrase: &str,
-    ) -> Result<CtfResponse> {
+    ) -> Result<i64> {
+        let call = Self::split_call(condition_id, amount)?;
+        self.submit_calls(&[call], signer, builder_api_key, builder_secret, builder_passphrase)
+            .await
+    }
+
+    /// Build a CTF splitPosition call for a binary condition
+    pub fn split_call(condition_id: &str, amount: Decimal) -> Result<CtfCall> {
         let amount_raw = to_raw_amount(amount);
         info!(
             "CTF Split: {} USDC ({} raw) for condition {}",
@@ -115,27 +139,21 @@ impl CtfService {
         );
 
         let usdc: Address = USDC_ADDRESS.parse()?;
-        let cond_bytes = parse_condition_id(condition_id)?;
-
         let call = splitPositionCall {
             collateralToken: usdc,
             parentCollectionId: B256::ZERO,
-            conditionId: cond_bytes,
+            conditionId: parse_condition_id(condition_id)?,
             partition: vec![U256::from(1), U256::from(2)],
             amount: U256::from(amount_raw),
         };
 
-        let ctf: Address = CTF_ADDRESS.parse()?;
-        self.submit_safe_transaction(
-            ctf,
-            &call.abi_encode(),
-            private_key,
-            builder_api_key,
-            builder_secret,
-            builder_passphrase,
-            "CTF Split",
-        )
-        .await
+        Ok(CtfCall {
+            to: CTF_ADDRESS.parse()?,
+            data: call.abi_encode(),
+            kind: OnchainJobKind::Split,
+            reference: condition_id.to_string(),
+            description: "CTF Split".to_string(),
+        })
     }
 
     /// Merge YES + NO token pairs back into USDC collateral.
@@ -148,29 +166,37 @@ impl CtfService {
         &self,
         condition_id: &str,
         amount: rust_decimal::Decimal,
-        private_key: &str,
+        signer: &WalletSigner,
         builder_api_key: &str,
         builder_secret: &str,
         builder_passphrase: &str,
         yes_token_id: Option<&str>,
         no_token_id: Option<&str>,
         neg_risk: bool,
-    ) -> Result<CtfResponse> {
+    ) -> Result<i64> {
+        let safe_address = safe_address_for(signer)?;
+        let call = self
+            .merge_call(condition_id, amount, safe_address, yes_token_id, no_token_id, neg_risk)
+            .await?;
+        self.submit_calls(&[call], signer, builder_api_key, builder_secret, builder_passphrase)
+            .await
+    }
+
+    /// Build a merge call, capped at what the Safe actually holds on-chain.
+    ///
+    /// Fails with "Safe holds 0 tokens" when there is nothing to merge.
+    pub async fn merge_call(
+        &self,
+        condition_id: &str,
+        amount: Decimal,
+        safe_address: Address,
+        yes_token_id: Option<&str>,
+        no_token_id: Option<&str>,
+        neg_risk: bool,
+    ) -> Result<CtfCall> {
         let ctf: Address = CTF_ADDRESS.parse()?;
         let cond_bytes = parse_condition_id(condition_id)?;
 
-        // Derive Safe address to check on-chain balances
-        let signer: PrivateKeySigner = private_key
-            .parse()
-            .context("Failed to parse private key")?;
-        let eoa_address = signer.address();
-        let safe_address_str =
-            crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa_address))
-                .map_err(|e| anyhow::anyhow!("Failed to derive safe: {}", e))?;
-        let safe_address: Address = safe_address_str
-            .parse()
-            .context("Failed to parse safe address")?;
-
         // Use CLOB token IDs for balance check (works for both NegRisk and standard)
         let (token_id_yes, token_id_no) = match (yes_token_id, no_token_id) {
             (Some(yes_str), Some(no_str)) => {
@@ -198,10 +224,10 @@ impl CtfService {
         let min_balance = std::cmp::min(bal_yes, bal_no);
 
         info!(
-            "CTF Merge: requested={} raw={} | on-chain YES={} NO={} min={} | neg_risk={} condition={} safe={}",
+            "CTF Merge: requested={} raw={} | on-chain YES={} NO={} min={} | neg_risk={} condition={} safe={:?}",
             amount, amount_raw, bal_yes, bal_no, min_balance, neg_risk,
             &condition_id[..12.min(condition_id.len())],
-            &safe_address_str[..10]
+            safe_address
         );
 
         if min_balance.is_zero() {
@@ -209,14 +235,10 @@ impl CtfService {
                 "CTF Merge: Safe holds 0 tokens (YES={} NO={}). Cannot merge.",
                 bal_yes, bal_no
             );
-            return Ok(CtfResponse {
-                success: false,
-                transaction_id: None,
-                error: Some(format!(
-                    "Safe holds 0 tokens (YES={}, NO={}). Tokens may not have been delivered or were already redeemed.",
-                    bal_yes, bal_no
-                )),
-            });
+            anyhow::bail!(
+                "Safe holds 0 tokens (YES={}, NO={}). Tokens may not have been delivered or were already redeemed.",
+                bal_yes, bal_no
+            );
         }
 
         // Use the minimum of requested amount and actual on-chain balance
@@ -236,16 +258,13 @@ impl CtfService {
                 amount: actual_raw,
             };
             info!("CTF Merge: Using NegRisk Adapter at {:?}", adapter);
-            self.submit_safe_transaction(
-                adapter,
-                &call.abi_encode(),
-                private_key,
-                builder_api_key,
-                builder_secret,
-                builder_passphrase,
-                "CTF Merge (NegRisk)",
-            )
-            .await
+            Ok(CtfCall {
+                to: adapter,
+                data: call.abi_encode(),
+                kind: OnchainJobKind::Merge,
+                reference: condition_id.to_string(),
+                description: "CTF Merge (NegRisk)".to_string(),
+            })
         } else {
             // Standard: call CTF.mergePositions(USDC, 0, conditionId, [1,2], amount)
             let usdc: Address = USDC_ADDRESS.parse()?;
@@ -256,16 +275,13 @@ impl CtfService {
                 partition: vec![U256::from(1), U256::from(2)],
                 amount: actual_raw,
             };
-            self.submit_safe_transaction(
-                ctf,
-                &call.abi_encode(),
-                private_key,
-                builder_api_key,
-                builder_secret,
-                builder_passphrase,
-                "CTF Merge",
-            )
-            .await
+            Ok(CtfCall {
+                to: ctf,
+                data: call.abi_encode(),
+                kind: OnchainJobKind::Merge,
+                reference: condition_id.to_string(),
+                description: "CTF Merge".to_string(),
+            })
         }
     }
 
@@ -277,12 +293,19 @@ impl CtfService {
         &self,
         condition_id: &str,
         index_sets: &[u32],
-        private_key: &str,
+        signer: &WalletSigner,
         builder_api_key: &str,
         builder_secret: &str,
         builder_passphrase: &str,
         neg_risk: bool,
-    ) -> Result<CtfResponse> {
+    ) -> Result<i64> {
+        let call = Self::redeem_call(condition_id, index_sets, neg_risk)?;
+        self.submit_calls(&[call], signer, builder_api_key, builder_secret, builder_passphrase)
+            .await
+    }
+
+    /// Build a redeem call for a resolved condition
+    pub fn redeem_call(condition_id: &str, index_sets: &[u32], neg_risk: bool) -> Result<CtfCall> {
         info!(
             "CTF Redeem: condition {} index_sets {:?} neg_risk={}",
             &condition_id[..12.min(condition_id.len())],
@@ -299,16 +322,13 @@ impl CtfService {
                 amounts: index_sets.iter().map(|&i| U256::from(i)).collect(),
             };
             info!("CTF Redeem: Using NegRisk Adapter at {:?}", adapter);
-            self.submit_safe_transaction(
-                adapter,
-                &call.abi_encode(),
-                private_key,
-                builder_api_key,
-                builder_secret,
-                builder_passphrase,
-                "CTF Redeem (NegRisk)",
-            )
-            .await
+            Ok(CtfCall {
+                to: adapter,
+                data: call.abi_encode(),
+                kind: OnchainJobKind::Redeem,
+                reference: condition_id.to_string(),
+                description: "CTF Redeem (NegRisk)".to_string(),
+            })
         } else {
             let usdc: Address = USDC_ADDRESS.parse()?;
             let call = redeemPositionsCall {
@@ -317,34 +337,162 @@ impl CtfService {
                 conditionId: cond_bytes,
                 indexSets: index_sets.iter().map(|&i| U256::from(i)).collect(),
             };
-            let ctf: Address = CTF_ADDRESS.parse()?;
-            self.submit_safe_transaction(
-                ctf,
-                &call.abi_encode(),
-                private_key,
-                builder_api_key,
-                builder_secret,
-                builder_passphrase,
-                "CTF Redeem",
-            )
+            Ok(CtfCall {
+                to: CTF_ADDRESS.parse()?,
+                data: call.abi_encode(),
+                kind: OnchainJobKind::Redeem,
+                reference: condition_id.to_string(),
+                description: "CTF Redeem".to_string(),
+            })
+        }
+    }
+
+    /// Redeem a resolved neg-risk outcome through the NegRisk Adapter.
+    ///
+    /// Unlike the CTF, the adapter takes the token amounts to redeem per outcome
+    /// ([YES, NO] in whole tokens) rather than index sets.
+    pub async fn redeem_neg_risk(
+        &self,
+        condition_id: &str,
+        yes_amount: Decimal,
+        no_amount: Decimal,
+        signer: &WalletSigner,
+        builder_api_key: &str,
+        builder_secret: &str,
+        builder_passphrase: &str,
+    ) -> Result<i64> {
+        let call = Self::redeem_neg_risk_call(condition_id, yes_amount, no_amount)?;
+        self.submit_calls(&[call], signer, builder_api_key, builder_secret, builder_passphrase)
             .await
+    }
+
+    /// Build a NegRisk Adapter redeem call for explicit [YES, NO] amounts
+    pub fn redeem_neg_risk_call(
+        condition_id: &str,
+        yes_amount: Decimal,
+        no_amount: Decimal,
+    ) -> Result<CtfCall> {
+        info!(
+            "CTF Redeem (NegRisk): condition {} YES={} NO={}",
+            &condition_id[..12.min(condition_id.len())],
+            yes_amount,
+            no_amount
+        );
+
+        let call = neg_risk_abi::redeemPositionsCall {
+            conditionId: parse_condition_id(condition_id)?,
+            amounts: vec![
+                U256::from(to_raw_amount(yes_amount)),
+                U256::from(to_raw_amount(no_amount)),
+            ],
+        };
+        Ok(CtfCall {
+            to: NEG_RISK_ADAPTER.parse()?,
+            data: call.abi_encode(),
+            kind: OnchainJobKind::Redeem,
+            reference: condition_id.to_string(),
+            description: "CTF Redeem (NegRisk)".to_string(),
+        })
+    }
+
+    /// Convert NO positions of a neg-risk event into collateral plus YES positions.
+    ///
+    /// Holding NO on every outcome in `outcome_indices` is converted via
+    /// NegRiskAdapter.convertPositions(marketId, indexSet, amount): for k converted
+    /// outcomes the Safe receives (k - 1) * amount USDC and `amount` YES tokens of
+    /// every other outcome in the event.
+    pub async fn convert_positions(
+        &self,
+        neg_risk_market_id: &str,
+        outcome_indices: &[u8],
+        amount: Decimal,
+        signer: &WalletSigner,
+        builder_api_key: &str,
+        builder_secret: &str,
+        builder_passphrase: &str,
+    ) -> Result<i64> {
+        let index_set = neg_risk_index_set(outcome_indices)?;
+
+        info!(
+            "CTF Convert (NegRisk): market {} outcomes {:?} amount {}",
+            &neg_risk_market_id[..12.min(neg_risk_market_id.len())],
+            outcome_indices,
+            amount
+        );
+
+        let call = neg_risk_abi::convertPositionsCall {
+            marketId: parse_condition_id(neg_risk_market_id)?,
+            indexSet: index_set,
+            amount: U256::from(to_raw_amount(amount)),
+        };
+        let call = CtfCall {
+            to: NEG_RISK_ADAPTER.parse()?,
+            data: call.abi_encode(),
+            kind: OnchainJobKind::Convert,
+            reference: neg_risk_market_id.to_string(),
+            description: "CTF Convert (NegRisk)".to_string(),
+        };
+        self.submit_calls(&[call], signer, builder_api_key, builder_secret, builder_passphrase)
+            .await
+    }
+
+    /// Submit one or more calls as a single Safe transaction via the relay.
+    ///
+    /// Several calls are bundled through Safe MultiSend (delegatecall), so they
+    /// land atomically: every call shares the transaction, and a revert fails
+    /// them all. Returns the on-chain job tracking the submission once the relay
+    /// accepts it; the job worker drives it from there, and callers follow its
+    /// state on the job broadcast (`OnchainJobTracker::outcome`).
+    pub async fn submit_calls(
+        &self,
+        calls: &[CtfCall],
+        signer: &WalletSigner,
+        api_key: &str,
+        secret: &str,
+        passphrase: &str,
+    ) -> Result<i64> {
+        if calls.is_empty() {
+            anyhow::bail!("No CTF calls to submit");
+        }
+
+        let wallet_address = format!("{:?}", signer.address());
+
+        let (kind, description, reference) = batch_summary(calls);
+        let txns: Vec<(Address, Vec<u8>)> = calls.iter().map(|c| (c.to, c.data.clone())).collect();
+        let (to, calldata, operation) = safe_activation::encode_multisend(&txns);
+        if calls.len() > 1 {
+            info!("CTF MultiSend: {} calls for {} ({})", calls.len(), wallet_address, description);
         }
+
+        let job_id = self.jobs.enqueue(&wallet_address, kind, &description, Some(&reference)).await?;
+        let tx_id = match self
+            .submit_to_relay(signer, to, &calldata, operation, api_key, secret, passphrase, &description)
+            .await
+        {
+            Ok(tx_id) => tx_id,
+            Err(e) => {
+                let error = format!("{:#}", e);
+                warn!("CTF relay submit failed: {}", error);
+                self.jobs.fail(job_id, &error).await?;
+                return Err(e);
+            }
+        };
+        self.jobs.submitted_relay(job_id, &tx_id).await?;
+        Ok(job_id)
     }
 
-    /// Submit an ABI-encoded transaction as a Safe transaction via the relay
-    async fn submit_safe_transaction(
+    /// Sign and submit a Safe transaction to the relay, returning the relay transaction ID
+    async fn submit_to_relay(
         &self,
+        signer: &WalletSigner,
         to: Address,
         calldata: &[u8],
-        private_key: &str,
+        operation: u8,
         api_key: &str,
         secret: &str,
         passphrase: &str,
         metadata: &str,
-    ) -> Result<CtfResponse> {
-        let signer: PrivateKeySigner = private_key
-            .parse()
-            .context("Failed to parse private key")?;
+    ) -> Result<String> {
         let eoa_address = signer.address();
 
         // Derive Safe address from EOA
@@ -376,7 +524,7 @@ impl CtfService {
 
         // 2. Compute EIP-712 Safe transaction hash
         let data_hex = format!("0x{}", hex::encode(calldata));
-        let data_hash = compute_safe_tx_hash(safe_address, to, calldata, &nonce);
+        let data_hash = compute_safe_tx_hash(safe_address, to, calldata, operation, &nonce);
 
         // 3. Sign with personal_sign (Safe uses v > 30 to indicate eth_sign style)
         let signature = signer
@@ -407,7 +555,7 @@ impl CtfService {
             "nonce": nonce,
             "signatureParams": {
                 "gasPrice": "0",
-                "operation": "0",
+                "operation": operation.to_string(),
                 "safeTxnGas": "0",
                 "baseGas": "0",
                 "gasToken": ZERO_ADDRESS,
@@ -443,12 +591,7 @@ impl CtfService {
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            warn!("CTF relay submit failed: {} - {}", status, error_body);
-            return Ok(CtfResponse {
-                success: false,
-                transaction_id: None,
-                error: Some(format!("Relay error {}: {}", status, error_body)),
-            });
+            anyhow::bail!("Relay error {}: {}", status, error_body);
         }
 
         #[derive(Debug, Deserialize)]
@@ -466,17 +609,10 @@ impl CtfService {
 
         let tx_id = submit_resp.transaction_id.unwrap_or_default();
         if tx_id.is_empty() {
-            return Ok(CtfResponse {
-                success: false,
-                transaction_id: None,
-                error: Some("No transaction ID returned".to_string()),
-            });
+            anyhow::bail!("No transaction ID returned");
         }
         info!("CTF relay submitted: tx_id={}, nonce={}", tx_id, nonce);
-
-        // 7. Poll for completion
-        self.poll_transaction(&tx_id, api_key, secret, passphrase)
-            .await
+        Ok(tx_id)
     }
 
     /// Get nonce from relay
@@ -527,112 +663,17 @@ impl CtfService {
         }
     }
 
-    /// Poll for transaction completion
-    async fn poll_transaction(
-        &self,
-        tx_id: &str,
-        api_key: &str,
-        secret: &str,
-        passphrase: &str,
-    ) -> Result<CtfResponse> {
-        info!("CTF relay: polling for tx_id={}", tx_id);
-
-        for i in 0..30 {
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-
-            let path = format!("/transaction?id={}", tx_id);
-            let timestamp = chrono::Utc::now().timestamp().to_string();
-            let sig_payload = format!("{}GET{}", timestamp, path);
-            let hmac_sig = match compute_hmac(secret, &sig_payload) {
-                Ok(h) => h,
-                Err(_) => continue,
-            };
-
-            let url = format!("{}{}", RELAY_URL, path);
-            let response = self
-                .client
-                .get(&url)
-                .header("POLY_BUILDER_TIMESTAMP", &timestamp)
-                .header("POLY_BUILDER_SIGNATURE", &hmac_sig)
-                .header("POLY_BUILDER_API_KEY", api_key)
-                .header("POLY_BUILDER_PASSPHRASE", passphrase)
-                .send()
-                .await;
-
-            if let Ok(resp) = response {
-                if let Ok(txns) = resp.json::<Vec<serde_json::Value>>().await {
-                    if let Some(txn) = txns.first() {
-                        let state = txn
-                            .get("state")
-                            .and_then(|s| s.as_str())
-                            .unwrap_or("");
-
-                        match state {
-                            "STATE_MINED" | "STATE_CONFIRMED" => {
-                                let tx_hash = txn
-                                    .get("transactionHash")
-                                    .and_then(|h| h.as_str())
-                                    .unwrap_or("unknown")
-                                    .to_string();
-                                info!("CTF operation confirmed: tx={}", tx_hash);
-                                return Ok(CtfResponse {
-                                    success: true,
-                                    transaction_id: Some(tx_hash),
-                                    error: None,
-                                });
-                            }
-                            "STATE_FAILED" | "STATE_INVALID" => {
-                                let tx_hash = txn
-                                    .get("transactionHash")
-                                    .and_then(|h| h.as_str())
-                                    .map(|s| s.to_string());
-                                warn!("CTF operation failed: full response: {}", serde_json::to_string_pretty(txn).unwrap_or_default());
-                                let msg = format!(
-                                    "Transaction {}: hash={:?}",
-                                    state,
-                                    tx_hash
-                                );
-                                warn!("CTF operation failed: {}", msg);
-                                return Ok(CtfResponse {
-                                    success: false,
-                                    transaction_id: tx_hash,
-                                    error: Some(msg),
-                                });
-                            }
-                            _ => {
-                                if i % 5 == 0 {
-                                    debug!(
-                                        "CTF relay polling: state={}, attempt {}/30",
-                                        state,
-                                        i + 1
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        warn!("CTF relay polling timed out for tx_id={}", tx_id);
-        Ok(CtfResponse {
-            success: false,
-            transaction_id: Some(tx_id.to_string()),
-            error: Some("Transaction polling timed out after 60s".to_string()),
-        })
-    }
-
     /// Public method to get token balance for a wallet's Safe address
     /// Returns the balance as a Decimal (scaled from raw 1e6)
     pub async fn get_token_balance(
         &self,
-        private_key: &str,
+        signer: &WalletSigner,
         token_id: &str,
     ) -> Result<Decimal> {
         let ctf: Address = CTF_ADDRESS.parse()?;
 
-        // Derive Safe address from private key
-        let safe_address_str = crate::services::mint_maker::order_manager::derive_safe_address(private_key)
+        // Derive Safe address from the signer's EOA
+        let safe_address_str = crate::services::mint_maker::order_manager::derive_safe_address(signer)
             .context("Failed to derive safe address")?;
         let safe_address: Address = safe_address_str
             .parse()
@@ -652,6 +693,36 @@ impl CtfService {
         Ok(raw_decimal / scale)
     }
 
+    /// Get the payout vector of a binary condition once it is reported on-chain.
+    ///
+    /// Returns None while the condition is unresolved, otherwise the
+    /// (YES, NO) payout as a fraction of 1 (e.g. (1, 0) when YES won).
+    pub async fn get_payouts(&self, condition_id: &str) -> Result<Option<(Decimal, Decimal)>> {
+        let ctf: Address = CTF_ADDRESS.parse()?;
+        let cond_bytes = parse_condition_id(condition_id)?;
+
+        let denominator = self
+            .eth_call(ctf, &payoutDenominatorCall { conditionId: cond_bytes }.abi_encode())
+            .await?;
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+
+        let mut payouts = [Decimal::ZERO; 2];
+        for (index, payout) in payouts.iter_mut().enumerate() {
+            let numerator = self
+                .eth_call(
+                    ctf,
+                    &payoutNumeratorsCall { conditionId: cond_bytes, index: U256::from(index) }.abi_encode(),
+                )
+                .await?;
+            *payout = Decimal::from_str(&numerator.to_string())?
+                / Decimal::from_str(&denominator.to_string())?;
+        }
+
+        Ok(Some((payouts[0], payouts[1])))
+    }
+
     /// Check ERC-1155 token balance on the CTF contract via Polygon RPC
     async fn check_ctf_balance(
         &self,
@@ -663,13 +734,18 @@ impl CtfService {
             account,
             id: token_id,
         };
-        let calldata = format!("0x{}", hex::encode(call.abi_encode()));
+        self.eth_call(ctf_address, &call.abi_encode()).await
+    }
+
+    /// Run a read-only contract call via Polygon RPC and decode a single uint256 result
+    async fn eth_call(&self, to: Address, calldata: &[u8]) -> Result<U256> {
+        let calldata = format!("0x{}", hex::encode(calldata));
 
         let rpc_payload = serde_json::json!({
             "jsonrpc": "2.0",
             "method": "eth_call",
             "params": [{
-                "to": format!("{:?}", ctf_address),
+                "to": format!("{:?}", to),
                 "data": calldata,
             }, "latest"],
             "id": 1
@@ -699,7 +775,13 @@ impl CtfService {
 }
 
 /// Compute EIP-712 Safe transaction hash
-fn compute_safe_tx_hash(safe_address: Address, to: Address, data: &[u8], nonce: &str) -> B256 {
+fn compute_safe_tx_hash(
+    safe_address: Address,
+    to: Address,
+    data: &[u8],
+    operation: u8,
+    nonce: &str,
+) -> B256 {
     // Domain separator: keccak256(abi.encode(typehash, chainId, verifyingContract))
     let domain_typehash =
         keccak256(b"EIP712Domain(uint256 chainId,address verifyingContract)");
@@ -734,8 +816,8 @@ fn compute_safe_tx_hash(safe_address: Address, to: Address, data: &[u8], nonce:
     // keccak256(data) — EIP-712 encodes bytes as their hash
     struct_data.extend_from_slice(data_hash.as_slice());
 
-    // operation = 0 (Call)
-    struct_data.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+    // operation (0 = Call, 1 = DelegateCall for MultiSend)
+    struct_data.extend_from_slice(&U256::from(operation).to_be_bytes::<32>());
 
     // safeTxGas = 0
     struct_data.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
@@ -767,6 +849,44 @@ fn compute_safe_tx_hash(safe_address: Address, to: Address, data: &[u8], nonce:
     keccak256(&final_data)
 }
 
+/// Derive the Safe proxy address that holds a generated wallet's tokens
+pub fn safe_address_for(signer: &WalletSigner) -> Result<Address> {
+    let safe_address_str =
+        crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", signer.address()))
+            .map_err(|e| anyhow::anyhow!("Failed to derive safe: {}", e))?;
+    safe_address_str
+        .parse()
+        .context("Failed to parse safe address")
+}
+
+/// Job kind, description and reference for a set of calls submitted together
+fn batch_summary(calls: &[CtfCall]) -> (OnchainJobKind, String, String) {
+    if let [call] = calls {
+        return (call.kind, call.description.clone(), call.reference.clone());
+    }
+
+    let merges = calls.iter().filter(|c| c.kind == OnchainJobKind::Merge).count();
+    let redeems = calls.iter().filter(|c| c.kind == OnchainJobKind::Redeem).count();
+    let others = calls.len() - merges - redeems;
+    let mut parts = Vec::new();
+    if merges > 0 {
+        parts.push(format!("{} merge{}", merges, if merges == 1 { "" } else { "s" }));
+    }
+    if redeems > 0 {
+        parts.push(format!("{} redeem{}", redeems, if redeems == 1 { "" } else { "s" }));
+    }
+    if others > 0 {
+        parts.push(format!("{} other", others));
+    }
+
+    let references: Vec<&str> = calls.iter().map(|c| c.reference.as_str()).collect();
+    (
+        OnchainJobKind::Batch,
+        format!("CTF MultiSend ({})", parts.join(", ")),
+        references.join(","),
+    )
+}
+
 /// Parse condition_id hex string to B256
 fn parse_condition_id(condition_id: &str) -> Result<B256> {
     let hex_str = condition_id.strip_prefix("0x").unwrap_or(condition_id);
@@ -780,6 +900,18 @@ fn parse_condition_id(condition_id: &str) -> Result<B256> {
     Ok(B256::from_slice(&bytes))
 }
 
+/// Build a NegRisk Adapter index set (bit i set = outcome i) from outcome indices.
+///
+/// Outcome indices are the question indices within the neg-risk market (0-based).
+fn neg_risk_index_set(outcome_indices: &[u8]) -> Result<U256> {
+    if outcome_indices.is_empty() {
+        anyhow::bail!("At least one outcome is required for conversion");
+    }
+    Ok(outcome_indices
+        .iter()
+        .fold(U256::ZERO, |set, &i| set | (U256::from(1) << i as usize)))
+}
+
 /// Convert Decimal amount (whole tokens) to raw 6-decimal units
 fn to_raw_amount(amount: rust_decimal::Decimal) -> u64 {
     use rust_decimal::prelude::*;
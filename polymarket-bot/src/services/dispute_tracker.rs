@@ -3,7 +3,15 @@
 //! Monitors UMA Optimistic Oracle for active Polymarket disputes.
 //! Queries the Goldsky subgraph for dispute events and tracks their status.
 //! Filters by Polymarket's callback recipient address to only track relevant assertions.
-
+//! When the subgraph is down or lagging, falls back to reading OOv3 logs on-chain
+//! via [`UmaEventSource`] (see `DISPUTE_SOURCE`).
+//! Disputes that escalate to the DVM are followed through commit/reveal by [`DvmTracker`].
+
+use crate::config::DisputeSource;
+use crate::scanner::{market_tags, GammaTag};
+use crate::services::dvm_tracker::DvmTracker;
+use crate::services::uma_events::{ChainAssertion, UmaEventSource};
+use crate::taxonomy::{classify, MarketTaxonomy};
 use crate::types::{DisputeAlert, DisputeStatus};
 use crate::Database;
 use anyhow::Result;
@@ -22,6 +30,9 @@ use urlencoding;
 const UMA_SUBGRAPH_URL: &str = "https://api.goldsky.com/api/public/project_clus2fndawbcc01w31192938i/subgraphs/polygon-optimistic-oracle-v3/1/gn";
 const UMA_SUBGRAPH_FALLBACK_URL: &str = "https://api.thegraph.com/subgraphs/name/umaprotocol/polygon-optimistic-oracle-v3";
 
+/// Max blocks the subgraph may trail chain head before auto mode switches to on-chain logs (~5 min)
+const SUBGRAPH_MAX_LAG_BLOCKS: u64 = 150;
+
 /// Polymarket UMA CTF Adapter addresses on Polygon (all versions, lowercase)
 /// v3.0 - newest adapter
 const ADAPTER_V3: &str = "0x157ce2d672854c848c9b79c49a8cc6cc89176a49";
@@ -54,6 +65,19 @@ struct GraphQLResponse {
 #[derive(Debug, Deserialize)]
 struct GraphQLData {
     assertions: Option<Vec<UmaAssertion>>,
+    #[serde(rename = "_meta")]
+    meta: Option<GraphQLMeta>,
+}
+
+/// Subgraph indexing status (`_meta { block { number } }`)
+#[derive(Debug, Deserialize)]
+struct GraphQLMeta {
+    block: GraphQLMetaBlock,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLMetaBlock {
+    number: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -95,6 +119,27 @@ struct UmaAssertion {
     callback_recipient: Option<String>,
 }
 
+impl From<ChainAssertion> for UmaAssertion {
+    fn from(a: ChainAssertion) -> Self {
+        Self {
+            id: a.assertion_id.clone(),
+            assertion_id: Some(a.assertion_id),
+            claim: Some(a.claim),
+            domain_id: Some(a.domain_id),
+            assertion_timestamp: Some(a.assertion_timestamp.to_string()),
+            expiration_time: Some(a.expiration_time.to_string()),
+            disputer: a.disputer,
+            settlement_resolution: a.settlement_resolution,
+            dispute_timestamp: a.dispute_timestamp.map(|t| t.to_string()),
+            settlement_timestamp: a.settlement_timestamp.map(|t| t.to_string()),
+            identifier: Some(a.identifier),
+            bond: Some(a.bond),
+            currency: Some(a.currency),
+            callback_recipient: Some(a.callback_recipient),
+        }
+    }
+}
+
 /// Market data from Gamma API
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -112,6 +157,10 @@ struct GammaMarket {
     #[serde(default)]
     clob_token_ids: Option<String>,
     #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    tags: Option<Vec<GammaTag>>,
+    #[serde(default)]
     events: Option<Vec<GammaEvent>>,
 }
 
@@ -120,6 +169,8 @@ struct GammaMarket {
 struct GammaEvent {
     #[serde(default)]
     slug: Option<String>,
+    #[serde(default)]
+    tags: Option<Vec<GammaTag>>,
 }
 
 /// Dispute tracker service
@@ -134,21 +185,33 @@ pub struct DisputeTracker {
     /// Track domain_id (questionId) -> count of assertions seen (for two-round detection)
     /// If count > 1, the latest assertion is a re-proposal (round 2)
     domain_assertion_count: HashMap<String, u8>,
+    /// Where assertions are read from (subgraph, on-chain logs, or auto failover)
+    source: DisputeSource,
+    /// On-chain OOv3/adapter log reader (used for Rpc and Auto sources)
+    chain: UmaEventSource,
+    /// DVM vote phase/tally for escalated disputes
+    dvm: DvmTracker,
 }
 
 impl DisputeTracker {
-    pub fn new(db: Arc<Database>) -> Self {
+    pub fn new(db: Arc<Database>, polygon_rpc_url: String, source: DisputeSource) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
 
+        let adapter_addresses: Vec<&str> = ADAPTERS.iter().map(|(addr, _)| *addr).collect();
+        let chain = UmaEventSource::new(db.clone(), polygon_rpc_url, &adapter_addresses);
+
         Self {
             db,
             client,
             tracked_disputes: HashMap::new(),
             market_cache: HashMap::new(),
             domain_assertion_count: HashMap::new(),
+            source,
+            chain,
+            dvm: DvmTracker::new(),
         }
     }
 
@@ -533,7 +596,7 @@ impl DisputeTracker {
 
     /// Check for active disputes
     async fn check_disputes(&mut self) -> Result<Vec<DisputeAlert>> {
-        let assertions = self.fetch_all_adapters().await?;
+        let assertions = self.fetch_assertions().await?;
         let mut alerts = Vec::new();
 
         // Track which assertions we've seen this scan
@@ -542,6 +605,8 @@ impl DisputeTracker {
         // First pass: count assertions per domain_id for two-round detection (Item 1)
         let mut domain_counts: HashMap<String, u8> = HashMap::new();
         let mut domain_disputed: HashMap<String, bool> = HashMap::new();
+        // Earliest disputed assertion per domain: a later disputed one is the second dispute (DVM)
+        let mut domain_first_dispute: HashMap<String, i64> = HashMap::new();
         for assertion in &assertions {
             if let Some(domain) = &assertion.domain_id {
                 if !domain.is_empty() {
@@ -554,6 +619,14 @@ impl DisputeTracker {
                         .unwrap_or(false);
                     if is_disputed {
                         domain_disputed.insert(domain.clone(), true);
+                        let ts = assertion.assertion_timestamp
+                            .as_ref()
+                            .and_then(|s| s.parse::<i64>().ok())
+                            .unwrap_or(0);
+                        domain_first_dispute
+                            .entry(domain.clone())
+                            .and_modify(|first| *first = (*first).min(ts))
+                            .or_insert(ts);
                     }
                 }
             }
@@ -599,9 +672,19 @@ impl DisputeTracker {
                 1u8
             };
 
+            let assertion_ts = assertion.assertion_timestamp
+                .as_ref()
+                .and_then(|s| s.parse::<i64>().ok())
+                .unwrap_or(0);
+            let is_second_dispute = is_disputed
+                && domain_first_dispute
+                    .get(&domain_id)
+                    .map(|first| assertion_ts > *first)
+                    .unwrap_or(false);
+
             // Determine status
             let status = if is_disputed {
-                if dispute_round >= 2 {
+                if dispute_round >= 2 || is_second_dispute {
                     // Second dispute → DVM escalation
                     DisputeStatus::DvmVote
                 } else if assertion.dispute_timestamp.is_some() {
@@ -621,16 +704,12 @@ impl DisputeTracker {
                 .unwrap_or(0);
 
             // Use actual expirationTime from subgraph (Item 6)
-            let estimated_resolution = assertion.expiration_time
+            let mut estimated_resolution = assertion.expiration_time
                 .as_ref()
                 .and_then(|s| s.parse::<i64>().ok())
                 .unwrap_or(0);
 
             // Calculate liveness period from assertion timestamp and expiration
-            let assertion_ts = assertion.assertion_timestamp
-                .as_ref()
-                .and_then(|s| s.parse::<i64>().ok())
-                .unwrap_or(0);
             let liveness_seconds = if assertion_ts > 0 && estimated_resolution > assertion_ts {
                 Some(estimated_resolution - assertion_ts)
             } else {
@@ -662,6 +741,7 @@ impl DisputeTracker {
             let mut liquidity = Decimal::ZERO;
             let mut yes_token_id = None;
             let mut no_token_id = None;
+            let mut taxonomy = MarketTaxonomy::default();
 
             {
                 if let Some(market) = self.get_market_data(&condition_id, &question).await {
@@ -688,6 +768,17 @@ impl DisputeTracker {
                     yes_token_id = tokens.0;
                     no_token_id = tokens.1;
 
+                    // Classify from the event's tags plus the market's own
+                    let mut tags = market_tags(
+                        market.events.as_ref().and_then(|e| e.first()).and_then(|e| e.tags.clone()),
+                    );
+                    for tag in market_tags(market.tags.clone()) {
+                        if !tags.iter().any(|t| t.slug == tag.slug) {
+                            tags.push(tag);
+                        }
+                    }
+                    taxonomy = classify(tags, None, market.category.as_deref());
+
                     info!("Market data loaded - yes: {}, no: {}, liq: {}, slug: {}",
                         yes_price, no_price, liquidity, slug);
                 } else {
@@ -701,6 +792,15 @@ impl DisputeTracker {
                 &proposed_outcome, yes_price, no_price, dispute_round
             );
 
+            // Follow DVM commit/reveal; the vote round end replaces the liveness-based estimate
+            let dvm = if status == DisputeStatus::DvmVote {
+                let info = self.dvm.vote_info(&assertion_id, dispute_timestamp, assertion_ts).await;
+                estimated_resolution = info.reveal_ends_at;
+                Some(info)
+            } else {
+                None
+            };
+
             let alert = DisputeAlert {
                 assertion_id: assertion_id.clone(),
                 condition_id: condition_id.clone(),
@@ -721,13 +821,16 @@ impl DisputeTracker {
                 adapter_version,
                 liveness_seconds,
                 expected_value,
+                dvm,
+                taxonomy,
             };
 
             // Check if status changed
-            let status_changed = self.tracked_disputes
-                .get(&assertion_id)
+            let previous = self.tracked_disputes.get(&assertion_id);
+            let status_changed = previous
                 .map(|(old_status, _)| *old_status != status)
                 .unwrap_or(true);
+            let old_phase = previous.and_then(|(_, old)| old.dvm.as_ref().map(|d| d.phase));
 
             if status_changed {
                 info!(
@@ -737,21 +840,118 @@ impl DisputeTracker {
                 );
             }
 
+            if let Some(dvm) = &alert.dvm {
+                if old_phase != Some(dvm.phase) {
+                    info!(
+                        "Dispute {} DVM round {} phase: {} (resolves ~{}, proposed share: {:?})",
+                        &assertion_id[..assertion_id.len().min(16)], dvm.round_id, dvm.phase,
+                        dvm.reveal_ends_at, dvm.proposed_vote_share
+                    );
+                }
+            }
+
             self.tracked_disputes.insert(assertion_id.clone(), (status, alert.clone()));
             alerts.push(alert);
         }
 
         // Remove disputes that are no longer in the subgraph
         self.tracked_disputes.retain(|id, _| seen_ids.contains(id));
+        self.dvm.retain(&seen_ids);
 
         debug!("Tracking {} active disputes", alerts.len());
         Ok(alerts)
     }
 
+    /// Fetch assertions from the configured source
+    ///
+    /// Auto mode keeps the on-chain cursor current every cycle (a few cheap `eth_getLogs`
+    /// calls) so switching over when the subgraph fails or lags needs no catch-up.
+    async fn fetch_assertions(&mut self) -> Result<Vec<UmaAssertion>> {
+        match self.source {
+            DisputeSource::Subgraph => self.fetch_all_adapters().await,
+            DisputeSource::Rpc => {
+                self.chain.sync().await?;
+                Ok(self.chain_assertions())
+            }
+            DisputeSource::Auto => {
+                let chain_ok = match self.chain.sync().await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        warn!("On-chain UMA event sync failed: {}", e);
+                        false
+                    }
+                };
+
+                match self.fetch_all_adapters().await {
+                    Ok(assertions) => {
+                        if chain_ok && self.subgraph_is_lagging().await {
+                            return Ok(self.chain_assertions());
+                        }
+                        Ok(assertions)
+                    }
+                    Err(e) if chain_ok => {
+                        warn!("UMA subgraph unavailable ({}), using on-chain events", e);
+                        Ok(self.chain_assertions())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Assertions from the on-chain event source in subgraph shape
+    fn chain_assertions(&self) -> Vec<UmaAssertion> {
+        let assertions: Vec<UmaAssertion> = self.chain
+            .assertions()
+            .into_iter()
+            .map(UmaAssertion::from)
+            .collect();
+        info!("Total: {} Polymarket assertions from on-chain events", assertions.len());
+        assertions
+    }
+
+    /// Whether the subgraph's indexed block trails chain head by more than the allowed lag
+    async fn subgraph_is_lagging(&self) -> bool {
+        let Some(head) = self.chain.head_block() else {
+            return false;
+        };
+
+        let request = GraphQLRequest {
+            query: "{ _meta { block { number } } }".to_string(),
+            variables: None,
+        };
+
+        let indexed = match self.client.post(UMA_SUBGRAPH_URL).json(&request).send().await {
+            Ok(response) => response
+                .json::<GraphQLResponse>()
+                .await
+                .ok()
+                .and_then(|r| r.data)
+                .and_then(|d| d.meta)
+                .map(|m| m.block.number),
+            Err(e) => {
+                debug!("Subgraph _meta query failed: {}", e);
+                None
+            }
+        };
+
+        match indexed {
+            Some(block) if head.saturating_sub(block) > SUBGRAPH_MAX_LAG_BLOCKS => {
+                warn!(
+                    "UMA subgraph lagging {} blocks behind head ({} vs {}), using on-chain events",
+                    head - block, block, head
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Fetch assertions from ALL Polymarket adapter versions (Item 2)
     async fn fetch_all_adapters(&self) -> Result<Vec<UmaAssertion>> {
         let mut all_assertions = Vec::new();
         let mut seen_ids = std::collections::HashSet::new();
+        let mut failures = 0;
 
         for (adapter_addr, version) in ADAPTERS {
             match self.fetch_assertions_for_adapter(adapter_addr).await {
@@ -771,10 +971,15 @@ impl DisputeTracker {
                 Err(e) => {
                     // Non-fatal: log and continue with other adapters
                     warn!("Failed to fetch assertions from adapter {} ({}): {}", version, adapter_addr, e);
+                    failures += 1;
                 }
             }
         }
 
+        if failures == ADAPTERS.len() {
+            anyhow::bail!("UMA subgraph unavailable for all adapters");
+        }
+
         info!("Total: {} unique Polymarket assertions across all adapters", all_assertions.len());
         Ok(all_assertions)
     }
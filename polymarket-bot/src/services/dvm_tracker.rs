@@ -0,0 +1,402 @@
+//! UMA DVM Vote Tracker
+//!
+//! Follows disputes that escalated to the UMA DVM (VotingV2 on Ethereum mainnet).
+//! Voting rounds are fixed 48h windows: 24h commit, then 24h reveal. A request is
+//! voted in the round after it arrives, and rolls to the next round if it fails to
+//! reach quorum. Phase timing is computed locally; the vote tally and final result
+//! come from the UMA voting subgraph once votes are revealed.
+
+use crate::types::{DvmPhase, DvmVoteInfo};
+use chrono::Utc;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// UMA VotingV2 subgraph (Ethereum mainnet)
+const UMA_VOTING_SUBGRAPH_URL: &str = "https://api.thegraph.com/subgraphs/name/umaprotocol/mainnet-voting-v2";
+
+/// VotingV2 phase length (commit or reveal) in seconds
+const PHASE_LENGTH_SECS: i64 = 86_400;
+
+/// VotingV2 round length (commit + reveal) in seconds
+const ROUND_LENGTH_SECS: i64 = 2 * PHASE_LENGTH_SECS;
+
+/// DVM price meaning "assertion is true" (1e18)
+const PRICE_TRUE: &str = "1000000000000000000";
+
+/// Minimum seconds between subgraph lookups for the same request
+const REFRESH_SECS: i64 = 300;
+
+#[derive(Debug, Deserialize)]
+struct VotingResponse {
+    data: Option<VotingData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VotingData {
+    price_requests: Option<Vec<PriceRequest>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceRequest {
+    #[serde(default)]
+    ancillary_data: Option<String>,
+    #[serde(default)]
+    is_resolved: bool,
+    #[serde(default)]
+    price: Option<String>,
+    #[serde(default)]
+    roll_count: Option<String>,
+    #[serde(default)]
+    latest_round: Option<PriceRequestRound>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceRequestRound {
+    #[serde(default)]
+    total_votes_revealed: Option<String>,
+    #[serde(default)]
+    groups: Vec<VoterGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VoterGroup {
+    price: String,
+    total_vote_amount: String,
+}
+
+/// Tracks DVM vote phase and tally per assertion
+pub struct DvmTracker {
+    client: Client,
+    /// assertion_id -> (last info, last subgraph lookup unix secs)
+    cache: HashMap<String, (DvmVoteInfo, i64)>,
+}
+
+impl DvmTracker {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Get vote info for an escalated assertion
+    ///
+    /// `request_time` is when the dispute reached the DVM (the dispute timestamp),
+    /// `assertion_time` is the OOv3 assertion time the DVM request is keyed by.
+    pub async fn vote_info(&mut self, assertion_id: &str, request_time: i64, assertion_time: i64) -> DvmVoteInfo {
+        let now = Utc::now().timestamp();
+
+        if let Some((info, fetched_at)) = self.cache.get(assertion_id) {
+            // Resolved results never change; otherwise only refresh phase timing between lookups
+            if info.phase == DvmPhase::Resolved || now - fetched_at < REFRESH_SECS {
+                let mut info = info.clone();
+                if info.phase != DvmPhase::Resolved {
+                    let (phase, ..) = phase_schedule(request_time, info.roll_count, now);
+                    info.phase = phase;
+                }
+                return info;
+            }
+        }
+
+        let roll_count = self.cache.get(assertion_id).map(|(i, _)| i.roll_count).unwrap_or(0);
+        let (phase, round_id, commit_ends_at, reveal_ends_at) = phase_schedule(request_time, roll_count, now);
+
+        let mut info = DvmVoteInfo {
+            phase,
+            round_id,
+            commit_ends_at,
+            reveal_ends_at,
+            roll_count,
+            total_votes_revealed: None,
+            proposed_vote_share: None,
+            proposed_upheld: None,
+        };
+
+        // Tally only exists once reveal has started
+        if matches!(phase, DvmPhase::Reveal | DvmPhase::AwaitingSettlement) {
+            match self.fetch_request(assertion_id, assertion_time).await {
+                Ok(Some(request)) => {
+                    apply_request(&mut info, &request, request_time, now);
+                    if info.phase == DvmPhase::Resolved {
+                        info!(
+                            "DVM resolved assertion {}: proposed outcome {}",
+                            &assertion_id[..assertion_id.len().min(16)],
+                            if info.proposed_upheld == Some(true) { "upheld" } else { "rejected" }
+                        );
+                    }
+                }
+                Ok(None) => debug!(
+                    "No DVM price request found yet for assertion {}",
+                    &assertion_id[..assertion_id.len().min(16)]
+                ),
+                Err(e) => warn!("DVM subgraph lookup failed: {}", e),
+            }
+        }
+
+        self.cache.insert(assertion_id.to_string(), (info.clone(), now));
+        info
+    }
+
+    /// Forget assertions that are no longer tracked
+    pub fn retain(&mut self, active: &std::collections::HashSet<String>) {
+        self.cache.retain(|id, _| active.contains(id));
+    }
+
+    /// Find the DVM price request for an assertion (keyed by assertion time, matched on ancillary data)
+    async fn fetch_request(&self, assertion_id: &str, assertion_time: i64) -> anyhow::Result<Option<PriceRequest>> {
+        let query = format!(r#"
+            {{
+                priceRequests(first: 20, where: {{ time: "{}" }}) {{
+                    ancillaryData
+                    isResolved
+                    price
+                    rollCount
+                    latestRound {{
+                        totalVotesRevealed
+                        groups {{
+                            price
+                            totalVoteAmount
+                        }}
+                    }}
+                }}
+            }}
+            "#, assertion_time);
+
+        let response: VotingResponse = self.client
+            .post(UMA_VOTING_SUBGRAPH_URL)
+            .json(&serde_json::json!({ "query": query }))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let needle = assertion_id.trim_start_matches("0x").to_lowercase();
+        Ok(response.data
+            .and_then(|d| d.price_requests)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|r| {
+                // Ancillary data is hex of an ASCII string containing "assertionId:<hex>"
+                r.ancillary_data
+                    .as_deref()
+                    .and_then(|a| hex::decode(a.trim_start_matches("0x")).ok())
+                    .map(|bytes| String::from_utf8_lossy(&bytes).to_lowercase().contains(&needle))
+                    .unwrap_or(false)
+            }))
+    }
+}
+
+impl Default for DvmTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Update vote info from the subgraph's view of the price request: rolls move the
+/// schedule to a later round, and only a published price resolves it
+fn apply_request(info: &mut DvmVoteInfo, request: &PriceRequest, request_time: i64, now: i64) {
+    let rolls = request.roll_count
+        .as_deref()
+        .and_then(|r| r.parse::<u32>().ok())
+        .unwrap_or(0);
+    if rolls != info.roll_count {
+        let (phase, round_id, commit_ends_at, reveal_ends_at) = phase_schedule(request_time, rolls, now);
+        info.phase = phase;
+        info.round_id = round_id;
+        info.commit_ends_at = commit_ends_at;
+        info.reveal_ends_at = reveal_ends_at;
+        info.roll_count = rolls;
+    }
+
+    if let Some(round) = &request.latest_round {
+        let (total, share) = tally(round);
+        info.total_votes_revealed = total;
+        info.proposed_vote_share = share;
+    }
+
+    if request.is_resolved {
+        if let Some(price) = request.price.as_deref() {
+            info.phase = DvmPhase::Resolved;
+            info.proposed_upheld = Some(price == PRICE_TRUE);
+        }
+    }
+}
+
+/// Compute (phase, round_id, commit_ends_at, reveal_ends_at) for a request.
+///
+/// A request is voted in the round after the one it arrived in, plus one round per roll.
+/// Once the round ends it awaits settlement: whether it resolved or rolled is only
+/// known from the subgraph.
+fn phase_schedule(request_time: i64, roll_count: u32, now: i64) -> (DvmPhase, u64, i64, i64) {
+    let round_id = (request_time / ROUND_LENGTH_SECS) as u64 + 1 + roll_count as u64;
+    let round_start = round_id as i64 * ROUND_LENGTH_SECS;
+    let commit_ends_at = round_start + PHASE_LENGTH_SECS;
+    let reveal_ends_at = round_start + ROUND_LENGTH_SECS;
+
+    let phase = if now < round_start {
+        DvmPhase::Queued
+    } else if now < commit_ends_at {
+        DvmPhase::Commit
+    } else if now < reveal_ends_at {
+        DvmPhase::Reveal
+    } else {
+        DvmPhase::AwaitingSettlement
+    };
+
+    (phase, round_id, commit_ends_at, reveal_ends_at)
+}
+
+/// Total revealed votes and the share voting that the assertion is true
+fn tally(round: &PriceRequestRound) -> (Option<Decimal>, Option<f64>) {
+    let mut total = 0.0;
+    let mut for_proposed = 0.0;
+    for group in &round.groups {
+        let amount = group.total_vote_amount.parse::<f64>().unwrap_or(0.0);
+        total += amount;
+        if group.price == PRICE_TRUE {
+            for_proposed += amount;
+        }
+    }
+
+    let revealed = round.total_votes_revealed
+        .as_deref()
+        .and_then(|t| Decimal::from_str(t).ok());
+
+    if total > 0.0 {
+        (revealed, Some(for_proposed / total))
+    } else {
+        (revealed, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_is_queued_until_next_round() {
+        // Request halfway through round 10 is voted in round 11
+        let request_time = 10 * ROUND_LENGTH_SECS + PHASE_LENGTH_SECS / 2;
+        let (phase, round_id, commit_ends, reveal_ends) = phase_schedule(request_time, 0, request_time + 60);
+
+        assert_eq!(phase, DvmPhase::Queued);
+        assert_eq!(round_id, 11);
+        assert_eq!(commit_ends, 11 * ROUND_LENGTH_SECS + PHASE_LENGTH_SECS);
+        assert_eq!(reveal_ends, 12 * ROUND_LENGTH_SECS);
+    }
+
+    #[test]
+    fn test_commit_then_reveal_then_awaiting_settlement() {
+        let request_time = 10 * ROUND_LENGTH_SECS;
+        let round_start = 11 * ROUND_LENGTH_SECS;
+
+        assert_eq!(phase_schedule(request_time, 0, round_start).0, DvmPhase::Commit);
+        assert_eq!(phase_schedule(request_time, 0, round_start + PHASE_LENGTH_SECS).0, DvmPhase::Reveal);
+        // The clock alone never resolves a request
+        assert_eq!(
+            phase_schedule(request_time, 0, round_start + ROUND_LENGTH_SECS).0,
+            DvmPhase::AwaitingSettlement
+        );
+    }
+
+    #[test]
+    fn test_roll_pushes_to_later_round() {
+        let request_time = 10 * ROUND_LENGTH_SECS;
+        let (phase, round_id, _, reveal_ends) = phase_schedule(request_time, 1, 12 * ROUND_LENGTH_SECS + 10);
+
+        assert_eq!(round_id, 12);
+        assert_eq!(phase, DvmPhase::Commit);
+        assert_eq!(reveal_ends, 13 * ROUND_LENGTH_SECS);
+    }
+
+    fn request(rolls: u32, resolved: bool, price: Option<&str>) -> PriceRequest {
+        PriceRequest {
+            ancillary_data: None,
+            is_resolved: resolved,
+            price: price.map(str::to_string),
+            roll_count: Some(rolls.to_string()),
+            latest_round: None,
+        }
+    }
+
+    fn info_at(request_time: i64, now: i64) -> DvmVoteInfo {
+        let (phase, round_id, commit_ends_at, reveal_ends_at) = phase_schedule(request_time, 0, now);
+        DvmVoteInfo {
+            phase,
+            round_id,
+            commit_ends_at,
+            reveal_ends_at,
+            roll_count: 0,
+            total_votes_revealed: None,
+            proposed_vote_share: None,
+            proposed_upheld: None,
+        }
+    }
+
+    #[test]
+    fn test_rolled_round_is_not_resolved() {
+        let request_time = 10 * ROUND_LENGTH_SECS;
+        // Just after round 11 ended without a price
+        let now = 12 * ROUND_LENGTH_SECS + 60;
+        let mut info = info_at(request_time, now);
+        assert_eq!(info.phase, DvmPhase::AwaitingSettlement);
+
+        // Subgraph reports the request rolled: voted again in round 12
+        apply_request(&mut info, &request(1, false, None), request_time, now);
+        assert_eq!(info.phase, DvmPhase::Commit);
+        assert_eq!(info.round_id, 12);
+        assert_eq!(info.roll_count, 1);
+        assert_eq!(info.reveal_ends_at, 13 * ROUND_LENGTH_SECS);
+        assert!(info.proposed_upheld.is_none());
+
+        // Still no price after the rolled round: waiting, not resolved
+        let later = 13 * ROUND_LENGTH_SECS + 60;
+        let mut info = DvmVoteInfo { phase: phase_schedule(request_time, 1, later).0, ..info };
+        apply_request(&mut info, &request(1, false, None), request_time, later);
+        assert_eq!(info.phase, DvmPhase::AwaitingSettlement);
+    }
+
+    #[test]
+    fn test_resolved_once_price_is_published() {
+        let request_time = 10 * ROUND_LENGTH_SECS;
+        let now = 12 * ROUND_LENGTH_SECS + 60;
+
+        let mut info = info_at(request_time, now);
+        apply_request(&mut info, &request(0, true, Some(PRICE_TRUE)), request_time, now);
+        assert_eq!(info.phase, DvmPhase::Resolved);
+        assert_eq!(info.proposed_upheld, Some(true));
+
+        let mut info = info_at(request_time, now);
+        apply_request(&mut info, &request(0, true, Some("0")), request_time, now);
+        assert_eq!(info.proposed_upheld, Some(false));
+    }
+
+    #[test]
+    fn test_tally_share() {
+        let round = PriceRequestRound {
+            total_votes_revealed: Some("4000".to_string()),
+            groups: vec![
+                VoterGroup { price: PRICE_TRUE.to_string(), total_vote_amount: "3000".to_string() },
+                VoterGroup { price: "0".to_string(), total_vote_amount: "1000".to_string() },
+            ],
+        };
+
+        let (total, share) = tally(&round);
+        assert_eq!(total, Some(Decimal::from(4000)));
+        assert!((share.unwrap() - 0.75).abs() < 1e-9);
+    }
+}
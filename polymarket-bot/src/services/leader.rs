@@ -0,0 +1,162 @@
+//! Leader Election Service
+//! Server instances sharing a database compete for one lease; only the holder
+//! runs the trading services, the others serve the API read-only
+
+use crate::db::Lease;
+use crate::Database;
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+/// Lease guarding the trading services
+pub const TRADING_LEASE: &str = "trading";
+
+/// Leader election over the `leader_leases` table
+#[derive(Clone)]
+pub struct LeaderElection {
+    db: Arc<Database>,
+    instance_id: Arc<str>,
+    lease: Duration,
+    leader_tx: Arc<watch::Sender<bool>>,
+}
+
+impl LeaderElection {
+    pub fn new(db: Arc<Database>, instance_id: String, lease_seconds: u64) -> Self {
+        let (leader_tx, _) = watch::channel(false);
+        Self {
+            db,
+            instance_id: instance_id.into(),
+            lease: Duration::from_secs(lease_seconds),
+            leader_tx: Arc::new(leader_tx),
+        }
+    }
+
+    /// Whether this instance currently holds the trading lease
+    pub fn is_leader(&self) -> bool {
+        *self.leader_tx.borrow()
+    }
+
+    /// Leadership changes (true while leading)
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.leader_tx.subscribe()
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Holder of the trading lease, if it hasn't expired
+    pub async fn current_leader(&self) -> Result<Option<Lease>> {
+        let lease = self.db.leases().get_lease(TRADING_LEASE).await?;
+        Ok(lease.filter(|l| !l.is_expired(Utc::now())))
+    }
+
+    /// Acquire or renew the lease every third of its length, forever
+    pub async fn run(&self) {
+        let renew_every = renew_interval(self.lease);
+        info!(
+            "Leader election started (instance: {}, lease: {:?})",
+            self.instance_id, self.lease
+        );
+
+        let mut tick = tokio::time::interval(renew_every);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_renewal: Option<Instant> = None;
+
+        loop {
+            tick.tick().await;
+
+            let expires_at = Utc::now()
+                + chrono::Duration::from_std(self.lease).unwrap_or_else(|_| chrono::Duration::seconds(15));
+            let leading = match self
+                .db
+                .leases()
+                .try_acquire_lease(TRADING_LEASE, &self.instance_id, expires_at)
+                .await
+            {
+                Ok(true) => {
+                    last_renewal = Some(Instant::now());
+                    true
+                }
+                Ok(false) => false,
+                Err(e) => {
+                    warn!("Leader lease renewal failed: {}", e);
+                    // Keep leading only while another instance can't have taken over yet
+                    self.is_leader() && still_holds(last_renewal, Instant::now(), self.lease)
+                }
+            };
+
+            self.set_leader(leading);
+        }
+    }
+
+    /// Give the lease up so a follower takes over without waiting for it to
+    /// expire. Stop `run` first, or it takes the lease straight back.
+    pub async fn step_down(&self) {
+        if !self.is_leader() {
+            return;
+        }
+        if let Err(e) = self.db.leases().release_lease(TRADING_LEASE, &self.instance_id).await {
+            warn!("Failed to release leader lease: {}", e);
+        }
+        self.set_leader(false);
+    }
+
+    fn set_leader(&self, leading: bool) {
+        let changed = self.leader_tx.send_if_modified(|current| {
+            if *current == leading {
+                return false;
+            }
+            *current = leading;
+            true
+        });
+
+        if changed {
+            if leading {
+                info!("Instance {} is now the leader", self.instance_id);
+            } else {
+                warn!("Instance {} is no longer the leader", self.instance_id);
+            }
+        }
+    }
+}
+
+/// How often the lease is renewed: a third of its length, at least a second
+fn renew_interval(lease: Duration) -> Duration {
+    (lease / 3).max(Duration::from_secs(1))
+}
+
+/// Whether a lease last renewed at `last_renewal` is still safely ours after a
+/// failed renewal. Leaves one renewal interval of margin before it expires.
+fn still_holds(last_renewal: Option<Instant>, now: Instant, lease: Duration) -> bool {
+    match last_renewal {
+        Some(at) => now.saturating_duration_since(at) + renew_interval(lease) < lease,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renew_interval() {
+        assert_eq!(renew_interval(Duration::from_secs(15)), Duration::from_secs(5));
+        assert_eq!(renew_interval(Duration::from_secs(2)), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_still_holds() {
+        let lease = Duration::from_secs(15);
+        let renewed = Instant::now();
+
+        assert!(still_holds(Some(renewed), renewed + Duration::from_secs(5), lease));
+        assert!(!still_holds(Some(renewed), renewed + Duration::from_secs(10), lease));
+        assert!(!still_holds(Some(renewed), renewed + Duration::from_secs(20), lease));
+        assert!(!still_holds(None, renewed, lease));
+    }
+}
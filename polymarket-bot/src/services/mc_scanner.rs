@@ -5,6 +5,8 @@
 //! progression. Observation mode only — no real money deployed.
 
 use crate::db::Database;
+use crate::market_index::MarketsUpdate;
+use crate::taxonomy::{Category, TaxonomyFilter};
 use crate::types::{DisputeAlert, TrackedMarket, Side};
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
@@ -13,7 +15,7 @@ use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, RwLock};
 use tracing::{info, warn, debug};
 
 // ==================== TYPES ====================
@@ -147,35 +149,43 @@ struct OrderbookLevel {
 pub struct McScanner {
     db: Arc<Database>,
     client: reqwest::Client,
+    /// Market categories/tags to evaluate
+    taxonomy: TaxonomyFilter,
 }
 
 impl McScanner {
-    pub async fn new(db: Arc<Database>) -> Self {
+    pub async fn new(db: Arc<Database>, taxonomy: TaxonomyFilter) -> Self {
         Self {
             db,
             client: reqwest::Client::new(),
+            taxonomy,
         }
     }
 
-    /// Main loop: receives raw markets, evaluates them, broadcasts status
+    /// Main loop: receives raw markets, evaluates them, broadcasts status until `stop` is set
     pub async fn run(
         &mut self,
-        mut markets_rx: broadcast::Receiver<Vec<TrackedMarket>>,
+        mut markets_rx: broadcast::Receiver<MarketsUpdate>,
         disputes: Arc<RwLock<Vec<DisputeAlert>>>,
         mc_tx: broadcast::Sender<McStatusUpdate>,
+        mut stop: watch::Receiver<bool>,
     ) {
         info!("Starting Millionaires Club observation scanner...");
 
         // Ensure config exists
-        if let Err(e) = self.db.mc_get_config().await {
+        if let Err(e) = self.db.mc().mc_get_config().await {
             warn!("MC config init error (will create default): {}", e);
         }
 
         loop {
-            match markets_rx.recv().await {
-                Ok(markets) => {
+            let received = tokio::select! {
+                received = markets_rx.recv() => received,
+                _ = stop.changed() => break,
+            };
+            match received {
+                Ok(update) => {
                     let dispute_list = disputes.read().await.clone();
-                    if let Err(e) = self.process_markets(&markets, &dispute_list, &mc_tx).await {
+                    if let Err(e) = self.process_markets(&update.markets, &dispute_list, &mc_tx).await {
                         warn!("MC scanner cycle error: {}", e);
                     }
                 }
@@ -196,7 +206,7 @@ impl McScanner {
         disputes: &[DisputeAlert],
         mc_tx: &broadcast::Sender<McStatusUpdate>,
     ) -> Result<()> {
-        let config = self.db.mc_get_config().await?;
+        let config = self.db.mc().mc_get_config().await?;
         let tier_def = get_tier_def(config.tier);
 
         // Check if paused
@@ -205,7 +215,7 @@ impl McScanner {
                 if let Ok(until) = DateTime::parse_from_rfc3339(pause_until) {
                     if Utc::now() > until.with_timezone(&Utc) {
                         // Unpause
-                        self.db.mc_update_pause_state("active", None).await?;
+                        self.db.mc().mc_update_pause_state("active", None).await?;
                         info!("MC scanner pause expired, resuming");
                     } else {
                         // Still paused — broadcast status but skip evaluation
@@ -223,7 +233,7 @@ impl McScanner {
         self.update_risk_state().await?;
 
         // Re-read config after risk updates
-        let config = self.db.mc_get_config().await?;
+        let config = self.db.mc().mc_get_config().await?;
         let tier_def = get_tier_def(config.tier);
 
         let mut scout_results = Vec::new();
@@ -233,12 +243,15 @@ impl McScanner {
             if !market.active || market.closed {
                 continue;
             }
+            if !self.taxonomy.allows(&market.taxonomy) {
+                continue;
+            }
 
             let result = self.evaluate_market(market, disputes, &config, &tier_def).await;
             match result {
                 Ok(Some(scout)) => {
                     // Log to database
-                    let _ = self.db.mc_insert_scout_log(&scout).await;
+                    let _ = self.db.mc().mc_insert_scout_log(&scout).await;
 
                     // Simulate trade if it passed
                     if scout.would_trade {
@@ -267,7 +280,7 @@ impl McScanner {
         self.broadcast_status(mc_tx, &scout_results, &config, &tier_def).await?;
 
         // Cleanup old scout logs (keep last 7 days)
-        let _ = self.db.mc_cleanup_old_scout_logs(7).await;
+        let _ = self.db.mc().mc_cleanup_old_scout_logs(7).await;
 
         Ok(())
     }
@@ -358,11 +371,11 @@ impl McScanner {
             }
         }
 
-        // Category correlation check (max 2 open per category)
+        // Category correlation check (max 2 open per taxonomy category)
         let mut would_trade = passed;
         if passed {
-            if let Some(ref cat) = market.category {
-                let cat_count = self.db.mc_get_category_trade_count(cat).await.unwrap_or(0);
+            if let Some(cat) = market.taxonomy.category_label() {
+                let cat_count = self.db.mc().mc_get_category_trade_count(&cat).await.unwrap_or(0);
                 if cat_count >= 2 {
                     reasons.push(format!("-SKIP category '{}' already has {} open trades", cat, cat_count));
                     would_trade = false;
@@ -370,7 +383,7 @@ impl McScanner {
             }
 
             // Check max positions
-            let open_count = self.db.mc_get_open_trade_count().await.unwrap_or(0);
+            let open_count = self.db.mc().mc_get_open_trade_count().await.unwrap_or(0);
             if open_count >= tier_def.max_positions as i64 {
                 reasons.push(format!("-SKIP max positions {} reached", tier_def.max_positions));
                 would_trade = false;
@@ -388,7 +401,7 @@ impl McScanner {
             side: side_str,
             price: fav_price.to_string(),
             volume: market.volume.to_string(),
-            category: market.category.clone(),
+            category: market.taxonomy.category_label(),
             end_date: market.end_date.map(|d| d.to_rfc3339()),
             passed,
             certainty_score,
@@ -472,12 +485,9 @@ impl McScanner {
         }
 
         // Category dispute history (check if this category has had disputes)
-        if let Some(ref cat) = market.category {
-            let dispute_cats = ["Politics", "Pop Culture"];
-            if dispute_cats.iter().any(|c| cat.contains(c)) {
-                score -= 20;
-                reasons.push(format!("-20 category '{}' has dispute history", cat));
-            }
+        if matches!(market.taxonomy.category, Category::Politics | Category::Culture) {
+            score -= 20;
+            reasons.push(format!("-20 category '{}' has dispute history", market.taxonomy.category.label()));
         }
 
         // Single human resolution
@@ -578,7 +588,7 @@ impl McScanner {
         // shares = bet_size / price
         let shares = if price > Decimal::ZERO { bet_size / price } else { Decimal::ZERO };
 
-        self.db.mc_insert_trade(
+        self.db.mc().mc_insert_trade(
             &scout.market_id,
             scout.condition_id.as_str(),
             &scout.question,
@@ -605,7 +615,7 @@ impl McScanner {
 
     /// Check if any simulated trades have resolved
     async fn check_simulated_resolutions(&self) -> Result<()> {
-        let open_trades = self.db.mc_get_open_trades().await?;
+        let open_trades = self.db.mc().mc_get_open_trades().await?;
 
         if open_trades.is_empty() {
             return Ok(());
@@ -632,7 +642,7 @@ impl McScanner {
                                 let exit = f64::from_str(exit_price).unwrap_or(0.0);
                                 let pnl = (exit - entry) * shares;
 
-                                self.db.mc_update_trade_resolution(
+                                self.db.mc().mc_update_trade_resolution(
                                     trade.id,
                                     exit_price,
                                     &format!("{:.6}", pnl),
@@ -640,13 +650,13 @@ impl McScanner {
                                 ).await?;
 
                                 // Update bankroll
-                                let config = self.db.mc_get_config().await?;
+                                let config = self.db.mc().mc_get_config().await?;
                                 let current_bankroll = f64::from_str(&config.bankroll).unwrap_or(40.0);
                                 let new_bankroll = current_bankroll + pnl;
                                 let peak = f64::from_str(&config.peak_bankroll).unwrap_or(40.0);
                                 let new_peak = peak.max(new_bankroll);
 
-                                self.db.mc_update_bankroll(
+                                self.db.mc().mc_update_bankroll(
                                     &format!("{:.2}", new_bankroll),
                                     &format!("{:.2}", new_peak),
                                 ).await?;
@@ -684,7 +694,7 @@ impl McScanner {
 
     /// Update risk state: drawdown checks, tier promotion/demotion
     async fn update_risk_state(&self) -> Result<()> {
-        let config = self.db.mc_get_config().await?;
+        let config = self.db.mc().mc_get_config().await?;
         let bankroll = f64::from_str(&config.bankroll).unwrap_or(40.0);
         let peak = f64::from_str(&config.peak_bankroll).unwrap_or(40.0);
         let tier_def = get_tier_def(config.tier);
@@ -700,8 +710,8 @@ impl McScanner {
         if drawdown_pct >= 35.0 && config.pause_state == "active" {
             // Pause trading for 48 hours
             let pause_until = (Utc::now() + Duration::hours(48)).to_rfc3339();
-            self.db.mc_update_pause_state("drawdown_paused", Some(&pause_until)).await?;
-            self.db.mc_insert_drawdown_event(
+            self.db.mc().mc_update_pause_state("drawdown_paused", Some(&pause_until)).await?;
+            self.db.mc().mc_insert_drawdown_event(
                 "pause",
                 &config.peak_bankroll,
                 &config.bankroll,
@@ -711,8 +721,8 @@ impl McScanner {
             info!("MC PAUSED: {:.1}% drawdown from peak ${}", drawdown_pct, peak);
         } else if drawdown_pct >= 20.0 && config.pause_state == "active" {
             // Reduce position size
-            self.db.mc_update_pause_state("drawdown_reduced", None).await?;
-            self.db.mc_insert_drawdown_event(
+            self.db.mc().mc_update_pause_state("drawdown_reduced", None).await?;
+            self.db.mc().mc_insert_drawdown_event(
                 "reduce",
                 &config.peak_bankroll,
                 &config.bankroll,
@@ -722,23 +732,23 @@ impl McScanner {
             info!("MC REDUCED: {:.1}% drawdown, halving position size", drawdown_pct);
         } else if drawdown_pct < 15.0 && config.pause_state == "drawdown_reduced" {
             // Resume normal trading
-            self.db.mc_update_pause_state("active", None).await?;
+            self.db.mc().mc_update_pause_state("active", None).await?;
             info!("MC RESUMED: drawdown recovered to {:.1}%", drawdown_pct);
         }
 
         // Weekly loss check (2 losses in 7 days = 48h pause)
-        let recent_losses = self.db.mc_get_recent_losses(7).await.unwrap_or(0);
+        let recent_losses = self.db.mc().mc_get_recent_losses(7).await.unwrap_or(0);
         if recent_losses >= 2 && config.pause_state == "active" {
             let pause_until = (Utc::now() + Duration::hours(48)).to_rfc3339();
-            self.db.mc_update_pause_state("weekly_loss_pause", Some(&pause_until)).await?;
+            self.db.mc().mc_update_pause_state("weekly_loss_pause", Some(&pause_until)).await?;
             info!("MC PAUSED: {} losses in 7 days, pausing 48h", recent_losses);
         }
 
         // Tier promotion/demotion
         if bankroll >= tier_def.promote_at && config.tier < 7 {
             let new_tier = config.tier + 1;
-            self.db.mc_update_tier(new_tier).await?;
-            self.db.mc_insert_tier_history(
+            self.db.mc().mc_update_tier(new_tier).await?;
+            self.db.mc().mc_insert_tier_history(
                 config.tier,
                 new_tier,
                 &config.bankroll,
@@ -747,8 +757,8 @@ impl McScanner {
             info!("MC PROMOTED to tier {} (bankroll: ${:.2})", new_tier, bankroll);
         } else if bankroll <= tier_def.demote_at && config.tier > 1 {
             let new_tier = config.tier - 1;
-            self.db.mc_update_tier(new_tier).await?;
-            self.db.mc_insert_tier_history(
+            self.db.mc().mc_update_tier(new_tier).await?;
+            self.db.mc().mc_insert_tier_history(
                 config.tier,
                 new_tier,
                 &config.bankroll,
@@ -773,9 +783,9 @@ impl McScanner {
         let drawdown = if peak > 0.0 { ((peak - bankroll) / peak) * 100.0 } else { 0.0 };
 
         // Get trade stats
-        let (total_trades, wins, total_pnl) = self.db.mc_get_trade_stats().await.unwrap_or((0, 0, 0.0));
+        let (total_trades, wins, total_pnl) = self.db.mc().mc_get_trade_stats().await.unwrap_or((0, 0, 0.0));
         let win_rate = if total_trades > 0 { (wins as f64 / total_trades as f64) * 100.0 } else { 0.0 };
-        let open_trades = self.db.mc_get_open_trade_count().await.unwrap_or(0);
+        let open_trades = self.db.mc().mc_get_open_trade_count().await.unwrap_or(0);
 
         // Effective bet size (halved if in drawdown_reduced state)
         let effective_bet = if config.pause_state == "drawdown_reduced" {
@@ -1,5 +1,6 @@
 //! Metrics collection for monitoring bot performance
 
+use crate::market_index::{ScanCost, ScanMode};
 use serde::Serialize;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -30,6 +31,13 @@ pub struct MetricsSnapshot {
     pub rate_limiter_general_util: f64,
     pub rate_limiter_post_util: f64,
     pub rate_limiter_delete_util: f64,
+    /// Market scan cycles by mode
+    pub scans_full: u64,
+    pub scans_incremental: u64,
+    /// Gamma requests made by market scans
+    pub scan_requests_total: u64,
+    /// Cost of the most recent scan cycle
+    pub last_scan: Option<ScanCost>,
 }
 
 /// Thread-safe metrics collector
@@ -54,6 +62,10 @@ struct MetricsInner {
     api_errors_total: AtomicU64,
     api_rate_limited: AtomicU64,
     rate_limiter_util: RwLock<(f64, f64, f64)>,
+    scans_full: AtomicU64,
+    scans_incremental: AtomicU64,
+    scan_requests_total: AtomicU64,
+    last_scan: RwLock<Option<ScanCost>>,
 }
 
 impl Metrics {
@@ -74,6 +86,10 @@ impl Metrics {
                 api_errors_total: AtomicU64::new(0),
                 api_rate_limited: AtomicU64::new(0),
                 rate_limiter_util: RwLock::new((0.0, 0.0, 0.0)),
+                scans_full: AtomicU64::new(0),
+                scans_incremental: AtomicU64::new(0),
+                scan_requests_total: AtomicU64::new(0),
+                last_scan: RwLock::new(None),
             }),
         }
     }
@@ -128,6 +144,17 @@ impl Metrics {
         *self.inner.rate_limiter_util.write().await = (general, post, delete);
     }
 
+    pub async fn record_scan(&self, cost: &ScanCost) {
+        match cost.mode {
+            ScanMode::Full => self.inner.scans_full.fetch_add(1, Ordering::Relaxed),
+            ScanMode::Incremental => self.inner.scans_incremental.fetch_add(1, Ordering::Relaxed),
+        };
+        self.inner
+            .scan_requests_total
+            .fetch_add(u64::from(cost.requests), Ordering::Relaxed);
+        *self.inner.last_scan.write().await = Some(cost.clone());
+    }
+
     pub async fn snapshot(&self) -> MetricsSnapshot {
         let (general, post, delete) = *self.inner.rate_limiter_util.read().await;
         MetricsSnapshot {
@@ -147,6 +174,10 @@ impl Metrics {
             rate_limiter_general_util: general,
             rate_limiter_post_util: post,
             rate_limiter_delete_util: delete,
+            scans_full: self.inner.scans_full.load(Ordering::Relaxed),
+            scans_incremental: self.inner.scans_incremental.load(Ordering::Relaxed),
+            scan_requests_total: self.inner.scan_requests_total.load(Ordering::Relaxed),
+            last_scan: self.inner.last_scan.read().await.clone(),
         }
     }
 }
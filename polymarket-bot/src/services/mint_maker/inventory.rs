@@ -1,62 +1,78 @@
 //! Inventory management for Mint Maker - merges matched pairs back to USDC
 
-use crate::db::Database;
-use crate::services::CtfService;
+use crate::db::{Database, MintMakerPairRow};
+use crate::services::{CtfCall, CtfService};
+use alloy::primitives::Address;
 use anyhow::Result;
 use rust_decimal::Decimal;
 use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{info, warn};
 
-/// Merge a matched pair (both sides filled) back into USDC via CTF relay
-pub async fn merge_matched_pair(
+/// Prepare a matched pair (both sides filled) for merging back into USDC.
+///
+/// Marks the pair Merging and returns the merge call to include in the wallet's
+/// relay batch. Returns `None` (and marks the pair MergeFailed or Cancelled)
+/// when there is nothing to merge.
+pub async fn prepare_merge(
     db: &Arc<Database>,
-    pair_id: i64,
-    condition_id: &str,
-    size: &str,
-    private_key: &str,
-    builder_api_key: &str,
-    builder_secret: &str,
-    builder_passphrase: &str,
-    yes_token_id: Option<&str>,
-    no_token_id: Option<&str>,
-    neg_risk: bool,
-) -> Result<Option<String>> {
-    // Mark as merging
-    db.update_mint_maker_pair_status(pair_id, "Merging").await?;
-
-    let amount = Decimal::from_str(size).unwrap_or(Decimal::ZERO);
+    ctf: &CtfService,
+    pair: &MintMakerPairRow,
+    safe_address: Address,
+) -> Result<Option<CtfCall>> {
+    let amount = Decimal::from_str(&pair.size).unwrap_or(Decimal::ZERO);
     if amount <= Decimal::ZERO {
-        warn!("MintMaker: Invalid merge amount for pair {}: {}", pair_id, size);
-        db.update_mint_maker_pair_status(pair_id, "Cancelled").await?;
+        warn!("MintMaker: Invalid merge amount for pair {}: {}", pair.id, pair.size);
+        db.mint_maker().update_mint_maker_pair_status(pair.id, "Cancelled").await?;
         return Ok(None);
     }
 
-    let ctf = CtfService::new();
-    let result = ctf.merge(
-        condition_id,
-        amount,
-        private_key,
-        builder_api_key,
-        builder_secret,
-        builder_passphrase,
-        yes_token_id,
-        no_token_id,
-        neg_risk,
-    ).await?;
+    // Mark as merging
+    db.mint_maker().update_mint_maker_pair_status(pair.id, "Merging").await?;
+
+    match ctf
+        .merge_call(
+            &pair.condition_id,
+            amount,
+            safe_address,
+            pair.yes_token_id.as_deref(),
+            pair.no_token_id.as_deref(),
+            pair.neg_risk,
+        )
+        .await
+    {
+        Ok(call) => Ok(Some(call)),
+        Err(e) if e.to_string().contains("Safe holds 0 tokens") => {
+            // Tokens are gone — either never delivered or already redeemed.
+            // No point retrying.
+            warn!("MintMaker: Pair {} has 0 tokens — marking MergeFailed", pair.id);
+            db.mint_maker().update_mint_maker_pair_status(pair.id, "MergeFailed").await?;
+            Ok(None)
+        }
+        Err(e) => {
+            // Revert to Matched so we can retry
+            db.mint_maker().update_mint_maker_pair_status(pair.id, "Matched").await?;
+            Err(e)
+        }
+    }
+}
 
-    if result.success {
-        let tx_id = result.transaction_id.unwrap_or_else(|| "unknown".to_string());
-        info!("MintMaker: Pair {} merged successfully, tx: {}", pair_id, tx_id);
-        db.mark_mint_maker_pair_merged(pair_id, &tx_id).await?;
-        Ok(Some(tx_id))
-    } else {
-        let err = result.error.unwrap_or_else(|| "unknown error".to_string());
-        warn!("MintMaker: Merge failed for pair {}: {}", pair_id, err);
-        // Revert to Matched so we can retry
-        db.update_mint_maker_pair_status(pair_id, "Matched").await?;
-        // Propagate as Err so the caller can detect 429 rate limits
-        Err(anyhow::anyhow!("{}", err))
+/// Record the outcome of a batched merge for one pair
+pub async fn finish_merge(
+    db: &Arc<Database>,
+    pair_id: i64,
+    result: std::result::Result<&str, &str>,
+) -> Result<()> {
+    match result {
+        Ok(tx_id) => {
+            info!("MintMaker: Pair {} merged successfully, tx: {}", pair_id, tx_id);
+            db.mint_maker().mark_mint_maker_pair_merged(pair_id, tx_id).await
+        }
+        Err(err) => {
+            warn!("MintMaker: Merge failed for pair {}: {}", pair_id, err);
+            // Revert to Matched so we can retry
+            db.mint_maker().update_mint_maker_pair_status(pair_id, "Matched").await
+        }
     }
 }
 
@@ -82,7 +98,7 @@ pub async fn cancel_half_filled(
     }
 
     // Mark pair as cancelled (the filled side token remains in wallet)
-    db.update_mint_maker_pair_status(pair_id, "Cancelled").await?;
+    db.mint_maker().update_mint_maker_pair_status(pair_id, "Cancelled").await?;
     info!("MintMaker: Half-filled pair {} cancelled, unfilled order {} cancelled", pair_id, unfilled_order_id);
 
     Ok(())
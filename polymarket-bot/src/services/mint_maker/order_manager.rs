@@ -2,51 +2,33 @@
 
 use anyhow::Result;
 use alloy::primitives::U256;
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use alloy::signers::Signer;
 use chrono::Utc;
 use polymarket_client_sdk::clob::{
     Client as ClobClient, Config as ClobConfig,
 };
 use polymarket_client_sdk::clob::types::{
-    AssetType, OrderType as ClobOrderType, Side as ClobSide, SignatureType,
+    AssetType, OrderType as ClobOrderType, Side as ClobSide,
     request::UpdateBalanceAllowanceRequest,
 };
 use rust_decimal::Decimal;
 use std::str::FromStr;
 use tracing::{info, warn};
 
-const POLYGON_CHAIN_ID: u64 = 137;
+use crate::services::clob_pool::{ClobAccount, ClobClientPool};
+use crate::services::rate_limiter::EndpointClass;
+use crate::services::signer::WalletSigner;
+
 const CLOB_ENDPOINT: &str = "https://clob.polymarket.com";
 
 /// Place a GTC BUY order for a specific number of shares (maker-only at below-market prices)
 pub async fn place_gtc_bid(
-    private_key: &str,
+    pool: &ClobClientPool,
+    signer: &WalletSigner,
     token_id: &str,
     price: Decimal,
     shares: Decimal,
 ) -> Result<String> {
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-    // Log the EOA and derived Safe address for debugging
-    let eoa_addr = signer.address();
-    let safe_addr = crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa_addr))
-        .unwrap_or_else(|_| "unknown".to_string());
-    info!("place_gtc_bid: EOA={:?}, Safe={}, using GnosisSafe signature type", eoa_addr, safe_addr);
-
-    let clob_config = ClobConfig::builder()
-        .use_server_time(true)
-        .build();
-
-    // Use GnosisSafe signature type so the SDK auto-derives the Safe proxy
-    // address as the funder/maker. Generated wallets hold USDC in the Safe,
-    // not the EOA — without this, the CLOB checks the empty EOA for balance.
-    let client = ClobClient::new(CLOB_ENDPOINT, clob_config)?
-        .authentication_builder(&signer)
-        .signature_type(SignatureType::GnosisSafe)
-        .authenticate()
-        .await?;
-
     let token_id_u256 = U256::from_str_radix(token_id, 10)?;
 
     // Round price down to tick size (0.01 = 2 decimals for most markets).
@@ -63,100 +45,75 @@ pub async fn place_gtc_bid(
     // immediately. On 15-min crypto markets, resting orders get maker (0% fee)
     // while crossing orders pay taker fees (up to 3%). Placing at scanner price
     // gives fast fills while potentially qualifying as maker on thin fresh books.
-    let order = client
-        .limit_order()
-        .token_id(token_id_u256)
-        .size(shares)
-        .side(ClobSide::Buy)
-        .price(price)
-        .order_type(ClobOrderType::GTC)
-        .build()
-        .await?;
-
-    let signed_order = client.sign(&signer, order).await?;
-    let response = client.post_order(signed_order).await?;
-
-    info!("GTC order placed: id={}", response.order_id);
-    Ok(response.order_id)
+    // GnosisSafe signature type makes the Safe proxy the funder/maker.
+    // Generated wallets hold USDC in the Safe, not the EOA — without this,
+    // the CLOB checks the empty EOA for balance.
+    let order_id = pool.run(signer, ClobAccount::GnosisSafe, EndpointClass::PostOrder, |c| async move {
+        let order = c.client
+            .limit_order()
+            .token_id(token_id_u256)
+            .size(shares)
+            .side(ClobSide::Buy)
+            .price(price)
+            .order_type(ClobOrderType::GTC)
+            .build()
+            .await?;
+
+        let signed_order = c.client.sign(&c.signer, order).await?;
+        let response = c.client.post_order(signed_order).await?;
+        Ok(response.order_id)
+    }).await?;
+
+    info!("GTC order placed: id={}", order_id);
+    Ok(order_id)
 }
 
 /// Place a GTC SELL order for a specific number of shares.
 /// Used by stop loss to exit a half-filled position.
 pub async fn place_gtc_sell(
-    private_key: &str,
+    pool: &ClobClientPool,
+    signer: &WalletSigner,
     token_id: &str,
     price: Decimal,
     shares: Decimal,
 ) -> Result<String> {
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-    let eoa_addr = signer.address();
-    let safe_addr = crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa_addr))
-        .unwrap_or_else(|_| "unknown".to_string());
-    info!("place_gtc_sell: EOA={:?}, Safe={}, using GnosisSafe signature type", eoa_addr, safe_addr);
-
-    let clob_config = ClobConfig::builder()
-        .use_server_time(true)
-        .build();
-
-    let client = ClobClient::new(CLOB_ENDPOINT, clob_config)?
-        .authentication_builder(&signer)
-        .signature_type(SignatureType::GnosisSafe)
-        .authenticate()
-        .await?;
-
     let token_id_u256 = U256::from_str_radix(token_id, 10)?;
     let price = price.trunc_with_scale(2);
 
     info!("Placing GTC limit SELL: token={}, price={}, shares={}", token_id, price, shares);
 
-    let order = client
-        .limit_order()
-        .token_id(token_id_u256)
-        .size(shares)
-        .side(ClobSide::Sell)
-        .price(price)
-        .order_type(ClobOrderType::GTC)
-        .build()
-        .await?;
-
-    let signed_order = client.sign(&signer, order).await?;
-    let response = client.post_order(signed_order).await?;
-
-    info!("GTC sell order placed: id={}", response.order_id);
-    Ok(response.order_id)
+    let order_id = pool.run(signer, ClobAccount::GnosisSafe, EndpointClass::PostOrder, |c| async move {
+        let order = c.client
+            .limit_order()
+            .token_id(token_id_u256)
+            .size(shares)
+            .side(ClobSide::Sell)
+            .price(price)
+            .order_type(ClobOrderType::GTC)
+            .build()
+            .await?;
+
+        let signed_order = c.client.sign(&c.signer, order).await?;
+        let response = c.client.post_order(signed_order).await?;
+        Ok(response.order_id)
+    }).await?;
+
+    info!("GTC sell order placed: id={}", order_id);
+    Ok(order_id)
 }
 
 /// Place a FOK (Fill or Kill) BUY order — fills entirely and immediately, or is cancelled.
 /// Used for mint maker pairs to guarantee both sides fill atomically.
 /// Takes USD amount (not shares) — the CLOB determines how many shares you get.
 pub async fn place_fok_buy(
-    private_key: &str,
+    pool: &ClobClientPool,
+    signer: &WalletSigner,
     token_id: &str,
     price: Decimal,
     usd_amount: Decimal,
 ) -> Result<String> {
     use polymarket_client_sdk::clob::types::Amount;
 
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-    let eoa_addr = signer.address();
-    let safe_addr = crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa_addr))
-        .unwrap_or_else(|_| "unknown".to_string());
-    info!("place_fok_buy: EOA={:?}, Safe={}, using GnosisSafe signature type", eoa_addr, safe_addr);
-
-    let clob_config = ClobConfig::builder()
-        .use_server_time(true)
-        .build();
-
-    let client = ClobClient::new(CLOB_ENDPOINT, clob_config)?
-        .authentication_builder(&signer)
-        .signature_type(SignatureType::GnosisSafe)
-        .authenticate()
-        .await?;
-
     let token_id_u256 = U256::from_str_radix(token_id, 10)?;
 
     // Round price to tick size
@@ -164,21 +121,24 @@ pub async fn place_fok_buy(
 
     info!("Placing FOK BUY: token={}, price={}, amount=${}", token_id, price, usd_amount);
 
-    let order = client
-        .market_order()
-        .token_id(token_id_u256)
-        .amount(Amount::usdc(usd_amount)?)
-        .side(ClobSide::Buy)
-        .price(price)
-        .order_type(ClobOrderType::FOK)
-        .build()
-        .await?;
-
-    let signed_order = client.sign(&signer, order).await?;
-    let response = client.post_order(signed_order).await?;
-
-    info!("FOK order placed: id={}", response.order_id);
-    Ok(response.order_id)
+    let order_id = pool.run(signer, ClobAccount::GnosisSafe, EndpointClass::PostOrder, |c| async move {
+        let order = c.client
+            .market_order()
+            .token_id(token_id_u256)
+            .amount(Amount::usdc(usd_amount)?)
+            .side(ClobSide::Buy)
+            .price(price)
+            .order_type(ClobOrderType::FOK)
+            .build()
+            .await?;
+
+        let signed_order = c.client.sign(&c.signer, order).await?;
+        let response = c.client.post_order(signed_order).await?;
+        Ok(response.order_id)
+    }).await?;
+
+    info!("FOK order placed: id={}", order_id);
+    Ok(order_id)
 }
 
 /// Cancel an order via CLOB SDK.
@@ -195,25 +155,15 @@ pub async fn cancel_order(
     cancel_order_raw(order_id, _wallet_address, _api_key, _api_secret, _api_passphrase).await
 }
 
-/// Cancel an order using a private key (SDK-based, for stop loss and internal use).
+/// Cancel an order with a wallet signer (SDK-based, for stop loss and internal use).
 pub async fn cancel_order_with_key(
-    private_key: &str,
+    pool: &ClobClientPool,
+    signer: &WalletSigner,
     order_id: &str,
 ) -> Result<()> {
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
-
-    let clob_config = ClobConfig::builder()
-        .use_server_time(true)
-        .build();
-
-    let client = ClobClient::new(CLOB_ENDPOINT, clob_config)?
-        .authentication_builder(&signer)
-        .signature_type(SignatureType::GnosisSafe)
-        .authenticate()
-        .await?;
-
-    let resp = client.cancel_order(order_id).await?;
+    let resp = pool.run(signer, ClobAccount::GnosisSafe, EndpointClass::DeleteOrder, |c| async move {
+        Ok(c.client.cancel_order(order_id).await?)
+    }).await?;
     info!("Order {} cancelled via SDK: {:?}", &order_id[..16.min(order_id.len())], resp);
     Ok(())
 }
@@ -437,24 +387,14 @@ pub enum FillStatus {
 ///
 /// This must be called with GnosisSafe signature type so the CLOB checks the Safe
 /// proxy address (where USDC lives), not the bare EOA.
-pub async fn refresh_clob_allowance_cache(private_key: &str) -> Result<()> {
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
+pub async fn refresh_clob_allowance_cache(pool: &ClobClientPool, signer: &WalletSigner) -> Result<()> {
+    let pooled = pool.client(signer, ClobAccount::GnosisSafe).await?;
+    let client = &pooled.client;
 
-    let eoa_addr = signer.address();
+    let eoa_addr = pooled.signer.address();
     let safe_addr = crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa_addr))
         .unwrap_or_else(|_| "unknown".to_string());
 
-    let clob_config = ClobConfig::builder()
-        .use_server_time(true)
-        .build();
-
-    let client = ClobClient::new(CLOB_ENDPOINT, clob_config)?
-        .authentication_builder(&signer)
-        .signature_type(SignatureType::GnosisSafe)
-        .authenticate()
-        .await?;
-
     // Refresh COLLATERAL (USDC) allowance cache
     let collateral_req = UpdateBalanceAllowanceRequest::builder()
         .asset_type(AssetType::Collateral)
@@ -482,36 +422,33 @@ pub async fn refresh_clob_allowance_cache(private_key: &str) -> Result<()> {
 /// before `enable` was called (or after a restart where key_store still has the key
 /// but API creds were lost), this ensures creds are available.
 pub async fn ensure_clob_api_credentials(
-    private_key: &str,
+    signer: &WalletSigner,
     db: &crate::db::Database,
     wallet_address: &str,
 ) -> Result<(String, String, String)> {
     use polymarket_client_sdk::auth::ExposeSecret;
 
     // Check if we already have credentials stored
-    if let Ok(Some(creds)) = db.get_api_credentials(wallet_address).await {
+    if let Ok(Some(creds)) = db.auth().get_api_credentials(wallet_address).await {
         return Ok(creds);
     }
 
-    info!("MintMaker: Deriving CLOB API credentials for {}", wallet_address);
-
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
+    info!("Deriving CLOB API credentials for {}", wallet_address);
 
     let clob_config = ClobConfig::builder()
         .use_server_time(true)
         .build();
 
     let unauth_client = ClobClient::new(CLOB_ENDPOINT, clob_config)?;
-    let creds = unauth_client.create_or_derive_api_key(&signer, None).await?;
+    let creds = unauth_client.create_or_derive_api_key(signer, None).await?;
 
     let api_key = creds.key().to_string();
     let api_secret = creds.secret().expose_secret().to_string();
     let api_passphrase = creds.passphrase().expose_secret().to_string();
 
     // Store for future use
-    db.store_api_credentials(wallet_address, &api_key, &api_secret, &api_passphrase).await?;
-    info!("MintMaker: Stored CLOB API credentials for {}", wallet_address);
+    db.auth().store_api_credentials(wallet_address, &api_key, &api_secret, &api_passphrase).await?;
+    info!("Stored CLOB API credentials for {}", wallet_address);
 
     Ok((api_key, api_secret, api_passphrase))
 }
@@ -565,12 +502,8 @@ pub async fn fetch_safe_usdc_balance(client: &reqwest::Client, safe_address: &st
     anyhow::bail!("Failed to fetch USDC balance from all RPC endpoints")
 }
 
-/// Derive the Safe proxy address for an EOA address
-pub fn derive_safe_address(private_key: &str) -> Result<String> {
-    use alloy::signers::local::PrivateKeySigner;
-    use alloy::signers::Signer;
-    let signer: PrivateKeySigner = private_key.parse()?;
-    let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
+/// Derive the Safe proxy address for a signer's EOA
+pub fn derive_safe_address(signer: &WalletSigner) -> Result<String> {
     let eoa_addr = signer.address();
     crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa_addr))
         .map_err(|e| anyhow::anyhow!("Failed to derive Safe address: {}", e))
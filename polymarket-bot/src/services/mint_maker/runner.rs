@@ -1,20 +1,20 @@
 //! Mint Maker autonomous runner - the core service loop
 
 use crate::config::MintMakerConfig;
-use crate::db::Database;
+use crate::db::{Database, MintMakerPairRow};
 use crate::services::auto_trader::KeyStore;
 use crate::services::price_ws::PriceUpdate;
+use crate::services::signer::WalletSigner;
 use crate::services::safe_activation::{self, BuilderCredentials};
-use crate::services::TickSizeCache;
+use crate::services::{ClobClientPool, CtfCall, CtfService, OnchainJobTracker, TickSizeCache};
 use crate::strategies::MintMakerStrategy;
-use crate::types::MintMakerMarket;
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use crate::types::{MintMakerMarket, OnchainJobStatus};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::sync::{broadcast, watch, Mutex, RwLock};
 use tokio::time::Instant;
 use chrono::Utc;
 use tracing::{debug, info, warn};
@@ -24,10 +24,48 @@ use super::inventory;
 use super::scanner;
 use super::types::{MintMakerMarketStatus, MintMakerStatsSnapshot, MintMakerStatusUpdate};
 
+/// Max calls bundled into one MultiSend relay transaction per wallet per cycle
+const RELAY_BATCH_CAP: usize = 12;
+
+/// What a call in a wallet's relay batch was for, so the result can be attributed back
+enum RelayBatchItem {
+    /// Merge of a matched pair
+    Merge(MintMakerPairRow),
+    /// Redeem of every pair on a resolved condition
+    Redeem(String),
+}
+
+/// A relay batch handed to the job worker, settled when its job finishes
+struct RelayBatch {
+    wallet_address: String,
+    items: Vec<RelayBatchItem>,
+    /// The call for each item, kept to resubmit part of the batch
+    calls: Vec<CtfCall>,
+}
+
+impl RelayBatch {
+    /// Split off the second half, if there is more than one item
+    fn bisect(&mut self) -> Option<RelayBatch> {
+        if self.items.len() < 2 {
+            return None;
+        }
+        let mid = self.items.len() / 2;
+        Some(RelayBatch {
+            wallet_address: self.wallet_address.clone(),
+            items: self.items.split_off(mid),
+            calls: self.calls.split_off(mid),
+        })
+    }
+}
+
 /// The Mint Maker runner - manages the autonomous loop
 pub struct MintMakerRunner {
     db: Arc<Database>,
     key_store: KeyStore,
+    clob_pool: Arc<ClobClientPool>,
+    /// On-chain job queue (Safe activation, merges, redeems)
+    onchain_jobs: Arc<OnchainJobTracker>,
+    ctf: CtfService,
     config: MintMakerConfig,
     client: reqwest::Client,
     _tick_size_cache: Arc<TickSizeCache>,
@@ -43,16 +81,19 @@ pub struct MintMakerRunner {
     merge_tracker: Mutex<HashMap<i64, (u32, Instant)>>,
     /// Relay rate-limit backoff: skip all relay ops (merge/redeem) until this time
     relay_backoff_until: Mutex<Option<chrono::DateTime<Utc>>>,
+    /// Submitted relay batches by on-chain job ID
+    relay_batches: Mutex<HashMap<i64, RelayBatch>>,
 }
 
 impl MintMakerRunner {
     pub fn new(
         db: Arc<Database>,
         key_store: KeyStore,
+        clob_pool: Arc<ClobClientPool>,
+        onchain_jobs: Arc<OnchainJobTracker>,
         config: MintMakerConfig,
         client: reqwest::Client,
         tick_size_cache: Arc<TickSizeCache>,
-        price_tx: broadcast::Sender<PriceUpdate>,
         mm_live_tokens: Arc<RwLock<HashSet<String>>>,
     ) -> Self {
         let price_cache: Arc<RwLock<HashMap<String, Decimal>>> =
@@ -60,34 +101,12 @@ impl MintMakerRunner {
         let bid_cache: Arc<RwLock<HashMap<String, Decimal>>> =
             Arc::new(RwLock::new(HashMap::new()));
 
-        // Spawn background listener for real-time price updates
-        let cache = price_cache.clone();
-        let bcache = bid_cache.clone();
-        let mut price_rx = price_tx.subscribe();
-        tokio::spawn(async move {
-            loop {
-                match price_rx.recv().await {
-                    Ok(update) => {
-                        if let Ok(price) = Decimal::from_str(&update.price) {
-                            cache.write().await.insert(update.token_id.clone(), price);
-                        }
-                        if let Some(bid_str) = &update.best_bid {
-                            if let Ok(bid) = Decimal::from_str(bid_str) {
-                                bcache.write().await.insert(update.token_id, bid);
-                            }
-                        }
-                    }
-                    Err(broadcast::error::RecvError::Lagged(n)) => {
-                        debug!("MintMaker price listener lagged {} messages", n);
-                    }
-                    Err(broadcast::error::RecvError::Closed) => break,
-                }
-            }
-        });
-
         Self {
             db,
             key_store,
+            clob_pool,
+            ctf: CtfService::new(onchain_jobs.clone()),
+            onchain_jobs,
             config,
             client,
             _tick_size_cache: tick_size_cache,
@@ -97,6 +116,7 @@ impl MintMakerRunner {
             activated_wallets: Mutex::new(HashSet::new()),
             merge_tracker: Mutex::new(HashMap::new()),
             relay_backoff_until: Mutex::new(None),
+            relay_batches: Mutex::new(HashMap::new()),
         }
     }
 
@@ -115,14 +135,14 @@ impl MintMakerRunner {
             } else {
                 info!("MintMaker: Relay backoff expired, resuming relay operations");
                 // Clear DB backoff too
-                let _ = self.db.set_mint_maker_relay_backoff(wallet_address, None).await;
+                let _ = self.db.mint_maker().set_mint_maker_relay_backoff(wallet_address, None).await;
                 return false;
             }
         }
         drop(guard);
 
         // Check DB for persisted backoff (handles restart case)
-        if let Ok(settings) = self.db.get_mint_maker_settings(wallet_address).await {
+        if let Ok(settings) = self.db.mint_maker().get_mint_maker_settings(wallet_address).await {
             if let Some(backoff_str) = settings.relay_backoff_until {
                 if let Ok(until) = chrono::DateTime::parse_from_rfc3339(&backoff_str) {
                     let until = until.with_timezone(&Utc);
@@ -134,7 +154,7 @@ impl MintMakerRunner {
                         return true;
                     } else {
                         // Expired, clear from DB
-                        let _ = self.db.set_mint_maker_relay_backoff(wallet_address, None).await;
+                        let _ = self.db.mint_maker().set_mint_maker_relay_backoff(wallet_address, None).await;
                     }
                 }
             }
@@ -149,7 +169,7 @@ impl MintMakerRunner {
         warn!("MintMaker: Relay 429 — backing off for {}s (until {})", seconds, until.format("%H:%M:%S"));
         *self.relay_backoff_until.lock().await = Some(until);
         // Persist to DB
-        let _ = self.db.set_mint_maker_relay_backoff(wallet_address, Some(&until.to_rfc3339())).await;
+        let _ = self.db.mint_maker().set_mint_maker_relay_backoff(wallet_address, Some(&until.to_rfc3339())).await;
     }
 
     /// Parse "resets in N seconds" from a relay 429 error message, returns seconds or a default
@@ -167,22 +187,115 @@ impl MintMakerRunner {
         3600 // 1 hour
     }
 
-    /// Main run loop - fetches markets directly and manages pairs
+    /// Keep the live price and best bid caches up to date from the price
+    /// WebSocket until `stop` is set
+    pub async fn listen_prices(
+        &self,
+        mut price_rx: broadcast::Receiver<PriceUpdate>,
+        mut stop: watch::Receiver<bool>,
+    ) {
+        loop {
+            let received = tokio::select! {
+                received = price_rx.recv() => received,
+                _ = stop.changed() => break,
+            };
+            match received {
+                Ok(update) => {
+                    if let Ok(price) = Decimal::from_str(&update.price) {
+                        self.price_cache.write().await.insert(update.token_id.clone(), price);
+                    }
+                    if let Some(bid_str) = &update.best_bid {
+                        if let Ok(bid) = Decimal::from_str(bid_str) {
+                            self.bid_cache.write().await.insert(update.token_id, bid);
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    debug!("MintMaker price listener lagged {} messages", n);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Main run loop - fetches markets directly and manages pairs until `stop` is set
     pub async fn run(
         &self,
         status_tx: broadcast::Sender<MintMakerStatusUpdate>,
+        mut stop: watch::Receiver<bool>,
     ) {
         info!("MintMaker runner started (dedicated scanner mode)");
+        self.restore_state().await;
 
         let mut interval = tokio::time::interval(
             std::time::Duration::from_secs(self.config.rebalance_interval_seconds),
         );
+        let mut job_updates = self.onchain_jobs.subscribe();
+        let mut jobs_open = true;
 
         loop {
-            interval.tick().await;
-            if let Err(e) = self.run_cycle(&status_tx).await {
-                warn!("MintMaker cycle error: {}", e);
+            tokio::select! {
+                _ = stop.changed() => break,
+                _ = interval.tick() => {
+                    // Catches a job that settled before its batch was recorded
+                    self.settle_relay_batches().await;
+                    if let Err(e) = self.run_cycle(&status_tx).await {
+                        warn!("MintMaker cycle error: {}", e);
+                    }
+                }
+                update = job_updates.recv(), if jobs_open => match update {
+                    Ok(job) if !job.status.is_terminal() => {}
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => self.settle_relay_batches().await,
+                    Err(broadcast::error::RecvError::Closed) => jobs_open = false,
+                },
+            }
+        }
+    }
+
+    /// Save in-memory state a restart would otherwise lose: merge attempt counts
+    /// and an active relay backoff. Called on shutdown once the loop is stopped.
+    pub async fn persist_state(&self) {
+        let attempts: Vec<(i64, i64)> = self
+            .merge_tracker
+            .lock()
+            .await
+            .iter()
+            .map(|(pair_id, (count, _))| (*pair_id, i64::from(*count)))
+            .collect();
+        match self.db.mint_maker().save_mint_maker_merge_attempts(&attempts).await {
+            Ok(()) => info!("MintMaker: Saved merge attempts for {} pairs", attempts.len()),
+            Err(e) => warn!("MintMaker: Failed to save merge attempts: {}", e),
+        }
+
+        let backoff = *self.relay_backoff_until.lock().await;
+        if let Some(until) = backoff.filter(|until| *until > Utc::now()) {
+            let wallets = self.db.mint_maker().get_mint_maker_enabled_wallets().await.unwrap_or_default();
+            for wallet in &wallets {
+                if let Err(e) = self
+                    .db
+                    .mint_maker()
+                    .set_mint_maker_relay_backoff(wallet, Some(&until.to_rfc3339()))
+                    .await
+                {
+                    warn!("MintMaker: Failed to save relay backoff for {}: {}", wallet, e);
+                }
+            }
+        }
+    }
+
+    /// Reload merge attempt counts saved by `persist_state`; the cooldown restarts
+    async fn restore_state(&self) {
+        match self.db.mint_maker().get_mint_maker_merge_attempts().await {
+            Ok(saved) if !saved.is_empty() => {
+                let mut tracker = self.merge_tracker.lock().await;
+                for (pair_id, count) in &saved {
+                    let count = u32::try_from(*count).unwrap_or(u32::MAX);
+                    tracker.insert(*pair_id, (count, Instant::now()));
+                }
+                info!("MintMaker: Restored merge attempts for {} pairs", saved.len());
             }
+            Ok(_) => {}
+            Err(e) => warn!("MintMaker: Failed to restore merge attempts: {}", e),
         }
     }
 
@@ -226,7 +339,7 @@ impl MintMakerRunner {
             }
             // Also register tokens from half-filled pairs so stop loss can track them
             // even after the market drops out of the scanner
-            if let Ok(hf_tokens) = self.db.get_mint_maker_half_filled_token_ids().await {
+            if let Ok(hf_tokens) = self.db.mint_maker().get_mint_maker_half_filled_token_ids().await {
                 for (yes_tid, no_tid) in &hf_tokens {
                     if let Some(tid) = yes_tid {
                         if !tid.is_empty() { live_tokens.insert(tid.clone()); }
@@ -253,8 +366,8 @@ impl MintMakerRunner {
 
         // Get wallets to process: enabled wallets + any wallet with open pairs
         // (so stop loss and fill tracking work even when mint maker is disabled)
-        let enabled_wallets = self.db.get_mint_maker_enabled_wallets().await?;
-        let open_pair_wallets = self.db.get_mint_maker_wallets_with_open_pairs().await.unwrap_or_default();
+        let enabled_wallets = self.db.mint_maker().get_mint_maker_enabled_wallets().await?;
+        let open_pair_wallets = self.db.mint_maker().get_mint_maker_wallets_with_open_pairs().await.unwrap_or_default();
 
         let mut all_wallets = enabled_wallets.clone();
         for w in &open_pair_wallets {
@@ -295,17 +408,17 @@ impl MintMakerRunner {
         wallet_address: &str,
         raw_markets: &[crate::types::TrackedMarket],
     ) -> anyhow::Result<()> {
-        let settings = self.db.get_mint_maker_settings(wallet_address).await?;
+        let settings = self.db.mint_maker().get_mint_maker_settings(wallet_address).await?;
 
         // Get API credentials for order management.
         // If missing (e.g. runner restarted, enable never called), derive them now.
-        let (api_key, api_secret, api_passphrase) = match self.db.get_api_credentials(wallet_address).await? {
+        let (api_key, api_secret, api_passphrase) = match self.db.auth().get_api_credentials(wallet_address).await? {
             Some(c) => c,
             None => {
-                // Try to derive credentials if we have the private key
-                match self.key_store.get_key(wallet_address).await {
-                    Some(pk) => {
-                        match order_manager::ensure_clob_api_credentials(&pk, &self.db, wallet_address).await {
+                // Try to derive credentials if the wallet's key is unlocked
+                match self.key_store.signer(wallet_address).await {
+                    Some(signer) => {
+                        match order_manager::ensure_clob_api_credentials(&signer, &self.db, wallet_address).await {
                             Ok(c) => c,
                             Err(e) => {
                                 warn!("MintMaker: Failed to derive API credentials for {}: {}", wallet_address, e);
@@ -326,7 +439,7 @@ impl MintMakerRunner {
         // are protected and tracked properly.
 
         // 1. Check fills on open pairs
-        let open_pairs = self.db.get_mint_maker_open_pairs(wallet_address).await?;
+        let open_pairs = self.db.mint_maker().get_mint_maker_open_pairs(wallet_address).await?;
         for pair in &open_pairs {
             // Validate order_id consistency with status
             let has_yes = !pair.yes_order_id.is_empty();
@@ -340,7 +453,7 @@ impl MintMakerRunner {
                             "MintMaker: Pair {} has status Pending but missing order IDs (yes={}, no={}) — marking Cancelled",
                             pair.id, has_yes, has_no
                         );
-                        let _ = self.db.update_mint_maker_pair_status(pair.id, "Cancelled").await;
+                        let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "Cancelled").await;
                         continue;
                     }
                 }
@@ -351,7 +464,7 @@ impl MintMakerRunner {
                             "MintMaker: Pair {} has status ExpPlaced but invalid order IDs (yes={}, no={}) — marking Cancelled",
                             pair.id, has_yes, has_no
                         );
-                        let _ = self.db.update_mint_maker_pair_status(pair.id, "Cancelled").await;
+                        let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "Cancelled").await;
                         continue;
                     }
                 }
@@ -435,9 +548,10 @@ impl MintMakerRunner {
                 let cheap_price: Decimal = cheap_price_str.parse().unwrap_or(Decimal::ZERO);
                 let cheap_shares: Decimal = cheap_shares_str.parse().unwrap_or(Decimal::ZERO);
 
-                if let Some(private_key) = self.key_store.get_key(wallet_address).await {
+                if let Some(signer) = self.key_store.signer(wallet_address).await {
                     match order_manager::place_gtc_bid(
-                        &private_key,
+                        &self.clob_pool,
+                        &signer,
                         cheap_token,
                         cheap_price,
                         cheap_shares,
@@ -446,7 +560,7 @@ impl MintMakerRunner {
                     {
                         Ok(cheap_order_id) => {
                             let exp_fill = fill_price.as_deref().unwrap_or("0");
-                            let _ = self.db.update_mint_maker_pair_cheap_order(
+                            let _ = self.db.mint_maker().update_mint_maker_pair_cheap_order(
                                 pair.id,
                                 &cheap_order_id,
                                 exp_fill,
@@ -454,10 +568,10 @@ impl MintMakerRunner {
                             ).await;
 
                             // Update analytics: expensive side filled, cheap side placed
-                            let _ = self.db.update_analytics_expensive_filled(pair.id).await;
-                            let _ = self.db.update_analytics_cheap_placed(pair.id).await;
+                            let _ = self.db.mint_maker().update_analytics_expensive_filled(pair.id).await;
+                            let _ = self.db.mint_maker().update_analytics_cheap_placed(pair.id).await;
 
-                            let _ = self.db.log_mint_maker_action(
+                            let _ = self.db.mint_maker().log_mint_maker_action(
                                 wallet_address,
                                 "cheap_placed",
                                 Some(&pair.market_id),
@@ -493,8 +607,8 @@ impl MintMakerRunner {
                 }
             } else if status == FillStatus::Cancelled {
                 // Expensive side was cancelled (by CLOB or manually) — zero loss
-                let _ = self.db.update_mint_maker_pair_status(pair.id, "Cancelled").await;
-                let _ = self.db.log_mint_maker_action(
+                let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "Cancelled").await;
+                let _ = self.db.mint_maker().log_mint_maker_action(
                     wallet_address,
                     "exp_cancelled",
                     Some(&pair.market_id),
@@ -532,161 +646,23 @@ impl MintMakerRunner {
             &wallet_address[..8], settings.assets, raw_markets.len(), eligible_markets.len(), eligible_ids
         );
 
-        // 2. Merge matched pairs (with cooldown + retry limit)
+        // 2. Merge matched pairs and redeem resolved markets in one MultiSend batch.
         //    Skipped entirely if relay is backed off from a 429.
-        let matched_pairs = self
-            .db
-            .get_mint_maker_pairs_by_status(wallet_address, "Matched")
-            .await?;
-        if !matched_pairs.is_empty() && !self.is_relay_backed_off(wallet_address).await {
-            if let Some(private_key) = self.key_store.get_key(wallet_address).await {
-                match (
-                    std::env::var("POLY_BUILDER_API_KEY").ok(),
-                    std::env::var("POLY_BUILDER_SECRET").ok(),
-                    std::env::var("POLY_BUILDER_PASSPHRASE").ok(),
-                ) {
-                    (Some(bk), Some(bs), Some(bp)) => {
-                        const MERGE_PER_CYCLE_CAP: u32 = 3;
-                        let mut merges_this_cycle: u32 = 0;
-                        for pair in &matched_pairs {
-                            if merges_this_cycle >= MERGE_PER_CYCLE_CAP {
-                                debug!("MintMaker: Hit merge cap ({}/cycle), remaining pairs will retry next cycle", MERGE_PER_CYCLE_CAP);
-                                break;
-                            }
-                            // Check merge cooldown: wait 30s between attempts, give up after 5 min
-                            const MERGE_COOLDOWN_SECS: u64 = 30;
-                            const MERGE_MAX_ATTEMPTS: u32 = 10; // 10 * 30s = 5 min
-
-                            let mut tracker = self.merge_tracker.lock().await;
-                            let (attempts, last_attempt) = tracker
-                                .entry(pair.id)
-                                .or_insert((0, Instant::now() - std::time::Duration::from_secs(MERGE_COOLDOWN_SECS)));
-
-                            if *attempts >= MERGE_MAX_ATTEMPTS {
-                                // Too many failed attempts — give up
-                                warn!(
-                                    "MintMaker: Pair {} failed to merge after {} attempts — marking MergeFailed",
-                                    pair.id, attempts
-                                );
-                                let _ = self.db.update_mint_maker_pair_status(pair.id, "MergeFailed").await;
-                                tracker.remove(&pair.id);
-                                continue;
-                            }
-
-                            if last_attempt.elapsed() < std::time::Duration::from_secs(MERGE_COOLDOWN_SECS) {
-                                debug!(
-                                    "MintMaker: Pair {} merge cooldown ({}/{} attempts, {}s since last)",
-                                    pair.id, attempts, MERGE_MAX_ATTEMPTS, last_attempt.elapsed().as_secs()
-                                );
-                                continue;
-                            }
-
-                            *attempts += 1;
-                            *last_attempt = Instant::now();
-                            let attempt_num = *attempts;
-                            drop(tracker);
-
-                            merges_this_cycle += 1;
-                            match inventory::merge_matched_pair(
-                                &self.db,
-                                pair.id,
-                                &pair.condition_id,
-                                &pair.size,
-                                &private_key,
-                                &bk,
-                                &bs,
-                                &bp,
-                                pair.yes_token_id.as_deref(),
-                                pair.no_token_id.as_deref(),
-                                pair.neg_risk,
-                            )
-                            .await
-                            {
-                                Ok(Some(tx_id)) => {
-                                    // Success — remove from tracker
-                                    self.merge_tracker.lock().await.remove(&pair.id);
-
-                                    // Update analytics: pair merged successfully
-                                    let merge_pnl = pair.profit.as_ref()
-                                        .and_then(|p| p.parse::<f64>().ok())
-                                        .unwrap_or(0.0);
-                                    let _ = self.db.update_analytics_merged(pair.id, merge_pnl).await;
-
-                                    let _ = self
-                                        .db
-                                        .log_mint_maker_action(
-                                            wallet_address,
-                                            "merge",
-                                            Some(&pair.market_id),
-                                            Some(&pair.question),
-                                            Some(&pair.asset),
-                                            None,
-                                            None,
-                                            pair.pair_cost.as_deref(),
-                                            pair.profit.as_deref(),
-                                            Some(&pair.size),
-                                            Some(&format!("tx: {}", tx_id)),
-                                        )
-                                        .await;
-                                }
-                                Ok(None) => {
-                                    info!(
-                                        "MintMaker: Pair {} merge attempt {}/{} — waiting for token settlement",
-                                        pair.id, attempt_num, MERGE_MAX_ATTEMPTS
-                                    );
-                                }
-                                Err(e) => {
-                                    let err_str = e.to_string();
-                                    if err_str.contains("Relay error 429") || err_str.contains("rate limit") {
-                                        let backoff_secs = Self::parse_relay_backoff_seconds(&err_str);
-                                        self.set_relay_backoff(wallet_address, backoff_secs).await;
-                                        // Stop trying more merges this cycle
-                                        break;
-                                    }
-                                    if err_str.contains("Safe holds 0 tokens") {
-                                        // Tokens are gone — either never delivered or already redeemed.
-                                        // No point retrying.
-                                        warn!("MintMaker: Pair {} has 0 tokens — marking MergeFailed", pair.id);
-                                        let _ = self.db.update_mint_maker_pair_status(pair.id, "MergeFailed").await;
-                                        self.merge_tracker.lock().await.remove(&pair.id);
-                                        continue;
-                                    }
-                                    warn!("MintMaker merge error for pair {}: {}", pair.id, err_str);
-                                }
-                            }
-                        }
-                    }
-                    _ => {
-                        warn!(
-                            "MintMaker: {} matched pairs waiting to merge but POLY_BUILDER_* env vars not set! \
-                             Set POLY_BUILDER_API_KEY, POLY_BUILDER_SECRET, POLY_BUILDER_PASSPHRASE.",
-                            matched_pairs.len()
-                        );
-                    }
-                }
-            } else {
-                warn!(
-                    "MintMaker: {} matched pairs waiting to merge but no private key loaded for {}. \
-                     Re-click Enable in the UI.",
-                    matched_pairs.len(), &wallet_address[..8]
-                );
+        if !self.is_relay_backed_off(wallet_address).await {
+            if let Err(e) = self.run_relay_batch(wallet_address).await {
+                warn!("MintMaker relay batch error for {}: {}", &wallet_address[..8], e);
             }
         }
 
-        // 2b. Auto-redeem resolved markets
-        if let Err(e) = self.check_auto_redeem(wallet_address).await {
-            warn!("MintMaker auto-redeem error for {}: {}", &wallet_address[..8], e);
-        }
-
         // 3. Cancel expired pairs — only cancel Pending/ExpPlaced orders whose market has closed
         //    (end_date has passed). Orders on open/future markets should keep waiting.
         let pending_pairs = self
             .db
-            .get_mint_maker_pairs_by_status(wallet_address, "Pending")
+            .mint_maker().get_mint_maker_pairs_by_status(wallet_address, "Pending")
             .await?;
         let exp_placed_pairs = self
             .db
-            .get_mint_maker_pairs_by_status(wallet_address, "ExpPlaced")
+            .mint_maker().get_mint_maker_pairs_by_status(wallet_address, "ExpPlaced")
             .await?;
         let cancelable_pairs: Vec<_> = pending_pairs.iter().chain(exp_placed_pairs.iter()).collect();
         let now = Utc::now();
@@ -729,11 +705,11 @@ impl MintMakerRunner {
             }
             let _ = self
                 .db
-                .update_mint_maker_pair_status(pair.id, "Cancelled")
+                .mint_maker().update_mint_maker_pair_status(pair.id, "Cancelled")
                 .await;
             let _ = self
                 .db
-                .log_mint_maker_action(
+                .mint_maker().log_mint_maker_action(
                     wallet_address,
                     "cancel_expired",
                     Some(&pair.market_id),
@@ -755,27 +731,23 @@ impl MintMakerRunner {
                 "MintMaker: AUTO-PLACE ON for {} — assets={:?} eligible={} max_markets={}",
                 &wallet_address[..8], settings.assets, eligible_markets.len(), settings.auto_max_markets
             );
-            if let Some(private_key) = self.key_store.get_key(wallet_address).await {
+            if let Some(signer) = self.key_store.signer(wallet_address).await {
                 // Ensure Safe has CLOB approval before placing any orders
-                let signer_result: Result<PrivateKeySigner, _> = private_key.parse();
-                if let Ok(signer) = signer_result {
-                    let signer = signer.with_chain_id(Some(137));
-                    if let (Some(bk), Some(bs), Some(bp)) = (
-                        std::env::var("POLY_BUILDER_API_KEY").ok(),
-                        std::env::var("POLY_BUILDER_SECRET").ok(),
-                        std::env::var("POLY_BUILDER_PASSPHRASE").ok(),
-                    ) {
-                        let bcreds = BuilderCredentials { api_key: bk, secret: bs, passphrase: bp };
-                        // Only check Safe activation once per session per wallet
-                        let already_activated = self.activated_wallets.lock().await.contains(wallet_address);
-                        if !already_activated {
-                            match safe_activation::ensure_safe_activated(&signer, &bcreds).await {
-                                Ok(addr) => {
-                                    info!("MintMaker: Safe ready at {}", addr);
-                                    self.activated_wallets.lock().await.insert(wallet_address.to_string());
-                                }
-                                Err(e) => warn!("MintMaker: Safe activation failed: {}", e),
+                if let (Some(bk), Some(bs), Some(bp)) = (
+                    std::env::var("POLY_BUILDER_API_KEY").ok(),
+                    std::env::var("POLY_BUILDER_SECRET").ok(),
+                    std::env::var("POLY_BUILDER_PASSPHRASE").ok(),
+                ) {
+                    let bcreds = BuilderCredentials { api_key: bk, secret: bs, passphrase: bp };
+                    // Only check Safe activation once per session per wallet
+                    let already_activated = self.activated_wallets.lock().await.contains(wallet_address);
+                    if !already_activated {
+                        match safe_activation::ensure_safe_activated(&signer, &bcreds, &self.onchain_jobs).await {
+                            Ok(addr) => {
+                                info!("MintMaker: Safe ready at {}", addr);
+                                self.activated_wallets.lock().await.insert(wallet_address.to_string());
                             }
+                            Err(e) => warn!("MintMaker: Safe activation failed: {}", e),
                         }
                     }
                 }
@@ -783,12 +755,12 @@ impl MintMakerRunner {
                 // Refresh CLOB's cached view of on-chain balance & allowances.
                 // Without this, the CLOB rejects orders with "insufficient balance"
                 // even though on-chain approvals are set.
-                if let Err(e) = order_manager::refresh_clob_allowance_cache(&private_key).await {
+                if let Err(e) = order_manager::refresh_clob_allowance_cache(&self.clob_pool, &signer).await {
                     warn!("MintMaker: CLOB cache refresh failed: {}", e);
                 }
 
                 // Check Safe balance once before placing
-                let safe_balance = match order_manager::derive_safe_address(&private_key) {
+                let safe_balance = match order_manager::derive_safe_address(&signer) {
                     Ok(safe_addr) => {
                         order_manager::fetch_safe_usdc_balance(&self.client, &safe_addr)
                             .await
@@ -811,7 +783,7 @@ impl MintMakerRunner {
                 // so we don't double-spend across cycles (on-chain balance doesn't change
                 // until GTC orders actually fill).
                 let committed = Decimal::from_str(
-                    &format!("{:.4}", self.db.sum_mint_maker_committed_capital(wallet_address).await.unwrap_or(0.0))
+                    &format!("{:.4}", self.db.mint_maker().sum_mint_maker_committed_capital(wallet_address).await.unwrap_or(0.0))
                 ).unwrap_or(Decimal::ZERO);
                 let available = on_chain_available - committed;
                 if committed > Decimal::ZERO {
@@ -844,7 +816,7 @@ impl MintMakerRunner {
                 // are done with the order book and shouldn't block new placements.
                 let mut total_open = self
                     .db
-                    .count_mint_maker_total_unfilled_pairs(wallet_address)
+                    .mint_maker().count_mint_maker_total_unfilled_pairs(wallet_address)
                     .await
                     .unwrap_or(0);
 
@@ -910,7 +882,7 @@ impl MintMakerRunner {
                     if settings.stop_after_profit {
                         let has_merge = self
                             .db
-                            .has_mint_maker_merged_pair_for_market(wallet_address, &m.market_id)
+                            .mint_maker().has_mint_maker_merged_pair_for_market(wallet_address, &m.market_id)
                             .await
                             .unwrap_or(false);
                         if has_merge {
@@ -922,7 +894,7 @@ impl MintMakerRunner {
                     // and shouldn't block new pair placement.
                     let market_pairs = self
                         .db
-                        .count_mint_maker_unfilled_pairs_for_market(wallet_address, &m.market_id)
+                        .mint_maker().count_mint_maker_unfilled_pairs_for_market(wallet_address, &m.market_id)
                         .await
                         .unwrap_or(0);
                     // stop_after_profit: only 1 pair at a time (wait for outcome before next)
@@ -935,7 +907,7 @@ impl MintMakerRunner {
                     if !settings.stop_after_profit {
                         let total_attempts = self
                             .db
-                            .count_mint_maker_all_pairs_for_market(wallet_address, &m.market_id)
+                            .mint_maker().count_mint_maker_all_pairs_for_market(wallet_address, &m.market_id)
                             .await
                             .unwrap_or(0);
                         if total_attempts >= settings.auto_max_attempts as i64 {
@@ -1033,7 +1005,7 @@ impl MintMakerRunner {
 
                         // Fill rate feedback: query recent outcomes for this asset
                         let (total, filled) = self.db
-                            .get_mint_maker_asset_fill_rate(wallet_address, &market.asset.to_string(), 4)
+                            .mint_maker().get_mint_maker_asset_fill_rate(wallet_address, &market.asset.to_string(), 4)
                             .await
                             .unwrap_or((0, 0));
                         let fill_rate = if total >= 3 { filled as f64 / total as f64 } else { 0.5 };
@@ -1210,10 +1182,9 @@ impl MintMakerRunner {
                     // === INVENTORY-AWARE PAIRING ===
                     // Check existing token balances to account for leftover shares from previous pairs.
                     // This prevents orphaned shares from accumulating and ensures we can merge everything.
-                    let ctf = crate::services::CtfService::new();
-                    let existing_yes = ctf.get_token_balance(&private_key, &market.yes_token_id)
+                    let existing_yes = self.ctf.get_token_balance(&signer, &market.yes_token_id)
                         .await.unwrap_or(Decimal::ZERO);
-                    let existing_no = ctf.get_token_balance(&private_key, &market.no_token_id)
+                    let existing_no = self.ctf.get_token_balance(&signer, &market.no_token_id)
                         .await.unwrap_or(Decimal::ZERO);
 
                     // Calculate base shares from budget
@@ -1303,7 +1274,8 @@ impl MintMakerRunner {
                             };
 
                             let exp_order_id = match order_manager::place_gtc_bid(
-                                &private_key,
+                                &self.clob_pool,
+                                &signer,
                                 exp_token,
                                 exp_price,
                                 exp_shares_val,
@@ -1335,7 +1307,8 @@ impl MintMakerRunner {
                             };
 
                             let cheap_order_id = match order_manager::place_gtc_bid(
-                                &private_key,
+                                &self.clob_pool,
+                                &signer,
                                 cheap_token,
                                 cheap_price,
                                 cheap_shares_val,
@@ -1369,7 +1342,8 @@ impl MintMakerRunner {
                             };
 
                             let exp_order_id = match order_manager::place_gtc_bid(
-                                &private_key,
+                                &self.clob_pool,
+                                &signer,
                                 exp_token,
                                 exp_price_val,
                                 exp_shares_val,
@@ -1413,7 +1387,7 @@ impl MintMakerRunner {
 
                     let pair_id = self
                         .db
-                        .create_mint_maker_pair(
+                        .mint_maker().create_mint_maker_pair(
                             wallet_address,
                             &market.market_id,
                             &market.condition_id,
@@ -1443,7 +1417,7 @@ impl MintMakerRunner {
                         (no_price.to_f64().unwrap_or(0.0), yes_price.to_f64().unwrap_or(0.0))
                     };
                     if pair_id > 0 {
-                        let _ = self.db.create_mint_maker_analytics(
+                        let _ = self.db.mint_maker().create_mint_maker_analytics(
                             wallet_address,
                             pair_id,
                             &market.market_id,
@@ -1471,7 +1445,7 @@ impl MintMakerRunner {
 
                     let _ = self
                         .db
-                        .log_mint_maker_action(
+                        .mint_maker().log_mint_maker_action(
                             wallet_address,
                             action_type,
                             Some(&market.market_id),
@@ -1601,8 +1575,8 @@ impl MintMakerRunner {
         // Cancel the unfilled order — use SDK cancel (correct endpoint)
         let unfilled_order_id = if yes_filled { &pair.no_order_id } else { &pair.yes_order_id };
         if !unfilled_order_id.is_empty() {
-            if let Some(ref pk) = self.key_store.get_key(wallet_address).await {
-                match order_manager::cancel_order_with_key(pk, unfilled_order_id).await {
+            if let Some(signer) = self.key_store.signer(wallet_address).await {
+                match order_manager::cancel_order_with_key(&self.clob_pool, &signer, unfilled_order_id).await {
                     Ok(()) => info!("MintMaker: Cancelled unfilled {} order for pair {}", if yes_filled { "NO" } else { "YES" }, pair.id),
                     Err(e) => {
                         warn!("MintMaker: SDK cancel failed for pair {}: {}. Trying raw.", pair.id, e);
@@ -1630,16 +1604,16 @@ impl MintMakerRunner {
             Some(s) if s > Decimal::ZERO => s,
             _ => {
                 warn!("MintMaker: No share count for {} side of pair {}", filled_side, pair.id);
-                let _ = self.db.update_mint_maker_pair_status(pair.id, "StopLoss").await;
+                let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "StopLoss").await;
                 return;
             }
         };
 
-        let private_key = match self.key_store.get_key(wallet_address).await {
-            Some(pk) => pk,
+        let signer = match self.key_store.signer(wallet_address).await {
+            Some(signer) => signer,
             None => {
-                warn!("MintMaker: No private key for stop loss on pair {}", pair.id);
-                let _ = self.db.update_mint_maker_pair_status(pair.id, "StopLoss").await;
+                warn!("MintMaker: No unlocked key for stop loss on pair {}", pair.id);
+                let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "StopLoss").await;
                 return;
             }
         };
@@ -1648,16 +1622,16 @@ impl MintMakerRunner {
         let sell_price = (current_price - Decimal::from_str("0.05").unwrap())
             .max(Decimal::from_str("0.01").unwrap());
 
-        match order_manager::place_gtc_sell(&private_key, token_id, sell_price, shares).await {
+        match order_manager::place_gtc_sell(&self.clob_pool, &signer, token_id, sell_price, shares).await {
             Ok(sell_order_id) => {
                 info!(
                     "MintMaker: Stop loss sell placed for pair {} — {} {}@{} order={}",
                     pair.id, filled_side, shares, sell_price, sell_order_id
                 );
                 // Store the sell order ID so we can track its fill status
-                let _ = self.db.set_mint_maker_stop_loss_order(pair.id, &sell_order_id, "StopLoss").await;
+                let _ = self.db.mint_maker().set_mint_maker_stop_loss_order(pair.id, &sell_order_id, "StopLoss").await;
                 let loss = (fill_price - sell_price) * shares;
-                let _ = self.db.log_mint_maker_action(
+                let _ = self.db.mint_maker().log_mint_maker_action(
                     wallet_address,
                     "stop_loss",
                     Some(&pair.market_id),
@@ -1676,7 +1650,7 @@ impl MintMakerRunner {
             }
             Err(e) => {
                 warn!("MintMaker: Stop loss sell FAILED for pair {}: {}. Marking for auto-redeem.", pair.id, e);
-                let _ = self.db.update_mint_maker_pair_status(pair.id, "StopLoss").await;
+                let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "StopLoss").await;
             }
         }
     }
@@ -1708,8 +1682,8 @@ impl MintMakerRunner {
                             "MintMaker: Stop loss SELL FILLED for pair {} — order={} price={} matched={}",
                             pair.id, &sell_order_id[..16.min(sell_order_id.len())], sell_price, r.size_matched
                         );
-                        let _ = self.db.update_mint_maker_pair_status(pair.id, "StopLossFilled").await;
-                        let _ = self.db.log_mint_maker_action(
+                        let _ = self.db.mint_maker().record_mint_maker_stop_loss_fill(pair.id, &r.size_matched, sell_price).await;
+                        let _ = self.db.mint_maker().log_mint_maker_action(
                             wallet_address,
                             "stop_loss_filled",
                             Some(&pair.market_id),
@@ -1729,7 +1703,7 @@ impl MintMakerRunner {
                             "MintMaker: Stop loss sell CANCELLED for pair {} — leaving for auto-redeem",
                             pair.id
                         );
-                        let _ = self.db.log_mint_maker_action(
+                        let _ = self.db.mint_maker().log_mint_maker_action(
                             wallet_address,
                             "stop_loss_sell_cancelled",
                             Some(&pair.market_id),
@@ -1818,12 +1792,12 @@ impl MintMakerRunner {
                     "MintMaker: Pair {} fill size mismatch — ordered {} but matched YES={} NO={}. Capping merge to {}",
                     pair.id, pair.size, yes_matched, no_matched, new_size
                 );
-                let _ = self.db.update_mint_maker_pair_size(pair.id, &new_size).await;
+                let _ = self.db.mint_maker().update_mint_maker_pair_size(pair.id, &new_size).await;
             }
 
             let _ = self
                 .db
-                .update_mint_maker_pair_fill(
+                .mint_maker().update_mint_maker_pair_fill(
                     pair.id,
                     yes_price.as_deref(),
                     no_price.as_deref(),
@@ -1832,7 +1806,7 @@ impl MintMakerRunner {
                 .await;
             let _ = self
                 .db
-                .log_mint_maker_action(
+                .mint_maker().log_mint_maker_action(
                     wallet_address,
                     "matched",
                     Some(&pair.market_id),
@@ -1860,7 +1834,7 @@ impl MintMakerRunner {
             // Record the fill price for the filled side
             let _ = self
                 .db
-                .update_mint_maker_pair_fill(
+                .mint_maker().update_mint_maker_pair_fill(
                     pair.id,
                     if yes_filled { yes_price.as_deref() } else { None },
                     if no_filled { no_price.as_deref() } else { None },
@@ -1870,7 +1844,7 @@ impl MintMakerRunner {
 
             let _ = self
                 .db
-                .log_mint_maker_action(
+                .mint_maker().log_mint_maker_action(
                     wallet_address,
                     "orphaned",
                     Some(&pair.market_id),
@@ -1886,7 +1860,7 @@ impl MintMakerRunner {
                 .await;
 
             // Update analytics: pair became an orphan
-            let _ = self.db.update_analytics_orphaned(pair.id, which_filled).await;
+            let _ = self.db.mint_maker().update_analytics_orphaned(pair.id, which_filled).await;
         } else if yes_filled || no_filled {
             // One side filled, other still open — mark as HalfFilled.
             // Only update if not already HalfFilled to avoid resetting updated_at
@@ -1894,7 +1868,7 @@ impl MintMakerRunner {
             if pair.status != "HalfFilled" {
                 let _ = self
                     .db
-                    .update_mint_maker_pair_fill(
+                    .mint_maker().update_mint_maker_pair_fill(
                         pair.id,
                         if yes_filled {
                             yes_price.as_deref()
@@ -1918,11 +1892,11 @@ impl MintMakerRunner {
             // Both sides cancelled (e.g. by CLOB) — mark pair as cancelled
             let _ = self
                 .db
-                .update_mint_maker_pair_status(pair.id, "Cancelled")
+                .mint_maker().update_mint_maker_pair_status(pair.id, "Cancelled")
                 .await;
             let _ = self
                 .db
-                .log_mint_maker_action(
+                .mint_maker().log_mint_maker_action(
                     wallet_address,
                     "both_cancelled",
                     Some(&pair.market_id),
@@ -1948,7 +1922,7 @@ impl MintMakerRunner {
         wallet_address: &str,
     ) -> anyhow::Result<MintMakerStatusUpdate> {
         let settings = if !wallet_address.is_empty() {
-            Some(self.db.get_mint_maker_settings(wallet_address).await?)
+            Some(self.db.mint_maker().get_mint_maker_settings(wallet_address).await?)
         } else {
             None
         };
@@ -1961,7 +1935,7 @@ impl MintMakerRunner {
         for market in eligible_markets {
             let open_pairs = if !wallet_address.is_empty() {
                 self.db
-                    .count_mint_maker_open_pairs_for_market(wallet_address, &market.market_id)
+                    .mint_maker().count_mint_maker_open_pairs_for_market(wallet_address, &market.market_id)
                     .await
                     .unwrap_or(0)
             } else {
@@ -2000,7 +1974,7 @@ impl MintMakerRunner {
         let stats = if !wallet_address.is_empty() {
             let (total, merged, cancelled, profit, cost, avg_spread) = self
                 .db
-                .get_mint_maker_stats(wallet_address)
+                .mint_maker().get_mint_maker_stats(wallet_address)
                 .await
                 .unwrap_or((0, 0, 0, 0.0, 0.0, 0.0));
             let fill_rate = if total > 0 {
@@ -2024,7 +1998,7 @@ impl MintMakerRunner {
         // Open pairs
         let open_pairs = if !wallet_address.is_empty() {
             self.db
-                .get_mint_maker_open_pairs(wallet_address)
+                .mint_maker().get_mint_maker_open_pairs(wallet_address)
                 .await
                 .unwrap_or_default()
         } else {
@@ -2034,7 +2008,7 @@ impl MintMakerRunner {
         // Recent log
         let recent_log = if !wallet_address.is_empty() {
             self.db
-                .get_mint_maker_log(wallet_address, 20)
+                .mint_maker().get_mint_maker_log(wallet_address, 20)
                 .await
                 .unwrap_or_default()
         } else {
@@ -2051,60 +2025,145 @@ impl MintMakerRunner {
         })
     }
 
-    /// Check for resolved markets and redeem winning tokens via CTF relay.
-    /// Rate-limited: at most 1 relay redeem per cycle; respects global relay backoff.
-    async fn check_auto_redeem(
-        &self,
-        wallet_address: &str,
-    ) -> anyhow::Result<()> {
-        // Respect relay backoff (shared with merge)
-        if self.is_relay_backed_off(wallet_address).await {
+    /// Merge matched pairs and redeem resolved markets for a wallet in one relay call.
+    ///
+    /// Every pair ready to merge (past its cooldown) and every resolved condition
+    /// still holding tokens is bundled into a single MultiSend Safe transaction,
+    /// so a busy wallet costs one relay submission per cycle instead of one per
+    /// pair. The batch is atomic: once its job settles (`settle_relay_batches`)
+    /// the transaction is attributed to every pair it covered. A failed batch is
+    /// bisected and resubmitted, so only the call that reverts ends up failed
+    /// (and charged a merge attempt) on its own.
+    async fn run_relay_batch(&self, wallet_address: &str) -> anyhow::Result<()> {
+        let matched_pairs = self
+            .db
+            .mint_maker().get_mint_maker_pairs_by_status(wallet_address, "Matched")
+            .await?;
+        let redeemable_pairs = self.db.mint_maker().get_mint_maker_redeemable_pairs(wallet_address).await?;
+        if matched_pairs.is_empty() && redeemable_pairs.is_empty() {
             return Ok(());
         }
 
-        let pairs = self.db.get_mint_maker_redeemable_pairs(wallet_address).await?;
-        if pairs.is_empty() {
+        let Some(signer) = self.key_store.signer(wallet_address).await else {
+            if !matched_pairs.is_empty() {
+                warn!(
+                    "MintMaker: {} matched pairs waiting to merge but no key unlocked for {}. \
+                     Re-click Enable in the UI.",
+                    matched_pairs.len(), &wallet_address[..8]
+                );
+            }
             return Ok(());
-        }
+        };
 
-        let private_key = match self.key_store.get_key(wallet_address).await {
-            Some(pk) => pk,
+        let creds = match builder_credentials() {
+            Some(creds) => creds,
             None => {
-                debug!("MintMaker auto-redeem: no private key for {}, skipping", &wallet_address[..8]);
+                if !matched_pairs.is_empty() {
+                    warn!(
+                        "MintMaker: {} matched pairs waiting to merge but POLY_BUILDER_* env vars not set! \
+                         Set POLY_BUILDER_API_KEY, POLY_BUILDER_SECRET, POLY_BUILDER_PASSPHRASE.",
+                        matched_pairs.len()
+                    );
+                }
                 return Ok(());
             }
         };
 
-        let (bk, bs, bp) = match (
-            std::env::var("POLY_BUILDER_API_KEY").ok(),
-            std::env::var("POLY_BUILDER_SECRET").ok(),
-            std::env::var("POLY_BUILDER_PASSPHRASE").ok(),
-        ) {
-            (Some(bk), Some(bs), Some(bp)) => (bk, bs, bp),
-            _ => {
-                debug!("MintMaker auto-redeem: POLY_BUILDER_* env vars not set, skipping");
-                return Ok(());
+        let safe_address = crate::services::ctf::safe_address_for(&signer)?;
+        let mut calls: Vec<CtfCall> = Vec::new();
+        let mut items: Vec<RelayBatchItem> = Vec::new();
+
+        // Conditions already in a submitted batch stay out until it settles
+        // (merging pairs are kept out by their Merging status)
+        let in_flight: HashSet<String> = self
+            .relay_batches
+            .lock()
+            .await
+            .values()
+            .filter(|b| b.wallet_address == wallet_address)
+            .flat_map(|b| b.items.iter())
+            .filter_map(|item| match item {
+                RelayBatchItem::Redeem(condition_id) => Some(condition_id.clone()),
+                RelayBatchItem::Merge(_) => None,
+            })
+            .collect();
+
+        // Merges (with cooldown + retry limit)
+        const MERGE_COOLDOWN_SECS: u64 = 30;
+        const MERGE_MAX_ATTEMPTS: u32 = 10; // 10 * 30s = 5 min
+        for pair in &matched_pairs {
+            if calls.len() >= RELAY_BATCH_CAP {
+                debug!("MintMaker: Hit relay batch cap ({}), remaining pairs will retry next cycle", RELAY_BATCH_CAP);
+                break;
+            }
+
+            let mut tracker = self.merge_tracker.lock().await;
+            let (attempts, last_attempt) = tracker
+                .entry(pair.id)
+                .or_insert((0, Instant::now() - std::time::Duration::from_secs(MERGE_COOLDOWN_SECS)));
+
+            if *attempts >= MERGE_MAX_ATTEMPTS {
+                // Too many failed attempts — give up
+                warn!(
+                    "MintMaker: Pair {} failed to merge after {} attempts — marking MergeFailed",
+                    pair.id, attempts
+                );
+                let _ = self.db.mint_maker().update_mint_maker_pair_status(pair.id, "MergeFailed").await;
+                tracker.remove(&pair.id);
+                continue;
+            }
+
+            if last_attempt.elapsed() < std::time::Duration::from_secs(MERGE_COOLDOWN_SECS) {
+                debug!(
+                    "MintMaker: Pair {} merge cooldown ({}/{} attempts, {}s since last)",
+                    pair.id, attempts, MERGE_MAX_ATTEMPTS, last_attempt.elapsed().as_secs()
+                );
+                continue;
             }
-        };
 
-        // Deduplicate by condition_id — multiple pairs can share the same market
-        let mut checked_conditions: HashSet<String> = HashSet::new();
-        const REDEEM_PER_CYCLE_CAP: u32 = 3;
-        let mut redeems_this_cycle: u32 = 0;
+            *attempts += 1;
+            *last_attempt = Instant::now();
+            let attempt_num = *attempts;
+            drop(tracker);
+
+            match inventory::prepare_merge(&self.db, &self.ctf, pair, safe_address).await {
+                Ok(Some(call)) => {
+                    calls.push(call);
+                    items.push(RelayBatchItem::Merge(pair.clone()));
+                }
+                Ok(None) => {
+                    self.merge_tracker.lock().await.remove(&pair.id);
+                }
+                Err(e) => {
+                    info!(
+                        "MintMaker: Pair {} merge attempt {}/{} not ready: {}",
+                        pair.id, attempt_num, MERGE_MAX_ATTEMPTS, e
+                    );
+                }
+            }
+        }
 
-        for pair in &pairs {
-            if redeems_this_cycle >= REDEEM_PER_CYCLE_CAP {
-                debug!("MintMaker auto-redeem: hit cap ({}/cycle), remaining will retry next cycle", REDEEM_PER_CYCLE_CAP);
+        // Redeems — deduplicate by condition_id, multiple pairs can share the same market.
+        // Conditions being merged in this batch are left for the next cycle.
+        let mut checked_conditions: HashSet<String> = items
+            .iter()
+            .filter_map(|item| match item {
+                RelayBatchItem::Merge(pair) => Some(pair.condition_id.clone()),
+                RelayBatchItem::Redeem(_) => None,
+            })
+            .chain(in_flight)
+            .collect();
+        for pair in &redeemable_pairs {
+            if calls.len() >= RELAY_BATCH_CAP {
+                debug!("MintMaker auto-redeem: hit relay batch cap ({}), remaining will retry next cycle", RELAY_BATCH_CAP);
                 break;
             }
-            if checked_conditions.contains(&pair.condition_id) {
+            if !checked_conditions.insert(pair.condition_id.clone()) {
                 continue;
             }
-            checked_conditions.insert(pair.condition_id.clone());
 
             // Check if market is resolved via Gamma API
-            let resolved = is_market_resolved(&self.client, &pair.condition_id).await;
-            if !resolved {
+            if !is_market_resolved(&self.client, &pair.condition_id).await {
                 continue;
             }
 
@@ -2112,138 +2171,276 @@ impl MintMakerRunner {
                 "MintMaker auto-redeem: market {} resolved, redeeming for {}",
                 &pair.condition_id[..10], &wallet_address[..8]
             );
+            // both YES and NO index sets
+            match CtfService::redeem_call(&pair.condition_id, &[1, 2], pair.neg_risk) {
+                Ok(call) => {
+                    calls.push(call);
+                    items.push(RelayBatchItem::Redeem(pair.condition_id.clone()));
+                }
+                Err(e) => warn!(
+                    "MintMaker auto-redeem: bad condition {}: {}",
+                    &pair.condition_id[..10.min(pair.condition_id.len())], e
+                ),
+            }
+        }
 
-            redeems_this_cycle += 1;
-            let ctf = crate::services::CtfService::new();
-            match ctf.redeem(
-                &pair.condition_id,
-                &[1, 2], // both YES and NO index sets
-                &private_key,
-                &bk,
-                &bs,
-                &bp,
-                pair.neg_risk,
-            ).await {
-                Ok(resp) if resp.success => {
-                    let tx_id = resp.transaction_id.unwrap_or_else(|| "unknown".to_string());
-                    info!(
-                        "MintMaker auto-redeem: condition {} redeemed, tx: {}",
-                        &pair.condition_id[..10], tx_id
-                    );
+        if calls.is_empty() {
+            return Ok(());
+        }
 
-                    // Determine winning outcome for PnL calculation on half-filled pairs
-                    let winning_outcome = get_winning_outcome(&self.client, &pair.condition_id).await;
-
-                    // Update ALL pairs for this condition_id to Redeemed with PnL
-                    for p in pairs.iter().filter(|p| p.condition_id == pair.condition_id) {
-                        let yes_filled = p.yes_fill_price.is_some();
-                        let no_filled = p.no_fill_price.is_some();
-                        let size: f64 = p.size.parse().unwrap_or(0.0);
-
-                        let (pair_cost_str, profit_str) = if yes_filled && no_filled {
-                            // Both sides filled — profit already calculated by merge logic
-                            (p.pair_cost.clone(), p.profit.clone())
-                        } else if let Some(winner) = winning_outcome {
-                            // Half-filled: calculate PnL based on which side won
-                            if yes_filled {
-                                let fill: f64 = p.yes_fill_price.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0);
-                                let cost = fill * size;
-                                if winner == 0 {
-                                    // YES won — we get $1 per share
-                                    let pnl = (1.0 - fill) * size;
-                                    info!("MintMaker redeem: pair {} YES filled@{} WON → +${:.2}", p.id, fill, pnl);
-                                    (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
-                                } else {
-                                    // NO won — YES shares worth $0
-                                    let pnl = -cost;
-                                    info!("MintMaker redeem: pair {} YES filled@{} LOST → -${:.2}", p.id, fill, cost);
-                                    (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
-                                }
-                            } else if no_filled {
-                                let fill: f64 = p.no_fill_price.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0);
-                                let cost = fill * size;
-                                if winner == 1 {
-                                    // NO won — we get $1 per share
-                                    let pnl = (1.0 - fill) * size;
-                                    info!("MintMaker redeem: pair {} NO filled@{} WON → +${:.2}", p.id, fill, pnl);
-                                    (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
-                                } else {
-                                    // YES won — NO shares worth $0
-                                    let pnl = -cost;
-                                    info!("MintMaker redeem: pair {} NO filled@{} LOST → -${:.2}", p.id, fill, cost);
-                                    (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
-                                }
-                            } else {
-                                // Neither side filled — no cost, no PnL
-                                (None, Some("0".to_string()))
-                            }
-                        } else {
-                            // Couldn't determine winner — leave PnL blank
-                            warn!("MintMaker redeem: pair {} — couldn't determine winning outcome", p.id);
-                            (p.pair_cost.clone(), p.profit.clone())
-                        };
+        info!(
+            "MintMaker: Submitting relay batch for {} ({} calls)",
+            &wallet_address[..8], calls.len()
+        );
+        let batch = RelayBatch { wallet_address: wallet_address.to_string(), items, calls };
+        self.submit_relay_batch(&signer, &creds, batch).await;
 
-                        let _ = self.db.update_mint_maker_pair_redeem(
-                            p.id, "Redeemed",
-                            pair_cost_str.as_deref(),
-                            profit_str.as_deref(),
-                        ).await;
-                        let profit_display = profit_str.as_deref().unwrap_or("?");
-                        let _ = self.db.log_mint_maker_action(
-                            wallet_address,
-                            "auto_redeem",
-                            Some(&p.market_id),
-                            Some(&p.question),
-                            Some(&p.asset),
-                            None,
-                            None,
-                            pair_cost_str.as_deref(),
-                            profit_str.as_deref(),
-                            Some(&p.size),
-                            Some(&format!("tx: {} pnl: {}", tx_id, profit_display)),
-                        ).await;
-                    }
+        Ok(())
+    }
+
+    /// Submit a batch and track its job; a batch the relay won't take fails right away
+    async fn submit_relay_batch(&self, signer: &WalletSigner, creds: &BuilderCredentials, batch: RelayBatch) {
+        let wallet_address = batch.wallet_address.clone();
+        match self
+            .ctf
+            .submit_calls(&batch.calls, signer, &creds.api_key, &creds.secret, &creds.passphrase)
+            .await
+        {
+            Ok(job_id) => {
+                debug!("MintMaker: Relay batch for {} is job #{}", &wallet_address[..8], job_id);
+                self.relay_batches.lock().await.insert(job_id, batch);
+            }
+            Err(e) => {
+                let err = format!("{:#}", e);
+                if err.contains("Relay error 429") || err.contains("rate limit") {
+                    let backoff_secs = Self::parse_relay_backoff_seconds(&err);
+                    self.set_relay_backoff(&wallet_address, backoff_secs).await;
                 }
-                Ok(resp) => {
-                    let err = resp.error.unwrap_or_else(|| "unknown error".to_string());
-                    // Check for 429 rate limit and set backoff
-                    if err.contains("Relay error 429") || err.contains("rate limit") {
-                        let backoff_secs = Self::parse_relay_backoff_seconds(&err);
-                        self.set_relay_backoff(wallet_address, backoff_secs).await;
-                        warn!(
-                            "MintMaker auto-redeem 429 for condition {}: {}",
-                            &pair.condition_id[..10], err
-                        );
-                        break;
+                warn!("MintMaker relay batch failed for {}: {}", &wallet_address[..8], err);
+                self.finish_relay_batch(batch, Err(err)).await;
+            }
+        }
+    }
+
+    /// Resubmit the two halves of a failed batch as separate transactions.
+    /// Falls back to failing the whole batch when it can't be resubmitted now.
+    async fn bisect_relay_batch(&self, mut batch: RelayBatch, err: String) {
+        let signer = self.key_store.signer(&batch.wallet_address).await;
+        let (Some(signer), Some(creds)) = (signer, builder_credentials()) else {
+            self.finish_relay_batch(batch, Err(err)).await;
+            return;
+        };
+        let Some(tail) = batch.bisect() else {
+            self.finish_relay_batch(batch, Err(err)).await;
+            return;
+        };
+
+        info!(
+            "MintMaker: Relay batch for {} failed ({}), resubmitting as {} + {} calls",
+            &batch.wallet_address[..8], err, batch.items.len(), tail.items.len()
+        );
+        self.submit_relay_batch(&signer, &creds, batch).await;
+        self.submit_relay_batch(&signer, &creds, tail).await;
+    }
+
+    /// Settle every submitted relay batch whose job has reached a final state
+    async fn settle_relay_batches(&self) {
+        let job_ids: Vec<i64> = self.relay_batches.lock().await.keys().copied().collect();
+        for job_id in job_ids {
+            let job = match self.onchain_jobs.outcome(job_id).await {
+                Ok(Some(job)) => job,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!("MintMaker: Relay job #{} lookup failed: {}", job_id, e);
+                    continue;
+                }
+            };
+            let Some(batch) = self.relay_batches.lock().await.remove(&job_id) else {
+                continue;
+            };
+
+            if job.status == OnchainJobStatus::Mined {
+                let tx_id = job.tx_hash.unwrap_or_else(|| format!("job #{}", job.id));
+                self.finish_relay_batch(batch, Ok(tx_id)).await;
+                continue;
+            }
+
+            let err = job.error.unwrap_or_else(|| "unknown error".to_string());
+            if batch.items.len() > 1 {
+                // One reverting call fails the whole MultiSend; the shared failure
+                // says nothing about which call it was
+                self.bisect_relay_batch(batch, err).await;
+            } else {
+                warn!("MintMaker relay batch failed for {}: {}", &batch.wallet_address[..8], err);
+                self.finish_relay_batch(batch, Err(err)).await;
+            }
+        }
+    }
+
+    /// Attribute a batch result back to every pair it covered
+    async fn finish_relay_batch(&self, batch: RelayBatch, outcome: std::result::Result<String, String>) {
+        let wallet_address = batch.wallet_address.as_str();
+        let redeemable_pairs = if batch.items.iter().any(|i| matches!(i, RelayBatchItem::Redeem(_))) && outcome.is_ok() {
+            self.db
+                .mint_maker()
+                .get_mint_maker_redeemable_pairs(wallet_address)
+                .await
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        for item in &batch.items {
+            match item {
+                RelayBatchItem::Merge(pair) => {
+                    let result = outcome.as_deref().map_err(|e| e.as_str());
+                    if let Err(e) = inventory::finish_merge(&self.db, pair.id, result).await {
+                        warn!("MintMaker: Failed to record merge for pair {}: {}", pair.id, e);
+                    }
+                    if let Ok(tx_id) = &outcome {
+                        self.on_pair_merged(wallet_address, pair, tx_id).await;
                     }
-                    warn!(
-                        "MintMaker auto-redeem failed for condition {}: {}",
-                        &pair.condition_id[..10], err
-                    );
                 }
-                Err(e) => {
-                    let err_str = e.to_string();
-                    if err_str.contains("Relay error 429") || err_str.contains("rate limit") {
-                        let backoff_secs = Self::parse_relay_backoff_seconds(&err_str);
-                        self.set_relay_backoff(wallet_address, backoff_secs).await;
-                        warn!(
-                            "MintMaker auto-redeem 429 for condition {}: {}",
-                            &pair.condition_id[..10], err_str
+                RelayBatchItem::Redeem(condition_id) => match &outcome {
+                    Ok(tx_id) => {
+                        info!(
+                            "MintMaker auto-redeem: condition {} redeemed, tx: {}",
+                            &condition_id[..10], tx_id
                         );
-                        break;
+                        self.on_condition_redeemed(wallet_address, &redeemable_pairs, condition_id, tx_id)
+                            .await;
                     }
-                    warn!(
-                        "MintMaker auto-redeem error for condition {}: {}",
-                        &pair.condition_id[..10], e
-                    );
-                }
+                    Err(err) => warn!(
+                        "MintMaker auto-redeem failed for condition {}: {}",
+                        &condition_id[..10], err
+                    ),
+                },
             }
         }
+    }
 
-        Ok(())
+    /// Book a successfully merged pair: analytics + action log
+    async fn on_pair_merged(&self, wallet_address: &str, pair: &MintMakerPairRow, tx_id: &str) {
+        // Success — remove from tracker
+        self.merge_tracker.lock().await.remove(&pair.id);
+
+        // Update analytics: pair merged successfully
+        let merge_pnl = pair.profit.as_ref()
+            .and_then(|p| p.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        let _ = self.db.mint_maker().update_analytics_merged(pair.id, merge_pnl).await;
+
+        let _ = self
+            .db
+            .mint_maker().log_mint_maker_action(
+                wallet_address,
+                "merge",
+                Some(&pair.market_id),
+                Some(&pair.question),
+                Some(&pair.asset),
+                None,
+                None,
+                pair.pair_cost.as_deref(),
+                pair.profit.as_deref(),
+                Some(&pair.size),
+                Some(&format!("tx: {}", tx_id)),
+            )
+            .await;
+    }
+
+    /// Mark every pair on a redeemed condition Redeemed, with PnL for half-filled pairs
+    async fn on_condition_redeemed(
+        &self,
+        wallet_address: &str,
+        pairs: &[MintMakerPairRow],
+        condition_id: &str,
+        tx_id: &str,
+    ) {
+        // Determine winning outcome for PnL calculation on half-filled pairs
+        let winning_outcome = get_winning_outcome(&self.client, condition_id).await;
+
+        // Update ALL pairs for this condition_id to Redeemed with PnL
+        for p in pairs.iter().filter(|p| p.condition_id == condition_id) {
+            let yes_filled = p.yes_fill_price.is_some();
+            let no_filled = p.no_fill_price.is_some();
+            let size: f64 = p.size.parse().unwrap_or(0.0);
+
+            let (pair_cost_str, profit_str) = if yes_filled && no_filled {
+                // Both sides filled — profit already calculated by merge logic
+                (p.pair_cost.clone(), p.profit.clone())
+            } else if let Some(winner) = winning_outcome {
+                // Half-filled: calculate PnL based on which side won
+                if yes_filled {
+                    let fill: f64 = p.yes_fill_price.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0);
+                    let cost = fill * size;
+                    if winner == 0 {
+                        // YES won — we get $1 per share
+                        let pnl = (1.0 - fill) * size;
+                        info!("MintMaker redeem: pair {} YES filled@{} WON → +${:.2}", p.id, fill, pnl);
+                        (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
+                    } else {
+                        // NO won — YES shares worth $0
+                        let pnl = -cost;
+                        info!("MintMaker redeem: pair {} YES filled@{} LOST → -${:.2}", p.id, fill, cost);
+                        (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
+                    }
+                } else if no_filled {
+                    let fill: f64 = p.no_fill_price.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0);
+                    let cost = fill * size;
+                    if winner == 1 {
+                        // NO won — we get $1 per share
+                        let pnl = (1.0 - fill) * size;
+                        info!("MintMaker redeem: pair {} NO filled@{} WON → +${:.2}", p.id, fill, pnl);
+                        (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
+                    } else {
+                        // YES won — NO shares worth $0
+                        let pnl = -cost;
+                        info!("MintMaker redeem: pair {} NO filled@{} LOST → -${:.2}", p.id, fill, cost);
+                        (Some(format!("{:.6}", cost)), Some(format!("{:.6}", pnl)))
+                    }
+                } else {
+                    // Neither side filled — no cost, no PnL
+                    (None, Some("0".to_string()))
+                }
+            } else {
+                // Couldn't determine winner — leave PnL blank
+                warn!("MintMaker redeem: pair {} — couldn't determine winning outcome", p.id);
+                (p.pair_cost.clone(), p.profit.clone())
+            };
+
+            let _ = self.db.mint_maker().update_mint_maker_pair_redeem(
+                p.id, "Redeemed",
+                pair_cost_str.as_deref(),
+                profit_str.as_deref(),
+                Some(tx_id),
+            ).await;
+            let profit_display = profit_str.as_deref().unwrap_or("?");
+            let _ = self.db.mint_maker().log_mint_maker_action(
+                wallet_address,
+                "auto_redeem",
+                Some(&p.market_id),
+                Some(&p.question),
+                Some(&p.asset),
+                None,
+                None,
+                pair_cost_str.as_deref(),
+                profit_str.as_deref(),
+                Some(&p.size),
+                Some(&format!("tx: {} pnl: {}", tx_id, profit_display)),
+            ).await;
+        }
     }
 }
 
+/// Relay builder credentials from the POLY_BUILDER_* env vars
+fn builder_credentials() -> Option<BuilderCredentials> {
+    Some(BuilderCredentials {
+        api_key: std::env::var("POLY_BUILDER_API_KEY").ok()?,
+        secret: std::env::var("POLY_BUILDER_SECRET").ok()?,
+        passphrase: std::env::var("POLY_BUILDER_PASSPHRASE").ok()?,
+    })
+}
+
 /// Query which outcome index won for a resolved condition.
 ///
 /// Calls `payoutNumerators(bytes32,uint256)` on the CTF contract for index 0 (YES).
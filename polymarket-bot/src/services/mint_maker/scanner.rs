@@ -4,6 +4,7 @@
 //! crypto markets in a single API call. No slug guessing needed.
 
 use crate::config::GammaApi;
+use crate::taxonomy::{Category, MarketTaxonomy};
 use crate::types::TrackedMarket;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -126,13 +127,18 @@ pub async fn fetch_15m_crypto_markets(
                 no_price,
                 volume,
                 liquidity,
-                category: Some("Crypto".to_string()),
+                category: Some(Category::Crypto.label().to_string()),
                 active: true,
                 closed: false,
                 yes_token_id: Some(token_ids[0].clone()),
                 no_token_id: Some(token_ids[1].clone()),
                 hours_until_close,
                 neg_risk: market.neg_risk,
+                event_id: None,
+                neg_risk_market_id: None,
+                group_item_title: None,
+                question_id: None,
+                taxonomy: MarketTaxonomy::of(Category::Crypto, None),
             });
         }
     }
@@ -1,36 +1,57 @@
 //! Background services for the trading bot
 
+pub mod auto_redeemer;
 pub mod auto_trader;
 pub mod clob_errors;
+pub mod clob_pool;
 pub mod ctf;
 pub mod dispute_tracker;
+pub mod dvm_tracker;
+pub mod leader;
 pub mod mc_scanner;
 pub mod mint_maker;
+pub mod notifications;
+pub mod onchain_jobs;
+pub mod polygon_tx;
+pub mod price_cache;
 pub mod price_ws;
 pub mod rate_limiter;
+pub mod reports;
 pub mod resolution_tracker;
 pub mod metrics;
 pub mod retry;
 pub mod safe_activation;
 pub mod safe_proxy;
+pub mod signer;
 pub mod tick_size;
+pub mod uma_events;
 pub mod user_ws;
 
 
 pub use auto_trader::{
     AutoBuyer, AutoSeller, AutoTradeLog, AutoTradingExecutor, AutoTradingSettings,
-    AutoTradingStats, DisputeSniper, ExitTrigger, KeyStore, PositionMonitor, SellSignal,
+    AutoTradingStats, DisputeSniper, ExitTrigger, KeyStore, KeyVault, NegRiskArbExecutor, PositionMonitor,
+    SellSignal,
 };
+pub use auto_redeemer::AutoRedeemer;
 pub use dispute_tracker::DisputeTracker;
+pub use leader::LeaderElection;
 pub use mc_scanner::{McScanner, McStatusUpdate, McScoutResult};
+pub use polygon_tx::{ConfirmOptions, PendingTx, PolygonTxService, TxReceipt};
+pub use price_cache::PriceCache;
 pub use price_ws::{PriceUpdate, PriceUpdateTx, PriceWebSocket};
 pub use clob_errors::ClobError;
+pub use clob_pool::{ClobAccount, ClobClientPool, PooledClient};
 pub use rate_limiter::{EndpointClass, RateLimiter};
+pub use reports::{Report, ReportPeriod, Reporter};
 pub use resolution_tracker::ResolutionTracker;
 pub use retry::{RetryConfig, with_retry};
 pub use safe_proxy::derive_safe_wallet;
+pub use signer::WalletSigner;
 pub use tick_size::TickSizeCache;
-pub use ctf::CtfService;
+pub use ctf::{CtfCall, CtfService};
 pub use metrics::Metrics;
 pub use mint_maker::{MintMakerRunner, MintMakerStatusUpdate};
+pub use notifications::{Notification, NotificationKind, NotificationRouter, Notifier};
+pub use onchain_jobs::OnchainJobTracker;
 pub use user_ws::{OrderEvent, UserWebSocket};
@@ -0,0 +1,293 @@
+//! Notifications - routes wallet events to user-configured channels
+//!
+//! Each wallet registers channels (Discord, Slack-compatible webhook, Telegram
+//! bot, generic JSON webhook or SMTP email) and subscribes each one to event
+//! kinds. Events come from:
+//! - the auto-trade and Mint Maker logs, tailed by id (every trading service
+//!   already records fills, exits, redeems and failures there)
+//! - dispute alerts on markets a wallet holds
+//! - daily and weekly report summaries from the reporter
+//! - `Notifier::notify` for events raised directly (wallet security events)
+//!
+//! Every channel has a per-minute rate limit; notifications over the limit are
+//! dropped and counted in the next message that goes through.
+
+pub mod router;
+pub mod sinks;
+pub mod template;
+
+pub use router::NotificationRouter;
+pub use sinks::{ChannelTarget, Message, NotificationSink, Sinks};
+
+use crate::db::MintMakerLogEntry;
+use crate::services::auto_trader::AutoTradeLog;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Event kinds a channel can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Auto-trading buys and profit/time exits
+    Fill,
+    /// Stop-loss and trailing-stop exits
+    StopLoss,
+    /// Disputes on held markets and dispute-driven exits
+    Dispute,
+    /// Mint Maker pairs left with one filled side
+    Orphan,
+    Redeem,
+    /// Failed sells, basket rollbacks
+    Error,
+    /// Daily report summary
+    DailyPnl,
+    WeeklyReport,
+    /// Key export, withdrawals, password and allowlist changes
+    Security,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 9] = [
+        NotificationKind::Fill,
+        NotificationKind::StopLoss,
+        NotificationKind::Dispute,
+        NotificationKind::Orphan,
+        NotificationKind::Redeem,
+        NotificationKind::Error,
+        NotificationKind::DailyPnl,
+        NotificationKind::WeeklyReport,
+        NotificationKind::Security,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Fill => "fill",
+            NotificationKind::StopLoss => "stop_loss",
+            NotificationKind::Dispute => "dispute",
+            NotificationKind::Orphan => "orphan",
+            NotificationKind::Redeem => "redeem",
+            NotificationKind::Error => "error",
+            NotificationKind::DailyPnl => "daily_pnl",
+            NotificationKind::WeeklyReport => "weekly_report",
+            NotificationKind::Security => "security",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        Self::ALL.into_iter().find(|k| k.as_str() == s)
+    }
+
+    /// Embed color for Discord
+    pub fn color(&self) -> u32 {
+        match self {
+            NotificationKind::Fill | NotificationKind::Redeem => 0x00FF00, // Green
+            NotificationKind::StopLoss | NotificationKind::Error => 0xFF0000, // Red
+            NotificationKind::Dispute | NotificationKind::Orphan | NotificationKind::Security => 0xFFA500, // Orange
+            NotificationKind::DailyPnl | NotificationKind::WeeklyReport => 0x3498DB, // Blue
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            NotificationKind::Fill => "✅",
+            NotificationKind::StopLoss => "🛑",
+            NotificationKind::Dispute => "⚖️",
+            NotificationKind::Orphan => "🧩",
+            NotificationKind::Redeem => "💰",
+            NotificationKind::Error => "⚠️",
+            NotificationKind::DailyPnl => "📊",
+            NotificationKind::WeeklyReport => "🗓️",
+            NotificationKind::Security => "🔐",
+        }
+    }
+
+    /// Kind of an auto-trade log action (None for actions nobody is notified about)
+    pub fn from_trade_action(action: &str) -> Option<Self> {
+        match action {
+            "auto_buy" | "dispute_snipe" | "neg_risk_arb_buy" | "take_profit" | "time_exit" => Some(NotificationKind::Fill),
+            "stop_loss" | "trailing_stop" => Some(NotificationKind::StopLoss),
+            "auto_redeem" => Some(NotificationKind::Redeem),
+            "sell_failed" | "neg_risk_arb_rollback" | "neg_risk_arb_rollback_failed" => Some(NotificationKind::Error),
+            a if a.starts_with("dispute_") => Some(NotificationKind::Dispute),
+            _ => None,
+        }
+    }
+
+    /// Kind of a Mint Maker log action
+    pub fn from_mint_maker_action(action: &str) -> Option<Self> {
+        match action {
+            "orphaned" => Some(NotificationKind::Orphan),
+            "stop_loss_filled" => Some(NotificationKind::StopLoss),
+            "auto_redeem" => Some(NotificationKind::Redeem),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a stored comma-separated kind list, ignoring unknown entries
+pub fn parse_kinds(s: &str) -> Vec<NotificationKind> {
+    s.split(',').filter_map(NotificationKind::parse).collect()
+}
+
+/// Serialize kinds for storage (deduplicated)
+pub fn format_kinds(kinds: &[NotificationKind]) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    for kind in kinds {
+        if !out.contains(&kind.as_str()) {
+            out.push(kind.as_str());
+        }
+    }
+    out.join(",")
+}
+
+/// A single event to deliver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    /// Wallet the event belongs to (its subscribed channels receive it)
+    pub wallet_address: Option<String>,
+    pub title: String,
+    /// Ordered (name, value) pairs, shown as message fields and usable as `{{name}}` in templates
+    pub fields: Vec<(String, String)>,
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind, wallet_address: Option<&str>, title: impl Into<String>) -> Self {
+        Self {
+            kind,
+            wallet_address: wallet_address.map(|w| w.to_lowercase()),
+            title: title.into(),
+            fields: Vec::new(),
+            url: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Add a field (skipped when the value is empty)
+    pub fn field(mut self, name: &str, value: impl ToString) -> Self {
+        let value = value.to_string();
+        if !value.is_empty() {
+            self.fields.push((name.to_string(), value));
+        }
+        self
+    }
+
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Value of a field by name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Build from an auto-trade log entry (None when the action isn't notifiable)
+    pub fn from_trade_log(log: &AutoTradeLog) -> Option<Self> {
+        let kind = NotificationKind::from_trade_action(&log.action)?;
+        let title = match kind {
+            NotificationKind::Fill if log.exit_price.is_some() => "Position sold",
+            NotificationKind::Fill => "Order filled",
+            NotificationKind::StopLoss => "Stop loss triggered",
+            NotificationKind::Dispute => "Dispute exit",
+            NotificationKind::Redeem => "Position redeemed",
+            _ => "Auto-trade error",
+        };
+
+        let mut n = Self::new(kind, Some(&log.wallet_address), title)
+            .field("action", &log.action)
+            .field("market", log.market_question.as_deref().unwrap_or_default())
+            .field("side", log.side.as_deref().unwrap_or_default());
+        if let Some(price) = log.entry_price {
+            n = n.field("entry_price", price.round_dp(4));
+        }
+        if let Some(price) = log.exit_price {
+            n = n.field("exit_price", price.round_dp(4));
+        }
+        if let Some(size) = log.size {
+            n = n.field("size", format!("${:.2}", size));
+        }
+        if let Some(pnl) = log.pnl {
+            n = n.field("pnl", format!("${:.2}", pnl));
+        }
+        n = n.field("reason", log.trigger_reason.as_deref().unwrap_or_default());
+        n.created_at = log.created_at;
+        Some(n)
+    }
+
+    /// Build from a Mint Maker log entry (None when the action isn't notifiable)
+    pub fn from_mint_maker_log(entry: &MintMakerLogEntry) -> Option<Self> {
+        let kind = NotificationKind::from_mint_maker_action(&entry.action)?;
+        let title = match kind {
+            NotificationKind::Orphan => "Mint Maker pair orphaned",
+            NotificationKind::StopLoss => "Mint Maker stop loss filled",
+            _ => "Mint Maker pair redeemed",
+        };
+
+        Some(
+            Self::new(kind, Some(&entry.wallet_address), title)
+                .field("action", &entry.action)
+                .field("market", entry.question.as_deref().unwrap_or_default())
+                .field("asset", entry.asset.as_deref().unwrap_or_default())
+                .field("yes_price", entry.yes_price.as_deref().unwrap_or_default())
+                .field("no_price", entry.no_price.as_deref().unwrap_or_default())
+                .field("size", entry.size.as_deref().unwrap_or_default())
+                .field("profit", entry.profit.as_deref().unwrap_or_default())
+                .field("reason", entry.details.as_deref().unwrap_or_default()),
+        )
+    }
+}
+
+/// Handle for raising notifications; cheap to clone
+#[derive(Clone)]
+pub struct Notifier {
+    tx: broadcast::Sender<Notification>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(256);
+        Self { tx }
+    }
+
+    /// Queue a notification for the router (dropped if the router isn't running)
+    pub fn notify(&self, notification: Notification) {
+        let _ = self.tx.send(notification);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.tx.subscribe()
+    }
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_action_kinds() {
+        assert_eq!(NotificationKind::from_trade_action("auto_buy"), Some(NotificationKind::Fill));
+        assert_eq!(NotificationKind::from_trade_action("trailing_stop"), Some(NotificationKind::StopLoss));
+        assert_eq!(NotificationKind::from_trade_action("dispute_dvm_exit"), Some(NotificationKind::Dispute));
+        assert_eq!(NotificationKind::from_trade_action("dispute_snipe"), Some(NotificationKind::Fill));
+        assert_eq!(NotificationKind::from_trade_action("sell_failed"), Some(NotificationKind::Error));
+        assert_eq!(NotificationKind::from_trade_action("place_pair"), None);
+    }
+
+    #[test]
+    fn test_kind_list_roundtrip() {
+        let kinds = parse_kinds("fill, stop_loss,bogus,fill");
+        assert_eq!(kinds, vec![NotificationKind::Fill, NotificationKind::StopLoss, NotificationKind::Fill]);
+        assert_eq!(format_kinds(&kinds), "fill,stop_loss");
+    }
+}
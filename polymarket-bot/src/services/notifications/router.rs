@@ -0,0 +1,299 @@
+//! Notification router - collects events and delivers them to subscribed channels
+
+use super::sinks::{ChannelTarget, Message, Sinks};
+use super::{parse_kinds, Notification, NotificationKind, Notifier};
+use crate::db::Database;
+use crate::types::{DisputeAlert, DisputeStatus};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// How often the auto-trade and Mint Maker logs are tailed
+const LOG_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Max log rows handled per poll
+const LOG_BATCH: i64 = 200;
+/// Budget key of the operator webhook (channel ids start at 1)
+const OPERATOR_CHANNEL_ID: i64 = 0;
+/// Rate limit of the operator webhook
+const OPERATOR_RATE_LIMIT_PER_MINUTE: u32 = 30;
+/// Kinds the operator webhook (DISCORD_WEBHOOK_URL) receives for every wallet
+const OPERATOR_KINDS: [NotificationKind; 2] = [NotificationKind::Security, NotificationKind::Error];
+
+/// Per-channel token bucket holding up to one minute of messages
+struct Budget {
+    tokens: f64,
+    last_refill: Instant,
+    /// Notifications dropped since the last delivery
+    suppressed: u32,
+}
+
+impl Budget {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        Self {
+            tokens: per_minute.max(1) as f64,
+            last_refill: now,
+            suppressed: 0,
+        }
+    }
+
+    /// Take a token, or count a suppressed notification when the bucket is empty.
+    /// The limit is passed in because a channel's limit can change at any time.
+    fn try_take(&mut self, per_minute: u32, now: Instant) -> bool {
+        let capacity = per_minute.max(1) as f64;
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * capacity / 60.0).min(capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            self.suppressed += 1;
+            false
+        }
+    }
+}
+
+/// Routes notifications to the operator webhook and wallet channels
+pub struct NotificationRouter {
+    db: Arc<Database>,
+    sinks: Sinks,
+    operator: Option<ChannelTarget>,
+    budgets: HashMap<i64, Budget>,
+    /// Last seen status per dispute assertion
+    disputes_seen: HashMap<String, DisputeStatus>,
+    /// False until the first dispute batch is recorded (disputes active at startup aren't re-announced)
+    disputes_primed: bool,
+}
+
+impl NotificationRouter {
+    pub fn new(db: Arc<Database>, sinks: Sinks, operator_webhook_url: Option<String>) -> Self {
+        Self {
+            db,
+            sinks,
+            operator: operator_webhook_url.map(|url| ChannelTarget::Discord { url }),
+            budgets: HashMap::new(),
+            disputes_seen: HashMap::new(),
+            disputes_primed: false,
+        }
+    }
+
+    /// Run forever: direct notifications, dispute alerts and log tailing
+    pub async fn run(mut self, notifier: Notifier, mut dispute_rx: broadcast::Receiver<Vec<DisputeAlert>>) {
+        let mut notifications = notifier.subscribe();
+
+        // Start at the current end of the logs; history isn't replayed after a restart
+        let mut last_trade_log_id = self.db.auto_trading().get_max_auto_trade_log_id().await.unwrap_or(0);
+        let mut last_mm_log_id = self.db.mint_maker().get_max_mint_maker_log_id().await.unwrap_or(0);
+
+        let mut poll = tokio::time::interval(LOG_POLL_INTERVAL);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        info!("[Notify] Router started (operator webhook: {})", self.operator.is_some());
+
+        loop {
+            tokio::select! {
+                received = notifications.recv() => match received {
+                    Ok(notification) => self.dispatch(&notification).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("[Notify] Router lagged, {} notifications dropped", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                received = dispute_rx.recv() => match received {
+                    Ok(alerts) => self.on_disputes(&alerts).await,
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = poll.tick() => {
+                    last_trade_log_id = self.poll_trade_log(last_trade_log_id).await;
+                    last_mm_log_id = self.poll_mint_maker_log(last_mm_log_id).await;
+                }
+            }
+        }
+
+        info!("[Notify] Router stopped");
+    }
+
+    /// Deliver a notification to the operator webhook and the wallet's subscribed channels
+    pub async fn dispatch(&mut self, notification: &Notification) {
+        if let Some(operator) = self.operator.clone() {
+            if OPERATOR_KINDS.contains(&notification.kind) {
+                if let Err(e) = self
+                    .send(OPERATOR_CHANNEL_ID, &operator, None, OPERATOR_RATE_LIMIT_PER_MINUTE, notification)
+                    .await
+                {
+                    warn!("[Notify] Operator webhook failed: {}", e);
+                }
+            }
+        }
+
+        let Some(wallet_address) = &notification.wallet_address else {
+            return;
+        };
+
+        let channels = match self.db.notifications().get_notification_channels(wallet_address).await {
+            Ok(channels) => channels,
+            Err(e) => {
+                warn!("[Notify] Failed to load channels for {}: {}", wallet_address, e);
+                return;
+            }
+        };
+
+        for channel in channels
+            .iter()
+            .filter(|c| c.enabled && parse_kinds(&c.events).contains(&notification.kind))
+        {
+            let target: ChannelTarget = match serde_json::from_str(&channel.target) {
+                Ok(target) => target,
+                Err(e) => {
+                    warn!("[Notify] Channel {} has an invalid target: {}", channel.id, e);
+                    continue;
+                }
+            };
+
+            let per_minute = u32::try_from(channel.rate_limit_per_minute).unwrap_or(1);
+            match self
+                .send(channel.id, &target, channel.template.as_deref(), per_minute, notification)
+                .await
+            {
+                Ok(false) => {}
+                Ok(true) => {
+                    let _ = self.db.notifications().record_notification_delivery(channel.id, None).await;
+                }
+                Err(e) => {
+                    warn!("[Notify] Channel {} ({}) failed: {}", channel.id, target.type_name(), e);
+                    let _ = self.db.notifications().record_notification_delivery(channel.id, Some(&e.to_string())).await;
+                }
+            }
+        }
+    }
+
+    /// Send through one channel unless its rate limit is used up. Ok(false) = suppressed.
+    async fn send(
+        &mut self,
+        channel_id: i64,
+        target: &ChannelTarget,
+        template: Option<&str>,
+        per_minute: u32,
+        notification: &Notification,
+    ) -> anyhow::Result<bool> {
+        let now = Instant::now();
+        let budget = self
+            .budgets
+            .entry(channel_id)
+            .or_insert_with(|| Budget::new(per_minute, now));
+        if !budget.try_take(per_minute, now) {
+            debug!("[Notify] Channel {} rate limited, {} suppressed", channel_id, budget.suppressed);
+            return Ok(false);
+        }
+        let suppressed = std::mem::take(&mut budget.suppressed);
+
+        let message = Message::new(notification, template, suppressed);
+        self.sinks.deliver(target, &message).await?;
+        Ok(true)
+    }
+
+    /// Notify new auto-trade log entries; returns the new high-water id
+    async fn poll_trade_log(&mut self, after_id: i64) -> i64 {
+        let logs = match self.db.auto_trading().get_auto_trade_logs_after(after_id, LOG_BATCH).await {
+            Ok(logs) => logs,
+            Err(e) => {
+                warn!("[Notify] Failed to read auto-trade log: {}", e);
+                return after_id;
+            }
+        };
+
+        let mut last_id = after_id;
+        for log in &logs {
+            last_id = last_id.max(log.id.unwrap_or(last_id));
+            if let Some(notification) = Notification::from_trade_log(log) {
+                self.dispatch(&notification).await;
+            }
+        }
+        last_id
+    }
+
+    /// Notify new Mint Maker log entries; returns the new high-water id
+    async fn poll_mint_maker_log(&mut self, after_id: i64) -> i64 {
+        let entries = match self.db.mint_maker().get_mint_maker_logs_after(after_id, LOG_BATCH).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("[Notify] Failed to read Mint Maker log: {}", e);
+                return after_id;
+            }
+        };
+
+        let mut last_id = after_id;
+        for entry in &entries {
+            last_id = last_id.max(entry.id);
+            if let Some(notification) = Notification::from_mint_maker_log(entry) {
+                self.dispatch(&notification).await;
+            }
+        }
+        last_id
+    }
+
+    /// Notify holders when a dispute on their market appears or changes status
+    async fn on_disputes(&mut self, alerts: &[DisputeAlert]) {
+        for alert in alerts {
+            let previous = self.disputes_seen.insert(alert.assertion_id.clone(), alert.dispute_status);
+            if !self.disputes_primed || previous == Some(alert.dispute_status) {
+                continue;
+            }
+
+            let wallets = match self.db.get_wallets_holding_condition(&alert.condition_id).await {
+                Ok(wallets) => wallets,
+                Err(e) => {
+                    warn!("[Notify] Failed to look up holders of {}: {}", alert.condition_id, e);
+                    continue;
+                }
+            };
+
+            let title = match alert.dispute_status {
+                DisputeStatus::Proposed => "Resolution proposed",
+                DisputeStatus::Disputed => "Market disputed",
+                DisputeStatus::DvmVote => "Dispute escalated to DVM vote",
+            };
+            for wallet in wallets {
+                let notification = Notification::new(NotificationKind::Dispute, Some(&wallet), title)
+                    .field("market", &alert.question)
+                    .field("status", alert.dispute_status)
+                    .field("proposed_outcome", &alert.proposed_outcome)
+                    .field("round", alert.dispute_round)
+                    .field("yes_price", alert.current_yes_price.round_dp(3))
+                    .field("no_price", alert.current_no_price.round_dp(3))
+                    .with_url(format!("https://polymarket.com/event/{}", alert.slug));
+                self.dispatch(&notification).await;
+            }
+        }
+
+        // Forget settled disputes
+        self.disputes_seen
+            .retain(|id, _| alerts.iter().any(|a| &a.assertion_id == id));
+        self.disputes_primed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_suppresses_and_refills() {
+        let start = Instant::now();
+        let mut budget = Budget::new(2, start);
+
+        assert!(budget.try_take(2, start));
+        assert!(budget.try_take(2, start));
+        assert!(!budget.try_take(2, start));
+        assert!(!budget.try_take(2, start));
+        assert_eq!(budget.suppressed, 2);
+
+        // 2/minute refills one token every 30s
+        assert!(budget.try_take(2, start + Duration::from_secs(30)));
+        assert!(!budget.try_take(2, start + Duration::from_secs(31)));
+    }
+}
@@ -0,0 +1,396 @@
+//! Notification sinks - one per channel type
+//!
+//! - Discord: embed with one field per notification field
+//! - Slack-compatible webhooks (Slack, Mattermost, Rocket.Chat): `{"text": ...}`
+//! - Telegram: Bot API `sendMessage`
+//! - Generic webhook: the notification as JSON, HMAC-SHA256 signed when a secret is set
+//! - Email: plain text through the configured SMTP relay
+
+use super::template::{self, field_label};
+use super::Notification;
+use crate::config::SmtpConfig;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Timeout for a single delivery
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Header carrying the hex HMAC-SHA256 of a generic webhook body
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+/// Where a channel delivers (stored as JSON on the channel)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChannelTarget {
+    Discord { url: String },
+    Slack { url: String },
+    Telegram { bot_token: String, chat_id: String },
+    Webhook {
+        url: String,
+        /// Signs the body when set
+        #[serde(default)]
+        secret: Option<String>,
+    },
+    Email { to: String },
+}
+
+impl ChannelTarget {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ChannelTarget::Discord { .. } => "discord",
+            ChannelTarget::Slack { .. } => "slack",
+            ChannelTarget::Telegram { .. } => "telegram",
+            ChannelTarget::Webhook { .. } => "webhook",
+            ChannelTarget::Email { .. } => "email",
+        }
+    }
+
+    /// Check a target before it is stored
+    pub fn validate(&self, smtp_configured: bool) -> Result<(), String> {
+        match self {
+            ChannelTarget::Discord { url } | ChannelTarget::Slack { url } | ChannelTarget::Webhook { url, .. } => {
+                if !url.starts_with("https://") || url.len() <= "https://".len() {
+                    return Err("Webhook URL must start with https://".to_string());
+                }
+            }
+            ChannelTarget::Telegram { bot_token, chat_id } => {
+                if !bot_token.contains(':') || bot_token.contains('/') {
+                    return Err("Invalid Telegram bot token".to_string());
+                }
+                if chat_id.trim().is_empty() {
+                    return Err("Telegram chat_id is required".to_string());
+                }
+            }
+            ChannelTarget::Email { to } => {
+                if !smtp_configured {
+                    return Err("Email notifications are not configured on this server".to_string());
+                }
+                if to.parse::<lettre::Address>().is_err() {
+                    return Err("Invalid email address".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy with secrets masked, for API responses
+    pub fn redacted(&self) -> Self {
+        match self {
+            ChannelTarget::Discord { url } => ChannelTarget::Discord { url: mask_url(url) },
+            ChannelTarget::Slack { url } => ChannelTarget::Slack { url: mask_url(url) },
+            ChannelTarget::Telegram { chat_id, .. } => ChannelTarget::Telegram {
+                bot_token: "********".to_string(),
+                chat_id: chat_id.clone(),
+            },
+            ChannelTarget::Webhook { url, secret } => ChannelTarget::Webhook {
+                url: mask_url(url),
+                secret: secret.as_ref().map(|_| "********".to_string()),
+            },
+            ChannelTarget::Email { to } => ChannelTarget::Email { to: to.clone() },
+        }
+    }
+}
+
+/// Keep scheme and host, hide the path (webhook URLs embed their secret there)
+fn mask_url(url: &str) -> String {
+    let host_end = url
+        .find("://")
+        .map(|i| i + 3)
+        .and_then(|start| url[start..].find('/').map(|i| start + i))
+        .unwrap_or(url.len());
+    if host_end < url.len() {
+        format!("{}/…", &url[..host_end])
+    } else {
+        url.to_string()
+    }
+}
+
+/// A rendered notification ready for a sink
+pub struct Message<'a> {
+    pub notification: &'a Notification,
+    /// Channel template output, or the default text layout
+    pub text: String,
+    /// Whether `text` comes from a channel template (sinks with their own layout use it verbatim)
+    pub templated: bool,
+    /// Notifications dropped by this channel's rate limit since its last delivery
+    pub suppressed: u32,
+}
+
+impl<'a> Message<'a> {
+    /// Render with the channel template, or the default layout when there is none
+    pub fn new(notification: &'a Notification, template: Option<&str>, suppressed: u32) -> Self {
+        match template {
+            Some(template) => Self {
+                notification,
+                text: template::render(template, notification),
+                templated: true,
+                suppressed,
+            },
+            None => Self {
+                notification,
+                text: template::default_text(notification),
+                templated: false,
+                suppressed,
+            },
+        }
+    }
+
+    /// `text` plus the suppressed-count note
+    fn full_text(&self) -> String {
+        match self.suppressed {
+            0 => self.text.clone(),
+            n => format!("{}\n(+{} more notifications suppressed by the rate limit)", self.text, n),
+        }
+    }
+}
+
+/// Delivers messages to one channel
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    async fn send(&self, message: &Message<'_>) -> Result<()>;
+}
+
+/// Shared clients for building sinks
+#[derive(Clone)]
+pub struct Sinks {
+    client: Client,
+    mailer: Option<(AsyncSmtpTransport<Tokio1Executor>, String)>,
+}
+
+impl Sinks {
+    pub fn new(smtp: Option<&SmtpConfig>) -> Result<Self> {
+        let client = Client::builder().timeout(SEND_TIMEOUT).build()?;
+
+        let mailer = match smtp {
+            Some(smtp) => {
+                let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)?
+                    .port(smtp.port)
+                    .timeout(Some(SEND_TIMEOUT));
+                if let (Some(user), Some(pass)) = (&smtp.username, &smtp.password) {
+                    builder = builder.credentials(Credentials::new(user.clone(), pass.clone()));
+                }
+                Some((builder.build(), smtp.from.clone()))
+            }
+            None => None,
+        };
+
+        Ok(Self { client, mailer })
+    }
+
+    pub fn smtp_configured(&self) -> bool {
+        self.mailer.is_some()
+    }
+
+    /// Sink for a channel target
+    pub fn sink(&self, target: &ChannelTarget) -> Result<Box<dyn NotificationSink>> {
+        let client = self.client.clone();
+        Ok(match target.clone() {
+            ChannelTarget::Discord { url } => Box::new(DiscordSink { client, url }),
+            ChannelTarget::Slack { url } => Box::new(SlackSink { client, url }),
+            ChannelTarget::Telegram { bot_token, chat_id } => Box::new(TelegramSink { client, bot_token, chat_id }),
+            ChannelTarget::Webhook { url, secret } => Box::new(WebhookSink { client, url, secret }),
+            ChannelTarget::Email { to } => {
+                let (mailer, from) = self
+                    .mailer
+                    .clone()
+                    .ok_or_else(|| anyhow!("SMTP is not configured"))?;
+                Box::new(EmailSink { mailer, from, to })
+            }
+        })
+    }
+
+    /// Build the sink for a target and send
+    pub async fn deliver(&self, target: &ChannelTarget, message: &Message<'_>) -> Result<()> {
+        self.sink(target)?.send(message).await
+    }
+}
+
+/// Send a request and fail on a non-2xx status. Errors never include the URL:
+/// webhook URLs and the Telegram API path carry secrets.
+async fn post_json(request: reqwest::RequestBuilder) -> Result<()> {
+    let response = request.send().await.map_err(|e| anyhow!(e.without_url()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("HTTP {}: {}", status, body.chars().take(200).collect::<String>()));
+    }
+    Ok(())
+}
+
+struct DiscordSink {
+    client: Client,
+    url: String,
+}
+
+#[async_trait]
+impl NotificationSink for DiscordSink {
+    async fn send(&self, message: &Message<'_>) -> Result<()> {
+        let n = message.notification;
+
+        let mut fields = Vec::new();
+        if let Some(wallet) = &n.wallet_address {
+            fields.push(json!({ "name": "Wallet", "value": wallet, "inline": false }));
+        }
+        if !message.templated {
+            fields.extend(n.fields.iter().map(|(name, value)| {
+                json!({ "name": field_label(name), "value": value, "inline": true })
+            }));
+        }
+
+        let footer = match message.suppressed {
+            0 => "Polymarket Bot".to_string(),
+            s => format!("Polymarket Bot · {} more suppressed by rate limit", s),
+        };
+
+        let mut embed = json!({
+            "title": format!("{} {}", n.kind.emoji(), n.title),
+            "color": n.kind.color(),
+            "fields": fields,
+            "footer": { "text": footer },
+            "timestamp": n.created_at.to_rfc3339()
+        });
+        if message.templated {
+            embed["description"] = json!(message.text);
+        }
+        if let Some(url) = &n.url {
+            embed["url"] = json!(url);
+        }
+
+        post_json(self.client.post(&self.url).json(&json!({ "embeds": [embed] }))).await
+    }
+}
+
+struct SlackSink {
+    client: Client,
+    url: String,
+}
+
+#[async_trait]
+impl NotificationSink for SlackSink {
+    async fn send(&self, message: &Message<'_>) -> Result<()> {
+        post_json(self.client.post(&self.url).json(&json!({ "text": message.full_text() }))).await
+    }
+}
+
+struct TelegramSink {
+    client: Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+#[async_trait]
+impl NotificationSink for TelegramSink {
+    async fn send(&self, message: &Message<'_>) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let body = json!({
+            "chat_id": self.chat_id,
+            "text": message.full_text(),
+            "disable_web_page_preview": true
+        });
+        post_json(self.client.post(url).json(&body)).await
+    }
+}
+
+struct WebhookSink {
+    client: Client,
+    url: String,
+    secret: Option<String>,
+}
+
+#[async_trait]
+impl NotificationSink for WebhookSink {
+    async fn send(&self, message: &Message<'_>) -> Result<()> {
+        let n = message.notification;
+        let fields: serde_json::Map<String, serde_json::Value> = n
+            .fields
+            .iter()
+            .map(|(name, value)| (name.clone(), json!(value)))
+            .collect();
+
+        let body = serde_json::to_vec(&json!({
+            "kind": n.kind,
+            "wallet_address": n.wallet_address,
+            "title": n.title,
+            "fields": fields,
+            "url": n.url,
+            "created_at": n.created_at.to_rfc3339(),
+            "text": message.text,
+            "suppressed": message.suppressed
+        }))?;
+
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.secret {
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes())?;
+            mac.update(&body);
+            request = request.header(SIGNATURE_HEADER, hex::encode(mac.finalize().into_bytes()));
+        }
+
+        post_json(request.body(body)).await
+    }
+}
+
+struct EmailSink {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    from: String,
+    to: String,
+}
+
+#[async_trait]
+impl NotificationSink for EmailSink {
+    async fn send(&self, message: &Message<'_>) -> Result<()> {
+        let email = lettre::Message::builder()
+            .from(self.from.parse()?)
+            .to(self.to.parse()?)
+            .subject(format!("[Polymarket Bot] {}", message.notification.title))
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.full_text())?;
+
+        self.mailer.send(email).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_json() {
+        let target: ChannelTarget =
+            serde_json::from_str(r#"{"type":"telegram","bot_token":"123:abc","chat_id":"-100"}"#).unwrap();
+        assert_eq!(target.type_name(), "telegram");
+        assert!(target.validate(false).is_ok());
+        assert_eq!(
+            target.redacted(),
+            ChannelTarget::Telegram { bot_token: "********".to_string(), chat_id: "-100".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_validate_and_mask() {
+        let discord = ChannelTarget::Discord { url: "https://discord.com/api/webhooks/1/secret".to_string() };
+        assert!(discord.validate(false).is_ok());
+        assert_eq!(discord.redacted(), ChannelTarget::Discord { url: "https://discord.com/…".to_string() });
+
+        let plain = ChannelTarget::Webhook { url: "http://10.0.0.1/hook".to_string(), secret: None };
+        assert!(plain.validate(false).is_err());
+
+        let email = ChannelTarget::Email { to: "me@example.com".to_string() };
+        assert!(email.validate(false).is_err());
+        assert!(email.validate(true).is_ok());
+    }
+}
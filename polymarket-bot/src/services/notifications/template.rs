@@ -0,0 +1,134 @@
+//! Message templating
+//!
+//! Channel templates are plain text with `{{name}}` placeholders. Besides the
+//! notification's own fields (`market`, `pnl`, `reason`, ...) every template can
+//! use `kind`, `title`, `wallet`, `wallet_short`, `time` and `url`. Unknown
+//! placeholders render as empty strings.
+
+use super::Notification;
+
+/// Longest template a channel may store
+pub const MAX_TEMPLATE_LEN: usize = 2000;
+
+/// Check a template before it is stored
+pub fn validate(template: &str) -> Result<(), String> {
+    if template.len() > MAX_TEMPLATE_LEN {
+        return Err(format!("Template must be at most {} characters", MAX_TEMPLATE_LEN));
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or("Unclosed '{{' in template")?;
+        let name = after[..end].trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid placeholder '{{{{{}}}}}'", &after[..end]));
+        }
+        rest = &after[end + 2..];
+    }
+    Ok(())
+}
+
+/// Render a channel template for a notification
+pub fn render(template: &str, notification: &Notification) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                out.push_str(&lookup(notification, after[..end].trim()));
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Default plain-text layout: title line, then one "Name: value" line per field
+pub fn default_text(notification: &Notification) -> String {
+    let mut lines = vec![format!("{} {}", notification.kind.emoji(), notification.title)];
+    if let Some(wallet) = &notification.wallet_address {
+        lines.push(format!("Wallet: {}", short_address(wallet)));
+    }
+    lines.extend(
+        notification
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}: {}", field_label(name), value)),
+    );
+    if let Some(url) = &notification.url {
+        lines.push(url.clone());
+    }
+    lines.join("\n")
+}
+
+/// Display label for a field name ("entry_price" -> "Entry price")
+pub fn field_label(name: &str) -> String {
+    let spaced = name.replace('_', " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn lookup(notification: &Notification, name: &str) -> String {
+    match name {
+        "kind" => notification.kind.as_str().to_string(),
+        "title" => notification.title.clone(),
+        "wallet" => notification.wallet_address.clone().unwrap_or_default(),
+        "wallet_short" => notification.wallet_address.as_deref().map(short_address).unwrap_or_default(),
+        "time" => notification.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        "url" => notification.url.clone().unwrap_or_default(),
+        _ => notification.get(name).unwrap_or_default().to_string(),
+    }
+}
+
+fn short_address(address: &str) -> String {
+    if address.len() > 10 {
+        format!("{}…{}", &address[..6], &address[address.len() - 4..])
+    } else {
+        address.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::notifications::NotificationKind;
+
+    fn sample() -> Notification {
+        Notification::new(NotificationKind::StopLoss, Some("0xABCDEF0123456789"), "Stop loss triggered")
+            .field("market", "Will it rain?")
+            .field("pnl", "$-4.20")
+    }
+
+    #[test]
+    fn test_render() {
+        let text = render("{{kind}}: {{ market }} ({{pnl}}) {{missing}}by {{wallet_short}}", &sample());
+        assert_eq!(text, "stop_loss: Will it rain? ($-4.20) by 0xabcd…6789");
+        assert_eq!(render("unclosed {{pnl", &sample()), "unclosed {{pnl");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("{{title}} - {{pnl}}").is_ok());
+        assert!(validate("{{title").is_err());
+        assert!(validate("{{bad name}}").is_err());
+    }
+
+    #[test]
+    fn test_default_text() {
+        let text = default_text(&sample());
+        assert!(text.starts_with("🛑 Stop loss triggered\nWallet: 0xabcd…6789\n"));
+        assert!(text.ends_with("Pnl: $-4.20"));
+    }
+}
@@ -0,0 +1,443 @@
+//! On-chain Job Tracker - persisted queue for relay and direct transactions
+//!
+//! Merges, redeems, splits, Safe deployments, approvals, deposits and
+//! withdrawals are recorded in `onchain_jobs`. Callers submit and hand the job
+//! over; a single worker drives every submitted job to a final state:
+//! - Relay jobs: polls the relay `/transaction` endpoint
+//! - Direct jobs: polls receipts, speeding up stuck transactions when the
+//!   wallet's key is unlocked
+//!
+//! Every state change is broadcast for the WebSocket feed. Services react to
+//! that broadcast and read the final state of a job with `outcome`; `wait`
+//! is for request handlers that have to answer with the result.
+
+use super::auto_trader::KeyStore;
+use super::polygon_tx::{Eip1559Tx, PendingTx, PolygonTxService};
+use super::safe_activation::{self, BuilderCredentials};
+use crate::db::Database;
+use crate::types::{OnchainJob, OnchainJobKind, OnchainJobStatus};
+use alloy::signers::Signer;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// Worker poll interval
+const WORKER_INTERVAL: Duration = Duration::from_secs(3);
+/// Queued jobs never submitted after this long are left over from a crash
+const QUEUED_TIMEOUT_SECS: i64 = 300;
+/// Relay jobs not mined after this long are failed
+const RELAY_TIMEOUT_SECS: i64 = 600;
+/// Direct transactions pending this long get sped up
+const STUCK_AFTER_SECS: i64 = 60;
+/// Maximum speed-ups per original transaction
+const MAX_REPLACEMENTS: usize = 3;
+/// Direct transactions not mined after this long are failed
+const DIRECT_TIMEOUT_SECS: i64 = 1800;
+/// Keep checking replaced transactions this long in case the original is mined
+const REPLACED_WATCH_SECS: i64 = 1800;
+
+/// Persisted on-chain job queue and its worker
+pub struct OnchainJobTracker {
+    db: Arc<Database>,
+    polygon_tx: Arc<PolygonTxService>,
+    key_store: KeyStore,
+    builder_creds: Option<BuilderCredentials>,
+    client: reqwest::Client,
+    update_tx: broadcast::Sender<OnchainJob>,
+}
+
+impl OnchainJobTracker {
+    pub fn new(
+        db: Arc<Database>,
+        polygon_tx: Arc<PolygonTxService>,
+        key_store: KeyStore,
+        builder_creds: Option<BuilderCredentials>,
+    ) -> Self {
+        let (update_tx, _) = broadcast::channel(128);
+        Self {
+            db,
+            polygon_tx,
+            key_store,
+            builder_creds,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("Failed to create HTTP client"),
+            update_tx,
+        }
+    }
+
+    /// Subscribe to job state changes
+    pub fn subscribe(&self) -> broadcast::Receiver<OnchainJob> {
+        self.update_tx.subscribe()
+    }
+
+    /// Record a job before submitting it
+    pub async fn enqueue(
+        &self,
+        wallet_address: &str,
+        kind: OnchainJobKind,
+        description: &str,
+        reference: Option<&str>,
+    ) -> Result<i64> {
+        let id = self
+            .db.onchain_jobs()
+            .create_onchain_job(wallet_address, kind, OnchainJobStatus::Queued, description, reference, None)
+            .await?;
+        self.publish(id).await;
+        Ok(id)
+    }
+
+    /// The relay accepted the job
+    pub async fn submitted_relay(&self, id: i64, relay_tx_id: &str) -> Result<()> {
+        self.db.onchain_jobs().mark_onchain_job_submitted(id, Some(relay_tx_id), None, None).await?;
+        self.publish(id).await;
+        Ok(())
+    }
+
+    /// The job was broadcast as a direct transaction
+    pub async fn submitted_tx(&self, id: i64, pending: &PendingTx) -> Result<()> {
+        let payload = pending.tx.to_json().to_string();
+        self.db.onchain_jobs()
+            .mark_onchain_job_submitted(id, None, Some(&pending.hash), Some(&payload))
+            .await?;
+        self.publish(id).await;
+        Ok(())
+    }
+
+    /// Submission failed
+    pub async fn fail(&self, id: i64, error: &str) -> Result<()> {
+        self.set_status(id, OnchainJobStatus::Failed, None, Some(error)).await
+    }
+
+    /// Record a relay job that was already submitted
+    pub async fn track_relay(
+        &self,
+        wallet_address: &str,
+        kind: OnchainJobKind,
+        description: &str,
+        reference: Option<&str>,
+        relay_tx_id: &str,
+    ) -> Result<i64> {
+        let id = self.enqueue(wallet_address, kind, description, reference).await?;
+        self.submitted_relay(id, relay_tx_id).await?;
+        Ok(id)
+    }
+
+    /// Record a direct transaction that was already broadcast
+    pub async fn track_tx(
+        &self,
+        wallet_address: &str,
+        kind: OnchainJobKind,
+        description: &str,
+        reference: Option<&str>,
+        pending: &PendingTx,
+    ) -> Result<i64> {
+        let id = self.enqueue(wallet_address, kind, description, reference).await?;
+        self.submitted_tx(id, pending).await?;
+        Ok(id)
+    }
+
+    /// Final state of the job `id` and its speed-up replacements: the version
+    /// that was mined, or the last one once none of them can be mined any more.
+    /// None while the job is still in flight.
+    pub async fn outcome(&self, id: i64) -> Result<Option<OnchainJob>> {
+        let mut chain = vec![self.db.onchain_jobs().get_onchain_job(id).await?.context("On-chain job not found")?];
+        while let Some(next) = self.db.onchain_jobs().get_onchain_job_replacement(chain[chain.len() - 1].id).await? {
+            chain.push(next);
+        }
+        Ok(chain_outcome(&chain, Utc::now()).cloned())
+    }
+
+    /// Wait for a job to reach a final state, following speed-up replacements.
+    ///
+    /// Returns the job as it stands at `timeout` if it hasn't finished; the
+    /// worker keeps driving it either way.
+    pub async fn wait(&self, id: i64, timeout: Duration) -> Result<OnchainJob> {
+        let mut rx = self.update_tx.subscribe();
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(job) = self.outcome(id).await? {
+                return Ok(job);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return self.db.onchain_jobs().get_onchain_job(id).await?.context("On-chain job not found");
+            }
+
+            // Any update may belong to the chain; re-read it from the DB
+            match tokio::time::timeout(remaining, rx.recv()).await {
+                Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) | Err(_) => {}
+                Ok(Err(broadcast::error::RecvError::Closed)) => anyhow::bail!("On-chain job feed closed"),
+            }
+        }
+    }
+
+    /// Run the worker loop until `stop` is set, finishing the pass in progress
+    pub async fn run(&self, mut stop: watch::Receiver<bool>) {
+        info!("[OnchainJobs] Worker started");
+        loop {
+            if let Err(e) = self.drive().await {
+                warn!("[OnchainJobs] Worker pass failed: {}", e);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(WORKER_INTERVAL) => {}
+                _ = stop.changed() => break,
+            }
+        }
+        info!("[OnchainJobs] Worker stopped");
+    }
+
+    /// One pass over every non-final job
+    async fn drive(&self) -> Result<()> {
+        for job in self.db.onchain_jobs().get_onchain_jobs_by_status(OnchainJobStatus::Queued).await? {
+            if age_secs(&job) > QUEUED_TIMEOUT_SECS {
+                self.fail(job.id, "Never submitted (interrupted before reaching the relay or node)").await?;
+            }
+        }
+
+        for job in self.db.onchain_jobs().get_onchain_jobs_by_status(OnchainJobStatus::Submitted).await? {
+            let result = if job.relay_tx_id.is_some() {
+                self.drive_relay(&job).await
+            } else {
+                self.drive_direct(&job).await
+            };
+            if let Err(e) = result {
+                debug!("[OnchainJobs] Job #{} check failed: {}", job.id, e);
+            }
+        }
+
+        for job in self.db.onchain_jobs().get_onchain_jobs_by_status(OnchainJobStatus::Replaced).await? {
+            if age_secs(&job) <= REPLACED_WATCH_SECS {
+                if let Err(e) = self.drive_replaced(&job).await {
+                    debug!("[OnchainJobs] Replaced job #{} check failed: {}", job.id, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn drive_relay(&self, job: &OnchainJob) -> Result<()> {
+        let creds = self
+            .builder_creds
+            .as_ref()
+            .context("Builder credentials not configured")?;
+        let relay_tx_id = job.relay_tx_id.as_deref().unwrap_or_default();
+
+        let path = format!("/transaction?id={}", relay_tx_id);
+        let result = safe_activation::relay_request(&self.client, creds, "GET", &path, None).await?;
+        let txn = result.as_array().and_then(|t| t.first()).cloned().unwrap_or_default();
+        let state = txn.get("state").and_then(|s| s.as_str()).unwrap_or("");
+        let tx_hash = txn.get("transactionHash").and_then(|h| h.as_str());
+
+        match state {
+            "STATE_MINED" | "STATE_CONFIRMED" => {
+                info!("[OnchainJobs] #{} {} mined: {}", job.id, job.description, tx_hash.unwrap_or("?"));
+                self.set_status(job.id, OnchainJobStatus::Mined, tx_hash, None).await
+            }
+            "STATE_FAILED" | "STATE_INVALID" => {
+                warn!("[OnchainJobs] #{} {} failed: {}", job.id, job.description, txn);
+                let error = format!("Relay transaction {}", state);
+                self.set_status(job.id, OnchainJobStatus::Failed, tx_hash, Some(&error)).await
+            }
+            _ if age_secs(job) > RELAY_TIMEOUT_SECS => {
+                let error = format!("Relay did not confirm within {}s (last state: {})", RELAY_TIMEOUT_SECS, state);
+                self.set_status(job.id, OnchainJobStatus::Failed, tx_hash, Some(&error)).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn drive_direct(&self, job: &OnchainJob) -> Result<()> {
+        let hash = job.tx_hash.as_deref().context("Direct job without tx hash")?;
+
+        if let Some(receipt) = self.polygon_tx.get_receipt(hash).await? {
+            return if receipt.success {
+                info!("[OnchainJobs] #{} {} mined in block {}", job.id, job.description, receipt.block_number);
+                self.set_status(job.id, OnchainJobStatus::Mined, Some(hash), None).await
+            } else {
+                let error = format!("Reverted in block {}", receipt.block_number);
+                self.set_status(job.id, OnchainJobStatus::Failed, Some(hash), Some(&error)).await
+            };
+        }
+
+        let pending_secs = (Utc::now() - job.updated_at).num_seconds();
+        if age_secs(job) > DIRECT_TIMEOUT_SECS {
+            let error = format!("Not mined within {}s", DIRECT_TIMEOUT_SECS);
+            return self.set_status(job.id, OnchainJobStatus::Failed, None, Some(&error)).await;
+        }
+        if pending_secs > STUCK_AFTER_SECS && self.replacement_depth(job).await? < MAX_REPLACEMENTS {
+            self.speed_up(job, hash).await?;
+        }
+        Ok(())
+    }
+
+    /// Re-broadcast a stuck direct transaction with bumped fees as a new job
+    async fn speed_up(&self, job: &OnchainJob, hash: &str) -> Result<()> {
+        let Some(signer) = self.key_store.signer(&job.wallet_address).await else {
+            debug!("[OnchainJobs] #{} stuck but wallet is locked, waiting", job.id);
+            return Ok(());
+        };
+        let payload: serde_json::Value =
+            serde_json::from_str(job.payload.as_deref().unwrap_or_default()).context("Missing tx payload")?;
+
+        let pending = PendingTx {
+            from: signer.address(),
+            tx: Eip1559Tx::from_json(&payload)?,
+            hash: hash.to_string(),
+            hashes: vec![hash.to_string()],
+            sent_at: Instant::now(),
+        };
+        let replaced = self.polygon_tx.speed_up(&signer, &pending).await?;
+
+        self.set_status(job.id, OnchainJobStatus::Replaced, None, None).await?;
+        let new_id = self
+            .db.onchain_jobs()
+            .create_onchain_job(
+                &job.wallet_address,
+                job.kind,
+                OnchainJobStatus::Queued,
+                &job.description,
+                job.reference.as_deref(),
+                Some(job.id),
+            )
+            .await?;
+        self.submitted_tx(new_id, &replaced).await?;
+        info!("[OnchainJobs] #{} sped up as #{} ({})", job.id, new_id, replaced.hash);
+        Ok(())
+    }
+
+    /// A replaced transaction can still be mined instead of its replacement
+    async fn drive_replaced(&self, job: &OnchainJob) -> Result<()> {
+        let Some(hash) = job.tx_hash.as_deref() else { return Ok(()) };
+        let Some(receipt) = self.polygon_tx.get_receipt(hash).await? else { return Ok(()) };
+
+        let (status, error) = if receipt.success {
+            (OnchainJobStatus::Mined, None)
+        } else {
+            (OnchainJobStatus::Failed, Some(format!("Reverted in block {}", receipt.block_number)))
+        };
+        self.set_status(job.id, status, Some(hash), error.as_deref()).await?;
+
+        // Every later version of this nonce is now dead
+        let superseded = format!("Superseded: job #{} was mined first", job.id);
+        let mut next = self.db.onchain_jobs().get_onchain_job_replacement(job.id).await?;
+        while let Some(successor) = next {
+            if !successor.status.is_terminal() {
+                self.set_status(successor.id, OnchainJobStatus::Failed, None, Some(&superseded)).await?;
+            }
+            next = self.db.onchain_jobs().get_onchain_job_replacement(successor.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Number of speed-ups already made for this job's original transaction
+    async fn replacement_depth(&self, job: &OnchainJob) -> Result<usize> {
+        let mut depth = 0;
+        let mut parent = job.replaces_job_id;
+        while let Some(id) = parent {
+            depth += 1;
+            parent = self.db.onchain_jobs().get_onchain_job(id).await?.and_then(|j| j.replaces_job_id);
+        }
+        Ok(depth)
+    }
+
+    async fn set_status(
+        &self,
+        id: i64,
+        status: OnchainJobStatus,
+        tx_hash: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.db.onchain_jobs().update_onchain_job_status(id, status, tx_hash, error).await?;
+        self.publish(id).await;
+        Ok(())
+    }
+
+    async fn publish(&self, id: i64) {
+        if let Ok(Some(job)) = self.db.onchain_jobs().get_onchain_job(id).await {
+            let _ = self.update_tx.send(job);
+        }
+    }
+}
+
+/// Seconds since the job was created
+fn age_secs(job: &OnchainJob) -> i64 {
+    (Utc::now() - job.created_at).num_seconds()
+}
+
+/// Outcome of a job and its replacements (oldest first). A mined version wins,
+/// even when a later one was failed as superseded; otherwise the chain has
+/// failed once nothing is pending and no replaced version is still watched.
+fn chain_outcome(chain: &[OnchainJob], now: DateTime<Utc>) -> Option<&OnchainJob> {
+    if let Some(mined) = chain.iter().find(|j| j.status == OnchainJobStatus::Mined) {
+        return Some(mined);
+    }
+    let live = chain.iter().any(|j| match j.status {
+        OnchainJobStatus::Queued | OnchainJobStatus::Submitted => true,
+        OnchainJobStatus::Replaced => (now - j.created_at).num_seconds() <= REPLACED_WATCH_SECS,
+        OnchainJobStatus::Mined | OnchainJobStatus::Failed => false,
+    });
+    if live {
+        return None;
+    }
+    chain.last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OnchainJobKind;
+
+    fn job(id: i64, status: OnchainJobStatus, replaces: Option<i64>, age_secs: i64) -> OnchainJob {
+        let created_at = Utc::now() - chrono::Duration::seconds(age_secs);
+        OnchainJob {
+            id,
+            wallet_address: "0xabc".to_string(),
+            kind: OnchainJobKind::Deposit,
+            status,
+            description: "Deposit 100 USDC".to_string(),
+            reference: None,
+            relay_tx_id: None,
+            tx_hash: None,
+            payload: None,
+            replaces_job_id: replaces,
+            error: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_chain_outcome_pending() {
+        let now = Utc::now();
+        assert!(chain_outcome(&[job(1, OnchainJobStatus::Submitted, None, 10)], now).is_none());
+        let chain = [job(1, OnchainJobStatus::Replaced, None, 120), job(2, OnchainJobStatus::Failed, Some(1), 60)];
+        // The replaced original can still be mined
+        assert!(chain_outcome(&chain, now).is_none());
+    }
+
+    #[test]
+    fn test_chain_outcome_original_mined_over_superseded_successor() {
+        let chain = [job(1, OnchainJobStatus::Mined, None, 120), job(2, OnchainJobStatus::Failed, Some(1), 60)];
+        assert_eq!(chain_outcome(&chain, Utc::now()).unwrap().id, 1);
+    }
+
+    #[test]
+    fn test_chain_outcome_failed() {
+        let now = Utc::now();
+        assert_eq!(chain_outcome(&[job(1, OnchainJobStatus::Failed, None, 10)], now).unwrap().id, 1);
+        let chain = [
+            job(1, OnchainJobStatus::Replaced, None, REPLACED_WATCH_SECS + 60),
+            job(2, OnchainJobStatus::Failed, Some(1), 60),
+        ];
+        assert_eq!(chain_outcome(&chain, now).unwrap().id, 2);
+    }
+}
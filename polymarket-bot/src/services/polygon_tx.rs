@@ -0,0 +1,550 @@
+//! Polygon Transactions - EIP-1559 build, sign, send and track
+//!
+//! Direct EOA transactions (deposits, gas-paid fallbacks) go through here
+//! instead of hand-rolled JSON-RPC in each route:
+//! - Gas limit from `eth_estimateGas` plus a safety buffer
+//! - Fees from the latest base fee and `eth_maxPriorityFeePerGas`, floored at
+//!   Polygon's minimum tip
+//! - Nonces reserved per sender so concurrent requests don't collide
+//! - Receipts awaited with a configurable number of confirmations
+//! - Transactions pending longer than `stuck_after` are replaced with bumped
+//!   fees (speed-up), or cancelled with a zero-value self-transfer
+
+use alloy::consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+use alloy::eips::eip2718::Encodable2718;
+use alloy::primitives::{Address, Bytes, Signature, TxKind, B256, U256};
+use alloy::signers::Signer;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use super::signer::WalletSigner;
+
+/// Polygon mainnet chain ID
+pub const POLYGON_CHAIN_ID: u64 = 137;
+/// Polygon validators reject tips below 25 gwei; stay a little above it
+const MIN_PRIORITY_FEE_WEI: u128 = 30_000_000_000;
+/// Gas for a plain value transfer (used by cancellations)
+const TRANSFER_GAS: u64 = 21_000;
+/// Headroom added on top of `eth_estimateGas`
+const GAS_LIMIT_BUFFER_PCT: u64 = 20;
+/// Nodes require at least a 10% bump to accept a replacement
+const REPLACEMENT_BUMP_PCT: u128 = 15;
+/// Receipt poll interval
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// An unsigned EIP-1559 (type 2) transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip1559Tx {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Vec<u8>,
+}
+
+impl Eip1559Tx {
+    /// The alloy consensus transaction (empty access list)
+    fn to_consensus(&self) -> TxEip1559 {
+        TxEip1559 {
+            chain_id: self.chain_id,
+            nonce: self.nonce,
+            gas_limit: self.gas_limit,
+            max_fee_per_gas: self.max_fee_per_gas,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+            to: TxKind::Call(self.to),
+            value: self.value,
+            access_list: Default::default(),
+            input: Bytes::copy_from_slice(&self.data),
+        }
+    }
+
+    /// keccak256(0x02 || rlp([chainId, nonce, tip, maxFee, gas, to, value, data, accessList]))
+    pub fn signing_hash(&self) -> B256 {
+        self.to_consensus().signature_hash()
+    }
+
+    /// Serialize for persistence (quantities as decimal strings, data as hex)
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "chain_id": self.chain_id,
+            "nonce": self.nonce,
+            "max_priority_fee_per_gas": self.max_priority_fee_per_gas.to_string(),
+            "max_fee_per_gas": self.max_fee_per_gas.to_string(),
+            "gas_limit": self.gas_limit,
+            "to": format!("{:?}", self.to),
+            "value": self.value.to_string(),
+            "data": format!("0x{}", hex::encode(&self.data)),
+        })
+    }
+
+    /// Inverse of `to_json`
+    pub fn from_json(v: &serde_json::Value) -> Result<Self> {
+        let str_field = |name: &str| v[name].as_str().with_context(|| format!("Missing tx field {}", name));
+        let u64_field = |name: &str| v[name].as_u64().with_context(|| format!("Missing tx field {}", name));
+
+        Ok(Self {
+            chain_id: u64_field("chain_id")?,
+            nonce: u64_field("nonce")?,
+            max_priority_fee_per_gas: str_field("max_priority_fee_per_gas")?.parse()?,
+            max_fee_per_gas: str_field("max_fee_per_gas")?.parse()?,
+            gas_limit: u64_field("gas_limit")?,
+            to: str_field("to")?.parse()?,
+            value: U256::from_str_radix(str_field("value")?, 10)?,
+            data: hex::decode(str_field("data")?.trim_start_matches("0x"))?,
+        })
+    }
+
+    /// Raw signed transaction: 0x02 || rlp([..fields, yParity, r, s])
+    pub fn encode_signed(&self, signature: Signature) -> Vec<u8> {
+        TxEnvelope::from(self.to_consensus().into_signed(signature)).encoded_2718()
+    }
+}
+
+/// EIP-1559 fee caps in wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeEstimate {
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+}
+
+impl FeeEstimate {
+    /// Fee caps from the current base fee: tip floored at the Polygon minimum,
+    /// max fee covering two full blocks of base fee growth.
+    pub fn from_base_fee(base_fee: u128, suggested_tip: u128) -> Self {
+        let tip = suggested_tip.max(MIN_PRIORITY_FEE_WEI);
+        Self {
+            max_priority_fee_per_gas: tip,
+            max_fee_per_gas: base_fee.saturating_mul(2).saturating_add(tip),
+        }
+    }
+
+    /// Fees for a replacement: both caps bumped past the node's minimum,
+    /// and never below what the network currently asks for.
+    pub fn replacement(&self, current: &FeeEstimate) -> Self {
+        let bump = |v: u128| v.saturating_mul(100 + REPLACEMENT_BUMP_PCT) / 100 + 1;
+        let tip = bump(self.max_priority_fee_per_gas).max(current.max_priority_fee_per_gas);
+        Self {
+            max_priority_fee_per_gas: tip,
+            max_fee_per_gas: bump(self.max_fee_per_gas).max(current.max_fee_per_gas).max(tip),
+        }
+    }
+}
+
+/// A broadcast transaction that may not be mined yet
+#[derive(Debug, Clone)]
+pub struct PendingTx {
+    pub from: Address,
+    pub tx: Eip1559Tx,
+    /// Hash of the latest broadcast version
+    pub hash: String,
+    /// Hashes of every version broadcast for this nonce, oldest first.
+    /// Any of them may be the one that gets mined.
+    pub hashes: Vec<String>,
+    pub sent_at: Instant,
+}
+
+/// A mined transaction receipt
+#[derive(Debug, Clone)]
+pub struct TxReceipt {
+    pub hash: String,
+    pub block_number: u64,
+    pub gas_used: u64,
+    /// False if the transaction reverted
+    pub success: bool,
+}
+
+/// How long to wait and when to replace a pending transaction
+#[derive(Debug, Clone)]
+pub struct ConfirmOptions {
+    /// Blocks on top of the inclusion block before a receipt counts (1 = just mined)
+    pub confirmations: u64,
+    /// Replace with bumped fees if not mined within this long
+    pub stuck_after: Duration,
+    /// Maximum number of speed-up replacements
+    pub max_replacements: u32,
+    /// Give up waiting after this long (the transaction may still be mined later)
+    pub timeout: Duration,
+}
+
+impl Default for ConfirmOptions {
+    fn default() -> Self {
+        Self {
+            confirmations: 3,
+            stuck_after: Duration::from_secs(60),
+            max_replacements: 3,
+            timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Builds, signs and tracks EIP-1559 transactions on Polygon
+pub struct PolygonTxService {
+    client: reqwest::Client,
+    rpc_url: String,
+    /// Next nonce to hand out per sender; resynced from the node when it runs ahead
+    nonces: Mutex<HashMap<Address, u64>>,
+}
+
+impl PolygonTxService {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("Failed to create HTTP client"),
+            rpc_url,
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Estimate the gas limit for a call, with a safety buffer.
+    /// Fails if the call would revert (e.g. insufficient token balance).
+    pub async fn estimate_gas(&self, from: Address, to: Address, value: U256, data: &[u8]) -> Result<u64> {
+        let result = self
+            .rpc(
+                "eth_estimateGas",
+                serde_json::json!([{
+                    "from": format!("{:?}", from),
+                    "to": format!("{:?}", to),
+                    "value": format!("{:#x}", value),
+                    "data": format!("0x{}", hex::encode(data)),
+                }]),
+            )
+            .await
+            .context("Gas estimation failed")?;
+        let estimate = parse_quantity(&result)? as u64;
+        Ok(estimate + estimate * GAS_LIMIT_BUFFER_PCT / 100)
+    }
+
+    /// Current fee caps from the latest block's base fee and the node's suggested tip
+    pub async fn estimate_fees(&self) -> Result<FeeEstimate> {
+        let block = self
+            .rpc("eth_getBlockByNumber", serde_json::json!(["latest", false]))
+            .await?;
+        let base_fee = parse_quantity(&block["baseFeePerGas"])
+            .context("Latest block has no baseFeePerGas")?;
+
+        let tip = match self.rpc("eth_maxPriorityFeePerGas", serde_json::json!([])).await {
+            Ok(v) => parse_quantity(&v).unwrap_or(MIN_PRIORITY_FEE_WEI),
+            Err(e) => {
+                debug!("[PolygonTx] eth_maxPriorityFeePerGas unavailable ({}), using floor", e);
+                MIN_PRIORITY_FEE_WEI
+            }
+        };
+
+        Ok(FeeEstimate::from_base_fee(base_fee, tip))
+    }
+
+    /// Estimate, sign and broadcast a transaction from `signer`
+    pub async fn send(
+        &self,
+        signer: &WalletSigner,
+        to: Address,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<PendingTx> {
+        let from = signer.address();
+        let gas_limit = self.estimate_gas(from, to, value, &data).await?;
+        let fees = self.estimate_fees().await?;
+        let nonce = self.reserve_nonce(from).await?;
+
+        let tx = Eip1559Tx {
+            chain_id: POLYGON_CHAIN_ID,
+            nonce,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            gas_limit,
+            to,
+            value,
+            data,
+        };
+
+        match self.sign_and_broadcast(signer, &tx).await {
+            Ok(hash) => {
+                info!("[PolygonTx] Sent {} from {:?} (nonce {}, gas {})", hash, from, nonce, gas_limit);
+                Ok(PendingTx { from, tx, hash: hash.clone(), hashes: vec![hash], sent_at: Instant::now() })
+            }
+            Err(e) => {
+                // The nonce was never used; resync from the node next time
+                self.nonces.lock().await.remove(&from);
+                Err(e)
+            }
+        }
+    }
+
+    /// Re-broadcast a pending transaction with the same nonce and bumped fees
+    pub async fn speed_up(&self, signer: &WalletSigner, pending: &PendingTx) -> Result<PendingTx> {
+        let mut tx = pending.tx.clone();
+        self.replace(signer, pending, &mut tx).await
+    }
+
+    /// Replace a pending transaction with a zero-value self-transfer at the same nonce
+    pub async fn cancel(&self, signer: &WalletSigner, pending: &PendingTx) -> Result<PendingTx> {
+        let mut tx = Eip1559Tx {
+            gas_limit: TRANSFER_GAS,
+            to: pending.from,
+            value: U256::ZERO,
+            data: Vec::new(),
+            ..pending.tx.clone()
+        };
+        self.replace(signer, pending, &mut tx).await
+    }
+
+    async fn replace(&self, signer: &WalletSigner, pending: &PendingTx, tx: &mut Eip1559Tx) -> Result<PendingTx> {
+        let current = self.estimate_fees().await?;
+        let previous = FeeEstimate {
+            max_priority_fee_per_gas: pending.tx.max_priority_fee_per_gas,
+            max_fee_per_gas: pending.tx.max_fee_per_gas,
+        };
+        let fees = previous.replacement(&current);
+        tx.max_priority_fee_per_gas = fees.max_priority_fee_per_gas;
+        tx.max_fee_per_gas = fees.max_fee_per_gas;
+
+        let hash = self.sign_and_broadcast(signer, tx).await?;
+        info!(
+            "[PolygonTx] Replaced {} with {} (nonce {}, tip {} -> {} wei)",
+            pending.hash, hash, tx.nonce, previous.max_priority_fee_per_gas, fees.max_priority_fee_per_gas
+        );
+
+        let mut hashes = pending.hashes.clone();
+        hashes.push(hash.clone());
+        Ok(PendingTx { from: pending.from, tx: tx.clone(), hash, hashes, sent_at: Instant::now() })
+    }
+
+    /// Wait for a pending transaction to be mined with `confirmations`, speeding it up
+    /// whenever it stays pending longer than `stuck_after`.
+    ///
+    /// Returns the receipt of whichever version was mined.
+    pub async fn confirm(
+        &self,
+        signer: &WalletSigner,
+        mut pending: PendingTx,
+        opts: &ConfirmOptions,
+    ) -> Result<TxReceipt> {
+        let started = Instant::now();
+        let mut replacements = 0;
+
+        loop {
+            for hash in pending.hashes.iter().rev() {
+                if let Some(receipt) = self.get_receipt(hash).await? {
+                    if self.wait_confirmations(&receipt, opts.confirmations).await? {
+                        return Ok(receipt);
+                    }
+                }
+            }
+
+            if started.elapsed() >= opts.timeout {
+                anyhow::bail!("Transaction {} not confirmed after {:?}", pending.hash, opts.timeout);
+            }
+
+            if pending.sent_at.elapsed() >= opts.stuck_after && replacements < opts.max_replacements {
+                warn!("[PolygonTx] {} pending for {:?}, speeding up", pending.hash, pending.sent_at.elapsed());
+                match self.speed_up(signer, &pending).await {
+                    Ok(replaced) => {
+                        pending = replaced;
+                        replacements += 1;
+                    }
+                    // "nonce too low" means one of the versions just got mined
+                    Err(e) => debug!("[PolygonTx] Speed-up of {} failed: {}", pending.hash, e),
+                }
+            }
+
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Wait for a single hash to be mined with `confirmations`, without replacement
+    pub async fn wait_for_receipt(&self, hash: &str, confirmations: u64, timeout: Duration) -> Result<Option<TxReceipt>> {
+        let started = Instant::now();
+        while started.elapsed() < timeout {
+            if let Some(receipt) = self.get_receipt(hash).await? {
+                if self.wait_confirmations(&receipt, confirmations).await? {
+                    return Ok(Some(receipt));
+                }
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+        Ok(None)
+    }
+
+    /// True once the chain head is `confirmations - 1` blocks past the receipt's block
+    async fn wait_confirmations(&self, receipt: &TxReceipt, confirmations: u64) -> Result<bool> {
+        if confirmations <= 1 {
+            return Ok(true);
+        }
+        let head = parse_quantity(&self.rpc("eth_blockNumber", serde_json::json!([])).await?)? as u64;
+        Ok(head + 1 >= receipt.block_number + confirmations)
+    }
+
+    /// Receipt for a hash, or None if it isn't mined yet
+    pub async fn get_receipt(&self, hash: &str) -> Result<Option<TxReceipt>> {
+        let result = self
+            .rpc("eth_getTransactionReceipt", serde_json::json!([hash]))
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(TxReceipt {
+            hash: hash.to_string(),
+            block_number: parse_quantity(&result["blockNumber"])? as u64,
+            gas_used: parse_quantity(&result["gasUsed"]).unwrap_or(0) as u64,
+            success: result["status"].as_str() == Some("0x1"),
+        }))
+    }
+
+    /// Hand out the next nonce for `from`, never reusing one that's still in flight
+    async fn reserve_nonce(&self, from: Address) -> Result<u64> {
+        let mut nonces = self.nonces.lock().await;
+        let chain = parse_quantity(
+            &self
+                .rpc("eth_getTransactionCount", serde_json::json!([format!("{:?}", from), "pending"]))
+                .await?,
+        )? as u64;
+        let nonce = next_nonce(nonces.get(&from).copied(), chain);
+        nonces.insert(from, nonce + 1);
+        Ok(nonce)
+    }
+
+    async fn sign_and_broadcast(&self, signer: &WalletSigner, tx: &Eip1559Tx) -> Result<String> {
+        let sig = signer
+            .sign_hash(&tx.signing_hash())
+            .await
+            .context("Failed to sign transaction")?;
+
+        let raw = tx.encode_signed(sig);
+        let result = self
+            .rpc("eth_sendRawTransaction", serde_json::json!([format!("0x{}", hex::encode(&raw))]))
+            .await?;
+
+        result
+            .as_str()
+            .map(str::to_string)
+            .context("eth_sendRawTransaction returned no hash")
+    }
+
+    async fn rpc(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": 1
+        });
+
+        let json: serde_json::Value = self
+            .client
+            .post(&self.rpc_url)
+            .json(&payload)
+            .send()
+            .await
+            .with_context(|| format!("Polygon RPC {} failed", method))?
+            .json()
+            .await
+            .context("Failed to parse RPC response")?;
+
+        if let Some(error) = json.get("error") {
+            let msg = error.get("message").and_then(|v| v.as_str()).unwrap_or("unknown error");
+            anyhow::bail!("RPC {} error: {}", method, msg);
+        }
+        Ok(json["result"].clone())
+    }
+}
+
+/// Next nonce given our cached counter and the node's pending count.
+/// The node wins if it is ahead (external sends); ours wins while our
+/// own transactions haven't reached its mempool yet.
+fn next_nonce(cached: Option<u64>, chain_pending: u64) -> u64 {
+    cached.map_or(chain_pending, |c| c.max(chain_pending))
+}
+
+/// Parse a hex JSON-RPC quantity ("0x1a")
+fn parse_quantity(v: &serde_json::Value) -> Result<u128> {
+    let s = v.as_str().context("Expected hex quantity")?;
+    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+        .with_context(|| format!("Invalid hex quantity: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tx() -> Eip1559Tx {
+        Eip1559Tx {
+            chain_id: POLYGON_CHAIN_ID,
+            nonce: 7,
+            max_priority_fee_per_gas: 30_000_000_000,
+            max_fee_per_gas: 100_000_000_000,
+            gas_limit: 60_000,
+            to: "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174".parse().unwrap(),
+            value: U256::from(1_000u64),
+            data: hex::decode("a9059cbb").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_signed_tx_recovers_sender() {
+        use alloy::consensus::Transaction;
+        use alloy::eips::eip2718::Decodable2718;
+        use alloy::signers::local::PrivateKeySigner;
+        use alloy::signers::SignerSync;
+
+        // Hardhat/Anvil account #0
+        let key: PrivateKeySigner = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            .parse()
+            .unwrap();
+        let tx = sample_tx();
+        let sig = key.sign_hash_sync(&tx.signing_hash()).unwrap();
+        let raw = tx.encode_signed(sig);
+        assert_eq!(raw[0], 0x02);
+
+        let TxEnvelope::Eip1559(signed) = TxEnvelope::decode_2718(&mut raw.as_slice()).unwrap() else {
+            panic!("not an EIP-1559 envelope");
+        };
+        assert_eq!(signed.tx().chain_id(), Some(POLYGON_CHAIN_ID));
+        assert_eq!(signed.tx().nonce(), 7);
+        assert_eq!(signed.tx().to(), Some(tx.to));
+        assert_eq!(signed.tx().input().as_ref(), tx.data.as_slice());
+        assert_eq!(
+            signed.signature().recover_address_from_prehash(&tx.signing_hash()).unwrap(),
+            key.address()
+        );
+    }
+
+    #[test]
+    fn test_tx_json_roundtrip() {
+        let tx = sample_tx();
+        assert_eq!(Eip1559Tx::from_json(&tx.to_json()).unwrap(), tx);
+        assert_ne!(tx.signing_hash(), Eip1559Tx { nonce: 8, ..tx.clone() }.signing_hash());
+    }
+
+    #[test]
+    fn test_fee_floor_and_replacement_bump() {
+        let fees = FeeEstimate::from_base_fee(50, 1);
+        assert_eq!(fees.max_priority_fee_per_gas, MIN_PRIORITY_FEE_WEI);
+        assert_eq!(fees.max_fee_per_gas, 100 + MIN_PRIORITY_FEE_WEI);
+
+        let replaced = fees.replacement(&fees);
+        assert!(replaced.max_priority_fee_per_gas * 100 >= fees.max_priority_fee_per_gas * 110);
+        assert!(replaced.max_fee_per_gas * 100 >= fees.max_fee_per_gas * 110);
+
+        // A base fee spike overrides the bump
+        let spiked = FeeEstimate::from_base_fee(1_000_000_000_000, 0);
+        assert_eq!(fees.replacement(&spiked).max_fee_per_gas, spiked.max_fee_per_gas);
+    }
+
+    #[test]
+    fn test_next_nonce() {
+        assert_eq!(next_nonce(None, 5), 5);
+        // Our own sends not yet visible to the node
+        assert_eq!(next_nonce(Some(7), 5), 7);
+        // Someone else sent from this key
+        assert_eq!(next_nonce(Some(5), 9), 9);
+    }
+}
@@ -0,0 +1,58 @@
+//! Latest token prices from the price WebSocket
+//!
+//! Shared by everything that marks holdings to market (ledger summaries,
+//! reports). The mark is the best bid when the feed has one — what the tokens
+//! could be sold for right now — and the last trade price otherwise.
+
+use crate::services::PriceUpdate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tracing::debug;
+
+/// Token id -> latest mark
+#[derive(Default)]
+pub struct PriceCache {
+    marks: RwLock<HashMap<String, Decimal>>,
+}
+
+impl PriceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record price updates until the feed closes
+    pub async fn run(self: Arc<Self>, mut price_rx: broadcast::Receiver<PriceUpdate>) {
+        loop {
+            match price_rx.recv().await {
+                Ok(update) => self.record(&update).await,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    debug!("Price cache lagged {} messages", n);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    pub async fn record(&self, update: &PriceUpdate) {
+        let mark = update
+            .best_bid
+            .as_deref()
+            .and_then(|b| Decimal::from_str(b).ok())
+            .or_else(|| Decimal::from_str(&update.price).ok());
+        if let Some(mark) = mark {
+            self.marks.write().await.insert(update.token_id.clone(), mark);
+        }
+    }
+
+    pub async fn mark(&self, token_id: &str) -> Option<Decimal> {
+        self.marks.read().await.get(token_id).copied()
+    }
+
+    /// Copy of every known mark
+    pub async fn snapshot(&self) -> HashMap<String, Decimal> {
+        self.marks.read().await.clone()
+    }
+}
@@ -0,0 +1,521 @@
+//! Performance reports - daily and weekly per-wallet summaries
+//!
+//! Shortly after each UTC day (and each Monday-to-Sunday week) ends, a report is
+//! generated for every wallet with live activity in the period and stored in
+//! the `reports` table. Wallets whose notification channels subscribe to
+//! `daily_pnl` / `weekly_report` also get a summary message.
+//!
+//! Realized figures cover positions closed, Mint Maker pairs merged and orphans
+//! resolved inside the period. Unrealized P&L and exposure are a snapshot of the
+//! open positions at generation time, marked with the shared price cache;
+//! positions without a price are valued at entry.
+
+use crate::db::{Database, MintMakerPairRow};
+use crate::services::notifications::{Notification, NotificationKind, Notifier};
+use crate::services::PriceCache;
+use crate::types::Position;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// How often the service checks whether a period has ended
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Report period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    Daily,
+    /// Monday to Sunday (UTC)
+    Weekly,
+}
+
+impl ReportPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportPeriod::Daily => "daily",
+            ReportPeriod::Weekly => "weekly",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "daily" => Some(ReportPeriod::Daily),
+            "weekly" => Some(ReportPeriod::Weekly),
+            _ => None,
+        }
+    }
+
+    pub fn days(&self) -> i64 {
+        match self {
+            ReportPeriod::Daily => 1,
+            ReportPeriod::Weekly => 7,
+        }
+    }
+
+    /// First day of the period containing `date`
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            ReportPeriod::Daily => date,
+            ReportPeriod::Weekly => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    /// First day of the latest period that has fully ended by `today`
+    pub fn last_complete(&self, today: NaiveDate) -> NaiveDate {
+        self.start_of(today) - Duration::days(self.days())
+    }
+
+    /// [start, end) timestamps of the period beginning on `start`
+    pub fn bounds(&self, start: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let from = start.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        (from, from + Duration::days(self.days()))
+    }
+
+    fn notification_kind(&self) -> NotificationKind {
+        match self {
+            ReportPeriod::Daily => NotificationKind::DailyPnl,
+            ReportPeriod::Weekly => NotificationKind::WeeklyReport,
+        }
+    }
+}
+
+/// Realized results of one strategy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyReport {
+    pub strategy: String,
+    pub trades: i64,
+    pub wins: i64,
+    pub realized_pnl: Decimal,
+    pub fees: Decimal,
+}
+
+/// A single closed trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeSummary {
+    pub strategy: String,
+    pub question: String,
+    pub pnl: Decimal,
+    pub closed_at: DateTime<Utc>,
+}
+
+/// Mint Maker pairs orphaned during the period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrphanReport {
+    pub count: i64,
+    /// Cost of the filled side of each orphaned pair
+    pub cost: Decimal,
+    /// P&L of orphans that resolved during the period
+    pub resolved_pnl: Decimal,
+}
+
+/// Open positions at generation time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExposureReport {
+    pub open_positions: i64,
+    pub cost_basis: Decimal,
+    pub market_value: Decimal,
+    /// Positions with no price seen on the feed (valued at entry)
+    pub unpriced_positions: i64,
+}
+
+/// Per-wallet performance report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub wallet_address: String,
+    pub period: ReportPeriod,
+    pub period_start: NaiveDate,
+    /// Last day of the period (inclusive)
+    pub period_end: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+    /// Closed trades plus resolved orphans
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub fees: Decimal,
+    pub trades: i64,
+    pub wins: i64,
+    pub losses: i64,
+    /// Fraction of closed trades with positive P&L (0.0 - 1.0)
+    pub win_rate: f64,
+    pub strategies: Vec<StrategyReport>,
+    pub best_trade: Option<TradeSummary>,
+    pub worst_trade: Option<TradeSummary>,
+    pub orphans: OrphanReport,
+    pub exposure: ExposureReport,
+}
+
+/// A closed position or merged Mint Maker pair, normalized for reporting
+#[derive(Debug, Clone)]
+struct ClosedTrade {
+    strategy: String,
+    question: String,
+    pnl: Decimal,
+    fee: Decimal,
+    closed_at: DateTime<Utc>,
+}
+
+impl ClosedTrade {
+    fn from_position(position: &Position) -> Self {
+        Self {
+            strategy: position.strategy.to_string(),
+            question: position.question.clone(),
+            pnl: position.pnl.or(position.realized_pnl).unwrap_or_default(),
+            fee: position.fee_paid.unwrap_or_default(),
+            closed_at: position.closed_at.unwrap_or(position.opened_at),
+        }
+    }
+
+    fn from_pair(pair: &MintMakerPairRow) -> Option<Self> {
+        Some(Self {
+            strategy: "MintMaker".to_string(),
+            question: pair.question.clone(),
+            pnl: pair.profit.as_deref().and_then(|p| Decimal::from_str(p).ok())?,
+            fee: Decimal::ZERO,
+            closed_at: DateTime::parse_from_rfc3339(&pair.updated_at).ok()?.with_timezone(&Utc),
+        })
+    }
+
+    fn summary(&self) -> TradeSummary {
+        TradeSummary {
+            strategy: self.strategy.clone(),
+            question: self.question.clone(),
+            pnl: self.pnl,
+            closed_at: self.closed_at,
+        }
+    }
+}
+
+impl Report {
+    /// Assemble a report from a period's closed trades, orphans and the current open positions
+    fn build(
+        wallet_address: &str,
+        period: ReportPeriod,
+        period_start: NaiveDate,
+        closed: &[ClosedTrade],
+        orphans: OrphanReport,
+        open: &[Position],
+        prices: &HashMap<String, Decimal>,
+    ) -> Self {
+        let mut strategies: Vec<StrategyReport> = Vec::new();
+        for trade in closed {
+            let index = match strategies.iter().position(|s| s.strategy == trade.strategy) {
+                Some(index) => index,
+                None => {
+                    strategies.push(StrategyReport {
+                        strategy: trade.strategy.clone(),
+                        ..Default::default()
+                    });
+                    strategies.len() - 1
+                }
+            };
+            let entry = &mut strategies[index];
+            entry.trades += 1;
+            if trade.pnl > Decimal::ZERO {
+                entry.wins += 1;
+            }
+            entry.realized_pnl += trade.pnl;
+            entry.fees += trade.fee;
+        }
+        strategies.sort_by(|a, b| a.strategy.cmp(&b.strategy));
+
+        let trades = closed.len() as i64;
+        let wins = closed.iter().filter(|t| t.pnl > Decimal::ZERO).count() as i64;
+        let losses = closed.iter().filter(|t| t.pnl < Decimal::ZERO).count() as i64;
+        let best_trade = closed.iter().max_by_key(|t| t.pnl).map(ClosedTrade::summary);
+        let worst_trade = closed
+            .iter()
+            .min_by_key(|t| t.pnl)
+            .filter(|t| t.pnl < Decimal::ZERO)
+            .map(ClosedTrade::summary);
+
+        let mut exposure = ExposureReport::default();
+        let mut unrealized_pnl = Decimal::ZERO;
+        for position in open {
+            let shares = match position.remaining_size {
+                Some(shares) => shares,
+                None if position.entry_price > Decimal::ZERO => position.size / position.entry_price,
+                None => Decimal::ZERO,
+            };
+            let cost = shares * position.entry_price;
+            let price = position.token_id.as_ref().and_then(|t| prices.get(t)).copied();
+            if price.is_none() {
+                exposure.unpriced_positions += 1;
+            }
+            let value = shares * price.unwrap_or(position.entry_price);
+
+            exposure.open_positions += 1;
+            exposure.cost_basis += cost;
+            exposure.market_value += value;
+            unrealized_pnl += value - cost;
+        }
+
+        Self {
+            wallet_address: wallet_address.to_lowercase(),
+            period,
+            period_start,
+            period_end: period_start + Duration::days(period.days() - 1),
+            generated_at: Utc::now(),
+            realized_pnl: closed.iter().map(|t| t.pnl).sum::<Decimal>() + orphans.resolved_pnl,
+            unrealized_pnl: unrealized_pnl.round_dp(6),
+            fees: closed.iter().map(|t| t.fee).sum(),
+            trades,
+            wins,
+            losses,
+            win_rate: if trades > 0 { wins as f64 / trades as f64 } else { 0.0 },
+            strategies,
+            best_trade: best_trade.filter(|t| t.pnl > Decimal::ZERO),
+            worst_trade,
+            orphans,
+            exposure,
+        }
+    }
+
+    /// Whether anything happened in the period (empty reports aren't pushed to channels)
+    pub fn has_activity(&self) -> bool {
+        self.trades > 0 || self.orphans.count > 0 || !self.orphans.resolved_pnl.is_zero()
+    }
+
+    /// Summary message for notification channels
+    pub fn to_notification(&self) -> Notification {
+        let title = match self.period {
+            ReportPeriod::Daily => format!("Daily report for {}", self.period_start),
+            ReportPeriod::Weekly => format!("Weekly report for {} - {}", self.period_start, self.period_end),
+        };
+
+        let mut n = Notification::new(self.period.notification_kind(), Some(&self.wallet_address), title)
+            .field("pnl", format!("${:.2}", self.realized_pnl))
+            .field("unrealized_pnl", format!("${:.2}", self.unrealized_pnl))
+            .field("trades", self.trades)
+            .field("win_rate", format!("{:.0}%", self.win_rate * 100.0))
+            .field("fees", format!("${:.2}", self.fees));
+        for strategy in &self.strategies {
+            n = n.field(
+                &format!("{}_pnl", strategy.strategy.to_lowercase()),
+                format!("${:.2} ({} trades)", strategy.realized_pnl, strategy.trades),
+            );
+        }
+        if let Some(best) = &self.best_trade {
+            n = n.field("best_trade", format!("${:.2} {}", best.pnl, best.question));
+        }
+        if let Some(worst) = &self.worst_trade {
+            n = n.field("worst_trade", format!("${:.2} {}", worst.pnl, worst.question));
+        }
+        if self.orphans.count > 0 {
+            n = n.field("orphan_cost", format!("${:.2} ({} pairs)", self.orphans.cost, self.orphans.count));
+        }
+        n.field("exposure", format!("${:.2}", self.exposure.market_value))
+    }
+}
+
+/// Generates, stores and announces reports
+pub struct Reporter {
+    db: Arc<Database>,
+    notifier: Notifier,
+    prices: Arc<PriceCache>,
+}
+
+impl Reporter {
+    pub fn new(db: Arc<Database>, notifier: Notifier, prices: Arc<PriceCache>) -> Self {
+        Self { db, notifier, prices }
+    }
+
+    /// Generate reports as periods end, until `stop` is set
+    pub async fn run(self: Arc<Self>, mut stop: watch::Receiver<bool>) {
+        let mut check = tokio::time::interval(CHECK_INTERVAL);
+        check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // Period start generated last, so the DB isn't asked every minute
+        let mut done: HashMap<ReportPeriod, NaiveDate> = HashMap::new();
+
+        info!("[Reports] Reporter started");
+
+        loop {
+            tokio::select! {
+                _ = check.tick() => {}
+                _ = stop.changed() => break,
+            }
+            let today = Utc::now().date_naive();
+            for period in [ReportPeriod::Daily, ReportPeriod::Weekly] {
+                let start = period.last_complete(today);
+                if done.get(&period) != Some(&start) {
+                    self.generate_due(period, start).await;
+                    done.insert(period, start);
+                }
+            }
+        }
+    }
+
+    /// Generate a finished period's reports for every active wallet that doesn't have a final one yet
+    async fn generate_due(&self, period: ReportPeriod, start: NaiveDate) {
+        let (from, to) = period.bounds(start);
+        let done = match self.db.reports().get_final_report_wallets(period.as_str(), start, &to).await {
+            Ok(done) => done,
+            Err(e) => {
+                warn!("[Reports] Failed to check {} reports for {}: {}", period.as_str(), start, e);
+                return;
+            }
+        };
+        let wallets = match self.db.get_wallets_with_activity_since(&from).await {
+            Ok(wallets) => wallets,
+            Err(e) => {
+                warn!("[Reports] Failed to list active wallets: {}", e);
+                return;
+            }
+        };
+
+        let mut generated = 0;
+        for wallet in wallets.iter().filter(|w| !done.contains(w)) {
+            match self.generate(wallet, period, start).await {
+                Ok((_, report)) => {
+                    generated += 1;
+                    if report.has_activity() {
+                        self.notifier.notify(report.to_notification());
+                    }
+                }
+                Err(e) => warn!("[Reports] Failed to generate {} report for {}: {}", period.as_str(), wallet, e),
+            }
+        }
+        if generated > 0 {
+            info!("[Reports] Generated {} {} reports for {}", generated, period.as_str(), start);
+        }
+    }
+
+    /// Generate (or regenerate) and store one wallet's report; returns its id
+    pub async fn generate(&self, wallet_address: &str, period: ReportPeriod, start: NaiveDate) -> Result<(i64, Report)> {
+        let start = period.start_of(start);
+        let (from, to) = period.bounds(start);
+
+        let mut closed: Vec<ClosedTrade> = self
+            .db
+            .positions().get_closed_positions_between(wallet_address, &from, &to)
+            .await?
+            .iter()
+            .map(ClosedTrade::from_position)
+            .collect();
+        closed.extend(
+            self.db
+                .mint_maker().get_merged_pairs_between(wallet_address, &from, &to)
+                .await?
+                .iter()
+                .filter_map(ClosedTrade::from_pair),
+        );
+
+        let (count, cost, resolved_pnl) = self.db.mint_maker().get_orphan_totals_between(wallet_address, &from, &to).await?;
+        let orphans = OrphanReport {
+            count,
+            cost: Decimal::from_f64_retain(cost).unwrap_or_default().round_dp(6),
+            resolved_pnl: Decimal::from_f64_retain(resolved_pnl).unwrap_or_default().round_dp(6),
+        };
+
+        let open = self.db.positions().get_open_positions_for_wallet(&wallet_address.to_lowercase()).await?;
+        let prices = self.prices.snapshot().await;
+        let report = Report::build(wallet_address, period, start, &closed, orphans, &open, &prices);
+
+        let id = self.db.reports().save_report(&report).await?;
+        Ok((id, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PositionStatus, Side, StrategyType};
+    use rust_decimal_macros::dec;
+
+    fn trade(strategy: &str, pnl: Decimal, fee: Decimal) -> ClosedTrade {
+        ClosedTrade {
+            strategy: strategy.to_string(),
+            question: format!("{} market", strategy),
+            pnl,
+            fee,
+            closed_at: Utc::now(),
+        }
+    }
+
+    fn open_position(token_id: &str, entry_price: Decimal, size: Decimal) -> Position {
+        Position {
+            id: 1,
+            wallet_address: "0xabc".to_string(),
+            market_id: "m".to_string(),
+            question: "q".to_string(),
+            slug: None,
+            side: Side::Yes,
+            entry_price,
+            size,
+            strategy: StrategyType::ResolutionSniper,
+            opened_at: Utc::now(),
+            closed_at: None,
+            exit_price: None,
+            pnl: None,
+            status: PositionStatus::Open,
+            is_paper: false,
+            end_date: None,
+            token_id: Some(token_id.to_string()),
+            order_id: None,
+            remaining_size: None,
+            realized_pnl: None,
+            total_sold_size: None,
+            avg_exit_price: None,
+            neg_risk: false,
+            fee_paid: None,
+        }
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        // 2026-10-14 is a Wednesday
+        let wed = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let mon = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        assert_eq!(ReportPeriod::Weekly.start_of(wed), mon);
+        assert_eq!(ReportPeriod::Weekly.last_complete(wed), mon - Duration::days(7));
+        assert_eq!(ReportPeriod::Weekly.last_complete(mon), mon - Duration::days(7));
+        assert_eq!(ReportPeriod::Daily.last_complete(wed), wed - Duration::days(1));
+
+        let (from, to) = ReportPeriod::Weekly.bounds(mon);
+        assert_eq!(to - from, Duration::days(7));
+        assert_eq!(from.date_naive(), mon);
+    }
+
+    #[test]
+    fn test_build_report() {
+        let closed = vec![
+            trade("Sniper", dec!(5), dec!(0.1)),
+            trade("Sniper", dec!(-2), dec!(0.1)),
+            trade("MintMaker", dec!(1), dec!(0)),
+        ];
+        let orphans = OrphanReport {
+            count: 1,
+            cost: dec!(4),
+            resolved_pnl: dec!(-4),
+        };
+        // 10 shares at 0.50; one priced at 0.60, one unpriced
+        let open = vec![
+            open_position("t1", dec!(0.5), dec!(5)),
+            open_position("t2", dec!(0.5), dec!(5)),
+        ];
+        let prices = HashMap::from([("t1".to_string(), dec!(0.6))]);
+
+        let start = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let report = Report::build("0xABC", ReportPeriod::Weekly, start, &closed, orphans, &open, &prices);
+
+        assert_eq!(report.period_end, NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+        assert_eq!(report.realized_pnl, dec!(0));
+        assert_eq!(report.fees, dec!(0.2));
+        assert_eq!((report.trades, report.wins, report.losses), (3, 2, 1));
+        assert_eq!(report.strategies.len(), 2);
+        assert_eq!(report.strategies[1].strategy, "Sniper");
+        assert_eq!(report.strategies[1].realized_pnl, dec!(3));
+        assert_eq!(report.best_trade.as_ref().unwrap().pnl, dec!(5));
+        assert_eq!(report.worst_trade.as_ref().unwrap().pnl, dec!(-2));
+        assert_eq!(report.unrealized_pnl, dec!(1));
+        assert_eq!(report.exposure.cost_basis, dec!(10));
+        assert_eq!(report.exposure.market_value, dec!(11));
+        assert_eq!(report.exposure.unpriced_positions, 1);
+        assert!(report.has_activity());
+    }
+}
@@ -1,7 +1,7 @@
 //! Resolution Tracker Service
 //! Monitors open positions and updates them when markets resolve
 
-use crate::types::{Position, Side};
+use crate::types::{Position, ResolutionOutcome, Side};
 use crate::Database;
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -9,13 +9,13 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
 /// Market data from Polymarket Gamma API
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GammaMarket {
-    #[allow(dead_code)]
     condition_id: Option<String>,
     #[serde(rename = "questionID")]
     #[allow(dead_code)]
@@ -46,17 +46,19 @@ struct GammaResponse {
 pub struct ResolutionTracker {
     db: Arc<Database>,
     client: reqwest::Client,
+    /// Resolved positions are announced here (the auto-redeemer listens)
+    outcome_tx: broadcast::Sender<ResolutionOutcome>,
 }
 
 impl ResolutionTracker {
-    pub fn new(db: Arc<Database>) -> Self {
+    pub fn new(db: Arc<Database>, outcome_tx: broadcast::Sender<ResolutionOutcome>) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { db, client }
+        Self { db, client, outcome_tx }
     }
 
     /// Start the resolution tracking loop
@@ -74,7 +76,7 @@ impl ResolutionTracker {
 
     /// Check all open positions for resolutions
     pub async fn check_resolutions(&self) -> Result<()> {
-        let open_positions = self.db.get_open_positions().await?;
+        let open_positions = self.db.positions().get_open_positions().await?;
 
         if open_positions.is_empty() {
             debug!("No open positions to check");
@@ -122,7 +124,7 @@ impl ResolutionTracker {
         // If position doesn't have end_date, update it from market data
         if position.end_date.is_none() {
             if let Some(end_date) = self.parse_market_end_date(&market) {
-                if let Err(e) = self.db.update_position_end_date(position.id, end_date).await {
+                if let Err(e) = self.db.positions().update_position_end_date(position.id, end_date).await {
                     warn!("Failed to update end_date for position {}: {}", position.id, e);
                 } else {
                     debug!("Updated end_date for position {} to {}", position.id, end_date);
@@ -202,7 +204,17 @@ impl ResolutionTracker {
         let exit_price = if we_won { Decimal::ONE } else { Decimal::ZERO };
 
         // Update position in database (PnL is calculated inside close_position)
-        self.db.close_position(position.id, exit_price, None).await?;
+        self.db.positions().close_position(position.id, exit_price, None).await?;
+
+        // No receivers just means the redeemer isn't running
+        let _ = self.outcome_tx.send(ResolutionOutcome {
+            position_id: position.id,
+            wallet_address: position.wallet_address.clone(),
+            condition_id: market.condition_id.clone(),
+            side: position.side,
+            won: we_won,
+            resolved_at: Utc::now(),
+        });
 
         Ok(Some((we_won, pnl)))
     }
@@ -3,17 +3,23 @@
 //! via Polymarket's relayer service.
 
 use alloy::primitives::{keccak256, Address, U256};
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use alloy::signers::Signer;
 use anyhow::{Context, Result};
 use base64::Engine;
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 use tracing::info;
 
+use crate::services::onchain_jobs::OnchainJobTracker;
+use crate::services::signer::WalletSigner;
+use crate::types::{OnchainJobKind, OnchainJobStatus};
+
 type HmacSha256 = Hmac<Sha256>;
 
 const RELAY_URL: &str = "https://relayer-v2.polymarket.com";
 const POLYGON_CHAIN_ID: u64 = 137;
+/// How long activation waits on each deploy/approval job before giving up
+const ACTIVATION_WAIT: std::time::Duration = std::time::Duration::from_secs(120);
 
 // Contract addresses on Polygon
 const SAFE_FACTORY: &str = "0xaacFeEa03eb1561C4e67d661e40682Bd20E3541b";
@@ -30,12 +36,31 @@ const SAFE_FACTORY_NAME: &str = "Polymarket Contract Proxy Factory";
 // Max uint256 for unlimited approval
 const MAX_APPROVAL: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
 
+#[derive(Clone)]
 pub struct BuilderCredentials {
     pub api_key: String,
     pub secret: String,
     pub passphrase: String,
 }
 
+impl BuilderCredentials {
+    /// Builder credentials from config, if all three are set
+    pub fn from_config(config: &crate::Config) -> Option<Self> {
+        match (
+            config.builder_api_key.as_ref(),
+            config.builder_secret.as_ref(),
+            config.builder_passphrase.as_ref(),
+        ) {
+            (Some(key), Some(secret), Some(pass)) => Some(Self {
+                api_key: key.clone(),
+                secret: secret.clone(),
+                passphrase: pass.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Create builder auth headers for relay requests
 fn create_builder_headers(
     creds: &BuilderCredentials,
@@ -59,7 +84,7 @@ fn create_builder_headers(
 }
 
 /// Make an authenticated request to the relay
-async fn relay_request(
+pub async fn relay_request(
     client: &reqwest::Client,
     creds: &BuilderCredentials,
     method: &str,
@@ -113,7 +138,7 @@ pub async fn check_safe_deployed(
 
 /// Deploy the Safe proxy via the relay
 pub async fn deploy_safe(
-    signer: &PrivateKeySigner,
+    signer: &WalletSigner,
     client: &reqwest::Client,
     creds: &BuilderCredentials,
     safe_address: &str,
@@ -224,7 +249,9 @@ fn build_set_approval_for_all_data(operator: &Address, approved: bool) -> Vec<u8
 }
 
 /// Encode multiple Safe transactions into a multisend call
-fn encode_multisend(txns: &[(Address, Vec<u8>)]) -> (Address, Vec<u8>, u8) {
+///
+/// Returns (to, data, operation); a single transaction is passed through as a plain call.
+pub fn encode_multisend(txns: &[(Address, Vec<u8>)]) -> (Address, Vec<u8>, u8) {
     let multisend_addr: Address = SAFE_MULTISEND.parse().unwrap();
 
     if txns.len() == 1 {
@@ -266,7 +293,7 @@ fn encode_multisend(txns: &[(Address, Vec<u8>)]) -> (Address, Vec<u8>, u8) {
 
 /// Set all on-chain approvals via Safe transactions through the relay
 pub async fn set_safe_approvals(
-    signer: &PrivateKeySigner,
+    signer: &WalletSigner,
     client: &reqwest::Client,
     creds: &BuilderCredentials,
     safe_address: &str,
@@ -422,7 +449,7 @@ pub async fn set_safe_approvals(
 /// Execute a single Safe transaction (Call) via the relay.
 /// Used for withdrawals (USDC transfer from Safe to EOA).
 pub async fn execute_safe_transaction(
-    signer: &PrivateKeySigner,
+    signer: &WalletSigner,
     client: &reqwest::Client,
     creds: &BuilderCredentials,
     safe_address: &str,
@@ -538,14 +565,19 @@ pub async fn execute_safe_transaction(
 
 /// Ensure the Safe wallet is fully activated (deployed + approvals set)
 /// Returns the Safe proxy address
+///
+/// Deployment and approval transactions are recorded as on-chain jobs, so
+/// activation progress shows up in the job feed like any other relay call.
 pub async fn ensure_safe_activated(
-    signer: &PrivateKeySigner,
+    signer: &WalletSigner,
     builder_creds: &BuilderCredentials,
+    jobs: &OnchainJobTracker,
 ) -> Result<String> {
     let eoa = signer.address();
+    let eoa_str = format!("{:?}", eoa);
 
     // Derive Safe address from EOA
-    let safe_address = crate::services::safe_proxy::derive_safe_wallet(&format!("{:?}", eoa))
+    let safe_address = crate::services::safe_proxy::derive_safe_wallet(&eoa_str)
         .map_err(|e| anyhow::anyhow!("Failed to derive Safe address: {}", e))?;
 
     let client = reqwest::Client::builder()
@@ -558,18 +590,18 @@ pub async fn ensure_safe_activated(
 
     if !is_deployed {
         info!("Safe not deployed for {}, deploying...", safe_address);
-        deploy_safe(signer, &client, builder_creds, &safe_address).await
+        let tx_id = deploy_safe(signer, &client, builder_creds, &safe_address).await
             .context("Failed to deploy Safe")?;
-
-        // Poll for deployment
-        for i in 0..30 {
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-            let deployed = check_safe_deployed(&client, builder_creds, &safe_address).await
-                .unwrap_or(false);
-            if deployed {
-                info!("Safe deployed after {} checks", i + 1);
-                break;
-            }
+        let job_id = jobs
+            .track_relay(&eoa_str, OnchainJobKind::SafeDeploy, "Deploy Safe", Some(&safe_address), &tx_id)
+            .await?;
+
+        let job = jobs.wait(job_id, ACTIVATION_WAIT).await?;
+        if job.status == OnchainJobStatus::Failed {
+            anyhow::bail!(
+                "Safe deployment failed: {}",
+                job.error.unwrap_or_else(|| "unknown error".to_string())
+            );
         }
 
         // Verify
@@ -592,17 +624,24 @@ pub async fn ensure_safe_activated(
 
     if !has_allowance {
         info!("Setting on-chain approvals for Safe {}...", safe_address);
-        set_safe_approvals(signer, &client, builder_creds, &safe_address).await
+        let tx_id = set_safe_approvals(signer, &client, builder_creds, &safe_address).await
             .context("Failed to set Safe approvals")?;
-
-        // Poll for allowance confirmation
-        for i in 0..40 {
-            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
-            let has_it = check_usdc_allowance(&client, &safe_address).await.unwrap_or(false);
-            if has_it {
-                info!("Allowances confirmed after {} checks", i + 1);
-                break;
+        let job_id = jobs
+            .track_relay(&eoa_str, OnchainJobKind::Approval, "Set Safe approvals", Some(&safe_address), &tx_id)
+            .await?;
+
+        let job = jobs.wait(job_id, ACTIVATION_WAIT).await?;
+        match job.status {
+            OnchainJobStatus::Failed => anyhow::bail!(
+                "Safe approvals failed: {}",
+                job.error.unwrap_or_else(|| "unknown error".to_string())
+            ),
+            OnchainJobStatus::Mined => {
+                if check_usdc_allowance(&client, &safe_address).await.unwrap_or(false) {
+                    info!("Allowances confirmed for {}", safe_address);
+                }
             }
+            _ => info!("Approvals for {} still pending (job #{})", safe_address, job_id),
         }
     } else {
         info!("Safe already has USDC allowance: {}", safe_address);
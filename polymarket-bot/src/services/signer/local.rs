@@ -0,0 +1,208 @@
+//! In-process signer backend
+//!
+//! Keys are parsed into `PrivateKeySigner`s (whose secret scalar is zeroized on
+//! drop) and the decrypted key string is wiped right after parsing. Each key can
+//! have its own expiry, and optionally auto-locks after sitting unused.
+
+use super::{SignerBackend, POLYGON_CHAIN_ID};
+use alloy::primitives::{Address, Signature, B256};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::Signer;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::info;
+use zeroize::Zeroizing;
+
+/// How often expired keys are swept
+const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+struct UnlockedKey {
+    signer: PrivateKeySigner,
+    /// `None` = no TTL
+    expires_at: Option<Instant>,
+    last_used: Instant,
+}
+
+/// In-memory key store with per-key TTL and idle auto-lock
+pub struct LocalSignerBackend {
+    keys: RwLock<HashMap<String, UnlockedKey>>,
+    /// TTL of keys unlocked without one (`None` = they stay unlocked until locked)
+    default_ttl: Option<Duration>,
+    /// Lock a key after this long without a signature (`None` = never)
+    idle_lock: Option<Duration>,
+}
+
+impl LocalSignerBackend {
+    pub fn new(default_ttl: Option<Duration>, idle_lock: Option<Duration>) -> Self {
+        Self {
+            keys: RwLock::new(HashMap::new()),
+            default_ttl,
+            idle_lock,
+        }
+    }
+
+    /// Periodically lock expired and idle keys
+    pub fn spawn_auto_lock(self: &Arc<Self>) {
+        let backend = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(backend) = backend.upgrade() else {
+                    break;
+                };
+                backend.lock_expired(Instant::now()).await;
+            }
+        });
+    }
+
+    /// Lock every key past its TTL or idle limit at `now`
+    async fn lock_expired(&self, now: Instant) {
+        let mut keys = self.keys.write().await;
+        keys.retain(|wallet_address, key| {
+            let live = !self.is_expired(key, now);
+            if !live {
+                info!("[Signer] Auto-locked key for wallet {}", wallet_address);
+            }
+            live
+        });
+    }
+
+    fn is_expired(&self, key: &UnlockedKey, now: Instant) -> bool {
+        key.expires_at.is_some_and(|expires_at| now >= expires_at)
+            || self
+                .idle_lock
+                .is_some_and(|idle| now.duration_since(key.last_used) >= idle)
+    }
+}
+
+#[async_trait]
+impl SignerBackend for LocalSignerBackend {
+    async fn unlock(&self, wallet_address: &str, private_key: Zeroizing<String>, ttl: Option<Duration>) -> Result<Address> {
+        let signer: PrivateKeySigner = private_key
+            .trim()
+            .parse()
+            .context("Failed to parse private key")?;
+        drop(private_key);
+        let signer = signer.with_chain_id(Some(POLYGON_CHAIN_ID));
+        let address = signer.address();
+
+        let now = Instant::now();
+        let ttl = ttl.or(self.default_ttl);
+        self.keys.write().await.insert(
+            wallet_address.to_lowercase(),
+            UnlockedKey { signer, expires_at: ttl.map(|ttl| now + ttl), last_used: now },
+        );
+        info!("[Signer] Unlocked key for wallet {} (ttl {:?})", wallet_address, ttl);
+        Ok(address)
+    }
+
+    async fn lock(&self, wallet_address: &str) -> Result<()> {
+        if self.keys.write().await.remove(&wallet_address.to_lowercase()).is_some() {
+            info!("[Signer] Locked key for wallet {}", wallet_address);
+        }
+        Ok(())
+    }
+
+    async fn lock_all(&self) -> Result<usize> {
+        let mut keys = self.keys.write().await;
+        let count = keys.len();
+        keys.clear();
+        if count > 0 {
+            info!("[Signer] Locked {} keys", count);
+        }
+        Ok(count)
+    }
+
+    async fn address(&self, wallet_address: &str) -> Result<Option<Address>> {
+        let now = Instant::now();
+        let keys = self.keys.read().await;
+        Ok(keys
+            .get(&wallet_address.to_lowercase())
+            .filter(|key| !self.is_expired(key, now))
+            .map(|key| key.signer.address()))
+    }
+
+    async fn wallets(&self) -> Result<Vec<String>> {
+        let now = Instant::now();
+        let keys = self.keys.read().await;
+        Ok(keys
+            .iter()
+            .filter(|(_, key)| !self.is_expired(key, now))
+            .map(|(wallet_address, _)| wallet_address.clone())
+            .collect())
+    }
+
+    async fn sign_hash(&self, wallet_address: &str, hash: &B256) -> Result<Signature> {
+        let now = Instant::now();
+        let mut keys = self.keys.write().await;
+        let wallet_address = wallet_address.to_lowercase();
+        let key = match keys.get_mut(&wallet_address) {
+            Some(key) if !self.is_expired(key, now) => key,
+            Some(_) => {
+                keys.remove(&wallet_address);
+                anyhow::bail!("Key for wallet {} is locked (expired)", wallet_address);
+            }
+            None => anyhow::bail!("Key for wallet {} is locked", wallet_address),
+        };
+        key.last_used = now;
+        Ok(key.signer.sign_hash(hash).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Anvil's first default account
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TEST_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    #[tokio::test]
+    async fn test_unlock_sign_lock() {
+        let backend = LocalSignerBackend::new(Some(Duration::from_secs(60)), None);
+        let address = backend
+            .unlock("0xWALLET", Zeroizing::new(TEST_KEY.to_string()), None)
+            .await
+            .unwrap();
+        assert_eq!(address, TEST_ADDRESS.parse::<Address>().unwrap());
+        assert_eq!(backend.wallets().await.unwrap(), vec!["0xwallet".to_string()]);
+
+        let hash = B256::repeat_byte(7);
+        let sig = backend.sign_hash("0xwallet", &hash).await.unwrap();
+        assert_eq!(sig.recover_address_from_prehash(&hash).unwrap(), address);
+
+        backend.lock("0xWallet").await.unwrap();
+        assert!(backend.sign_hash("0xwallet", &hash).await.is_err());
+        assert!(backend.address("0xwallet").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_key_is_locked() {
+        let backend = LocalSignerBackend::new(Some(Duration::from_secs(60)), None);
+        backend
+            .unlock("0xwallet", Zeroizing::new(TEST_KEY.to_string()), Some(Duration::ZERO))
+            .await
+            .unwrap();
+        assert!(backend.wallets().await.unwrap().is_empty());
+        assert!(backend.sign_hash("0xwallet", &B256::ZERO).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_key_without_ttl_outlives_sweeps() {
+        let backend = LocalSignerBackend::new(None, None);
+        let address = backend
+            .unlock("0xwallet", Zeroizing::new(TEST_KEY.to_string()), None)
+            .await
+            .unwrap();
+
+        // A sweep a week later still leaves the key unlocked
+        backend.lock_expired(Instant::now() + Duration::from_secs(7 * 86_400)).await;
+        assert_eq!(backend.address("0xwallet").await.unwrap(), Some(address));
+        assert!(backend.sign_hash("0xwallet", &B256::ZERO).await.is_ok());
+    }
+}
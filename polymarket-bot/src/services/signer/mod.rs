@@ -0,0 +1,177 @@
+//! Signer - services request signatures instead of holding raw private keys
+//!
+//! A `SignerBackend` holds the unlocked keys and signs 32-byte hashes on request:
+//! - `LocalSignerBackend`: in-process store of zeroized keys with optional TTL and idle auto-lock
+//! - `RemoteSignerBackend`: an external signer process reached over a Unix socket,
+//!   so a compromised web process never sees a decrypted key after handing it off
+//!
+//! Services get a `WalletSigner` handle per wallet. It implements alloy's `Signer`
+//! trait, so it plugs into the CLOB SDK and every Safe/transaction signing path.
+
+pub mod local;
+pub mod remote;
+
+pub use local::LocalSignerBackend;
+pub use remote::{serve, RemoteSignerBackend};
+
+use alloy::primitives::{Address, ChainId, Signature, B256};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Chain ID attached to every wallet signer (Polygon mainnet)
+pub const POLYGON_CHAIN_ID: ChainId = 137;
+
+/// Lifetime of a key decrypted for a single request
+const ONE_OFF_TTL: Duration = Duration::from_secs(300);
+
+/// Key custody backend: unlocks keys for a bounded time and signs hashes with them
+#[async_trait]
+pub trait SignerBackend: Send + Sync {
+    /// Unlock a wallet's key. It locks again after `ttl` (or the backend default).
+    async fn unlock(&self, wallet_address: &str, private_key: Zeroizing<String>, ttl: Option<Duration>) -> Result<Address>;
+
+    /// Lock (forget) a wallet's key
+    async fn lock(&self, wallet_address: &str) -> Result<()>;
+
+    /// Lock every key
+    async fn lock_all(&self) -> Result<usize>;
+
+    /// EOA address of an unlocked wallet, `None` when locked
+    async fn address(&self, wallet_address: &str) -> Result<Option<Address>>;
+
+    /// Wallets that currently have an unlocked key
+    async fn wallets(&self) -> Result<Vec<String>>;
+
+    /// Sign a 32-byte hash with a wallet's key
+    async fn sign_hash(&self, wallet_address: &str, hash: &B256) -> Result<Signature>;
+}
+
+/// Signing handle for one wallet; never exposes the key itself
+#[derive(Clone)]
+pub struct WalletSigner {
+    wallet_address: String,
+    address: Address,
+    chain_id: Option<ChainId>,
+    backend: Arc<dyn SignerBackend>,
+}
+
+impl WalletSigner {
+    pub fn new(wallet_address: &str, address: Address, backend: Arc<dyn SignerBackend>) -> Self {
+        Self {
+            wallet_address: wallet_address.to_lowercase(),
+            address,
+            chain_id: Some(POLYGON_CHAIN_ID),
+            backend,
+        }
+    }
+
+    /// One-off signer for a key decrypted for a single request (manual trades,
+    /// withdrawals). The key is wiped when the last handle is dropped, or locked
+    /// after `ONE_OFF_TTL` if a handle lingers.
+    pub async fn from_private_key(wallet_address: &str, private_key: Zeroizing<String>) -> Result<Self> {
+        let backend: Arc<dyn SignerBackend> = Arc::new(LocalSignerBackend::new(Some(ONE_OFF_TTL), None));
+        let address = backend.unlock(wallet_address, private_key, None).await?;
+        Ok(Self::new(wallet_address, address, backend))
+    }
+
+    /// Wallet this signer was unlocked for
+    pub fn wallet_address(&self) -> &str {
+        &self.wallet_address
+    }
+}
+
+impl std::fmt::Debug for WalletSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletSigner")
+            .field("wallet_address", &self.wallet_address)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl alloy::signers::Signer for WalletSigner {
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Signature> {
+        self.backend
+            .sign_hash(&self.wallet_address, hash)
+            .await
+            .map_err(alloy::signers::Error::other)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
+
+/// Request sent to the signer process (one JSON object per line)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SignerRequest {
+    Unlock {
+        wallet_address: String,
+        private_key: String,
+        ttl_seconds: Option<u64>,
+    },
+    Lock {
+        wallet_address: String,
+    },
+    LockAll,
+    Address {
+        wallet_address: String,
+    },
+    Wallets,
+    SignHash {
+        wallet_address: String,
+        /// 0x-prefixed 32-byte hash
+        hash: String,
+    },
+}
+
+impl Drop for SignerRequest {
+    fn drop(&mut self) {
+        if let SignerRequest::Unlock { private_key, .. } = self {
+            zeroize::Zeroize::zeroize(private_key);
+        }
+    }
+}
+
+/// Response from the signer process
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SignerResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 0x-prefixed EOA address (unlock / address)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallets: Option<Vec<String>>,
+    /// 0x-prefixed 65-byte signature (sign_hash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Keys locked (lock_all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+}
+
+impl SignerResponse {
+    fn ok() -> Self {
+        Self { ok: true, ..Default::default() }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self { ok: false, error: Some(message.into()), ..Default::default() }
+    }
+}
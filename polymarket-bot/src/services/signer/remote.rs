@@ -0,0 +1,299 @@
+//! External signer process over a Unix socket
+//!
+//! Line-delimited JSON: one `SignerRequest` per connection, answered with one
+//! `SignerResponse`. The signer process (`polymarket-signer`) serves a
+//! `LocalSignerBackend`, so decrypted keys only live in that process.
+
+use super::{SignerBackend, SignerRequest, SignerResponse};
+use alloy::primitives::{Address, Signature, B256};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+/// Max time for one request/response round-trip
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client for an external signer process
+pub struct RemoteSignerBackend {
+    socket_path: PathBuf,
+}
+
+impl RemoteSignerBackend {
+    pub fn new(socket_path: impl Into<PathBuf>) -> Self {
+        Self { socket_path: socket_path.into() }
+    }
+
+    async fn call(&self, request: SignerRequest) -> Result<SignerResponse> {
+        let round_trip = async {
+            let mut stream = UnixStream::connect(&self.socket_path)
+                .await
+                .with_context(|| format!("Failed to connect to signer at {}", self.socket_path.display()))?;
+
+            let mut line = Zeroizing::new(serde_json::to_string(&request)?);
+            line.push('\n');
+            stream.write_all(line.as_bytes()).await?;
+            drop(line);
+
+            let mut reader = BufReader::new(stream);
+            let mut response = String::new();
+            reader.read_line(&mut response).await?;
+            let response: SignerResponse =
+                serde_json::from_str(&response).context("Invalid signer response")?;
+            Ok::<_, anyhow::Error>(response)
+        };
+
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, round_trip)
+            .await
+            .context("Signer request timed out")??;
+        if !response.ok {
+            anyhow::bail!(
+                "Signer error: {}",
+                response.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl SignerBackend for RemoteSignerBackend {
+    async fn unlock(&self, wallet_address: &str, private_key: Zeroizing<String>, ttl: Option<Duration>) -> Result<Address> {
+        let response = self
+            .call(SignerRequest::Unlock {
+                wallet_address: wallet_address.to_lowercase(),
+                private_key: private_key.to_string(),
+                ttl_seconds: ttl.map(|t| t.as_secs()),
+            })
+            .await?;
+        let address = response.address.context("Signer returned no address")?;
+        Ok(Address::from_str(&address)?)
+    }
+
+    async fn lock(&self, wallet_address: &str) -> Result<()> {
+        self.call(SignerRequest::Lock { wallet_address: wallet_address.to_lowercase() })
+            .await?;
+        Ok(())
+    }
+
+    async fn lock_all(&self) -> Result<usize> {
+        Ok(self.call(SignerRequest::LockAll).await?.count.unwrap_or(0))
+    }
+
+    async fn address(&self, wallet_address: &str) -> Result<Option<Address>> {
+        let response = self
+            .call(SignerRequest::Address { wallet_address: wallet_address.to_lowercase() })
+            .await?;
+        response
+            .address
+            .map(|a| Address::from_str(&a).map_err(Into::into))
+            .transpose()
+    }
+
+    async fn wallets(&self) -> Result<Vec<String>> {
+        Ok(self.call(SignerRequest::Wallets).await?.wallets.unwrap_or_default())
+    }
+
+    async fn sign_hash(&self, wallet_address: &str, hash: &B256) -> Result<Signature> {
+        let response = self
+            .call(SignerRequest::SignHash {
+                wallet_address: wallet_address.to_lowercase(),
+                hash: format!("{:?}", hash),
+            })
+            .await?;
+        let signature = response.signature.context("Signer returned no signature")?;
+        let bytes = alloy::hex::decode(&signature)?;
+        Ok(Signature::try_from(bytes.as_slice())?)
+    }
+}
+
+/// Serve a signer backend on a Unix socket (owner-only permissions)
+pub async fn serve(socket_path: &Path, backend: Arc<dyn SignerBackend>) -> Result<()> {
+    let listener = bind_private(socket_path)?;
+    info!("[Signer] Listening on {}", socket_path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let backend = backend.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, backend).await {
+                warn!("[Signer] Connection failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Bind the socket so no other user can ever connect: it's created inside a
+/// fresh 0700 directory, restricted to 0600 and only then moved into place.
+/// Refuses a group- or world-writable parent, where the socket could be swapped.
+fn bind_private(socket_path: &Path) -> Result<UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let parent = socket_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let mode = std::fs::metadata(parent)
+        .with_context(|| format!("Failed to read signer socket directory {}", parent.display()))?
+        .permissions()
+        .mode();
+    if mode & 0o022 != 0 {
+        anyhow::bail!(
+            "Refusing to serve: signer socket directory {} is group- or world-writable ({:o})",
+            parent.display(),
+            mode & 0o777
+        );
+    }
+
+    if socket_path.exists() {
+        std::fs::remove_file(socket_path)
+            .with_context(|| format!("Failed to remove stale socket {}", socket_path.display()))?;
+    }
+
+    let staging = parent.join(format!(".polymarket-signer-{}", std::process::id()));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+    let staged = staging.join("signer.sock");
+    let bound = UnixListener::bind(&staged)
+        .context("Failed to bind signer socket")
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&staged, socket_path)
+                .with_context(|| format!("Failed to move signer socket to {}", socket_path.display()))?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&staging);
+    bound
+}
+
+async fn handle_connection(stream: UnixStream, backend: Arc<dyn SignerBackend>) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    let mut line = Zeroizing::new(String::new());
+    reader.read_line(&mut line).await?;
+
+    let response = match serde_json::from_str::<SignerRequest>(&line) {
+        Ok(request) => handle_request(&request, backend.as_ref()).await,
+        Err(e) => SignerResponse::error(format!("Invalid request: {}", e)),
+    };
+    drop(line);
+
+    let mut out = serde_json::to_string(&response)?;
+    out.push('\n');
+    write.write_all(out.as_bytes()).await?;
+    Ok(())
+}
+
+async fn handle_request(request: &SignerRequest, backend: &dyn SignerBackend) -> SignerResponse {
+    let result: Result<SignerResponse> = async {
+        Ok(match request {
+            SignerRequest::Unlock { wallet_address, private_key, ttl_seconds } => {
+                let address = backend
+                    .unlock(
+                        wallet_address,
+                        Zeroizing::new(private_key.clone()),
+                        ttl_seconds.map(Duration::from_secs),
+                    )
+                    .await?;
+                SignerResponse { address: Some(format!("{:?}", address)), ..SignerResponse::ok() }
+            }
+            SignerRequest::Lock { wallet_address } => {
+                backend.lock(wallet_address).await?;
+                SignerResponse::ok()
+            }
+            SignerRequest::LockAll => {
+                let count = backend.lock_all().await?;
+                SignerResponse { count: Some(count), ..SignerResponse::ok() }
+            }
+            SignerRequest::Address { wallet_address } => {
+                let address = backend.address(wallet_address).await?;
+                SignerResponse { address: address.map(|a| format!("{:?}", a)), ..SignerResponse::ok() }
+            }
+            SignerRequest::Wallets => {
+                SignerResponse { wallets: Some(backend.wallets().await?), ..SignerResponse::ok() }
+            }
+            SignerRequest::SignHash { wallet_address, hash } => {
+                let hash = B256::from_str(hash).context("Invalid hash")?;
+                let signature = backend.sign_hash(wallet_address, &hash).await?;
+                debug!("[Signer] Signed hash for {}", wallet_address);
+                SignerResponse {
+                    signature: Some(format!("0x{}", alloy::hex::encode(signature.as_bytes()))),
+                    ..SignerResponse::ok()
+                }
+            }
+        })
+    }
+    .await;
+
+    result.unwrap_or_else(|e| SignerResponse::error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::LocalSignerBackend;
+    use super::*;
+
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn test_remote_round_trip() {
+        let dir = std::env::temp_dir().join(format!("signer-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+        }
+        let socket = dir.join("signer.sock");
+
+        let local: Arc<dyn SignerBackend> = Arc::new(LocalSignerBackend::new(Some(Duration::from_secs(60)), None));
+        let server_socket = socket.clone();
+        tokio::spawn(async move { serve(&server_socket, local).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let remote = RemoteSignerBackend::new(&socket);
+        let address = remote
+            .unlock("0xwallet", Zeroizing::new(TEST_KEY.to_string()), None)
+            .await
+            .unwrap();
+        assert_eq!(remote.address("0xwallet").await.unwrap(), Some(address));
+
+        let hash = B256::repeat_byte(3);
+        let sig = remote.sign_hash("0xwallet", &hash).await.unwrap();
+        assert_eq!(sig.recover_address_from_prehash(&hash).unwrap(), address);
+
+        assert_eq!(remote.lock_all().await.unwrap(), 1);
+        assert!(remote.sign_hash("0xwallet", &hash).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_refuses_world_writable_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("signer-test-open-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+        let local: Arc<dyn SignerBackend> = Arc::new(LocalSignerBackend::new(None, None));
+        assert!(serve(&dir.join("signer.sock"), local).await.is_err());
+        assert!(!dir.join("signer.sock").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,666 @@
+//! On-chain UMA event source
+//!
+//! Reads Optimistic Oracle V3 `AssertionMade` / `AssertionDisputed` / `AssertionSettled`
+//! events and UmaCtfAdapter `QuestionResolved` / `QuestionReset` events directly from
+//! Polygon via `eth_getLogs`. Used by the dispute tracker when the subgraph is down or lagging.
+//!
+//! The last processed block is persisted in `chain_cursors` and assertion state in
+//! `uma_chain_assertions`, so a restart resumes where it stopped instead of re-scanning.
+
+use crate::Database;
+use alloy::primitives::B256;
+use alloy::sol;
+use alloy::sol_types::SolEvent;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// UMA Optimistic Oracle V3 on Polygon
+const OOV3_ADDRESS: &str = "0x5953f2538f613e05baed8a5aefa8e6622467ad3d";
+
+/// Cursor name in the `chain_cursors` table
+const CURSOR_NAME: &str = "uma_oov3";
+
+/// Blocks to stay behind head to avoid reorged logs (~10s on Polygon)
+const CONFIRMATIONS: u64 = 5;
+
+/// How far back to start when there is no cursor yet (~2 days of Polygon blocks)
+const INITIAL_LOOKBACK_BLOCKS: u64 = 86_400;
+
+/// Block range per `eth_getLogs` call (public RPCs commonly cap at 1-2k)
+const LOGS_CHUNK_BLOCKS: u64 = 1_000;
+
+/// Max chunks per sync so a long catch-up doesn't stall a tracker cycle
+const MAX_CHUNKS_PER_SYNC: u64 = 20;
+
+sol! {
+    event AssertionMade(
+        bytes32 indexed assertionId,
+        bytes32 domainId,
+        bytes claim,
+        address indexed asserter,
+        address callbackRecipient,
+        address escalationManager,
+        address caller,
+        uint64 expirationTime,
+        address currency,
+        uint256 bond,
+        bytes32 indexed identifier
+    );
+
+    event AssertionDisputed(
+        bytes32 indexed assertionId,
+        address indexed caller,
+        address indexed disputer
+    );
+
+    event AssertionSettled(
+        bytes32 indexed assertionId,
+        address indexed bondRecipient,
+        bool disputed,
+        bool settlementResolution,
+        address settleCaller
+    );
+}
+
+// UmaCtfAdapter events, in a separate module to keep the generated names apart.
+mod adapter_abi {
+    alloy::sol! {
+        event QuestionResolved(bytes32 indexed questionID, int256 indexed settledPrice, uint256[] payouts);
+        event QuestionReset(bytes32 indexed questionID);
+    }
+}
+
+/// Assertion state assembled from on-chain events
+#[derive(Debug, Clone)]
+pub struct ChainAssertion {
+    pub assertion_id: String,
+    /// For Polymarket this is the questionId used to create the condition
+    pub domain_id: String,
+    /// Hex-encoded claim bytes (0x-prefixed)
+    pub claim: String,
+    pub identifier: String,
+    pub callback_recipient: String,
+    pub currency: String,
+    /// Bond in raw token units (decimal string)
+    pub bond: String,
+    pub assertion_timestamp: i64,
+    pub expiration_time: i64,
+    pub disputer: Option<String>,
+    pub dispute_timestamp: Option<i64>,
+    pub settlement_resolution: Option<bool>,
+    pub settlement_timestamp: Option<i64>,
+    /// Block the assertion was made in
+    pub block_number: i64,
+}
+
+/// Log entry as returned by `eth_getLogs`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcLog {
+    topics: Vec<String>,
+    data: String,
+    block_number: String,
+    #[serde(default)]
+    log_index: Option<String>,
+    #[serde(default)]
+    removed: bool,
+}
+
+/// On-chain UMA event reader with a persisted block cursor
+pub struct UmaEventSource {
+    db: Arc<Database>,
+    client: Client,
+    rpc_url: String,
+    /// Adapter addresses (lowercase) whose assertions we care about
+    adapters: Vec<String>,
+    /// Unsettled (and recently settled) assertions keyed by assertion_id
+    assertions: HashMap<String, ChainAssertion>,
+    /// Last block fully processed
+    cursor: Option<u64>,
+    /// Latest chain head seen on the last sync
+    head_block: Option<u64>,
+    /// Block timestamps fetched during the current sync
+    block_times: HashMap<u64, i64>,
+    loaded: bool,
+}
+
+impl UmaEventSource {
+    pub fn new(db: Arc<Database>, rpc_url: String, adapters: &[&str]) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        // Skip malformed addresses: one bad entry makes the RPC reject the whole filter
+        let adapters = adapters
+            .iter()
+            .map(|a| a.to_lowercase())
+            .filter(|a| {
+                let valid = a.len() == 42 && a[2..].chars().all(|c| c.is_ascii_hexdigit());
+                if !valid {
+                    warn!("Ignoring malformed adapter address for log filter: {}", a);
+                }
+                valid
+            })
+            .collect();
+
+        Self {
+            db,
+            client,
+            rpc_url,
+            adapters,
+            assertions: HashMap::new(),
+            cursor: None,
+            head_block: None,
+            block_times: HashMap::new(),
+            loaded: false,
+        }
+    }
+
+    /// Latest chain head seen on the last successful sync
+    pub fn head_block(&self) -> Option<u64> {
+        self.head_block
+    }
+
+    /// Current assertion state (unsettled assertions plus ones settled during this run)
+    pub fn assertions(&self) -> Vec<ChainAssertion> {
+        self.assertions.values().cloned().collect()
+    }
+
+    /// Pull new logs since the persisted cursor and apply them
+    pub async fn sync(&mut self) -> Result<()> {
+        if !self.loaded {
+            self.load().await?;
+        }
+
+        let head = self.block_number().await?;
+        self.head_block = Some(head);
+        let safe_head = head.saturating_sub(CONFIRMATIONS);
+
+        let mut from = start_block(self.cursor, safe_head);
+
+        let mut chunks = 0;
+        while from <= safe_head && chunks < MAX_CHUNKS_PER_SYNC {
+            let to = (from + LOGS_CHUNK_BLOCKS - 1).min(safe_head);
+            let logs = self.get_logs(from, to).await?;
+            if !logs.is_empty() {
+                debug!("UMA logs {}..{}: {} entries", from, to, logs.len());
+            }
+            self.apply_logs(logs).await?;
+
+            self.db.chain_events().set_chain_cursor(CURSOR_NAME, to as i64).await?;
+            self.cursor = Some(to);
+            from = to + 1;
+            chunks += 1;
+        }
+        self.block_times.clear();
+
+        // Settled assertions only need to be reported once; drop them after an hour
+        let cutoff = Utc::now().timestamp() - 3600;
+        self.assertions
+            .retain(|_, a| a.settlement_timestamp.map(|t| t > cutoff).unwrap_or(true));
+
+        if from <= safe_head {
+            info!("UMA event source catching up: {} blocks behind head", safe_head - from + 1);
+        }
+
+        Ok(())
+    }
+
+    /// Restore the block cursor and unsettled assertions persisted by a previous run
+    async fn load(&mut self) -> Result<()> {
+        self.cursor = self.db.chain_events().get_chain_cursor(CURSOR_NAME).await?.map(|b| b as u64);
+        for assertion in self.db.chain_events().get_unsettled_uma_chain_assertions().await? {
+            self.assertions.insert(assertion.assertion_id.clone(), assertion);
+        }
+        info!(
+            "UMA event source loaded {} unsettled assertions (cursor: {:?})",
+            self.assertions.len(),
+            self.cursor
+        );
+        self.loaded = true;
+        Ok(())
+    }
+
+    /// Decode and apply a batch of logs in chain order, persisting touched assertions
+    async fn apply_logs(&mut self, mut logs: Vec<RpcLog>) -> Result<()> {
+        logs.retain(|l| !l.removed);
+        logs.sort_by_key(|l| {
+            (
+                parse_hex_u64(&l.block_number).unwrap_or(0),
+                l.log_index.as_deref().and_then(parse_hex_u64).unwrap_or(0),
+            )
+        });
+
+        let mut touched: Vec<String> = Vec::new();
+
+        for log in logs {
+            let Some(topic0) = log.topics.first() else { continue };
+            let topic0 = topic0.to_lowercase();
+            let topics: Vec<B256> = log.topics.iter().filter_map(|t| t.parse().ok()).collect();
+            let data = hex::decode(log.data.trim_start_matches("0x")).unwrap_or_default();
+            let block = parse_hex_u64(&log.block_number).unwrap_or(0);
+
+            if topic0 == hex_b256(&AssertionMade::SIGNATURE_HASH) {
+                let Ok(ev) = AssertionMade::decode_raw_log(topics, &data) else {
+                    warn!("Failed to decode AssertionMade log at block {}", block);
+                    continue;
+                };
+                let callback = format!("{:?}", ev.callbackRecipient).to_lowercase();
+                if !self.adapters.contains(&callback) {
+                    continue;
+                }
+                let timestamp = self.block_timestamp(block).await?;
+                let assertion = ChainAssertion {
+                    assertion_id: hex_b256(&ev.assertionId),
+                    domain_id: hex_b256(&ev.domainId),
+                    claim: format!("0x{}", hex::encode(&ev.claim)),
+                    identifier: hex_b256(&ev.identifier),
+                    callback_recipient: callback,
+                    currency: format!("{:?}", ev.currency).to_lowercase(),
+                    bond: ev.bond.to_string(),
+                    assertion_timestamp: timestamp,
+                    expiration_time: ev.expirationTime as i64,
+                    disputer: None,
+                    dispute_timestamp: None,
+                    settlement_resolution: None,
+                    settlement_timestamp: None,
+                    block_number: block as i64,
+                };
+                info!(
+                    "On-chain assertion {} made at block {}",
+                    &assertion.assertion_id[..18],
+                    block
+                );
+                touched.push(assertion.assertion_id.clone());
+                self.assertions.insert(assertion.assertion_id.clone(), assertion);
+            } else if topic0 == hex_b256(&AssertionDisputed::SIGNATURE_HASH) {
+                let Ok(ev) = AssertionDisputed::decode_raw_log(topics, &data) else { continue };
+                let id = hex_b256(&ev.assertionId);
+                if !self.assertions.contains_key(&id) {
+                    continue;
+                }
+                let timestamp = self.block_timestamp(block).await?;
+                if let Some(assertion) = self.assertions.get_mut(&id) {
+                    assertion.disputer = Some(format!("{:?}", ev.disputer).to_lowercase());
+                    assertion.dispute_timestamp = Some(timestamp);
+                    info!("On-chain assertion {} disputed at block {}", &id[..18], block);
+                    touched.push(id);
+                }
+            } else if topic0 == hex_b256(&AssertionSettled::SIGNATURE_HASH) {
+                let Ok(ev) = AssertionSettled::decode_raw_log(topics, &data) else { continue };
+                let id = hex_b256(&ev.assertionId);
+                if !self.assertions.contains_key(&id) {
+                    continue;
+                }
+                let timestamp = self.block_timestamp(block).await?;
+                if let Some(assertion) = self.assertions.get_mut(&id) {
+                    assertion.settlement_resolution = Some(ev.settlementResolution);
+                    assertion.settlement_timestamp = Some(timestamp);
+                    debug!("On-chain assertion {} settled ({})", &id[..18], ev.settlementResolution);
+                    touched.push(id);
+                }
+            } else if topic0 == hex_b256(&adapter_abi::QuestionResolved::SIGNATURE_HASH) {
+                let Ok(ev) = adapter_abi::QuestionResolved::decode_raw_log(topics, &data) else { continue };
+                let question_id = hex_b256(&ev.questionID);
+                let timestamp = self.block_timestamp(block).await?;
+                // Resolution on the adapter settles every open assertion for the question,
+                // including ones whose AssertionSettled fell outside our lookback window
+                for assertion in self.assertions.values_mut() {
+                    if assertion.domain_id == question_id && assertion.settlement_timestamp.is_none() {
+                        assertion.settlement_timestamp = Some(timestamp);
+                        touched.push(assertion.assertion_id.clone());
+                    }
+                }
+                debug!("Adapter question {} resolved at block {}", &question_id[..18], block);
+            } else if topic0 == hex_b256(&adapter_abi::QuestionReset::SIGNATURE_HASH) {
+                if let Ok(ev) = adapter_abi::QuestionReset::decode_raw_log(topics, &data) {
+                    info!(
+                        "Adapter question {} reset after dispute (round 2 proposal expected)",
+                        &hex_b256(&ev.questionID)[..18]
+                    );
+                }
+            }
+        }
+
+        touched.sort();
+        touched.dedup();
+        for id in &touched {
+            if let Some(assertion) = self.assertions.get(id) {
+                self.db.chain_events().upsert_uma_chain_assertion(assertion).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetch OOv3 + adapter logs for an inclusive block range
+    async fn get_logs(&self, from: u64, to: u64) -> Result<Vec<RpcLog>> {
+        let mut addresses = vec![OOV3_ADDRESS.to_string()];
+        addresses.extend(self.adapters.iter().cloned());
+
+        let topic0s = vec![
+            hex_b256(&AssertionMade::SIGNATURE_HASH),
+            hex_b256(&AssertionDisputed::SIGNATURE_HASH),
+            hex_b256(&AssertionSettled::SIGNATURE_HASH),
+            hex_b256(&adapter_abi::QuestionResolved::SIGNATURE_HASH),
+            hex_b256(&adapter_abi::QuestionReset::SIGNATURE_HASH),
+        ];
+
+        let result = self
+            .rpc(
+                "eth_getLogs",
+                serde_json::json!([{
+                    "fromBlock": format!("0x{:x}", from),
+                    "toBlock": format!("0x{:x}", to),
+                    "address": addresses,
+                    "topics": [topic0s],
+                }]),
+            )
+            .await?;
+
+        serde_json::from_value(result).context("Failed to parse eth_getLogs response")
+    }
+
+    /// Current block number
+    async fn block_number(&self) -> Result<u64> {
+        let result = self.rpc("eth_blockNumber", serde_json::json!([])).await?;
+        result
+            .as_str()
+            .and_then(parse_hex_u64)
+            .context("Invalid eth_blockNumber response")
+    }
+
+    /// Block timestamp, cached for the current sync
+    async fn block_timestamp(&mut self, block: u64) -> Result<i64> {
+        if let Some(ts) = self.block_times.get(&block) {
+            return Ok(*ts);
+        }
+        let result = self
+            .rpc(
+                "eth_getBlockByNumber",
+                serde_json::json!([format!("0x{:x}", block), false]),
+            )
+            .await?;
+        let ts = result["timestamp"]
+            .as_str()
+            .and_then(parse_hex_u64)
+            .context("Block has no timestamp")? as i64;
+        self.block_times.insert(block, ts);
+        Ok(ts)
+    }
+
+    /// Raw JSON-RPC call returning the `result` field
+    async fn rpc(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let resp = self
+            .client
+            .post(&self.rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params,
+                "id": 1
+            }))
+            .send()
+            .await
+            .with_context(|| format!("Polygon RPC {} failed", method))?;
+
+        let json: serde_json::Value = resp
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} response", method))?;
+
+        if let Some(error) = json.get("error") {
+            anyhow::bail!("RPC {} error: {}", method, error);
+        }
+
+        Ok(json.get("result").cloned().unwrap_or(serde_json::Value::Null))
+    }
+}
+
+/// 0x-prefixed lowercase hex of a 32-byte word
+fn hex_b256(value: &B256) -> String {
+    format!("0x{}", hex::encode(value.as_slice()))
+}
+
+/// First block to fetch: right after the persisted cursor, or a lookback window on first run
+fn start_block(cursor: Option<u64>, safe_head: u64) -> u64 {
+    match cursor {
+        Some(c) => c + 1,
+        None => safe_head.saturating_sub(INITIAL_LOOKBACK_BLOCKS),
+    }
+}
+
+fn parse_hex_u64(s: &str) -> Option<u64> {
+    u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADAPTER: &str = "0x2f5e3684cb1f318ec51b00edba38d79ac2c0aa9d";
+    const OTHER_CALLBACK: &str = "0x00000000000000000000000000000000000000aa";
+    const ASSERTION_ID: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+    const QUESTION_ID: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+    const ASSERTER: &str = "0x00000000000000000000000000000000000000a1";
+    const DISPUTER: &str = "0x00000000000000000000000000000000000000d1";
+    const USDC: &str = "0x2791bca1f2de4661ed88a30c99a7a9449aa84174";
+    const IDENTIFIER: &str = "0x4153534552545f54525554480000000000000000000000000000000000000000";
+
+    /// One 32-byte ABI word (hex without prefix), left-padded
+    fn word(hex: &str) -> String {
+        format!("{:0>64}", hex.trim_start_matches("0x"))
+    }
+
+    fn topic(hex: &str) -> String {
+        format!("0x{}", word(hex))
+    }
+
+    /// An `eth_getLogs` entry as the RPC returns it
+    fn rpc_log(topics: Vec<String>, words: &[String], block: u64, index: u64) -> RpcLog {
+        serde_json::from_value(serde_json::json!({
+            "address": OOV3_ADDRESS,
+            "topics": topics,
+            "data": format!("0x{}", words.concat()),
+            "blockNumber": format!("0x{:x}", block),
+            "logIndex": format!("0x{:x}", index),
+            "removed": false,
+        }))
+        .unwrap()
+    }
+
+    fn assertion_made(assertion_id: &str, callback: &str, block: u64) -> RpcLog {
+        let claim = hex::encode("q: title: Will it rain?");
+        let topics = vec![
+            hex_b256(&AssertionMade::SIGNATURE_HASH),
+            topic(assertion_id),
+            topic(ASSERTER),
+            IDENTIFIER.to_string(),
+        ];
+        let words = [
+            word(QUESTION_ID),                        // domainId
+            word("100"),                              // claim offset (8 head words)
+            word(callback),                           // callbackRecipient
+            word("0"),                                // escalationManager
+            word(callback),                           // caller
+            word(&format!("{:x}", 1_700_007_200u64)), // expirationTime
+            word(USDC),                               // currency
+            word(&format!("{:x}", 750_000_000u64)),   // bond
+            word(&format!("{:x}", claim.len() / 2)),  // claim length
+            format!("{:0<64}", claim),                // claim bytes, right-padded
+        ];
+        rpc_log(topics, &words, block, 0)
+    }
+
+    fn assertion_disputed(block: u64) -> RpcLog {
+        let topics = vec![
+            hex_b256(&AssertionDisputed::SIGNATURE_HASH),
+            topic(ASSERTION_ID),
+            topic(ADAPTER),
+            topic(DISPUTER),
+        ];
+        rpc_log(topics, &[], block, 1)
+    }
+
+    fn assertion_settled(resolution: bool, block: u64) -> RpcLog {
+        let topics = vec![
+            hex_b256(&AssertionSettled::SIGNATURE_HASH),
+            topic(ASSERTION_ID),
+            topic(ASSERTER),
+        ];
+        let words = [word("1"), word(if resolution { "1" } else { "0" }), word(ADAPTER)];
+        rpc_log(topics, &words, block, 2)
+    }
+
+    fn question_resolved(block: u64) -> RpcLog {
+        let topics = vec![
+            hex_b256(&adapter_abi::QuestionResolved::SIGNATURE_HASH),
+            QUESTION_ID.to_string(),
+            topic(&format!("{:x}", 1_000_000_000_000_000_000u64)),
+        ];
+        // uint256[] payouts = [1, 0]
+        let words = [word("20"), word("2"), word("1"), word("0")];
+        rpc_log(topics, &words, block, 3)
+    }
+
+    fn question_reset(block: u64) -> RpcLog {
+        let topics = vec![hex_b256(&adapter_abi::QuestionReset::SIGNATURE_HASH), QUESTION_ID.to_string()];
+        rpc_log(topics, &[], block, 4)
+    }
+
+    async fn test_db(name: &str) -> Arc<Database> {
+        let path = std::env::temp_dir().join(format!("uma-events-{}-{}.db", name, std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+        Arc::new(Database::new(&path.to_string_lossy()).await.unwrap())
+    }
+
+    /// Event source with block timestamps pre-filled so no RPC call is made
+    fn event_source(db: Arc<Database>) -> UmaEventSource {
+        let mut source = UmaEventSource::new(db, "http://127.0.0.1:1".to_string(), &[ADAPTER]);
+        for block in 100..110 {
+            source.block_times.insert(block, 1_700_000_000 + (block as i64 - 100) * 2);
+        }
+        source
+    }
+
+    #[tokio::test]
+    async fn test_decodes_assertion_made_for_tracked_adapter() {
+        let mut source = event_source(test_db("made").await);
+        let other_id = "0x3333333333333333333333333333333333333333333333333333333333333333";
+        source
+            .apply_logs(vec![assertion_made(ASSERTION_ID, ADAPTER, 100), assertion_made(other_id, OTHER_CALLBACK, 100)])
+            .await
+            .unwrap();
+
+        let assertions = source.assertions();
+        assert_eq!(assertions.len(), 1);
+        let a = &assertions[0];
+        assert_eq!(a.assertion_id, ASSERTION_ID);
+        assert_eq!(a.domain_id, QUESTION_ID);
+        assert_eq!(a.claim, format!("0x{}", hex::encode("q: title: Will it rain?")));
+        assert_eq!(a.identifier, IDENTIFIER);
+        assert_eq!(a.callback_recipient, ADAPTER);
+        assert_eq!(a.currency, USDC);
+        assert_eq!(a.bond, "750000000");
+        assert_eq!(a.expiration_time, 1_700_007_200);
+        assert_eq!(a.assertion_timestamp, 1_700_000_000);
+        assert_eq!(a.block_number, 100);
+        assert!(a.disputer.is_none() && a.settlement_timestamp.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_applies_events_in_chain_order() {
+        let mut source = event_source(test_db("order").await);
+        // Returned out of order; applied by (block, log index)
+        source
+            .apply_logs(vec![
+                assertion_settled(true, 104),
+                assertion_disputed(102),
+                assertion_made(ASSERTION_ID, ADAPTER, 100),
+            ])
+            .await
+            .unwrap();
+
+        let a = &source.assertions()[0];
+        assert_eq!(a.disputer.as_deref(), Some(DISPUTER));
+        assert_eq!(a.dispute_timestamp, Some(1_700_000_004));
+        assert_eq!(a.settlement_resolution, Some(true));
+        assert_eq!(a.settlement_timestamp, Some(1_700_000_008));
+    }
+
+    #[tokio::test]
+    async fn test_adapter_resolution_settles_open_assertion() {
+        let mut source = event_source(test_db("adapter").await);
+        source
+            .apply_logs(vec![assertion_made(ASSERTION_ID, ADAPTER, 100), assertion_disputed(101)])
+            .await
+            .unwrap();
+
+        // A reset only starts a new proposal round; the disputed assertion stays open
+        source.apply_logs(vec![question_reset(102)]).await.unwrap();
+        assert!(source.assertions()[0].settlement_timestamp.is_none());
+
+        source.apply_logs(vec![question_resolved(105)]).await.unwrap();
+        let a = &source.assertions()[0];
+        assert_eq!(a.settlement_timestamp, Some(1_700_000_010));
+        assert!(a.settlement_resolution.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ignores_removed_logs() {
+        let mut source = event_source(test_db("reorg").await);
+        let mut reorged = assertion_disputed(101);
+        reorged.removed = true;
+        source
+            .apply_logs(vec![assertion_made(ASSERTION_ID, ADAPTER, 100), reorged])
+            .await
+            .unwrap();
+
+        let a = &source.assertions()[0];
+        assert!(a.disputer.is_none());
+        assert!(a.dispute_timestamp.is_none());
+
+        // The re-mined dispute in a later block still applies
+        source.apply_logs(vec![assertion_disputed(103)]).await.unwrap();
+        assert_eq!(source.assertions()[0].dispute_timestamp, Some(1_700_000_006));
+    }
+
+    #[tokio::test]
+    async fn test_resumes_from_persisted_cursor() {
+        let db = test_db("resume").await;
+        let mut first = event_source(db.clone());
+        first.apply_logs(vec![assertion_made(ASSERTION_ID, ADAPTER, 100)]).await.unwrap();
+        db.chain_events().set_chain_cursor(CURSOR_NAME, 105).await.unwrap();
+
+        // A restarted source picks up the cursor and the open assertion
+        let mut second = event_source(db.clone());
+        second.load().await.unwrap();
+        assert_eq!(second.cursor, Some(105));
+        assert_eq!(start_block(second.cursor, 500), 106);
+        assert_eq!(second.assertions().len(), 1);
+
+        second.apply_logs(vec![assertion_disputed(102)]).await.unwrap();
+        assert_eq!(second.assertions()[0].disputer.as_deref(), Some(DISPUTER));
+
+        // Settled assertions are not restored
+        second.apply_logs(vec![assertion_settled(false, 107)]).await.unwrap();
+        let mut third = event_source(db);
+        third.load().await.unwrap();
+        assert!(third.assertions().is_empty());
+    }
+
+    #[test]
+    fn test_start_block_without_cursor_looks_back() {
+        assert_eq!(start_block(None, 100_000), 100_000 - INITIAL_LOOKBACK_BLOCKS);
+        assert_eq!(start_block(None, 10), 0);
+    }
+}
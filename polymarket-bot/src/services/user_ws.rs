@@ -279,7 +279,7 @@ impl UserWebSocket {
             }
 
             if let Err(e) = db
-                .update_order_status(&order_id, new_status, fill_size, fill_price)
+                .orders().update_order_status(&order_id, new_status, fill_size, fill_price)
                 .await
             {
                 debug!("[User WS] Failed to update order {}: {}", order_id, e);
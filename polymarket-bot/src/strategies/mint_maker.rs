@@ -40,6 +40,11 @@ impl MintMakerStrategy {
             return None;
         }
 
+        // Must pass the configured category/tag filter
+        if !self.config.taxonomy.allows(&market.taxonomy) {
+            return None;
+        }
+
         // Must have both token IDs
         let yes_token = market.yes_token_id.as_ref()?;
         let no_token = market.no_token_id.as_ref()?;
@@ -1,9 +1,11 @@
 //! Trading strategies for Polymarket
 
 pub mod mint_maker;
+pub mod neg_risk_arb;
 pub mod sniper;
 
 pub use mint_maker::MintMakerStrategy;
+pub use neg_risk_arb::NegRiskArbStrategy;
 pub use sniper::SniperStrategy;
 
 use crate::config::Config;
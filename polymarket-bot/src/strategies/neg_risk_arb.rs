@@ -0,0 +1,347 @@
+//! Neg-Risk Basket Arbitrage Strategy
+//!
+//! The play: in a neg-risk event exactly one outcome resolves YES.
+//! - One share of every YES token always pays $1
+//! - One share of every NO token always pays N - 1
+//! When either basket can be bought for less than its payout after taker
+//! fees, holding it to resolution locks in the difference.
+//!
+//! Screening runs on Gamma prices; sizing re-prices every leg against the
+//! CLOB asks so the profit check uses the VWAP actually paid.
+
+use crate::config::NegRiskArbConfig;
+use crate::types::{ArbBasket, BasketLeg, NegRiskEvent, Side};
+use rust_decimal::prelude::*;
+
+/// Neg-risk basket arbitrage strategy implementation
+pub struct NegRiskArbStrategy {
+    config: NegRiskArbConfig,
+    /// Taker fee as a fraction of notional
+    fee_rate: Decimal,
+}
+
+/// A basket sized against the live orderbooks
+#[derive(Debug, Clone)]
+pub struct SizedBasket {
+    /// Shares bought on every leg
+    pub shares: Decimal,
+    /// USDC spent per leg (same order as the basket legs)
+    pub leg_costs: Vec<Decimal>,
+    /// Worst ask each leg reaches for `shares`, its limit price (same order as the legs)
+    pub leg_limits: Vec<Decimal>,
+    /// Total USDC spent across legs, before fees
+    pub total_cost: Decimal,
+    /// Total taker fees
+    pub total_fees: Decimal,
+    /// Guaranteed payout minus cost and fees
+    pub expected_profit: Decimal,
+}
+
+impl NegRiskArbStrategy {
+    pub fn new(config: NegRiskArbConfig, taker_fee_bps: u32) -> Self {
+        Self {
+            config,
+            fee_rate: Decimal::from(taker_fee_bps) / Decimal::from(10_000),
+        }
+    }
+
+    pub fn config(&self) -> &NegRiskArbConfig {
+        &self.config
+    }
+
+    /// Screen events for YES and NO baskets that clear the minimum profit at Gamma prices
+    pub fn find_baskets(&self, events: &[NegRiskEvent]) -> Vec<ArbBasket> {
+        let min_liquidity = Decimal::from_f64(self.config.min_leg_liquidity).unwrap_or_default();
+
+        let mut baskets: Vec<ArbBasket> = events
+            .iter()
+            .filter(|e| e.outcomes.len() >= 2 && e.outcomes.len() <= self.config.max_outcomes)
+            // A basket missing an outcome isn't guaranteed to pay out
+            .filter(|e| e.dropped_outcomes == 0)
+            .filter(|e| e.outcomes.iter().all(|o| o.liquidity >= min_liquidity))
+            .filter(|e| self.config.taxonomy.allows(&e.taxonomy))
+            .flat_map(|e| [self.evaluate(e, Side::Yes), self.evaluate(e, Side::No)])
+            .flatten()
+            .collect();
+
+        // Most profitable first
+        baskets.sort_by(|a, b| b.profit_per_set.cmp(&a.profit_per_set));
+        baskets
+    }
+
+    /// Build the basket for one side of an event, if every leg is tradable and it clears min profit
+    fn evaluate(&self, event: &NegRiskEvent, side: Side) -> Option<ArbBasket> {
+        let legs = event
+            .outcomes
+            .iter()
+            .map(|o| {
+                let (token_id, price) = match side {
+                    Side::Yes => (o.yes_token_id.clone()?, o.yes_price),
+                    Side::No => (o.no_token_id.clone()?, o.no_price),
+                };
+                if price <= Decimal::ZERO || price >= Decimal::ONE {
+                    return None;
+                }
+                Some(BasketLeg {
+                    condition_id: o.condition_id.clone(),
+                    label: o.label.clone(),
+                    token_id,
+                    price,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let payout_per_set = payout_per_set(side, legs.len());
+        let cost_per_set: Decimal = legs.iter().map(|l| l.price).sum();
+        let fee_per_set = cost_per_set * self.fee_rate;
+        let profit_per_set = payout_per_set - cost_per_set - fee_per_set;
+
+        if profit_per_set < self.min_profit_per_set() {
+            return None;
+        }
+
+        Some(ArbBasket {
+            event_id: event.event_id.clone(),
+            title: event.title.clone(),
+            neg_risk_market_id: event.neg_risk_market_id.clone(),
+            side,
+            legs,
+            cost_per_set,
+            payout_per_set,
+            fee_per_set,
+            profit_per_set,
+        })
+    }
+
+    /// Size a basket against the ask side of each leg's book.
+    ///
+    /// `asks[i]` holds (price, size) levels for leg `i`, best first. The set count
+    /// is capped by the thinnest leg, `max_sets` and `budget`, then halved until
+    /// the VWAP profit per set clears the minimum.
+    pub fn size_basket(
+        &self,
+        basket: &ArbBasket,
+        asks: &[Vec<(Decimal, Decimal)>],
+        budget: Decimal,
+    ) -> Option<SizedBasket> {
+        if asks.len() != basket.legs.len() || budget <= Decimal::ZERO {
+            return None;
+        }
+
+        let depth = asks
+            .iter()
+            .map(|levels| levels.iter().map(|(_, size)| *size).sum::<Decimal>())
+            .min()?;
+        let best_cost: Decimal = asks
+            .iter()
+            .map(|levels| levels.first().map(|(price, _)| *price))
+            .sum::<Option<Decimal>>()?;
+        if best_cost <= Decimal::ZERO {
+            return None;
+        }
+
+        let affordable = budget / (best_cost * (Decimal::ONE + self.fee_rate));
+        let mut shares = depth
+            .min(Decimal::from(self.config.max_sets))
+            .min(affordable)
+            .floor();
+
+        while shares >= Decimal::ONE {
+            if let Some(sized) = self.price_sets(basket, asks, shares, budget) {
+                return Some(sized);
+            }
+            shares = (shares / Decimal::TWO).floor();
+        }
+        None
+    }
+
+    /// Price `shares` sets at VWAP; None if any leg is too thin or profit/budget fail
+    fn price_sets(
+        &self,
+        basket: &ArbBasket,
+        asks: &[Vec<(Decimal, Decimal)>],
+        shares: Decimal,
+        budget: Decimal,
+    ) -> Option<SizedBasket> {
+        let leg_costs = asks
+            .iter()
+            .map(|levels| fill_cost(levels, shares))
+            .collect::<Option<Vec<_>>>()?;
+        let leg_limits = asks
+            .iter()
+            .map(|levels| worst_ask(levels, shares))
+            .collect::<Option<Vec<_>>>()?;
+        let total_cost: Decimal = leg_costs.iter().sum();
+        let total_fees = total_cost * self.fee_rate;
+        if total_cost + total_fees > budget {
+            return None;
+        }
+
+        let expected_profit = basket.payout_per_set * shares - total_cost - total_fees;
+        if expected_profit / shares < self.min_profit_per_set() {
+            return None;
+        }
+
+        Some(SizedBasket {
+            shares,
+            leg_costs,
+            leg_limits,
+            total_cost,
+            total_fees,
+            expected_profit,
+        })
+    }
+
+    fn min_profit_per_set(&self) -> Decimal {
+        Decimal::from_f64(self.config.min_profit_per_set).unwrap_or_default()
+    }
+}
+
+/// Guaranteed payout for one share of every leg of an N-outcome event
+pub fn payout_per_set(side: Side, outcomes: usize) -> Decimal {
+    match side {
+        Side::Yes => Decimal::ONE,
+        Side::No => Decimal::from(outcomes.saturating_sub(1)),
+    }
+}
+
+/// USDC needed to buy `shares` walking ask levels best-first; None if the book is too thin
+pub fn fill_cost(asks: &[(Decimal, Decimal)], shares: Decimal) -> Option<Decimal> {
+    let mut remaining = shares;
+    let mut cost = Decimal::ZERO;
+    for (price, size) in asks {
+        let take = remaining.min(*size);
+        cost += take * price;
+        remaining -= take;
+        if remaining <= Decimal::ZERO {
+            return Some(cost);
+        }
+    }
+    None
+}
+
+/// Price of the last ask level reached when buying `shares` best-first; None if the book is too thin
+pub fn worst_ask(asks: &[(Decimal, Decimal)], shares: Decimal) -> Option<Decimal> {
+    let mut remaining = shares;
+    for (price, size) in asks {
+        remaining -= remaining.min(*size);
+        if remaining <= Decimal::ZERO {
+            return Some(*price);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventOutcome;
+    use rust_decimal_macros::dec;
+
+    fn outcome(label: &str, yes: Decimal, no: Decimal) -> EventOutcome {
+        EventOutcome {
+            market_id: label.to_string(),
+            condition_id: format!("0x{}", label),
+            label: label.to_string(),
+            question_index: None,
+            yes_price: yes,
+            no_price: no,
+            yes_token_id: Some(format!("{}-yes", label)),
+            no_token_id: Some(format!("{}-no", label)),
+            liquidity: dec!(10000),
+        }
+    }
+
+    fn event(outcomes: Vec<EventOutcome>) -> NegRiskEvent {
+        NegRiskEvent::new("e1".into(), None, "Who wins?".into(), "who-wins".into(), None, outcomes)
+    }
+
+    fn strategy(fee_bps: u32) -> NegRiskArbStrategy {
+        NegRiskArbStrategy::new(NegRiskArbConfig::default(), fee_bps)
+    }
+
+    #[test]
+    fn test_yes_basket_under_one_dollar() {
+        let e = event(vec![
+            outcome("a", dec!(0.50), dec!(0.50)),
+            outcome("b", dec!(0.30), dec!(0.70)),
+            outcome("c", dec!(0.15), dec!(0.85)),
+        ]);
+        let baskets = strategy(0).find_baskets(&[e]);
+        assert_eq!(baskets.len(), 1);
+        assert_eq!(baskets[0].side, Side::Yes);
+        assert_eq!(baskets[0].profit_per_set, dec!(0.05));
+    }
+
+    #[test]
+    fn test_no_basket_pays_n_minus_one() {
+        let e = event(vec![
+            outcome("a", dec!(0.40), dec!(0.55)),
+            outcome("b", dec!(0.35), dec!(0.60)),
+            outcome("c", dec!(0.25), dec!(0.75)),
+        ]);
+        let baskets = strategy(0).find_baskets(&[e]);
+        assert_eq!(baskets.len(), 1);
+        assert_eq!(baskets[0].side, Side::No);
+        assert_eq!(baskets[0].payout_per_set, dec!(2));
+        assert_eq!(baskets[0].profit_per_set, dec!(0.10));
+    }
+
+    #[test]
+    fn test_incomplete_event_skipped() {
+        let mut e = event(vec![
+            outcome("a", dec!(0.50), dec!(0.50)),
+            outcome("b", dec!(0.30), dec!(0.70)),
+        ]);
+        e.dropped_outcomes = 1;
+        assert!(strategy(0).find_baskets(&[e]).is_empty());
+    }
+
+    #[test]
+    fn test_fees_remove_thin_edge() {
+        let e = event(vec![
+            outcome("a", dec!(0.50), dec!(0.50)),
+            outcome("b", dec!(0.48), dec!(0.52)),
+        ]);
+        // 2c edge before fees, 2% of 0.98 = 1.96c fees -> below 1c minimum
+        assert!(strategy(200).find_baskets(&[e]).is_empty());
+    }
+
+    #[test]
+    fn test_fill_cost_walks_levels() {
+        let asks = vec![(dec!(0.30), dec!(10)), (dec!(0.32), dec!(10))];
+        assert_eq!(fill_cost(&asks, dec!(15)), Some(dec!(4.60)));
+        assert_eq!(fill_cost(&asks, dec!(25)), None);
+    }
+
+    #[test]
+    fn test_worst_ask_is_last_level_reached() {
+        let asks = vec![(dec!(0.30), dec!(10)), (dec!(0.32), dec!(10))];
+        assert_eq!(worst_ask(&asks, dec!(10)), Some(dec!(0.30)));
+        assert_eq!(worst_ask(&asks, dec!(15)), Some(dec!(0.32)));
+        assert_eq!(worst_ask(&asks, dec!(25)), None);
+    }
+
+    #[test]
+    fn test_size_basket_limited_by_thinnest_leg_and_profit() {
+        let e = event(vec![
+            outcome("a", dec!(0.50), dec!(0.50)),
+            outcome("b", dec!(0.40), dec!(0.60)),
+        ]);
+        let s = strategy(0);
+        let basket = s.find_baskets(&[e]).remove(0);
+        let asks = vec![
+            vec![(dec!(0.50), dec!(20)), (dec!(0.58), dec!(100))],
+            vec![(dec!(0.40), dec!(100))],
+        ];
+        let sized = s.size_basket(&basket, &asks, dec!(1000)).unwrap();
+        // Capped at max_sets (50): leg a costs 0.50*20 + 0.58*30 = 27.4, VWAP profit 5.2c/set
+        assert_eq!(sized.shares, dec!(50));
+        assert_eq!(sized.leg_limits, vec![dec!(0.58), dec!(0.40)]);
+        assert!(sized.expected_profit > Decimal::ZERO);
+
+        // A tight budget caps the set count
+        let sized = s.size_basket(&basket, &asks, dec!(9)).unwrap();
+        assert_eq!(sized.shares, dec!(10));
+    }
+}
@@ -30,6 +30,7 @@ impl SniperStrategy {
                 hours >= self.config.min_hours && hours <= self.config.max_hours
             })
             .filter(|m| m.liquidity >= dec!(1000))
+            .filter(|m| self.config.taxonomy.allows(&m.taxonomy))
             .filter_map(|m| self.evaluate_market(m))
             .collect();
 
@@ -120,6 +121,7 @@ impl SniperStrategy {
             neg_risk: market.neg_risk,
             meets_criteria: true,
             holders: None,
+            taxonomy: market.taxonomy.clone(),
         })
     }
 
@@ -0,0 +1,399 @@
+//! Market taxonomy
+//!
+//! Gamma describes markets with free-form event tags, an optional series
+//! (recurring events such as a league season or a daily price market) and a
+//! mostly empty `category`. This module normalizes those into a fixed
+//! category/subcategory hierarchy that strategies and risk limits filter on.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Top-level market category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Sports,
+    Crypto,
+    Politics,
+    World,
+    Economics,
+    Tech,
+    Science,
+    Weather,
+    Culture,
+    #[default]
+    Other,
+}
+
+impl Category {
+    pub const ALL: [Category; 10] = [
+        Category::Sports,
+        Category::Crypto,
+        Category::Politics,
+        Category::World,
+        Category::Economics,
+        Category::Tech,
+        Category::Science,
+        Category::Weather,
+        Category::Culture,
+        Category::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Category::Sports => "sports",
+            Category::Crypto => "crypto",
+            Category::Politics => "politics",
+            Category::World => "world",
+            Category::Economics => "economics",
+            Category::Tech => "tech",
+            Category::Science => "science",
+            Category::Weather => "weather",
+            Category::Culture => "culture",
+            Category::Other => "other",
+        }
+    }
+
+    /// Display label (also what `TrackedMarket::category` carries)
+    pub fn label(&self) -> &'static str {
+        match self {
+            Category::Sports => "Sports",
+            Category::Crypto => "Crypto",
+            Category::Politics => "Politics",
+            Category::World => "World",
+            Category::Economics => "Economics",
+            Category::Tech => "Tech",
+            Category::Science => "Science",
+            Category::Weather => "Weather",
+            Category::Culture => "Culture",
+            Category::Other => "Other",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        Self::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+
+    /// Position in `ALL`, used to break ties between equally tagged categories
+    fn rank(&self) -> usize {
+        Self::ALL.iter().position(|c| c == self).unwrap_or(usize::MAX)
+    }
+}
+
+/// Parse a comma-separated category list, ignoring unknown entries
+pub fn parse_categories(s: &str) -> Vec<Category> {
+    s.split(',').filter_map(Category::parse).collect()
+}
+
+/// Gamma tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketTag {
+    pub id: Option<String>,
+    pub label: String,
+    pub slug: String,
+}
+
+/// Gamma series the market's event belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketSeries {
+    pub id: String,
+    pub slug: Option<String>,
+    pub title: Option<String>,
+}
+
+/// Normalized classification of a market or event
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketTaxonomy {
+    pub category: Category,
+    /// Most specific known tag within the category (e.g. "NBA", "Bitcoin")
+    pub subcategory: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<MarketTag>,
+    #[serde(default)]
+    pub series: Option<MarketSeries>,
+}
+
+/// Tag slugs that decide a category. Slugs equal to the category name are
+/// roots and never become a subcategory.
+const CATEGORY_TAGS: &[(&str, Category)] = &[
+    // Sports
+    ("sports", Category::Sports),
+    ("nba", Category::Sports),
+    ("wnba", Category::Sports),
+    ("basketball", Category::Sports),
+    ("nfl", Category::Sports),
+    ("football", Category::Sports),
+    ("cfb", Category::Sports),
+    ("ncaab", Category::Sports),
+    ("cbb", Category::Sports),
+    ("mlb", Category::Sports),
+    ("baseball", Category::Sports),
+    ("nhl", Category::Sports),
+    ("hockey", Category::Sports),
+    ("soccer", Category::Sports),
+    ("epl", Category::Sports),
+    ("premier-league", Category::Sports),
+    ("la-liga", Category::Sports),
+    ("bundesliga", Category::Sports),
+    ("serie-a", Category::Sports),
+    ("ligue-1", Category::Sports),
+    ("champions-league", Category::Sports),
+    ("mls", Category::Sports),
+    ("tennis", Category::Sports),
+    ("golf", Category::Sports),
+    ("ufc", Category::Sports),
+    ("mma", Category::Sports),
+    ("boxing", Category::Sports),
+    ("f1", Category::Sports),
+    ("formula-1", Category::Sports),
+    ("cricket", Category::Sports),
+    ("esports", Category::Sports),
+    ("olympics", Category::Sports),
+    // Crypto
+    ("crypto", Category::Crypto),
+    ("crypto-prices", Category::Crypto),
+    ("bitcoin", Category::Crypto),
+    ("ethereum", Category::Crypto),
+    ("solana", Category::Crypto),
+    ("xrp", Category::Crypto),
+    ("dogecoin", Category::Crypto),
+    ("memecoins", Category::Crypto),
+    ("airdrops", Category::Crypto),
+    ("stablecoins", Category::Crypto),
+    // Politics
+    ("politics", Category::Politics),
+    ("elections", Category::Politics),
+    ("us-politics", Category::Politics),
+    ("us-election", Category::Politics),
+    ("trump", Category::Politics),
+    ("congress", Category::Politics),
+    ("senate", Category::Politics),
+    ("primaries", Category::Politics),
+    ("global-elections", Category::Politics),
+    // World
+    ("world", Category::World),
+    ("geopolitics", Category::World),
+    ("middle-east", Category::World),
+    ("ukraine", Category::World),
+    ("russia", Category::World),
+    ("china", Category::World),
+    ("israel", Category::World),
+    // Economics
+    ("economics", Category::Economics),
+    ("economy", Category::Economics),
+    ("business", Category::Economics),
+    ("finance", Category::Economics),
+    ("fed", Category::Economics),
+    ("fed-rates", Category::Economics),
+    ("inflation", Category::Economics),
+    ("stocks", Category::Economics),
+    ("earnings", Category::Economics),
+    // Tech
+    ("tech", Category::Tech),
+    ("ai", Category::Tech),
+    ("big-tech", Category::Tech),
+    // Science
+    ("science", Category::Science),
+    ("space", Category::Science),
+    ("climate", Category::Science),
+    ("health", Category::Science),
+    // Weather
+    ("weather", Category::Weather),
+    ("temperature", Category::Weather),
+    ("hurricanes", Category::Weather),
+    // Culture
+    ("culture", Category::Culture),
+    ("pop-culture", Category::Culture),
+    ("movies", Category::Culture),
+    ("music", Category::Culture),
+    ("awards", Category::Culture),
+    ("celebrities", Category::Culture),
+    ("tv", Category::Culture),
+];
+
+fn category_for_slug(slug: &str) -> Option<Category> {
+    CATEGORY_TAGS
+        .iter()
+        .find(|(s, _)| *s == slug)
+        .map(|(_, c)| *c)
+}
+
+/// Lowercase, dash-separated form used to match free-form labels against tag slugs
+fn slugify(s: &str) -> String {
+    s.trim()
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Classify a market from its tags, series and Gamma category.
+///
+/// The category with the most matching tags wins (ties go to the earlier
+/// category in `Category::ALL`). The series slug counts as one more tag; the
+/// Gamma category is only used when no tag is recognized.
+pub fn classify(
+    tags: Vec<MarketTag>,
+    series: Option<MarketSeries>,
+    gamma_category: Option<&str>,
+) -> MarketTaxonomy {
+    let mut votes: HashMap<Category, usize> = HashMap::new();
+    let mut candidates = tags.iter().map(|t| slugify(&t.slug)).collect::<Vec<_>>();
+    if let Some(slug) = series.as_ref().and_then(|s| s.slug.as_deref()) {
+        candidates.push(slugify(slug));
+    }
+    for slug in &candidates {
+        if let Some(category) = category_for_slug(slug) {
+            *votes.entry(category).or_default() += 1;
+        }
+    }
+
+    let category = Category::ALL
+        .into_iter()
+        .filter(|c| votes.contains_key(c))
+        .max_by(|a, b| votes[a].cmp(&votes[b]).then_with(|| b.rank().cmp(&a.rank())))
+        .or_else(|| gamma_category.map(slugify).and_then(|s| category_for_slug(&s)))
+        .unwrap_or_default();
+
+    let subcategory = tags
+        .iter()
+        .find(|t| {
+            let slug = slugify(&t.slug);
+            slug != category.as_str() && category_for_slug(&slug) == Some(category)
+        })
+        .map(|t| t.label.clone());
+
+    MarketTaxonomy {
+        category,
+        subcategory,
+        tags,
+        series,
+    }
+}
+
+impl MarketTaxonomy {
+    /// Taxonomy for a market whose category is known up front
+    pub fn of(category: Category, subcategory: Option<String>) -> Self {
+        Self {
+            category,
+            subcategory,
+            ..Default::default()
+        }
+    }
+
+    /// Category label for `TrackedMarket::category`, `None` when unclassified
+    pub fn category_label(&self) -> Option<String> {
+        (self.category != Category::Other).then(|| self.category.label().to_string())
+    }
+
+    /// "category/subcategory" path (e.g. "sports/nba")
+    pub fn path(&self) -> String {
+        match &self.subcategory {
+            Some(sub) => format!("{}/{}", self.category.as_str(), slugify(sub)),
+            None => self.category.as_str().to_string(),
+        }
+    }
+
+    pub fn has_tag(&self, slug: &str) -> bool {
+        self.tags.iter().any(|t| t.slug.eq_ignore_ascii_case(slug))
+    }
+}
+
+/// Category/tag filter applied by strategies and auto-trading risk limits
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaxonomyFilter {
+    /// Only these categories (all when empty)
+    #[serde(default)]
+    pub include: Vec<Category>,
+    #[serde(default)]
+    pub exclude: Vec<Category>,
+    /// Tag slugs to skip regardless of category
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+}
+
+impl TaxonomyFilter {
+    pub fn allows(&self, taxonomy: &MarketTaxonomy) -> bool {
+        if !self.include.is_empty() && !self.include.contains(&taxonomy.category) {
+            return false;
+        }
+        if self.exclude.contains(&taxonomy.category) {
+            return false;
+        }
+        !self.exclude_tags.iter().any(|t| taxonomy.has_tag(t))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.exclude_tags.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(label: &str) -> MarketTag {
+        MarketTag {
+            id: None,
+            label: label.to_string(),
+            slug: slugify(label),
+        }
+    }
+
+    #[test]
+    fn test_classify_from_tags() {
+        let t = classify(vec![tag("Sports"), tag("NBA"), tag("Games")], None, None);
+        assert_eq!(t.category, Category::Sports);
+        assert_eq!(t.subcategory.as_deref(), Some("NBA"));
+        assert_eq!(t.path(), "sports/nba");
+
+        // Majority wins over a single stray tag
+        let t = classify(vec![tag("Politics"), tag("Crypto"), tag("Trump")], None, None);
+        assert_eq!(t.category, Category::Politics);
+        assert_eq!(t.subcategory.as_deref(), Some("Trump"));
+    }
+
+    #[test]
+    fn test_classify_fallbacks() {
+        let series = MarketSeries {
+            id: "1".to_string(),
+            slug: Some("bitcoin".to_string()),
+            title: None,
+        };
+        assert_eq!(classify(vec![tag("Daily")], Some(series), None).category, Category::Crypto);
+        assert_eq!(classify(vec![], None, Some("Pop Culture")).category, Category::Culture);
+
+        let t = classify(vec![tag("Mentions")], None, None);
+        assert_eq!(t.category, Category::Other);
+        assert_eq!(t.category_label(), None);
+    }
+
+    #[test]
+    fn test_filter() {
+        let nba = classify(vec![tag("Sports"), tag("NBA")], None, None);
+        let btc = classify(vec![tag("Crypto"), tag("Bitcoin")], None, None);
+
+        let no_sports = TaxonomyFilter {
+            exclude: vec![Category::Sports],
+            ..Default::default()
+        };
+        assert!(!no_sports.allows(&nba));
+        assert!(no_sports.allows(&btc));
+
+        let crypto_only = TaxonomyFilter {
+            include: vec![Category::Crypto],
+            exclude_tags: vec!["bitcoin".to_string()],
+            ..Default::default()
+        };
+        assert!(!crypto_only.allows(&nba));
+        assert!(!crypto_only.allows(&btc));
+        assert!(TaxonomyFilter::default().allows(&nba));
+    }
+
+    #[test]
+    fn test_parse_categories() {
+        assert_eq!(parse_categories("sports, Crypto,bogus"), vec![Category::Sports, Category::Crypto]);
+    }
+}
@@ -2,6 +2,7 @@
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use crate::taxonomy::{Category, MarketTaxonomy};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -28,6 +29,21 @@ pub struct TrackedMarket {
     pub hours_until_close: Option<f64>,
     /// Whether this is a neg-risk market (uses different exchange contract)
     pub neg_risk: bool,
+    /// Gamma event this market belongs to (outcomes of a neg-risk event share it)
+    #[serde(default)]
+    pub event_id: Option<String>,
+    /// NegRiskAdapter market ID shared by all outcomes of a neg-risk event
+    #[serde(default)]
+    pub neg_risk_market_id: Option<String>,
+    /// Outcome label within a multi-outcome event (e.g. "Lakers")
+    #[serde(default)]
+    pub group_item_title: Option<String>,
+    /// UMA question ID; for neg-risk markets this is the adapter market ID with the outcome index as last byte
+    #[serde(default)]
+    pub question_id: Option<String>,
+    /// Normalized category hierarchy plus the event's tags and series
+    #[serde(default)]
+    pub taxonomy: MarketTaxonomy,
 }
 
 impl TrackedMarket {
@@ -42,13 +58,10 @@ impl TrackedMarket {
 
     /// Check if this is a fast-resolving market based on category/source
     pub fn is_fast_resolution(&self) -> bool {
-        let fast_categories = ["Sports", "Crypto", "NBA", "NFL", "MLB", "Soccer"];
         let fast_sources = ["chainlink", "espn", "official", "ap news", "reuters"];
 
-        if let Some(cat) = &self.category {
-            if fast_categories.iter().any(|c| cat.to_lowercase().contains(&c.to_lowercase())) {
-                return true;
-            }
+        if matches!(self.taxonomy.category, Category::Sports | Category::Crypto) {
+            return true;
         }
 
         if let Some(src) = &self.resolution_source {
@@ -62,6 +75,124 @@ impl TrackedMarket {
     }
 }
 
+/// One outcome of a neg-risk event. Each outcome is its own binary YES/NO market.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventOutcome {
+    pub market_id: String,
+    pub condition_id: String,
+    /// Outcome label (falls back to the market question)
+    pub label: String,
+    /// Index of this outcome within the NegRiskAdapter market (for conversions)
+    pub question_index: Option<u8>,
+    pub yes_price: Decimal,
+    pub no_price: Decimal,
+    pub yes_token_id: Option<String>,
+    pub no_token_id: Option<String>,
+    pub liquidity: Decimal,
+}
+
+/// A neg-risk event: mutually exclusive outcomes where exactly one resolves YES
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegRiskEvent {
+    pub event_id: String,
+    /// NegRiskAdapter market ID (needed for converting NO positions)
+    pub neg_risk_market_id: Option<String>,
+    pub title: String,
+    pub slug: String,
+    pub end_date: Option<DateTime<Utc>>,
+    pub outcomes: Vec<EventOutcome>,
+    /// Sum of YES prices across outcomes (1.00 when fairly priced)
+    pub yes_price_sum: Decimal,
+    /// Sum of NO prices across outcomes (N - 1 when fairly priced)
+    pub no_price_sum: Decimal,
+    /// Taxonomy of the event (taken from its outcome markets)
+    #[serde(default)]
+    pub taxonomy: MarketTaxonomy,
+    /// Outcome markets left out as inactive, closed or unparseable. Baskets are
+    /// only complete (and only pay out for sure) when this is 0.
+    #[serde(default)]
+    pub dropped_outcomes: usize,
+}
+
+impl NegRiskEvent {
+    pub fn new(
+        event_id: String,
+        neg_risk_market_id: Option<String>,
+        title: String,
+        slug: String,
+        end_date: Option<DateTime<Utc>>,
+        outcomes: Vec<EventOutcome>,
+    ) -> Self {
+        let yes_price_sum = outcomes.iter().map(|o| o.yes_price).sum();
+        let no_price_sum = outcomes.iter().map(|o| o.no_price).sum();
+        Self {
+            event_id,
+            neg_risk_market_id,
+            title,
+            slug,
+            end_date,
+            outcomes,
+            yes_price_sum,
+            no_price_sum,
+            taxonomy: MarketTaxonomy::default(),
+            dropped_outcomes: 0,
+        }
+    }
+
+    /// YES price sum minus 1.00 (positive = outcomes overpriced, negative = underpriced)
+    pub fn price_deviation(&self) -> Decimal {
+        self.yes_price_sum - Decimal::ONE
+    }
+
+    /// Whether the YES price sum has drifted further than `tolerance` from 1.00
+    pub fn is_mispriced(&self, tolerance: Decimal) -> bool {
+        self.price_deviation().abs() > tolerance
+    }
+}
+
+/// Outcome index of a neg-risk question: its question ID is the adapter market ID
+/// with the last byte replaced by the index.
+pub fn neg_risk_question_index(neg_risk_market_id: &str, question_id: &str) -> Option<u8> {
+    let market = neg_risk_market_id.trim_start_matches("0x").to_lowercase();
+    let question = question_id.trim_start_matches("0x").to_lowercase();
+    if market.len() != 64 || question.len() != 64 || market[..62] != question[..62] {
+        return None;
+    }
+    u8::from_str_radix(&question[62..], 16).ok()
+}
+
+/// One leg of a neg-risk basket: the chosen side of a single outcome market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasketLeg {
+    pub condition_id: String,
+    pub label: String,
+    pub token_id: String,
+    /// Price used to evaluate the basket (Gamma price when screening, VWAP once sized)
+    pub price: Decimal,
+}
+
+/// A basket that buys the same side of every outcome in a neg-risk event.
+///
+/// Exactly one outcome resolves YES, so one share of every YES token pays $1 and
+/// one share of every NO token pays N - 1. When the legs cost less than that
+/// after fees, the basket locks in the difference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbBasket {
+    pub event_id: String,
+    pub title: String,
+    pub neg_risk_market_id: Option<String>,
+    pub side: Side,
+    pub legs: Vec<BasketLeg>,
+    /// Sum of leg prices for one share of every leg
+    pub cost_per_set: Decimal,
+    /// Guaranteed payout for one share of every leg
+    pub payout_per_set: Decimal,
+    /// Taker fees for one set
+    pub fee_per_set: Decimal,
+    /// payout - cost - fees
+    pub profit_per_set: Decimal,
+}
+
 /// Trading side
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Side {
@@ -85,6 +216,7 @@ pub enum StrategyType {
     Dispute,
     MillionairesClub,
     MintMaker,
+    NegRiskArb,
 }
 
 impl fmt::Display for StrategyType {
@@ -94,6 +226,7 @@ impl fmt::Display for StrategyType {
             StrategyType::Dispute => write!(f, "Dispute"),
             StrategyType::MillionairesClub => write!(f, "MC"),
             StrategyType::MintMaker => write!(f, "MintMaker"),
+            StrategyType::NegRiskArb => write!(f, "NegRiskArb"),
         }
     }
 }
@@ -272,6 +405,9 @@ pub struct Opportunity {
     /// Top holders on each side of the market
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub holders: Option<MarketHolders>,
+    /// Normalized category hierarchy plus the event's tags and series
+    #[serde(default)]
+    pub taxonomy: MarketTaxonomy,
 }
 
 fn default_meets_criteria() -> bool {
@@ -306,81 +442,14 @@ impl Opportunity {
             && self.time_to_close_hours.map(|h| h <= 12.0).unwrap_or(false)
     }
 
-    /// Helper for word boundary matching without regex
-    fn contains_word(text: &str, word: &str) -> bool {
-        let text = text.to_lowercase();
-        let word = word.to_lowercase();
-
-        for (i, _) in text.match_indices(&word) {
-            let before_ok = i == 0 || !text.chars().nth(i - 1).unwrap_or(' ').is_alphanumeric();
-            let after_ok = i + word.len() >= text.len()
-                || !text.chars().nth(i + word.len()).unwrap_or(' ').is_alphanumeric();
-            if before_ok && after_ok {
-                return true;
-            }
-        }
-        false
-    }
-
     /// Check if opportunity is crypto-related
     pub fn is_crypto(&self) -> bool {
-        let q = &self.question;
-        let cat = self.category.as_deref().unwrap_or("").to_lowercase();
-
-        if cat == "crypto" || cat == "cryptocurrency" {
-            return true;
-        }
-
-        // Match crypto keywords with word boundaries
-        let crypto_keywords = [
-            "bitcoin", "btc", "ethereum", "eth", "solana", "sol", "xrp", "ripple",
-            "dogecoin", "doge", "cardano", "ada", "polkadot", "dot", "avalanche",
-            "avax", "chainlink", "link", "polygon", "matic", "litecoin", "ltc",
-            "crypto", "cryptocurrency",
-        ];
-
-        crypto_keywords.iter().any(|kw| Self::contains_word(q, kw))
+        self.taxonomy.category == Category::Crypto
     }
 
     /// Check if opportunity is sports-related
     pub fn is_sports(&self) -> bool {
-        let q = self.question.to_lowercase();
-        let cat = self.category.as_deref().unwrap_or("").to_lowercase();
-
-        if cat == "sports" {
-            return true;
-        }
-
-        // Sports keywords (simple contains is fine for multi-word phrases)
-        let sports_keywords = [
-            "spread:", "moneyline", "over/under", "fight", "fighter", "knockout",
-            "submission", "rounds", "decision", "unanimous",
-            "nba", "nfl", "mlb", "nhl", "mls", "ufc", "bellator", "pga", "atp", "wta",
-            "premier league", "la liga", "serie a", "bundesliga", "ligue 1",
-            "champions league", "europa league", "super bowl", "world series",
-            "stanley cup", "world cup",
-        ];
-
-        if sports_keywords.iter().any(|kw| q.contains(kw)) {
-            return true;
-        }
-
-        // Check for "vs" pattern (Team vs Team)
-        if q.contains(" vs ") || q.contains(" vs.") {
-            return true;
-        }
-
-        // Check for KO/TKO with word boundaries
-        if Self::contains_word(&q, "ko") || Self::contains_word(&q, "tko") {
-            return true;
-        }
-
-        // Check for O/U pattern
-        if Self::contains_word(&q, "o/u") {
-            return true;
-        }
-
-        false
+        self.taxonomy.category == Category::Sports
     }
 }
 
@@ -419,8 +488,11 @@ impl Opportunity {
             StrategyType::ResolutionSniper => {
                 self.recalculate_sniper(price_f64)
             }
-            StrategyType::Dispute | StrategyType::MillionairesClub | StrategyType::MintMaker => {
-                // Disputes, MC, and MintMaker don't use sniper price filtering
+            StrategyType::Dispute
+            | StrategyType::MillionairesClub
+            | StrategyType::MintMaker
+            | StrategyType::NegRiskArb => {
+                // Disputes, MC, MintMaker and baskets don't use sniper price filtering
                 true
             }
         };
@@ -603,6 +675,111 @@ pub struct Order {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Kind of on-chain operation tracked as a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OnchainJobKind {
+    Split,
+    Merge,
+    Redeem,
+    Convert,
+    SafeDeploy,
+    Approval,
+    Deposit,
+    Withdraw,
+    /// Several CTF calls bundled into one MultiSend Safe transaction
+    Batch,
+}
+
+impl OnchainJobKind {
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        Some(match s {
+            "Split" => Self::Split,
+            "Merge" => Self::Merge,
+            "Redeem" => Self::Redeem,
+            "Convert" => Self::Convert,
+            "SafeDeploy" => Self::SafeDeploy,
+            "Approval" => Self::Approval,
+            "Deposit" => Self::Deposit,
+            "Withdraw" => Self::Withdraw,
+            "Batch" => Self::Batch,
+            _ => return None,
+        })
+    }
+}
+
+/// On-chain job state
+///
+/// Queued -> Submitted -> Mined | Failed. A direct transaction that gets
+/// sped up is marked Replaced and continues as a new job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OnchainJobStatus {
+    /// Recorded, not yet accepted by the relay or node
+    Queued,
+    /// Accepted by the relay / broadcast, waiting to be mined
+    Submitted,
+    /// Included in a block
+    Mined,
+    /// Rejected, reverted or timed out
+    Failed,
+    /// Superseded by a replacement transaction at the same nonce
+    Replaced,
+}
+
+impl OnchainJobStatus {
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        Some(match s {
+            "Queued" => Self::Queued,
+            "Submitted" => Self::Submitted,
+            "Mined" => Self::Mined,
+            "Failed" => Self::Failed,
+            "Replaced" => Self::Replaced,
+            _ => return None,
+        })
+    }
+
+    /// No further transitions expected (Replaced can still turn Mined if the original wins)
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Mined | Self::Failed)
+    }
+}
+
+/// A persisted on-chain job (relay Safe transaction or direct EOA transaction)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnchainJob {
+    pub id: i64,
+    pub wallet_address: String,
+    pub kind: OnchainJobKind,
+    pub status: OnchainJobStatus,
+    /// Human-readable summary ("Merge 25 sets", "Deposit 100 USDC")
+    pub description: String,
+    /// What the job acts on (condition ID, pair ID, ...)
+    pub reference: Option<String>,
+    /// Relay transaction ID for Safe transactions
+    pub relay_tx_id: Option<String>,
+    /// On-chain transaction hash, once known
+    pub tx_hash: Option<String>,
+    /// Serialized transaction for direct sends (needed to speed them up)
+    #[serde(skip_serializing)]
+    pub payload: Option<String>,
+    /// Job this one replaced (speed-ups)
+    pub replaces_job_id: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A live position's market resolving, as seen by the ResolutionTracker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionOutcome {
+    pub position_id: i64,
+    pub wallet_address: String,
+    /// Condition ID of the resolved market, when Gamma reports it
+    pub condition_id: Option<String>,
+    pub side: Side,
+    pub won: bool,
+    pub resolved_at: DateTime<Utc>,
+}
+
 /// A tracked position
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
@@ -766,6 +943,63 @@ pub struct DisputeAlert {
     /// Expected value considering 50-50 outcome possibility
     #[serde(default)]
     pub expected_value: Option<Decimal>,
+    /// DVM vote timing and tally (only for disputes escalated to DvmVote)
+    #[serde(default)]
+    pub dvm: Option<DvmVoteInfo>,
+    /// Normalized category/tags of the disputed market
+    #[serde(default)]
+    pub taxonomy: MarketTaxonomy,
+}
+
+/// Phase of a UMA DVM price request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DvmPhase {
+    /// Request is waiting for the next voting round to start
+    Queued,
+    /// Voters are committing hidden votes (first 24h of the round)
+    Commit,
+    /// Voters are revealing votes (last 24h of the round); tally becomes visible
+    Reveal,
+    /// Round finished without a published price yet; the request may still roll
+    AwaitingSettlement,
+    /// The DVM returned a price
+    Resolved,
+}
+
+impl fmt::Display for DvmPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DvmPhase::Queued => write!(f, "Queued"),
+            DvmPhase::Commit => write!(f, "Commit"),
+            DvmPhase::Reveal => write!(f, "Reveal"),
+            DvmPhase::AwaitingSettlement => write!(f, "Awaiting settlement"),
+            DvmPhase::Resolved => write!(f, "Resolved"),
+        }
+    }
+}
+
+/// DVM vote state for a dispute that reached DvmVote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DvmVoteInfo {
+    pub phase: DvmPhase,
+    /// VotingV2 round the request is (or will be) voted in
+    pub round_id: u64,
+    /// When the commit phase of that round ends (unix seconds)
+    pub commit_ends_at: i64,
+    /// When the reveal phase ends, i.e. estimated DVM resolution (unix seconds)
+    pub reveal_ends_at: i64,
+    /// Times the request rolled to a later round (no quorum / no majority)
+    #[serde(default)]
+    pub roll_count: u32,
+    /// UMA tokens revealed so far (only after reveal starts)
+    #[serde(default)]
+    pub total_votes_revealed: Option<Decimal>,
+    /// Share of revealed votes supporting the proposed outcome (0.0-1.0)
+    #[serde(default)]
+    pub proposed_vote_share: Option<f64>,
+    /// Final DVM result: true if the proposed outcome was upheld
+    #[serde(default)]
+    pub proposed_upheld: Option<bool>,
 }
 
 fn default_dispute_round() -> u8 {
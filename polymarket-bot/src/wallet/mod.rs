@@ -1,9 +1,10 @@
 //! Wallet management module for multi-user support
 //!
-//! Provides wallet generation, import, and encrypted storage.
+//! Provides wallet generation, import, encrypted storage and withdrawal safeguards.
 
 mod generator;
 mod encryption;
+pub mod safeguards;
 
 pub use generator::{generate_wallet, wallet_from_private_key, GeneratedWallet};
 pub use encryption::{encrypt_private_key, decrypt_private_key, EncryptedKey};
@@ -0,0 +1,259 @@
+//! Withdrawal safeguards
+//!
+//! Pure checks applied before funds or keys leave the server:
+//! - a cool-down after the wallet password changes (withdrawals and key export)
+//! - a rolling 24h withdrawal limit, raised only after the same time-lock as
+//!   new allowlist addresses (lowering it applies at once)
+//! - destinations other than the wallet's own EOA must be on its allowlist and
+//!   past their time-lock
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use std::fmt;
+
+/// Window the daily withdrawal limit applies to
+pub const LIMIT_WINDOW_HOURS: i64 = 24;
+
+/// Allowlisted withdrawal destination
+#[derive(Debug, Clone)]
+pub struct AllowedDestination {
+    pub address: String,
+    /// When the time-lock for this address ends
+    pub active_at: DateTime<Utc>,
+}
+
+/// A raised daily limit waiting out its time-lock
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingLimit {
+    /// The requested limit (`None` = the server maximum)
+    pub limit: Option<Decimal>,
+    /// When the new limit takes effect
+    pub active_at: DateTime<Utc>,
+}
+
+/// A wallet's own daily limit setting
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WithdrawLimitSetting {
+    /// Limit in force (`None` = the server maximum)
+    pub limit: Option<Decimal>,
+    pub pending: Option<PendingLimit>,
+}
+
+impl WithdrawLimitSetting {
+    /// The wallet's own limit at `now`, counting a pending raise once unlocked
+    pub fn in_force(&self, now: DateTime<Utc>) -> Option<Decimal> {
+        match &self.pending {
+            Some(pending) if now >= pending.active_at => pending.limit,
+            _ => self.limit,
+        }
+    }
+
+    /// A raise that has not unlocked yet
+    pub fn waiting(&self, now: DateTime<Utc>) -> Option<&PendingLimit> {
+        self.pending.as_ref().filter(|p| now < p.active_at)
+    }
+
+    /// Effective limit at `now`, capped at the server-wide one
+    pub fn effective(&self, server_limit: Decimal, now: DateTime<Utc>) -> Decimal {
+        capped(self.in_force(now), server_limit)
+    }
+
+    /// The setting after a change to `limit`: lowering applies at once (dropping any
+    /// pending raise), raising waits `delay`
+    pub fn request(&self, limit: Option<Decimal>, server_limit: Decimal, delay: Duration, now: DateTime<Utc>) -> Self {
+        let current = self.in_force(now);
+        if capped(limit, server_limit) > capped(current, server_limit) {
+            Self {
+                limit: current,
+                pending: Some(PendingLimit { limit, active_at: now + delay }),
+            }
+        } else {
+            Self { limit, pending: None }
+        }
+    }
+}
+
+fn capped(limit: Option<Decimal>, server_limit: Decimal) -> Decimal {
+    limit.map_or(server_limit, |l| l.min(server_limit))
+}
+
+/// Why a withdrawal or export was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafeguardViolation {
+    /// The password changed recently
+    PasswordCooldown { until: DateTime<Utc> },
+    /// The amount would exceed the rolling daily limit
+    DailyLimit { limit: Decimal, remaining: Decimal },
+    /// The destination is not on the allowlist
+    NotAllowlisted { address: String },
+    /// The destination was allowlisted too recently
+    TimeLocked { address: String, until: DateTime<Utc> },
+}
+
+impl fmt::Display for SafeguardViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafeguardViolation::PasswordCooldown { until } => write!(
+                f,
+                "Password was changed recently; withdrawals and key export are locked until {}",
+                until.to_rfc3339()
+            ),
+            SafeguardViolation::DailyLimit { limit, remaining } => write!(
+                f,
+                "Daily withdrawal limit of {} USDC reached ({} USDC remaining in the last 24h)",
+                limit, remaining
+            ),
+            SafeguardViolation::NotAllowlisted { address } => {
+                write!(f, "Destination {} is not on this wallet's withdrawal allowlist", address)
+            }
+            SafeguardViolation::TimeLocked { address, until } => write!(
+                f,
+                "Destination {} was added recently and can receive withdrawals from {}",
+                address,
+                until.to_rfc3339()
+            ),
+        }
+    }
+}
+
+/// Refuse if the password changed less than `cooldown` ago
+pub fn check_password_cooldown(
+    password_changed_at: Option<DateTime<Utc>>,
+    cooldown: Duration,
+    now: DateTime<Utc>,
+) -> Result<(), SafeguardViolation> {
+    match password_changed_at {
+        Some(changed_at) if now < changed_at + cooldown => Err(SafeguardViolation::PasswordCooldown {
+            until: changed_at + cooldown,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Refuse if `amount` plus what was withdrawn in the last 24h exceeds `limit`
+pub fn check_daily_limit(
+    amount: Decimal,
+    withdrawn_last_24h: Decimal,
+    limit: Decimal,
+) -> Result<(), SafeguardViolation> {
+    if withdrawn_last_24h + amount > limit {
+        return Err(daily_limit_exceeded(withdrawn_last_24h, limit));
+    }
+    Ok(())
+}
+
+/// The violation reported once `withdrawn_last_24h` leaves no room for a withdrawal under `limit`
+pub fn daily_limit_exceeded(withdrawn_last_24h: Decimal, limit: Decimal) -> SafeguardViolation {
+    SafeguardViolation::DailyLimit {
+        limit,
+        remaining: (limit - withdrawn_last_24h).max(Decimal::ZERO),
+    }
+}
+
+/// Refuse destinations other than the wallet's own EOA unless allowlisted and unlocked
+pub fn check_destination(
+    destination: &str,
+    own_address: &str,
+    allowlist: &[AllowedDestination],
+    now: DateTime<Utc>,
+) -> Result<(), SafeguardViolation> {
+    if destination.eq_ignore_ascii_case(own_address) {
+        return Ok(());
+    }
+
+    let entry = allowlist
+        .iter()
+        .find(|a| a.address.eq_ignore_ascii_case(destination))
+        .ok_or_else(|| SafeguardViolation::NotAllowlisted {
+            address: destination.to_string(),
+        })?;
+
+    if now < entry.active_at {
+        return Err(SafeguardViolation::TimeLocked {
+            address: destination.to_string(),
+            until: entry.active_at,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_password_cooldown() {
+        let now = Utc::now();
+        let cooldown = Duration::hours(24);
+        assert!(check_password_cooldown(None, cooldown, now).is_ok());
+        assert!(check_password_cooldown(Some(now - Duration::hours(25)), cooldown, now).is_ok());
+        assert!(matches!(
+            check_password_cooldown(Some(now - Duration::hours(1)), cooldown, now),
+            Err(SafeguardViolation::PasswordCooldown { .. })
+        ));
+    }
+
+    #[test]
+    fn test_daily_limit() {
+        assert!(check_daily_limit(dec!(100), dec!(400), dec!(500)).is_ok());
+        assert_eq!(
+            check_daily_limit(dec!(101), dec!(400), dec!(500)),
+            Err(SafeguardViolation::DailyLimit { limit: dec!(500), remaining: dec!(100) })
+        );
+    }
+
+    #[test]
+    fn test_limit_raise_is_time_locked() {
+        let now = Utc::now();
+        let delay = Duration::hours(24);
+        let setting = WithdrawLimitSetting { limit: Some(dec!(100)), pending: None };
+
+        let raised = setting.request(Some(dec!(1000)), dec!(5000), delay, now);
+        assert_eq!(raised.effective(dec!(5000), now), dec!(100));
+        assert_eq!(raised.waiting(now).map(|p| p.active_at), Some(now + delay));
+        assert_eq!(raised.effective(dec!(5000), now + delay), dec!(1000));
+
+        // Clearing the limit means the server maximum: also a raise
+        let cleared = setting.request(None, dec!(5000), delay, now);
+        assert_eq!(cleared.effective(dec!(5000), now), dec!(100));
+        assert_eq!(cleared.effective(dec!(5000), now + delay), dec!(5000));
+    }
+
+    #[test]
+    fn test_limit_decrease_applies_at_once() {
+        let now = Utc::now();
+        let delay = Duration::hours(24);
+        let raised = WithdrawLimitSetting { limit: Some(dec!(100)), pending: None }
+            .request(Some(dec!(1000)), dec!(5000), delay, now);
+
+        // Lowering drops the pending raise
+        let lowered = raised.request(Some(dec!(50)), dec!(5000), delay, now + Duration::hours(1));
+        assert_eq!(lowered, WithdrawLimitSetting { limit: Some(dec!(50)), pending: None });
+
+        // Once unlocked, the raise is the baseline for the next change
+        let later = now + delay;
+        let lowered = raised.request(Some(dec!(500)), dec!(5000), delay, later);
+        assert_eq!(lowered.effective(dec!(5000), later), dec!(500));
+        assert!(lowered.pending.is_none());
+    }
+
+    #[test]
+    fn test_destination() {
+        let now = Utc::now();
+        let allowlist = vec![
+            AllowedDestination { address: "0xaaa".to_string(), active_at: now - Duration::hours(1) },
+            AllowedDestination { address: "0xbbb".to_string(), active_at: now + Duration::hours(1) },
+        ];
+        assert!(check_destination("0xOWN", "0xown", &allowlist, now).is_ok());
+        assert!(check_destination("0xAAA", "0xown", &allowlist, now).is_ok());
+        assert!(matches!(
+            check_destination("0xbbb", "0xown", &allowlist, now),
+            Err(SafeguardViolation::TimeLocked { .. })
+        ));
+        assert!(matches!(
+            check_destination("0xccc", "0xown", &allowlist, now),
+            Err(SafeguardViolation::NotAllowlisted { .. })
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 //! Discord webhook notifications for sniper opportunities
+//!
+//! Wallet events (fills, exits, security, ...) go through `services::notifications`.
 
 use crate::Opportunity;
 use reqwest::Client;
@@ -9,6 +9,8 @@ import type {
   AutoTradingSettings,
   AutoTradeLog,
   AutoTradingStats,
+  NegRiskEvent,
+  OnchainJob,
 } from '../types'
 
 const API_BASE = '/api'
@@ -104,7 +106,7 @@ export async function depositToSafe(
   sessionToken: string,
   password: string,
   amount: string
-): Promise<{ tx_hash: string; safe_address: string; amount: string }> {
+): Promise<{ tx_hash: string; job_id: number | null; safe_address: string; amount: string }> {
   return fetchJson(`${API_BASE}/wallet/deposit`, {
     method: 'POST',
     headers: {
@@ -114,18 +116,32 @@ export async function depositToSafe(
   })
 }
 
-// Withdraw USDC from Safe to EOA (generated wallets)
+// Withdraw USDC from Safe to the EOA, or to an allowlisted address (generated wallets)
 export async function withdrawFromSafe(
   sessionToken: string,
   password: string,
-  amount: string
-): Promise<{ transaction_id: string; safe_address: string; amount: string }> {
+  amount: string,
+  toAddress?: string
+): Promise<{ transaction_id: string; job_id: number | null; safe_address: string; to_address: string; amount: string }> {
   return fetchJson(`${API_BASE}/wallet/withdraw`, {
     method: 'POST',
     headers: {
       Authorization: `Bearer ${sessionToken}`,
     },
-    body: JSON.stringify({ password, amount }),
+    body: JSON.stringify({ password, amount, to_address: toAddress }),
+  })
+}
+
+// Recent on-chain jobs (deposits, withdrawals, merges, redeems) for the session wallet
+export async function getOnchainJobs(
+  sessionToken: string,
+  limit?: number
+): Promise<{ jobs: OnchainJob[]; total: number }> {
+  const url = limit ? `${API_BASE}/onchain/jobs?limit=${limit}` : `${API_BASE}/onchain/jobs`
+  return fetchJson(url, {
+    headers: {
+      Authorization: `Bearer ${sessionToken}`,
+    },
   })
 }
 
@@ -137,6 +153,20 @@ export async function getOpportunities(strategy?: string): Promise<Opportunity[]
   return fetchJson(url)
 }
 
+// Neg-risk events endpoint
+interface NegRiskEventsResponse {
+  events: NegRiskEvent[]
+  total: number
+  tolerance: string
+}
+
+export async function getNegRiskEvents(mispricedOnly = false): Promise<NegRiskEvent[]> {
+  const response = await fetchJson<NegRiskEventsResponse>(
+    `${API_BASE}/events/neg-risk${mispricedOnly ? '?mispriced=true' : ''}`
+  )
+  return response.events
+}
+
 // Positions endpoints
 interface PositionsResponse {
   positions: Position[]
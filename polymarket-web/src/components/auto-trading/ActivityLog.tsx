@@ -1,4 +1,4 @@
-import { History, TrendingUp, TrendingDown, ShoppingCart, Clock, Activity } from 'lucide-react'
+import { History, TrendingUp, TrendingDown, ShoppingCart, Clock, Activity, Coins } from 'lucide-react'
 import type { AutoTradeLog } from '../../types'
 
 interface Props {
@@ -17,6 +17,8 @@ function getActionIcon(action: string) {
       return <Activity className="w-4 h-4 text-yellow-500" />
     case 'time_exit':
       return <Clock className="w-4 h-4 text-blue-400" />
+    case 'auto_redeem':
+      return <Coins className="w-4 h-4 text-poly-green" />
     default:
       return <History className="w-4 h-4 text-gray-400" />
   }
@@ -34,6 +36,8 @@ function getActionLabel(action: string) {
       return 'Trailing Stop'
     case 'time_exit':
       return 'Time Exit'
+    case 'auto_redeem':
+      return 'Redeemed'
     default:
       return action
   }
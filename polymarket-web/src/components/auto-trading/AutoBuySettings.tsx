@@ -73,6 +73,18 @@ export function AutoBuySettings({ settings, onUpdate, disabled, isPending }: Pro
           >
             Sniper
           </button>
+          <button
+            onClick={() => handleStrategyToggle('neg_risk_arb')}
+            disabled={disabled || isPending}
+            title="Buy every outcome of a neg-risk event when the basket costs less than its payout after fees"
+            className={`px-3 py-1.5 rounded-lg text-sm transition ${
+              settings?.strategies?.includes('neg_risk_arb')
+                ? 'bg-poly-green/20 text-poly-green border border-poly-green'
+                : 'bg-gray-700 text-gray-400 border border-gray-600'
+            }`}
+          >
+            Neg-Risk Arb
+          </button>
         </div>
       </div>
 
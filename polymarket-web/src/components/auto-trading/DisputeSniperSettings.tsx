@@ -113,6 +113,137 @@ export function DisputeSniperSettings({ settings, onUpdate, disabled, isPending
           <p className="text-xs text-gray-500">
             Automatically sells if a dispute escalates from Proposed to Disputed or DVM Vote.
           </p>
+
+          {/* Trade DVM votes on the revealed tally */}
+          <div className="flex items-center justify-between">
+            <label className="text-sm text-gray-400">Trade DVM vote tally</label>
+            <button
+              onClick={() => onUpdate({ dispute_dvm_enabled: !settings?.dispute_dvm_enabled })}
+              disabled={disabled || isPending}
+              className={`relative w-11 h-6 rounded-full transition-colors ${
+                settings?.dispute_dvm_enabled ? 'bg-poly-green' : 'bg-gray-600'
+              }`}
+            >
+              <span
+                className={`absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                  settings?.dispute_dvm_enabled ? 'translate-x-5' : 'translate-x-0'
+                }`}
+              />
+            </button>
+          </div>
+          {settings?.dispute_dvm_enabled && (
+            <div>
+              <label className="text-sm text-gray-400 block mb-1">
+                Min vote share ({((settings?.dispute_dvm_min_vote_share || 0.65) * 100).toFixed(0)}%)
+              </label>
+              <input
+                type="range"
+                value={(settings?.dispute_dvm_min_vote_share || 0.65) * 100}
+                onChange={(e) => onUpdate({ dispute_dvm_min_vote_share: parseFloat(e.target.value) / 100 })}
+                disabled={disabled || isPending}
+                className="w-full"
+                min="51"
+                max="95"
+                step="1"
+              />
+            </div>
+          )}
+          <p className="text-xs text-gray-500">
+            During the DVM reveal phase, buys the proposed outcome once its share of revealed votes
+            reaches the threshold, and sells if the tally turns against your side.
+          </p>
+
+          {/* Take profit on convergence */}
+          <div className="flex items-center justify-between">
+            <label className="text-sm text-gray-400">Take profit on convergence</label>
+            <button
+              onClick={() => onUpdate({ dispute_take_profit_enabled: !settings?.dispute_take_profit_enabled })}
+              disabled={disabled || isPending}
+              className={`relative w-11 h-6 rounded-full transition-colors ${
+                settings?.dispute_take_profit_enabled ? 'bg-poly-green' : 'bg-gray-600'
+              }`}
+            >
+              <span
+                className={`absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                  settings?.dispute_take_profit_enabled ? 'translate-x-5' : 'translate-x-0'
+                }`}
+              />
+            </button>
+          </div>
+          {settings?.dispute_take_profit_enabled && !settings?.dispute_hold_to_settlement && (
+            <div>
+              <label className="text-sm text-gray-400 block mb-1">
+                Sell at price ({((settings?.dispute_take_profit_price || 0.97) * 100).toFixed(0)}¢)
+              </label>
+              <input
+                type="range"
+                value={(settings?.dispute_take_profit_price || 0.97) * 100}
+                onChange={(e) => onUpdate({ dispute_take_profit_price: parseFloat(e.target.value) / 100 })}
+                disabled={disabled || isPending}
+                className="w-full"
+                min="80"
+                max="99"
+                step="1"
+              />
+            </div>
+          )}
+
+          {/* Exit before a counter-proposal passes liveness */}
+          <div className="flex items-center justify-between">
+            <label className="text-sm text-gray-400">Exit on counter-proposal</label>
+            <button
+              onClick={() => onUpdate({ dispute_liveness_exit_enabled: !settings?.dispute_liveness_exit_enabled })}
+              disabled={disabled || isPending}
+              className={`relative w-11 h-6 rounded-full transition-colors ${
+                settings?.dispute_liveness_exit_enabled ? 'bg-poly-green' : 'bg-gray-600'
+              }`}
+            >
+              <span
+                className={`absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                  settings?.dispute_liveness_exit_enabled ? 'translate-x-5' : 'translate-x-0'
+                }`}
+              />
+            </button>
+          </div>
+          {settings?.dispute_liveness_exit_enabled && (
+            <div>
+              <label className="text-sm text-gray-400 block mb-1">Minutes before liveness expiry</label>
+              <input
+                type="number"
+                value={settings?.dispute_liveness_exit_minutes ?? 15}
+                onChange={(e) => onUpdate({ dispute_liveness_exit_minutes: parseInt(e.target.value) || 15 })}
+                disabled={disabled || isPending}
+                className="w-full bg-gray-700 border border-gray-600 rounded-lg px-3 py-2 text-base"
+                min="1"
+                step="1"
+              />
+            </div>
+          )}
+          <p className="text-xs text-gray-500">
+            Sells if a proposal for the other outcome is about to pass its challenge window undisputed.
+          </p>
+
+          {/* Hold to settlement */}
+          <div className="flex items-center justify-between">
+            <label className="text-sm text-gray-400">Hold to settlement</label>
+            <button
+              onClick={() => onUpdate({ dispute_hold_to_settlement: !settings?.dispute_hold_to_settlement })}
+              disabled={disabled || isPending}
+              className={`relative w-11 h-6 rounded-full transition-colors ${
+                settings?.dispute_hold_to_settlement ? 'bg-poly-green' : 'bg-gray-600'
+              }`}
+            >
+              <span
+                className={`absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                  settings?.dispute_hold_to_settlement ? 'translate-x-5' : 'translate-x-0'
+                }`}
+              />
+            </button>
+          </div>
+          <p className="text-xs text-gray-500">
+            Never sells dispute positions on price; winning tokens are redeemed automatically once the
+            assertion settles.
+          </p>
         </div>
       )}
     </div>
@@ -103,6 +103,14 @@ export function useWebSocket() {
           case 'mint_maker_status':
             setMintMakerStatus(message.data)
             break
+          case 'onchain_job':
+            // Dispatch on-chain job updates for deposit/withdraw/merge tracking
+            window.dispatchEvent(
+              new CustomEvent('onchain-job', {
+                detail: message.data,
+              })
+            )
+            break
           case 'error':
             console.error('WebSocket error:', message.data.message)
             break
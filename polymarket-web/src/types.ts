@@ -39,6 +39,25 @@ export interface Opportunity {
   meets_criteria?: boolean
   /** Top holders on each side of the market */
   holders?: MarketHolders | null
+  /** Category hierarchy, tags and series from Gamma */
+  taxonomy?: MarketTaxonomy
+}
+
+export type MarketCategory =
+  | 'sports' | 'crypto' | 'politics' | 'world' | 'economics'
+  | 'tech' | 'science' | 'weather' | 'culture' | 'other'
+
+export interface MarketTaxonomy {
+  category: MarketCategory
+  subcategory: string | null
+  tags: { id: string | null; label: string; slug: string }[]
+  series: { id: string; slug: string | null; title: string | null } | null
+}
+
+export interface TaxonomyFilter {
+  include?: MarketCategory[]
+  exclude?: MarketCategory[]
+  exclude_tags?: string[]
 }
 
 // Wallet response
@@ -66,7 +85,7 @@ export interface Position {
   side: 'Yes' | 'No'
   entry_price: string
   size: string
-  strategy: 'ResolutionSniper' | 'Dispute'
+  strategy: 'ResolutionSniper' | 'Dispute' | 'MillionairesClub' | 'MintMaker' | 'NegRiskArb'
   opened_at: string
   closed_at: string | null
   exit_price: string | null
@@ -135,6 +154,32 @@ export interface WsScanStatusMessage {
   data: { scan_interval_seconds: number; last_scan_at: number }
 }
 
+// Outcome of a neg-risk multi-outcome event
+export interface EventOutcome {
+  market_id: string
+  condition_id: string
+  label: string
+  question_index: number | null
+  yes_price: string
+  no_price: string
+  yes_token_id: string | null
+  no_token_id: string | null
+  liquidity: string
+}
+
+// Neg-risk event grouping mutually exclusive outcomes
+export interface NegRiskEvent {
+  event_id: string
+  neg_risk_market_id: string | null
+  title: string
+  slug: string
+  end_date: string | null
+  outcomes: EventOutcome[]
+  /** Sum of YES prices across outcomes (1.00 when fairly priced) */
+  yes_price_sum: string
+  no_price_sum: string
+}
+
 // UMA dispute alert
 export interface DisputeAlert {
   assertion_id: string
@@ -161,6 +206,22 @@ export interface DisputeAlert {
   liveness_seconds?: number | null
   /** Expected value accounting for 50-50 outcome possibility */
   expected_value?: string | null
+  /** DVM voting round info (only for DvmVote disputes) */
+  dvm?: DvmVoteInfo | null
+  taxonomy?: MarketTaxonomy
+}
+
+// UMA DVM vote state for an escalated dispute
+export interface DvmVoteInfo {
+  phase: 'Queued' | 'Commit' | 'Reveal' | 'AwaitingSettlement' | 'Resolved'
+  round_id: number
+  commit_ends_at: number
+  reveal_ends_at: number
+  roll_count: number
+  total_votes_revealed: string | null
+  /** Share of revealed votes supporting the proposed outcome (0.0-1.0) */
+  proposed_vote_share: number | null
+  proposed_upheld: boolean | null
 }
 
 export interface WsDisputesMessage {
@@ -376,7 +437,31 @@ export interface WsMintMakerStatusMessage {
   data: MintMakerStatus
 }
 
-export type WsMessage = WsConnectedMessage | WsOpportunitiesMessage | WsErrorMessage | WsPriceUpdateMessage | WsScanStatusMessage | WsDisputesMessage | WsWalletBalanceMessage | WsOrderEventMessage | WsMcStatusMessage | WsMintMakerStatusMessage
+// On-chain job (relay or direct transaction) tracked by the backend worker
+export type OnchainJobKind = 'Split' | 'Merge' | 'Redeem' | 'Convert' | 'SafeDeploy' | 'Approval' | 'Deposit' | 'Withdraw' | 'Batch'
+export type OnchainJobStatus = 'Queued' | 'Submitted' | 'Mined' | 'Failed' | 'Replaced'
+
+export interface OnchainJob {
+  id: number
+  wallet_address: string
+  kind: OnchainJobKind
+  status: OnchainJobStatus
+  description: string
+  reference: string | null
+  relay_tx_id: string | null
+  tx_hash: string | null
+  replaces_job_id: number | null
+  error: string | null
+  created_at: string
+  updated_at: string
+}
+
+export interface WsOnchainJobMessage {
+  type: 'onchain_job'
+  data: OnchainJob
+}
+
+export type WsMessage = WsConnectedMessage | WsOpportunitiesMessage | WsErrorMessage | WsPriceUpdateMessage | WsScanStatusMessage | WsDisputesMessage | WsWalletBalanceMessage | WsOrderEventMessage | WsMcStatusMessage | WsMintMakerStatusMessage | WsOnchainJobMessage
 
 // Auto-Trading Settings
 export interface AutoTradingSettings {
@@ -399,6 +484,14 @@ export interface AutoTradingSettings {
   min_dispute_edge: number
   dispute_position_size: string
   dispute_exit_on_escalation: boolean
+  dispute_dvm_enabled: boolean
+  dispute_dvm_min_vote_share: number
+  dispute_take_profit_enabled: boolean
+  dispute_take_profit_price: number
+  dispute_liveness_exit_enabled: boolean
+  dispute_liveness_exit_minutes: number
+  dispute_hold_to_settlement: boolean
+  market_filter: TaxonomyFilter
 }
 
 // Auto-Trade Log Entry